@@ -1,8 +1,19 @@
 use crate::{
+    grpc::call_entity_service,
     middleware::AuthenticationMiddleware,
+    rate_limit::QueryRateLimiter,
+    registry::build_registry_schema,
+    usage::QueryUsageTracker,
     uses::{
-        get_nonce, graphql_playground, health_check, indexer_status, query_graph,
-        register_indexer_assets, remove_indexer, sql_query, verify_signature,
+        api_key_token, audit_log, create_api_key, export_entity, get_nonce,
+        get_sign_in_challenge, graphql_playground, health_check,
+        indexer_lifecycle_events, indexer_progress_status, indexer_proto,
+        indexer_query_route, indexer_status, indexer_versions, list_api_keys,
+        openapi_spec, pause_indexer, plan_indexer_migration, promote_indexer_version,
+        query_graph, query_registry, query_usage, readiness_check,
+        register_indexer_assets, reindex_indexer, reindex_indexer_range, remove_indexer,
+        remove_indexer_query_route, resume_indexer, revoke_api_key, rollback_indexer,
+        rotate_api_key, sql_query, verify_signature,
     },
 };
 
@@ -18,14 +29,23 @@ use axum::{
     routing::{delete, get, post},
     Router,
 };
+use axum_server::tls_rustls::RustlsConfig;
 use fuel_indexer_database::{IndexerConnectionPool, IndexerDatabaseError};
-use fuel_indexer_graphql::graphql::GraphqlError;
-use fuel_indexer_lib::{config::IndexerConfig, defaults, utils::ServiceRequest};
+use fuel_indexer_graphql::{graphql::GraphqlError, statements::StatementCache};
+use fuel_indexer_lib::{
+    config::{IndexerConfig, IndexerConfigError, WebApiConfig},
+    defaults,
+    utils::ServiceRequest,
+};
 use fuel_indexer_schema::db::{manager::SchemaManager, IndexerSchemaDbError};
-use hyper::Method;
+use hyper::{
+    header::{REFERRER_POLICY, X_CONTENT_TYPE_OPTIONS, X_FRAME_OPTIONS},
+    http::{request::Parts, HeaderValue},
+};
 use serde_json::json;
 use std::{
     net::SocketAddr,
+    path::PathBuf,
     str::FromStr,
     time::{Duration, Instant},
 };
@@ -34,12 +54,14 @@ use tokio::sync::mpsc::{error::SendError, Sender};
 use tower::ServiceBuilder;
 use tower::{buffer::BufferLayer, limit::RateLimitLayer};
 use tower_http::{
-    cors::{Any, CorsLayer},
+    compression::CompressionLayer,
+    cors::{AllowOrigin, Any, CorsLayer},
     limit::RequestBodyLimitLayer,
+    set_header::SetResponseHeaderLayer,
     trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
     LatencyUnit,
 };
-use tracing::{error, Level};
+use tracing::{error, info, Level};
 
 /// Result type returned by web API operations.
 pub type ApiResult<T> = core::result::Result<T, ApiError>;
@@ -56,6 +78,8 @@ pub enum HttpError {
     Conflict(String),
     #[error("Unauthorized request.")]
     Unauthorized,
+    #[error("Too many requests. {0:#?}")]
+    TooManyRequests(String),
     #[error("Not not found. {0:#?}")]
     NotFound(String),
     #[error("Error.")]
@@ -101,8 +125,16 @@ pub enum ApiError {
     BoxError(#[from] axum::BoxError),
     #[error("Sql validator error: {0:?}")]
     SqlValidator(#[from] crate::sql::SqlValidatorError),
+    #[error("Export error: {0:?}")]
+    Export(#[from] crate::export::ExportError),
     #[error("ParseError: {0:?}")]
     ParseError(#[from] strum::ParseError),
+    #[error("IO error: {0:?}")]
+    Io(#[from] std::io::Error),
+    #[error("IPFS error: {0:?}")]
+    Ipfs(String),
+    #[error("Config error: {0:?}")]
+    Config(#[from] IndexerConfigError),
 }
 
 impl Default for ApiError {
@@ -134,6 +166,10 @@ impl IntoResponse for ApiError {
             Self::Http(HttpError::Unauthorized) => {
                 (StatusCode::UNAUTHORIZED, "Unauthorized.".to_string())
             }
+            Self::Http(HttpError::TooManyRequests(limit)) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Rate limit exceeded: {limit}."),
+            ),
             Self::Http(HttpError::NotFound(e)) => {
                 (StatusCode::NOT_FOUND, format!("Not found: {e}."))
             }
@@ -161,6 +197,8 @@ impl IntoResponse for ApiError {
                 error!("SqlValidatorError: {e:?}");
                 (StatusCode::BAD_REQUEST, format!("Error: {e}"))
             }
+            ApiError::Export(e) => (StatusCode::BAD_REQUEST, format!("Error: {e}")),
+            ApiError::Ipfs(e) => (StatusCode::BAD_GATEWAY, format!("IPFS error: {e}.")),
             ApiError::ParseError(e) => {
                 error!("ParseError: {e:?}");
                 // This is currently the only type of ParseError on the web server
@@ -182,6 +220,54 @@ impl IntoResponse for ApiError {
     }
 }
 
+/// Whether the origin is in the list of allowed origins. An empty list, or a list
+/// containing `*`, allows any origin.
+fn origin_allowed(origin: &HeaderValue, allowed: &[String]) -> bool {
+    allowed.is_empty()
+        || allowed
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.as_bytes() == origin.as_bytes())
+}
+
+/// Build the CORS layer for the web API from its configuration.
+///
+/// Requests to the routes of an indexer with its own entry in `cors_indexer_origins`
+/// are checked against that entry rather than `cors_allowed_origins`.
+///
+/// Invalid CORS methods are rejected when the configuration is loaded, but are
+/// returned as an error here too, for configurations built without loading them.
+fn cors_layer(config: &WebApiConfig) -> ApiResult<CorsLayer> {
+    let mut cors = CorsLayer::new().allow_methods(config.cors_methods()?);
+
+    if let Some(max_age) = config.cors_max_age {
+        cors = cors.max_age(Duration::from_secs(max_age));
+    }
+
+    let any_origin = config.cors_allowed_origins.is_empty()
+        || config
+            .cors_allowed_origins
+            .iter()
+            .any(|origin| origin == "*");
+    if any_origin && config.cors_indexer_origins.is_empty() {
+        return Ok(cors.allow_origin(Any {}));
+    }
+
+    let config = config.clone();
+    Ok(cors.allow_origin(AllowOrigin::predicate(
+        move |origin: &HeaderValue, parts: &Parts| {
+            // Indexer routes look like `/api/<route>/<namespace>/<identifier>`.
+            let segments = parts.uri.path().split('/').collect::<Vec<_>>();
+            let allowed = match segments.as_slice() {
+                ["", "api", _, namespace, identifier, ..] => {
+                    config.cors_origins_for(namespace, identifier)
+                }
+                _ => &config.cors_allowed_origins,
+            };
+            origin_allowed(origin, allowed)
+        },
+    )))
+}
+
 /// GraphQL API server.
 pub struct WebApi;
 
@@ -195,7 +281,13 @@ impl WebApi {
         let sm = SchemaManager::new(pool.clone());
         let schema_manager = Arc::new(RwLock::new(sm));
         let max_body_size = config.web_api.max_body_size;
+        let cors = cors_layer(&config.web_api)?;
+        let security_headers = config.web_api.security_headers;
         let start_time = Arc::new(Instant::now());
+        let usage = Arc::new(QueryUsageTracker::default());
+        let limiter = Arc::new(QueryRateLimiter::new(&config.rate_limit));
+        let statements =
+            Arc::new(StatementCache::new(config.web_api.statement_cache_size));
 
         // NOTE: We never expect to panic here, and if we do panic,
         // the panic is on service start, so will be found immediately
@@ -204,16 +296,29 @@ impl WebApi {
 
         let mut graph_routes = Router::new()
             .route("/:namespace/:identifier", post(query_graph))
+            .layer(AuthenticationMiddleware::new(&config, pool.clone()))
+            .layer(Extension(limiter.clone()))
+            .layer(Extension(usage.clone()))
+            .layer(Extension(statements.clone()))
             .layer(Extension(schema_manager.clone()))
             .layer(Extension(pool.clone()))
+            .layer(Extension(config.clone()))
             .layer(RequestBodyLimitLayer::new(max_body_size));
 
+        let export_routes = Router::new()
+            .route("/:namespace/:identifier/:entity", get(export_entity))
+            .layer(AuthenticationMiddleware::new(&config, pool.clone()))
+            .layer(Extension(pool.clone()));
+
+        #[cfg(feature = "metrics")]
+        let export_routes = export_routes.layer(MetricsMiddleware::default());
+
         let mut sql_routes = Router::new();
 
         if config.accept_sql_queries {
             sql_routes = Router::new()
                 .route("/:namespace/:identifier", post(sql_query))
-                .layer(AuthenticationMiddleware::from(&config))
+                .layer(AuthenticationMiddleware::new(&config, pool.clone()))
                 .layer(Extension(pool.clone()))
                 .layer(RequestBodyLimitLayer::new(max_body_size));
         }
@@ -245,14 +350,47 @@ impl WebApi {
 
         let indexer_routes = Router::new()
             .route("/:namespace/:identifier", post(register_indexer_assets))
-            .layer(AuthenticationMiddleware::from(&config))
+            .route("/:namespace/:identifier/versions", get(indexer_versions))
+            .route(
+                "/:namespace/:identifier/migrate/plan",
+                post(plan_indexer_migration),
+            )
+            .route(
+                "/:namespace/:identifier/snapshot",
+                get(export_indexer_snapshot).post(import_indexer_snapshot),
+            )
+            .route(
+                "/:namespace/:identifier/rollback/:version",
+                post(rollback_indexer),
+            )
+            .route("/:namespace/:identifier/pause", post(pause_indexer))
+            .route("/:namespace/:identifier/resume", post(resume_indexer))
+            .route("/:namespace/:identifier/reindex", post(reindex_indexer))
+            .route(
+                "/:namespace/:identifier/reindex/range",
+                post(reindex_indexer_range),
+            )
+            .route(
+                "/:namespace/:identifier/events",
+                get(indexer_lifecycle_events),
+            )
+            .route(
+                "/:namespace/:identifier/promote/:version",
+                post(promote_indexer_version),
+            )
+            .route(
+                "/:namespace/:identifier/route",
+                get(indexer_query_route).delete(remove_indexer_query_route),
+            )
+            .layer(AuthenticationMiddleware::new(&config, pool.clone()))
             .layer(Extension(tx.clone()))
             .layer(Extension(schema_manager.clone()))
             .layer(Extension(pool.clone()))
             .layer(Extension(config.clone()))
             .route("/:namespace/:identifier", delete(remove_indexer))
-            .layer(AuthenticationMiddleware::from(&config))
+            .layer(AuthenticationMiddleware::new(&config, pool.clone()))
             .layer(Extension(tx))
+            .layer(Extension(statements))
             .layer(Extension(pool.clone()))
             .layer(Extension(config.clone()))
             .layer(RequestBodyLimitLayer::new(max_body_size));
@@ -262,10 +400,21 @@ impl WebApi {
 
         let root_routes = Router::new()
             .route("/status", get(indexer_status))
+            .route("/audit", get(audit_log))
+            .route(
+                "/status/:namespace/:identifier",
+                get(indexer_progress_status),
+            )
+            .route("/registry", post(query_registry))
+            .route("/usage", get(query_usage))
+            .layer(Extension(usage.clone()))
+            .layer(Extension(build_registry_schema(pool.clone())))
             .layer(Extension(pool.clone()))
-            .layer(AuthenticationMiddleware::from(&config))
+            .layer(AuthenticationMiddleware::new(&config, pool.clone()))
             .layer(Extension(config.clone()))
             .route("/health", get(health_check))
+            .route("/ready", get(readiness_check))
+            .route("/openapi.json", get(openapi_spec))
             .layer(Extension(config.clone()))
             .layer(Extension(pool.clone()))
             .layer(Extension(start_time));
@@ -276,15 +425,42 @@ impl WebApi {
             .layer(MetricsMiddleware::default());
 
         let auth_routes = Router::new()
+            .route("/keys", post(create_api_key).get(list_api_keys))
+            .route("/keys/:id", delete(revoke_api_key))
+            .route("/keys/:id/rotate", post(rotate_api_key))
+            .layer(AuthenticationMiddleware::new(&config, pool.clone()))
             .route("/nonce", get(get_nonce))
             .layer(Extension(pool.clone()))
+            .route("/challenge", get(get_sign_in_challenge))
             .route("/signature", post(verify_signature))
+            .route("/token", post(api_key_token))
             .layer(Extension(pool.clone()))
             .layer(Extension(config));
 
         #[cfg(feature = "metrics")]
         let auth_routes = auth_routes.layer(MetricsMiddleware::default());
 
+        // gRPC calls are made to `/<package>.<service>/<method>`, rather than
+        // under `/api`. The size of their messages is limited by the codec.
+        let grpc_routes = Router::new()
+            .route("/:service/:method", post(call_entity_service))
+            .layer(AuthenticationMiddleware::new(&config, pool.clone()))
+            .layer(Extension(limiter))
+            .layer(Extension(usage))
+            .layer(Extension(schema_manager.clone()))
+            .layer(Extension(pool.clone()));
+
+        #[cfg(feature = "metrics")]
+        let grpc_routes = grpc_routes.layer(MetricsMiddleware::default());
+
+        let proto_routes = Router::new()
+            .route("/:namespace/:identifier", get(indexer_proto))
+            .layer(Extension(schema_manager.clone()))
+            .layer(Extension(pool.clone()));
+
+        #[cfg(feature = "metrics")]
+        let proto_routes = proto_routes.layer(MetricsMiddleware::default());
+
         let playground_route = Router::new()
             .route("/:namespace/:identifier", get(graphql_playground))
             .layer(Extension(schema_manager))
@@ -300,10 +476,13 @@ impl WebApi {
             .nest("/index", indexer_routes)
             .nest("/graph", graph_routes)
             .nest("/sql", sql_routes)
+            .nest("/export", export_routes)
+            .nest("/proto", proto_routes)
             .nest("/auth", auth_routes);
 
-        let app = Router::new()
+        let mut app = Router::new()
             .nest("/api", api_routes)
+            .merge(grpc_routes)
             .layer(
                 TraceLayer::new_for_http()
                     .make_span_with(DefaultMakeSpan::new().include_headers(true))
@@ -314,24 +493,31 @@ impl WebApi {
                             .latency_unit(LatencyUnit::Micros),
                     ),
             )
-            .layer(
-                CorsLayer::new()
-                    .allow_methods(vec![Method::GET, Method::POST])
-                    .allow_origin(Any {}),
-            );
+            .layer(CompressionLayer::new())
+            .layer(cors);
+
+        if security_headers {
+            app = app
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    X_CONTENT_TYPE_OPTIONS,
+                    HeaderValue::from_static("nosniff"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    X_FRAME_OPTIONS,
+                    HeaderValue::from_static("DENY"),
+                ))
+                .layer(SetResponseHeaderLayer::if_not_present(
+                    REFERRER_POLICY,
+                    HeaderValue::from_static("no-referrer"),
+                ));
+        }
 
         Ok(app)
     }
 
     /// Start the GraphQL API server.
     pub async fn run(config: IndexerConfig, app: Router) -> ApiResult<()> {
-        let listen_on: SocketAddr = config.web_api.into();
-
-        axum::Server::bind(&listen_on)
-            .serve(app.into_make_service())
-            .await?;
-
-        Ok(())
+        serve(&config.web_api, app).await
     }
 
     /// Build a GraphQL API web server as an `axum` application and start the server.
@@ -342,13 +528,81 @@ impl WebApi {
         pool: IndexerConnectionPool,
         tx: Sender<ServiceRequest>,
     ) -> ApiResult<()> {
-        let listen_on: SocketAddr = config.web_api.clone().into();
+        let web_api = config.web_api.clone();
         let app = WebApi::build(config, pool, tx).await?;
 
-        axum::Server::bind(&listen_on)
-            .serve(app.into_make_service())
-            .await?;
+        serve(&web_api, app).await
+    }
+}
 
-        Ok(())
+/// Serve the application over HTTPS if a TLS certificate and key are configured,
+/// and over plain HTTP otherwise.
+async fn serve(config: &WebApiConfig, app: Router) -> ApiResult<()> {
+    let listen_on: SocketAddr = config.clone().into();
+    let make_service = app.into_make_service_with_connect_info::<SocketAddr>();
+
+    match (&config.tls_cert, &config.tls_key) {
+        (Some(cert), Some(key)) => {
+            let tls = RustlsConfig::from_pem_file(cert, key).await?;
+
+            if let Some(interval) = config.tls_reload_interval {
+                tokio::spawn(reload_tls_config(
+                    tls.clone(),
+                    cert.clone(),
+                    key.clone(),
+                    Duration::from_secs(interval),
+                ));
+            }
+
+            axum_server::bind_rustls(listen_on, tls)
+                .serve(make_service)
+                .await?;
+        }
+        (None, None) => {
+            axum::Server::bind(&listen_on).serve(make_service).await?;
+        }
+        _ => {
+            return Err(ApiError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Both a TLS certificate and a TLS key are required to serve HTTPS.",
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Reload the TLS certificate and key whenever either file changes, so that
+/// rotated certificates are picked up without restarting the server.
+async fn reload_tls_config(
+    tls: RustlsConfig,
+    cert: PathBuf,
+    key: PathBuf,
+    interval: Duration,
+) {
+    let modified = |path: &PathBuf| {
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    };
+
+    let mut last_modified = (modified(&cert), modified(&key));
+    let mut interval = tokio::time::interval(interval);
+
+    loop {
+        interval.tick().await;
+
+        let current = (modified(&cert), modified(&key));
+        if current == last_modified {
+            continue;
+        }
+
+        match tls.reload_from_pem_file(&cert, &key).await {
+            Ok(()) => {
+                info!("Reloaded TLS certificate from {cert:?}.");
+                last_modified = current;
+            }
+            Err(e) => error!("Failed to reload TLS certificate from {cert:?}: {e}."),
+        }
     }
 }
@@ -3,16 +3,14 @@ use fuel_indexer_database::{queries, IndexerConnectionPool};
 use fuel_indexer_lib::{
     config::{ApiServerArgs, IndexerConfig},
     defaults::SERVICE_REQUEST_CHANNEL_SIZE,
+    telemetry,
     utils::{init_logging, ServiceRequest},
 };
 use tokio::sync::mpsc::channel;
 use tracing::info;
 
 pub async fn exec(args: ApiServerArgs) -> anyhow::Result<()> {
-    let config = match &args.config {
-        Some(path) => IndexerConfig::from_file(path)?,
-        None => IndexerConfig::from(args),
-    };
+    let config = IndexerConfig::load(&args)?;
 
     info!("Configuration: {:?}", config);
 
@@ -29,5 +27,7 @@ pub async fn exec(args: ApiServerArgs) -> anyhow::Result<()> {
 
     let _ = WebApi::build_and_run(config.clone(), pool, tx).await;
 
+    telemetry::shutdown();
+
     Ok(())
 }
@@ -0,0 +1,598 @@
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, RecordBatch,
+    StringArray,
+};
+use arrow_schema::{DataType, Field, Schema};
+use axum::{
+    body::{Bytes, StreamBody},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use fuel_indexer_database::{queries, IndexerConnection};
+use futures_util::{stream, StreamExt};
+use parquet::{
+    arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties,
+};
+use std::{
+    io::{self, Write},
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+use tracing::debug;
+
+/// Number of chunks of an export buffered before reading waits for the client.
+const EXPORT_BUFFER_SIZE: usize = 4;
+
+/// Number of rows in each row group of a Parquet export.
+const PARQUET_ROW_GROUP_SIZE: usize = 10_000;
+
+/// Column holding the serialized entity, which is not exported unless requested.
+const OBJECT_COLUMN: &str = "object";
+
+/// Error type returned when an export request is invalid.
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Unknown export format '{0}', expected 'csv' or 'parquet'.")]
+    UnknownFormat(String),
+    #[error("Unknown column '{0}'.")]
+    UnknownColumn(String),
+    #[error("Invalid filter '{0}', expected '<column>:<operator>[:<value>]'.")]
+    InvalidFilter(String),
+    #[error("Invalid limit '{0}'.")]
+    InvalidLimit(String),
+    #[error("Unknown query parameter '{0}'.")]
+    UnknownParameter(String),
+    #[error("Query failed: {0}")]
+    Query(#[from] sqlx::Error),
+}
+
+type ExportResult<T> = Result<T, ExportError>;
+
+/// Format in which an entity's rows are exported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum ExportFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    /// File extension of exports in this format.
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    /// Content type of exports in this format.
+    fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportError;
+
+    fn from_str(s: &str) -> ExportResult<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            _ => Err(ExportError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+/// A comparison operator of an export filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FilterOperator {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Like,
+    IsNull,
+    NotNull,
+}
+
+/// A condition that exported rows must meet, given as `<column>:<operator>:<value>`
+/// (e.g., `amount:gte:100`), or `<column>:null` and `<column>:notnull`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExportFilter {
+    column: String,
+    operator: FilterOperator,
+    value: Option<String>,
+}
+
+impl FromStr for ExportFilter {
+    type Err = ExportError;
+
+    fn from_str(s: &str) -> ExportResult<Self> {
+        let invalid = || ExportError::InvalidFilter(s.to_string());
+        let mut parts = s.splitn(3, ':');
+        let column = parts.next().filter(|c| !c.is_empty()).ok_or_else(invalid)?;
+        let operator = match parts.next().ok_or_else(invalid)? {
+            "eq" => FilterOperator::Eq,
+            "ne" => FilterOperator::Ne,
+            "gt" => FilterOperator::Gt,
+            "gte" => FilterOperator::Gte,
+            "lt" => FilterOperator::Lt,
+            "lte" => FilterOperator::Lte,
+            "like" => FilterOperator::Like,
+            "null" => FilterOperator::IsNull,
+            "notnull" => FilterOperator::NotNull,
+            _ => return Err(invalid()),
+        };
+        let value = parts.next().map(str::to_string);
+
+        let needs_value =
+            !matches!(operator, FilterOperator::IsNull | FilterOperator::NotNull);
+        if needs_value != value.is_some() {
+            return Err(invalid());
+        }
+
+        Ok(Self::new(column, operator, value))
+    }
+}
+
+impl ExportFilter {
+    /// Create a new `ExportFilter`, whose value must be given unless the operator
+    /// is `IsNull` or `NotNull`.
+    pub(crate) fn new(
+        column: &str,
+        operator: FilterOperator,
+        value: Option<String>,
+    ) -> Self {
+        Self {
+            column: column.to_lowercase(),
+            operator,
+            value,
+        }
+    }
+
+    /// Return this filter as a SQL condition.
+    ///
+    /// The column must have been checked against the table's columns.
+    pub(crate) fn to_sql(&self) -> String {
+        let column = quote_identifier(&self.column);
+        let value = self.value.as_deref().map(quote_literal).unwrap_or_default();
+        match self.operator {
+            FilterOperator::Eq => format!("{column} = {value}"),
+            FilterOperator::Ne => format!("{column} <> {value}"),
+            FilterOperator::Gt => format!("{column} > {value}"),
+            FilterOperator::Gte => format!("{column} >= {value}"),
+            FilterOperator::Lt => format!("{column} < {value}"),
+            FilterOperator::Lte => format!("{column} <= {value}"),
+            FilterOperator::Like => format!("{column}::text LIKE {value}"),
+            FilterOperator::IsNull => format!("{column} IS NULL"),
+            FilterOperator::NotNull => format!("{column} IS NOT NULL"),
+        }
+    }
+}
+
+/// Quote a SQL identifier.
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Quote a SQL string literal, which PostgreSQL casts to the type it is compared with.
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// An export of an entity's rows, as given in the query string of an export request.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ExportRequest {
+    /// Format of the export.
+    pub format: ExportFormat,
+
+    /// Columns to export. Every column but the serialized entity is exported if none are given.
+    columns: Vec<String>,
+
+    /// Conditions that exported rows must meet.
+    filters: Vec<ExportFilter>,
+
+    /// Maximum number of rows exported.
+    limit: Option<u64>,
+}
+
+impl ExportRequest {
+    /// Parse an export request from the parameters of its query string.
+    ///
+    /// `filter` may be given more than once, and `columns` is a comma-separated list.
+    pub(crate) fn from_params(params: &[(String, String)]) -> ExportResult<Self> {
+        let mut request = Self::default();
+        for (key, value) in params {
+            match key.as_str() {
+                "format" => request.format = value.parse()?,
+                "columns" => request.columns.extend(
+                    value
+                        .split(',')
+                        .map(|c| c.trim().to_lowercase())
+                        .filter(|c| !c.is_empty()),
+                ),
+                "filter" => request.filters.push(value.parse()?),
+                "limit" => {
+                    request.limit = Some(
+                        value
+                            .parse()
+                            .map_err(|_| ExportError::InvalidLimit(value.clone()))?,
+                    )
+                }
+                _ => return Err(ExportError::UnknownParameter(key.clone())),
+            }
+        }
+        Ok(request)
+    }
+
+    /// Return the names and data types of the exported columns, given those of the table.
+    pub(crate) fn columns(
+        &self,
+        table_columns: &[(String, String)],
+    ) -> ExportResult<Vec<(String, String)>> {
+        for name in self
+            .columns
+            .iter()
+            .chain(self.filters.iter().map(|f| &f.column))
+        {
+            if !table_columns.iter().any(|(column, _)| column == name) {
+                return Err(ExportError::UnknownColumn(name.clone()));
+            }
+        }
+
+        Ok(table_columns
+            .iter()
+            .filter(|(name, _)| {
+                if self.columns.is_empty() {
+                    name != OBJECT_COLUMN
+                } else {
+                    self.columns.contains(name)
+                }
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Return the query selecting the exported rows of the given table.
+    ///
+    /// Columns are cast to `text` for Parquet exports, and converted to the
+    /// column's Parquet type as the rows are written.
+    pub(crate) fn query(&self, table: &str, columns: &[(String, String)]) -> String {
+        let projection = columns
+            .iter()
+            .map(|(name, _)| match self.format {
+                ExportFormat::Csv => quote_identifier(name),
+                ExportFormat::Parquet => {
+                    format!("{0}::text AS {0}", quote_identifier(name))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut query = format!("SELECT {projection} FROM {table}");
+        if !self.filters.is_empty() {
+            let conditions = self
+                .filters
+                .iter()
+                .map(ExportFilter::to_sql)
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            query.push_str(&format!(" WHERE {conditions}"));
+        }
+        query.push_str(" ORDER BY id");
+        if let Some(limit) = self.limit {
+            query.push_str(&format!(" LIMIT {limit}"));
+        }
+        query
+    }
+}
+
+/// Build a response streaming the rows of the given query in the given format.
+///
+/// The query is started before the response is returned, so that invalid
+/// filters are reported as errors rather than as truncated exports.
+pub(crate) async fn export_response(
+    conn: IndexerConnection,
+    format: ExportFormat,
+    query: String,
+    columns: Vec<(String, String)>,
+    filename: &str,
+) -> ExportResult<Response> {
+    let (tx, rx) = mpsc::channel::<io::Result<Bytes>>(EXPORT_BUFFER_SIZE);
+    let (started_tx, started_rx) = oneshot::channel();
+
+    match format {
+        ExportFormat::Csv => {
+            tokio::spawn(stream_csv(conn, query, started_tx, tx));
+        }
+        ExportFormat::Parquet => {
+            tokio::spawn(stream_parquet(conn, query, columns, started_tx, tx));
+        }
+    }
+
+    started_rx
+        .await
+        .unwrap_or(Err(sqlx::Error::WorkerCrashed))?;
+
+    let body = StreamBody::new(stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    }));
+
+    Ok((
+        [
+            (CONTENT_TYPE, format.content_type().to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}.{}\"", format.extension()),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Stream the rows of a query as CSV, with a header row.
+async fn stream_csv(
+    mut conn: IndexerConnection,
+    query: String,
+    started: oneshot::Sender<sqlx::Result<()>>,
+    tx: mpsc::Sender<io::Result<Bytes>>,
+) {
+    let statement = format!("COPY ({query}) TO STDOUT WITH (FORMAT csv, HEADER true)");
+    let mut chunks = match queries::copy_out(&mut conn, &statement).await {
+        Ok(chunks) => {
+            let _ = started.send(Ok(()));
+            chunks
+        }
+        Err(e) => {
+            let _ = started.send(Err(e));
+            return;
+        }
+    };
+
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk
+            .map(Bytes::from)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e));
+        if tx.send(chunk).await.is_err() {
+            debug!("Stopped streaming CSV export: response dropped.");
+            return;
+        }
+    }
+}
+
+/// Stream the rows of a query, whose columns are all `text`, as a Parquet file.
+async fn stream_parquet(
+    mut conn: IndexerConnection,
+    query: String,
+    columns: Vec<(String, String)>,
+    started: oneshot::Sender<sqlx::Result<()>>,
+    tx: mpsc::Sender<io::Result<Bytes>>,
+) {
+    let schema = Arc::new(Schema::new(
+        columns
+            .iter()
+            .map(|(name, data_type)| Field::new(name, parquet_type(data_type), true))
+            .collect::<Vec<_>>(),
+    ));
+    let buffer = SharedBuffer::default();
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_max_row_group_size(PARQUET_ROW_GROUP_SIZE)
+        .build();
+    let mut writer =
+        match ArrowWriter::try_new(buffer.clone(), schema.clone(), Some(properties)) {
+            Ok(writer) => writer,
+            Err(e) => {
+                let _ = started.send(Err(sqlx::Error::Protocol(e.to_string())));
+                return;
+            }
+        };
+
+    let mut rows = queries::fetch_text_rows(&mut conn, &query);
+    let mut started = Some(started);
+    let mut batch = Vec::with_capacity(PARQUET_ROW_GROUP_SIZE);
+
+    loop {
+        // The query fails, if at all, when its first row is read.
+        let done = match rows.next().await {
+            Some(Ok(row)) => {
+                batch.push(row);
+                false
+            }
+            Some(Err(e)) => {
+                match started.take() {
+                    Some(started) => {
+                        let _ = started.send(Err(e));
+                    }
+                    None => {
+                        let _ =
+                            tx.send(Err(io::Error::new(io::ErrorKind::Other, e))).await;
+                    }
+                }
+                return;
+            }
+            None => true,
+        };
+        if let Some(started) = started.take() {
+            let _ = started.send(Ok(()));
+        }
+
+        if batch.len() == PARQUET_ROW_GROUP_SIZE || (done && !batch.is_empty()) {
+            let written = record_batch(&schema, std::mem::take(&mut batch))
+                .map_err(|e| e.to_string())
+                .and_then(|batch| writer.write(&batch).map_err(|e| e.to_string()));
+            if let Err(e) = written {
+                let _ = tx.send(Err(io::Error::new(io::ErrorKind::Other, e))).await;
+                return;
+            }
+        }
+
+        if done {
+            break;
+        }
+
+        let chunk = buffer.take();
+        if !chunk.is_empty() && tx.send(Ok(Bytes::from(chunk))).await.is_err() {
+            debug!("Stopped streaming Parquet export: response dropped.");
+            return;
+        }
+    }
+
+    if let Err(e) = writer.close() {
+        let _ = tx.send(Err(io::Error::new(io::ErrorKind::Other, e))).await;
+        return;
+    }
+    let _ = tx.send(Ok(Bytes::from(buffer.take()))).await;
+}
+
+/// Return the Parquet type of a PostgreSQL column's data type.
+///
+/// Types without a lossless equivalent (e.g., `numeric`) are exported as strings.
+fn parquet_type(data_type: &str) -> DataType {
+    match data_type {
+        "boolean" => DataType::Boolean,
+        "smallint" | "integer" => DataType::Int32,
+        "bigint" => DataType::Int64,
+        "real" | "double precision" => DataType::Float64,
+        _ => DataType::Utf8,
+    }
+}
+
+/// Build a record batch of the given schema from rows of `text` values.
+fn record_batch(
+    schema: &Arc<Schema>,
+    rows: Vec<Vec<Option<String>>>,
+) -> Result<RecordBatch, arrow_schema::ArrowError> {
+    let columns = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let values = rows.iter().map(|row| row[i].as_deref());
+            let array: ArrayRef = match field.data_type() {
+                DataType::Boolean => Arc::new(
+                    values
+                        .map(|v| v.map(|v| v == "true" || v == "t"))
+                        .collect::<BooleanArray>(),
+                ),
+                DataType::Int32 => Arc::new(
+                    values
+                        .map(|v| v.and_then(|v| v.parse().ok()))
+                        .collect::<Int32Array>(),
+                ),
+                DataType::Int64 => Arc::new(
+                    values
+                        .map(|v| v.and_then(|v| v.parse().ok()))
+                        .collect::<Int64Array>(),
+                ),
+                DataType::Float64 => Arc::new(
+                    values
+                        .map(|v| v.and_then(|v| v.parse().ok()))
+                        .collect::<Float64Array>(),
+                ),
+                _ => Arc::new(values.collect::<StringArray>()),
+            };
+            array
+        })
+        .collect::<Vec<_>>();
+
+    RecordBatch::try_new(schema.clone(), columns)
+}
+
+/// A buffer that Parquet files are written to, and that written bytes are taken
+/// from as they are sent to the client.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    /// Take the bytes written since the buffer was last taken.
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.lock().expect("Export buffer lock poisoned."))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .expect("Export buffer lock poisoned.")
+            .extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_export_request_builds_filtered_projected_query() {
+        let request = ExportRequest::from_params(&params(&[
+            ("format", "parquet"),
+            ("columns", "id,amount"),
+            ("filter", "amount:gte:100"),
+            ("filter", "memo:eq:it's"),
+            ("filter", "owner:notnull"),
+            ("limit", "10"),
+        ]))
+        .unwrap();
+        assert_eq!(request.format, ExportFormat::Parquet);
+
+        let table_columns = params(&[
+            ("id", "numeric"),
+            ("amount", "bigint"),
+            ("memo", "character varying"),
+            ("owner", "character varying"),
+            ("object", "bytea"),
+        ]);
+        let columns = request.columns(&table_columns).unwrap();
+        assert_eq!(columns, params(&[("id", "numeric"), ("amount", "bigint")]));
+
+        assert_eq!(
+            request.query("ns_ident.transfer", &columns),
+            r#"SELECT "id"::text AS "id", "amount"::text AS "amount" FROM ns_ident.transfer WHERE "amount" >= '100' AND "memo" = 'it''s' AND "owner" IS NOT NULL ORDER BY id LIMIT 10"#
+        );
+    }
+
+    #[test]
+    fn test_export_request_rejects_invalid_params() {
+        let table_columns = params(&[("id", "numeric")]);
+
+        for invalid in [
+            params(&[("format", "xlsx")]),
+            params(&[("filter", "id:between:1")]),
+            params(&[("filter", "id:null:1")]),
+            params(&[("filter", "id:eq")]),
+            params(&[("limit", "-1")]),
+            params(&[("sort", "id")]),
+        ] {
+            assert!(ExportRequest::from_params(&invalid).is_err());
+        }
+
+        let request =
+            ExportRequest::from_params(&params(&[("filter", "\"id\":eq:1")])).unwrap();
+        assert!(matches!(
+            request.columns(&table_columns),
+            Err(ExportError::UnknownColumn(_))
+        ));
+    }
+}
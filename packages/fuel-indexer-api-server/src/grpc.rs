@@ -0,0 +1,501 @@
+//! gRPC API mirroring the GraphQL API of each indexer.
+//!
+//! Calls are routed by their path, `/<namespace>.<identifier>.<Entity>Service/<Method>`,
+//! and their messages are encoded and decoded with the descriptors generated
+//! from the indexer's schema by [`IndexerProto`].
+
+use crate::{
+    export::{ExportFilter, FilterOperator},
+    models::{Claims, ScopeAction},
+    proto::{self, EntityMethod, IndexerProto},
+    rate_limit::QueryRateLimiter,
+    usage::QueryUsageTracker,
+    uses::{query_client, rate_limit_client},
+};
+use async_std::sync::{Arc, RwLock};
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Extension, Path},
+    http::Request,
+    response::{IntoResponse, Response},
+};
+use fuel_indexer_database::{queries, IndexerConnectionPool};
+use fuel_indexer_schema::db::manager::SchemaManager;
+use futures_util::future::BoxFuture;
+use prost::Message;
+use prost_reflect::{DynamicMessage, Kind, MessageDescriptor, Value};
+use serde_json::Value as JsonValue;
+use std::{net::SocketAddr, time::Instant};
+use tonic::{
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    server::{Grpc, UnaryService},
+    Status,
+};
+
+/// Number of entities returned by `List` and `Filter` calls if no limit is given.
+const DEFAULT_LIMIT: u32 = 100;
+
+/// Maximum number of entities returned by `List` and `Filter` calls.
+const MAX_LIMIT: u32 = 1000;
+
+/// Codec of messages whose types are only known at runtime.
+struct DynamicCodec {
+    /// Descriptor of the decoded requests.
+    request: MessageDescriptor,
+}
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder {
+            request: self.request.clone(),
+        }
+    }
+}
+
+struct DynamicEncoder;
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(
+        &mut self,
+        item: DynamicMessage,
+        dst: &mut EncodeBuf<'_>,
+    ) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|e| Status::internal(format!("Failed to encode response: {e}")))
+    }
+}
+
+struct DynamicDecoder {
+    request: MessageDescriptor,
+}
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(
+        &mut self,
+        src: &mut DecodeBuf<'_>,
+    ) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.request.clone(), src)
+            .map(Some)
+            .map_err(|e| {
+                Status::invalid_argument(format!("Failed to decode request: {e}"))
+            })
+    }
+}
+
+/// A call to a method of an entity service.
+#[derive(Clone)]
+struct EntityCall {
+    /// The called method.
+    method: EntityMethod,
+
+    /// Descriptor of the entity's message.
+    entity: MessageDescriptor,
+
+    /// Descriptor of the method's response.
+    response: MessageDescriptor,
+
+    /// Fully qualified name of the entity's table.
+    table: String,
+
+    /// Pool of connections to the indexer's database.
+    pool: IndexerConnectionPool,
+
+    /// Limiter of the caller's queries.
+    limiter: Arc<QueryRateLimiter>,
+
+    /// Tracker of the caller's usage.
+    usage: Arc<QueryUsageTracker>,
+
+    /// UID of the called indexer.
+    uid: String,
+
+    /// API key of the caller, if any.
+    api_key: Option<String>,
+
+    /// Caller, by which usage is counted.
+    client: String,
+
+    /// Caller, by which rate limits are applied.
+    limit_client: String,
+}
+
+impl UnaryService<DynamicMessage> for EntityCall {
+    type Response = DynamicMessage;
+    type Future = BoxFuture<'static, Result<tonic::Response<DynamicMessage>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<DynamicMessage>) -> Self::Future {
+        let call = self.clone();
+        Box::pin(async move {
+            call.respond(request.into_inner())
+                .await
+                .map(tonic::Response::new)
+        })
+    }
+}
+
+impl EntityCall {
+    /// Query the entities requested by the call.
+    async fn respond(self, request: DynamicMessage) -> Result<DynamicMessage, Status> {
+        let start = Instant::now();
+
+        let mut conditions = Vec::new();
+        let limit = match self.method {
+            EntityMethod::Get => {
+                let id = u64_field(&request, "id").unwrap_or_default();
+                conditions.push(format!("id = {id}"));
+                1
+            }
+            EntityMethod::List | EntityMethod::Filter => {
+                if let Some(after) = u64_field(&request, "after") {
+                    conditions.push(format!("id > {after}"));
+                }
+                if self.method == EntityMethod::Filter {
+                    for condition in list_field(&request, "conditions") {
+                        conditions.push(self.condition(&condition)?);
+                    }
+                }
+                match u64_field(&request, "limit").unwrap_or_default() as u32 {
+                    0 => DEFAULT_LIMIT,
+                    limit => limit.min(MAX_LIMIT),
+                }
+            }
+        };
+
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| Status::unavailable(format!("Database error: {e}")))?;
+        let result = queries::run_query(&mut conn, self.query(&conditions, limit))
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(e) => Status::invalid_argument(e.to_string()),
+                e => Status::internal(format!("Database error: {e}")),
+            })?;
+
+        // The rows are aggregated into a single JSON array, or null if there are none.
+        let rows = result
+            .as_array()
+            .and_then(|rows| rows.first())
+            .and_then(JsonValue::as_array)
+            .map(|rows| {
+                rows.iter()
+                    .map(|row| entity_message(&self.entity, row))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        self.limiter
+            .record_rows(&self.limit_client, rows.len() as u64);
+        self.usage.record(
+            &self.uid,
+            self.api_key.as_deref(),
+            &self.client,
+            rows.len() as u64,
+            start.elapsed(),
+        );
+
+        match self.method {
+            EntityMethod::Get => rows.into_iter().next().ok_or_else(|| {
+                Status::not_found(format!("{} not found.", self.entity.name()))
+            }),
+            EntityMethod::List | EntityMethod::Filter => {
+                let mut response = DynamicMessage::new(self.response.clone());
+                if rows.len() == limit as usize {
+                    if let Some(next) = rows.last().and_then(|row| u64_field(row, "id")) {
+                        response.set_field_by_name("next", Value::U64(next));
+                    }
+                }
+                response.set_field_by_name(
+                    "items",
+                    Value::List(rows.into_iter().map(Value::Message).collect()),
+                );
+                Ok(response)
+            }
+        }
+    }
+
+    /// Return the SQL condition of a condition of a `Filter` call.
+    fn condition(&self, condition: &DynamicMessage) -> Result<String, Status> {
+        let field = string_field(condition, "field");
+        if self.entity.get_field_by_name(&field).is_none() {
+            return Err(Status::invalid_argument(format!(
+                "Unknown field '{field}' of {}.",
+                self.entity.name()
+            )));
+        }
+
+        let op = match condition.get_field_by_name("op").as_deref() {
+            Some(Value::EnumNumber(op)) => *op,
+            _ => 0,
+        };
+        let operator = match proto::OPERATORS.get(op as usize).copied() {
+            Some("EQ") => FilterOperator::Eq,
+            Some("NE") => FilterOperator::Ne,
+            Some("GT") => FilterOperator::Gt,
+            Some("GTE") => FilterOperator::Gte,
+            Some("LT") => FilterOperator::Lt,
+            Some("LTE") => FilterOperator::Lte,
+            Some("LIKE") => FilterOperator::Like,
+            Some("IS_NULL") => FilterOperator::IsNull,
+            Some("NOT_NULL") => FilterOperator::NotNull,
+            _ => {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid operator of the condition on '{field}'."
+                )))
+            }
+        };
+        let value = match operator {
+            FilterOperator::IsNull | FilterOperator::NotNull => None,
+            _ => Some(string_field(condition, "value")),
+        };
+
+        Ok(ExportFilter::new(&field, operator, value).to_sql())
+    }
+
+    /// Return the query aggregating the requested rows into a JSON array.
+    ///
+    /// Columns that are returned as strings are cast to `text`, so that large
+    /// integers don't lose precision.
+    fn query(&self, conditions: &[String], limit: u32) -> String {
+        let projection = self
+            .entity
+            .fields()
+            .map(|field| {
+                // Columns are created with unquoted names, so they are lowercase.
+                let column = format!("\"{}\"", field.name().to_lowercase());
+                let column = match (field.kind(), field.is_list()) {
+                    (Kind::String, true) => format!("{column}::text[]"),
+                    (Kind::String, false) => format!("{column}::text"),
+                    _ => column,
+                };
+                format!("{column} AS \"{}\"", field.name())
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut query = format!("SELECT {projection} FROM {}", self.table);
+        if !conditions.is_empty() {
+            query.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        query.push_str(&format!(" ORDER BY id LIMIT {limit}"));
+
+        format!("SELECT json_agg(t ORDER BY t.id) FROM ({query}) t")
+    }
+}
+
+/// Return the value of an integer field of a message, if it is set.
+fn u64_field(message: &DynamicMessage, name: &str) -> Option<u64> {
+    if !message.has_field_by_name(name) {
+        return None;
+    }
+    match message.get_field_by_name(name).as_deref() {
+        Some(Value::U64(value)) => Some(*value),
+        Some(Value::U32(value)) => Some(*value as u64),
+        _ => None,
+    }
+}
+
+/// Return the value of a string field of a message.
+fn string_field(message: &DynamicMessage, name: &str) -> String {
+    message
+        .get_field_by_name(name)
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Return the messages of a repeated message field of a message.
+fn list_field(message: &DynamicMessage, name: &str) -> Vec<DynamicMessage> {
+    match message.get_field_by_name(name).as_deref() {
+        Some(Value::List(values)) => values
+            .iter()
+            .filter_map(Value::as_message)
+            .cloned()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Convert a row to a message of its entity.
+fn entity_message(entity: &MessageDescriptor, row: &JsonValue) -> DynamicMessage {
+    let mut message = DynamicMessage::new(entity.clone());
+    for field in entity.fields() {
+        let value = match row.get(field.name()) {
+            Some(value) => value,
+            None => continue,
+        };
+        let value = if field.is_list() {
+            value.as_array().map(|values| {
+                Value::List(
+                    values
+                        .iter()
+                        .filter_map(|value| scalar_value(value, &field.kind()))
+                        .collect(),
+                )
+            })
+        } else {
+            scalar_value(value, &field.kind())
+        };
+
+        // Null columns are left unset.
+        if let Some(value) = value {
+            message.set_field(&field, value);
+        }
+    }
+    message
+}
+
+/// Convert a JSON value to a scalar of the given kind.
+fn scalar_value(value: &JsonValue, kind: &Kind) -> Option<Value> {
+    match kind {
+        Kind::Bool => value.as_bool().map(Value::Bool),
+        Kind::Int32 => value.as_i64().map(|value| Value::I32(value as i32)),
+        Kind::Int64 => value.as_i64().map(Value::I64),
+        Kind::Uint32 => value.as_u64().map(|value| Value::U32(value as u32)),
+        Kind::Uint64 => value.as_u64().map(Value::U64),
+        Kind::String => value.as_str().map(|value| Value::String(value.to_string())),
+        _ => None,
+    }
+}
+
+/// Handle a gRPC call to an entity service of an indexer.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn call_entity_service(
+    Path((service, method)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(claims): Extension<Claims>,
+    Extension(limiter): Extension<Arc<QueryRateLimiter>>,
+    Extension(usage): Extension<Arc<QueryUsageTracker>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request: Request<Body>,
+) -> Response {
+    let client = query_client(&claims, connect_info);
+    let limit_client = rate_limit_client(&claims, connect_info);
+    let call = EntityCallContext {
+        pool,
+        limiter,
+        usage,
+        api_key: claims.api_key().map(str::to_string),
+        client,
+        limit_client,
+    };
+
+    match resolve_call(&service, &method, &claims, &manager, call).await {
+        Ok((call, request_descriptor)) => {
+            let codec = DynamicCodec {
+                request: request_descriptor,
+            };
+            Grpc::new(codec).unary(call, request).await.into_response()
+        }
+        Err(status) => status.to_http().into_response(),
+    }
+}
+
+/// State shared by the calls made by a client.
+struct EntityCallContext {
+    pool: IndexerConnectionPool,
+    limiter: Arc<QueryRateLimiter>,
+    usage: Arc<QueryUsageTracker>,
+    api_key: Option<String>,
+    client: String,
+    limit_client: String,
+}
+
+/// Resolve the entity and method of a call, returning the call and the
+/// descriptor of its request.
+async fn resolve_call(
+    service: &str,
+    method: &str,
+    claims: &Claims,
+    manager: &RwLock<SchemaManager>,
+    context: EntityCallContext,
+) -> Result<(EntityCall, MessageDescriptor), Status> {
+    let unknown = || Status::unimplemented(format!("Unknown service '{service}'."));
+    let (package, service_name) = service.rsplit_once('.').ok_or_else(unknown)?;
+    let (namespace, identifier) = package.split_once('.').ok_or_else(unknown)?;
+    let entity = service_name.strip_suffix("Service").ok_or_else(unknown)?;
+    let method = EntityMethod::from_name(method).ok_or_else(|| {
+        Status::unimplemented(format!("Unknown method '{method}' of '{service}'."))
+    })?;
+
+    if !claims.allows(ScopeAction::Query, namespace) {
+        return Err(Status::permission_denied("Unauthorized."));
+    }
+
+    let uid = format!("{namespace}.{identifier}");
+    if let Err(limit) = context.limiter.check(&context.limit_client, &uid) {
+        return Err(Status::resource_exhausted(format!(
+            "Rate limit exceeded: {}.",
+            limit.as_str()
+        )));
+    }
+
+    // Queries may be routed to another version of the indexer that was promoted,
+    // which keeps the package of the version that clients were generated from.
+    let database_error =
+        |e: sqlx::Error| Status::unavailable(format!("Database error: {e}"));
+    let routed = {
+        let mut conn = context.pool.acquire().await.map_err(database_error)?;
+        queries::get_query_route(&mut conn, namespace, identifier)
+            .await
+            .map_err(database_error)?
+            .unwrap_or_else(|| identifier.to_string())
+    };
+
+    let schema = manager
+        .read()
+        .await
+        .load_schema(namespace, &routed)
+        .await
+        .map_err(|_| {
+            Status::not_found(format!("The graph '{namespace}.{routed}' was not found."))
+        })?;
+
+    let proto = IndexerProto::new(namespace, identifier, &schema);
+    let pool = proto
+        .descriptor_pool()
+        .map_err(|e| Status::internal(format!("Invalid Protobuf definitions: {e}")))?;
+    let method_descriptor = pool
+        .get_service_by_name(service)
+        .and_then(|service| service.methods().find(|m| m.name() == method.name()))
+        .ok_or_else(unknown)?;
+    let entity_descriptor = pool
+        .get_message_by_name(&format!("{}.{entity}", proto.package()))
+        .ok_or_else(unknown)?;
+
+    let call = EntityCall {
+        method,
+        entity: entity_descriptor,
+        response: method_descriptor.output(),
+        table: format!(
+            "{}.{}",
+            schema.parsed().fully_qualified_namespace(),
+            entity.to_lowercase()
+        ),
+        pool: context.pool,
+        limiter: context.limiter,
+        usage: context.usage,
+        uid,
+        api_key: context.api_key,
+        client: context.client,
+        limit_client: context.limit_client,
+    };
+
+    Ok((call, method_descriptor.input()))
+}
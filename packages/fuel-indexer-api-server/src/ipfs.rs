@@ -0,0 +1,113 @@
+//! Publishing of deployed indexer assets to IPFS.
+//!
+//! Each asset of a deployment is added to the IPFS node configured with
+//! `ipfs_api_url`, which pins it, and the resulting content identifiers are
+//! recorded with the deployment. Anyone can then fetch an indexer's assets by CID
+//! and check that they are the ones another operator is running.
+
+use fuel_indexer_database::types::IndexerAssetBundle;
+use http::{header::CONTENT_TYPE, Method, Request};
+use hyper::{Body, Client};
+use hyper_rustls::HttpsConnectorBuilder;
+use serde::{Deserialize, Serialize};
+
+/// Boundary between the parts of the multipart body sent to the IPFS node.
+const BOUNDARY: &str = "fuel-indexer-ipfs-asset";
+
+/// Response of the IPFS node to an `add` request.
+#[derive(Deserialize)]
+struct AddResponse {
+    /// CID of the added content.
+    #[serde(rename = "Hash")]
+    hash: String,
+}
+
+/// IPFS CIDs of the assets of a deployment.
+#[derive(Debug, Serialize)]
+pub(crate) struct AssetCids {
+    /// CID of the manifest.
+    pub manifest: String,
+
+    /// CID of the GraphQL schema.
+    pub schema: String,
+
+    /// CID of the WASM module.
+    pub wasm: String,
+}
+
+/// Add and pin the given assets on the IPFS node at the given URL, returning their CIDs.
+pub(crate) async fn publish_assets(
+    api_url: &str,
+    namespace: &str,
+    identifier: &str,
+    assets: &IndexerAssetBundle,
+) -> Result<AssetCids, String> {
+    let prefix = format!("{namespace}.{identifier}");
+    Ok(AssetCids {
+        manifest: add(
+            api_url,
+            &format!("{prefix}.manifest.yaml"),
+            &assets.manifest.bytes,
+        )
+        .await?,
+        schema: add(
+            api_url,
+            &format!("{prefix}.schema.graphql"),
+            &assets.schema.bytes,
+        )
+        .await?,
+        wasm: add(api_url, &format!("{prefix}.wasm"), &assets.wasm.bytes).await?,
+    })
+}
+
+/// Add and pin a file on the IPFS node at the given URL, returning its CID.
+///
+/// Files are added as raw CIDv1 leaves, so that a file's CID only depends on its
+/// bytes and can be recomputed by anyone holding it.
+async fn add(api_url: &str, name: &str, bytes: &[u8]) -> Result<String, String> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{name}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(bytes);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!(
+            "{}/api/v0/add?pin=true&cid-version=1&raw-leaves=true",
+            api_url.trim_end_matches('/')
+        ))
+        .header(
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))
+        .map_err(|e| e.to_string())?;
+
+    let https = HttpsConnectorBuilder::new()
+        .with_native_roots()
+        .https_or_http()
+        .enable_http1()
+        .build();
+    let response = Client::builder()
+        .build::<_, Body>(https)
+        .request(request)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        return Err(format!(
+            "Failed to add {name} to IPFS ({status}): {}",
+            String::from_utf8_lossy(&body).trim()
+        ));
+    }
+
+    serde_json::from_slice::<AddResponse>(&body)
+        .map(|response| response.hash)
+        .map_err(|e| format!("Invalid response to adding {name} to IPFS: {e}"))
+}
@@ -3,9 +3,21 @@
 pub mod api;
 pub mod cli;
 pub(crate) mod commands;
+pub(crate) mod export;
+pub(crate) mod grpc;
+pub(crate) mod ipfs;
 pub(crate) mod middleware;
 pub(crate) mod models;
+pub(crate) mod openapi;
+pub(crate) mod proto;
+pub(crate) mod rate_limit;
+pub(crate) mod registry;
+pub(crate) mod signing;
+pub(crate) mod siwf;
+pub(crate) mod snapshot;
 pub(crate) mod sql;
+pub(crate) mod stream;
+pub(crate) mod usage;
 mod uses;
 
 pub mod utils {
@@ -1,6 +1,8 @@
 use crate::models::Claims;
 use axum::http::Request;
+use fuel_indexer_database::{queries, IndexerConnectionPool};
 use fuel_indexer_lib::config::{auth::AuthenticationStrategy, IndexerConfig};
+use futures_util::future::BoxFuture;
 use jsonwebtoken::{decode, DecodingKey, Validation};
 use std::task::{Context, Poll};
 use tower::{Layer, Service};
@@ -9,6 +11,7 @@ use tracing::error;
 #[derive(Clone)]
 struct MiddlewareState {
     config: IndexerConfig,
+    pool: IndexerConnectionPool,
 }
 
 #[derive(Clone)]
@@ -16,11 +19,14 @@ pub struct AuthenticationMiddleware {
     state: MiddlewareState,
 }
 
-impl From<&IndexerConfig> for AuthenticationMiddleware {
-    fn from(config: &IndexerConfig) -> Self {
+impl AuthenticationMiddleware {
+    /// Create a new `AuthenticationMiddleware`, checking the API keys that tokens
+    /// were minted from against the given pool.
+    pub fn new(config: &IndexerConfig, pool: IndexerConnectionPool) -> Self {
         Self {
             state: MiddlewareState {
                 config: config.clone(),
+                pool,
             },
         }
     }
@@ -43,55 +49,106 @@ pub struct AuthenticationService<S> {
     state: MiddlewareState,
 }
 
+impl<S> AuthenticationService<S> {
+    /// Decode the claims of the given request.
+    fn decode_claims<B>(&self, req: &Request<B>) -> Claims {
+        let config = &self.state.config;
+
+        if !config.authentication.enabled {
+            return Claims::default();
+        }
+
+        let header = req
+            .headers()
+            .get(http::header::AUTHORIZATION)
+            .and_then(|header| header.to_str().ok());
+
+        // Requests without a token, such as those of the sign-in routes, are
+        // unauthenticated rather than failing to decode, and are rejected by
+        // every route that checks the scopes of their claims.
+        let header = match header {
+            Some(header) if !header.is_empty() => header,
+            _ => return Claims::unauthenticated(),
+        };
+
+        match &config.authentication.strategy {
+            Some(AuthenticationStrategy::JWT) => {
+                let secret = config.authentication.jwt_secret.clone().unwrap_or_default();
+                match decode::<Claims>(
+                    header,
+                    &DecodingKey::from_secret(secret.as_bytes()),
+                    &Validation::default(),
+                ) {
+                    Ok(token) => token.claims,
+                    Err(e) => {
+                        error!("Failed to decode claims: {e}.");
+                        Claims::unauthenticated()
+                    }
+                }
+            }
+            _ => {
+                unimplemented!("Unsupported authentication strategy.");
+            }
+        }
+    }
+}
+
+/// Whether the API key with the given ID exists and has not been revoked.
+///
+/// Keys that can't be checked are treated as revoked.
+async fn is_active_api_key(pool: &IndexerConnectionPool, key_id: &str) -> bool {
+    let mut conn = match pool.acquire().await {
+        Ok(conn) => conn,
+        Err(e) => {
+            error!("Failed to acquire a connection to check API key: {e}.");
+            return false;
+        }
+    };
+
+    match queries::get_api_key(&mut conn, key_id).await {
+        Ok(Some(key)) => !key.revoked,
+        Ok(None) => false,
+        Err(e) => {
+            error!("Failed to check API key: {e}.");
+            false
+        }
+    }
+}
+
 impl<S, B> Service<Request<B>> for AuthenticationService<S>
 where
-    S: Service<Request<B>>,
+    S: Service<Request<B>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
 {
     type Response = S::Response;
     type Error = S::Error;
-    type Future = S::Future;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         self.inner.poll_ready(cx)
     }
 
     fn call(&mut self, mut req: Request<B>) -> Self::Future {
-        let config = &self.state.config;
+        let claims = self.decode_claims(&req);
+        let pool = self.state.pool.clone();
 
-        if config.authentication.enabled {
-            let header = req
-                .headers()
-                .get(http::header::AUTHORIZATION)
-                .and_then(|header| header.to_str().ok());
-
-            let header = header.unwrap_or_default();
-
-            match &config.authentication.strategy {
-                Some(AuthenticationStrategy::JWT) => {
-                    let secret =
-                        config.authentication.jwt_secret.clone().unwrap_or_default();
-                    match decode::<Claims>(
-                        header,
-                        &DecodingKey::from_secret(secret.as_bytes()),
-                        &Validation::default(),
-                    ) {
-                        Ok(token) => {
-                            req.extensions_mut().insert(token.claims);
-                        }
-                        Err(e) => {
-                            error!("Failed to decode claims: {e}.");
-                            req.extensions_mut().insert(Claims::unauthenticated());
-                        }
-                    }
-                    return self.inner.call(req);
-                }
-                _ => {
-                    unimplemented!("Unsupported authentication strategy.");
+        // The inner service that was polled ready is the one that must be called.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            // Tokens minted from an API key are only valid while the key is, so that
+            // revoking or rotating a key also revokes its tokens.
+            let claims = match claims.api_key() {
+                Some(key_id) if !is_active_api_key(&pool, key_id).await => {
+                    Claims::unauthenticated()
                 }
-            }
-        }
+                _ => claims,
+            };
 
-        req.extensions_mut().insert(Claims::default());
-        self.inner.call(req)
+            req.extensions_mut().insert(claims);
+            inner.call(req).await
+        })
     }
 }
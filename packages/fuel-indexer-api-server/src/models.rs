@@ -12,6 +12,62 @@ pub struct VerifySignatureRequest {
     pub message: String,
 }
 
+/// Query of a request for a sign-in message.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SignInChallengeQuery {
+    /// Hex-encoded address of the wallet signing in.
+    pub address: String,
+}
+
+/// Request to create an API key.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CreateApiKeyRequest {
+    /// Name of the key, used to tell keys apart.
+    pub name: String,
+
+    /// Authorization scopes granted by the key. Defaults to the scopes of the
+    /// token used to create it.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+/// Request to exchange an API key for a JWT.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApiKeyTokenRequest {
+    /// The full API key.
+    pub key: String,
+}
+
+/// Request to reindex an indexer.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReindexIndexerRequest {
+    /// Block height from which the indexer is reindexed.
+    pub start_block: u64,
+}
+
+/// Request to reprocess a range of an indexer's blocks in place.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReindexRangeIndexerRequest {
+    /// First block height to reprocess.
+    pub start_block: u64,
+
+    /// Last block height to reprocess.
+    pub end_block: u64,
+}
+
+/// Filters of a request for entries of the audit log.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AuditLogQuery {
+    /// Only return entries for indexers in this namespace.
+    pub namespace: Option<String>,
+
+    /// Only return entries for indexers with this identifier.
+    pub identifier: Option<String>,
+
+    /// Maximum number of entries to return.
+    pub limit: Option<u64>,
+}
+
 /// GraphQL web API response.
 #[derive(Serialize)]
 pub(crate) struct QueryResponse {
@@ -33,6 +89,40 @@ pub struct Claims {
 
     /// Expiration time (as UTC timestamp).
     exp: usize,
+
+    /// Authorization scopes, e.g. `deploy:my_namespace` or `query:*`.
+    ///
+    /// A token without scopes is not restricted to any namespace.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    scopes: Vec<String>,
+
+    /// ID of the API key the token was exchanged for, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    api_key: Option<String>,
+}
+
+/// Operations on an indexer that can be restricted with authorization scopes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeAction {
+    /// Registering or rolling back an indexer.
+    Deploy,
+
+    /// Stopping and removing an indexer.
+    Stop,
+
+    /// Querying the data of an indexer.
+    Query,
+}
+
+impl ScopeAction {
+    /// Name of the action, as used in scopes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScopeAction::Deploy => "deploy",
+            ScopeAction::Stop => "stop",
+            ScopeAction::Query => "query",
+        }
+    }
 }
 
 /// The payload of the JWT token if JWT authentication is enabled.
@@ -49,14 +139,38 @@ impl Claims {
             iss,
             iat: now,
             exp: now + expiry,
+            scopes: Vec::new(),
+            api_key: None,
         }
     }
 
+    /// Set the authorization scopes of the claims.
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Set the ID of the API key the claims were issued for.
+    pub fn with_api_key(mut self, api_key: String) -> Self {
+        self.api_key = Some(api_key);
+        self
+    }
+
     /// The subject of the claims.
     pub fn sub(&self) -> &str {
         &self.sub
     }
 
+    /// The authorization scopes of the claims.
+    pub fn scopes(&self) -> &[String] {
+        &self.scopes
+    }
+
+    /// The ID of the API key the claims were issued for, if any.
+    pub fn api_key(&self) -> Option<&str> {
+        self.api_key.as_deref()
+    }
+
     /// Like `Claims::new`, but with `iat` and `exp` values that indicate
     /// the claims have yet to be authenticated.
     pub fn unauthenticated() -> Self {
@@ -65,6 +179,8 @@ impl Claims {
             iss: "".to_string(),
             iat: 1,
             exp: 1,
+            scopes: Vec::new(),
+            api_key: None,
         }
     }
 
@@ -72,6 +188,69 @@ impl Claims {
     pub fn is_unauthenticated(&self) -> bool {
         self.exp == 1 && self.iat == 1
     }
+
+    /// Whether the claims can grant the given scopes to an API key, i.e., whether
+    /// the scopes are no broader than those of the claims.
+    pub fn can_grant(&self, scopes: &[String]) -> bool {
+        self.scopes.is_empty()
+            || (!scopes.is_empty() && scopes.iter().all(|s| self.scopes.contains(s)))
+    }
+
+    /// Whether the scopes of the claims allow the given action on indexers in the
+    /// given namespace.
+    ///
+    /// Scopes have the form `<action>:<namespace>`, and either part can be `*` to
+    /// match any action or namespace. Authenticated claims without scopes allow every
+    /// action, while unauthenticated claims allow none.
+    pub fn allows(&self, action: ScopeAction, namespace: &str) -> bool {
+        if self.is_unauthenticated() {
+            return false;
+        }
+
+        if self.scopes.is_empty() {
+            return true;
+        }
+
+        self.scopes.iter().any(|scope| match scope.split_once(':') {
+            Some((scope_action, scope_namespace)) => {
+                (scope_action == "*" || scope_action == action.as_str())
+                    && (scope_namespace == "*" || scope_namespace == namespace)
+            }
+            None => false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claims_scopes_restrict_actions_by_namespace() {
+        let mut claims = Claims::new("sub".to_string(), "iss".to_string(), 60);
+        assert!(claims.allows(ScopeAction::Deploy, "any"));
+
+        claims.scopes = vec!["deploy:foo".to_string(), "query:*".to_string()];
+        assert!(claims.allows(ScopeAction::Deploy, "foo"));
+        assert!(!claims.allows(ScopeAction::Deploy, "bar"));
+        assert!(!claims.allows(ScopeAction::Stop, "foo"));
+        assert!(claims.allows(ScopeAction::Query, "bar"));
+
+        claims.scopes = vec!["*:foo".to_string()];
+        assert!(claims.allows(ScopeAction::Stop, "foo"));
+        assert!(!claims.allows(ScopeAction::Query, "bar"));
+    }
+
+    #[test]
+    fn test_unauthenticated_claims_allow_nothing() {
+        let claims = Claims::unauthenticated();
+        assert!(claims.scopes().is_empty());
+        assert!(!claims.allows(ScopeAction::Query, "foo"));
+        assert!(!claims.allows(ScopeAction::Deploy, "foo"));
+
+        // Claims of requests made while authentication is disabled allow everything.
+        assert!(Claims::default().allows(ScopeAction::Query, "foo"));
+    }
 }
 
 /// A SQL query posted to the web API.
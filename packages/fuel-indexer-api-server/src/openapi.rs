@@ -0,0 +1,769 @@
+//! OpenAPI document of the web API's management routes, served at `/api/openapi.json`
+//! so that clients of the management plane can be generated from it.
+//!
+//! The GraphQL and gRPC routes are left out, since those are described by the
+//! introspection of each indexer's schema and by `/api/proto`, respectively.
+
+use fuel_indexer_lib::config::IndexerConfig;
+use serde_json::{json, Map, Value};
+
+/// Request body of an operation.
+enum RequestBody {
+    None,
+
+    /// A JSON object matching the named schema of the document's components.
+    Json(&'static str),
+
+    /// A multipart form with the given binary fields.
+    Multipart(&'static [&'static str]),
+
+    /// Raw bytes of the given content type.
+    Binary(&'static str),
+}
+
+/// Successful response of an operation.
+enum ResponseBody {
+    /// A JSON value, described by the named schema of the document's components,
+    /// or by no schema if `None`.
+    Json(Option<&'static str>),
+
+    /// Text or bytes of the given content types.
+    Content(&'static [&'static str]),
+
+    /// A status code without a meaningful body.
+    Empty,
+}
+
+/// A documented route of the web API.
+struct Operation {
+    method: &'static str,
+
+    /// Path of the route, relative to `/api`, using the router's `:param` syntax.
+    path: &'static str,
+    operation_id: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+
+    /// Whether the route requires a token when authentication is enabled.
+    authenticated: bool,
+
+    /// Query parameters, as (name, type, description).
+    query: &'static [(&'static str, &'static str, &'static str)],
+    request: RequestBody,
+    response: ResponseBody,
+}
+
+const INDEXER_ASSETS: &[&str] = &[
+    "manifest",
+    "schema",
+    "wasm",
+    "manifest_signature",
+    "wasm_signature",
+];
+
+/// Routes of the web API, in the order in which `WebApi::build` registers them.
+const OPERATIONS: &[Operation] = &[
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier",
+        operation_id: "registerIndexer",
+        tag: "indexers",
+        summary: "Deploy an indexer's manifest, schema and WASM module.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::Multipart(INDEXER_ASSETS),
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/index/:namespace/:identifier/versions",
+        operation_id: "listIndexerVersions",
+        tag: "indexers",
+        summary: "List the deployed versions of an indexer.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier/migrate/plan",
+        operation_id: "planIndexerMigration",
+        tag: "indexers",
+        summary: "Plan the migration of an indexer's tables to a new schema.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::Multipart(&["schema"]),
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/index/:namespace/:identifier/snapshot",
+        operation_id: "exportIndexerSnapshot",
+        tag: "indexers",
+        summary: "Export a snapshot of an indexer's assets and data.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Content(&["application/octet-stream"]),
+    },
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier/snapshot",
+        operation_id: "importIndexerSnapshot",
+        tag: "indexers",
+        summary: "Import a snapshot of an indexer's assets and data.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::Binary("application/octet-stream"),
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier/rollback/:version",
+        operation_id: "rollbackIndexer",
+        tag: "indexers",
+        summary: "Roll an indexer back to one of its previous versions.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier/pause",
+        operation_id: "pauseIndexer",
+        tag: "indexers",
+        summary: "Pause an indexer.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier/resume",
+        operation_id: "resumeIndexer",
+        tag: "indexers",
+        summary: "Resume a paused indexer.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier/reindex",
+        operation_id: "reindexIndexer",
+        tag: "indexers",
+        summary: "Reindex an indexer from the given block.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::Json("ReindexIndexerRequest"),
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier/reindex/range",
+        operation_id: "reindexIndexerRange",
+        tag: "indexers",
+        summary: "Reprocess a range of an indexer's blocks in place.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::Json("ReindexRangeIndexerRequest"),
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/index/:namespace/:identifier/events",
+        operation_id: "listIndexerEvents",
+        tag: "indexers",
+        summary: "List the lifecycle events of an indexer.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/index/:namespace/:identifier/promote/:version",
+        operation_id: "promoteIndexerVersion",
+        tag: "indexers",
+        summary: "Route an indexer's queries to one of its versions.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/index/:namespace/:identifier/route",
+        operation_id: "getIndexerQueryRoute",
+        tag: "indexers",
+        summary: "Return the version to which an indexer's queries are routed.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "delete",
+        path: "/index/:namespace/:identifier/route",
+        operation_id: "removeIndexerQueryRoute",
+        tag: "indexers",
+        summary: "Route an indexer's queries back to its latest version.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "delete",
+        path: "/index/:namespace/:identifier",
+        operation_id: "removeIndexer",
+        tag: "indexers",
+        summary: "Stop an indexer and remove its assets and data.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/status",
+        operation_id: "listIndexerStatuses",
+        tag: "status",
+        summary: "Return the status of every indexer.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/audit",
+        operation_id: "listAuditLog",
+        tag: "status",
+        summary: "List entries of the audit log of management operations.",
+        authenticated: true,
+        query: &[
+            (
+                "namespace",
+                "string",
+                "Only return entries for indexers in this namespace.",
+            ),
+            (
+                "identifier",
+                "string",
+                "Only return entries for indexers with this identifier.",
+            ),
+            ("limit", "integer", "Maximum number of entries to return."),
+        ],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/status/:namespace/:identifier",
+        operation_id: "getIndexerProgress",
+        tag: "status",
+        summary: "Return the progress of an indexer towards the chain's head.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/usage",
+        operation_id: "getQueryUsage",
+        tag: "status",
+        summary: "Return the query usage of each indexer.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/health",
+        operation_id: "healthCheck",
+        tag: "health",
+        summary: "Return the health of the service, its database and its Fuel client.",
+        authenticated: false,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/ready",
+        operation_id: "readinessCheck",
+        tag: "health",
+        summary: "Return 200 if the service is ready to serve requests, or 503 otherwise.",
+        authenticated: false,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Empty,
+    },
+    Operation {
+        method: "get",
+        path: "/openapi.json",
+        operation_id: "getOpenApiDocument",
+        tag: "health",
+        summary: "Return this document.",
+        authenticated: false,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/auth/keys",
+        operation_id: "createApiKey",
+        tag: "auth",
+        summary: "Create an API key. The full key is only returned once.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::Json("CreateApiKeyRequest"),
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/auth/keys",
+        operation_id: "listApiKeys",
+        tag: "auth",
+        summary: "List API keys, without their secrets.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "delete",
+        path: "/auth/keys/:id",
+        operation_id: "revokeApiKey",
+        tag: "auth",
+        summary: "Revoke an API key.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/auth/keys/:id/rotate",
+        operation_id: "rotateApiKey",
+        tag: "auth",
+        summary: "Replace the secret of an API key.",
+        authenticated: true,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/auth/nonce",
+        operation_id: "getNonce",
+        tag: "auth",
+        summary: "Create a nonce to be signed for authentication.",
+        authenticated: false,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "get",
+        path: "/auth/challenge",
+        operation_id: "getSignInChallenge",
+        tag: "auth",
+        summary: "Create a Sign-In with Fuel message to be signed for authentication.",
+        authenticated: false,
+        query: &[("address", "string", "Address of the wallet signing in.")],
+        request: RequestBody::None,
+        response: ResponseBody::Json(None),
+    },
+    Operation {
+        method: "post",
+        path: "/auth/signature",
+        operation_id: "verifySignature",
+        tag: "auth",
+        summary: "Exchange a signed nonce for a token.",
+        authenticated: false,
+        query: &[],
+        request: RequestBody::Json("VerifySignatureRequest"),
+        response: ResponseBody::Json(Some("TokenResponse")),
+    },
+    Operation {
+        method: "post",
+        path: "/auth/token",
+        operation_id: "apiKeyToken",
+        tag: "auth",
+        summary: "Exchange an API key for a token.",
+        authenticated: false,
+        query: &[],
+        request: RequestBody::Json("ApiKeyTokenRequest"),
+        response: ResponseBody::Json(Some("TokenResponse")),
+    },
+    Operation {
+        method: "get",
+        path: "/export/:namespace/:identifier/:entity",
+        operation_id: "exportEntity",
+        tag: "export",
+        summary: "Stream the rows of an entity's table as CSV or Parquet.",
+        authenticated: true,
+        query: &[
+            ("format", "string", "Either `csv` (the default) or `parquet`."),
+            (
+                "columns",
+                "string",
+                "Comma-separated list of the columns to export. Defaults to all.",
+            ),
+            (
+                "filter",
+                "string",
+                "Filter on a column, such as `amount:gt:1000`. May be given more than once.",
+            ),
+            ("limit", "integer", "Maximum number of rows to export."),
+        ],
+        request: RequestBody::None,
+        response: ResponseBody::Content(&["text/csv", "application/vnd.apache.parquet"]),
+    },
+    Operation {
+        method: "get",
+        path: "/proto/:namespace/:identifier",
+        operation_id: "getIndexerProto",
+        tag: "export",
+        summary: "Return the protobuf definitions of an indexer's gRPC services.",
+        authenticated: false,
+        query: &[],
+        request: RequestBody::None,
+        response: ResponseBody::Content(&["text/plain"]),
+    },
+];
+
+/// The SQL route, which is only registered if the service accepts SQL queries.
+const SQL_OPERATION: Operation = Operation {
+    method: "post",
+    path: "/sql/:namespace/:identifier",
+    operation_id: "sqlQuery",
+    tag: "export",
+    summary: "Run a read-only SQL query against an indexer's tables.",
+    authenticated: true,
+    query: &[],
+    request: RequestBody::Json("SqlQuery"),
+    response: ResponseBody::Json(Some("DataResponse")),
+};
+
+/// The metrics route, which is only registered with the `metrics` feature.
+#[cfg(feature = "metrics")]
+const METRICS_OPERATION: Operation = Operation {
+    method: "get",
+    path: "/metrics",
+    operation_id: "getMetrics",
+    tag: "health",
+    summary: "Return the service's Prometheus metrics.",
+    authenticated: false,
+    query: &[],
+    request: RequestBody::None,
+    response: ResponseBody::Content(&["text/plain"]),
+};
+
+/// Convert a route path using `:param` segments into an OpenAPI path template,
+/// returning it along with the names of its parameters.
+fn path_template(path: &str) -> (String, Vec<&str>) {
+    let mut params = Vec::new();
+    let template = path
+        .split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(param) => {
+                params.push(param);
+                format!("{{{param}}}")
+            }
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    (template, params)
+}
+
+impl Operation {
+    fn to_json(&self) -> Value {
+        let (_, path_params) = path_template(self.path);
+        let parameters = path_params
+            .into_iter()
+            .map(|name| {
+                // Rollbacks take the number of a version, whereas promotions also
+                // accept its name.
+                let schema = if name == "version" && self.path.contains("/rollback/") {
+                    json!({ "type": "integer" })
+                } else {
+                    json!({ "type": "string" })
+                };
+                json!({ "name": name, "in": "path", "required": true, "schema": schema })
+            })
+            .chain(self.query.iter().map(|(name, ty, description)| {
+                json!({
+                    "name": name,
+                    "in": "query",
+                    "required": false,
+                    "description": description,
+                    "schema": { "type": ty },
+                })
+            }))
+            .collect::<Vec<_>>();
+
+        let success = match &self.response {
+            ResponseBody::Json(schema) => {
+                let schema = schema
+                    .map(|name| json!({ "$ref": format!("#/components/schemas/{name}") }))
+                    .unwrap_or_else(|| json!({}));
+                json!({
+                    "description": "Success.",
+                    "content": { "application/json": { "schema": schema } },
+                })
+            }
+            ResponseBody::Content(types) => json!({
+                "description": "Success.",
+                "content": types
+                    .iter()
+                    .map(|ty| {
+                        (
+                            ty.to_string(),
+                            json!({ "schema": { "type": "string", "format": "binary" } }),
+                        )
+                    })
+                    .collect::<Map<_, _>>(),
+            }),
+            ResponseBody::Empty => json!({ "description": "Success." }),
+        };
+
+        let mut operation = json!({
+            "operationId": self.operation_id,
+            "tags": [self.tag],
+            "summary": self.summary,
+            "parameters": parameters,
+            "responses": {
+                "200": success,
+                "default": { "$ref": "#/components/responses/Error" },
+            },
+        });
+
+        let content = match &self.request {
+            RequestBody::None => None,
+            RequestBody::Json(name) => Some(json!({
+                "application/json": {
+                    "schema": { "$ref": format!("#/components/schemas/{name}") }
+                }
+            })),
+            RequestBody::Multipart(fields) => Some(json!({
+                "multipart/form-data": {
+                    "schema": {
+                        "type": "object",
+                        "properties": fields
+                            .iter()
+                            .map(|field| {
+                                (
+                                    field.to_string(),
+                                    json!({ "type": "string", "format": "binary" }),
+                                )
+                            })
+                            .collect::<Map<_, _>>(),
+                    }
+                }
+            })),
+            RequestBody::Binary(ty) => Some(json!({
+                (*ty): { "schema": { "type": "string", "format": "binary" } }
+            })),
+        };
+        if let Some(content) = content {
+            operation["requestBody"] = json!({ "required": true, "content": content });
+        }
+
+        if self.authenticated {
+            operation["security"] = json!([{ "bearerAuth": [] }]);
+        }
+
+        operation
+    }
+}
+
+/// Schemas of the JSON request and response bodies, mirroring `crate::models`.
+fn schemas() -> Value {
+    json!({
+        "VerifySignatureRequest": {
+            "type": "object",
+            "required": ["signature", "message"],
+            "properties": {
+                "signature": {
+                    "type": "string",
+                    "description": "Hexdigest of signature to be verified."
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Message to be verified against signature."
+                }
+            }
+        },
+        "CreateApiKeyRequest": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "description": "Name of the key, used to tell keys apart."
+                },
+                "scopes": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Authorization scopes granted by the key. Defaults to the scopes of the token used to create it."
+                }
+            }
+        },
+        "ApiKeyTokenRequest": {
+            "type": "object",
+            "required": ["key"],
+            "properties": {
+                "key": { "type": "string", "description": "The full API key." }
+            }
+        },
+        "ReindexIndexerRequest": {
+            "type": "object",
+            "required": ["start_block"],
+            "properties": {
+                "start_block": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Block height from which the indexer is reindexed."
+                }
+            }
+        },
+        "ReindexRangeIndexerRequest": {
+            "type": "object",
+            "required": ["start_block", "end_block"],
+            "properties": {
+                "start_block": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "First block height to reprocess."
+                },
+                "end_block": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Last block height to reprocess."
+                }
+            }
+        },
+        "SqlQuery": {
+            "type": "object",
+            "required": ["query"],
+            "properties": {
+                "query": { "type": "string", "description": "A single SELECT statement." }
+            }
+        },
+        "TokenResponse": {
+            "type": "object",
+            "properties": { "token": { "type": "string" } }
+        },
+        "DataResponse": {
+            "type": "object",
+            "properties": { "data": {} }
+        },
+        "Error": {
+            "type": "object",
+            "properties": {
+                "success": { "type": "string" },
+                "details": { "type": "string" }
+            }
+        }
+    })
+}
+
+/// Build the OpenAPI document of the routes registered for the given configuration.
+pub(crate) fn openapi_document(config: &IndexerConfig) -> Value {
+    let mut paths = Map::new();
+
+    let operations = OPERATIONS
+        .iter()
+        .chain(config.accept_sql_queries.then_some(&SQL_OPERATION));
+    #[cfg(feature = "metrics")]
+    let operations = operations.chain(std::iter::once(&METRICS_OPERATION));
+
+    for operation in operations {
+        let (template, _) = path_template(operation.path);
+        let item = paths.entry(template).or_insert_with(|| json!({}));
+        item[operation.method] = operation.to_json();
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Fuel Indexer API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "servers": [{ "url": "/api" }],
+        "paths": paths,
+        "components": {
+            "schemas": schemas(),
+            "responses": {
+                "Error": {
+                    "description": "The request failed.",
+                    "content": {
+                        "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Error" }
+                        }
+                    }
+                }
+            },
+            "securitySchemes": {
+                "bearerAuth": {
+                    "type": "http",
+                    "scheme": "bearer",
+                    "bearerFormat": "JWT",
+                }
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_path_template_extracts_parameters() {
+        assert_eq!(
+            path_template("/index/:namespace/:identifier/rollback/:version"),
+            (
+                "/index/{namespace}/{identifier}/rollback/{version}".to_string(),
+                vec!["namespace", "identifier", "version"]
+            )
+        );
+    }
+
+    #[test]
+    fn test_openapi_document_references_are_defined() {
+        let config = IndexerConfig {
+            accept_sql_queries: true,
+            ..Default::default()
+        };
+        let document = openapi_document(&config);
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+
+        let mut operation_ids = HashSet::new();
+        for item in document["paths"].as_object().unwrap().values() {
+            for operation in item.as_object().unwrap().values() {
+                assert!(operation_ids.insert(operation["operationId"].to_string()));
+
+                let text = operation.to_string();
+                for reference in text.split("#/components/schemas/").skip(1) {
+                    let name = reference.split('"').next().unwrap();
+                    assert!(schemas.contains_key(name), "Undefined schema {name}");
+                }
+            }
+        }
+
+        assert!(document["paths"]["/sql/{namespace}/{identifier}"]["post"].is_object());
+    }
+}
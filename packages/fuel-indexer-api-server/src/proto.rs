@@ -0,0 +1,554 @@
+//! Protobuf definitions of the gRPC API of an indexer, generated from its schema.
+//!
+//! Each entity is a message, and is queried through a service of its own with
+//! `Get`, `List` and `Filter` methods. The definitions are built once as a
+//! descriptor, which is used to encode and decode messages at runtime and is
+//! rendered as a `.proto` file for clients to generate their stubs from.
+
+use fuel_indexer_database::types::{ColumnType, SqlNamed};
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use prost_reflect::{DescriptorError, DescriptorPool};
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FileDescriptorProto, FileDescriptorSet, MethodDescriptorProto, OneofDescriptorProto,
+    ServiceDescriptorProto,
+};
+use std::fmt;
+
+/// Entity types that have no service, as they are internal to the indexer.
+const IGNORED_ENTITY_TYPES: [&str; 1] = ["IndexMetadataEntity"];
+
+/// Name of the message holding a condition of a `Filter` call.
+pub(crate) const CONDITION_MESSAGE: &str = "FilterCondition";
+
+/// Name of the enum of the operators of a condition.
+pub(crate) const OPERATOR_ENUM: &str = "FilterOperator";
+
+/// Operators of a condition, in the order of their enum values.
+pub(crate) const OPERATORS: [&str; 10] = [
+    "OPERATOR_UNSPECIFIED",
+    "EQ",
+    "NE",
+    "GT",
+    "GTE",
+    "LT",
+    "LTE",
+    "LIKE",
+    "IS_NULL",
+    "NOT_NULL",
+];
+
+/// A method of an entity service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EntityMethod {
+    /// Return the entity with the given ID.
+    Get,
+
+    /// Return a page of entities, ordered by ID.
+    List,
+
+    /// Return a page of the entities that meet some conditions, ordered by ID.
+    Filter,
+}
+
+impl EntityMethod {
+    /// Every method of an entity service.
+    const ALL: [EntityMethod; 3] = [Self::Get, Self::List, Self::Filter];
+
+    /// Name of the method.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::Get => "Get",
+            Self::List => "List",
+            Self::Filter => "Filter",
+        }
+    }
+
+    /// Return the method with the given name.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|method| method.name() == name)
+    }
+
+    /// Name of the input message of the method for the given entity.
+    fn input(&self, entity: &str) -> String {
+        format!("{}{entity}Request", self.name())
+    }
+
+    /// Name of the output message of the method for the given entity.
+    fn output(&self, entity: &str) -> String {
+        match self {
+            Self::Get => entity.to_string(),
+            Self::List | Self::Filter => list_message(entity),
+        }
+    }
+}
+
+/// Name of the service of the given entity.
+pub(crate) fn service_name(entity: &str) -> String {
+    format!("{entity}Service")
+}
+
+/// Name of the message holding a page of the given entity.
+fn list_message(entity: &str) -> String {
+    format!("{entity}List")
+}
+
+/// Protobuf scalar type of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScalarType {
+    Bool,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    String,
+}
+
+impl ScalarType {
+    /// Protobuf type in which values of the given column type are returned.
+    ///
+    /// Integers that don't fit in 64 bits, hex-encoded bytes, timestamps and
+    /// JSON are returned as strings, as they are by the GraphQL API.
+    fn from_column_type(typ: ColumnType) -> Self {
+        match typ {
+            ColumnType::Boolean => Self::Bool,
+            ColumnType::Int1 | ColumnType::Int4 => Self::Int32,
+            ColumnType::Int8 => Self::Int64,
+            ColumnType::UInt1 | ColumnType::UInt4 | ColumnType::BlockHeight => {
+                Self::Uint32
+            }
+            ColumnType::ID | ColumnType::ForeignKey | ColumnType::UInt8 => Self::Uint64,
+            _ => Self::String,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::Int32 => "int32",
+            Self::Int64 => "int64",
+            Self::Uint32 => "uint32",
+            Self::Uint64 => "uint64",
+            Self::String => "string",
+        }
+    }
+
+    fn descriptor_type(&self) -> Type {
+        match self {
+            Self::Bool => Type::Bool,
+            Self::Int32 => Type::Int32,
+            Self::Int64 => Type::Int64,
+            Self::Uint32 => Type::Uint32,
+            Self::Uint64 => Type::Uint64,
+            Self::String => Type::String,
+        }
+    }
+}
+
+/// Type of a field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FieldType {
+    Scalar(ScalarType),
+    Message(String),
+    Enum(String),
+}
+
+/// Whether a field is singular, optional or repeated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldLabel {
+    Singular,
+    Optional,
+    Repeated,
+}
+
+/// A field of a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProtoField {
+    name: String,
+    number: i32,
+    typ: FieldType,
+    label: FieldLabel,
+}
+
+impl ProtoField {
+    fn new(name: &str, number: i32, typ: FieldType, label: FieldLabel) -> Self {
+        Self {
+            name: name.to_string(),
+            number,
+            typ,
+            label,
+        }
+    }
+
+    fn scalar(name: &str, number: i32, typ: ScalarType, label: FieldLabel) -> Self {
+        Self::new(name, number, FieldType::Scalar(typ), label)
+    }
+}
+
+/// A message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProtoMessage {
+    name: String,
+    fields: Vec<ProtoField>,
+}
+
+/// Protobuf definitions of the gRPC API of an indexer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct IndexerProto {
+    /// Package of the definitions, which is `<namespace>.<identifier>`.
+    package: String,
+
+    /// Names of the entities, each of which has a service.
+    entities: Vec<String>,
+
+    /// Entity, request and response messages.
+    messages: Vec<ProtoMessage>,
+}
+
+impl IndexerProto {
+    /// Generate the definitions of the given indexer from its schema.
+    pub(crate) fn new(namespace: &str, identifier: &str, schema: &IndexerSchema) -> Self {
+        let parsed = schema.parsed();
+
+        let mut entities = parsed
+            .non_enum_typdefs()
+            .into_iter()
+            .map(|(name, _)| name.to_string())
+            .filter(|name| {
+                !parsed.is_virtual_typedef(name)
+                    && !IGNORED_ENTITY_TYPES.contains(&name.as_str())
+            })
+            .filter_map(|name| {
+                schema
+                    .tables()
+                    .iter()
+                    .find(|table| table.sql_name() == name.to_lowercase())
+                    .map(|table| (name, table))
+            })
+            .collect::<Vec<_>>();
+        entities.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut messages = vec![ProtoMessage {
+            name: CONDITION_MESSAGE.to_string(),
+            fields: vec![
+                ProtoField::scalar("field", 1, ScalarType::String, FieldLabel::Singular),
+                ProtoField::new(
+                    "op",
+                    2,
+                    FieldType::Enum(OPERATOR_ENUM.to_string()),
+                    FieldLabel::Singular,
+                ),
+                ProtoField::scalar("value", 3, ScalarType::String, FieldLabel::Singular),
+            ],
+        }];
+
+        for (name, table) in entities.iter() {
+            let fields = table
+                .columns()
+                .iter()
+                .filter(|column| column.coltype != ColumnType::Object)
+                .enumerate()
+                .map(|(i, column)| {
+                    let (typ, label) = match column.array_coltype {
+                        Some(typ) if column.coltype == ColumnType::Array => {
+                            (typ, FieldLabel::Repeated)
+                        }
+                        _ if column.nullable => (column.coltype, FieldLabel::Optional),
+                        _ => (column.coltype, FieldLabel::Singular),
+                    };
+                    ProtoField::scalar(
+                        &column.name,
+                        i as i32 + 1,
+                        ScalarType::from_column_type(typ),
+                        label,
+                    )
+                })
+                .collect();
+            messages.push(ProtoMessage {
+                name: name.clone(),
+                fields,
+            });
+
+            let id = ScalarType::Uint64;
+            let limit =
+                ProtoField::scalar("limit", 1, ScalarType::Uint32, FieldLabel::Singular);
+            let after = ProtoField::scalar("after", 2, id, FieldLabel::Optional);
+
+            messages.push(ProtoMessage {
+                name: EntityMethod::Get.input(name),
+                fields: vec![ProtoField::scalar("id", 1, id, FieldLabel::Singular)],
+            });
+            messages.push(ProtoMessage {
+                name: EntityMethod::List.input(name),
+                fields: vec![limit.clone(), after.clone()],
+            });
+            messages.push(ProtoMessage {
+                name: EntityMethod::Filter.input(name),
+                fields: vec![
+                    limit,
+                    after,
+                    ProtoField::new(
+                        "conditions",
+                        3,
+                        FieldType::Message(CONDITION_MESSAGE.to_string()),
+                        FieldLabel::Repeated,
+                    ),
+                ],
+            });
+            messages.push(ProtoMessage {
+                name: list_message(name),
+                fields: vec![
+                    ProtoField::new(
+                        "items",
+                        1,
+                        FieldType::Message(name.clone()),
+                        FieldLabel::Repeated,
+                    ),
+                    ProtoField::scalar("next", 2, id, FieldLabel::Optional),
+                ],
+            });
+        }
+
+        Self {
+            package: format!("{namespace}.{identifier}"),
+            entities: entities.into_iter().map(|(name, _)| name).collect(),
+            messages,
+        }
+    }
+
+    /// Package of the definitions.
+    pub(crate) fn package(&self) -> &str {
+        &self.package
+    }
+
+    /// Build a pool holding the descriptors of these definitions.
+    pub(crate) fn descriptor_pool(&self) -> Result<DescriptorPool, DescriptorError> {
+        let type_name = |name: &str| format!(".{}.{name}", self.package);
+
+        let message_type = self
+            .messages
+            .iter()
+            .map(|message| {
+                let mut oneof_decl = Vec::new();
+                let field = message
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let (typ, name) = match &field.typ {
+                            FieldType::Scalar(typ) => (typ.descriptor_type(), None),
+                            FieldType::Message(name) => {
+                                (Type::Message, Some(type_name(name)))
+                            }
+                            FieldType::Enum(name) => (Type::Enum, Some(type_name(name))),
+                        };
+                        let label = match field.label {
+                            FieldLabel::Repeated => Label::Repeated,
+                            FieldLabel::Singular | FieldLabel::Optional => {
+                                Label::Optional
+                            }
+                        };
+
+                        // Optional fields of proto3 are each in a synthetic oneof.
+                        let oneof_index =
+                            (field.label == FieldLabel::Optional).then(|| {
+                                oneof_decl.push(OneofDescriptorProto {
+                                    name: Some(format!("_{}", field.name)),
+                                    ..OneofDescriptorProto::default()
+                                });
+                                oneof_decl.len() as i32 - 1
+                            });
+
+                        FieldDescriptorProto {
+                            name: Some(field.name.clone()),
+                            number: Some(field.number),
+                            label: Some(label as i32),
+                            r#type: Some(typ as i32),
+                            type_name: name,
+                            json_name: Some(field.name.clone()),
+                            oneof_index,
+                            proto3_optional: oneof_index.map(|_| true),
+                            ..FieldDescriptorProto::default()
+                        }
+                    })
+                    .collect();
+
+                DescriptorProto {
+                    name: Some(message.name.clone()),
+                    field,
+                    oneof_decl,
+                    ..DescriptorProto::default()
+                }
+            })
+            .collect();
+
+        let enum_type = vec![EnumDescriptorProto {
+            name: Some(OPERATOR_ENUM.to_string()),
+            value: OPERATORS
+                .iter()
+                .enumerate()
+                .map(|(i, name)| EnumValueDescriptorProto {
+                    name: Some(name.to_string()),
+                    number: Some(i as i32),
+                    ..EnumValueDescriptorProto::default()
+                })
+                .collect(),
+            ..EnumDescriptorProto::default()
+        }];
+
+        let service = self
+            .entities
+            .iter()
+            .map(|entity| ServiceDescriptorProto {
+                name: Some(service_name(entity)),
+                method: EntityMethod::ALL
+                    .iter()
+                    .map(|method| MethodDescriptorProto {
+                        name: Some(method.name().to_string()),
+                        input_type: Some(type_name(&method.input(entity))),
+                        output_type: Some(type_name(&method.output(entity))),
+                        ..MethodDescriptorProto::default()
+                    })
+                    .collect(),
+                ..ServiceDescriptorProto::default()
+            })
+            .collect();
+
+        DescriptorPool::from_file_descriptor_set(FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some(format!("{}.proto", self.package)),
+                package: Some(self.package.clone()),
+                message_type,
+                enum_type,
+                service,
+                syntax: Some("proto3".to_string()),
+                ..FileDescriptorProto::default()
+            }],
+        })
+    }
+}
+
+impl fmt::Display for IndexerProto {
+    /// Render the definitions as a `.proto` file.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "syntax = \"proto3\";")?;
+        writeln!(f)?;
+        writeln!(f, "package {};", self.package)?;
+        writeln!(f)?;
+
+        writeln!(f, "enum {OPERATOR_ENUM} {{")?;
+        for (i, name) in OPERATORS.iter().enumerate() {
+            writeln!(f, "  {name} = {i};")?;
+        }
+        writeln!(f, "}}")?;
+
+        for message in self.messages.iter() {
+            writeln!(f)?;
+            writeln!(f, "message {} {{", message.name)?;
+            for field in message.fields.iter() {
+                let label = match field.label {
+                    FieldLabel::Singular => "",
+                    FieldLabel::Optional => "optional ",
+                    FieldLabel::Repeated => "repeated ",
+                };
+                let typ = match &field.typ {
+                    FieldType::Scalar(typ) => typ.as_str(),
+                    FieldType::Message(name) | FieldType::Enum(name) => name.as_str(),
+                };
+                writeln!(f, "  {label}{typ} {} = {};", field.name, field.number)?;
+            }
+            writeln!(f, "}}")?;
+        }
+
+        for entity in self.entities.iter() {
+            writeln!(f)?;
+            writeln!(f, "service {} {{", service_name(entity))?;
+            for method in EntityMethod::ALL.iter() {
+                writeln!(
+                    f,
+                    "  rpc {}({}) returns ({});",
+                    method.name(),
+                    method.input(entity),
+                    method.output(entity)
+                )?;
+            }
+            writeln!(f, "}}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_database::DbType;
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+
+    fn indexer_proto() -> IndexerProto {
+        let schema = IndexerSchema::new(
+            "test_namespace",
+            "index1",
+            &GraphQLSchema::new(
+                r#"
+type Account @entity {
+    id: ID!
+    balance: UInt8!
+    label: Charfield
+}
+
+type Transfer @entity {
+    id: ID!
+    sender: Account!
+    amount: Int8!
+}"#
+                .to_string(),
+            ),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        IndexerProto::new("test_namespace", "index1", &schema)
+    }
+
+    #[test]
+    fn test_proto_renders_entity_messages_and_services() {
+        let proto = indexer_proto().to_string();
+
+        assert!(proto.contains("package test_namespace.index1;"));
+        assert!(proto.contains(
+            "message Account {\n  uint64 id = 1;\n  uint64 balance = 2;\n  optional string label = 3;\n}"
+        ));
+        assert!(proto.contains(
+            "message Transfer {\n  uint64 id = 1;\n  uint64 sender = 2;\n  int64 amount = 3;\n}"
+        ));
+        assert!(proto.contains(
+            "service TransferService {\n  rpc Get(GetTransferRequest) returns (Transfer);\n  rpc List(ListTransferRequest) returns (TransferList);\n  rpc Filter(FilterTransferRequest) returns (TransferList);\n}"
+        ));
+        assert!(!proto.contains("IndexMetadataEntity"));
+    }
+
+    #[test]
+    fn test_proto_descriptor_pool() {
+        let pool = indexer_proto().descriptor_pool().unwrap();
+
+        let service = pool
+            .get_service_by_name("test_namespace.index1.AccountService")
+            .unwrap();
+        let methods = service
+            .methods()
+            .map(|m| m.name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(methods, vec!["Get", "List", "Filter"]);
+
+        let account = pool
+            .get_message_by_name("test_namespace.index1.Account")
+            .unwrap();
+        assert!(account
+            .get_field_by_name("label")
+            .unwrap()
+            .supports_presence());
+        assert!(account.get_field_by_name("object").is_none());
+    }
+}
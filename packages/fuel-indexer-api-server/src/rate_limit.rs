@@ -0,0 +1,248 @@
+use fuel_indexer_lib::config::limit::RateLimitConfig;
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
+
+/// Number of buckets after which idle buckets are dropped.
+const MAX_IDLE_BUCKETS: usize = 10_000;
+
+/// Time after which a bucket that hasn't been used is considered idle.
+const BUCKET_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A token bucket that holds up to one second's worth of tokens.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Tokens currently available. Can go negative when more is taken than was
+    /// available, e.g., when a query returns more rows than allowed.
+    tokens: f64,
+
+    /// Tokens added per second, which is also the capacity of the bucket.
+    rate: f64,
+
+    /// Last time tokens were added to the bucket.
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64) -> Self {
+        Self {
+            tokens: rate as f64,
+            rate: rate as f64,
+            updated_at: Instant::now(),
+        }
+    }
+
+    /// Add the tokens accrued since the bucket was last updated.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.updated_at = now;
+    }
+
+    /// Take a token if one is available.
+    fn try_take(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return true;
+        }
+        false
+    }
+
+    /// Whether the bucket is not in debt.
+    fn has_tokens(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens > 0.0
+    }
+
+    /// Take the given number of tokens, going into debt if needed.
+    fn take(&mut self, n: u64, now: Instant) {
+        self.refill(now);
+        self.tokens -= n as f64;
+    }
+}
+
+/// The kinds of limit applied by `QueryRateLimiter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum RateLimit {
+    /// Queries per second from a single client.
+    ClientRequests,
+
+    /// Rows per second returned to a single client.
+    ClientRows,
+
+    /// Queries per second to a single indexer.
+    IndexerRequests,
+}
+
+impl RateLimit {
+    /// Name of the limit, as used in metrics.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            RateLimit::ClientRequests => "client_requests",
+            RateLimit::ClientRows => "client_rows",
+            RateLimit::IndexerRequests => "indexer_requests",
+        }
+    }
+}
+
+/// Token bucket rate limits for GraphQL queries, keyed by client and by indexer.
+///
+/// Unlike the global `--rate-limit-request-count` limit, these limits stop a single
+/// client or a single popular indexer from exhausting a shared deployment.
+#[derive(Debug, Default)]
+pub(crate) struct QueryRateLimiter {
+    /// Limits in units per second, if configured.
+    limits: HashMap<RateLimit, u64>,
+
+    /// Buckets keyed by the limit and the client or indexer they apply to.
+    buckets: Mutex<HashMap<(RateLimit, String), TokenBucket>>,
+}
+
+impl QueryRateLimiter {
+    /// Create a new `QueryRateLimiter` from the rate limit configuration.
+    pub(crate) fn new(config: &RateLimitConfig) -> Self {
+        let mut limits = HashMap::new();
+
+        if config.enabled {
+            let configured = [
+                (RateLimit::ClientRequests, config.client_rps),
+                (RateLimit::ClientRows, config.client_rows),
+                (RateLimit::IndexerRequests, config.indexer_rps),
+            ];
+            for (limit, rate) in configured {
+                if let Some(rate) = rate {
+                    limits.insert(limit, rate);
+                }
+            }
+        }
+
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check whether a query from the given client to the given indexer is allowed,
+    /// returning the limit that was exceeded if it isn't.
+    pub(crate) fn check(&self, client: &str, indexer: &str) -> Result<(), RateLimit> {
+        if self.limits.is_empty() {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().expect("Rate limiter lock poisoned.");
+
+        if buckets.len() > MAX_IDLE_BUCKETS {
+            buckets.retain(|_, bucket| {
+                now.duration_since(bucket.updated_at) < BUCKET_IDLE_TIMEOUT
+            });
+        }
+
+        let mut allowed = |limit: RateLimit, key: &str, take: bool| {
+            let rate = match self.limits.get(&limit) {
+                Some(rate) => *rate,
+                None => return true,
+            };
+            let bucket = buckets
+                .entry((limit, key.to_string()))
+                .or_insert_with(|| TokenBucket::new(rate));
+            if take {
+                bucket.try_take(now)
+            } else {
+                bucket.has_tokens(now)
+            }
+        };
+
+        let exceeded = if !allowed(RateLimit::ClientRows, client, false) {
+            Some(RateLimit::ClientRows)
+        } else if !allowed(RateLimit::ClientRequests, client, true) {
+            Some(RateLimit::ClientRequests)
+        } else if !allowed(RateLimit::IndexerRequests, indexer, true) {
+            Some(RateLimit::IndexerRequests)
+        } else {
+            None
+        };
+
+        match exceeded {
+            Some(limit) => {
+                #[cfg(feature = "metrics")]
+                METRICS.web.record_rate_limited(limit.as_str(), indexer);
+
+                Err(limit)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Record the number of rows returned to the given client.
+    pub(crate) fn record_rows(&self, client: &str, rows: u64) {
+        let rate = match self.limits.get(&RateLimit::ClientRows) {
+            Some(rate) => *rate,
+            None => return,
+        };
+
+        let now = Instant::now();
+        self.buckets
+            .lock()
+            .expect("Rate limiter lock poisoned.")
+            .entry((RateLimit::ClientRows, client.to_string()))
+            .or_insert_with(|| TokenBucket::new(rate))
+            .take(rows, now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::count_rows;
+    use serde_json::json;
+
+    #[test]
+    fn test_query_rate_limiter_limits_clients_and_rows() {
+        let config = RateLimitConfig {
+            enabled: true,
+            client_rps: Some(2),
+            client_rows: Some(10),
+            ..RateLimitConfig::default()
+        };
+        let limiter = QueryRateLimiter::new(&config);
+
+        assert!(limiter.check("alice", "indexer").is_ok());
+        assert!(limiter.check("alice", "indexer").is_ok());
+        assert_eq!(
+            limiter.check("alice", "indexer"),
+            Err(RateLimit::ClientRequests)
+        );
+        assert!(limiter.check("bob", "indexer").is_ok());
+
+        limiter.record_rows("bob", 100);
+        assert_eq!(limiter.check("bob", "indexer"), Err(RateLimit::ClientRows));
+    }
+
+    #[test]
+    fn test_query_rate_limiter_limits_rows_of_list_responses() {
+        let config = RateLimitConfig {
+            enabled: true,
+            client_rows: Some(10),
+            ..RateLimitConfig::default()
+        };
+        let limiter = QueryRateLimiter::new(&config);
+
+        let response = json!({
+            "transfers": (0..20).map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+        });
+        assert!(limiter.check("alice", "indexer").is_ok());
+        limiter.record_rows("alice", count_rows(&response));
+        assert_eq!(
+            limiter.check("alice", "indexer"),
+            Err(RateLimit::ClientRows)
+        );
+        assert!(limiter.check("bob", "indexer").is_ok());
+    }
+}
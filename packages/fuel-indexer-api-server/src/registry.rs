@@ -0,0 +1,249 @@
+use crate::models::Claims;
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Object, Result, Schema, SimpleObject,
+};
+use fuel_indexer_database::{
+    queries,
+    types::{IndexerAssetType, IndexerStatus, RegisteredIndexer},
+    IndexerConnectionPool,
+};
+
+/// GraphQL schema used to introspect the indexers registered with the service.
+pub(crate) type RegistrySchema = Schema<RegistryQuery, EmptyMutation, EmptySubscription>;
+
+/// Build the registry schema, which resolves its data using the given pool.
+pub(crate) fn build_registry_schema(pool: IndexerConnectionPool) -> RegistrySchema {
+    Schema::build(RegistryQuery, EmptyMutation, EmptySubscription)
+        .data(pool)
+        .finish()
+}
+
+/// Root query of the registry schema.
+pub(crate) struct RegistryQuery;
+
+#[Object]
+impl RegistryQuery {
+    /// Indexers registered with the service.
+    ///
+    /// Users other than the admin only see the indexers they own.
+    async fn indexers(&self, ctx: &Context<'_>) -> Result<Vec<Indexer>> {
+        let pool = ctx.data::<IndexerConnectionPool>()?;
+        let claims = ctx.data::<Claims>()?;
+
+        let mut conn = pool.acquire().await?;
+        let indexers = queries::all_registered_indexers(&mut conn)
+            .await?
+            .into_iter()
+            .filter(|indexer| visible_to(indexer, claims))
+            .map(Indexer)
+            .collect();
+
+        Ok(indexers)
+    }
+
+    /// The indexer with the given namespace and identifier, if it's registered.
+    async fn indexer(
+        &self,
+        ctx: &Context<'_>,
+        namespace: String,
+        identifier: String,
+    ) -> Result<Option<Indexer>> {
+        let pool = ctx.data::<IndexerConnectionPool>()?;
+        let claims = ctx.data::<Claims>()?;
+
+        let mut conn = pool.acquire().await?;
+        let indexer = queries::all_registered_indexers(&mut conn)
+            .await?
+            .into_iter()
+            .find(|indexer| {
+                indexer.namespace == namespace && indexer.identifier == identifier
+            })
+            .filter(|indexer| visible_to(indexer, claims))
+            .map(Indexer);
+
+        Ok(indexer)
+    }
+}
+
+/// Whether the given indexer can be seen by the holder of the given claims.
+fn visible_to(indexer: &RegisteredIndexer, claims: &Claims) -> bool {
+    claims.sub().is_empty() || indexer.pubkey.as_deref() == Some(claims.sub())
+}
+
+/// An indexer registered with the service.
+pub(crate) struct Indexer(RegisteredIndexer);
+
+#[Object]
+impl Indexer {
+    /// Database ID of the indexer.
+    async fn id(&self) -> i64 {
+        self.0.id
+    }
+
+    /// Namespace of the indexer.
+    async fn namespace(&self) -> &str {
+        &self.0.namespace
+    }
+
+    /// Identifier of the indexer.
+    async fn identifier(&self) -> &str {
+        &self.0.identifier
+    }
+
+    /// Unique identifier of the indexer, as `namespace.identifier`.
+    async fn uid(&self) -> String {
+        self.0.uid()
+    }
+
+    /// Public key of the indexer's owner, if authentication is enabled.
+    async fn pubkey(&self) -> Option<&str> {
+        self.0.pubkey.as_deref()
+    }
+
+    /// Time at which the indexer was registered, in RFC 3339 format.
+    async fn created_at(&self) -> String {
+        self.0.created_at.to_rfc3339()
+    }
+
+    /// Lifecycle state of the indexer, e.g., `running` or `paused`.
+    async fn state(&self, ctx: &Context<'_>) -> Result<String> {
+        let pool = ctx.data::<IndexerConnectionPool>()?;
+        let mut conn = pool.acquire().await?;
+        let state =
+            queries::indexer_state(&mut conn, &self.0.namespace, &self.0.identifier)
+                .await?;
+
+        Ok(state.as_ref().to_string())
+    }
+
+    /// Version of the indexer's GraphQL schema.
+    async fn schema_version(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let pool = ctx.data::<IndexerConnectionPool>()?;
+        let mut conn = pool.acquire().await?;
+
+        match queries::graph_root_latest(&mut conn, &self.0.namespace, &self.0.identifier)
+            .await
+        {
+            Ok(root) => Ok(Some(root.version)),
+            Err(sqlx::Error::RowNotFound) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Sync progress of the indexer, if its executor has reported any.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Option<SyncStatus>> {
+        let pool = ctx.data::<IndexerConnectionPool>()?;
+        let mut conn = pool.acquire().await?;
+        let status =
+            queries::get_indexer_status(&mut conn, &self.0.namespace, &self.0.identifier)
+                .await?;
+
+        Ok(status.map(SyncStatus::from))
+    }
+
+    /// Latest version and digest of each of the indexer's assets, and their IPFS
+    /// CIDs if the latest deployment was published to IPFS.
+    async fn assets(&self, ctx: &Context<'_>) -> Result<Vec<Asset>> {
+        let pool = ctx.data::<IndexerConnectionPool>()?;
+        let mut conn = pool.acquire().await?;
+
+        let deployment = queries::indexer_deployments(
+            &mut conn,
+            &self.0.namespace,
+            &self.0.identifier,
+        )
+        .await?
+        .pop();
+
+        let mut assets = Vec::new();
+        for asset_type in [
+            IndexerAssetType::Manifest,
+            IndexerAssetType::Schema,
+            IndexerAssetType::Wasm,
+        ] {
+            if let Some((version, digest)) =
+                queries::latest_asset_digest(&mut conn, &self.0.id, asset_type.clone())
+                    .await?
+            {
+                let cid = deployment.as_ref().and_then(|deployment| match asset_type {
+                    IndexerAssetType::Manifest => deployment.manifest_cid.clone(),
+                    IndexerAssetType::Schema => deployment.schema_cid.clone(),
+                    IndexerAssetType::Wasm => deployment.wasm_cid.clone(),
+                });
+                assets.push(Asset {
+                    asset_type: asset_type.as_ref().to_string(),
+                    version,
+                    digest,
+                    cid,
+                });
+            }
+        }
+
+        Ok(assets)
+    }
+}
+
+/// Sync progress of an indexer.
+#[derive(SimpleObject)]
+pub(crate) struct SyncStatus {
+    /// Height of the last block processed by the indexer.
+    current_height: u64,
+
+    /// Height of the latest block on the chain, as last seen by the indexer.
+    chain_tip: u64,
+
+    /// Number of blocks between the indexer and the chain tip.
+    blocks_behind: u64,
+
+    /// Average number of blocks processed per second.
+    blocks_per_second: f64,
+
+    /// CPU time spent running the indexer's handlers since it was deployed, in seconds.
+    cpu_seconds: f64,
+
+    /// Estimated number of seconds until the indexer catches up to the chain tip.
+    eta_seconds: Option<u64>,
+
+    /// Time at which the status was last updated, in RFC 3339 format.
+    updated_at: String,
+
+    /// Last error raised while the indexer was processing blocks, if any.
+    last_error: Option<String>,
+
+    /// Time at which the last error was raised, in RFC 3339 format.
+    last_error_at: Option<String>,
+}
+
+impl From<IndexerStatus> for SyncStatus {
+    fn from(status: IndexerStatus) -> Self {
+        Self {
+            current_height: status.current_height,
+            chain_tip: status.chain_tip,
+            blocks_behind: status.blocks_behind(),
+            blocks_per_second: status.blocks_per_second,
+            cpu_seconds: status.cpu_seconds,
+            eta_seconds: status.eta_seconds(),
+            updated_at: status.updated_at.to_rfc3339(),
+            last_error_at: status
+                .last_error_at
+                .map(|last_error_at| last_error_at.to_rfc3339()),
+            last_error: status.last_error,
+        }
+    }
+}
+
+/// The latest version of one of an indexer's assets.
+#[derive(SimpleObject)]
+pub(crate) struct Asset {
+    /// Type of the asset, i.e., `manifest`, `schema` or `wasm`.
+    asset_type: String,
+
+    /// Version of the asset.
+    version: i32,
+
+    /// Digest of the asset's bytes.
+    digest: String,
+
+    /// IPFS CID of the asset, if it was published to IPFS.
+    cid: Option<String>,
+}
@@ -0,0 +1,94 @@
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use tracing::error;
+
+/// Parse a hex-encoded ed25519 signature.
+fn parse_signature(signature: &str) -> Option<Signature> {
+    let bytes: [u8; 64] = hex::decode(signature.trim()).ok()?.try_into().ok()?;
+    Some(Signature::from_bytes(&bytes))
+}
+
+/// Parse a hex-encoded ed25519 public key.
+fn parse_public_key(key: &str) -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(key.trim()).ok()?.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+/// Return the signer that signed both the manifest and the WASM module of a
+/// deployment, given the hex-encoded signatures of each.
+///
+/// Only the hex-encoded ed25519 public keys in `signers` are accepted.
+pub(crate) fn deployment_signer(
+    signers: &[String],
+    manifest: &[u8],
+    manifest_signature: &str,
+    wasm: &[u8],
+    wasm_signature: &str,
+) -> Option<String> {
+    let manifest_signature = parse_signature(manifest_signature)?;
+    let wasm_signature = parse_signature(wasm_signature)?;
+
+    signers
+        .iter()
+        .find(|signer| match parse_public_key(signer) {
+            Some(key) => {
+                key.verify(manifest, &manifest_signature).is_ok()
+                    && key.verify(wasm, &wasm_signature).is_ok()
+            }
+            None => {
+                error!("Invalid deployment signer: {signer}.");
+                false
+            }
+        })
+        .map(|signer| signer.trim().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_deployment_signer_requires_allowed_key_to_sign_both_assets() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let other = SigningKey::from_bytes(&[8; 32]);
+        let signers = vec![hex::encode(key.verifying_key().to_bytes())];
+
+        let manifest_signature = hex::encode(key.sign(b"manifest").to_bytes());
+        let wasm_signature = hex::encode(key.sign(b"wasm").to_bytes());
+        assert_eq!(
+            deployment_signer(
+                &signers,
+                b"manifest",
+                &manifest_signature,
+                b"wasm",
+                &wasm_signature
+            ),
+            Some(signers[0].clone())
+        );
+
+        // Signatures must match the assets they're given for.
+        assert_eq!(
+            deployment_signer(
+                &signers,
+                b"manifest",
+                &wasm_signature,
+                b"wasm",
+                &manifest_signature
+            ),
+            None
+        );
+
+        // Signers must be allowed.
+        let wasm_signature = hex::encode(other.sign(b"wasm").to_bytes());
+        assert_eq!(
+            deployment_signer(
+                &signers,
+                b"manifest",
+                &manifest_signature,
+                b"wasm",
+                &wasm_signature
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Sign-In with Fuel: authentication of a Fuel wallet by its signature of a
+//! structured message, modelled after EIP-4361 (Sign-In with Ethereum).
+//!
+//! ```text
+//! indexer.example.com wants you to sign in with your Fuel account:
+//! 0x6b63804cfbf9856e68e5b6e7aef238dc8311ec55bec04df774003a2c96e0418e
+//!
+//! Sign in to the Fuel indexer service.
+//!
+//! URI: https://indexer.example.com
+//! Version: 1
+//! Chain ID: 0
+//! Nonce: 2b4c8d6b8c4f4b6f9a1e7b9d2c3f4a5b
+//! Issued At: 2023-07-22T09:00:00Z
+//! Expiration Time: 2023-07-22T10:00:00Z
+//! ```
+//!
+//! The message binds the signature to the service's domain and chain, so that it
+//! can't be replayed elsewhere, and to a nonce issued by the service, so that it
+//! can't be replayed at all.
+
+use chrono::{DateTime, TimeZone, Utc};
+use fuel_crypto::{Message, PublicKey, Signature};
+use std::fmt::{Display, Formatter};
+
+/// Header of a message, following the domain requesting the sign-in.
+const HEADER: &str = " wants you to sign in with your Fuel account:";
+
+/// Statement of the messages issued by the service.
+const STATEMENT: &str = "Sign in to the Fuel indexer service.";
+
+/// Version of the message format.
+const VERSION: &str = "1";
+
+/// Errors raised while parsing or verifying a sign-in message.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub(crate) enum SignInError {
+    #[error("Malformed sign-in message: {0}")]
+    Malformed(String),
+    #[error("Sign-in message is for {0}, not this service.")]
+    WrongDomain(String),
+    #[error("Sign-in message is for chain {0}, not this service's chain.")]
+    WrongChain(u64),
+    #[error("Sign-in message is not valid at this time.")]
+    Expired,
+    #[error("Sign-in message was not signed by {0}.")]
+    WrongSigner(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+}
+
+/// A sign-in message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SignInMessage {
+    /// Host of the service the wallet signs in to.
+    pub domain: String,
+
+    /// Hex-encoded address of the wallet, with a `0x` prefix.
+    pub address: String,
+
+    /// Human-readable statement the wallet agrees to.
+    pub statement: Option<String>,
+
+    /// URI of the service.
+    pub uri: String,
+
+    /// Chain ID of the network the service indexes.
+    pub chain_id: u64,
+
+    /// Nonce issued by the service.
+    pub nonce: String,
+
+    /// Time at which the message was issued.
+    pub issued_at: DateTime<Utc>,
+
+    /// Time after which the message is no longer valid.
+    pub expiration_time: Option<DateTime<Utc>>,
+}
+
+impl SignInMessage {
+    /// Create the message the given address signs to sign in with the given nonce.
+    pub(crate) fn new(
+        domain: &str,
+        scheme: &str,
+        address: &str,
+        chain_id: u64,
+        nonce: &str,
+        expiry: i64,
+    ) -> Self {
+        Self {
+            domain: domain.to_string(),
+            address: normalize_address(address),
+            statement: Some(STATEMENT.to_string()),
+            uri: format!("{scheme}://{domain}"),
+            chain_id,
+            nonce: nonce.to_string(),
+            issued_at: Utc::now(),
+            expiration_time: Utc.timestamp_opt(expiry, 0).single(),
+        }
+    }
+
+    /// Whether the given text looks like a sign-in message, rather than a bare nonce.
+    pub(crate) fn is_sign_in_message(text: &str) -> bool {
+        text.lines()
+            .next()
+            .map_or(false, |line| line.ends_with(HEADER))
+    }
+
+    /// Check that this message is for the given domain and chain, and is valid now,
+    /// and that the given hex-encoded signature of it was made by its address.
+    /// Returns the public key of the signer.
+    pub(crate) fn verify(
+        &self,
+        signature: &str,
+        domain: &str,
+        chain_id: u64,
+    ) -> Result<PublicKey, SignInError> {
+        if self.domain != domain {
+            return Err(SignInError::WrongDomain(self.domain.clone()));
+        }
+
+        if self.chain_id != chain_id {
+            return Err(SignInError::WrongChain(self.chain_id));
+        }
+
+        let now = Utc::now();
+        if self.issued_at > now
+            || self.expiration_time.map_or(false, |expiry| expiry <= now)
+        {
+            return Err(SignInError::Expired);
+        }
+
+        let bytes: [u8; 64] = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|e| SignInError::InvalidSignature(e.to_string()))?
+            .try_into()
+            .map_err(|_| {
+                SignInError::InvalidSignature("expected 64 bytes".to_string())
+            })?;
+        let signature = Signature::from_bytes(bytes);
+        let message = Message::new(self.to_string());
+        let pk = signature
+            .recover(&message)
+            .map_err(|e| SignInError::InvalidSignature(e.to_string()))?;
+
+        // The address of a Fuel wallet is the hash of its public key.
+        if normalize_address(&pk.hash().to_string()) != self.address {
+            return Err(SignInError::WrongSigner(self.address.clone()));
+        }
+
+        Ok(pk)
+    }
+}
+
+/// Return a hex-encoded address in lowercase, with a `0x` prefix.
+fn normalize_address(address: &str) -> String {
+    format!(
+        "0x{}",
+        address.trim().trim_start_matches("0x").to_lowercase()
+    )
+}
+
+impl Display for SignInMessage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}{HEADER}", self.domain)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+            writeln!(f)?;
+        }
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {VERSION}")?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", rfc3339(&self.issued_at))?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", rfc3339(expiration_time))?;
+        }
+        Ok(())
+    }
+}
+
+/// Format a time in RFC 3339, to the second.
+fn rfc3339(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+impl std::str::FromStr for SignInMessage {
+    type Err = SignInError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let malformed = |reason: &str| SignInError::Malformed(reason.to_string());
+        let mut lines = text.lines().peekable();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(HEADER))
+            .filter(|domain| !domain.is_empty())
+            .ok_or_else(|| malformed("missing header"))?
+            .to_string();
+        let address = lines
+            .next()
+            .filter(|address| {
+                address.starts_with("0x")
+                    && address.len() == 66
+                    && hex::decode(&address[2..]).is_ok()
+            })
+            .ok_or_else(|| malformed("missing or invalid address"))?;
+        if lines.next() != Some("") {
+            return Err(malformed("expected a blank line after the address"));
+        }
+
+        let statement = match lines.peek() {
+            Some(line) if !line.starts_with("URI: ") => {
+                let statement = line.to_string();
+                lines.next();
+                if lines.next() != Some("") {
+                    return Err(malformed("expected a blank line after the statement"));
+                }
+                Some(statement)
+            }
+            _ => None,
+        };
+
+        let uri = field(&mut lines, "URI")?;
+        if field(&mut lines, "Version")? != VERSION {
+            return Err(malformed("unsupported version"));
+        }
+        let chain_id = field(&mut lines, "Chain ID")?
+            .parse()
+            .map_err(|_| malformed("invalid chain ID"))?;
+        let nonce = field(&mut lines, "Nonce")?;
+        if nonce.is_empty() || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(malformed("invalid nonce"));
+        }
+        let issued_at = parse_time(&field(&mut lines, "Issued At")?)?;
+        let expiration_time = match lines.peek() {
+            Some(line) if line.starts_with("Expiration Time: ") => {
+                Some(parse_time(&field(&mut lines, "Expiration Time")?)?)
+            }
+            _ => None,
+        };
+
+        if lines.next().is_some() {
+            return Err(malformed("unexpected trailing lines"));
+        }
+
+        let message = Self {
+            domain,
+            address: normalize_address(address),
+            statement,
+            uri,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+        };
+
+        // Signatures are checked against the message as formatted here, so it must
+        // be exactly the message that was signed.
+        if message.to_string() != text {
+            return Err(malformed("not in canonical form"));
+        }
+
+        Ok(message)
+    }
+}
+
+/// Return the value of the next line of a message, which must be the given field.
+fn field<'a>(
+    lines: &mut impl Iterator<Item = &'a str>,
+    name: &str,
+) -> Result<String, SignInError> {
+    lines
+        .next()
+        .and_then(|line| line.strip_prefix(name))
+        .and_then(|line| line.strip_prefix(": "))
+        .map(str::to_string)
+        .ok_or_else(|| SignInError::Malformed(format!("missing `{name}`")))
+}
+
+/// Parse an RFC 3339 time.
+fn parse_time(time: &str) -> Result<DateTime<Utc>, SignInError> {
+    DateTime::parse_from_rfc3339(time)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| SignInError::Malformed(format!("invalid time '{time}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_crypto::SecretKey;
+
+    #[test]
+    fn test_sign_in_message_round_trips_and_verifies() {
+        let secret = SecretKey::try_from(&[7u8; 32][..]).unwrap();
+        let address = secret.public_key().hash().to_string();
+        let expiry = Utc::now().timestamp() + 60;
+
+        let message =
+            SignInMessage::new("localhost:29987", "http", &address, 0, "abc", expiry);
+        let text = message.to_string();
+        assert!(SignInMessage::is_sign_in_message(&text));
+
+        let parsed = text.parse::<SignInMessage>().unwrap();
+        assert_eq!(parsed.to_string(), text);
+
+        let signature = Signature::sign(&secret, &Message::new(&text));
+        let signature = hex::encode(<[u8; 64]>::from(signature));
+        assert_eq!(
+            parsed.verify(&signature, "localhost:29987", 0).unwrap(),
+            secret.public_key()
+        );
+        assert_eq!(
+            parsed.verify(&signature, "example.com", 0),
+            Err(SignInError::WrongDomain("localhost:29987".to_string()))
+        );
+        assert_eq!(
+            parsed.verify(&signature, "localhost:29987", 1),
+            Err(SignInError::WrongChain(0))
+        );
+
+        // A message claiming another address fails, even with a valid signature.
+        let mut other = parsed.clone();
+        other.address = format!("0x{}", "00".repeat(32));
+        let signature = Signature::sign(&secret, &Message::new(other.to_string()));
+        let signature = hex::encode(<[u8; 64]>::from(signature));
+        assert!(matches!(
+            other.verify(&signature, "localhost:29987", 0),
+            Err(SignInError::WrongSigner(_))
+        ));
+    }
+
+    #[test]
+    fn test_sign_in_message_rejects_malformed_messages() {
+        assert!(!SignInMessage::is_sign_in_message("2b4c8d6b8c4f4b6f"));
+        assert!(
+            "localhost wants you to sign in with your Fuel account:\n0x12\n"
+                .parse::<SignInMessage>()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Snapshots of an indexer's data, which can be exported from one indexer service
+//! and imported into another, so that a new deployment doesn't have to sync from
+//! scratch.
+//!
+//! Snapshots are plain SQL in the format written by `pg_dump`: a header of
+//! comments describing the snapshot, followed by a `COPY ... FROM stdin;` block
+//! for each of the indexer's tables. They can be restored with `psql` as well as
+//! through the web API.
+
+use std::fmt::Write;
+
+/// Line that starts the header of a snapshot.
+const SNAPSHOT_MAGIC: &str = "-- Fuel indexer snapshot";
+
+/// Line that ends the data of a `COPY` block.
+const COPY_END: &str = "\\.";
+
+/// Description of a snapshot.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SnapshotHeader {
+    /// Namespace of the indexer.
+    pub namespace: String,
+
+    /// Identifier of the indexer.
+    pub identifier: String,
+
+    /// Version of the indexer's schema, which the importing indexer must match.
+    pub schema_version: String,
+
+    /// Height of the indexer's latest checkpoint, if any.
+    pub block_height: Option<u64>,
+
+    /// Serialized executor state of the indexer's latest checkpoint, if any.
+    pub checkpoint: Option<Vec<u8>>,
+}
+
+/// A snapshot of an indexer's data.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Snapshot {
+    /// Description of the snapshot.
+    pub header: SnapshotHeader,
+
+    /// Rows of each of the indexer's tables, in the text format of `COPY`.
+    pub tables: Vec<(String, Vec<u8>)>,
+}
+
+impl Snapshot {
+    /// Write the snapshot as SQL.
+    pub(crate) fn to_sql(&self) -> Vec<u8> {
+        let SnapshotHeader {
+            namespace,
+            identifier,
+            schema_version,
+            block_height,
+            checkpoint,
+        } = &self.header;
+
+        let mut header = format!(
+            "{SNAPSHOT_MAGIC}\n--\n-- namespace: {namespace}\n-- identifier: {identifier}\n-- schema_version: {schema_version}\n"
+        );
+        if let Some(block_height) = block_height {
+            let _ = writeln!(header, "-- block_height: {block_height}");
+        }
+        if let Some(checkpoint) = checkpoint {
+            let _ = writeln!(header, "-- checkpoint: {}", hex::encode(checkpoint));
+        }
+        header.push_str("--\n\n");
+
+        let mut sql = header.into_bytes();
+        for (table, rows) in self.tables.iter() {
+            sql.extend_from_slice(
+                format!("COPY {namespace}_{identifier}.{table} FROM stdin;\n").as_bytes(),
+            );
+            sql.extend_from_slice(rows);
+            sql.extend_from_slice(format!("{COPY_END}\n\n").as_bytes());
+        }
+
+        sql
+    }
+
+    /// Read a snapshot from SQL written by `Snapshot::to_sql`.
+    pub(crate) fn from_sql(sql: &[u8]) -> Result<Self, String> {
+        let sql = std::str::from_utf8(sql).map_err(|e| e.to_string())?;
+        let mut lines = sql.split_inclusive('\n');
+
+        if lines.next().map(str::trim_end) != Some(SNAPSHOT_MAGIC) {
+            return Err("Not a Fuel indexer snapshot.".to_string());
+        }
+
+        let mut snapshot = Snapshot::default();
+        let mut table: Option<(String, Vec<u8>)> = None;
+
+        for line in lines {
+            if let Some((_, rows)) = table.as_mut() {
+                if line.trim_end() == COPY_END {
+                    snapshot.tables.extend(table.take());
+                } else {
+                    rows.extend_from_slice(line.as_bytes());
+                }
+                continue;
+            }
+
+            let line = line.trim_end();
+            if let Some(field) = line.strip_prefix("-- ") {
+                if let Some((key, value)) = field.split_once(": ") {
+                    let header = &mut snapshot.header;
+                    match key {
+                        "namespace" => header.namespace = value.to_string(),
+                        "identifier" => header.identifier = value.to_string(),
+                        "schema_version" => header.schema_version = value.to_string(),
+                        "block_height" => {
+                            header.block_height =
+                                Some(value.parse().map_err(|_| "Invalid block height.")?)
+                        }
+                        "checkpoint" => {
+                            header.checkpoint = Some(
+                                hex::decode(value).map_err(|_| "Invalid checkpoint.")?,
+                            )
+                        }
+                        _ => {}
+                    }
+                }
+            } else if let Some(copy) = line.strip_prefix("COPY ") {
+                let name = copy
+                    .strip_suffix(" FROM stdin;")
+                    .and_then(|name| name.rsplit_once('.'))
+                    .map(|(_, table)| table.to_string())
+                    .ok_or_else(|| format!("Invalid COPY statement: {line}"))?;
+                table = Some((name, Vec::new()));
+            }
+        }
+
+        if let Some((name, _)) = table {
+            return Err(format!("Data of table '{name}' is incomplete."));
+        }
+
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_sql_round_trip() {
+        let snapshot = Snapshot {
+            header: SnapshotHeader {
+                namespace: "fuel".to_string(),
+                identifier: "explorer".to_string(),
+                schema_version: "abc123".to_string(),
+                block_height: Some(42),
+                checkpoint: Some(vec![1, 2, 3]),
+            },
+            tables: vec![
+                (
+                    "block".to_string(),
+                    b"1\t42\tfoo\\nbar\n2\t43\t\\N\n".to_vec(),
+                ),
+                ("transaction".to_string(), Vec::new()),
+            ],
+        };
+
+        let sql = snapshot.to_sql();
+        assert!(String::from_utf8_lossy(&sql)
+            .contains("COPY fuel_explorer.block FROM stdin;\n1\t42\tfoo\\nbar\n"));
+        assert_eq!(Snapshot::from_sql(&sql).unwrap(), snapshot);
+
+        assert!(Snapshot::from_sql(b"SELECT 1;").is_err());
+    }
+}
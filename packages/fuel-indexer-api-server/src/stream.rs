@@ -0,0 +1,307 @@
+use axum::{
+    body::{Bytes, StreamBody},
+    http::header::CONTENT_TYPE,
+    response::{IntoResponse, Response},
+};
+use fuel_indexer_database::{queries, types::QueryParameter, IndexerConnection};
+use futures_util::{stream, StreamExt};
+use serde_json::{json, Value};
+use std::io::{self, Write};
+use tokio::sync::{
+    mpsc::{self, Sender},
+    oneshot,
+};
+use tracing::debug;
+
+/// Size of the chunks in which JSON responses are sent.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of serialized chunks buffered before serialization waits for the client.
+const CHUNK_BUFFER_SIZE: usize = 4;
+
+/// Build a response containing `{"data": data}`, serialized in chunks as the client
+/// reads them.
+///
+/// Serialized responses are never fully buffered, so at most `CHUNK_BUFFER_SIZE`
+/// chunks of a large result set are held in memory alongside the result itself.
+pub(crate) fn json_data_response(data: Value) -> Response {
+    let (tx, rx) = mpsc::channel::<Bytes>(CHUNK_BUFFER_SIZE);
+
+    tokio::task::spawn_blocking(move || {
+        let mut writer = ChunkWriter::new(tx);
+        if let Err(e) = serde_json::to_writer(&mut writer, &json!({ "data": data }))
+            .map_err(io::Error::from)
+            .and_then(|_| writer.flush())
+        {
+            // The client most likely went away before reading the whole response.
+            debug!("Stopped streaming JSON response: {e}");
+        }
+    });
+
+    let body = StreamBody::new(stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (Ok::<_, io::Error>(chunk), rx))
+    }));
+
+    ([(CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+/// Build a response containing `{"data": [row, ...]}`, where each row is the JSON
+/// value of a row of the given queries, serialized as it's read from the database.
+///
+/// Neither the result set nor the serialized response is held in memory as a whole,
+/// so memory use is bounded by the size of a row and `CHUNK_BUFFER_SIZE` chunks. The
+/// first query is started before the response is returned, so that failing queries
+/// are reported as errors rather than as truncated responses.
+///
+/// Each query is run with its parameters bound to it, one after the other. If
+/// `prepared` is set, queries are cached by the connection as prepared statements.
+///
+/// Once the response has been streamed, or the client went away, `on_complete` is
+/// called with the number of rows counted in the response by `count_rows`.
+pub(crate) async fn json_rows_response(
+    conn: IndexerConnection,
+    statements: Vec<(String, Vec<QueryParameter>)>,
+    prepared: bool,
+    on_complete: impl FnOnce(u64) + Send + 'static,
+) -> sqlx::Result<Response> {
+    let (tx, rx) = mpsc::channel::<io::Result<Bytes>>(CHUNK_BUFFER_SIZE);
+    let (started_tx, started_rx) = oneshot::channel();
+
+    tokio::spawn(stream_json_rows(
+        conn,
+        statements,
+        prepared,
+        started_tx,
+        tx,
+        on_complete,
+    ));
+
+    started_rx
+        .await
+        .unwrap_or(Err(sqlx::Error::WorkerCrashed))?;
+
+    let body = StreamBody::new(stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|chunk| (chunk, rx))
+    }));
+
+    Ok(([(CONTENT_TYPE, "application/json")], body).into_response())
+}
+
+/// Stream the JSON rows of the given queries as a `{"data": [row, ...]}` document.
+async fn stream_json_rows(
+    mut conn: IndexerConnection,
+    statements: Vec<(String, Vec<QueryParameter>)>,
+    prepared: bool,
+    started: oneshot::Sender<sqlx::Result<()>>,
+    tx: mpsc::Sender<io::Result<Bytes>>,
+    on_complete: impl FnOnce(u64),
+) {
+    let mut started = Some(started);
+    let mut writer = JsonRowsWriter::new();
+
+    for (query, params) in &statements {
+        let mut rows = if prepared {
+            queries::fetch_statement_json_rows(&mut conn, query, params)
+        } else {
+            queries::fetch_json_rows(&mut conn, query, params)
+        };
+
+        // A query fails, if at all, when its first row is read.
+        while let Some(row) = rows.next().await {
+            let row = match row {
+                Ok(row) => row,
+                Err(e) => {
+                    if let Some(started) = started.take() {
+                        let _ = started.send(Err(e));
+                        return;
+                    }
+                    let _ = tx.send(Err(io::Error::new(io::ErrorKind::Other, e))).await;
+                    on_complete(writer.count());
+                    return;
+                }
+            };
+            if let Some(started) = started.take() {
+                let _ = started.send(Ok(()));
+            }
+
+            if let Some(chunk) = writer.push(&row) {
+                if tx.send(Ok(chunk)).await.is_err() {
+                    debug!("Stopped streaming JSON response: response dropped.");
+                    on_complete(writer.count());
+                    return;
+                }
+            }
+        }
+    }
+
+    if let Some(started) = started.take() {
+        let _ = started.send(Ok(()));
+    }
+    if tx.send(Ok(writer.finish())).await.is_err() {
+        debug!("Stopped streaming JSON response: response dropped.");
+    }
+
+    on_complete(writer.count());
+}
+
+/// Serializes rows into the chunks of a `{"data": [row, ...]}` document.
+struct JsonRowsWriter {
+    /// Chunk currently being written.
+    buf: Vec<u8>,
+
+    /// Number of rows written.
+    rows: usize,
+
+    /// Number of rows counted in the rows written, by `count_rows`.
+    count: u64,
+}
+
+impl JsonRowsWriter {
+    fn new() -> Self {
+        let mut buf = Vec::with_capacity(CHUNK_SIZE);
+        buf.extend_from_slice(b"{\"data\":[");
+        Self {
+            buf,
+            rows: 0,
+            count: 0,
+        }
+    }
+
+    /// Append a row to the document, returning the current chunk if it's full.
+    fn push(&mut self, row: &Value) -> Option<Bytes> {
+        if self.rows > 0 {
+            self.buf.push(b',');
+        }
+        serde_json::to_writer(&mut self.buf, row)
+            .expect("JSON values are always serializable.");
+        self.rows += 1;
+        self.count += count_rows(row);
+
+        if self.buf.len() >= CHUNK_SIZE {
+            let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(CHUNK_SIZE));
+            Some(Bytes::from(chunk))
+        } else {
+            None
+        }
+    }
+
+    /// Close the document, returning its last chunk.
+    fn finish(&mut self) -> Bytes {
+        self.buf.extend_from_slice(b"]}");
+        Bytes::from(std::mem::take(&mut self.buf))
+    }
+
+    fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Number of rows in a GraphQL response, counted as the number of objects nested
+/// in the response, including the objects of lists.
+pub(crate) fn count_rows(value: &Value) -> u64 {
+    let nested = |v: &Value| v.is_object() as u64 + count_rows(v);
+    match value {
+        Value::Array(values) => values.iter().map(nested).sum(),
+        Value::Object(fields) => fields.values().map(nested).sum(),
+        _ => 0,
+    }
+}
+
+/// A writer that sends what is written to it as chunks of `CHUNK_SIZE` bytes.
+struct ChunkWriter {
+    /// Channel to which full chunks are sent.
+    tx: Sender<Bytes>,
+
+    /// Chunk currently being written.
+    buf: Vec<u8>,
+}
+
+impl ChunkWriter {
+    fn new(tx: Sender<Bytes>) -> Self {
+        Self {
+            tx,
+            buf: Vec::with_capacity(CHUNK_SIZE),
+        }
+    }
+
+    /// Send the current chunk, blocking until there is room in the channel.
+    fn send_chunk(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(CHUNK_SIZE));
+        self.tx
+            .blocking_send(Bytes::from(chunk))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Response dropped."))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        if self.buf.len() >= CHUNK_SIZE {
+            self.send_chunk()?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_chunk()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_json_data_response_streams_complete_document() {
+        let data = json!((0..10_000)
+            .map(|i| json!({ "id": i, "name": format!("entity_{i}") }))
+            .collect::<Vec<_>>());
+
+        let response = json_data_response(data.clone());
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let value: Value = serde_json::from_slice(&body).unwrap();
+
+        assert!(body.len() > CHUNK_SIZE);
+        assert_eq!(value, json!({ "data": data }));
+    }
+
+    #[test]
+    fn test_json_rows_writer_writes_complete_document_in_chunks() {
+        let rows = (0..10_000)
+            .map(|i| json!({ "thing": { "id": i, "name": format!("entity_{i}") } }))
+            .collect::<Vec<_>>();
+
+        let mut writer = JsonRowsWriter::new();
+        let mut chunks = rows
+            .iter()
+            .filter_map(|row| writer.push(row))
+            .collect::<Vec<_>>();
+        assert!(!chunks.is_empty());
+        chunks.push(writer.finish());
+
+        let body = chunks.concat();
+        let value: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value, json!({ "data": rows }));
+        assert_eq!(writer.count(), 10_000);
+
+        let mut writer = JsonRowsWriter::new();
+        assert_eq!(&writer.finish()[..], br#"{"data":[]}"#);
+    }
+
+    #[test]
+    fn test_count_rows_counts_objects_of_lists() {
+        let response = json!({
+            "transfers": [
+                { "id": 1, "block": { "height": 1 } },
+                { "id": 2, "block": null },
+            ],
+            "block": { "height": 1, "ids": [1, 2] },
+        });
+        assert_eq!(count_rows(&response), 4);
+        assert_eq!(count_rows(&json!({ "transfers": [] })), 0);
+    }
+}
@@ -0,0 +1,117 @@
+use serde::Serialize;
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
+
+/// Query usage of an indexer by a single client.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(crate) struct QueryUsage {
+    /// UID of the indexer that was queried.
+    pub indexer: String,
+
+    /// ID of the API key the queries were made with, if any.
+    pub api_key: Option<String>,
+
+    /// Subject of the queries' claims, or the client's IP address if the queries
+    /// were unauthenticated.
+    pub client: String,
+
+    /// Number of queries made.
+    pub queries: u64,
+
+    /// Number of rows returned.
+    pub rows: u64,
+
+    /// Time spent executing the queries, in seconds.
+    pub compute_seconds: f64,
+}
+
+/// Accumulates the query usage of each indexer by each client, so that operators
+/// can bill or throttle heavy consumers.
+///
+/// Usage is kept in memory since the service started; the same counts are
+/// exported as metrics, which should be used to track usage across restarts.
+#[derive(Debug, Default)]
+pub(crate) struct QueryUsageTracker {
+    /// Usage keyed by indexer UID, API key and client.
+    usage: Mutex<HashMap<(String, Option<String>, String), QueryUsage>>,
+}
+
+impl QueryUsageTracker {
+    /// Record a query made by the given client to the given indexer.
+    pub(crate) fn record(
+        &self,
+        indexer: &str,
+        api_key: Option<&str>,
+        client: &str,
+        rows: u64,
+        elapsed: Duration,
+    ) {
+        #[cfg(feature = "metrics")]
+        METRICS.web.record_query_usage(
+            indexer,
+            api_key.unwrap_or_default(),
+            rows,
+            elapsed.as_secs_f64(),
+        );
+
+        let key = (
+            indexer.to_string(),
+            api_key.map(str::to_string),
+            client.to_string(),
+        );
+
+        let mut usage = self.usage.lock().expect("Usage tracker lock poisoned.");
+        let entry = usage.entry(key).or_insert_with(|| QueryUsage {
+            indexer: indexer.to_string(),
+            api_key: api_key.map(str::to_string),
+            client: client.to_string(),
+            ..QueryUsage::default()
+        });
+        entry.queries += 1;
+        entry.rows += rows;
+        entry.compute_seconds += elapsed.as_secs_f64();
+    }
+
+    /// Return the usage of the indexers matching the given predicate, sorted by
+    /// indexer and client.
+    pub(crate) fn usage(&self, include: impl Fn(&str) -> bool) -> Vec<QueryUsage> {
+        let mut usage = self
+            .usage
+            .lock()
+            .expect("Usage tracker lock poisoned.")
+            .values()
+            .filter(|usage| include(&usage.indexer))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        usage.sort_by(|a, b| {
+            (&a.indexer, &a.api_key, &a.client).cmp(&(&b.indexer, &b.api_key, &b.client))
+        });
+
+        usage
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_usage_tracker_accumulates_by_indexer_and_client() {
+        let tracker = QueryUsageTracker::default();
+
+        tracker.record("ns.a", Some("key"), "alice", 10, Duration::from_millis(500));
+        tracker.record("ns.a", Some("key"), "alice", 5, Duration::from_millis(250));
+        tracker.record("ns.a", None, "bob", 1, Duration::from_millis(100));
+        tracker.record("ns.b", None, "bob", 1, Duration::from_millis(100));
+
+        let usage = tracker.usage(|indexer| indexer == "ns.a");
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].client, "bob");
+        assert_eq!(usage[1].queries, 2);
+        assert_eq!(usage[1].rows, 15);
+        assert_eq!(usage[1].compute_seconds, 0.75);
+    }
+}
@@ -1,57 +1,119 @@
 use crate::{
     api::{ApiError, ApiResult, HttpError},
-    models::{Claims, SqlQuery, VerifySignatureRequest},
+    export::{export_response, ExportRequest},
+    ipfs::{self, AssetCids},
+    models::{
+        ApiKeyTokenRequest, AuditLogQuery, Claims, CreateApiKeyRequest,
+        ReindexIndexerRequest, ReindexRangeIndexerRequest, ScopeAction,
+        SignInChallengeQuery, SqlQuery, VerifySignatureRequest,
+    },
+    openapi::openapi_document,
+    proto::IndexerProto,
+    rate_limit::QueryRateLimiter,
+    registry::RegistrySchema,
+    signing::deployment_signer,
+    siwf::SignInMessage,
+    snapshot::{Snapshot, SnapshotHeader},
     sql::SqlQueryValidator,
+    stream::{count_rows, json_data_response, json_rows_response},
+    usage::QueryUsageTracker,
 };
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
-use async_graphql_axum::GraphQLRequest;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use async_std::sync::{Arc, RwLock};
 use axum::{
     body::Body,
-    extract::{multipart::Multipart, Extension, Json, Path},
+    extract::{multipart::Multipart, ConnectInfo, Extension, Json, Path, Query},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
 use fuel_crypto::{Message, Signature};
 use fuel_indexer_database::{
     queries,
-    types::{IndexerAsset, IndexerAssetType},
-    IndexerConnectionPool,
+    types::{
+        ApiKey, IndexerAsset, IndexerAssetType, IndexerLifecycleAction, IndexerState,
+        IndexerStatus,
+    },
+    IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_graphql::{
+    dynamic::{build_dynamic_schema, prepare_query, PreparedQuery},
+    graphql::GraphqlError,
+    statements::StatementCache,
 };
-use fuel_indexer_graphql::dynamic::{build_dynamic_schema, execute_query};
 use fuel_indexer_lib::{
     config::{auth::AuthenticationStrategy, IndexerConfig},
-    defaults,
-    graphql::GraphQLSchema,
+    defaults, fully_qualified_namespace,
+    graphql::{query_hash, GraphQLSchema},
+    manifest::Manifest,
     utils::{
-        FuelClientHealthResponse, ReloadRequest, ServiceRequest, ServiceStatus,
-        StopRequest,
+        sha256_digest, FuelClientHealthResponse, ReindexRangeRequest, ReindexRequest,
+        ReloadRequest, ResumeRequest, ServiceRequest, ServiceStatus, StopRequest,
     },
-    ExecutionSource,
+    versioned_identifier, ExecutionSource,
 };
 use fuel_indexer_schema::db::manager::SchemaManager;
 use hyper::Client;
 use hyper_rustls::HttpsConnectorBuilder;
 use jsonwebtoken::{encode, EncodingKey, Header};
 use serde_json::{json, Value};
-use std::{convert::From, str::FromStr, time::Instant};
+use std::{
+    collections::HashMap, convert::From, net::SocketAddr, str::FromStr, time::Instant,
+};
 use tokio::sync::mpsc::Sender;
-use tracing::error;
+use tracing::{error, info_span, Instrument};
 
 #[cfg(feature = "metrics")]
-use fuel_indexer_metrics::encode_metrics_response;
+use fuel_indexer_metrics::{encode_metrics_response, METRICS};
 
 #[cfg(feature = "metrics")]
 use http::Request;
 
+/// Number of audit log entries returned if no limit is given.
+const DEFAULT_AUDIT_LOG_LIMIT: u64 = 100;
+
+/// Maximum number of audit log entries returned at once.
+const MAX_AUDIT_LOG_LIMIT: u64 = 1000;
+
 /// Given an indexer namespace and identifier, return the results for the given
 /// `GraphQLRequest`.
 pub(crate) async fn query_graph(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(pool): Extension<IndexerConnectionPool>,
     Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(claims): Extension<Claims>,
+    Extension(limiter): Extension<Arc<QueryRateLimiter>>,
+    Extension(usage): Extension<Arc<QueryUsageTracker>>,
+    Extension(statements): Extension<Arc<StatementCache>>,
+    Extension(config): Extension<IndexerConfig>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     req: GraphQLRequest,
-) -> ApiResult<axum::Json<Value>> {
+) -> ApiResult<Response> {
+    let identifier = versioned_identifier(&identifier);
+
+    if !claims.allows(ScopeAction::Query, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let client = query_client(&claims, connect_info);
+    let limit_client = rate_limit_client(&claims, connect_info);
+
+    let uid = format!("{namespace}.{identifier}");
+
+    if let Err(limit) = limiter.check(&limit_client, &uid) {
+        return Err(ApiError::Http(HttpError::TooManyRequests(
+            limit.as_str().to_string(),
+        )));
+    }
+
+    // Queries may be routed to another version of the indexer that was promoted.
+    let identifier = {
+        let mut conn = pool.acquire().await?;
+        queries::get_query_route(&mut conn, &namespace, &identifier)
+            .await?
+            .unwrap_or(identifier)
+    };
+
     match manager
         .read()
         .await
@@ -59,13 +121,67 @@ pub(crate) async fn query_graph(
         .await
     {
         Ok(schema) => {
+            let start = Instant::now();
             let dynamic_schema = build_dynamic_schema(&schema)?;
             let user_query = req.0.query.clone();
-            let response =
-                execute_query(req.into_inner(), dynamic_schema, user_query, pool, schema)
-                    .await?;
-            let data = serde_json::json!({ "data": response });
-            Ok(axum::Json(data))
+            let span = info_span!(
+                "graphql_query",
+                indexer = %uid,
+                query_hash = %query_hash(&user_query),
+            );
+            let prepared = prepare_query(
+                req.into_inner(),
+                dynamic_schema,
+                user_query,
+                pool.clone(),
+                schema,
+                &statements,
+                config.web_api.page_info_count,
+            )
+            .instrument(span)
+            .await?;
+
+            // Rows and time are recorded once the whole response has been sent.
+            let api_key = claims.api_key().map(str::to_string);
+            let record_usage = move |rows: u64| {
+                limiter.record_rows(&limit_client, rows);
+                let elapsed = start.elapsed();
+                usage.record(&uid, api_key.as_deref(), &client, rows, elapsed);
+
+                #[cfg(feature = "metrics")]
+                METRICS.web.record_query_duration(
+                    &namespace,
+                    &identifier,
+                    elapsed.as_secs_f64(),
+                );
+            };
+
+            match prepared {
+                PreparedQuery::Resolved(response) => {
+                    record_usage(count_rows(&response));
+                    Ok(json_data_response(response))
+                }
+                PreparedQuery::Queries(queries) => {
+                    let conn = pool.acquire().await?;
+                    let statements = queries
+                        .into_iter()
+                        .map(|query| {
+                            let params = query.parameters();
+                            (query.sql, params)
+                        })
+                        .collect();
+                    json_rows_response(conn, statements, false, record_usage)
+                        .await
+                        .map_err(|e| GraphqlError::QueryError(e.to_string()).into())
+                }
+                PreparedQuery::Statement { name, query } => {
+                    let conn = pool.acquire().await?;
+                    let statement = (query.statement_sql(&name), query.parameters());
+                    json_rows_response(conn, vec![statement], true, record_usage)
+                        .await
+                        .map_err(|e| GraphqlError::QueryError(e.to_string()).into())
+                }
+            }
         }
         Err(_e) => Err(ApiError::Http(HttpError::NotFound(format!(
             "The graph '{namespace}.{identifier}' was not found."
@@ -73,6 +189,38 @@ pub(crate) async fn query_graph(
     }
 }
 
+/// Return the client by which a query's usage is counted.
+///
+/// Authenticated clients are identified by subject, so that an API key's usage
+/// doesn't depend on where it is used from, and others by their IP address.
+pub(crate) fn query_client(
+    claims: &Claims,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> String {
+    if !claims.is_unauthenticated() && !claims.sub().is_empty() {
+        claims.sub().to_string()
+    } else {
+        connect_info
+            .map(|ConnectInfo(addr)| addr.ip().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Return the client by which a query's rate limits are counted.
+///
+/// Queries made with a token issued for an API key are limited by that key, so
+/// that each of an owner's keys has limits of its own. Other queries are limited
+/// by the client that `query_client` counts their usage by.
+pub(crate) fn rate_limit_client(
+    claims: &Claims,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> String {
+    match claims.api_key() {
+        Some(key_id) => format!("api_key:{key_id}"),
+        None => query_client(claims, connect_info),
+    }
+}
+
 /// Return the `ServiceStatus` for the Fuel client.
 pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
     let https = HttpsConnectorBuilder::new()
@@ -105,7 +253,12 @@ pub(crate) async fn get_fuel_status(config: &IndexerConfig) -> ServiceStatus {
 }
 
 /// Return a JSON payload with the health status of various components, including
-/// the fuel client, the database, and the uptime of the service.
+/// the fuel client, the database, each registered indexer, and the uptime of the
+/// service.
+///
+/// This responds with `200 OK` for as long as the service is up, so that it can be
+/// used as a liveness check. Use `readiness_check` to check whether the service can
+/// serve requests.
 pub(crate) async fn health_check(
     Extension(config): Extension<IndexerConfig>,
     Extension(pool): Extension<IndexerConnectionPool>,
@@ -114,14 +267,120 @@ pub(crate) async fn health_check(
     let db_status = pool.is_connected().await.unwrap_or(ServiceStatus::NotOk);
     let uptime = start_time.elapsed().as_secs().to_string();
     let client_status = get_fuel_status(&config).await;
+    let (connections, idle_connections) = pool.connections();
+
+    let indexers = match indexers_health(&pool).await {
+        Ok(indexers) => indexers,
+        Err(e) => {
+            error!("Failed to fetch the health of indexers: {e:?}.");
+            Value::Null
+        }
+    };
 
     Ok(Json(json!({
         "client_status": client_status,
         "uptime": uptime,
         "database_status": db_status,
+        "database": {
+            "status": db_status,
+            "connections": connections,
+            "idle_connections": idle_connections,
+        },
+        "indexers": indexers,
     })))
 }
 
+/// Return the OpenAPI document of the web API's management routes.
+pub(crate) async fn openapi_spec(
+    Extension(config): Extension<IndexerConfig>,
+) -> axum::Json<Value> {
+    Json(openapi_document(&config))
+}
+
+/// Return `200 OK` if the service is ready to serve requests, meaning that both the
+/// database and the Fuel node are reachable, and `503 Service Unavailable` otherwise.
+pub(crate) async fn readiness_check(
+    Extension(config): Extension<IndexerConfig>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+) -> Response {
+    let db_status = pool.is_connected().await.unwrap_or(ServiceStatus::NotOk);
+    let client_status = get_fuel_status(&config).await;
+
+    let ready = matches!(db_status, ServiceStatus::OK)
+        && matches!(client_status, ServiceStatus::OK);
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(json!({
+            "ready": ready,
+            "client_status": client_status,
+            "database_status": db_status,
+        })),
+    )
+        .into_response()
+}
+
+/// Build the health status of each registered indexer, keyed by its UID.
+///
+/// An indexer is `crash_looping` if it has failed to process the same page of blocks
+/// `CRASH_LOOP_FAILURES` times in a row, `failing` if it has raised an error since it
+/// last made progress, and `lagging` if it's more than `MAX_HEALTHY_BLOCKS_BEHIND` blocks behind the
+/// chain tip. Indexers that haven't reported their progress yet are `starting`.
+async fn indexers_health(pool: &IndexerConnectionPool) -> ApiResult<Value> {
+    let mut conn = pool.acquire().await?;
+
+    let indexers = queries::all_registered_indexers(&mut conn).await?;
+    let statuses = queries::all_indexer_statuses(&mut conn)
+        .await?
+        .into_iter()
+        .map(|status| (status.index_id, status))
+        .collect::<HashMap<_, _>>();
+
+    let mut health = serde_json::Map::new();
+    for indexer in indexers {
+        let state =
+            queries::indexer_state(&mut conn, &indexer.namespace, &indexer.identifier)
+                .await?;
+        let status = statuses.get(&indexer.id);
+
+        health.insert(
+            indexer.uid(),
+            json!({
+                "status": indexer_health(state, status),
+                "blocks_behind": status.map(|status| status.blocks_behind()),
+                "consecutive_failures": status.map(|status| status.consecutive_failures),
+                "last_error": status.and_then(|status| status.last_error.clone()),
+                "last_error_at": status
+                    .and_then(|status| status.last_error_at)
+                    .map(|last_error_at| last_error_at.to_rfc3339()),
+            }),
+        );
+    }
+
+    Ok(Value::Object(health))
+}
+
+/// Health of an indexer, given its state and its last reported status.
+fn indexer_health(state: IndexerState, status: Option<&IndexerStatus>) -> &'static str {
+    match status {
+        _ if state == IndexerState::Paused => "paused",
+        Some(status) if status.is_crash_looping(defaults::CRASH_LOOP_FAILURES) => {
+            "crash_looping"
+        }
+        Some(status) if status.is_failing() => "failing",
+        Some(status) if status.blocks_behind() > defaults::MAX_HEALTHY_BLOCKS_BEHIND => {
+            "lagging"
+        }
+        Some(_) => "healthy",
+        None => "starting",
+    }
+}
+
 /// Return a JSON payload containing the status of a given indexer, or set of indexers.
 pub(crate) async fn indexer_status(
     Extension(pool): Extension<IndexerConnectionPool>,
@@ -145,11 +404,90 @@ pub(crate) async fn indexer_status(
         }
     };
 
-    let json: serde_json::Value = serde_json::to_value(indexers)?;
+    let statuses = queries::all_indexer_statuses(&mut conn)
+        .await?
+        .into_iter()
+        .map(|status| (status.index_id, status))
+        .collect::<HashMap<_, _>>();
+
+    let mut json = Vec::with_capacity(indexers.len());
+    for indexer in indexers {
+        let state =
+            queries::indexer_state(&mut conn, &indexer.namespace, &indexer.identifier)
+                .await?;
+        let status = statuses.get(&indexer.id);
+
+        let mut value = serde_json::to_value(&indexer)?;
+        value["health"] = indexer_health(state, status).into();
+        value["status"] = match status {
+            Some(status) => indexer_progress(status),
+            None => Value::Null,
+        };
+        json.push(value);
+    }
 
     Ok(Json(json!(json)))
 }
 
+/// Return the results of a `GraphQLRequest` against the registry schema, which
+/// describes the indexers registered with the service.
+pub(crate) async fn query_registry(
+    Extension(schema): Extension<RegistrySchema>,
+    Extension(claims): Extension<Claims>,
+    req: GraphQLRequest,
+) -> ApiResult<GraphQLResponse> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    Ok(schema.execute(req.into_inner().data(claims)).await.into())
+}
+
+/// Return a JSON payload containing the sync progress of a given indexer.
+pub(crate) async fn indexer_progress_status(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    match queries::get_indexer_status(&mut conn, &namespace, &identifier).await? {
+        Some(status) => Ok(Json(indexer_progress(&status))),
+        None => Err(ApiError::Http(HttpError::NotFound(format!(
+            "No status reported for Indexer({namespace}.{identifier})."
+        )))),
+    }
+}
+
+/// Build the JSON representation of an indexer's sync progress.
+fn indexer_progress(status: &IndexerStatus) -> Value {
+    json!({
+        "current_height": status.current_height,
+        "chain_tip": status.chain_tip,
+        "blocks_behind": status.blocks_behind(),
+        "blocks_per_second": status.blocks_per_second,
+        "rows_per_minute": status.rows_per_minute,
+        "cpu_seconds": status.cpu_seconds,
+        "eta_seconds": status.eta_seconds(),
+        "updated_at": status.updated_at.to_rfc3339(),
+        "last_error": status.last_error,
+        "last_error_at": status.last_error_at.map(|last_error_at| last_error_at.to_rfc3339()),
+        "failed_height": status.failed_height,
+        "consecutive_failures": status.consecutive_failures,
+    })
+}
+
 /// Given an indexer namespace and identifier, remove the indexer from the database
 /// and send a `ServiceRequest::Stop` to the service for this indexer.
 pub(crate) async fn remove_indexer(
@@ -158,8 +496,12 @@ pub(crate) async fn remove_indexer(
     Extension(pool): Extension<IndexerConnectionPool>,
     Extension(claims): Extension<Claims>,
     Extension(config): Extension<IndexerConfig>,
+    Extension(statements): Extension<Arc<StatementCache>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
 ) -> ApiResult<axum::Json<Value>> {
-    if claims.is_unauthenticated() {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Stop, &namespace) {
         return Err(ApiError::Http(HttpError::Unauthorized));
     }
 
@@ -179,7 +521,34 @@ pub(crate) async fn remove_indexer(
         return Err(ApiError::Sqlx(sqlx::Error::RowNotFound));
     }
 
+    if let Err(e) = queries::record_lifecycle_event(
+        &mut conn,
+        &namespace,
+        &identifier,
+        IndexerLifecycleAction::Remove,
+        None,
+        actor(&claims),
+    )
+    .await
+    {
+        error!("Failed to record removal of Indexer({namespace}.{identifier}): {e}");
+        queries::revert_transaction(&mut conn).await?;
+        return Err(e.into());
+    }
+
     queries::commit_transaction(&mut conn).await?;
+    statements.invalidate(&fully_qualified_namespace(&namespace, &identifier));
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "remove",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
 
     tx.send(ServiceRequest::Stop(StopRequest {
         namespace,
@@ -192,156 +561,1381 @@ pub(crate) async fn remove_indexer(
     })))
 }
 
-/// Given an indexer namespace and identifier, register the indexer in the database, and
-/// send a `ServiceRequest::Reload` to the service for this indexer.
-pub(crate) async fn register_indexer_assets(
+/// Given an indexer namespace and identifier, stop the indexer's executor without
+/// removing its data, and record that the indexer was paused.
+pub(crate) async fn pause_indexer(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(tx): Extension<Sender<ServiceRequest>>,
-    Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
-    Extension(claims): Extension<Claims>,
     Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
     Extension(config): Extension<IndexerConfig>,
-    multipart: Option<Multipart>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
 ) -> ApiResult<axum::Json<Value>> {
-    if claims.is_unauthenticated() {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Stop, &namespace) {
         return Err(ApiError::Http(HttpError::Unauthorized));
     }
 
     let mut conn = pool.acquire().await?;
-    let mut assets: Vec<IndexerAsset> = Vec::new();
-
-    if let Some(mut multipart) = multipart {
-        queries::start_transaction(&mut conn).await?;
-
-        let indexer_exists = queries::get_indexer_id(&mut conn, &namespace, &identifier)
-            .await
-            .is_ok();
-        if indexer_exists {
-            if !config.replace_indexer {
-                error!("Indexer({namespace}.{identifier}) already exists.");
-                queries::revert_transaction(&mut conn).await?;
-                return Err(ApiError::Http(HttpError::Conflict(format!(
-                    "Indexer({namespace}.{identifier}) already exists"
-                ))));
-            } else if let Err(e) =
-                queries::remove_indexer(&mut conn, &namespace, &identifier).await
-            {
-                error!("Failed to remove Indexer({namespace}.{identifier}): {e}");
-                queries::revert_transaction(&mut conn).await?;
-                return Err(e.into());
-            }
-        }
-
-        while let Ok(Some(field)) = multipart.next_field().await {
-            let name = field.name().unwrap_or("").to_string();
-            let data = field.bytes().await.unwrap_or_default();
-
-            match IndexerAssetType::from_str(&name) {
-                Ok(asset_type) => {
-                    match asset_type {
-                        IndexerAssetType::Wasm | IndexerAssetType::Manifest => {
-                            match queries::register_indexer_asset(
-                                &mut conn,
-                                &namespace,
-                                &identifier,
-                                data.to_vec(),
-                                asset_type,
-                                Some(claims.sub()),
-                            )
-                            .await
-                            {
-                                Ok(result) => {
-                                    assets.push(result);
-                                }
-                                Err(e) => {
-                                    let _res =
-                                        queries::revert_transaction(&mut conn).await?;
-                                    return Err(e.into());
-                                }
-                            }
-                        }
-                        IndexerAssetType::Schema => {
-                            match queries::register_indexer_asset(
-                                &mut conn,
-                                &namespace,
-                                &identifier,
-                                data.to_vec(),
-                                IndexerAssetType::Schema,
-                                Some(claims.sub()),
-                            )
-                            .await
-                            {
-                                Ok(result) => {
-                                    let schema = GraphQLSchema::new(
-                                        String::from_utf8_lossy(&data).to_string(),
-                                    );
-                                    match schema_manager
-                                        .write()
-                                        .await
-                                        .new_schema(
-                                            &namespace,
-                                            &identifier,
-                                            schema,
-                                            // Only WASM can be sent over the web.
-                                            ExecutionSource::Wasm,
-                                            &mut conn,
-                                        )
-                                        .await
-                                    {
-                                        Ok(_) => {
-                                            assets.push(result);
-                                        }
-                                        Err(e) => {
-                                            let _res =
-                                                queries::revert_transaction(&mut conn)
-                                                    .await?;
-                                            return Err(e.into());
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    let _res =
-                                        queries::revert_transaction(&mut conn).await?;
-                                    return Err(e.into());
-                                }
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    let _res = queries::revert_transaction(&mut conn).await?;
-                    return Err(e.into());
-                }
-            }
-        }
 
-        queries::commit_transaction(&mut conn).await?;
+    let event = record_lifecycle_action(
+        &mut conn,
+        &config,
+        &claims,
+        &namespace,
+        &identifier,
+        IndexerLifecycleAction::Pause,
+        None,
+    )
+    .await?;
 
-        tx.send(ServiceRequest::Reload(ReloadRequest {
-            namespace,
-            identifier,
-        }))
-        .await?;
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "pause",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
 
-        return Ok(Json(json!({
-            "success": "true",
-            "assets": assets,
-        })));
-    }
+    tx.send(ServiceRequest::Stop(StopRequest {
+        namespace,
+        identifier,
+    }))
+    .await?;
 
-    Err(ApiError::default())
+    Ok(Json(json!(event)))
 }
 
-/// Return a `Nonce` to be used for authentication.
-pub(crate) async fn get_nonce(
+/// Given an indexer namespace and identifier, restart the paused indexer's executor
+/// from its latest checkpoint, and record that the indexer was resumed.
+pub(crate) async fn resume_indexer(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
     Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
 ) -> ApiResult<axum::Json<Value>> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Stop, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
     let mut conn = pool.acquire().await?;
-    let nonce = queries::create_nonce(&mut conn).await?;
-    Ok(Json(json!(nonce)))
-}
 
-/// Given a message and signature, verify the signature and return a JWT token for authentication.
+    let event = record_lifecycle_action(
+        &mut conn,
+        &config,
+        &claims,
+        &namespace,
+        &identifier,
+        IndexerLifecycleAction::Resume,
+        None,
+    )
+    .await?;
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "resume",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
+
+    tx.send(ServiceRequest::Resume(ResumeRequest {
+        namespace,
+        identifier,
+    }))
+    .await?;
+
+    Ok(Json(json!(event)))
+}
+
+/// Given an indexer namespace and identifier, clear the indexer's data and restart
+/// its executor from the requested block height, and record that it was reindexed.
+pub(crate) async fn reindex_indexer(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Json(request): Json<ReindexIndexerRequest>,
+) -> ApiResult<axum::Json<Value>> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    let event = record_lifecycle_action(
+        &mut conn,
+        &config,
+        &claims,
+        &namespace,
+        &identifier,
+        IndexerLifecycleAction::Reindex,
+        Some(request.start_block),
+    )
+    .await?;
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "reindex",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
+
+    tx.send(ServiceRequest::Reindex(ReindexRequest {
+        namespace,
+        identifier,
+        start_block: request.start_block,
+    }))
+    .await?;
+
+    Ok(Json(json!(event)))
+}
+
+/// Given an indexer namespace and identifier, reprocess the requested range of blocks
+/// in place, without clearing the rest of the indexer's data.
+///
+/// The indexer's executor is stopped while the range is reprocessed, and restarted
+/// from its latest checkpoint once it's done.
+pub(crate) async fn reindex_indexer_range(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    Json(request): Json<ReindexRangeIndexerRequest>,
+) -> ApiResult<axum::Json<Value>> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    if request.start_block > request.end_block {
+        error!(
+            "Invalid block range {}..={} for Indexer({namespace}.{identifier}).",
+            request.start_block, request.end_block
+        );
+        return Err(ApiError::Http(HttpError::BadRequest));
+    }
+
+    let mut conn = pool.acquire().await?;
+    check_indexer_access(&mut conn, &config, &claims, &namespace, &identifier).await?;
+
+    // The executor is restarted once the range is reprocessed, which would resume
+    // an indexer that was paused.
+    let state = queries::indexer_state(&mut conn, &namespace, &identifier).await?;
+    if state != IndexerState::Running {
+        return Err(ApiError::Http(HttpError::Conflict(format!(
+            "Indexer({namespace}.{identifier}) must be running to reindex a block range, but it is {}.",
+            state.as_ref()
+        ))));
+    }
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "reindex_range",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
+
+    tx.send(ServiceRequest::ReindexRange(ReindexRangeRequest {
+        namespace,
+        identifier,
+        start_block: request.start_block,
+        end_block: request.end_block,
+    }))
+    .await?;
+
+    Ok(Json(json!({
+        "success": "true",
+        "start_block": request.start_block,
+        "end_block": request.end_block,
+    })))
+}
+
+/// Given an indexer namespace and identifier, return the indexer's current state and
+/// the lifecycle actions that have been taken on it.
+pub(crate) async fn indexer_lifecycle_events(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let state = queries::indexer_state(&mut conn, &namespace, &identifier).await?;
+    let events = queries::lifecycle_events(&mut conn, &namespace, &identifier).await?;
+
+    Ok(Json(json!({
+        "state": state,
+        "events": events,
+    })))
+}
+
+/// Given an indexer namespace, identifier, and version, route the indexer's GraphQL
+/// queries to that version, which is deployed as `<identifier>@<version>`.
+///
+/// The switch is a single write, so queries are served by either the previous or
+/// the promoted version, and never by neither.
+pub(crate) async fn promote_indexer_version(
+    Path((namespace, identifier, version)): Path<(String, String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let target = versioned_identifier(&format!("{identifier}@{version}"));
+
+    let mut conn = pool.acquire().await?;
+
+    if queries::get_indexer_id(&mut conn, &namespace, &target)
+        .await
+        .is_err()
+    {
+        return Err(ApiError::Http(HttpError::NotFound(format!(
+            "Indexer({namespace}.{identifier}@{version}) not found."
+        ))));
+    }
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(&mut conn, &namespace, &target, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    queries::put_query_route(&mut conn, &namespace, &identifier, &target).await?;
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "promote",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
+
+    Ok(Json(json!({
+        "success": "true",
+        "target": target,
+    })))
+}
+
+/// Given an indexer namespace and identifier, return the indexer that serves its
+/// GraphQL queries.
+pub(crate) async fn indexer_query_route(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let target = queries::get_query_route(&mut conn, &namespace, &identifier)
+        .await?
+        .unwrap_or(identifier);
+
+    Ok(Json(json!({ "target": target })))
+}
+
+/// Given an indexer namespace and identifier, remove the route of its GraphQL queries,
+/// so that they're served by the indexer itself again.
+pub(crate) async fn remove_indexer_query_route(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    if !queries::remove_query_route(&mut conn, &namespace, &identifier).await? {
+        return Err(ApiError::Http(HttpError::NotFound(format!(
+            "Indexer({namespace}.{identifier}) has no query route."
+        ))));
+    }
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "remove_route",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
+
+    Ok(Json(json!({
+        "success": "true",
+    })))
+}
+
+/// Check that the given lifecycle action can be taken on the indexer in its current
+/// state, and record it.
+async fn record_lifecycle_action(
+    conn: &mut IndexerConnection,
+    config: &IndexerConfig,
+    claims: &Claims,
+    namespace: &str,
+    identifier: &str,
+    action: IndexerLifecycleAction,
+    block_height: Option<u64>,
+) -> ApiResult<Value> {
+    if queries::get_indexer_id(conn, namespace, identifier)
+        .await
+        .is_err()
+    {
+        return Err(ApiError::Http(HttpError::NotFound(format!(
+            "Indexer({namespace}.{identifier}) not found."
+        ))));
+    }
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(conn, namespace, identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    // An indexer whose latest event is a removal has since been deployed again, and
+    // so is running.
+    let state = match queries::indexer_state(conn, namespace, identifier).await? {
+        IndexerState::Removed => IndexerState::Running,
+        state => state,
+    };
+    let allowed = match action {
+        IndexerLifecycleAction::Pause => state == IndexerState::Running,
+        IndexerLifecycleAction::Resume => state == IndexerState::Paused,
+        IndexerLifecycleAction::Reindex | IndexerLifecycleAction::Remove => true,
+    };
+    if !allowed {
+        return Err(ApiError::Http(HttpError::Conflict(format!(
+            "Indexer({namespace}.{identifier}) can't be {}d while it is {}.",
+            action.as_ref(),
+            state.as_ref()
+        ))));
+    }
+
+    let event = queries::record_lifecycle_event(
+        conn,
+        namespace,
+        identifier,
+        action,
+        block_height,
+        actor(claims),
+    )
+    .await?;
+
+    Ok(json!(event))
+}
+
+/// The subject of the claims, if they're authenticated, used as the actor of
+/// lifecycle events.
+fn actor(claims: &Claims) -> Option<&str> {
+    if claims.is_unauthenticated() {
+        None
+    } else {
+        Some(claims.sub())
+    }
+}
+
+/// Record an action taken on an indexer through the web API in the audit log.
+///
+/// Failures are logged rather than returned, since the action has already been taken.
+async fn audit(
+    pool: &IndexerConnectionPool,
+    claims: &Claims,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    action: &str,
+    namespace: &str,
+    identifier: &str,
+    manifest_digest: Option<&str>,
+) {
+    let source_ip = connect_info.map(|ConnectInfo(addr)| addr.ip().to_string());
+
+    let result = match pool.acquire().await {
+        Ok(mut conn) => {
+            queries::put_audit_log_entry(
+                &mut conn,
+                action,
+                namespace,
+                identifier,
+                actor(claims),
+                source_ip.as_deref(),
+                manifest_digest,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        error!("Failed to record {action} of Indexer({namespace}.{identifier}) in the audit log: {e}");
+    }
+}
+
+/// Return the latest entries of the audit log, newest first.
+///
+/// Users other than administrators only see the entries of their own actions.
+pub(crate) async fn audit_log(
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<AuditLogQuery>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let principal = if claims.sub().is_empty() {
+        None
+    } else {
+        Some(claims.sub())
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_AUDIT_LOG_LIMIT)
+        .min(MAX_AUDIT_LOG_LIMIT);
+
+    let mut conn = pool.acquire().await?;
+    let entries = queries::audit_log_entries(
+        &mut conn,
+        query.namespace.as_deref(),
+        query.identifier.as_deref(),
+        principal,
+        limit,
+    )
+    .await?;
+
+    Ok(Json(json!(entries)))
+}
+
+/// Return the query usage of each indexer by each client since the service started.
+///
+/// Users other than the admin only see the usage of the indexers they own.
+pub(crate) async fn query_usage(
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(usage): Extension<Arc<QueryUsageTracker>>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    if claims.sub().is_empty() {
+        return Ok(Json(json!(usage.usage(|_| true))));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let owned = queries::all_registered_indexers(&mut conn)
+        .await?
+        .into_iter()
+        .filter(|i| i.pubkey.as_deref() == Some(claims.sub()))
+        .map(|i| i.uid())
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!(
+        usage.usage(|indexer| owned.iter().any(|uid| uid == indexer))
+    )))
+}
+
+/// Given an indexer namespace and identifier, register the indexer in the database, and
+/// send a `ServiceRequest::Reload` to the service for this indexer.
+pub(crate) async fn register_indexer_assets(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
+    Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(claims): Extension<Claims>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(config): Extension<IndexerConfig>,
+    Extension(statements): Extension<Arc<StatementCache>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    multipart: Option<Multipart>,
+) -> ApiResult<axum::Json<Value>> {
+    // Versions are deployed as indexers of their own, and their manifests must
+    // name them as such, since their type IDs are derived from the identifier.
+    let versioned = identifier.contains('@');
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let mut assets: Vec<IndexerAsset> = Vec::new();
+
+    if let Some(mut multipart) = multipart {
+        queries::start_transaction(&mut conn).await?;
+
+        let indexer_exists = queries::get_indexer_id(&mut conn, &namespace, &identifier)
+            .await
+            .is_ok();
+        if indexer_exists {
+            if !config.replace_indexer {
+                error!("Indexer({namespace}.{identifier}) already exists.");
+                queries::revert_transaction(&mut conn).await?;
+                return Err(ApiError::Http(HttpError::Conflict(format!(
+                    "Indexer({namespace}.{identifier}) already exists"
+                ))));
+            } else if let Err(e) =
+                queries::remove_indexer(&mut conn, &namespace, &identifier).await
+            {
+                error!("Failed to remove Indexer({namespace}.{identifier}): {e}");
+                queries::revert_transaction(&mut conn).await?;
+                return Err(e.into());
+            }
+        }
+
+        let mut manifest_digest = None;
+        let (mut manifest, mut wasm) = (Vec::new(), Vec::new());
+        let (mut manifest_signature, mut wasm_signature) = (None, None);
+
+        while let Ok(Some(field)) = multipart.next_field().await {
+            let name = field.name().unwrap_or("").to_string();
+            let data = field.bytes().await.unwrap_or_default();
+
+            // Signatures are sent as hex-encoded fields alongside the assets.
+            match name.as_str() {
+                "manifest_signature" => {
+                    manifest_signature = Some(String::from_utf8_lossy(&data).to_string());
+                    continue;
+                }
+                "wasm_signature" => {
+                    wasm_signature = Some(String::from_utf8_lossy(&data).to_string());
+                    continue;
+                }
+                _ => {}
+            }
+
+            match IndexerAssetType::from_str(&name) {
+                Ok(asset_type) => {
+                    match asset_type {
+                        IndexerAssetType::Manifest => manifest = data.to_vec(),
+                        IndexerAssetType::Wasm => wasm = data.to_vec(),
+                        IndexerAssetType::Schema => {}
+                    }
+
+                    if let IndexerAssetType::Manifest = asset_type {
+                        let manifest = match Manifest::try_from(&data.to_vec())
+                            .and_then(|manifest| manifest.validate().map(|_| manifest))
+                        {
+                            Ok(manifest) => manifest,
+                            Err(e) => {
+                                error!("Invalid manifest for Indexer({namespace}.{identifier}): {e}");
+                                queries::revert_transaction(&mut conn).await?;
+                                return Err(ApiError::Http(HttpError::BadRequest));
+                            }
+                        };
+
+                        manifest_digest = Some(sha256_digest(&data));
+
+                        if versioned
+                            && (manifest.namespace() != namespace
+                                || manifest.identifier() != identifier)
+                        {
+                            error!(
+                                "Manifest of Indexer({namespace}.{identifier}) is for Indexer({}).",
+                                manifest.uid()
+                            );
+                            queries::revert_transaction(&mut conn).await?;
+                            return Err(ApiError::Http(HttpError::BadRequest));
+                        }
+                    }
+
+                    match asset_type {
+                        IndexerAssetType::Wasm | IndexerAssetType::Manifest => {
+                            match queries::register_indexer_asset(
+                                &mut conn,
+                                &namespace,
+                                &identifier,
+                                data.to_vec(),
+                                asset_type,
+                                Some(claims.sub()),
+                            )
+                            .await
+                            {
+                                Ok(result) => {
+                                    assets.push(result);
+                                }
+                                Err(e) => {
+                                    let _res =
+                                        queries::revert_transaction(&mut conn).await?;
+                                    return Err(e.into());
+                                }
+                            }
+                        }
+                        IndexerAssetType::Schema => {
+                            match queries::register_indexer_asset(
+                                &mut conn,
+                                &namespace,
+                                &identifier,
+                                data.to_vec(),
+                                IndexerAssetType::Schema,
+                                Some(claims.sub()),
+                            )
+                            .await
+                            {
+                                Ok(result) => {
+                                    let schema = GraphQLSchema::new(
+                                        String::from_utf8_lossy(&data).to_string(),
+                                    );
+                                    match schema_manager
+                                        .write()
+                                        .await
+                                        .new_schema(
+                                            &namespace,
+                                            &identifier,
+                                            schema,
+                                            // Only WASM can be sent over the web.
+                                            ExecutionSource::Wasm,
+                                            &mut conn,
+                                        )
+                                        .await
+                                    {
+                                        Ok(_) => {
+                                            assets.push(result);
+                                        }
+                                        Err(e) => {
+                                            let _res =
+                                                queries::revert_transaction(&mut conn)
+                                                    .await?;
+                                            return Err(e.into());
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    let _res =
+                                        queries::revert_transaction(&mut conn).await?;
+                                    return Err(e.into());
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    let _res = queries::revert_transaction(&mut conn).await?;
+                    return Err(e.into());
+                }
+            }
+        }
+
+        let signer = match (manifest_signature, wasm_signature) {
+            (Some(manifest_signature), Some(wasm_signature)) => {
+                let signer = deployment_signer(
+                    &config.authentication.deployment_signers,
+                    &manifest,
+                    &manifest_signature,
+                    &wasm,
+                    &wasm_signature,
+                );
+                if signer.is_none() {
+                    error!("Invalid deployment signature for Indexer({namespace}.{identifier}).");
+                    queries::revert_transaction(&mut conn).await?;
+                    return Err(ApiError::Http(HttpError::Unauthorized));
+                }
+                signer
+            }
+            _ => None,
+        };
+
+        if signer.is_none() && config.authentication.require_signed_deployments {
+            error!("Deployment of Indexer({namespace}.{identifier}) is not signed.");
+            queries::revert_transaction(&mut conn).await?;
+            return Err(ApiError::Http(HttpError::Unauthorized));
+        }
+
+        let version = match record_deployment(
+            &mut conn,
+            &namespace,
+            &identifier,
+            None,
+            signer.as_deref(),
+        )
+        .await
+        {
+            Ok(version) => version,
+            Err(e) => {
+                queries::revert_transaction(&mut conn).await?;
+                return Err(e);
+            }
+        };
+
+        // Assets are published before the deployment is committed, so that every
+        // deployment of a service with IPFS enabled has CIDs.
+        let cids = match &config.ipfs_api_url {
+            Some(api_url) => {
+                match publish_deployment(
+                    &mut conn,
+                    api_url,
+                    &namespace,
+                    &identifier,
+                    version,
+                )
+                .await
+                {
+                    Ok(cids) => Some(cids),
+                    Err(e) => {
+                        error!("Failed to publish Indexer({namespace}.{identifier}) to IPFS: {e}");
+                        queries::revert_transaction(&mut conn).await?;
+                        return Err(e);
+                    }
+                }
+            }
+            None => None,
+        };
+
+        queries::commit_transaction(&mut conn).await?;
+
+        // Replaced indexers keep their schema's version, so their shapes aren't
+        // renamed unless they're invalidated.
+        statements.invalidate(&fully_qualified_namespace(&namespace, &identifier));
+
+        audit(
+            &pool,
+            &claims,
+            connect_info,
+            "deploy",
+            &namespace,
+            &identifier,
+            manifest_digest.as_deref(),
+        )
+        .await;
+
+        tx.send(ServiceRequest::Reload(ReloadRequest {
+            namespace,
+            identifier,
+        }))
+        .await?;
+
+        return Ok(Json(json!({
+            "success": "true",
+            "assets": assets,
+            "version": version,
+            "signer": signer,
+            "cids": cids,
+        })));
+    }
+
+    Err(ApiError::default())
+}
+
+/// Record the latest assets of an indexer as a new deployment, returning its version.
+async fn record_deployment(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    rollback_of: Option<i32>,
+    signer: Option<&str>,
+) -> ApiResult<i32> {
+    let index_id = queries::get_indexer_id(conn, namespace, identifier).await?;
+    let assets = queries::latest_assets_for_indexer(conn, &index_id).await?;
+    let version = queries::record_deployment(
+        conn,
+        namespace,
+        identifier,
+        &assets,
+        rollback_of,
+        signer,
+    )
+    .await?;
+    Ok(version)
+}
+
+/// Add and pin the latest assets of an indexer to IPFS, and record their CIDs with
+/// the given deployment.
+async fn publish_deployment(
+    conn: &mut IndexerConnection,
+    api_url: &str,
+    namespace: &str,
+    identifier: &str,
+    version: i32,
+) -> ApiResult<AssetCids> {
+    let index_id = queries::get_indexer_id(conn, namespace, identifier).await?;
+    let assets = queries::latest_assets_for_indexer(conn, &index_id).await?;
+    let cids = ipfs::publish_assets(api_url, namespace, identifier, &assets)
+        .await
+        .map_err(ApiError::Ipfs)?;
+    queries::set_deployment_cids(
+        conn,
+        namespace,
+        identifier,
+        version,
+        &cids.manifest,
+        &cids.schema,
+        &cids.wasm,
+    )
+    .await?;
+    Ok(cids)
+}
+
+/// Replace an indexer with the given assets of one of its deployments.
+async fn replace_with_deployment(
+    conn: &mut IndexerConnection,
+    schema_manager: &RwLock<SchemaManager>,
+    namespace: &str,
+    identifier: &str,
+    assets: Vec<(IndexerAssetType, Vec<u8>)>,
+    pubkey: &str,
+) -> ApiResult<()> {
+    if queries::get_indexer_id(conn, namespace, identifier)
+        .await
+        .is_ok()
+    {
+        queries::remove_indexer(conn, namespace, identifier).await?;
+    }
+
+    for (asset_type, bytes) in assets {
+        if let IndexerAssetType::Schema = asset_type {
+            let schema = GraphQLSchema::new(String::from_utf8_lossy(&bytes).to_string());
+            schema_manager
+                .write()
+                .await
+                .new_schema(namespace, identifier, schema, ExecutionSource::Wasm, conn)
+                .await?;
+        }
+
+        queries::register_indexer_asset(
+            conn,
+            namespace,
+            identifier,
+            bytes,
+            asset_type,
+            Some(pubkey),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Plan the deployment of a new version of an indexer's schema, returning the
+/// changes it makes to the indexer's tables and the SQL it would execute.
+///
+/// The schema is sent as the `schema` field of a multipart form, as when deploying.
+/// Nothing is written to the database.
+pub(crate) async fn plan_indexer_migration(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(claims): Extension<Claims>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    multipart: Option<Multipart>,
+) -> ApiResult<axum::Json<Value>> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut schema = None;
+    if let Some(mut multipart) = multipart {
+        while let Ok(Some(field)) = multipart.next_field().await {
+            if field.name() == Some(IndexerAssetType::Schema.as_ref()) {
+                let data = field.bytes().await.unwrap_or_default();
+                schema = Some(String::from_utf8_lossy(&data).to_string());
+            }
+        }
+    }
+
+    let schema = schema.ok_or(ApiError::Http(HttpError::BadRequest))?;
+
+    let mut conn = pool.acquire().await?;
+    let plan = schema_manager
+        .read()
+        .await
+        .plan_schema(
+            &namespace,
+            &identifier,
+            GraphQLSchema::new(schema),
+            // Only WASM can be sent over the web.
+            ExecutionSource::Wasm,
+            &mut conn,
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "success": "true",
+        "plan": plan,
+    })))
+}
+
+/// Given an indexer namespace and identifier, export the indexer's data and latest
+/// checkpoint as a snapshot that can be imported by another indexer service.
+pub(crate) async fn export_indexer_snapshot(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> ApiResult<Response> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    check_indexer_access(&mut conn, &config, &claims, &namespace, &identifier).await?;
+
+    // Read the tables and the checkpoint as of a single point in time, so that
+    // the snapshot is consistent while the indexer keeps running.
+    queries::execute_query(
+        &mut conn,
+        "BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY".to_string(),
+    )
+    .await?;
+
+    let snapshot = async {
+        let schema_version =
+            queries::graph_root_latest(&mut conn, &namespace, &identifier)
+                .await?
+                .version;
+        let checkpoint =
+            queries::get_checkpoint(&mut conn, &namespace, &identifier).await?;
+
+        let mut tables = Vec::new();
+        for table in queries::indexer_tables(&mut conn, &namespace, &identifier).await? {
+            let rows =
+                queries::copy_table_out(&mut conn, &namespace, &identifier, &table)
+                    .await?;
+            tables.push((table, rows));
+        }
+
+        Ok::<_, sqlx::Error>(Snapshot {
+            header: SnapshotHeader {
+                namespace: namespace.clone(),
+                identifier: identifier.clone(),
+                schema_version,
+                block_height: checkpoint.as_ref().map(|c| c.block_height),
+                checkpoint: checkpoint.map(|c| c.snapshot),
+            },
+            tables,
+        })
+    }
+    .await;
+
+    queries::commit_transaction(&mut conn).await?;
+    let snapshot = snapshot?;
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "export_snapshot",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
+
+    let filename = format!(
+        "{namespace}_{identifier}_{}.sql",
+        snapshot.header.schema_version
+    );
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(http::header::CONTENT_TYPE, "application/sql")
+        .header(
+            http::header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{filename}\""),
+        )
+        .body(Body::from(snapshot.to_sql()))?
+        .into_response())
+}
+
+/// Given an indexer namespace and identifier, replace the paused indexer's data
+/// and latest checkpoint with those of a snapshot exported by `export_indexer_snapshot`.
+///
+/// The snapshot must have been exported from the same version of the indexer's
+/// schema. The indexer stays paused, and continues from the snapshot once resumed.
+pub(crate) async fn import_indexer_snapshot(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    body: axum::body::Bytes,
+) -> ApiResult<axum::Json<Value>> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let snapshot = Snapshot::from_sql(&body).map_err(|e| {
+        error!("Invalid snapshot for Indexer({namespace}.{identifier}): {e}");
+        ApiError::Http(HttpError::BadRequest)
+    })?;
+
+    if snapshot.header.namespace != namespace || snapshot.header.identifier != identifier
+    {
+        error!(
+            "Snapshot of Indexer({}.{}) can't be imported into Indexer({namespace}.{identifier}).",
+            snapshot.header.namespace, snapshot.header.identifier
+        );
+        return Err(ApiError::Http(HttpError::BadRequest));
+    }
+
+    let mut conn = pool.acquire().await?;
+    check_indexer_access(&mut conn, &config, &claims, &namespace, &identifier).await?;
+
+    let state = queries::indexer_state(&mut conn, &namespace, &identifier).await?;
+    if state != IndexerState::Paused {
+        return Err(ApiError::Http(HttpError::Conflict(format!(
+            "Indexer({namespace}.{identifier}) must be paused to import a snapshot, but it is {}.",
+            state.as_ref()
+        ))));
+    }
+
+    let schema_version = queries::graph_root_latest(&mut conn, &namespace, &identifier)
+        .await?
+        .version;
+    if snapshot.header.schema_version != schema_version {
+        return Err(ApiError::Http(HttpError::Conflict(format!(
+            "Snapshot is of schema version {}, but Indexer({namespace}.{identifier}) has schema version {schema_version}.",
+            snapshot.header.schema_version
+        ))));
+    }
+
+    let tables = queries::indexer_tables(&mut conn, &namespace, &identifier).await?;
+    if let Some((table, _)) = snapshot
+        .tables
+        .iter()
+        .find(|(table, _)| !tables.contains(table))
+    {
+        error!(
+            "Snapshot of Indexer({namespace}.{identifier}) has unknown table '{table}'."
+        );
+        return Err(ApiError::Http(HttpError::BadRequest));
+    }
+
+    queries::start_transaction(&mut conn).await?;
+
+    let block_height = snapshot.header.block_height;
+    let result = async {
+        if !tables.is_empty() {
+            let tables = tables
+                .iter()
+                .map(|table| format!("{namespace}_{identifier}.{table}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            queries::execute_query(&mut conn, format!("TRUNCATE {tables}")).await?;
+        }
+
+        let mut rows = 0;
+        for (table, data) in snapshot.tables {
+            rows +=
+                queries::copy_table_in(&mut conn, &namespace, &identifier, &table, data)
+                    .await?;
+        }
+
+        if let (Some(block_height), Some(checkpoint)) =
+            (block_height, snapshot.header.checkpoint)
+        {
+            queries::put_checkpoint(
+                &mut conn,
+                &namespace,
+                &identifier,
+                block_height,
+                checkpoint,
+            )
+            .await?;
+        }
+
+        Ok::<_, sqlx::Error>(rows)
+    }
+    .await;
+
+    let rows = match result {
+        Ok(rows) => {
+            queries::commit_transaction(&mut conn).await?;
+            rows
+        }
+        Err(e) => {
+            error!("Failed to import snapshot of Indexer({namespace}.{identifier}): {e}");
+            queries::revert_transaction(&mut conn).await?;
+            return Err(e.into());
+        }
+    };
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "import_snapshot",
+        &namespace,
+        &identifier,
+        None,
+    )
+    .await;
+
+    Ok(Json(json!({
+        "success": "true",
+        "rows": rows,
+        "block_height": block_height,
+    })))
+}
+
+/// Check that the given indexer exists and, if authentication is enabled, that it
+/// is owned by the subject of the given claims.
+async fn check_indexer_access(
+    conn: &mut IndexerConnection,
+    config: &IndexerConfig,
+    claims: &Claims,
+    namespace: &str,
+    identifier: &str,
+) -> ApiResult<()> {
+    if queries::get_indexer_id(conn, namespace, identifier)
+        .await
+        .is_err()
+    {
+        return Err(ApiError::Http(HttpError::NotFound(format!(
+            "Indexer({namespace}.{identifier}) not found."
+        ))));
+    }
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(conn, namespace, identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    Ok(())
+}
+
+/// Given an indexer namespace and identifier, return the indexer's deployed versions.
+pub(crate) async fn indexer_versions(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let deployments =
+        queries::indexer_deployments(&mut conn, &namespace, &identifier).await?;
+
+    Ok(Json(json!(deployments)))
+}
+
+/// Given an indexer namespace, identifier, and deployment version, replace the indexer
+/// with the assets of that deployment, and send a `ServiceRequest::Reload` to the
+/// service for this indexer.
+///
+/// The rollback is itself recorded as a new deployment.
+pub(crate) async fn rollback_indexer(
+    Path((namespace, identifier, version)): Path<(String, String, i32)>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
+    Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(claims): Extension<Claims>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(config): Extension<IndexerConfig>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+) -> ApiResult<axum::Json<Value>> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Deploy, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    let assets = queries::deployment_assets(&mut conn, &namespace, &identifier, version)
+        .await?
+        .ok_or_else(|| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "Indexer({namespace}.{identifier}) has no deployment {version}"
+            )))
+        })?;
+
+    // Rollbacks keep the signer of the deployment they roll back to, so unsigned
+    // deployments can't be brought back once signatures are required.
+    let signer = queries::indexer_deployments(&mut conn, &namespace, &identifier)
+        .await?
+        .into_iter()
+        .find(|deployment| deployment.version == version)
+        .and_then(|deployment| deployment.signer);
+    if config.authentication.require_signed_deployments && signer.is_none() {
+        error!(
+            "Deployment {version} of Indexer({namespace}.{identifier}) is not signed."
+        );
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let manifest_digest = assets
+        .iter()
+        .find(|(asset_type, _)| *asset_type == IndexerAssetType::Manifest)
+        .map(|(_, bytes)| sha256_digest(bytes))
+        .unwrap_or_default();
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    queries::start_transaction(&mut conn).await?;
+
+    let res = match replace_with_deployment(
+        &mut conn,
+        &schema_manager,
+        &namespace,
+        &identifier,
+        assets,
+        claims.sub(),
+    )
+    .await
+    {
+        Ok(()) => {
+            record_deployment(
+                &mut conn,
+                &namespace,
+                &identifier,
+                Some(version),
+                signer.as_deref(),
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+
+    let deployment = match res {
+        Ok(deployment) => deployment,
+        Err(e) => {
+            error!("Failed to roll back Indexer({namespace}.{identifier}) to deployment {version}: {e}");
+            queries::revert_transaction(&mut conn).await?;
+            return Err(e);
+        }
+    };
+
+    queries::commit_transaction(&mut conn).await?;
+
+    audit(
+        &pool,
+        &claims,
+        connect_info,
+        "rollback",
+        &namespace,
+        &identifier,
+        Some(&manifest_digest),
+    )
+    .await;
+
+    tx.send(ServiceRequest::Reload(ReloadRequest {
+        namespace,
+        identifier,
+    }))
+    .await?;
+
+    Ok(Json(json!({
+        "success": "true",
+        "version": deployment,
+        "rollback_of": version,
+    })))
+}
+
+/// Return a `Nonce` to be used for authentication.
+pub(crate) async fn get_nonce(
+    Extension(pool): Extension<IndexerConnectionPool>,
+) -> ApiResult<axum::Json<Value>> {
+    let mut conn = pool.acquire().await?;
+    let nonce = queries::create_nonce(&mut conn).await?;
+    Ok(Json(json!(nonce)))
+}
+
+/// Return a Sign-In with Fuel message for the given address to sign, with a new nonce.
+///
+/// The message names the configured `siwf_domain`, rather than the host the request
+/// was sent to, so that signatures obtained by another site can't be replayed here.
+pub(crate) async fn get_sign_in_challenge(
+    Extension(config): Extension<IndexerConfig>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Query(query): Query<SignInChallengeQuery>,
+) -> ApiResult<axum::Json<Value>> {
+    let domain = siwf_domain(&config)?;
+    let address = query.address.trim_start_matches("0x");
+    if address.len() != 64 || hex::decode(address).is_err() {
+        return Err(ApiError::Http(HttpError::BadRequest));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let nonce = queries::create_nonce(&mut conn).await?;
+    let scheme = if config.web_api.tls_cert.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    let message = SignInMessage::new(
+        domain,
+        scheme,
+        address,
+        config.chain_id,
+        &nonce.uid,
+        nonce.expiry,
+    );
+
+    Ok(Json(json!({
+        "message": message.to_string(),
+        "nonce": nonce.uid,
+        "expiry": nonce.expiry,
+    })))
+}
+
+/// Return the domain that Sign-In with Fuel messages must name, if it's enabled.
+fn siwf_domain(config: &IndexerConfig) -> ApiResult<&str> {
+    config.authentication.siwf_domain.as_deref().ok_or_else(|| {
+        ApiError::Http(HttpError::NotFound(
+            "Sign-In with Fuel is not enabled.".to_string(),
+        ))
+    })
+}
+
+/// Given a message and signature, verify the signature and return a JWT token for authentication.
+///
+/// The message is either a nonce, or a Sign-In with Fuel message holding a nonce,
+/// which must name the configured `siwf_domain` and be signed by the address it names.
+/// The nonce is consumed before the token is issued, so that it's only redeemed once.
 pub(crate) async fn verify_signature(
     Extension(config): Extension<IndexerConfig>,
     Extension(pool): Extension<IndexerConnectionPool>,
@@ -351,46 +1945,51 @@ pub(crate) async fn verify_signature(
         let mut conn = pool.acquire().await?;
         match config.authentication.strategy {
             Some(AuthenticationStrategy::JWT) => {
-                let nonce = queries::get_nonce(&mut conn, &payload.message).await?;
+                let (nonce, pk) =
+                    if SignInMessage::is_sign_in_message(&payload.message) {
+                        let domain = siwf_domain(&config)?;
+                        let sign_in = payload.message.parse::<SignInMessage>().and_then(
+                            |sign_in| {
+                                sign_in
+                                    .verify(&payload.signature, domain, config.chain_id)
+                                    .map(|pk| (sign_in.nonce, pk))
+                            },
+                        );
+                        match sign_in {
+                            Ok(sign_in) => sign_in,
+                            Err(e) => {
+                                error!("Failed to verify sign-in message: {e}");
+                                return Err(ApiError::Http(HttpError::Unauthorized));
+                            }
+                        }
+                    } else {
+                        let buff: [u8; 64] = hex::decode(&payload.signature)?
+                            .try_into()
+                            .unwrap_or([0u8; 64]);
+                        let sig = Signature::from_bytes(buff);
+                        let msg = Message::new(&payload.message);
+                        let pk = sig.recover(&msg)?;
+                        if let Err(e) = sig.verify(&pk, &msg) {
+                            error!("Failed to verify signature: {e}.");
+                            return Err(ApiError::FuelCrypto(e));
+                        }
+                        (payload.message, pk)
+                    };
 
-                if nonce.is_expired() {
+                if queries::consume_nonce(&mut conn, &nonce).await?.is_none() {
                     return Err(ApiError::Http(HttpError::Unauthorized));
                 }
 
-                let buff: [u8; 64] = hex::decode(&payload.signature)?
-                    .try_into()
-                    .unwrap_or([0u8; 64]);
-                let sig = Signature::from_bytes(buff);
-                let msg = Message::new(payload.message);
-                let pk = sig.recover(&msg)?;
-
                 let claims = Claims::new(
                     pk.to_string(),
-                    config.authentication.jwt_issuer.unwrap_or_default(),
+                    config.authentication.jwt_issuer.clone().unwrap_or_default(),
                     config
                         .authentication
                         .jwt_expiry
                         .unwrap_or(defaults::JWT_EXPIRY_SECS),
                 );
 
-                if let Err(e) = sig.verify(&pk, &msg) {
-                    error!("Failed to verify signature: {e}.");
-                    return Err(ApiError::FuelCrypto(e));
-                }
-
-                let token = encode(
-                    &Header::default(),
-                    &claims,
-                    &EncodingKey::from_secret(
-                        config
-                            .authentication
-                            .jwt_secret
-                            .unwrap_or_default()
-                            .as_ref(),
-                    ),
-                )?;
-
-                queries::delete_nonce(&mut conn, &nonce).await?;
+                let token = encode_claims(&config, &claims)?;
 
                 return Ok(Json(json!({ "token": token })));
             }
@@ -403,6 +2002,186 @@ pub(crate) async fn verify_signature(
     unreachable!();
 }
 
+/// Encode the given claims as a JWT, signed with the configured secret.
+fn encode_claims(config: &IndexerConfig, claims: &Claims) -> ApiResult<String> {
+    let secret = config.authentication.jwt_secret.clone().unwrap_or_default();
+    let token = encode(
+        &Header::default(),
+        claims,
+        &EncodingKey::from_secret(secret.as_ref()),
+    )?;
+    Ok(token)
+}
+
+/// Create an API key owned by the authenticated user, returning the full key.
+///
+/// The key can't grant broader scopes than the token used to create it.
+pub(crate) async fn create_api_key(
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let CreateApiKeyRequest { name, scopes } = payload;
+    let scopes = if scopes.is_empty() {
+        claims.scopes().to_vec()
+    } else {
+        scopes
+    };
+
+    if !claims.can_grant(&scopes) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let (api_key, key) =
+        queries::create_api_key(&mut conn, &name, claims.sub(), &scopes).await?;
+
+    Ok(Json(json!({
+        "key": key,
+        "api_key": api_key,
+    })))
+}
+
+/// Return the API keys owned by the authenticated user.
+pub(crate) async fn list_api_keys(
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let keys = queries::api_keys(&mut conn, Some(claims.sub())).await?;
+
+    Ok(Json(json!(keys)))
+}
+
+/// Return the API key with the given ID, if it is owned by the authenticated user.
+async fn owned_api_key(
+    conn: &mut IndexerConnection,
+    claims: &Claims,
+    key_id: &str,
+) -> ApiResult<ApiKey> {
+    match queries::get_api_key(conn, key_id).await? {
+        Some(api_key) if api_key.owner == claims.sub() => Ok(api_key),
+        _ => Err(ApiError::Http(HttpError::NotFound(format!(
+            "API key {key_id} was not found."
+        )))),
+    }
+}
+
+/// Revoke the given API key, which must be owned by the authenticated user.
+///
+/// Tokens minted from the key are rejected once it is revoked.
+pub(crate) async fn revoke_api_key(
+    Path(key_id): Path<String>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    owned_api_key(&mut conn, &claims, &key_id).await?;
+    let revoked = queries::revoke_api_key(&mut conn, &key_id).await?;
+
+    Ok(Json(json!({
+        "success": "true",
+        "revoked": revoked,
+    })))
+}
+
+/// Replace the given API key with a new key with the same name and scopes, and
+/// revoke the old key, along with the tokens minted from it.
+pub(crate) async fn rotate_api_key(
+    Path(key_id): Path<String>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let old_key = owned_api_key(&mut conn, &claims, &key_id).await?;
+
+    if old_key.revoked {
+        return Err(ApiError::Http(HttpError::Conflict(format!(
+            "API key {key_id} has been revoked"
+        ))));
+    }
+
+    queries::start_transaction(&mut conn).await?;
+
+    let res = match queries::create_api_key(
+        &mut conn,
+        &old_key.name,
+        &old_key.owner,
+        &old_key.scopes,
+    )
+    .await
+    {
+        Ok(created) => queries::revoke_api_key(&mut conn, &key_id)
+            .await
+            .map(|_| created),
+        Err(e) => Err(e),
+    };
+
+    let (api_key, key) = match res {
+        Ok(created) => created,
+        Err(e) => {
+            error!("Failed to rotate API key {key_id}: {e}");
+            queries::revert_transaction(&mut conn).await?;
+            return Err(e.into());
+        }
+    };
+
+    queries::commit_transaction(&mut conn).await?;
+
+    Ok(Json(json!({
+        "key": key,
+        "api_key": api_key,
+        "rotated": key_id,
+    })))
+}
+
+/// Exchange an API key for a JWT with the key's owner and scopes.
+pub(crate) async fn api_key_token(
+    Extension(config): Extension<IndexerConfig>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Json(payload): Json<ApiKeyTokenRequest>,
+) -> ApiResult<axum::Json<Value>> {
+    if !config.authentication.enabled {
+        return Err(ApiError::Http(HttpError::BadRequest));
+    }
+
+    let mut conn = pool.acquire().await?;
+    let api_key = queries::verify_api_key(&mut conn, &payload.key)
+        .await?
+        .ok_or(ApiError::Http(HttpError::Unauthorized))?;
+
+    let claims = Claims::new(
+        api_key.owner,
+        config.authentication.jwt_issuer.clone().unwrap_or_default(),
+        config
+            .authentication
+            .jwt_expiry
+            .unwrap_or(defaults::JWT_EXPIRY_SECS),
+    )
+    .with_scopes(api_key.scopes)
+    .with_api_key(api_key.id);
+
+    let token = encode_claims(&config, &claims)?;
+
+    Ok(Json(json!({ "token": token })))
+}
+
 /// Endpoint for the GraphQL playground.
 ///
 /// This is route just produces/creates the GraphQL playground, the actual queries
@@ -423,6 +2202,40 @@ pub async fn graphql_playground(
     Ok(response)
 }
 
+/// Return the Protobuf definitions of an indexer's gRPC API, from which clients
+/// can generate their stubs.
+pub(crate) async fn indexer_proto(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+) -> ApiResult<Response> {
+    let routed = {
+        let mut conn = pool.acquire().await?;
+        queries::get_query_route(&mut conn, &namespace, &identifier)
+            .await?
+            .unwrap_or_else(|| identifier.clone())
+    };
+
+    let schema = manager
+        .read()
+        .await
+        .load_schema(&namespace, &routed)
+        .await
+        .map_err(|_| {
+            ApiError::Http(HttpError::NotFound(format!(
+                "The graph '{namespace}.{routed}' was not found."
+            )))
+        })?;
+
+    let proto = IndexerProto::new(&namespace, &identifier, &schema);
+
+    Ok((
+        [(http::header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+        proto.to_string(),
+    )
+        .into_response())
+}
+
 /// Return a response containing various Prometheus metrics for the service.
 #[cfg(feature = "metrics")]
 pub async fn get_metrics(_req: Request<Body>) -> impl IntoResponse {
@@ -431,17 +2244,96 @@ pub async fn get_metrics(_req: Request<Body>) -> impl IntoResponse {
 
 /// Return the results from a validated, arbitrary SQL query.
 pub async fn sql_query(
-    Path((_namespace, _identifier)): Path<(String, String)>,
+    Path((namespace, _identifier)): Path<(String, String)>,
     Extension(claims): Extension<Claims>,
     Extension(pool): Extension<IndexerConnectionPool>,
     Json(query): Json<SqlQuery>,
-) -> ApiResult<axum::Json<Value>> {
-    if claims.is_unauthenticated() {
+) -> ApiResult<Response> {
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Query, &namespace) {
         return Err(ApiError::Http(HttpError::Unauthorized));
     }
     let SqlQuery { query } = query;
     SqlQueryValidator::validate_sql_query(&query)?;
     let mut conn = pool.acquire().await?;
     let result = queries::run_query(&mut conn, query).await?;
-    Ok(Json(json!({ "data": result })))
+    Ok(json_data_response(result))
+}
+
+/// Given an indexer namespace, identifier, and entity, stream the entity's rows
+/// as CSV or Parquet, optionally filtered and projected onto some of its columns.
+pub(crate) async fn export_entity(
+    Path((namespace, identifier, entity)): Path<(String, String, String)>,
+    Query(params): Query<Vec<(String, String)>>,
+    Extension(claims): Extension<Claims>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+) -> ApiResult<Response> {
+    let identifier = versioned_identifier(&identifier);
+
+    if claims.is_unauthenticated() || !claims.allows(ScopeAction::Query, &namespace) {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let request = ExportRequest::from_params(&params)?;
+    let entity = entity.to_lowercase();
+
+    let mut conn = pool.acquire().await?;
+
+    // Schemas are named after both the namespace and the identifier, so the indexer
+    // must exist in the namespace that was authorized, lest an identifier such as
+    // `b_c` in namespace `a` read the schema of identifier `c` in namespace `a_b`.
+    if queries::get_indexer(&mut conn, &namespace, &identifier)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::Http(HttpError::NotFound(format!(
+            "Indexer({namespace}.{identifier}) not found."
+        ))));
+    }
+
+    let table_columns =
+        queries::table_columns(&mut conn, &namespace, &identifier, &entity).await?;
+    if table_columns.is_empty() {
+        return Err(ApiError::Http(HttpError::NotFound(format!(
+            "Entity({entity}) of Indexer({namespace}.{identifier}) not found."
+        ))));
+    }
+
+    let schema = fully_qualified_namespace(&namespace, &identifier);
+    let columns = request.columns(&table_columns)?;
+    let query = request.query(&format!("{schema}.{entity}"), &columns);
+
+    Ok(export_response(
+        conn,
+        request.format,
+        query,
+        columns,
+        &format!("{schema}_{entity}"),
+    )
+    .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_client_keys_api_keys_on_their_own() {
+        let addr = ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 8080)));
+        let claims = Claims::new("owner".to_string(), "iss".to_string(), 60);
+
+        let first = claims.clone().with_api_key("first".to_string());
+        let second = claims.clone().with_api_key("second".to_string());
+        assert_ne!(
+            rate_limit_client(&first, Some(addr)),
+            rate_limit_client(&second, Some(addr))
+        );
+
+        // Usage is still counted by the owner of the keys.
+        assert_eq!(query_client(&first, Some(addr)), "owner");
+        assert_eq!(rate_limit_client(&claims, Some(addr)), "owner");
+        assert_eq!(
+            rate_limit_client(&Claims::unauthenticated(), Some(addr)),
+            "127.0.0.1"
+        );
+    }
 }
@@ -0,0 +1,233 @@
+use criterion::{
+    black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion,
+    Throughput,
+};
+use fuel_indexer::{
+    block_source::SyntheticBlockSource,
+    prelude::fuel::{Address, BlockData, Bytes32},
+    Database, Executor, FtColumn, IndexerConfig, Manifest, WasmIndexExecutor,
+};
+use fuel_indexer_database::IndexerConnectionPool;
+use fuel_indexer_lib::{
+    fully_qualified_namespace,
+    graphql::GraphQLSchema,
+    type_id,
+    utils::{deserialize, serialize},
+};
+use fuel_indexer_schema::db::manager::SchemaManager;
+use fuel_indexer_tests::{assets, fixtures::TestPostgresDb};
+
+/// Numbers of entities written in each benchmark of the write path.
+const ENTITY_COUNTS: [usize; 3] = [1, 100, 1000];
+
+/// Numbers of blocks passed to a WASM module in each benchmark of FFI crossing.
+const BLOCK_COUNTS: [u64; 2] = [10, 100];
+
+/// Columns of a transfer-like entity, covering the column types most indexers use.
+fn transfer_columns(id: u64) -> Vec<FtColumn> {
+    vec![
+        FtColumn::ID(Some(id)),
+        FtColumn::Address(Some(Address::from([1u8; 32]))),
+        FtColumn::Address(Some(Address::from([2u8; 32]))),
+        FtColumn::Bytes32(Some(Bytes32::from([3u8; 32]))),
+        FtColumn::UInt8(Some(id * 1_000)),
+        FtColumn::UInt4(Some(id as u32)),
+        FtColumn::Boolean(Some(id % 2 == 0)),
+        FtColumn::Charfield(Some(format!("transfer-{id}"))),
+        FtColumn::Timestamp(Some(1_690_000_000 + id as i64)),
+        FtColumn::Charfield(None),
+    ]
+}
+
+/// Columns of the simple-wasm test indexer's `Thing2` entity.
+fn thing2_columns(id: u64) -> Vec<FtColumn> {
+    vec![
+        FtColumn::ID(Some(id)),
+        FtColumn::Address(Some(Address::from([4u8; 32]))),
+        FtColumn::Bytes32(Some(Bytes32::from([5u8; 32]))),
+    ]
+}
+
+/// Manifest of the simple-wasm test indexer.
+fn simple_wasm_manifest() -> Manifest {
+    Manifest::try_from(assets::SIMPLE_WASM_MANIFEST).unwrap()
+}
+
+/// Blocks generated for the synthetic load benchmark, starting at height 1.
+fn synthetic_blocks(manifest: &Manifest, count: u64) -> Vec<BlockData> {
+    let source = SyntheticBlockSource::new(manifest);
+    (1..=count).map(|height| source.block(height)).collect()
+}
+
+/// Serialize entities, as handlers do before passing them to `put_object`, and
+/// build the query fragments of their columns, as `put_object` does.
+fn entity_serialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("entity_serialization");
+    for count in ENTITY_COUNTS {
+        let rows = (0..count as u64).map(transfer_columns).collect::<Vec<_>>();
+        group.throughput(Throughput::Elements(count as u64));
+
+        group.bench_with_input(BenchmarkId::new("serialize", count), &rows, |b, rows| {
+            b.iter(|| {
+                rows.iter()
+                    .map(|columns| serialize(black_box(columns)))
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        let bytes = rows.iter().map(serialize).collect::<Vec<_>>();
+        group.bench_with_input(
+            BenchmarkId::new("deserialize", count),
+            &bytes,
+            |b, bytes| {
+                b.iter(|| {
+                    bytes
+                        .iter()
+                        .map(|bytes| {
+                            deserialize::<Vec<FtColumn>>(black_box(bytes)).unwrap()
+                        })
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("query_fragments", count),
+            &rows,
+            |b, rows| {
+                b.iter(|| {
+                    rows.iter()
+                        .map(|columns| {
+                            columns
+                                .iter()
+                                .map(|column| black_box(column).query_fragment())
+                                .collect::<Vec<_>>()
+                        })
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Pass blocks into a WASM module and run its handlers, with its entities held in
+/// memory, so that only the cost of crossing into the module is measured.
+fn ffi_crossing(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let manifest = simple_wasm_manifest();
+    let schema = GraphQLSchema::new(assets::SIMPLE_WASM_SCHEMA.to_string());
+    let config = IndexerConfig::default();
+
+    let mut group = c.benchmark_group("ffi_crossing");
+    for count in BLOCK_COUNTS {
+        let blocks = synthetic_blocks(&manifest, count);
+        group.throughput(Throughput::Elements(count));
+        group.bench_with_input(
+            BenchmarkId::new("handle_events", count),
+            &blocks,
+            |b, blocks| {
+                b.iter_batched(
+                    // A fresh executor is created for each run, so that runs don't
+                    // share entities.
+                    || {
+                        rt.block_on(async {
+                            let db =
+                                Database::in_memory(&manifest, &config, &schema).unwrap();
+                            let executor = WasmIndexExecutor::with_database(
+                                &config,
+                                &manifest,
+                                assets::SIMPLE_WASM_WASM,
+                                db,
+                            )
+                            .await
+                            .unwrap();
+                            (executor, blocks.clone())
+                        })
+                    },
+                    |(mut executor, blocks)| {
+                        rt.block_on(executor.handle_events(blocks)).unwrap()
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Write entities to Postgres in a transaction, as an executor does for each page
+/// of blocks.
+fn postgres_writes(c: &mut Criterion) {
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let manifest = simple_wasm_manifest();
+    let config = IndexerConfig::default();
+    let schema = GraphQLSchema::new(assets::SIMPLE_WASM_SCHEMA.to_string());
+    let version = schema.version().to_string();
+    let thing2 = type_id(
+        &fully_qualified_namespace(manifest.namespace(), manifest.identifier()),
+        "Thing2",
+    );
+
+    let (test_db, mut db) = rt.block_on(async {
+        let test_db = TestPostgresDb::new().await.unwrap();
+        let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+        let mut conn = pool.acquire().await.unwrap();
+        SchemaManager::new(pool.clone())
+            .new_schema(
+                manifest.namespace(),
+                manifest.identifier(),
+                schema,
+                manifest.execution_source(),
+                &mut conn,
+            )
+            .await
+            .unwrap();
+
+        let mut db = Database::new(pool, &manifest, &config).await.unwrap();
+        db.load_schema(version).await.unwrap();
+        (test_db, db)
+    });
+
+    // Entities are upserted, so each run writes new rows rather than updating the
+    // rows of the previous run.
+    let mut next_id = 0;
+
+    let mut group = c.benchmark_group("postgres_writes");
+    for count in ENTITY_COUNTS {
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_function(BenchmarkId::new("put_object", count), |b| {
+            b.iter(|| {
+                rt.block_on(async {
+                    db.start_transaction().await.unwrap();
+                    for _ in 0..count {
+                        next_id += 1;
+                        let columns = thing2_columns(next_id);
+                        let bytes = serialize(&columns);
+                        db.put_object(thing2, columns, bytes).await.unwrap();
+                    }
+                    db.commit_transaction().await.unwrap();
+                })
+            })
+        });
+    }
+    group.finish();
+
+    // The test database is dropped once the benchmarks are done.
+    drop(db);
+    drop(test_db);
+}
+
+criterion_group!(
+    write_path,
+    entity_serialization,
+    ffi_crossing,
+    postgres_writes
+);
+criterion_main!(write_path);
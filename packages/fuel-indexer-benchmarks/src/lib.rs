@@ -1,11 +1,12 @@
 use criterion::Criterion;
 use fuel_core_client::client::FuelClient;
 use fuel_indexer::{
-    executor::retrieve_blocks_from_node, prelude::fuel::BlockData, Executor,
-    IndexerConfig, Manifest, WasmIndexExecutor,
+    executor::{retrieve_blocks_from_node, retrieve_fee_parameters},
+    prelude::fuel::BlockData,
+    Executor, IndexerConfig, Manifest, WasmIndexExecutor,
 };
 use fuel_indexer_database::IndexerConnectionPool;
-use fuel_indexer_lib::config::DatabaseConfig;
+use fuel_indexer_lib::config::{ConversionErrorPolicy, DatabaseConfig};
 use fuel_indexer_tests::fixtures::TestPostgresDb;
 use std::str::FromStr;
 
@@ -20,11 +21,16 @@ async fn get_blocks(start_cursor: u64, num_blocks: usize) -> Result<Vec<BlockDat
     let client = FuelClient::from_str(NODE_URL)
         .unwrap_or_else(|e| panic!("Node connection failed: {e}."));
     let next_cursor = Some(start_cursor.to_string());
+    let fee_params = retrieve_fee_parameters(&client)
+        .await
+        .expect("Could not retrieve fee parameters from node");
     let (blocks, _) = retrieve_blocks_from_node(
         &client,
         num_blocks,
         &next_cursor,
         Some(start_cursor + num_blocks as u64),
+        &fee_params,
+        ConversionErrorPolicy::Fail,
         "",
     )
     .await
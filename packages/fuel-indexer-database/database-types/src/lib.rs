@@ -9,7 +9,7 @@ use async_graphql_parser::{
 };
 use async_graphql_value::Name;
 use chrono::{
-    serde::ts_microseconds,
+    serde::{ts_microseconds, ts_microseconds_option},
     {DateTime, Utc},
 };
 use fuel_indexer_lib::{
@@ -83,6 +83,8 @@ pub enum ColumnType {
     Virtual = 33,
     BlockId = 34,
     Array = 35,
+    I128 = 36,
+    U128 = 37,
 }
 
 impl From<ColumnType> for i32 {
@@ -124,6 +126,8 @@ impl From<ColumnType> for i32 {
             ColumnType::Virtual => 33,
             ColumnType::BlockId => 34,
             ColumnType::Array => 35,
+            ColumnType::I128 => 36,
+            ColumnType::U128 => 37,
         }
     }
 }
@@ -180,6 +184,8 @@ impl From<i32> for ColumnType {
             33 => ColumnType::Virtual,
             34 => ColumnType::BlockId,
             35 => ColumnType::Array,
+            36 => ColumnType::I128,
+            37 => ColumnType::U128,
             _ => unimplemented!("Invalid ColumnType: {num}."),
         }
     }
@@ -224,6 +230,8 @@ impl From<&str> for ColumnType {
             "Virtual" => ColumnType::Virtual,
             "BlockId" => ColumnType::BlockId,
             "Array" => ColumnType::Array,
+            "I128" => ColumnType::I128,
+            "U128" => ColumnType::U128,
             _ => unimplemented!("Invalid ColumnType: '{name}'."),
         }
     }
@@ -362,7 +370,7 @@ impl Column {
     }
 
     /// Derive the respective PostgreSQL field type for a given `Columns`
-    fn sql_type(&self) -> String {
+    pub fn sql_type(&self) -> String {
         // Here we're essentially matching `ColumnType`s to PostgreSQL field
         // types. Note that we're using `numeric` field types for integer-like
         // fields due to the ability to specify custom scale and precision. Some
@@ -385,6 +393,7 @@ impl Column {
             ColumnType::Enum => "varchar(255)".to_string(),
             ColumnType::ForeignKey => "numeric(20, 0)".to_string(),
             ColumnType::HexString => "varchar(10485760)".to_string(),
+            ColumnType::I128 => "numeric(39, 0)".to_string(),
             ColumnType::ID => "numeric(20, 0) primary key".to_string(),
             ColumnType::Identity => "varchar(66)".to_string(),
             ColumnType::Int1 => "integer".to_string(),
@@ -400,6 +409,7 @@ impl Column {
             ColumnType::Tai64Timestamp => "varchar(128)".to_string(),
             ColumnType::Timestamp => "timestamp".to_string(),
             ColumnType::TxId => "varchar(64)".to_string(),
+            ColumnType::U128 => "numeric(39, 0)".to_string(),
             ColumnType::UInt1 => "integer".to_string(),
             ColumnType::UInt16 => "numeric(39, 0)".to_string(),
             ColumnType::UInt4 => "integer".to_string(),
@@ -418,7 +428,10 @@ impl Column {
                     ColumnType::Timestamp => "timestamp",
                     ColumnType::Int8 => "bigint",
                     ColumnType::UInt8 => "numeric(20, 0)",
-                    ColumnType::UInt16 | ColumnType::Int16 => "numeric(39, 0)",
+                    ColumnType::UInt16
+                    | ColumnType::Int16
+                    | ColumnType::I128
+                    | ColumnType::U128 => "numeric(39, 0)",
                     ColumnType::Address
                     | ColumnType::Bytes4
                     | ColumnType::Bytes8
@@ -642,6 +655,23 @@ impl DbType {
     }
 }
 
+/// Value of a parameter of a prepared statement, bound to it with the given type.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum QueryParameter {
+    /// 64-bit integer, bound as a `bigint`.
+    BigInt(i64),
+
+    /// Number too large for a `bigint`, bound as text to be cast to `numeric` by the
+    /// statement.
+    Numeric(String),
+
+    /// String, bound as `text`.
+    Text(String),
+
+    /// Boolean, bound as a `boolean`.
+    Boolean(bool),
+}
+
 /// SQL primary key constraint for a given set of columns.
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct PrimaryKey {
@@ -851,6 +881,301 @@ impl SqlFragment for ForeignKey {
     }
 }
 
+/// A snapshot of an indexer executor's state, used to recover after a restart.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexerCheckpoint {
+    /// Database ID of the indexer.
+    pub index_id: i64,
+
+    /// Height of the last block processed before the checkpoint was taken.
+    pub block_height: u64,
+
+    /// Serialized executor state.
+    pub snapshot: Vec<u8>,
+
+    /// Time at which the checkpoint was taken.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Sync progress of an indexer, as last reported by its executor.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexerStatus {
+    /// Database ID of the indexer.
+    pub index_id: i64,
+
+    /// Height of the last block processed by the indexer.
+    pub current_height: u64,
+
+    /// Height of the latest block on the chain, as last seen by the indexer.
+    pub chain_tip: u64,
+
+    /// Average number of blocks processed per second since the executor started.
+    pub blocks_per_second: f64,
+
+    /// Average number of rows written per minute since the executor started.
+    pub rows_per_minute: f64,
+
+    /// CPU time spent running the indexer's handlers since it was deployed, in seconds.
+    pub cpu_seconds: f64,
+
+    /// Time at which the status was last updated.
+    #[serde(with = "ts_microseconds")]
+    pub updated_at: DateTime<Utc>,
+
+    /// Last error raised while the indexer was processing blocks, if any.
+    pub last_error: Option<String>,
+
+    /// Time at which the last error was raised.
+    #[serde(with = "ts_microseconds_option")]
+    pub last_error_at: Option<DateTime<Utc>>,
+
+    /// Height of the first block of the page the indexer last failed to process.
+    pub failed_height: Option<u64>,
+
+    /// Number of times in a row the indexer has failed to process that page.
+    pub consecutive_failures: u32,
+}
+
+impl IndexerStatus {
+    /// Number of blocks between the indexer and the chain tip.
+    pub fn blocks_behind(&self) -> u64 {
+        self.chain_tip.saturating_sub(self.current_height)
+    }
+
+    /// Estimated number of seconds until the indexer catches up to the chain tip,
+    /// if the indexer is making progress.
+    pub fn eta_seconds(&self) -> Option<u64> {
+        if self.blocks_per_second > 0.0 {
+            Some((self.blocks_behind() as f64 / self.blocks_per_second).ceil() as u64)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the indexer has raised an error since it last made progress.
+    pub fn is_failing(&self) -> bool {
+        self.last_error_at
+            .map(|last_error_at| last_error_at >= self.updated_at)
+            .unwrap_or(false)
+    }
+
+    /// Whether the indexer has failed to process the same page of blocks at least
+    /// `max_failures` times in a row.
+    pub fn is_crash_looping(&self, max_failures: u32) -> bool {
+        self.consecutive_failures >= max_failures
+    }
+}
+
+/// A contract discovered by an indexer at runtime, e.g., from a factory's events.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiscoveredContract {
+    /// Database ID of the indexer.
+    pub index_id: i64,
+
+    /// ID of the discovered contract.
+    pub contract_id: String,
+
+    /// Name of the manifest template used to index the contract.
+    pub template: String,
+
+    /// Time at which the contract was discovered.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// A deployment of an indexer, whose assets are kept so that it can be rolled back to.
+///
+/// Deployments are recorded by namespace and identifier, so that they outlive the
+/// indexer's registration when the indexer is replaced.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexerDeployment {
+    /// Namespace of the indexer.
+    pub namespace: String,
+
+    /// Identifier of the indexer.
+    pub identifier: String,
+
+    /// Version of the deployment, starting at 1 for the first deployment.
+    pub version: i32,
+
+    /// Digest of the deployed manifest.
+    pub manifest_digest: String,
+
+    /// Digest of the deployed GraphQL schema.
+    pub schema_digest: String,
+
+    /// Digest of the deployed WASM module.
+    pub wasm_digest: String,
+
+    /// Version that this deployment rolled back to, if it was a rollback.
+    pub rollback_of: Option<i32>,
+
+    /// Hex-encoded ed25519 public key that signed the deployed manifest and WASM
+    /// module, if they were signed.
+    pub signer: Option<String>,
+
+    /// IPFS CID of the deployed manifest, if it was published to IPFS.
+    pub manifest_cid: Option<String>,
+
+    /// IPFS CID of the deployed GraphQL schema, if it was published to IPFS.
+    pub schema_cid: Option<String>,
+
+    /// IPFS CID of the deployed WASM module, if it was published to IPFS.
+    pub wasm_cid: Option<String>,
+
+    /// Time at which the indexer was deployed.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// A long-lived API key that can be exchanged for a JWT.
+///
+/// Only a salted hash of the key's secret is stored, so the full key is only
+/// available when the key is created.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Public ID of the key, which prefixes the full key.
+    pub id: String,
+
+    /// Name given to the key by its owner.
+    pub name: String,
+
+    /// Subject (public key) of the user that created the key.
+    pub owner: String,
+
+    /// Authorization scopes granted by the key.
+    pub scopes: Vec<String>,
+
+    /// Time at which the key was created.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+
+    /// Whether the key has been revoked.
+    pub revoked: bool,
+}
+
+/// State of an indexer's executor, as recorded by its lifecycle events.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Default,
+    EnumString,
+    AsRefStr,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexerState {
+    /// The indexer's executor is running.
+    #[default]
+    #[strum(serialize = "running")]
+    Running,
+
+    /// The indexer's executor is stopped, but its data is kept so that it can be resumed.
+    #[strum(serialize = "paused")]
+    Paused,
+
+    /// The indexer and its data have been removed.
+    #[strum(serialize = "removed")]
+    Removed,
+}
+
+/// An action taken on an indexer through the admin API.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, EnumString, AsRefStr, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexerLifecycleAction {
+    /// Stop the indexer's executor, keeping its data.
+    #[strum(serialize = "pause")]
+    Pause,
+
+    /// Restart a paused indexer's executor from its latest checkpoint.
+    #[strum(serialize = "resume")]
+    Resume,
+
+    /// Clear the indexer's data and restart its executor from a given height.
+    #[strum(serialize = "reindex")]
+    Reindex,
+
+    /// Remove the indexer, including its schema and data.
+    #[strum(serialize = "remove")]
+    Remove,
+}
+
+impl IndexerLifecycleAction {
+    /// State of the indexer after this action has been taken.
+    pub fn state(&self) -> IndexerState {
+        match self {
+            Self::Pause => IndexerState::Paused,
+            Self::Resume | Self::Reindex => IndexerState::Running,
+            Self::Remove => IndexerState::Removed,
+        }
+    }
+}
+
+/// A state transition of an indexer, recorded when a lifecycle action is taken.
+///
+/// Like deployments, events are recorded by namespace and identifier, so that they
+/// outlive the indexer's registration when the indexer is removed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexerLifecycleEvent {
+    /// Namespace of the indexer.
+    pub namespace: String,
+
+    /// Identifier of the indexer.
+    pub identifier: String,
+
+    /// Action that was taken.
+    pub action: IndexerLifecycleAction,
+
+    /// State of the indexer after the action.
+    pub state: IndexerState,
+
+    /// Block height from which the indexer was reindexed, if the action was a reindex.
+    pub block_height: Option<u64>,
+
+    /// Subject (public key) of the user that took the action, if authenticated.
+    pub actor: Option<String>,
+
+    /// Time at which the action was taken.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// A mutating request made to the web API, such as deploying or removing an indexer.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    /// Database ID of the entry.
+    pub id: i64,
+
+    /// Action that was taken, e.g., `deploy`.
+    pub action: String,
+
+    /// Namespace of the indexer the action was taken on.
+    pub namespace: String,
+
+    /// Identifier of the indexer the action was taken on.
+    pub identifier: String,
+
+    /// Subject (public key) of the user that took the action, if authenticated.
+    pub principal: Option<String>,
+
+    /// IP address from which the request was made, if known.
+    pub source_ip: Option<String>,
+
+    /// Digest of the manifest that was deployed, for deployments and rollbacks.
+    pub manifest_digest: Option<String>,
+
+    /// Time at which the action was taken.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+}
+
 /// Nonce used for indexer authentication.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Nonce {
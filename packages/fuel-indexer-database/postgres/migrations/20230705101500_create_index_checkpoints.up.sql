@@ -0,0 +1,10 @@
+create table if not exists index_checkpoints (
+    id bigserial primary key,
+    index_id bigint not null unique,
+    block_height bigint not null,
+    snapshot bytea not null,
+    created_at timestamp not null default current_timestamp,
+    constraint fk_index_id
+        foreign key(index_id)
+            references index_registry(id)
+);
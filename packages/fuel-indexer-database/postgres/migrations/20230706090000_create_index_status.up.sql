@@ -0,0 +1,11 @@
+create table if not exists index_status (
+    id bigserial primary key,
+    index_id bigint not null unique,
+    current_height bigint not null,
+    chain_tip bigint not null,
+    blocks_per_second double precision not null,
+    updated_at timestamp not null default current_timestamp,
+    constraint fk_index_id
+        foreign key(index_id)
+            references index_registry(id)
+);
@@ -0,0 +1,11 @@
+create table if not exists index_discovered_contracts (
+    id bigserial primary key,
+    index_id bigint not null,
+    contract_id varchar(255) not null,
+    template varchar(255) not null,
+    created_at timestamp not null default current_timestamp,
+    unique(index_id, contract_id),
+    constraint fk_index_id
+        foreign key(index_id)
+            references index_registry(id)
+);
@@ -0,0 +1,15 @@
+create table if not exists index_deployments (
+    id bigserial primary key,
+    namespace varchar(32) not null,
+    identifier varchar(32) not null,
+    version integer not null,
+    manifest bytea not null,
+    schema bytea not null,
+    wasm bytea not null,
+    manifest_digest varchar(64) not null,
+    schema_digest varchar(64) not null,
+    wasm_digest varchar(64) not null,
+    rollback_of integer,
+    created_at timestamp not null default current_timestamp,
+    unique(namespace, identifier, version)
+);
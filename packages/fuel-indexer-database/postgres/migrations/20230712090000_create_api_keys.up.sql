@@ -0,0 +1,11 @@
+create table if not exists api_keys (
+    id bigserial primary key,
+    key_id varchar(32) not null unique,
+    salt varchar(32) not null,
+    key_hash varchar(64) not null,
+    name varchar(255) not null,
+    owner varchar(255) not null,
+    scopes text not null default '',
+    created_at timestamp not null default current_timestamp,
+    revoked_at timestamp
+);
@@ -0,0 +1,13 @@
+create table if not exists index_lifecycle_events (
+    id bigserial primary key,
+    namespace varchar(32) not null,
+    identifier varchar(32) not null,
+    action varchar(16) not null,
+    state varchar(16) not null,
+    block_height bigint,
+    actor varchar(255),
+    created_at timestamp not null default current_timestamp
+);
+
+create index if not exists index_lifecycle_events_indexer
+    on index_lifecycle_events (namespace, identifier, id);
@@ -0,0 +1,2 @@
+alter table index_status drop column if exists last_error;
+alter table index_status drop column if exists last_error_at;
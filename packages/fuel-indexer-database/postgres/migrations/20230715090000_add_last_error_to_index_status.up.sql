@@ -0,0 +1,2 @@
+alter table index_status add column if not exists last_error text;
+alter table index_status add column if not exists last_error_at timestamp;
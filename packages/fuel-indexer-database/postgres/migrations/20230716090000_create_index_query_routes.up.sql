@@ -0,0 +1,7 @@
+create table if not exists index_query_routes (
+    namespace varchar(32) not null,
+    identifier varchar(32) not null,
+    target varchar(32) not null,
+    updated_at timestamp not null default current_timestamp,
+    primary key (namespace, identifier)
+);
@@ -0,0 +1,12 @@
+create table if not exists audit_log (
+    id bigserial primary key,
+    action varchar(32) not null,
+    namespace varchar(32) not null,
+    identifier varchar(32) not null,
+    principal varchar(255),
+    source_ip varchar(64),
+    manifest_digest varchar(64),
+    created_at timestamp not null default current_timestamp
+);
+
+create index if not exists audit_log_indexer on audit_log (namespace, identifier);
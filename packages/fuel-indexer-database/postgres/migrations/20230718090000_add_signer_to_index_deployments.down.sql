@@ -0,0 +1 @@
+alter table index_deployments drop column if exists signer;
@@ -0,0 +1 @@
+alter table index_deployments add column signer varchar(64);
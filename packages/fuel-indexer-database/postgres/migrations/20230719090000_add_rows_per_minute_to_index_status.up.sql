@@ -0,0 +1 @@
+alter table index_status add column rows_per_minute double precision not null default 0;
@@ -0,0 +1,2 @@
+alter table index_status drop column if exists failed_height;
+alter table index_status drop column if exists consecutive_failures;
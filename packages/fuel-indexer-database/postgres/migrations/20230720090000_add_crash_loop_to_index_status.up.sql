@@ -0,0 +1,2 @@
+alter table index_status add column failed_height bigint;
+alter table index_status add column consecutive_failures integer not null default 0;
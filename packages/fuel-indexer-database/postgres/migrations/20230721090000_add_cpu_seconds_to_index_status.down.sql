@@ -0,0 +1 @@
+alter table index_status drop column if exists cpu_seconds;
@@ -0,0 +1 @@
+alter table index_status add column cpu_seconds double precision not null default 0;
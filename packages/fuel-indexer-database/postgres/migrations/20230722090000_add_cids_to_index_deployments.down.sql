@@ -0,0 +1,3 @@
+alter table index_deployments drop column if exists manifest_cid;
+alter table index_deployments drop column if exists schema_cid;
+alter table index_deployments drop column if exists wasm_cid;
@@ -0,0 +1,3 @@
+alter table index_deployments add column manifest_cid varchar(255);
+alter table index_deployments add column schema_cid varchar(255);
+alter table index_deployments add column wasm_cid varchar(255);
@@ -0,0 +1,3 @@
+alter table api_keys alter column scopes drop default;
+alter table api_keys alter column scopes type text using array_to_string(scopes, ',');
+alter table api_keys alter column scopes set default '';
@@ -0,0 +1,3 @@
+alter table api_keys alter column scopes drop default;
+alter table api_keys alter column scopes type text[] using case when scopes = '' then '{}'::text[] else string_to_array(scopes, ',') end;
+alter table api_keys alter column scopes set default '{}';
@@ -2,15 +2,19 @@
 
 use bigdecimal::ToPrimitive;
 use fuel_indexer_database_types::*;
-use fuel_indexer_lib::utils::sha256_digest;
+use fuel_indexer_lib::{fully_qualified_namespace, utils::sha256_digest};
+use futures_util::{stream::BoxStream, StreamExt, TryStreamExt};
+use rand::{rngs::OsRng, RngCore};
 use sqlx::{
     pool::PoolConnection,
-    postgres::PgRow,
+    postgres::{PgArguments, PgRow},
+    query::Query,
     types::{BigDecimal, JsonValue},
-    Postgres, Row,
+    Executor, Postgres, Row,
 };
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
+use subtle::ConstantTimeEq;
 use tracing::info;
 
 #[cfg(feature = "metrics")]
@@ -53,6 +57,18 @@ pub async fn get_object(
     Ok(row.get(0))
 }
 
+/// Fetch the blobs of serialized `FtColumns` of all rows matching a query.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_objects(
+    conn: &mut PoolConnection<Postgres>,
+    query: String,
+) -> sqlx::Result<Vec<Vec<u8>>> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+    let query = builder.build();
+    let rows = query.fetch_all(conn).await?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
 /// Run database migrations.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn run_migration(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<()> {
@@ -94,6 +110,47 @@ pub async fn execute_query(
     Ok(result.rows_affected() as usize)
 }
 
+/// Execute the given statements, separated by semicolons, in a single round trip.
+///
+/// The statements are sent as a simple query, so they can't have parameters, and
+/// none of them are run once one fails.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn execute_batch(
+    conn: &mut PoolConnection<Postgres>,
+    statements: String,
+) -> sqlx::Result<usize> {
+    let result = conn.execute(statements.as_str()).await?;
+    Ok(result.rows_affected() as usize)
+}
+
+/// Run a query with the given parameters and fetch all results, as `run_query` does.
+///
+/// The query is cached by the connection as a prepared statement.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn run_statement(
+    conn: &mut PoolConnection<Postgres>,
+    query: &str,
+    params: &[QueryParameter],
+) -> sqlx::Result<JsonValue> {
+    let rows: Vec<JsonValue> = fetch_statement_json_rows(conn, query, params)
+        .try_collect()
+        .await?;
+    Ok(rows.into())
+}
+
+/// Run a query with the given parameters and fetch all results, as `run_query` does.
+///
+/// The query isn't cached by the connection as a statement.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn run_parameterized_query(
+    conn: &mut PoolConnection<Postgres>,
+    query: &str,
+    params: &[QueryParameter],
+) -> sqlx::Result<JsonValue> {
+    let rows: Vec<JsonValue> = fetch_json_rows(conn, query, params).try_collect().await?;
+    Ok(rows.into())
+}
+
 /// Return a set of `RootColumn`s associated with a given `GraphRoot`.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn root_columns_list_by_id(
@@ -651,6 +708,27 @@ pub async fn latest_asset_for_indexer(
     })
 }
 
+/// Return the version and digest of the latest asset of the given type, without
+/// loading the asset's bytes.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn latest_asset_digest(
+    conn: &mut PoolConnection<Postgres>,
+    index_id: &i64,
+    asset_type: IndexerAssetType,
+) -> sqlx::Result<Option<(i32, String)>> {
+    let query = format!(
+        "SELECT version, digest FROM index_asset_registry_{} WHERE index_id = $1 ORDER BY id DESC LIMIT 1",
+        asset_type.as_ref(),
+    );
+
+    let row = sqlx::query(&query)
+        .bind(index_id)
+        .fetch_optional(conn)
+        .await?;
+
+    Ok(row.map(|row| (row.get(0), row.get(1))))
+}
+
 /// Return the latest version for every indexer asset type.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn latest_assets_for_indexer(
@@ -811,6 +889,36 @@ pub async fn remove_indexer(
     )
     .await?;
 
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_checkpoints WHERE index_id IN
+            (SELECT id FROM index_registry
+                WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+        ),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_discovered_contracts WHERE index_id IN
+            (SELECT id FROM index_registry
+                WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+        ),
+    )
+    .await?;
+
+    execute_query(
+        conn,
+        format!(
+            "DELETE FROM index_status WHERE index_id IN
+            (SELECT id FROM index_registry
+                WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+        ),
+    )
+    .await?;
+
     execute_query(
         conn,
         format!(
@@ -862,6 +970,586 @@ pub async fn remove_indexer(
     Ok(())
 }
 
+/// Save a checkpoint for the given indexer, replacing any previous checkpoint.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_checkpoint(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+    snapshot: Vec<u8>,
+) -> sqlx::Result<()> {
+    let index_id = get_indexer_id(conn, namespace, identifier).await?;
+
+    let _ = sqlx::query(
+        "INSERT INTO index_checkpoints (index_id, block_height, snapshot, created_at)
+        VALUES ($1, $2, $3, current_timestamp)
+        ON CONFLICT (index_id) DO UPDATE SET
+            block_height = EXCLUDED.block_height,
+            snapshot = EXCLUDED.snapshot,
+            created_at = EXCLUDED.created_at",
+    )
+    .bind(index_id)
+    .bind(block_height as i64)
+    .bind(snapshot)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Return the latest checkpoint for the given indexer, if any.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_checkpoint(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerCheckpoint>> {
+    match sqlx::query(
+        "SELECT index_checkpoints.index_id, index_checkpoints.block_height,
+            index_checkpoints.snapshot, index_checkpoints.created_at
+        FROM index_checkpoints
+        INNER JOIN index_registry ON index_registry.id = index_checkpoints.index_id
+        WHERE index_registry.namespace = $1
+        AND index_registry.identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?
+    {
+        Some(row) => {
+            let created_at: DateTime<Utc> = {
+                let created_at: NaiveDateTime = row.get(3);
+                DateTime::<Utc>::from_utc(created_at, Utc)
+            };
+            let block_height: i64 = row.get(1);
+
+            Ok(Some(IndexerCheckpoint {
+                index_id: row.get(0),
+                block_height: block_height as u64,
+                snapshot: row.get(2),
+                created_at,
+            }))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Return the names of the tables in the schema of the given indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn indexer_tables(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    let rows = sqlx::query(
+        "SELECT table_name::text FROM information_schema.tables
+        WHERE table_schema = $1 AND table_type = 'BASE TABLE'
+        ORDER BY table_name",
+    )
+    .bind(format!("{namespace}_{identifier}"))
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Return the rows of one of the given indexer's tables, in the text format of
+/// PostgreSQL's `COPY`.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn copy_table_out(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+) -> sqlx::Result<Vec<u8>> {
+    let mut stream = conn
+        .copy_out_raw(&format!("COPY {namespace}_{identifier}.{table} TO STDOUT"))
+        .await?;
+
+    let mut data = Vec::new();
+    while let Some(chunk) = stream.try_next().await? {
+        data.extend_from_slice(&chunk);
+    }
+
+    Ok(data)
+}
+
+/// Return the names and data types of the columns of one of the given indexer's
+/// tables, in order.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn table_columns(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+) -> sqlx::Result<Vec<(String, String)>> {
+    let rows = sqlx::query(
+        "SELECT column_name::text, data_type::text FROM information_schema.columns
+        WHERE table_schema = $1 AND table_name = $2
+        ORDER BY ordinal_position",
+    )
+    .bind(fully_qualified_namespace(namespace, identifier))
+    .bind(table)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows.iter().map(|row| (row.get(0), row.get(1))).collect())
+}
+
+/// Stream the output of a `COPY ... TO STDOUT` statement as it is produced.
+pub async fn copy_out<'c>(
+    conn: &'c mut PoolConnection<Postgres>,
+    statement: &str,
+) -> sqlx::Result<BoxStream<'c, sqlx::Result<Vec<u8>>>> {
+    let stream = conn.copy_out_raw(statement).await?;
+    Ok(stream.map_ok(|chunk| chunk.to_vec()).boxed())
+}
+
+/// Stream the rows of a query whose columns are all of type `text`.
+pub fn fetch_text_rows<'c>(
+    conn: &'c mut PoolConnection<Postgres>,
+    query: &'c str,
+) -> BoxStream<'c, sqlx::Result<Vec<Option<String>>>> {
+    sqlx::query(query)
+        .fetch(conn)
+        .map_ok(|row: PgRow| {
+            (0..row.len())
+                .map(|i| row.get::<Option<String>, _>(i))
+                .collect()
+        })
+        .boxed()
+}
+
+/// Stream the JSON values in the first column of the rows of a query run with the
+/// given parameters, as they are read.
+///
+/// Rows whose first column can't be converted to `JsonValue` are skipped, as they are
+/// by `run_query`. The query isn't cached by the connection as a statement; queries
+/// of the same shape can share a statement through `fetch_statement_json_rows`
+/// instead.
+pub fn fetch_json_rows<'c>(
+    conn: &'c mut PoolConnection<Postgres>,
+    query: &'c str,
+    params: &'c [QueryParameter],
+) -> BoxStream<'c, sqlx::Result<JsonValue>> {
+    bind_parameters(sqlx::query(query), params)
+        .persistent(false)
+        .fetch(conn)
+        .try_filter_map(|row: PgRow| async move {
+            Ok(row.try_get::<JsonValue, usize>(0).ok())
+        })
+        .boxed()
+}
+
+/// Stream the JSON values in the first column of the rows of a query run with the
+/// given parameters, as `fetch_json_rows` does.
+///
+/// The query is cached by the connection as a prepared statement, evicting its least
+/// recently used statement once the connection's statement cache is full, so queries
+/// with the same SQL are planned once per connection and then run in a single round
+/// trip.
+pub fn fetch_statement_json_rows<'c>(
+    conn: &'c mut PoolConnection<Postgres>,
+    query: &'c str,
+    params: &'c [QueryParameter],
+) -> BoxStream<'c, sqlx::Result<JsonValue>> {
+    bind_parameters(sqlx::query(query), params)
+        .fetch(conn)
+        .try_filter_map(|row: PgRow| async move {
+            Ok(row.try_get::<JsonValue, usize>(0).ok())
+        })
+        .boxed()
+}
+
+/// Bind the given parameters to a query, in order of their placeholders.
+fn bind_parameters<'q>(
+    mut query: Query<'q, Postgres, PgArguments>,
+    params: &'q [QueryParameter],
+) -> Query<'q, Postgres, PgArguments> {
+    for param in params {
+        query = match param {
+            QueryParameter::BigInt(v) => query.bind(*v),
+            QueryParameter::Numeric(v) | QueryParameter::Text(v) => {
+                query.bind(v.as_str())
+            }
+            QueryParameter::Boolean(v) => query.bind(*v),
+        };
+    }
+    query
+}
+
+/// Insert rows, in the text format of PostgreSQL's `COPY`, into one of the given
+/// indexer's tables, returning the number of rows inserted.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn copy_table_in(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+    data: Vec<u8>,
+) -> sqlx::Result<u64> {
+    let mut copy = conn
+        .copy_in_raw(&format!("COPY {namespace}_{identifier}.{table} FROM STDIN"))
+        .await?;
+    copy.send(data).await?;
+    copy.finish().await
+}
+
+/// Save the sync status of the given indexer, replacing any previous status.
+///
+/// `cpu_seconds` is the CPU time spent by the indexer's handlers since its status
+/// was last saved, which is added to its total.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_indexer_status(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    current_height: u64,
+    chain_tip: u64,
+    blocks_per_second: f64,
+    rows_per_minute: f64,
+    cpu_seconds: f64,
+) -> sqlx::Result<()> {
+    let index_id = get_indexer_id(conn, namespace, identifier).await?;
+
+    let _ = sqlx::query(
+        "INSERT INTO index_status
+            (index_id, current_height, chain_tip, blocks_per_second, rows_per_minute,
+            cpu_seconds, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, current_timestamp)
+        ON CONFLICT (index_id) DO UPDATE SET
+            current_height = EXCLUDED.current_height,
+            chain_tip = EXCLUDED.chain_tip,
+            blocks_per_second = EXCLUDED.blocks_per_second,
+            rows_per_minute = EXCLUDED.rows_per_minute,
+            cpu_seconds = index_status.cpu_seconds + EXCLUDED.cpu_seconds,
+            updated_at = EXCLUDED.updated_at,
+            failed_height = NULL,
+            consecutive_failures = 0",
+    )
+    .bind(index_id)
+    .bind(current_height as i64)
+    .bind(chain_tip as i64)
+    .bind(blocks_per_second)
+    .bind(rows_per_minute)
+    .bind(cpu_seconds)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Save the last error raised by the given indexer while processing blocks, along
+/// with the block it failed on and how many times in a row it has failed on it.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_indexer_error(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    error: &str,
+    failed_height: Option<u64>,
+    consecutive_failures: u32,
+) -> sqlx::Result<()> {
+    let index_id = get_indexer_id(conn, namespace, identifier).await?;
+
+    let _ = sqlx::query(
+        "INSERT INTO index_status
+            (index_id, current_height, chain_tip, blocks_per_second, updated_at,
+            last_error, last_error_at, failed_height, consecutive_failures)
+        VALUES ($1, 0, 0, 0, current_timestamp, $2, current_timestamp, $3, $4)
+        ON CONFLICT (index_id) DO UPDATE SET
+            last_error = EXCLUDED.last_error,
+            last_error_at = EXCLUDED.last_error_at,
+            failed_height = EXCLUDED.failed_height,
+            consecutive_failures = EXCLUDED.consecutive_failures",
+    )
+    .bind(index_id)
+    .bind(error)
+    .bind(failed_height.map(|height| height as i64))
+    .bind(consecutive_failures as i32)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+fn indexer_status_from_row(row: PgRow) -> IndexerStatus {
+    let current_height: i64 = row.get(1);
+    let chain_tip: i64 = row.get(2);
+    let updated_at: DateTime<Utc> = {
+        let updated_at: NaiveDateTime = row.get(4);
+        DateTime::<Utc>::from_utc(updated_at, Utc)
+    };
+    let last_error_at: Option<DateTime<Utc>> = {
+        let last_error_at: Option<NaiveDateTime> = row.get(6);
+        last_error_at.map(|last_error_at| DateTime::<Utc>::from_utc(last_error_at, Utc))
+    };
+    let failed_height: Option<i64> = row.get(8);
+    let consecutive_failures: i32 = row.get(9);
+
+    IndexerStatus {
+        index_id: row.get(0),
+        current_height: current_height as u64,
+        chain_tip: chain_tip as u64,
+        blocks_per_second: row.get(3),
+        rows_per_minute: row.get(7),
+        cpu_seconds: row.get(10),
+        updated_at,
+        last_error: row.get(5),
+        last_error_at,
+        failed_height: failed_height.map(|height| height as u64),
+        consecutive_failures: consecutive_failures as u32,
+    }
+}
+
+/// Return the sync status of the given indexer, if any has been reported.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_indexer_status(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerStatus>> {
+    Ok(sqlx::query(
+        "SELECT index_status.index_id, index_status.current_height, index_status.chain_tip,
+            index_status.blocks_per_second, index_status.updated_at,
+            index_status.last_error, index_status.last_error_at,
+            index_status.rows_per_minute, index_status.failed_height,
+            index_status.consecutive_failures, index_status.cpu_seconds
+        FROM index_status
+        INNER JOIN index_registry ON index_registry.id = index_status.index_id
+        WHERE index_registry.namespace = $1
+        AND index_registry.identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?
+    .map(indexer_status_from_row))
+}
+
+/// Return the sync status of all indexers that have reported one.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn all_indexer_statuses(
+    conn: &mut PoolConnection<Postgres>,
+) -> sqlx::Result<Vec<IndexerStatus>> {
+    Ok(sqlx::query(
+        "SELECT index_id, current_height, chain_tip, blocks_per_second, updated_at,
+            last_error, last_error_at, rows_per_minute, failed_height,
+            consecutive_failures, cpu_seconds
+        FROM index_status",
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(indexer_status_from_row)
+    .collect())
+}
+
+/// Register a contract discovered by the given indexer at runtime.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn register_contract(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    template: &str,
+    contract_id: &str,
+) -> sqlx::Result<()> {
+    let index_id = get_indexer_id(conn, namespace, identifier).await?;
+
+    let _ = sqlx::query(
+        "INSERT INTO index_discovered_contracts (index_id, contract_id, template)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (index_id, contract_id) DO NOTHING",
+    )
+    .bind(index_id)
+    .bind(contract_id)
+    .bind(template)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Return all contracts discovered by the given indexer at runtime.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn discovered_contracts(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<DiscoveredContract>> {
+    Ok(sqlx::query(
+        "SELECT index_discovered_contracts.index_id, index_discovered_contracts.contract_id,
+            index_discovered_contracts.template, index_discovered_contracts.created_at
+        FROM index_discovered_contracts
+        INNER JOIN index_registry ON index_registry.id = index_discovered_contracts.index_id
+        WHERE index_registry.namespace = $1
+        AND index_registry.identifier = $2
+        ORDER BY index_discovered_contracts.id ASC",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        let created_at: DateTime<Utc> = {
+            let created_at: NaiveDateTime = row.get(3);
+            DateTime::<Utc>::from_utc(created_at, Utc)
+        };
+
+        DiscoveredContract {
+            index_id: row.get(0),
+            contract_id: row.get(1),
+            template: row.get(2),
+            created_at,
+        }
+    })
+    .collect())
+}
+
+/// Record a deployment of the given indexer assets, returning the deployment's version.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn record_deployment(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    assets: &IndexerAssetBundle,
+    rollback_of: Option<i32>,
+    signer: Option<&str>,
+) -> sqlx::Result<i32> {
+    // A rollback keeps the signer and IPFS CIDs of the deployment that it rolls
+    // back to, since its assets are the same.
+    let row = sqlx::query(
+        "INSERT INTO index_deployments
+            (namespace, identifier, version, manifest, schema, wasm,
+            manifest_digest, schema_digest, wasm_digest, rollback_of, signer,
+            manifest_cid, schema_cid, wasm_cid)
+        SELECT $1, $2, COALESCE(MAX(version), 0) + 1, $3, $4, $5, $6, $7, $8, $9,
+            COALESCE($10, MAX(signer) FILTER (WHERE version = $9)),
+            MAX(manifest_cid) FILTER (WHERE version = $9),
+            MAX(schema_cid) FILTER (WHERE version = $9),
+            MAX(wasm_cid) FILTER (WHERE version = $9)
+        FROM index_deployments
+        WHERE namespace = $1 AND identifier = $2
+        RETURNING version",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(&assets.manifest.bytes)
+    .bind(&assets.schema.bytes)
+    .bind(&assets.wasm.bytes)
+    .bind(&assets.manifest.digest)
+    .bind(&assets.schema.digest)
+    .bind(&assets.wasm.digest)
+    .bind(rollback_of)
+    .bind(signer)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row.get(0))
+}
+
+/// Record the IPFS CIDs of the assets of the given deployment of an indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn set_deployment_cids(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    version: i32,
+    manifest_cid: &str,
+    schema_cid: &str,
+    wasm_cid: &str,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "UPDATE index_deployments
+        SET manifest_cid = $4, schema_cid = $5, wasm_cid = $6
+        WHERE namespace = $1 AND identifier = $2 AND version = $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(version)
+    .bind(manifest_cid)
+    .bind(schema_cid)
+    .bind(wasm_cid)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Return all deployments of the given indexer, oldest first.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn indexer_deployments(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerDeployment>> {
+    Ok(sqlx::query(
+        "SELECT namespace, identifier, version, manifest_digest, schema_digest,
+            wasm_digest, rollback_of, signer, manifest_cid, schema_cid, wasm_cid,
+            created_at
+        FROM index_deployments
+        WHERE namespace = $1 AND identifier = $2
+        ORDER BY version ASC",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        let created_at: DateTime<Utc> = {
+            let created_at: NaiveDateTime = row.get(11);
+            DateTime::<Utc>::from_utc(created_at, Utc)
+        };
+
+        IndexerDeployment {
+            namespace: row.get(0),
+            identifier: row.get(1),
+            version: row.get(2),
+            manifest_digest: row.get(3),
+            schema_digest: row.get(4),
+            wasm_digest: row.get(5),
+            rollback_of: row.get(6),
+            signer: row.get(7),
+            manifest_cid: row.get(8),
+            schema_cid: row.get(9),
+            wasm_cid: row.get(10),
+            created_at,
+        }
+    })
+    .collect())
+}
+
+/// Return the assets of the given deployment of an indexer, if it exists.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn deployment_assets(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    version: i32,
+) -> sqlx::Result<Option<Vec<(IndexerAssetType, Vec<u8>)>>> {
+    Ok(sqlx::query(
+        "SELECT manifest, schema, wasm
+        FROM index_deployments
+        WHERE namespace = $1 AND identifier = $2 AND version = $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(version)
+    .fetch_optional(conn)
+    .await?
+    .map(|row| {
+        vec![
+            (IndexerAssetType::Manifest, row.get(0)),
+            (IndexerAssetType::Schema, row.get(1)),
+            (IndexerAssetType::Wasm, row.get(2)),
+        ]
+    }))
+}
+
 /// Create a new nonce for a requesting user's authentication.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn create_nonce(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<Nonce> {
@@ -873,6 +1561,11 @@ pub async fn create_nonce(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<N
 
     let expiry = now + NONCE_EXPIRY_SECS;
 
+    // Nonces that were never used would otherwise accumulate.
+    sqlx::query(&format!("DELETE FROM nonce WHERE expiry <= {now}"))
+        .execute(&mut *conn)
+        .await?;
+
     let row = sqlx::QueryBuilder::new(&format!(
         "INSERT INTO nonce (uid, expiry) VALUES ('{uid}', {expiry}) RETURNING *"
     ))
@@ -892,7 +1585,8 @@ pub async fn get_nonce(
     conn: &mut PoolConnection<Postgres>,
     uid: &str,
 ) -> sqlx::Result<Nonce> {
-    let row = sqlx::query(&format!("SELECT * FROM nonce WHERE uid = '{uid}'"))
+    let row = sqlx::query("SELECT * FROM nonce WHERE uid = $1")
+        .bind(uid)
         .fetch_one(conn)
         .await?;
 
@@ -910,13 +1604,197 @@ pub async fn delete_nonce(
     conn: &mut PoolConnection<Postgres>,
     nonce: &Nonce,
 ) -> sqlx::Result<()> {
-    let _ = sqlx::query(&format!("DELETE FROM nonce WHERE uid = '{}'", nonce.uid))
+    let _ = sqlx::query("DELETE FROM nonce WHERE uid = $1")
+        .bind(&nonce.uid)
         .execute(conn)
         .await?;
 
     Ok(())
 }
 
+/// Delete the specified nonce if it hasn't expired, returning it, or `None` if it
+/// doesn't exist or has expired.
+///
+/// The nonce is deleted as it's read, so that it can be used to authenticate once,
+/// even by concurrent requests.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn consume_nonce(
+    conn: &mut PoolConnection<Postgres>,
+    uid: &str,
+) -> sqlx::Result<Option<Nonce>> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    let row = sqlx::query(
+        "DELETE FROM nonce WHERE uid = $1 AND expiry > $2 RETURNING uid, expiry",
+    )
+    .bind(uid)
+    .bind(now)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.map(|row| Nonce {
+        uid: row.get(0),
+        expiry: row.get(1),
+    }))
+}
+
+/// Build an `ApiKey` from a row of the `api_keys` table, selected with `API_KEY_COLUMNS`.
+fn api_key_from_row(row: &PgRow) -> ApiKey {
+    let created_at: NaiveDateTime = row.get(4);
+    let revoked_at: Option<NaiveDateTime> = row.get(5);
+
+    ApiKey {
+        id: row.get(0),
+        name: row.get(1),
+        owner: row.get(2),
+        scopes: row.get(3),
+        created_at: DateTime::<Utc>::from_utc(created_at, Utc),
+        revoked: revoked_at.is_some(),
+    }
+}
+
+/// Columns of the `api_keys` table read by `api_key_from_row`.
+const API_KEY_COLUMNS: &str = "key_id, name, owner, scopes, created_at, revoked_at";
+
+/// Return the given number of random bytes from the operating system, hex-encoded.
+fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Create a new API key with the given name and scopes, returning the key and its
+/// full value. Only a salted hash of the key's secret is stored.
+///
+/// The secret is made of 32 random bytes from the operating system, rather than
+/// being derived from a UUID, whose bits aren't all random.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn create_api_key(
+    conn: &mut PoolConnection<Postgres>,
+    name: &str,
+    owner: &str,
+    scopes: &[String],
+) -> sqlx::Result<(ApiKey, String)> {
+    let key_id = uuid::Uuid::new_v4().as_simple().to_string();
+    let salt = random_hex(16);
+    let secret = random_hex(32);
+    let key_hash = sha256_digest(&format!("{salt}{secret}"));
+
+    let row = sqlx::query(&format!(
+        "INSERT INTO api_keys (key_id, salt, key_hash, name, owner, scopes)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING {API_KEY_COLUMNS}"
+    ))
+    .bind(&key_id)
+    .bind(salt)
+    .bind(key_hash)
+    .bind(name)
+    .bind(owner)
+    .bind(scopes)
+    .fetch_one(conn)
+    .await?;
+
+    Ok((api_key_from_row(&row), format!("{key_id}.{secret}")))
+}
+
+/// Return the API keys created by the given owner, or all API keys if no owner
+/// is given.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn api_keys(
+    conn: &mut PoolConnection<Postgres>,
+    owner: Option<&str>,
+) -> sqlx::Result<Vec<ApiKey>> {
+    let rows = match owner {
+        Some(owner) => {
+            sqlx::query(&format!(
+                "SELECT {API_KEY_COLUMNS} FROM api_keys WHERE owner = $1 ORDER BY id ASC"
+            ))
+            .bind(owner)
+            .fetch_all(conn)
+            .await?
+        }
+        None => {
+            sqlx::query(&format!(
+                "SELECT {API_KEY_COLUMNS} FROM api_keys ORDER BY id ASC"
+            ))
+            .fetch_all(conn)
+            .await?
+        }
+    };
+
+    Ok(rows.iter().map(api_key_from_row).collect())
+}
+
+/// Return the API key with the given ID, if it exists.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_api_key(
+    conn: &mut PoolConnection<Postgres>,
+    key_id: &str,
+) -> sqlx::Result<Option<ApiKey>> {
+    Ok(sqlx::query(&format!(
+        "SELECT {API_KEY_COLUMNS} FROM api_keys WHERE key_id = $1"
+    ))
+    .bind(key_id)
+    .fetch_optional(conn)
+    .await?
+    .map(|row| api_key_from_row(&row)))
+}
+
+/// Return the API key matching the given full key value, if it exists and has not
+/// been revoked.
+///
+/// The hash of the secret is compared in constant time, so that the time taken to
+/// reject a key doesn't reveal how much of its hash matched.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn verify_api_key(
+    conn: &mut PoolConnection<Postgres>,
+    key: &str,
+) -> sqlx::Result<Option<ApiKey>> {
+    let (key_id, secret) = match key.split_once('.') {
+        Some(parts) => parts,
+        None => return Ok(None),
+    };
+
+    let row = sqlx::query(&format!(
+        "SELECT {API_KEY_COLUMNS}, salt, key_hash FROM api_keys
+        WHERE key_id = $1 AND revoked_at IS NULL"
+    ))
+    .bind(key_id)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(row.and_then(|row| {
+        let salt: String = row.get(6);
+        let key_hash: String = row.get(7);
+        let digest = sha256_digest(&format!("{salt}{secret}"));
+        if bool::from(digest.as_bytes().ct_eq(key_hash.as_bytes())) {
+            Some(api_key_from_row(&row))
+        } else {
+            None
+        }
+    }))
+}
+
+/// Revoke the API key with the given ID. Returns whether a key was revoked.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn revoke_api_key(
+    conn: &mut PoolConnection<Postgres>,
+    key_id: &str,
+) -> sqlx::Result<bool> {
+    let result = sqlx::query(
+        "UPDATE api_keys SET revoked_at = current_timestamp
+        WHERE key_id = $1 AND revoked_at IS NULL",
+    )
+    .bind(key_id)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 /// Return whether or not the given user (identified by a public key) owns the given indexer.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn indexer_owned_by(
@@ -947,3 +1825,342 @@ pub async fn put_many_to_many_record(
     execute_query(conn, query).await?;
     Ok(())
 }
+
+/// Record a lifecycle action taken on the given indexer, returning the recorded event.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn record_lifecycle_event(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    action: IndexerLifecycleAction,
+    block_height: Option<u64>,
+    actor: Option<&str>,
+) -> sqlx::Result<IndexerLifecycleEvent> {
+    let row = sqlx::query(
+        "INSERT INTO index_lifecycle_events
+            (namespace, identifier, action, state, block_height, actor)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING namespace, identifier, action, state, block_height, actor, created_at",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(action.as_ref())
+    .bind(action.state().as_ref())
+    .bind(block_height.map(|height| height as i64))
+    .bind(actor)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(lifecycle_event_from_row(&row))
+}
+
+/// Return the lifecycle events of the given indexer, oldest first.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn lifecycle_events(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerLifecycleEvent>> {
+    Ok(sqlx::query(
+        "SELECT namespace, identifier, action, state, block_height, actor, created_at
+        FROM index_lifecycle_events
+        WHERE namespace = $1 AND identifier = $2
+        ORDER BY id ASC",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?
+    .iter()
+    .map(lifecycle_event_from_row)
+    .collect())
+}
+
+/// Return the current state of the given indexer, which is the state after its
+/// latest lifecycle event. Indexers without any events are running.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn indexer_state(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<IndexerState> {
+    let state: Option<String> = sqlx::query_scalar(
+        "SELECT state FROM index_lifecycle_events
+        WHERE namespace = $1 AND identifier = $2
+        ORDER BY id DESC
+        LIMIT 1",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await?;
+
+    Ok(state
+        .and_then(|state| IndexerState::from_str(&state).ok())
+        .unwrap_or_default())
+}
+
+/// Build an `IndexerLifecycleEvent` from a row of the `index_lifecycle_events` table.
+fn lifecycle_event_from_row(row: &PgRow) -> IndexerLifecycleEvent {
+    let action: String = row.get(2);
+    let state: String = row.get(3);
+    let block_height: Option<i64> = row.get(4);
+    let created_at: DateTime<Utc> = {
+        let created_at: NaiveDateTime = row.get(6);
+        DateTime::<Utc>::from_utc(created_at, Utc)
+    };
+
+    IndexerLifecycleEvent {
+        namespace: row.get(0),
+        identifier: row.get(1),
+        action: IndexerLifecycleAction::from_str(&action)
+            .expect("Invalid lifecycle action."),
+        state: IndexerState::from_str(&state).unwrap_or_default(),
+        block_height: block_height.map(|height| height as u64),
+        actor: row.get(5),
+        created_at,
+    }
+}
+
+/// Delete all of the data indexed by the given indexer, along with its checkpoint,
+/// status, and discovered contracts, so that it can be reindexed from scratch.
+///
+/// The indexer's registration, assets, and schema are kept.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn reset_indexer_data(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT table_name FROM information_schema.tables
+        WHERE table_schema = $1 AND table_type = 'BASE TABLE'",
+    )
+    .bind(format!("{namespace}_{identifier}"))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    if !tables.is_empty() {
+        let tables = tables
+            .iter()
+            .map(|table| format!("{namespace}_{identifier}.{table}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        execute_query(conn, format!("TRUNCATE TABLE {tables}")).await?;
+    }
+
+    for table in [
+        "index_checkpoints",
+        "index_status",
+        "index_discovered_contracts",
+    ] {
+        execute_query(
+            conn,
+            format!(
+                "DELETE FROM {table} WHERE index_id IN
+                (SELECT id FROM index_registry
+                    WHERE namespace = '{namespace}' AND identifier = '{identifier}')"
+            ),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Route GraphQL queries for the given indexer to another indexer in the same
+/// namespace, replacing any existing route.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_query_route(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    target: &str,
+) -> sqlx::Result<()> {
+    let _ = sqlx::query(
+        "INSERT INTO index_query_routes (namespace, identifier, target, updated_at)
+        VALUES ($1, $2, $3, current_timestamp)
+        ON CONFLICT (namespace, identifier) DO UPDATE SET
+            target = EXCLUDED.target,
+            updated_at = EXCLUDED.updated_at",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(target)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Return the identifier of the indexer that serves GraphQL queries for the given
+/// indexer, if its queries are routed elsewhere.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn get_query_route(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<String>> {
+    sqlx::query_scalar(
+        "SELECT target FROM index_query_routes
+        WHERE namespace = $1 AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_optional(conn)
+    .await
+}
+
+/// Remove the query route of the given indexer, so that it serves its own queries.
+/// Returns whether a route was removed.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn remove_query_route(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<bool> {
+    let result = sqlx::query(
+        "DELETE FROM index_query_routes WHERE namespace = $1 AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Record an action taken on an indexer through the web API in the audit log.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_audit_log_entry(
+    conn: &mut PoolConnection<Postgres>,
+    action: &str,
+    namespace: &str,
+    identifier: &str,
+    principal: Option<&str>,
+    source_ip: Option<&str>,
+    manifest_digest: Option<&str>,
+) -> sqlx::Result<()> {
+    let _ = sqlx::query(
+        "INSERT INTO audit_log
+            (action, namespace, identifier, principal, source_ip, manifest_digest)
+        VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(action)
+    .bind(namespace)
+    .bind(identifier)
+    .bind(principal)
+    .bind(source_ip)
+    .bind(manifest_digest)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Return the latest entries of the audit log, newest first, optionally only those
+/// for a given namespace, indexer, or principal.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn audit_log_entries(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: Option<&str>,
+    identifier: Option<&str>,
+    principal: Option<&str>,
+    limit: u64,
+) -> sqlx::Result<Vec<AuditLogEntry>> {
+    Ok(sqlx::query(
+        "SELECT id, action, namespace, identifier, principal, source_ip,
+            manifest_digest, created_at
+        FROM audit_log
+        WHERE ($1::varchar IS NULL OR namespace = $1)
+        AND ($2::varchar IS NULL OR identifier = $2)
+        AND ($3::varchar IS NULL OR principal = $3)
+        ORDER BY id DESC
+        LIMIT $4",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(principal)
+    .bind(limit as i64)
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| {
+        let created_at: DateTime<Utc> = {
+            let created_at: NaiveDateTime = row.get(7);
+            DateTime::<Utc>::from_utc(created_at, Utc)
+        };
+
+        AuditLogEntry {
+            id: row.get(0),
+            action: row.get(1),
+            namespace: row.get(2),
+            identifier: row.get(3),
+            principal: row.get(4),
+            source_ip: row.get(5),
+            manifest_digest: row.get(6),
+            created_at,
+        }
+    })
+    .collect())
+}
+
+/// Create or update a logical replication publication covering exactly the given
+/// tables, and set the replica identity of each of them.
+///
+/// Existing publications are only updated if all of their tables are in the schemas
+/// of the given tables, so that publications of other indexers, or created by
+/// anyone else, are left as they are.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_publication(
+    conn: &mut PoolConnection<Postgres>,
+    publication: &str,
+    tables: &[String],
+    replica_identity: &str,
+) -> sqlx::Result<()> {
+    for table in tables {
+        execute_query(
+            conn,
+            format!("ALTER TABLE {table} REPLICA IDENTITY {replica_identity}"),
+        )
+        .await?;
+    }
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM pg_publication WHERE pubname = $1)",
+    )
+    .bind(publication)
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if exists {
+        let schemas: Vec<String> = sqlx::query_scalar(
+            "SELECT DISTINCT schemaname::TEXT FROM pg_publication_tables WHERE pubname = $1",
+        )
+        .bind(publication)
+        .fetch_all(&mut *conn)
+        .await?;
+        let owned = |schema: &String| {
+            tables
+                .iter()
+                .any(|table| table.split_once('.').map(|(s, _)| s) == Some(schema))
+        };
+        if !schemas.iter().all(owned) {
+            return Err(sqlx::Error::Protocol(format!(
+                "Publication({publication}) publishes tables of other schemas"
+            )));
+        }
+    }
+
+    // Publications can't be created if-not-exists, so an existing publication has
+    // its tables replaced, which also picks up tables added by a new schema version.
+    let tables = tables.join(", ");
+    let query = if exists {
+        format!("ALTER PUBLICATION {publication} SET TABLE {tables}")
+    } else {
+        format!("CREATE PUBLICATION {publication} FOR TABLE {tables}")
+    };
+    execute_query(conn, query).await?;
+
+    Ok(())
+}
@@ -99,6 +99,13 @@ impl IndexerConnectionPool {
         }
     }
 
+    /// Return the number of open connections in the pool, and how many of them are idle.
+    pub fn connections(&self) -> (u32, usize) {
+        match self {
+            IndexerConnectionPool::Postgres(p) => (p.size(), p.num_idle()),
+        }
+    }
+
     pub async fn acquire(&self) -> sqlx::Result<IndexerConnection> {
         match self {
             IndexerConnectionPool::Postgres(p) => {
@@ -1,5 +1,6 @@
 use crate::{types::*, IndexerConnection};
 use fuel_indexer_postgres as postgres;
+use futures_util::stream::BoxStream;
 use sqlx::types::{
     chrono::{DateTime, Utc},
     JsonValue,
@@ -142,6 +143,16 @@ pub async fn get_object(
     }
 }
 
+/// Fetch the blobs of serialized `FtColumns` of all rows matching a query.
+pub async fn get_objects(
+    conn: &mut IndexerConnection,
+    query: String,
+) -> sqlx::Result<Vec<Vec<u8>>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::get_objects(c, query).await,
+    }
+}
+
 /// Run an arbitrary query and fetch all results.
 ///
 /// Note that if the results of the query can't be converted to `JsonValue`, this function
@@ -165,6 +176,48 @@ pub async fn execute_query(
     }
 }
 
+/// Execute the given statements, separated by semicolons, in a single round trip.
+pub async fn execute_batch(
+    conn: &mut IndexerConnection,
+    statements: String,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::execute_batch(c, statements).await
+        }
+    }
+}
+
+/// Run a query with the given parameters and fetch all results, as `run_query` does.
+///
+/// The query is cached by the connection as a prepared statement.
+pub async fn run_statement(
+    conn: &mut IndexerConnection,
+    query: &str,
+    params: &[QueryParameter],
+) -> sqlx::Result<JsonValue> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::run_statement(c, query, params).await
+        }
+    }
+}
+
+/// Run a query with the given parameters and fetch all results, as `run_query` does.
+///
+/// The query isn't cached by the connection as a statement.
+pub async fn run_parameterized_query(
+    conn: &mut IndexerConnection,
+    query: &str,
+    params: &[QueryParameter],
+) -> sqlx::Result<JsonValue> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::run_parameterized_query(c, query, params).await
+        }
+    }
+}
+
 /// Return a set of `RootColumn`s associated with a given `GraphRoot`.
 pub async fn root_columns_list_by_id(
     conn: &mut IndexerConnection,
@@ -274,6 +327,20 @@ pub async fn latest_asset_for_indexer(
     }
 }
 
+/// Return the version and digest of the latest asset of the given type, without
+/// loading the asset's bytes.
+pub async fn latest_asset_digest(
+    conn: &mut IndexerConnection,
+    index_id: &i64,
+    asset_type: IndexerAssetType,
+) -> sqlx::Result<Option<(i32, String)>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::latest_asset_digest(c, index_id, asset_type).await
+        }
+    }
+}
+
 /// Return the latest version for every indexer asset type.
 pub async fn latest_assets_for_indexer(
     conn: &mut IndexerConnection,
@@ -368,6 +435,249 @@ pub async fn remove_indexer(
     }
 }
 
+/// Save a checkpoint for the given indexer, replacing any previous checkpoint.
+pub async fn put_checkpoint(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    block_height: u64,
+    snapshot: Vec<u8>,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_checkpoint(c, namespace, identifier, block_height, snapshot)
+                .await
+        }
+    }
+}
+
+/// Return the latest checkpoint for the given indexer, if any.
+pub async fn get_checkpoint(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerCheckpoint>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_checkpoint(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the names of the tables in the schema of the given indexer.
+pub async fn indexer_tables(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_tables(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the rows of one of the given indexer's tables, in the text format of
+/// PostgreSQL's `COPY`.
+pub async fn copy_table_out(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+) -> sqlx::Result<Vec<u8>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::copy_table_out(c, namespace, identifier, table).await
+        }
+    }
+}
+
+/// Return the names and data types of the columns of one of the given indexer's
+/// tables, in order.
+pub async fn table_columns(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+) -> sqlx::Result<Vec<(String, String)>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::table_columns(c, namespace, identifier, table).await
+        }
+    }
+}
+
+/// Stream the output of a `COPY ... TO STDOUT` statement as it is produced.
+pub async fn copy_out<'c>(
+    conn: &'c mut IndexerConnection,
+    statement: &str,
+) -> sqlx::Result<BoxStream<'c, sqlx::Result<Vec<u8>>>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::copy_out(c, statement).await,
+    }
+}
+
+/// Stream the rows of a query whose columns are all of type `text`.
+pub fn fetch_text_rows<'c>(
+    conn: &'c mut IndexerConnection,
+    query: &'c str,
+) -> BoxStream<'c, sqlx::Result<Vec<Option<String>>>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::fetch_text_rows(c, query),
+    }
+}
+
+/// Stream the JSON values in the first column of the rows of a query run with the
+/// given parameters, as they are read.
+pub fn fetch_json_rows<'c>(
+    conn: &'c mut IndexerConnection,
+    query: &'c str,
+    params: &'c [QueryParameter],
+) -> BoxStream<'c, sqlx::Result<JsonValue>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::fetch_json_rows(c, query, params)
+        }
+    }
+}
+
+/// Stream the JSON values in the first column of the rows of a query run with the
+/// given parameters, caching the query by the connection as a prepared statement.
+pub fn fetch_statement_json_rows<'c>(
+    conn: &'c mut IndexerConnection,
+    query: &'c str,
+    params: &'c [QueryParameter],
+) -> BoxStream<'c, sqlx::Result<JsonValue>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::fetch_statement_json_rows(c, query, params)
+        }
+    }
+}
+
+/// Insert rows, in the text format of PostgreSQL's `COPY`, into one of the given
+/// indexer's tables, returning the number of rows inserted.
+pub async fn copy_table_in(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    table: &str,
+    data: Vec<u8>,
+) -> sqlx::Result<u64> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::copy_table_in(c, namespace, identifier, table, data).await
+        }
+    }
+}
+
+/// Save the sync status of the given indexer, replacing any previous status.
+///
+/// `cpu_seconds` is the CPU time spent by the indexer's handlers since its status
+/// was last saved, which is added to its total.
+pub async fn put_indexer_status(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    current_height: u64,
+    chain_tip: u64,
+    blocks_per_second: f64,
+    rows_per_minute: f64,
+    cpu_seconds: f64,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_indexer_status(
+                c,
+                namespace,
+                identifier,
+                current_height,
+                chain_tip,
+                blocks_per_second,
+                rows_per_minute,
+                cpu_seconds,
+            )
+            .await
+        }
+    }
+}
+
+/// Save the last error raised by the given indexer while processing blocks, along
+/// with the block it failed on and how many times in a row it has failed on it.
+pub async fn put_indexer_error(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    error: &str,
+    failed_height: Option<u64>,
+    consecutive_failures: u32,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_indexer_error(
+                c,
+                namespace,
+                identifier,
+                error,
+                failed_height,
+                consecutive_failures,
+            )
+            .await
+        }
+    }
+}
+
+/// Return the sync status of the given indexer, if any has been reported.
+pub async fn get_indexer_status(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<IndexerStatus>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_indexer_status(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the sync status of all indexers that have reported one.
+pub async fn all_indexer_statuses(
+    conn: &mut IndexerConnection,
+) -> sqlx::Result<Vec<IndexerStatus>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::all_indexer_statuses(c).await,
+    }
+}
+
+/// Register a contract discovered by the given indexer at runtime.
+pub async fn register_contract(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    template: &str,
+    contract_id: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::register_contract(c, namespace, identifier, template, contract_id)
+                .await
+        }
+    }
+}
+
+/// Return all contracts discovered by the given indexer at runtime.
+pub async fn discovered_contracts(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<DiscoveredContract>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::discovered_contracts(c, namespace, identifier).await
+        }
+    }
+}
+
 /// Create a new nonce for a requesting user's authentication.
 pub async fn create_nonce(conn: &mut IndexerConnection) -> sqlx::Result<Nonce> {
     match conn {
@@ -394,6 +704,17 @@ pub async fn delete_nonce(
     }
 }
 
+/// Delete the specified nonce if it hasn't expired, returning it, so that it can be
+/// used to authenticate once.
+pub async fn consume_nonce(
+    conn: &mut IndexerConnection,
+    uid: &str,
+) -> sqlx::Result<Option<Nonce>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::consume_nonce(c, uid).await,
+    }
+}
+
 /// Return whether or not the given user (identified by a public key) owns the given indexer.
 pub async fn indexer_owned_by(
     conn: &mut IndexerConnection,
@@ -420,3 +741,299 @@ pub async fn put_many_to_many_record(
         }
     }
 }
+
+/// Record a deployment of the given indexer assets, returning the deployment's version.
+pub async fn record_deployment(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    assets: &IndexerAssetBundle,
+    rollback_of: Option<i32>,
+    signer: Option<&str>,
+) -> sqlx::Result<i32> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::record_deployment(
+                c,
+                namespace,
+                identifier,
+                assets,
+                rollback_of,
+                signer,
+            )
+            .await
+        }
+    }
+}
+
+/// Record the IPFS CIDs of the assets of the given deployment of an indexer.
+pub async fn set_deployment_cids(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    version: i32,
+    manifest_cid: &str,
+    schema_cid: &str,
+    wasm_cid: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::set_deployment_cids(
+                c,
+                namespace,
+                identifier,
+                version,
+                manifest_cid,
+                schema_cid,
+                wasm_cid,
+            )
+            .await
+        }
+    }
+}
+
+/// Create a new API key with the given name and scopes, returning the key and its
+/// full value.
+pub async fn create_api_key(
+    conn: &mut IndexerConnection,
+    name: &str,
+    owner: &str,
+    scopes: &[String],
+) -> sqlx::Result<(ApiKey, String)> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::create_api_key(c, name, owner, scopes).await
+        }
+    }
+}
+
+/// Return the API keys created by the given owner, or all API keys if no owner
+/// is given.
+pub async fn api_keys(
+    conn: &mut IndexerConnection,
+    owner: Option<&str>,
+) -> sqlx::Result<Vec<ApiKey>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::api_keys(c, owner).await,
+    }
+}
+
+/// Return the API key with the given ID, if it exists.
+pub async fn get_api_key(
+    conn: &mut IndexerConnection,
+    key_id: &str,
+) -> sqlx::Result<Option<ApiKey>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::get_api_key(c, key_id).await,
+    }
+}
+
+/// Return the API key matching the given full key value, if it exists and has not
+/// been revoked.
+pub async fn verify_api_key(
+    conn: &mut IndexerConnection,
+    key: &str,
+) -> sqlx::Result<Option<ApiKey>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::verify_api_key(c, key).await,
+    }
+}
+
+/// Revoke the API key with the given ID. Returns whether a key was revoked.
+pub async fn revoke_api_key(
+    conn: &mut IndexerConnection,
+    key_id: &str,
+) -> sqlx::Result<bool> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::revoke_api_key(c, key_id).await
+        }
+    }
+}
+
+/// Return all deployments of the given indexer, oldest first.
+pub async fn indexer_deployments(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerDeployment>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_deployments(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the assets of the given deployment of an indexer, if it exists.
+pub async fn deployment_assets(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    version: i32,
+) -> sqlx::Result<Option<Vec<(IndexerAssetType, Vec<u8>)>>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::deployment_assets(c, namespace, identifier, version).await
+        }
+    }
+}
+
+/// Record a lifecycle action taken on the given indexer, returning the recorded event.
+pub async fn record_lifecycle_event(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    action: IndexerLifecycleAction,
+    block_height: Option<u64>,
+    actor: Option<&str>,
+) -> sqlx::Result<IndexerLifecycleEvent> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::record_lifecycle_event(
+                c,
+                namespace,
+                identifier,
+                action,
+                block_height,
+                actor,
+            )
+            .await
+        }
+    }
+}
+
+/// Return the lifecycle events of the given indexer, oldest first.
+pub async fn lifecycle_events(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<IndexerLifecycleEvent>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::lifecycle_events(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the current state of the given indexer.
+pub async fn indexer_state(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<IndexerState> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_state(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Delete all of the data indexed by the given indexer, so that it can be reindexed.
+pub async fn reset_indexer_data(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::reset_indexer_data(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Route GraphQL queries for the given indexer to another indexer in the same namespace.
+pub async fn put_query_route(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    target: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_query_route(c, namespace, identifier, target).await
+        }
+    }
+}
+
+/// Return the identifier of the indexer that serves GraphQL queries for the given
+/// indexer, if its queries are routed elsewhere.
+pub async fn get_query_route(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<String>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::get_query_route(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Remove the query route of the given indexer. Returns whether a route was removed.
+pub async fn remove_query_route(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<bool> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::remove_query_route(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Record an action taken on an indexer through the web API in the audit log.
+pub async fn put_audit_log_entry(
+    conn: &mut IndexerConnection,
+    action: &str,
+    namespace: &str,
+    identifier: &str,
+    principal: Option<&str>,
+    source_ip: Option<&str>,
+    manifest_digest: Option<&str>,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_audit_log_entry(
+                c,
+                action,
+                namespace,
+                identifier,
+                principal,
+                source_ip,
+                manifest_digest,
+            )
+            .await
+        }
+    }
+}
+
+/// Return the latest entries of the audit log, newest first.
+pub async fn audit_log_entries(
+    conn: &mut IndexerConnection,
+    namespace: Option<&str>,
+    identifier: Option<&str>,
+    principal: Option<&str>,
+    limit: u64,
+) -> sqlx::Result<Vec<AuditLogEntry>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::audit_log_entries(c, namespace, identifier, principal, limit).await
+        }
+    }
+}
+
+/// Create or update a logical replication publication covering exactly the given
+/// tables, and set the replica identity of each of them.
+pub async fn put_publication(
+    conn: &mut IndexerConnection,
+    publication: &str,
+    tables: &[String],
+    replica_identity: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_publication(c, publication, tables, replica_identity).await
+        }
+    }
+}
@@ -0,0 +1,36 @@
+#![no_main]
+
+use fuel_indexer_database_types::DbType;
+use fuel_indexer_graphql::graphql::plan_query;
+use fuel_indexer_lib::{config::PageInfoCount, graphql::GraphQLSchema, ExecutionSource};
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use libfuzzer_sys::fuzz_target;
+
+const SCHEMA: &str = r#"
+type Block @entity {
+    id: ID!
+    height: UInt8!
+    timestamp: Int8!
+}
+
+type Tx @entity {
+    id: ID!
+    timestamp: Int8!
+    block: Block
+    input_data: Json!
+}
+"#;
+
+fuzz_target!(|query: &str| {
+    let schema = IndexerSchema::new(
+        "fuzz",
+        "indexer",
+        &GraphQLSchema::new(SCHEMA.to_string()),
+        DbType::Postgres,
+        ExecutionSource::Wasm,
+    )
+    .expect("Invalid fuzzing schema.");
+
+    // Planning must return an error for bad queries, never panic.
+    let _ = plan_query(&schema, query, DbType::Postgres, PageInfoCount::Exact);
+});
@@ -1,5 +1,5 @@
 use super::graphql::GraphqlError;
-use fuel_indexer_database::DbType;
+use fuel_indexer_database::{types::QueryParameter, DbType};
 use fuel_indexer_schema::db::tables::IndexerSchema;
 
 use async_graphql_value::{indexmap::IndexMap, Name, Value};
@@ -41,14 +41,18 @@ impl QueryParams {
     }
 
     /// Return a string comprised of the query's filtering clauses, if any.
-    pub(crate) fn get_filtering_expression(&self, db_type: &DbType) -> String {
+    pub(crate) fn get_filtering_expression(
+        &self,
+        db_type: &DbType,
+        bindings: &mut Bindings,
+    ) -> String {
         let mut query_clause = "".to_string();
 
         if !self.filters.is_empty() {
             let where_expressions = self
                 .filters
                 .iter()
-                .map(|f| f.to_sql(db_type))
+                .map(|f| f.to_sql(db_type, bindings))
                 .collect::<Vec<String>>()
                 .join(" AND ");
             query_clause =
@@ -58,6 +62,27 @@ impl QueryParams {
         query_clause
     }
 
+    /// Return a string comprised of the query's filtering clauses on the given table,
+    /// if any.
+    pub(crate) fn get_table_filtering_expression(
+        &self,
+        table: &str,
+        db_type: &DbType,
+        bindings: &mut Bindings,
+    ) -> String {
+        let where_expressions = self
+            .filters
+            .iter()
+            .filter(|f| f.fully_qualified_table_name == table)
+            .map(|f| f.to_sql(db_type, bindings))
+            .collect::<Vec<String>>();
+
+        if where_expressions.is_empty() {
+            return "".to_string();
+        }
+        format!("WHERE {}", where_expressions.join(" AND "))
+    }
+
     /// Return a string comprised of modifiers to the order of the result set, if any.
     pub(crate) fn get_ordering_modififer(&self, db_type: &DbType) -> String {
         let mut query_clause = "".to_string();
@@ -89,9 +114,12 @@ pub struct Filter {
 }
 
 impl Filter {
-    pub fn to_sql(&self, db_type: &DbType) -> String {
-        self.filter_type
-            .to_sql(self.fully_qualified_table_name.clone(), db_type)
+    pub fn to_sql(&self, db_type: &DbType, bindings: &mut Bindings) -> String {
+        self.filter_type.to_sql(
+            self.fully_qualified_table_name.clone(),
+            db_type,
+            bindings,
+        )
     }
 }
 
@@ -129,6 +157,7 @@ impl fmt::Display for SortOrder {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParsedValue {
     BigNumber(u128),
+    SignedBigNumber(i128),
     Number(u64),
     String(String),
     Boolean(bool),
@@ -145,19 +174,102 @@ impl fmt::Display for ParsedValue {
             Self::BigNumber(bn) => {
                 write!(f, "{bn}")
             }
+            Self::SignedBigNumber(bn) => {
+                write!(f, "{bn}")
+            }
             Self::Boolean(b) => {
                 write!(f, "{b}")
             }
             Self::Number(n) => {
                 write!(f, "{n}")
             }
+            // Quotes are doubled, so that the value can't end the string literal.
             Self::String(s) => {
-                write!(f, "\'{s}\'")
+                write!(f, "'{}'", s.replace('\'', "''"))
             }
         }
     }
 }
 
+impl ParsedValue {
+    /// Return the value as the parameter of a prepared statement.
+    ///
+    /// Numbers are bound as `bigint`s, which compare against integer and numeric
+    /// columns without casting the column, unless they're too large for one.
+    pub fn to_parameter(&self) -> QueryParameter {
+        match self {
+            Self::BigNumber(bn) => QueryParameter::Numeric(bn.to_string()),
+            Self::SignedBigNumber(bn) => QueryParameter::Numeric(bn.to_string()),
+            Self::Boolean(b) => QueryParameter::Boolean(*b),
+            Self::Number(n) => i64::try_from(*n)
+                .map(QueryParameter::BigInt)
+                .unwrap_or_else(|_| QueryParameter::Numeric(n.to_string())),
+            Self::String(s) => QueryParameter::Text(s.clone()),
+        }
+    }
+}
+
+/// Renders the values of a query's parameters into its SQL.
+///
+/// Values are either written into the SQL, or replaced by numbered placeholders
+/// and collected in order, so that queries differing only in their values have
+/// the same SQL and can share a prepared statement.
+#[derive(Debug, Clone, Default)]
+pub struct Bindings {
+    /// Whether values are replaced by placeholders.
+    parameterized: bool,
+
+    /// Values replaced by placeholders, in order of their placeholders.
+    values: Vec<ParsedValue>,
+}
+
+impl Bindings {
+    /// Create bindings that write values into the SQL, for planned queries that are
+    /// inspected rather than run. Queries are always run with their values bound as
+    /// parameters.
+    pub fn inline() -> Self {
+        Self::default()
+    }
+
+    /// Create bindings that replace values by placeholders.
+    pub fn parameterized() -> Self {
+        Self {
+            parameterized: true,
+            values: Vec::new(),
+        }
+    }
+
+    /// Return the SQL of the given value. Placeholders have the type the value is
+    /// bound with, with numbers too large for a `bigint` cast to `numeric`.
+    pub fn bind(&mut self, value: &ParsedValue) -> String {
+        if !self.parameterized {
+            return value.to_string();
+        }
+        self.values.push(value.clone());
+        match value.to_parameter() {
+            QueryParameter::Numeric(_) => format!("${}::numeric", self.values.len()),
+            _ => format!("${}", self.values.len()),
+        }
+    }
+
+    /// Return the SQL of the given value, cast to the given type if it's replaced
+    /// by a placeholder, for values whose type can't be inferred from where they're
+    /// used.
+    pub fn bind_as(&mut self, value: &ParsedValue, sql_type: &str) -> String {
+        let sql = self.bind(value);
+        if self.parameterized {
+            format!("{sql}::{sql_type}")
+        } else {
+            sql
+        }
+    }
+
+    /// Return the values replaced by placeholders, in order of their placeholders.
+    pub fn into_values(self) -> Vec<ParsedValue> {
+        self.values
+    }
+}
+
 /// Represents an operation through which records can be included or excluded.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilterType {
@@ -204,45 +316,72 @@ pub enum LogicOp {
 
 impl FilterType {
     /// Returns a string to be used as part of a SQL database query.
-    pub fn to_sql(&self, fully_qualified_table: String, db_type: &DbType) -> String {
+    pub fn to_sql(
+        &self,
+        fully_qualified_table: String,
+        db_type: &DbType,
+        bindings: &mut Bindings,
+    ) -> String {
         match db_type {
             DbType::Postgres => match self {
                 Self::Comparison(c) => match c {
                     Comparison::Between(field, min, max) => {
-                        format!("{fully_qualified_table}.{field} BETWEEN {min} AND {max}",)
+                        format!(
+                            "{fully_qualified_table}.{field} BETWEEN {} AND {}",
+                            bindings.bind(min),
+                            bindings.bind(max)
+                        )
                     }
                     Comparison::Equals(field, val) => {
-                        format!("{fully_qualified_table}.{field} = {val}",)
+                        format!(
+                            "{fully_qualified_table}.{field} = {}",
+                            bindings.bind(val)
+                        )
                     }
                     Comparison::NotEquals(field, val) => {
-                        format!("{fully_qualified_table}.{field} <> {val}",)
+                        format!(
+                            "{fully_qualified_table}.{field} <> {}",
+                            bindings.bind(val)
+                        )
                     }
                     Comparison::Greater(field, val) => {
-                        format!("{fully_qualified_table}.{field} > {val}",)
+                        format!(
+                            "{fully_qualified_table}.{field} > {}",
+                            bindings.bind(val)
+                        )
                     }
                     Comparison::GreaterEqual(field, val) => {
-                        format!("{fully_qualified_table}.{field} >= {val}",)
+                        format!(
+                            "{fully_qualified_table}.{field} >= {}",
+                            bindings.bind(val)
+                        )
                     }
                     Comparison::Less(field, val) => {
-                        format!("{fully_qualified_table}.{field} < {val}",)
+                        format!(
+                            "{fully_qualified_table}.{field} < {}",
+                            bindings.bind(val)
+                        )
                     }
                     Comparison::LessEqual(field, val) => {
-                        format!("{fully_qualified_table}.{field} <= {val}",)
+                        format!(
+                            "{fully_qualified_table}.{field} <= {}",
+                            bindings.bind(val)
+                        )
                     }
                 },
                 Self::IdSelection(id) => {
-                    format!("{fully_qualified_table}.id = {id}")
+                    format!("{fully_qualified_table}.id = {}", bindings.bind(id))
                 }
                 Self::LogicOp(lo) => match lo {
                     LogicOp::And(r1, r2) => format!(
                         "({} AND {})",
-                        r1.to_sql(fully_qualified_table.clone(), db_type),
-                        r2.to_sql(fully_qualified_table, db_type)
+                        r1.to_sql(fully_qualified_table.clone(), db_type, bindings),
+                        r2.to_sql(fully_qualified_table, db_type, bindings)
                     ),
                     LogicOp::Or(r1, r2) => format!(
                         "({} OR {})",
-                        r1.to_sql(fully_qualified_table.clone(), db_type),
-                        r2.to_sql(fully_qualified_table, db_type)
+                        r1.to_sql(fully_qualified_table.clone(), db_type, bindings),
+                        r2.to_sql(fully_qualified_table, db_type, bindings)
                     ),
                     // The NOT logical operator does not get turned into a string as
                     // it will have already been used to transform a filter into its
@@ -255,7 +394,7 @@ impl FilterType {
                             "{fully_qualified_table}.{field} IN ({})",
                             member_set
                                 .iter()
-                                .map(|v| v.to_string())
+                                .map(|v| bindings.bind(v))
                                 .collect::<Vec<String>>()
                                 .join(", ")
                         )
@@ -265,7 +404,7 @@ impl FilterType {
                             "{fully_qualified_table}.{field} NOT IN ({})",
                             member_set
                                 .iter()
-                                .map(|v| v.to_string())
+                                .map(|v| bindings.bind(v))
                                 .collect::<Vec<String>>()
                                 .join(", ")
                         )
@@ -570,7 +709,8 @@ fn parse_arg_pred_pair(
             }
         }
         other => {
-            if schema.parsed().graphql_type(entity_type, other).is_some() {
+            if let Some(field_type) = schema.parsed().graphql_type(entity_type, other) {
+                let parse_field = |value: &Value| parse_field_value(value, field_type);
                 if let Value::Object(inner_obj) = predicate {
                     for (key, predicate) in inner_obj.iter() {
                         match key.as_str() {
@@ -581,7 +721,7 @@ fn parse_arg_pred_pair(
                                         complex_comparison_obj.get("max"),
                                     ) {
                                         let (min, max) =
-                                            (parse_value(min)?, parse_value(max)?);
+                                            (parse_field(min)?, parse_field(max)?);
                                         return Ok(FilterType::Comparison(
                                             Comparison::Between(
                                                 other.to_string(),
@@ -595,40 +735,40 @@ fn parse_arg_pred_pair(
                             "equals" => {
                                 return Ok(FilterType::Comparison(Comparison::Equals(
                                     other.to_string(),
-                                    parse_value(predicate)?,
+                                    parse_field(predicate)?,
                                 )))
                             }
                             "gt" => {
                                 return Ok(FilterType::Comparison(Comparison::Greater(
                                     other.to_string(),
-                                    parse_value(predicate)?,
+                                    parse_field(predicate)?,
                                 )))
                             }
                             "gte" => {
                                 return Ok(FilterType::Comparison(
                                     Comparison::GreaterEqual(
                                         other.to_string(),
-                                        parse_value(predicate)?,
+                                        parse_field(predicate)?,
                                     ),
                                 ));
                             }
                             "lt" => {
                                 return Ok(FilterType::Comparison(Comparison::Less(
                                     other.to_string(),
-                                    parse_value(predicate)?,
+                                    parse_field(predicate)?,
                                 )))
                             }
                             "lte" => {
                                 return Ok(FilterType::Comparison(Comparison::LessEqual(
                                     other.to_string(),
-                                    parse_value(predicate)?,
+                                    parse_field(predicate)?,
                                 )))
                             }
                             "in" => {
                                 if let Value::List(elements) = predicate {
                                     let parsed_elements = elements
                                             .iter()
-                                            .map(parse_value)
+                                            .map(&parse_field)
                                             .collect::<Result<Vec<ParsedValue>,GraphqlError>>();
                                     if let Ok(elements) = parsed_elements {
                                         return Ok(FilterType::Membership(
@@ -739,6 +879,43 @@ fn parse_binary_logical_operator(
     }
 }
 
+/// Parse a value compared to a field of the given type into a `ParsedValue`.
+///
+/// 128-bit integers don't fit in a GraphQL `Int`, so values compared to fields of
+/// those types may also be given as strings, and are checked to be in range.
+fn parse_field_value(
+    value: &Value,
+    field_type: &str,
+) -> Result<ParsedValue, GraphqlError> {
+    let digits = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => return parse_value(value),
+    };
+
+    match field_type {
+        "I128" | "Int16" => digits
+            .parse::<i128>()
+            .map(ParsedValue::SignedBigNumber)
+            .map_err(|_| {
+                GraphqlError::UnableToParseValue(format!(
+                    "Could not parse {digits} into i128"
+                ))
+            }),
+        "U128" | "UInt16" => {
+            digits
+                .parse::<u128>()
+                .map(ParsedValue::BigNumber)
+                .map_err(|_| {
+                    GraphqlError::UnableToParseValue(format!(
+                        "Could not parse {digits} into u128"
+                    ))
+                })
+        }
+        _ => parse_value(value),
+    }
+}
+
 /// Parse a value from the parsed GraphQL document into a `ParsedValue` for use in the indexer.
 ///
 /// Value types from the parsed GraphQL query should be turned into `ParsedValue`
@@ -11,11 +11,17 @@ use async_graphql::{
 use async_graphql_parser::types::{BaseType, Type};
 use async_graphql_value::Name;
 use fuel_indexer_database::{queries, IndexerConnectionPool};
+use fuel_indexer_lib::{config::PageInfoCount, fully_qualified_namespace};
 use fuel_indexer_schema::db::tables::IndexerSchema;
 use lazy_static::lazy_static;
 use serde_json::Value;
 
-use crate::graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult};
+use crate::{
+    federation,
+    graphql::{plan_parameterized_query, GraphqlError, GraphqlResult},
+    queries::ParameterizedQuery,
+    statements::StatementCache,
+};
 
 lazy_static! {
     /// Scalar types supported by the Fuel indexer. These should always stay up-to-date
@@ -35,6 +41,7 @@ lazy_static! {
         "Color",
         "ContractId",
         "HexString",
+        "I128",
         "ID",
         "Identity",
         "Int1",
@@ -50,6 +57,7 @@ lazy_static! {
         "Tai64Timestamp",
         "Timestamp",
         "TxId",
+        "U128",
         "UInt1",
         "UInt16",
         "UInt4",
@@ -103,6 +111,7 @@ lazy_static! {
         "Color",
         "ContractId",
         "HexString",
+        "I128",
         "ID",
         "Identity",
         "Int16",
@@ -114,6 +123,7 @@ lazy_static! {
         "Signature",
         "Tai64Timestamp",
         "Timestamp",
+        "U128",
         "UInt16",
         "UInt4",
         "UInt8",
@@ -132,14 +142,45 @@ lazy_static! {
         HashSet::from(["object"]);
 }
 
-/// Execute user query and return results.
-pub async fn execute_query(
+/// A user query, prepared to be run.
+#[derive(Debug)]
+pub enum PreparedQuery {
+    /// Data of the query's response, for queries that were resolved while being
+    /// prepared, i.e., introspection and federation queries.
+    Resolved(Value),
+
+    /// Queries whose rows are the JSON values making up the data of the query's
+    /// response, each run with its values bound as parameters, one after the other.
+    /// Queries with several root selections, or any query if statements aren't
+    /// prepared, are run this way.
+    Queries(Vec<ParameterizedQuery>),
+
+    /// A query of a shape whose SQL is kept as a prepared statement, to be run with
+    /// the query's values bound to the statement.
+    Statement {
+        /// Name of the prepared statement.
+        name: String,
+
+        /// SQL of the statement, and the values it's run with.
+        query: ParameterizedQuery,
+    },
+}
+
+/// Prepare a user query to be run, resolving it if it doesn't query entities
+/// directly.
+///
+/// Values of the query are always bound as parameters. Queries with a single root
+/// selection are run as the prepared statement of their shape, unless the given
+/// cache doesn't prepare statements.
+pub async fn prepare_query(
     dynamic_request: Request,
     dynamic_schema: DynamicSchema,
     user_query: String,
     pool: IndexerConnectionPool,
     schema: IndexerSchema,
-) -> GraphqlResult<Value> {
+    statements: &StatementCache,
+    page_info_count: PageInfoCount,
+) -> GraphqlResult<PreparedQuery> {
     // Because the schema types from async-graphql expect each field to be resolved
     // separately, it became untenable to use the .execute() method of the dynamic
     // schema itself to resolve queries. Instead, we set it to only resolve
@@ -150,24 +191,105 @@ pub async fn execute_query(
             let introspection_results = dynamic_schema.execute(dynamic_request).await;
             let data = introspection_results.data.into_json()?;
 
-            Ok(data)
+            Ok(PreparedQuery::Resolved(data))
         }
         Some(_) | None => {
-            let query =
-                GraphqlQueryBuilder::new(&schema, user_query.as_str())?.build()?;
+            if let Some(data) = federation::execute_federation_query(
+                &dynamic_request,
+                &dynamic_schema,
+                &pool,
+                &schema,
+            )
+            .await?
+            {
+                return Ok(PreparedQuery::Resolved(data));
+            }
 
-            let queries = query.as_sql(&schema, pool.database_type())?.join(";\n");
+            let mut planned = plan_parameterized_query(
+                &schema,
+                user_query.as_str(),
+                pool.database_type(),
+                page_info_count,
+            )?;
+
+            if planned.len() == 1 {
+                let indexer_uid = fully_qualified_namespace(
+                    schema.parsed().namespace(),
+                    schema.parsed().identifier(),
+                );
+                if let Some(name) = statements.statement_name(
+                    &indexer_uid,
+                    schema.version(),
+                    &planned[0].sql,
+                ) {
+                    let query = planned.remove(0);
+                    return Ok(PreparedQuery::Statement { name, query });
+                }
+            }
+
+            Ok(PreparedQuery::Queries(planned))
+        }
+    }
+}
 
-            let mut conn = match pool.acquire().await {
-                Ok(c) => c,
-                Err(e) => return Err(GraphqlError::QueryError(e.to_string())),
-            };
+/// Execute user query and return results.
+pub async fn execute_query(
+    dynamic_request: Request,
+    dynamic_schema: DynamicSchema,
+    user_query: String,
+    pool: IndexerConnectionPool,
+    schema: IndexerSchema,
+    statements: &StatementCache,
+    page_info_count: PageInfoCount,
+) -> GraphqlResult<Value> {
+    let prepared = prepare_query(
+        dynamic_request,
+        dynamic_schema,
+        user_query,
+        pool.clone(),
+        schema,
+        statements,
+        page_info_count,
+    )
+    .await?;
+
+    let mut conn = match pool.acquire().await {
+        Ok(c) => c,
+        Err(e) => return Err(GraphqlError::QueryError(e.to_string())),
+    };
 
-            match queries::run_query(&mut conn, queries).await {
-                Ok(r) => Ok(r),
-                Err(e) => Err(GraphqlError::QueryError(e.to_string())),
+    let result = match prepared {
+        PreparedQuery::Resolved(data) => return Ok(data),
+        PreparedQuery::Queries(planned) => {
+            let mut rows = Vec::new();
+            for query in planned {
+                match queries::run_parameterized_query(
+                    &mut conn,
+                    &query.sql,
+                    &query.parameters(),
+                )
+                .await
+                {
+                    Ok(Value::Array(values)) => rows.extend(values),
+                    Ok(value) => rows.push(value),
+                    Err(e) => return Err(GraphqlError::QueryError(e.to_string())),
+                }
             }
+            Ok(Value::Array(rows))
+        }
+        PreparedQuery::Statement { name, query } => {
+            queries::run_statement(
+                &mut conn,
+                &query.statement_sql(&name),
+                &query.parameters(),
+            )
+            .await
         }
+    };
+
+    match result {
+        Ok(r) => Ok(r),
+        Err(e) => Err(GraphqlError::QueryError(e.to_string())),
     }
 }
 
@@ -340,6 +462,14 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             .into_iter()
             .fold(Object::new(entity_type.clone()), |obj, f| obj.field(f));
 
+        // Entities are keyed by their ID, so that the indexer can be composed into
+        // a federated supergraph.
+        let obj = if field_map.contains_key("id") {
+            obj.key("id")
+        } else {
+            obj
+        };
+
         // Create field for entity object and add it to root level query object.
         let field = create_field_with_assoc_args(
             entity_type.to_string().to_lowercase(),
@@ -480,6 +610,8 @@ fn create_filter_val_and_objects_for_field<'a>(
 ) -> (InputValue, Vec<InputObject>) {
     let mut input_objs: Vec<InputObject> = Vec::new();
 
+    // `I128` and `U128` values don't fit in a GraphQL `Int`, so they're filtered by
+    // strings.
     let filter_arg_type = if NUMERIC_SCALAR_TYPES.contains(field_type) {
         TypeRef::INT
     } else {
@@ -0,0 +1,363 @@
+//! Apollo Federation support, so that an indexer can be composed into a supergraph
+//! as a subgraph.
+//!
+//! Every entity is keyed by its `id`. A gateway fetches the indexer's SDL with the
+//! `_service` query, and resolves references to its entities with the `_entities`
+//! query. Both are answered here, since the custom query resolver only handles
+//! queries of the indexer's own entities.
+
+use async_graphql::{dynamic::Schema as DynamicSchema, Request, SDLExportOptions};
+use async_graphql_parser::{
+    parse_query,
+    types::{
+        DocumentOperations, Field, FragmentDefinition, OperationDefinition, Selection,
+        SelectionSet,
+    },
+    Positioned,
+};
+use async_graphql_value::Name;
+use fuel_indexer_database::{queries, IndexerConnection, IndexerConnectionPool};
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    future::Future,
+    pin::Pin,
+};
+
+use crate::graphql::{GraphqlError, GraphqlResult};
+
+/// Name of the root query type of an indexer's schema.
+const QUERY_ROOT: &str = "QueryRoot";
+
+/// Fragments of a query, by name.
+type Fragments = HashMap<Name, Positioned<FragmentDefinition>>;
+
+/// Return the SDL of the given schema, with the directives Apollo Federation expects.
+pub fn federation_sdl(dynamic_schema: &DynamicSchema) -> String {
+    dynamic_schema.sdl_with_options(SDLExportOptions::new().federation())
+}
+
+/// Execute the given request if it's a federation query, i.e., one that selects
+/// `_service` or `_entities`. Returns `None` for any other query.
+pub async fn execute_federation_query(
+    request: &Request,
+    dynamic_schema: &DynamicSchema,
+    pool: &IndexerConnectionPool,
+    schema: &IndexerSchema,
+) -> GraphqlResult<Option<Value>> {
+    let document = parse_query(&request.query)?;
+
+    let operation = match (&document.operations, request.operation_name.as_deref()) {
+        (DocumentOperations::Single(operation), _) => &operation.node,
+        (DocumentOperations::Multiple(operations), Some(name)) => {
+            match operations.get(name) {
+                Some(operation) => &operation.node,
+                None => return Ok(None),
+            }
+        }
+        (DocumentOperations::Multiple(_), None) => return Ok(None),
+    };
+
+    let fields = root_fields(operation);
+    if !fields
+        .iter()
+        .any(|f| matches!(f.name.node.as_str(), "_service" | "_entities"))
+    {
+        return Ok(None);
+    }
+
+    let mut data = Map::new();
+    for field in fields {
+        let key = field.alias.as_ref().unwrap_or(&field.name).node.to_string();
+        let value = match field.name.node.as_str() {
+            "__typename" => Value::String(QUERY_ROOT.to_string()),
+            "_service" => {
+                serde_json::json!({ "sdl": federation_sdl(dynamic_schema) })
+            }
+            "_entities" => {
+                let representations = field
+                    .get_argument("representations")
+                    .ok_or_else(|| {
+                        GraphqlError::UnrecognizedArgument(
+                            "_entities".to_string(),
+                            "representations".to_string(),
+                        )
+                    })?
+                    .node
+                    .clone()
+                    .into_const_with(|name| {
+                        request.variables.get(&name).cloned().ok_or_else(|| {
+                            GraphqlError::UnableToParseValue(name.to_string())
+                        })
+                    })?
+                    .into_json()?;
+
+                let mut conn = pool
+                    .acquire()
+                    .await
+                    .map_err(|e| GraphqlError::QueryError(e.to_string()))?;
+                Value::Array(
+                    resolve_entities(
+                        &mut conn,
+                        schema,
+                        representations,
+                        &field.selection_set.node,
+                        &document.fragments,
+                    )
+                    .await?,
+                )
+            }
+            // Federation queries are only sent by gateways, which never mix them
+            // with queries of the indexer's entities.
+            name => {
+                return Err(GraphqlError::UnrecognizedField(
+                    QUERY_ROOT.to_string(),
+                    name.to_string(),
+                ))
+            }
+        };
+        data.insert(key, value);
+    }
+
+    Ok(Some(Value::Object(data)))
+}
+
+/// Return the fields selected at the root of the given operation.
+fn root_fields(operation: &OperationDefinition) -> Vec<&Field> {
+    operation
+        .selection_set
+        .node
+        .items
+        .iter()
+        .filter_map(|selection| match &selection.node {
+            Selection::Field(field) => Some(&field.node),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Resolve entity representations (e.g., `{ "__typename": "Transfer", "id": "1" }`)
+/// to the selected fields of the entities they refer to, in the same order.
+/// Representations of entities that don't exist resolve to `null`.
+async fn resolve_entities(
+    conn: &mut IndexerConnection,
+    schema: &IndexerSchema,
+    representations: Value,
+    selection_set: &SelectionSet,
+    fragments: &Fragments,
+) -> GraphqlResult<Vec<Value>> {
+    let representations = match representations {
+        Value::Array(representations) => representations,
+        other => return Err(GraphqlError::UnsupportedValueType(other.to_string())),
+    };
+
+    let references = representations
+        .iter()
+        .map(|representation| {
+            let entity = representation["__typename"]
+                .as_str()
+                .ok_or_else(|| {
+                    GraphqlError::UnsupportedValueType(representation.to_string())
+                })?
+                .to_string();
+            Ok((entity, entity_id(&representation["id"])))
+        })
+        .collect::<GraphqlResult<Vec<_>>>()?;
+
+    let mut ids = BTreeMap::<&str, BTreeSet<u64>>::new();
+    for (entity, id) in references.iter() {
+        let entity_ids = ids.entry(entity.as_str()).or_default();
+        if let Some(id) = id {
+            entity_ids.insert(*id);
+        }
+    }
+
+    let mut entities = HashMap::new();
+    for (entity, ids) in ids {
+        let mut fields = Vec::new();
+        collect_fields(entity, selection_set, fragments, &mut fields);
+        let ids = ids.into_iter().collect::<Vec<_>>();
+        for (id, object) in fetch(conn, schema, entity, &ids, &fields).await? {
+            entities.insert((entity.to_string(), id), object);
+        }
+    }
+
+    Ok(references
+        .into_iter()
+        .map(|(entity, id)| {
+            id.and_then(|id| entities.get(&(entity, id)).cloned())
+                .unwrap_or(Value::Null)
+        })
+        .collect())
+}
+
+/// Return the ID of an entity given as a string or a number.
+fn entity_id(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(id) => id.parse().ok(),
+        Value::Number(id) => id.as_u64(),
+        _ => None,
+    }
+}
+
+/// Collect the fields selected on the given entity, through fragments that apply to it.
+fn collect_fields<'a>(
+    entity: &str,
+    selection_set: &'a SelectionSet,
+    fragments: &'a Fragments,
+    fields: &mut Vec<&'a Field>,
+) {
+    for selection in selection_set.items.iter() {
+        match &selection.node {
+            Selection::Field(field) => fields.push(&field.node),
+            Selection::InlineFragment(fragment) => {
+                let applies = match &fragment.node.type_condition {
+                    Some(condition) => condition.node.on.node == entity,
+                    None => true,
+                };
+                if applies {
+                    collect_fields(
+                        entity,
+                        &fragment.node.selection_set.node,
+                        fragments,
+                        fields,
+                    );
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                if let Some(fragment) = fragments.get(&spread.node.fragment_name.node) {
+                    if fragment.node.type_condition.node.on.node == entity {
+                        collect_fields(
+                            entity,
+                            &fragment.node.selection_set.node,
+                            fragments,
+                            fields,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Fetch the given fields of the entities with the given IDs, following nested
+/// selections of the entities they reference. Returns the entities by ID.
+fn fetch<'a>(
+    conn: &'a mut IndexerConnection,
+    schema: &'a IndexerSchema,
+    entity: &'a str,
+    ids: &'a [u64],
+    fields: &'a [&'a Field],
+) -> Pin<Box<dyn Future<Output = GraphqlResult<HashMap<u64, Value>>> + Send + 'a>> {
+    Box::pin(async move {
+        let field_types = schema
+            .parsed()
+            .object_field_mappings()
+            .get(entity)
+            .filter(|_| !schema.parsed().is_virtual_typedef(entity))
+            .ok_or_else(|| GraphqlError::UnrecognizedType(entity.to_string()))?;
+
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let query = format!(
+            "SELECT row_to_json(t) FROM {}.{} t WHERE t.id IN ({})",
+            schema.parsed().fully_qualified_namespace(),
+            entity.to_lowercase(),
+            ids.iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let rows = match queries::run_query(conn, query).await {
+            Ok(Value::Array(rows)) => rows,
+            Ok(_) => Vec::new(),
+            Err(e) => return Err(GraphqlError::QueryError(e.to_string())),
+        };
+
+        // Entities referenced by nested selections are fetched once per field.
+        let mut nested = HashMap::new();
+        for field in fields.iter() {
+            let name = field.name.node.as_str();
+            if field.selection_set.node.items.is_empty() || name == "__typename" {
+                continue;
+            }
+
+            let field_type = field_types.get(name).ok_or_else(|| {
+                GraphqlError::UnrecognizedField(entity.to_string(), name.to_string())
+            })?;
+            // Only entities referenced by ID can be selected, not lists of entities,
+            // whose IDs are kept in join tables.
+            let referenced = field_type.as_str();
+            let is_list = schema
+                .parsed()
+                .field_defs()
+                .get(&format!("{entity}.{name}"))
+                .map_or(false, |(def, _)| def.ty.to_string().starts_with('['));
+            if is_list || !schema.parsed().is_possible_foreign_key(referenced) {
+                return Err(GraphqlError::SelectionNotSupported);
+            }
+
+            let column = name.to_lowercase();
+            let mut referenced_ids = rows
+                .iter()
+                .filter_map(|row| entity_id(&row[&column]))
+                .collect::<Vec<_>>();
+            referenced_ids.sort_unstable();
+            referenced_ids.dedup();
+
+            let mut referenced_fields = Vec::new();
+            collect_fields(
+                referenced,
+                &field.selection_set.node,
+                &HashMap::new(),
+                &mut referenced_fields,
+            );
+            let objects = fetch(
+                conn,
+                schema,
+                referenced,
+                &referenced_ids,
+                &referenced_fields,
+            )
+            .await?;
+            nested.insert(name, objects);
+        }
+
+        let mut objects = HashMap::new();
+        for row in rows {
+            let id = match entity_id(&row["id"]) {
+                Some(id) => id,
+                None => continue,
+            };
+
+            let mut object = Map::new();
+            for field in fields.iter() {
+                let name = field.name.node.as_str();
+                let key = field.alias.as_ref().unwrap_or(&field.name).node.to_string();
+                let value = if name == "__typename" {
+                    Value::String(entity.to_string())
+                } else if !field_types.contains_key(name) {
+                    return Err(GraphqlError::UnrecognizedField(
+                        entity.to_string(),
+                        name.to_string(),
+                    ));
+                } else {
+                    let value = row[&name.to_lowercase()].clone();
+                    match nested.get(name) {
+                        Some(objects) => entity_id(&value)
+                            .and_then(|id| objects.get(&id).cloned())
+                            .unwrap_or(Value::Null),
+                        None => value,
+                    }
+                };
+                object.insert(key, value);
+            }
+            objects.insert(id, Value::Object(object));
+        }
+
+        Ok(objects)
+    })
+}
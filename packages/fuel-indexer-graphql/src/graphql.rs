@@ -1,6 +1,8 @@
 use super::{
     arguments::{parse_argument_into_param, ParamType, QueryParams},
-    queries::{JoinCondition, QueryElement, QueryJoinNode, UserQuery},
+    queries::{
+        JoinCondition, ParameterizedQuery, QueryElement, QueryJoinNode, UserQuery,
+    },
 };
 use async_graphql_parser::{
     parse_query,
@@ -10,12 +12,20 @@ use async_graphql_parser::{
     },
 };
 use fuel_indexer_database_types::DbType;
+use fuel_indexer_lib::config::PageInfoCount;
 use fuel_indexer_schema::db::tables::IndexerSchema;
 use std::collections::HashMap;
 use thiserror::Error;
 
 pub type GraphqlResult<T> = Result<T, GraphqlError>;
 
+/// Maximum depth to which selection sets, arguments, and values can be nested in
+/// a user query.
+///
+/// Queries are parsed and planned recursively, so deeper queries are rejected
+/// before being parsed rather than risking a stack overflow.
+pub const MAX_QUERY_DEPTH: usize = 64;
+
 #[derive(Debug, Error)]
 pub enum GraphqlError {
     #[error("GraphQl Parser error: {0:?}")]
@@ -54,6 +64,91 @@ pub enum GraphqlError {
     UnorderedPaginatedQuery,
     #[error("Query error: {0:?}")]
     QueryError(String),
+    #[error("Query is nested deeper than the maximum depth of {0}.")]
+    QueryTooDeep(usize),
+}
+
+/// Parse the given user query and plan it against the schema, returning an SQL
+/// statement for each of the query's root selections, with the query's values
+/// written into it.
+///
+/// This is the whole of the user query pipeline short of running the statements,
+/// and has no side effects. Any input, however malformed, results in either
+/// statements or an error. Statements are run as planned by
+/// `plan_parameterized_query`, which binds the values instead.
+pub fn plan_query(
+    schema: &IndexerSchema,
+    query: &str,
+    db_type: DbType,
+    page_info_count: PageInfoCount,
+) -> GraphqlResult<Vec<String>> {
+    GraphqlQueryBuilder::new(schema, query)?.build()?.as_sql(
+        schema,
+        db_type,
+        page_info_count,
+    )
+}
+
+/// Plan the given user query as `plan_query` does, with the values of the query
+/// replaced by placeholders in each statement.
+///
+/// Queries of the same shape, i.e., selecting the same fields with the same
+/// arguments, differing only in their values, are planned into the same SQL.
+pub fn plan_parameterized_query(
+    schema: &IndexerSchema,
+    query: &str,
+    db_type: DbType,
+    page_info_count: PageInfoCount,
+) -> GraphqlResult<Vec<ParameterizedQuery>> {
+    GraphqlQueryBuilder::new(schema, query)?
+        .build()?
+        .as_parameterized_sql(schema, db_type, page_info_count)
+}
+
+/// Check that the nesting of braces, brackets, and parentheses in the given query
+/// doesn't exceed `MAX_QUERY_DEPTH`, ignoring those within strings and comments.
+fn check_query_depth(query: &str) -> GraphqlResult<()> {
+    let bytes = query.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'"' if bytes[i..].starts_with(b"\"\"\"") => {
+                i += 3;
+                while i < bytes.len() && !bytes[i..].starts_with(b"\"\"\"") {
+                    i += if bytes[i..].starts_with(b"\\\"\"\"") {
+                        4
+                    } else {
+                        1
+                    };
+                }
+                i += 2;
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' && bytes[i] != b'\n' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+            }
+            b'{' | b'[' | b'(' => {
+                depth += 1;
+                if depth > MAX_QUERY_DEPTH {
+                    return Err(GraphqlError::QueryTooDeep(MAX_QUERY_DEPTH));
+                }
+            }
+            b'}' | b']' | b')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        i += 1;
+    }
+
+    Ok(())
 }
 
 #[derive(Clone, Debug)]
@@ -347,9 +442,11 @@ impl Operation {
 
                 let mut last_seen_entities_len = entities.len();
 
-                while let Some(current) = queue.pop() {
-                    let entity_name = entities.pop().unwrap();
-
+                // Every selection in the queue has its entity at the same position
+                // in the entity list, so both run out at the same time.
+                while let (Some(current), Some(entity_name)) =
+                    (queue.pop(), entities.pop())
+                {
                     // If a selection was processed without adding additional selections
                     // to the queue, then check the entity of the selection against the
                     // current nesting level. If they differ, then the operation has moved
@@ -387,6 +484,7 @@ impl Operation {
                             }
                         } else {
                             let mut new_entity = field_name.clone();
+                            let mut relation = None;
                             // If the current entity has a foreign key on the current
                             // selection, join the foreign table on that primary key
                             // and set the field as the innermost entity by pushing to the stack.
@@ -442,6 +540,7 @@ impl Operation {
                                     if *foreign_key_table != field_name {
                                         new_entity = foreign_key_table.to_string();
                                     }
+                                    relation = Some(join_condition.clone());
 
                                     match joins.get_mut(&join_condition.primary_key_table)
                                     {
@@ -488,6 +587,7 @@ impl Operation {
 
                             elements.push(QueryElement::ObjectOpeningBoundary {
                                 key: alias.unwrap_or(field_name.clone()),
+                                join: relation,
                             });
 
                             queue.append(&mut subselections.get_selections());
@@ -512,6 +612,7 @@ impl Operation {
                     entity_name,
                     query_params,
                     alias,
+                    page_info_count: PageInfoCount::default(),
                 };
 
                 queries.push(query)
@@ -542,14 +643,35 @@ impl GraphqlQuery {
         &self,
         schema: &IndexerSchema,
         db_type: DbType,
+        page_info_count: PageInfoCount,
     ) -> Result<Vec<String>, GraphqlError> {
         let queries = self.parse(schema);
 
         queries
             .into_iter()
-            .map(|mut q| q.to_sql(&db_type))
+            .map(|mut q| {
+                q.page_info_count = page_info_count;
+                q.to_sql(&db_type)
+            })
             .collect::<Result<Vec<String>, GraphqlError>>()
     }
+
+    pub fn as_parameterized_sql(
+        &self,
+        schema: &IndexerSchema,
+        db_type: DbType,
+        page_info_count: PageInfoCount,
+    ) -> Result<Vec<ParameterizedQuery>, GraphqlError> {
+        let queries = self.parse(schema);
+
+        queries
+            .into_iter()
+            .map(|mut q| {
+                q.page_info_count = page_info_count;
+                q.to_parameterized_sql(&db_type)
+            })
+            .collect::<Result<Vec<ParameterizedQuery>, GraphqlError>>()
+    }
 }
 
 pub struct GraphqlQueryBuilder<'a> {
@@ -562,6 +684,7 @@ impl<'a> GraphqlQueryBuilder<'a> {
         schema: &'a IndexerSchema,
         query: &'a str,
     ) -> GraphqlResult<GraphqlQueryBuilder<'a>> {
+        check_query_depth(query)?;
         let document = parse_query::<&str>(query)?;
         Ok(GraphqlQueryBuilder { schema, document })
     }
@@ -680,6 +803,265 @@ mod tests {
 
     use super::*;
     use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+    use proptest::prelude::*;
+
+    fn test_schema() -> IndexerSchema {
+        let schema = r#"
+type Block @entity {
+    id: ID!
+    height: UInt8!
+    timestamp: Int8!
+}
+
+type Tx @entity {
+    id: ID!
+    timestamp: Int8!
+    block: Block
+    input_data: Json!
+    amount: U128
+    balance: I128
+}
+"#;
+
+        IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_plan_query_rejects_deeply_nested_queries() {
+        let schema = test_schema();
+
+        let query = "query { tx { block { id height } id } }";
+        assert_eq!(
+            plan_query(&schema, query, DbType::Postgres, PageInfoCount::Exact)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let query = format!(
+            "query {{ tx(filter: {}{}) {{ id }} }}",
+            "{ not: ".repeat(MAX_QUERY_DEPTH),
+            "}".repeat(MAX_QUERY_DEPTH)
+        );
+        assert!(matches!(
+            plan_query(&schema, &query, DbType::Postgres, PageInfoCount::Exact),
+            Err(GraphqlError::QueryTooDeep(MAX_QUERY_DEPTH))
+        ));
+
+        // Braces in strings and comments don't count towards the depth.
+        let query = format!(
+            "# {}\nquery {{ tx(filter: {{ id: {{ equals: \"{}\" }} }}) {{ id }} }}",
+            "{".repeat(100),
+            "[".repeat(100)
+        );
+        assert!(!matches!(
+            plan_query(&schema, &query, DbType::Postgres, PageInfoCount::Exact),
+            Err(GraphqlError::QueryTooDeep(_))
+        ));
+    }
+
+    #[test]
+    fn test_plan_parameterized_query_shares_sql_between_values() {
+        use crate::arguments::ParsedValue;
+        use fuel_indexer_database_types::QueryParameter;
+
+        let schema = test_schema();
+        let query = |timestamp: u64, offset: u64| {
+            format!(
+                "query {{ tx(filter: {{ timestamp: {{ gt: {timestamp} }} }}, order: {{ id: asc }}, first: 10, offset: {offset}) {{ id timestamp }} }}"
+            )
+        };
+
+        let first = plan_parameterized_query(
+            &schema,
+            &query(5, 0),
+            DbType::Postgres,
+            PageInfoCount::Exact,
+        )
+        .unwrap()
+        .remove(0);
+        let second = plan_parameterized_query(
+            &schema,
+            &query(7, 20),
+            DbType::Postgres,
+            PageInfoCount::Exact,
+        )
+        .unwrap()
+        .remove(0);
+        assert_eq!(first.sql, second.sql);
+        assert!(first.sql.contains("> $1"));
+        assert!(!first.sql.contains("> 5"));
+        assert_eq!(
+            second.params,
+            vec![
+                ParsedValue::Number(7),
+                ParsedValue::Number(10),
+                ParsedValue::Number(20)
+            ]
+        );
+        assert_eq!(
+            second.parameters(),
+            vec![
+                QueryParameter::BigInt(7),
+                QueryParameter::BigInt(10),
+                QueryParameter::BigInt(20)
+            ]
+        );
+        assert_eq!(
+            second.statement_sql("gql_1"),
+            format!("/* gql_1 */ {}", second.sql)
+        );
+
+        // Numbers too large for a bigint are cast from text, and make another shape.
+        let large = plan_parameterized_query(
+            &schema,
+            &query(u64::MAX, 20),
+            DbType::Postgres,
+            PageInfoCount::Exact,
+        )
+        .unwrap()
+        .remove(0);
+        assert_ne!(large.sql, second.sql);
+        assert!(large.sql.contains("> $1::numeric"));
+        assert_eq!(
+            large.parameters()[0],
+            QueryParameter::Numeric(u64::MAX.to_string())
+        );
+
+        // Strings are bound as they are, rather than written into the SQL.
+        let query = r#"query { tx(filter: { id: { equals: "it's" } }) { id } }"#;
+        let statement = plan_parameterized_query(
+            &schema,
+            query,
+            DbType::Postgres,
+            PageInfoCount::Exact,
+        )
+        .unwrap()
+        .remove(0);
+        assert!(!statement.sql.contains("it's"));
+        assert_eq!(
+            statement.parameters(),
+            vec![QueryParameter::Text("it's".to_string())]
+        );
+
+        // Each root selection of a query is planned into its own statement, with
+        // the values of each bound as its parameters.
+        let query = r#"query {
+            a: tx(filter: { id: { equals: "x' OR '1'='1" } }) { id }
+            b: block(filter: { id: { equals: "y" } }) { id }
+        }"#;
+        let statements = plan_parameterized_query(
+            &schema,
+            query,
+            DbType::Postgres,
+            PageInfoCount::Exact,
+        )
+        .unwrap();
+        assert_eq!(statements.len(), 2);
+        assert!(statements
+            .iter()
+            .all(|statement| !statement.sql.contains("OR '1'")));
+        assert_eq!(
+            statements[0].parameters(),
+            vec![QueryParameter::Text("x' OR '1'='1".to_string())]
+        );
+        assert_eq!(
+            statements[1].parameters(),
+            vec![QueryParameter::Text("y".to_string())]
+        );
+
+        // Values written into planned queries can't end their string literals.
+        let sql = plan_query(&schema, query, DbType::Postgres, PageInfoCount::Exact)
+            .unwrap()
+            .remove(0);
+        assert!(sql.contains("'x'' OR ''1''=''1'"));
+    }
+
+    #[test]
+    fn test_plan_query_counts_pages_by_page_info_count() {
+        let schema = test_schema();
+        let query = "query { tx(order: { id: asc }, first: 10) { id } }";
+        let plan = |page_info_count| {
+            plan_query(&schema, query, DbType::Postgres, page_info_count)
+                .unwrap()
+                .remove(0)
+        };
+
+        let exact = plan(PageInfoCount::Exact);
+        assert!(exact.contains("SELECT COUNT(*) as count FROM selection_cte"));
+
+        // Without an exact count, the matching entities are read no further than
+        // one past the page.
+        let estimated = plan(PageInfoCount::Estimated);
+        assert!(!estimated.contains("total_count_cte"));
+        assert!(estimated.contains("FROM selection_cte LIMIT 10 + 1 OFFSET 0"));
+        assert!(estimated.contains("to_regclass('fuel_indexer_test_test_index.tx')"));
+
+        let omitted = plan(PageInfoCount::Omitted);
+        assert!(omitted.contains("'total_count', NULL"));
+        assert!(!omitted.contains("pg_class"));
+    }
+
+    #[test]
+    fn test_plan_query_filters_128_bit_integers() {
+        let schema = test_schema();
+        let plan = |query: &str| {
+            plan_query(&schema, query, DbType::Postgres, PageInfoCount::Exact)
+                .map(|mut queries| queries.remove(0))
+        };
+
+        // Values that don't fit in 64 bits are given as strings.
+        let sql = plan(
+            r#"query { tx(filter: { amount: { gt: "340282366920938463463374607431768211455" } }) { id } }"#,
+        )
+        .unwrap();
+        assert!(sql.contains("amount > 340282366920938463463374607431768211455"));
+
+        let sql = plan("query { tx(filter: { balance: { lt: -5 } }) { id } }").unwrap();
+        assert!(sql.contains("balance < -5"));
+
+        assert!(
+            plan(r#"query { tx(filter: { amount: { equals: "-1" } }) { id } }"#).is_err()
+        );
+        assert!(
+            plan(r#"query { tx(filter: { balance: { equals: "1.5" } }) { id } }"#)
+                .is_err()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_plan_query_never_panics_on_arbitrary_input(query in ".{0,256}") {
+            let _ = plan_query(&test_schema(), &query, DbType::Postgres, PageInfoCount::Exact);
+        }
+
+        #[test]
+        fn test_plan_query_never_panics_on_query_like_input(
+            tokens in proptest::collection::vec(
+                prop_oneof![
+                    Just("query"), Just("fragment"), Just("on"), Just("Tx"),
+                    Just("Block"), Just("tx"), Just("block"), Just("id"),
+                    Just("height"), Just("timestamp"), Just("filter"), Just("order"),
+                    Just("first"), Just("offset"), Just("has"), Just("and"),
+                    Just("or"), Just("not"), Just("equals"), Just("between"),
+                    Just("in"), Just("asc"), Just("desc"), Just("..."), Just("{"),
+                    Just("}"), Just("("), Just(")"), Just("["), Just("]"), Just(":"),
+                    Just(","), Just("$x"), Just("1"), Just("-1"), Just("\"a\""),
+                    Just("true"), Just("null"),
+                ],
+                0..64,
+            )
+        ) {
+            let _ = plan_query(&test_schema(), &tokens.join(" "), DbType::Postgres, PageInfoCount::Exact);
+        }
+    }
 
     #[test]
     fn test_operation_parse_into_user_query() {
@@ -781,6 +1163,14 @@ type Tx @entity {
             elements: vec![
                 QueryElement::ObjectOpeningBoundary {
                     key: "block".to_string(),
+                    join: Some(JoinCondition {
+                        referencing_key_table: "fuel_indexer_test_test_index.tx"
+                            .to_string(),
+                        referencing_key_col: "block".to_string(),
+                        primary_key_table: "fuel_indexer_test_test_index.block"
+                            .to_string(),
+                        primary_key_col: "id".to_string(),
+                    }),
                 },
                 QueryElement::Field {
                     key: "height".to_string(),
@@ -840,6 +1230,7 @@ type Tx @entity {
             entity_name: "tx".to_string(),
             query_params: QueryParams::default(),
             alias: None,
+            page_info_count: PageInfoCount::Exact,
         }];
         assert_eq!(expected, operation.parse(&schema));
     }
@@ -1,5 +1,9 @@
-use super::{arguments::QueryParams, graphql::GraphqlError};
-use fuel_indexer_database::DbType;
+use super::{
+    arguments::{Bindings, ParsedValue, QueryParams},
+    graphql::GraphqlError,
+};
+use fuel_indexer_database::{types::QueryParameter, DbType};
+use fuel_indexer_lib::config::PageInfoCount;
 
 use std::{collections::HashMap, fmt::Display};
 
@@ -9,8 +13,14 @@ use std::{collections::HashMap, fmt::Display};
 /// be used as a JSON key in the final database query.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum QueryElement {
-    Field { key: String, value: String },
-    ObjectOpeningBoundary { key: String },
+    Field {
+        key: String,
+        value: String,
+    },
+    ObjectOpeningBoundary {
+        key: String,
+        join: Option<JoinCondition>,
+    },
     ObjectClosingBoundary,
 }
 
@@ -44,6 +54,18 @@ pub struct QueryJoinNode {
     pub dependents: HashMap<String, JoinCondition>,
 }
 
+/// An object of a user query whose nested objects are built by lateral subqueries.
+struct LateralObject<'a> {
+    /// Table of the object's entity.
+    table: &'a str,
+
+    /// Name by which the object's table is referred to in the query.
+    alias: String,
+
+    /// Lateral subqueries building the object's nested objects.
+    laterals: Vec<String>,
+}
+
 /// Represents the full amount of requested information from a user query.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UserQuery {
@@ -64,49 +86,73 @@ pub struct UserQuery {
 
     // An optional user-suppled alias for an entity field.
     pub alias: Option<String>,
+
+    /// How the entities matching the query are counted, if it's paginated.
+    pub page_info_count: PageInfoCount,
+}
+
+/// An SQL query whose values are replaced by numbered placeholders, so that user
+/// queries of the same shape, i.e., differing only in their values, have the same SQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterizedQuery {
+    /// SQL of the query, with a `$n` placeholder for each of its parameters.
+    pub sql: String,
+
+    /// Values of the query's parameters, in order of their placeholders.
+    pub params: Vec<ParsedValue>,
+}
+
+impl ParameterizedQuery {
+    /// Returns the query's SQL tagged with the name of its prepared statement, so
+    /// that connections cache a statement per name rather than per SQL.
+    pub fn statement_sql(&self, statement: &str) -> String {
+        format!("/* {statement} */ {}", self.sql)
+    }
+
+    /// Returns the values of the query's parameters, as they're bound to its statement.
+    pub fn parameters(&self) -> Vec<QueryParameter> {
+        self.params.iter().map(ParsedValue::to_parameter).collect()
+    }
 }
 
 impl UserQuery {
     /// Returns the query as a database-specific SQL query.
     pub fn to_sql(&mut self, db_type: &DbType) -> Result<String, GraphqlError> {
+        self.build_sql(db_type, &mut Bindings::inline())
+    }
+
+    /// Returns the query as a database-specific SQL query, with its values replaced
+    /// by placeholders.
+    pub fn to_parameterized_sql(
+        &mut self,
+        db_type: &DbType,
+    ) -> Result<ParameterizedQuery, GraphqlError> {
+        let mut bindings = Bindings::parameterized();
+        let sql = self.build_sql(db_type, &mut bindings)?;
+        Ok(ParameterizedQuery {
+            sql,
+            params: bindings.into_values(),
+        })
+    }
+
+    /// Returns the query as a database-specific SQL query, with its values rendered
+    /// by the given bindings.
+    fn build_sql(
+        &mut self,
+        db_type: &DbType,
+        bindings: &mut Bindings,
+    ) -> Result<String, GraphqlError> {
         // Different database solutions have unique ways of
         // constructing JSON-formatted queries and results.
         match db_type {
             DbType::Postgres => {
-                let selections = self.parse_query_elements_into_selections(db_type);
-
-                let selections_str = selections.join("");
-
-                let sorted_joins = self.get_topologically_sorted_joins();
-
-                let mut last_seen_primary_key_table = "".to_string();
-                let mut joins: Vec<String> = Vec::new();
-
-                // For each clause in the list of topologically-sorted joins,
-                // check if the clause's primary key table matches the last primary key
-                // key table that was seen in this loop. If so, add the join condition to
-                // the last join condition; if not, push this clause into the list of joins.
-                // This is required because Postgres does not allow for joined primary key tables
-                // to be mentioned multiple times.
-                for sj in sorted_joins {
-                    if sj.primary_key_table == last_seen_primary_key_table {
-                        if let Some(elem) = joins.last_mut() {
-                            *elem = format!("{elem} AND {sj}")
-                        }
-                    } else {
-                        joins.push(format!(
-                            "INNER JOIN {} ON {}",
-                            sj.primary_key_table, sj
-                        ));
-                        last_seen_primary_key_table = sj.primary_key_table;
-                    }
-                }
-
-                let joins_str = if !joins.is_empty() {
-                    joins.join(" ")
-                } else {
-                    "".to_string()
-                };
+                // Nested objects are looked up by lateral subqueries where possible,
+                // and otherwise by joining their tables into the query.
+                let (selections_str, joins_str, filtering_str) =
+                    match self.get_lateral_selections(db_type, bindings) {
+                        Some(lateral) => lateral,
+                        None => self.get_joined_selections(db_type, bindings),
+                    };
 
                 // If there's a limit applied to the query, then we need to create a query
                 // with pagination info. Otherwise, we can return the entire result set.
@@ -115,8 +161,10 @@ impl UserQuery {
                     if !self.query_params.sorts.is_empty() {
                         self.create_query_with_pageinfo(
                             db_type,
+                            bindings,
                             selections_str,
                             joins_str,
+                            filtering_str,
                             limit,
                         )
                     } else {
@@ -129,7 +177,7 @@ impl UserQuery {
                         self.namespace_identifier,
                         self.entity_name,
                         joins_str,
-                        self.query_params.get_filtering_expression(db_type),
+                        filtering_str,
                         self.query_params.get_ordering_modififer(db_type)
                     )
                 };
@@ -139,12 +187,221 @@ impl UserQuery {
         }
     }
 
+    /// Returns the selections, joins, and filtering clause of the query, with the
+    /// tables of its nested objects joined into the query.
+    fn get_joined_selections(
+        &mut self,
+        db_type: &DbType,
+        bindings: &mut Bindings,
+    ) -> (String, String, String) {
+        let selections = self.parse_query_elements_into_selections(db_type);
+
+        let selections_str = selections.join("");
+
+        let sorted_joins = self.get_topologically_sorted_joins();
+
+        let mut last_seen_primary_key_table = "".to_string();
+        let mut joins: Vec<String> = Vec::new();
+
+        // For each clause in the list of topologically-sorted joins,
+        // check if the clause's primary key table matches the last primary key
+        // key table that was seen in this loop. If so, add the join condition to
+        // the last join condition; if not, push this clause into the list of joins.
+        // This is required because Postgres does not allow for joined primary key tables
+        // to be mentioned multiple times.
+        for sj in sorted_joins {
+            if sj.primary_key_table == last_seen_primary_key_table {
+                if let Some(elem) = joins.last_mut() {
+                    *elem = format!("{elem} AND {sj}")
+                }
+            } else {
+                joins.push(format!("INNER JOIN {} ON {}", sj.primary_key_table, sj));
+                last_seen_primary_key_table = sj.primary_key_table;
+            }
+        }
+
+        let joins_str = if !joins.is_empty() {
+            joins.join(" ")
+        } else {
+            "".to_string()
+        };
+
+        let filtering_str = self
+            .query_params
+            .get_filtering_expression(db_type, bindings);
+        (selections_str, joins_str, filtering_str)
+    }
+
+    /// Returns the selections, lateral joins, and filtering clause of the query, with
+    /// each of its nested objects built by a lateral subquery on the object's table,
+    /// or `None` if the query's tables must be joined into the query instead.
+    ///
+    /// Nested objects are looked up by the primary key their parent references, so
+    /// the database resolves each one with an index lookup, rather than planning an
+    /// order in which to join every table of the query, which gets expensive for
+    /// deeply nested queries. Since each lookup has its own alias, the same table
+    /// can also be nested more than once. Queries whose nested objects aren't
+    /// relations, or that are sorted by the fields of nested objects, are joined.
+    fn get_lateral_selections(
+        &self,
+        db_type: &DbType,
+        bindings: &mut Bindings,
+    ) -> Option<(String, String, String)> {
+        let has_nested_objects = self
+            .elements
+            .iter()
+            .any(|e| matches!(e, QueryElement::ObjectOpeningBoundary { .. }));
+        if !has_nested_objects {
+            return None;
+        }
+
+        let table = format!("{}.{}", self.namespace_identifier, self.entity_name);
+        let sorted_by_nested_fields = self.query_params.sorts.iter().any(|s| {
+            !s.fully_qualified_table_name
+                .starts_with(&format!("{table}."))
+        });
+        if sorted_by_nested_fields {
+            return None;
+        }
+
+        // Values are only bound once the whole query can be built this way.
+        let mut lateral_bindings = bindings.clone();
+        let mut elements = self.elements.iter();
+        let mut lateral = LateralObject {
+            table: &table,
+            alias: table.clone(),
+            laterals: Vec::new(),
+        };
+        let mut tables = vec![table.clone()];
+        let mut aliases = 0;
+        let selections_str = self.get_lateral_object_selections(
+            db_type,
+            &mut lateral_bindings,
+            &mut elements,
+            &mut lateral,
+            &mut tables,
+            &mut aliases,
+        )?;
+        if elements.next().is_some() {
+            return None;
+        }
+
+        // Filters on tables that aren't in the query would otherwise be dropped.
+        let all_filters_applied = self
+            .query_params
+            .filters
+            .iter()
+            .all(|f| tables.contains(&f.fully_qualified_table_name));
+        if !all_filters_applied {
+            return None;
+        }
+
+        let filtering_str = self.query_params.get_table_filtering_expression(
+            &table,
+            db_type,
+            &mut lateral_bindings,
+        );
+        *bindings = lateral_bindings;
+
+        Some((selections_str, lateral.laterals.join(" "), filtering_str))
+    }
+
+    /// Returns the selections of a lateral object, from its elements up to its closing
+    /// boundary, adding a lateral subquery to the object for each of its nested objects.
+    fn get_lateral_object_selections(
+        &self,
+        db_type: &DbType,
+        bindings: &mut Bindings,
+        elements: &mut std::slice::Iter<QueryElement>,
+        object: &mut LateralObject,
+        tables: &mut Vec<String>,
+        aliases: &mut usize,
+    ) -> Option<String> {
+        let mut selections = Vec::new();
+
+        while let Some(element) = elements.next() {
+            match element {
+                QueryElement::Field { key, value } => {
+                    let column = value.strip_prefix(&format!("{}.", object.table))?;
+                    selections.push(format!("'{key}', {}.{column}", object.alias));
+                }
+                QueryElement::ObjectOpeningBoundary { key, join } => {
+                    let join = join.as_ref()?;
+                    if join.referencing_key_table != object.table {
+                        return None;
+                    }
+
+                    *aliases += 1;
+                    let entity = join
+                        .primary_key_table
+                        .rsplit('.')
+                        .next()
+                        .unwrap_or_default();
+                    let mut nested = LateralObject {
+                        table: &join.primary_key_table,
+                        alias: format!("{entity}_{aliases}"),
+                        laterals: Vec::new(),
+                    };
+                    let nested_selections = self.get_lateral_object_selections(
+                        db_type,
+                        bindings,
+                        elements,
+                        &mut nested,
+                        tables,
+                        aliases,
+                    )?;
+
+                    let mut conditions = vec![format!(
+                        "{}.{} = {}.{}",
+                        nested.alias,
+                        join.primary_key_col,
+                        object.alias,
+                        join.referencing_key_col
+                    )];
+                    conditions.extend(
+                        self.query_params
+                            .filters
+                            .iter()
+                            .filter(|f| f.fully_qualified_table_name == *nested.table)
+                            .map(|f| {
+                                f.filter_type.to_sql(
+                                    nested.alias.clone(),
+                                    db_type,
+                                    bindings,
+                                )
+                            }),
+                    );
+
+                    let from = [format!("{} AS {}", nested.table, nested.alias)]
+                        .into_iter()
+                        .chain(nested.laterals)
+                        .collect::<Vec<String>>()
+                        .join(" ");
+                    object.laterals.push(format!(
+                        "CROSS JOIN LATERAL (SELECT json_build_object({}) AS object FROM {} WHERE {}) AS {}_object",
+                        nested_selections,
+                        from,
+                        conditions.join(" AND "),
+                        nested.alias
+                    ));
+                    selections.push(format!("'{key}', {}_object.object", nested.alias));
+                    tables.push(nested.table.to_string());
+                }
+                QueryElement::ObjectClosingBoundary => break,
+            }
+        }
+
+        Some(selections.join(", "))
+    }
+
     /// Returns a SQL query that contains the requested results and a PageInfo object.
     fn create_query_with_pageinfo(
         &self,
         db_type: &DbType,
+        bindings: &mut Bindings,
         selections_str: String,
         joins_str: String,
+        filtering_str: String,
         limit: u64,
     ) -> String {
         // In order to create information about pagination, we need to calculate
@@ -170,37 +427,75 @@ impl UserQuery {
                     self.namespace_identifier,
                     self.entity_name,
                     joins_str,
-                    self.query_params.get_filtering_expression(db_type),
+                    filtering_str,
                     self.query_params.get_ordering_modififer(db_type),
                 );
 
-                let total_count_cte =
-                    "total_count_cte AS (SELECT COUNT(*) as count FROM selection_cte)"
-                        .to_string();
-
-                let offset = self.query_params.offset.unwrap_or(0);
+                // The limit and offset are used in arithmetic, from which the type of
+                // their placeholders can't be inferred.
+                let limit = bindings.bind_as(&ParsedValue::Number(limit), "bigint");
+                let offset = bindings.bind_as(
+                    &ParsedValue::Number(self.query_params.offset.unwrap_or(0)),
+                    "bigint",
+                );
                 let alias = self.alias.clone().unwrap_or(self.entity_name.clone());
 
+                // Counting the matching entities requires reading all of them, so
+                // when they aren't counted, whether there's a next page is found by
+                // reading a single entity past the requested page instead.
+                let (count_cte, has_next_page, total_count, page) = match self
+                    .page_info_count
+                {
+                    PageInfoCount::Exact => (
+                        "total_count_cte AS (SELECT COUNT(*) as count FROM selection_cte)"
+                            .to_string(),
+                        format!(
+                            "(({limit} + {offset}) < (SELECT count from total_count_cte))"
+                        ),
+                        "(SELECT count from total_count_cte)".to_string(),
+                        format!("selection_cte LIMIT {limit} OFFSET {offset}"),
+                    ),
+                    PageInfoCount::Estimated | PageInfoCount::Omitted => {
+                        // Estimates are taken from the statistics the database keeps
+                        // of the entity's table, so they don't account for filters.
+                        let total_count = match self.page_info_count {
+                            PageInfoCount::Estimated => format!(
+                                "(SELECT GREATEST(reltuples, 0)::bigint FROM pg_class WHERE oid = to_regclass('{}.{}'))",
+                                self.namespace_identifier, self.entity_name
+                            ),
+                            _ => "NULL".to_string(),
+                        };
+                        (
+                            format!(
+                                "page_cte AS (SELECT {} FROM selection_cte LIMIT {limit} + 1 OFFSET {offset})",
+                                self.entity_name
+                            ),
+                            format!("((SELECT COUNT(*) FROM page_cte) > {limit})"),
+                            total_count,
+                            format!("page_cte LIMIT {limit}"),
+                        )
+                    }
+                };
+
                 let selection_query = format!(
                     r#"SELECT json_build_object(
                         'page_info', json_build_object(
-                            'has_next_page', (({limit} + {offset}) < (SELECT count from total_count_cte)),
+                            'has_next_page', {has_next_page},
                             'limit', {limit},
                             'offset', {offset},
-                            'pages', ceil((SELECT count from total_count_cte)::float / {limit}::float),
-                            'total_count', (SELECT count from total_count_cte)
+                            'pages', ceil({total_count}::float / {limit}::float),
+                            'total_count', {total_count}
                         ),
                         '{alias}', (
                             SELECT json_agg(item)
                             FROM (
-                                SELECT {json_selections_str} FROM selection_cte
-                                LIMIT {limit} OFFSET {offset}
+                                SELECT {json_selections_str} FROM {page}
                             ) item
                         )
                     );"#
                 );
 
-                [selection_cte, total_count_cte, selection_query].join("\n")
+                [selection_cte, count_cte, selection_query].join("\n")
             }
         }
     }
@@ -239,7 +534,7 @@ impl UserQuery {
 
                         // If the element is an object opener boundary, then we need to set a
                         // key so that the recipient can properly refer to the nested object.
-                        QueryElement::ObjectOpeningBoundary { key } => {
+                        QueryElement::ObjectOpeningBoundary { key, .. } => {
                             selections.push(format!("'{key}', json_build_object("))
                         }
 
@@ -283,7 +578,7 @@ impl UserQuery {
                             ));
                         }
 
-                        QueryElement::ObjectOpeningBoundary { key } => {
+                        QueryElement::ObjectOpeningBoundary { key, .. } => {
                             selections.push(format!(
                                 "{}->'{}' AS {}",
                                 self.entity_name, key, key
@@ -358,7 +653,7 @@ impl UserQuery {
 mod tests {
     use super::*;
 
-    use crate::arguments::{Filter, FilterType, ParsedValue};
+    use crate::arguments::{Comparison, Filter, FilterType, ParsedValue};
 
     #[test]
     fn test_user_query_parse_query_elements() {
@@ -369,6 +664,7 @@ mod tests {
             },
             QueryElement::ObjectOpeningBoundary {
                 key: "nested_object_key".to_string(),
+                join: None,
             },
             QueryElement::Field {
                 key: "nested_field_key".to_string(),
@@ -383,6 +679,7 @@ mod tests {
             entity_name: "".to_string(),
             query_params: QueryParams::default(),
             alias: None,
+            page_info_count: PageInfoCount::Exact,
         };
 
         let expected = vec![
@@ -408,6 +705,7 @@ mod tests {
             },
             QueryElement::ObjectOpeningBoundary {
                 key: "tx".to_string(),
+                join: None,
             },
             QueryElement::Field {
                 key: "hash".to_string(),
@@ -466,10 +764,75 @@ mod tests {
                 limit: None,
             },
             alias: None,
+            page_info_count: PageInfoCount::Exact,
         };
 
         let expected = "SELECT json_build_object('hash', name_ident.block.hash, 'tx', json_build_object('hash', name_ident.tx.hash), 'height', name_ident.block.height) FROM name_ident.entity_name INNER JOIN name_ident.block ON name_ident.tx.block = name_ident.block.id WHERE  name_ident.entity_name.id = 1 "
             .to_string();
         assert_eq!(expected, uq.to_sql(&DbType::Postgres).unwrap());
     }
+
+    #[test]
+    fn test_user_query_to_sql_with_lateral_joins() {
+        let elements = vec![
+            QueryElement::Field {
+                key: "id".to_string(),
+                value: "ns.tx.id".to_string(),
+            },
+            QueryElement::ObjectOpeningBoundary {
+                key: "block".to_string(),
+                join: Some(JoinCondition {
+                    referencing_key_table: "ns.tx".to_string(),
+                    referencing_key_col: "block".to_string(),
+                    primary_key_table: "ns.block".to_string(),
+                    primary_key_col: "id".to_string(),
+                }),
+            },
+            QueryElement::Field {
+                key: "height".to_string(),
+                value: "ns.block.height".to_string(),
+            },
+            QueryElement::ObjectClosingBoundary,
+        ];
+
+        let mut uq = UserQuery {
+            elements,
+            joins: HashMap::new(),
+            namespace_identifier: "ns".to_string(),
+            entity_name: "tx".to_string(),
+            query_params: QueryParams {
+                filters: vec![
+                    Filter {
+                        fully_qualified_table_name: "ns.block".to_string(),
+                        filter_type: FilterType::Comparison(Comparison::Greater(
+                            "height".to_string(),
+                            ParsedValue::Number(5),
+                        )),
+                    },
+                    Filter {
+                        fully_qualified_table_name: "ns.tx".to_string(),
+                        filter_type: FilterType::IdSelection(ParsedValue::Number(1)),
+                    },
+                ],
+                sorts: vec![],
+                offset: None,
+                limit: None,
+            },
+            alias: None,
+            page_info_count: PageInfoCount::Exact,
+        };
+
+        let expected = "SELECT json_build_object('id', ns.tx.id, 'block', block_1_object.object) FROM ns.tx CROSS JOIN LATERAL (SELECT json_build_object('height', block_1.height) AS object FROM ns.block AS block_1 WHERE block_1.id = ns.tx.block AND block_1.height > 5) AS block_1_object WHERE ns.tx.id = 1 "
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Postgres).unwrap());
+
+        // Values of nested objects' filters are bound before those of the entity.
+        let parameterized = uq.to_parameterized_sql(&DbType::Postgres).unwrap();
+        assert!(parameterized.sql.contains("block_1.height > $1"));
+        assert!(parameterized.sql.contains("ns.tx.id = $2"));
+        assert_eq!(
+            parameterized.params,
+            vec![ParsedValue::Number(5), ParsedValue::Number(1)]
+        );
+    }
 }
@@ -0,0 +1,224 @@
+use std::{collections::HashMap, sync::Mutex};
+
+/// Prefix of the names of the prepared statements of user queries.
+const STATEMENT_NAME_PREFIX: &str = "graphql_query_";
+
+/// Names of the prepared statements of user queries, keyed by the indexer they
+/// query and the parameterized SQL of their shape.
+///
+/// Connections cache statements by their SQL, which is tagged with the name of its
+/// shape, so that queries of the same shape run the same prepared statement on each
+/// connection, and a shape given a new name is prepared anew. Names are never given
+/// to another shape.
+///
+/// Once the cache is full, the least recently used shape is evicted to make room for
+/// a new one. The shapes of an indexer are evicted when it's queried with a new
+/// version of its schema, or when it's invalidated, so that its new tables aren't
+/// queried through statements planned against its old ones.
+#[derive(Debug, Default)]
+pub struct StatementCache {
+    /// Maximum number of shapes named.
+    capacity: usize,
+
+    /// Named shapes, and the versions of the schemas they were named for.
+    state: Mutex<StatementCacheState>,
+}
+
+#[derive(Debug, Default)]
+struct StatementCacheState {
+    /// Named shapes, keyed by the UID of the indexer they query and their SQL.
+    shapes: HashMap<(String, String), NamedShape>,
+
+    /// Version of the schema of each indexer whose shapes are named.
+    versions: HashMap<String, String>,
+
+    /// Number of names given, so that each name is given once.
+    named: u64,
+
+    /// Number of lookups, by which shapes are ordered by when they were last used.
+    lookups: u64,
+}
+
+#[derive(Debug)]
+struct NamedShape {
+    /// Name of the shape's prepared statement.
+    name: String,
+
+    /// Lookup in which the shape was last used.
+    last_used: u64,
+}
+
+impl StatementCache {
+    /// Create a cache naming up to `capacity` shapes. Statements aren't prepared if
+    /// `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(StatementCacheState::default()),
+        }
+    }
+
+    /// Return the maximum number of shapes named.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Return the name of the prepared statement of the given SQL, querying the given
+    /// version of the indexer's schema, naming it if it's a new shape, or `None` if
+    /// statements aren't prepared.
+    pub fn statement_name(
+        &self,
+        indexer_uid: &str,
+        version: &str,
+        sql: &str,
+    ) -> Option<String> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        let mut state = self.state.lock().expect("Statement cache lock poisoned.");
+        if state.versions.get(indexer_uid).map(String::as_str) != Some(version) {
+            state.evict_indexer(indexer_uid);
+            state
+                .versions
+                .insert(indexer_uid.to_string(), version.to_string());
+        }
+
+        state.lookups += 1;
+        let lookup = state.lookups;
+        let key = (indexer_uid.to_string(), sql.to_string());
+        if let Some(shape) = state.shapes.get_mut(&key) {
+            shape.last_used = lookup;
+            return Some(shape.name.clone());
+        }
+
+        if state.shapes.len() >= self.capacity {
+            let least_recently_used = state
+                .shapes
+                .iter()
+                .min_by_key(|(_, shape)| shape.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = least_recently_used {
+                state.shapes.remove(&key);
+            }
+        }
+
+        state.named += 1;
+        let name = format!("{STATEMENT_NAME_PREFIX}{}", state.named);
+        state.shapes.insert(
+            key,
+            NamedShape {
+                name: name.clone(),
+                last_used: lookup,
+            },
+        );
+        Some(name)
+    }
+
+    /// Evict the shapes of the given indexer, e.g., once its tables are replaced.
+    pub fn invalidate(&self, indexer_uid: &str) {
+        let mut state = self.state.lock().expect("Statement cache lock poisoned.");
+        state.evict_indexer(indexer_uid);
+        state.versions.remove(indexer_uid);
+    }
+
+    /// Return the number of shapes named.
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .expect("Statement cache lock poisoned.")
+            .shapes
+            .len()
+    }
+
+    /// Return whether no shapes have been named.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl StatementCacheState {
+    /// Remove the shapes querying the given indexer.
+    fn evict_indexer(&mut self, indexer_uid: &str) {
+        self.shapes.retain(|(uid, _), _| uid != indexer_uid);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statement_cache_names_each_shape_once() {
+        let cache = StatementCache::new(2);
+
+        let first = cache.statement_name("ns_a", "v1", "SELECT $1").unwrap();
+        let second = cache.statement_name("ns_a", "v1", "SELECT $1, $2").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            cache.statement_name("ns_a", "v1", "SELECT $1"),
+            Some(first.clone())
+        );
+        assert_eq!(cache.len(), 2);
+
+        // The same SQL querying another indexer is another shape.
+        let other = cache.statement_name("ns_b", "v1", "SELECT $1").unwrap();
+        assert_ne!(other, first);
+
+        assert_eq!(
+            StatementCache::new(0).statement_name("ns_a", "v1", "SELECT $1"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_statement_cache_evicts_least_recently_used_shape() {
+        let cache = StatementCache::new(2);
+
+        let first = cache.statement_name("ns_a", "v1", "SELECT $1").unwrap();
+        let second = cache.statement_name("ns_a", "v1", "SELECT $1, $2").unwrap();
+        assert_eq!(
+            cache.statement_name("ns_a", "v1", "SELECT $1"),
+            Some(first.clone())
+        );
+
+        // The second shape was used least recently, so it makes room for the third.
+        let third = cache
+            .statement_name("ns_a", "v1", "SELECT $1, $2, $3")
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.statement_name("ns_a", "v1", "SELECT $1"), Some(first));
+        assert_eq!(
+            cache.statement_name("ns_a", "v1", "SELECT $1, $2, $3"),
+            Some(third.clone())
+        );
+
+        // An evicted shape is given a new name when it's used again.
+        let renamed = cache.statement_name("ns_a", "v1", "SELECT $1, $2").unwrap();
+        assert_ne!(renamed, second);
+        assert_ne!(renamed, third);
+    }
+
+    #[test]
+    fn test_statement_cache_renames_shapes_of_changed_schemas() {
+        let cache = StatementCache::new(4);
+
+        let first = cache.statement_name("ns_a", "v1", "SELECT $1").unwrap();
+        let other = cache.statement_name("ns_b", "v1", "SELECT $1").unwrap();
+
+        // A new version of the schema evicts the indexer's shapes, and no others.
+        let upgraded = cache.statement_name("ns_a", "v2", "SELECT $1").unwrap();
+        assert_ne!(upgraded, first);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.statement_name("ns_b", "v1", "SELECT $1"),
+            Some(other.clone())
+        );
+
+        cache.invalidate("ns_a");
+        assert_eq!(cache.len(), 1);
+        let redeployed = cache.statement_name("ns_a", "v2", "SELECT $1").unwrap();
+        assert_ne!(redeployed, upgraded);
+        assert_eq!(cache.statement_name("ns_b", "v1", "SELECT $1"), Some(other));
+    }
+}
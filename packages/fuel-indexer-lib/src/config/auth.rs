@@ -29,6 +29,20 @@ pub struct AuthenticationConfig {
 
     /// Amount of time (seconds) before expiring token (if JWT scheme is specified).
     pub jwt_expiry: Option<usize>,
+
+    /// Require deployed manifests and WASM modules to be signed by one of the
+    /// `deployment_signers`.
+    #[serde(default)]
+    pub require_signed_deployments: bool,
+
+    /// Hex-encoded ed25519 public keys allowed to sign deployments.
+    #[serde(default)]
+    pub deployment_signers: Vec<String>,
+
+    /// Domain of the service, which Sign-In with Fuel messages must name. Sign-In with
+    /// Fuel is disabled if not set.
+    #[serde(default)]
+    pub siwf_domain: Option<String>,
 }
 
 impl Default for AuthenticationConfig {
@@ -39,6 +53,9 @@ impl Default for AuthenticationConfig {
             jwt_secret: None,
             jwt_issuer: None,
             jwt_expiry: None,
+            require_signed_deployments: defaults::REQUIRE_SIGNED_DEPLOYMENTS,
+            deployment_signers: Vec::new(),
+            siwf_domain: None,
         }
     }
 }
@@ -50,6 +67,9 @@ impl Debug for AuthenticationConfig {
             jwt_expiry,
             strategy,
             jwt_issuer,
+            require_signed_deployments,
+            deployment_signers,
+            siwf_domain,
             ..
         } = self;
         let _ = f
@@ -59,6 +79,9 @@ impl Debug for AuthenticationConfig {
             .field("jwt_secret", &"XXXX")
             .field("jwt_issuer", &jwt_issuer)
             .field("jwt_expiry", &jwt_expiry)
+            .field("require_signed_deployments", &require_signed_deployments)
+            .field("deployment_signers", &deployment_signers)
+            .field("siwf_domain", &siwf_domain)
             .finish();
 
         Ok(())
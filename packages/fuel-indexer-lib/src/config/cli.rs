@@ -1,10 +1,14 @@
+use crate::config::{setting, ConfigArgs, SettingPath};
 pub use crate::{
     config::{
         auth::{AuthenticationConfig, AuthenticationStrategy},
         client::FuelClientConfig,
         database::DatabaseConfig,
         limit::RateLimitConfig,
+        network::Network,
+        telemetry::TelemetryConfig,
         web::WebApiConfig,
+        BenchMode, ConversionErrorPolicy, PageInfoCount,
     },
     defaults,
 };
@@ -22,6 +26,10 @@ pub struct IndexerArgs {
     #[clap(long, default_value = defaults::LOG_LEVEL, value_parser(["info", "debug", "error", "warn"]), help = "Log level passed to the Fuel Indexer service.")]
     pub log_level: String,
 
+    /// Format of the service's log output.
+    #[clap(long, default_value = defaults::LOG_FORMAT, value_parser(["text", "json"]), help = "Format of the service's log output.")]
+    pub log_format: String,
+
     /// Indexer service config file.
     #[clap(
         short,
@@ -67,6 +75,81 @@ pub struct IndexerArgs {
     #[clap(long, help = "Max body size for web API requests.", default_value_t = defaults::MAX_BODY_SIZE )]
     pub max_body_size: usize,
 
+    /// Origin allowed to make cross-origin requests to the web API. Any origin is allowed if none are given.
+    #[clap(
+        long,
+        value_name = "ORIGIN",
+        multiple_occurrences = true,
+        help = "Origin allowed to make cross-origin requests to the web API. Can be given multiple times. Any origin is allowed if none are given."
+    )]
+    pub cors_allowed_origin: Vec<String>,
+
+    /// Method allowed in cross-origin requests to the web API. Defaults to GET and POST if none are given.
+    #[clap(
+        long,
+        value_name = "METHOD",
+        multiple_occurrences = true,
+        help = "Method allowed in cross-origin requests to the web API. Can be given multiple times. Defaults to GET and POST if none are given."
+    )]
+    pub cors_allowed_method: Vec<String>,
+
+    /// Number of seconds for which browsers may cache CORS preflight responses.
+    #[clap(
+        long,
+        help = "Number of seconds for which browsers may cache CORS preflight responses."
+    )]
+    pub cors_max_age: Option<u64>,
+
+    /// Don't add standard security headers to web API responses.
+    #[clap(
+        long,
+        help = "Don't add standard security headers to web API responses."
+    )]
+    pub disable_security_headers: bool,
+
+    /// Path to a PEM-encoded TLS certificate chain. The web API is served over HTTPS if given.
+    #[clap(
+        long,
+        value_name = "FILE",
+        requires = "tls_key",
+        help = "Path to a PEM-encoded TLS certificate chain. The web API is served over HTTPS if given."
+    )]
+    pub tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key of the TLS certificate.
+    #[clap(
+        long,
+        value_name = "FILE",
+        requires = "tls_cert",
+        help = "Path to the PEM-encoded private key of the TLS certificate."
+    )]
+    pub tls_key: Option<PathBuf>,
+
+    /// Interval (seconds) at which the TLS certificate and key are checked for changes and reloaded.
+    #[clap(
+        long,
+        value_name = "SECONDS",
+        help = "Interval (seconds) at which the TLS certificate and key are checked for changes and reloaded."
+    )]
+    pub tls_reload_interval: Option<u64>,
+
+    /// Number of GraphQL query shapes whose SQL is kept as a prepared statement. Statements aren't prepared if 0.
+    #[clap(
+        long,
+        help = "Number of GraphQL query shapes whose SQL is kept as a prepared statement. Statements aren't prepared if 0.",
+        default_value_t = defaults::STATEMENT_CACHE_SIZE
+    )]
+    pub statement_cache_size: usize,
+
+    /// How the entities matching paginated queries are counted in their `page_info`: `exact` counts them, `estimated` estimates the size of the entity's table, and `omitted` doesn't count them.
+    #[clap(
+        long,
+        value_name = "MODE",
+        default_value = "exact",
+        help = "How the entities matching paginated queries are counted in their `page_info`: `exact` counts them, `estimated` estimates the size of the entity's table, and `omitted` doesn't count them."
+    )]
+    pub page_info_count: PageInfoCount,
+
     /// Postgres username.
     #[clap(long, help = "Postgres username.")]
     pub postgres_user: Option<String>,
@@ -135,6 +218,30 @@ pub struct IndexerArgs {
     )]
     pub jwt_expiry: Option<usize>,
 
+    /// Require deployed manifests and WASM modules to be signed by a deployment signer.
+    #[clap(
+        long,
+        help = "Require deployed manifests and WASM modules to be signed by a deployment signer."
+    )]
+    pub require_signed_deployments: bool,
+
+    /// Hex-encoded ed25519 public key allowed to sign deployments.
+    #[clap(
+        long,
+        value_name = "PUBLIC_KEY",
+        multiple_occurrences = true,
+        help = "Hex-encoded ed25519 public key allowed to sign deployments. Can be given multiple times."
+    )]
+    pub deployment_signer: Vec<String>,
+
+    /// Domain of the service, which Sign-In with Fuel messages must name. Sign-In with Fuel is disabled if not specified.
+    #[clap(
+        long,
+        value_name = "DOMAIN",
+        help = "Domain of the service, which Sign-In with Fuel messages must name. Sign-In with Fuel is disabled if not specified."
+    )]
+    pub siwf_domain: Option<String>,
+
     /// Enable verbose logging.
     #[clap(short, long, help = "Enable verbose logging.")]
     pub verbose: bool,
@@ -162,6 +269,39 @@ pub struct IndexerArgs {
     #[clap(long, help = "Number of seconds over which to allow --rate-limit-rps.")]
     pub rate_limit_window_size: Option<u64>,
 
+    /// Maximum number of GraphQL queries per second from a single client.
+    #[clap(
+        long,
+        help = "Maximum number of GraphQL queries per second from a single client."
+    )]
+    pub rate_limit_client_rps: Option<u64>,
+
+    /// Maximum number of rows per second returned to a single client by GraphQL queries.
+    #[clap(
+        long,
+        help = "Maximum number of rows per second returned to a single client by GraphQL queries."
+    )]
+    pub rate_limit_client_rows: Option<u64>,
+
+    /// Maximum number of GraphQL queries per second to a single indexer.
+    #[clap(
+        long,
+        help = "Maximum number of GraphQL queries per second to a single indexer."
+    )]
+    pub rate_limit_indexer_rps: Option<u64>,
+
+    /// Export traces to an OpenTelemetry collector.
+    #[clap(long, help = "Export traces to an OpenTelemetry collector.")]
+    pub telemetry: bool,
+
+    /// OTLP gRPC endpoint of the OpenTelemetry collector.
+    #[clap(long, help = "OTLP gRPC endpoint of the OpenTelemetry collector.", default_value = defaults::OTLP_ENDPOINT)]
+    pub otlp_endpoint: String,
+
+    /// Ratio of traces to sample, between 0 and 1.
+    #[clap(long, help = "Ratio of traces to sample, between 0 and 1.", default_value_t = defaults::TRACE_SAMPLING_RATIO)]
+    pub trace_sampling_ratio: f64,
+
     /// The number of WASM opcodes after which the indexer's event handler will stop execution.
     #[clap(
         long,
@@ -184,6 +324,192 @@ pub struct IndexerArgs {
     /// Amount of blocks to return in a request to a Fuel node.
     #[clap(long, help = "Amount of blocks to return in a request to a Fuel node.", default_value_t = defaults::NODE_BLOCK_PAGE_SIZE)]
     pub block_page_size: usize,
+
+    /// Directory in which compiled WASM modules are cached. If not specified, modules are recompiled on every start.
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory in which compiled WASM modules are cached. If not specified, modules are recompiled on every start."
+    )]
+    pub module_cache_dir: Option<PathBuf>,
+
+    /// Maximum number of compiled WASM modules to keep in the module cache.
+    #[clap(long, help = "Maximum number of compiled WASM modules to keep in the module cache.", default_value_t = defaults::MODULE_CACHE_SIZE)]
+    pub module_cache_size: usize,
+
+    /// Number of block pages to prefetch from the Fuel node while the executor is busy.
+    #[clap(long, help = "Number of block pages to prefetch from the Fuel node while the executor is busy.", default_value_t = defaults::BLOCK_PREFETCH_SIZE)]
+    pub block_prefetch_size: usize,
+
+    /// Directory in which blocks fetched from the Fuel node are archived, and from which they are read when reindexing. If not specified, all blocks are fetched from the Fuel node.
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory in which blocks fetched from the Fuel node are archived, and from which they are read when reindexing. If not specified, all blocks are fetched from the Fuel node."
+    )]
+    pub block_archive_dir: Option<PathBuf>,
+
+    /// Address of a Fuel node to fail over to if the Fuel node being indexed fails or falls behind. Can be given multiple times.
+    #[clap(
+        long,
+        value_name = "URL",
+        multiple_occurrences = true,
+        help = "Address of a Fuel node to fail over to if the Fuel node being indexed fails or falls behind. Can be given multiple times."
+    )]
+    pub fallback_fuel_node: Vec<String>,
+
+    /// Number of block pages to process between executor checkpoints. Set to 0 to disable checkpoints.
+    #[clap(long, help = "Number of block pages to process between executor checkpoints. Set to 0 to disable checkpoints.", default_value_t = defaults::CHECKPOINT_INTERVAL)]
+    pub checkpoint_interval: u64,
+
+    /// Share of a CPU core that each WASM indexer's handlers may use on average. Indexers using more are delayed between block pages.
+    #[clap(
+        long,
+        help = "Share of a CPU core that each WASM indexer's handlers may use on average (e.g., 0.5). Indexers using more are delayed between block pages. If not specified, indexers aren't delayed."
+    )]
+    pub max_cpu_share: Option<f64>,
+
+    /// Fuel network to index. Populates the Fuel node, chain ID, and genesis height settings.
+    #[clap(
+        long,
+        help = "Fuel network to index (beta-3, beta-4, or local). Populates the Fuel node, chain ID, and genesis height settings."
+    )]
+    pub network: Option<Network>,
+
+    /// Chain ID of the Fuel network being indexed.
+    #[clap(long, help = "Chain ID of the Fuel network being indexed.", default_value_t = defaults::CHAIN_ID)]
+    pub chain_id: u64,
+
+    /// Height of the first block of the Fuel network being indexed.
+    #[clap(long, help = "Height of the first block of the Fuel network being indexed.", default_value_t = defaults::GENESIS_HEIGHT)]
+    pub genesis_height: u64,
+
+    /// URL of the HTTP API of an IPFS node to which the assets of each deployed indexer are added and pinned. If not specified, assets are not published to IPFS.
+    #[clap(
+        long,
+        value_name = "URL",
+        help = "URL of the HTTP API of an IPFS node to which the assets of each deployed indexer are added and pinned. If not specified, assets are not published to IPFS."
+    )]
+    pub ipfs_api_url: Option<String>,
+
+    /// Benchmark to run in place of indexing a Fuel node. With `synthetic-load`, the service indexes generated blocks and logs its throughput.
+    #[clap(
+        long,
+        value_name = "MODE",
+        help = "Benchmark to run in place of indexing a Fuel node. With `synthetic-load`, the service indexes generated blocks and logs its throughput."
+    )]
+    pub bench: Option<BenchMode>,
+
+    /// What indexers do with transactions fetched from the Fuel node that can't be converted: `fail` stops the indexer, and `skip` logs the error and indexes the rest of the block.
+    #[clap(
+        long,
+        value_name = "POLICY",
+        default_value = "fail",
+        help = "What indexers do with transactions fetched from the Fuel node that can't be converted: `fail` stops the indexer, and `skip` logs the error and indexes the rest of the block."
+    )]
+    pub on_conversion_error: ConversionErrorPolicy,
+}
+
+impl ConfigArgs for IndexerArgs {
+    fn config_file(&self) -> Option<&PathBuf> {
+        self.config.as_ref()
+    }
+
+    fn settings(&self) -> Vec<(SettingPath, serde_yaml::Value)> {
+        vec![
+            setting(&["log_level"], &self.log_level),
+            setting(&["log_format"], &self.log_format),
+            setting(&["verbose"], self.verbose),
+            setting(&["fuel_node", "host"], &self.fuel_node_host),
+            setting(&["fuel_node", "port"], &self.fuel_node_port),
+            setting(&["web_api", "host"], &self.web_api_host),
+            setting(&["web_api", "port"], &self.web_api_port),
+            setting(&["web_api", "max_body_size"], self.max_body_size),
+            setting(
+                &["web_api", "cors_allowed_origins"],
+                &self.cors_allowed_origin,
+            ),
+            setting(
+                &["web_api", "cors_allowed_methods"],
+                &self.cors_allowed_method,
+            ),
+            setting(&["web_api", "cors_max_age"], self.cors_max_age),
+            setting(
+                &["web_api", "security_headers"],
+                !self.disable_security_headers,
+            ),
+            setting(&["web_api", "tls_cert"], &self.tls_cert),
+            setting(&["web_api", "tls_key"], &self.tls_key),
+            setting(
+                &["web_api", "tls_reload_interval"],
+                self.tls_reload_interval,
+            ),
+            setting(
+                &["web_api", "statement_cache_size"],
+                self.statement_cache_size,
+            ),
+            setting(&["web_api", "page_info_count"], self.page_info_count),
+            setting(&["database", "postgres", "user"], &self.postgres_user),
+            setting(
+                &["database", "postgres", "password"],
+                &self.postgres_password,
+            ),
+            setting(&["database", "postgres", "host"], &self.postgres_host),
+            setting(&["database", "postgres", "port"], &self.postgres_port),
+            setting(
+                &["database", "postgres", "database"],
+                &self.postgres_database,
+            ),
+            setting(&["run_migrations"], self.run_migrations),
+            setting(&["metrics"], self.metrics),
+            setting(&["authentication", "enabled"], self.auth_enabled),
+            setting(&["authentication", "auth_strategy"], &self.auth_strategy),
+            setting(&["authentication", "jwt_secret"], &self.jwt_secret),
+            setting(&["authentication", "jwt_issuer"], &self.jwt_issuer),
+            setting(&["authentication", "jwt_expiry"], self.jwt_expiry),
+            setting(
+                &["authentication", "require_signed_deployments"],
+                self.require_signed_deployments,
+            ),
+            setting(
+                &["authentication", "deployment_signers"],
+                &self.deployment_signer,
+            ),
+            setting(&["authentication", "siwf_domain"], &self.siwf_domain),
+            setting(&["rate_limit", "enabled"], self.rate_limit),
+            setting(
+                &["rate_limit", "request_count"],
+                self.rate_limit_request_count,
+            ),
+            setting(&["rate_limit", "window_size"], self.rate_limit_window_size),
+            setting(&["rate_limit", "client_rps"], self.rate_limit_client_rps),
+            setting(&["rate_limit", "client_rows"], self.rate_limit_client_rows),
+            setting(&["rate_limit", "indexer_rps"], self.rate_limit_indexer_rps),
+            setting(&["telemetry", "enabled"], self.telemetry),
+            setting(&["telemetry", "otlp_endpoint"], &self.otlp_endpoint),
+            setting(&["telemetry", "sampling_ratio"], self.trace_sampling_ratio),
+            setting(&["accept_sql_queries"], self.accept_sql_queries),
+            setting(&["metering_points"], self.metering_points),
+            setting(&["stop_idle_indexers"], self.stop_idle_indexers),
+            setting(&["local_fuel_node"], self.local_fuel_node),
+            setting(&["indexer_net_config"], self.indexer_net_config),
+            setting(&["replace_indexer"], self.replace_indexer),
+            setting(&["block_page_size"], self.block_page_size),
+            setting(&["module_cache_dir"], &self.module_cache_dir),
+            setting(&["module_cache_size"], self.module_cache_size),
+            setting(&["block_prefetch_size"], self.block_prefetch_size),
+            setting(&["block_archive_dir"], &self.block_archive_dir),
+            setting(&["fallback_fuel_nodes"], &self.fallback_fuel_node),
+            setting(&["checkpoint_interval"], self.checkpoint_interval),
+            setting(&["max_cpu_share"], self.max_cpu_share),
+            setting(&["network"], self.network),
+            setting(&["chain_id"], self.chain_id),
+            setting(&["genesis_height"], self.genesis_height),
+            setting(&["ipfs_api_url"], &self.ipfs_api_url),
+            setting(&["bench"], self.bench),
+            setting(&["on_conversion_error"], self.on_conversion_error),
+        ]
+    }
 }
 
 #[derive(Debug, Parser, Clone)]
@@ -197,6 +523,10 @@ pub struct ApiServerArgs {
     #[clap(long, default_value = defaults::LOG_LEVEL, value_parser(["info", "debug", "error", "warn"]), help = "Log level passed to the Fuel Indexer service.")]
     pub log_level: String,
 
+    /// Format of the service's log output.
+    #[clap(long, default_value = defaults::LOG_FORMAT, value_parser(["text", "json"]), help = "Format of the service's log output.")]
+    pub log_format: String,
+
     /// API server config file.
     #[clap(short, long, help = "API server config file.")]
     pub config: Option<PathBuf>,
@@ -233,6 +563,81 @@ pub struct ApiServerArgs {
     #[clap(long, help = "Max body size for web requests.", default_value_t = defaults::MAX_BODY_SIZE )]
     pub max_body_size: usize,
 
+    /// Origin allowed to make cross-origin requests to the web API. Any origin is allowed if none are given.
+    #[clap(
+        long,
+        value_name = "ORIGIN",
+        multiple_occurrences = true,
+        help = "Origin allowed to make cross-origin requests to the web API. Can be given multiple times. Any origin is allowed if none are given."
+    )]
+    pub cors_allowed_origin: Vec<String>,
+
+    /// Method allowed in cross-origin requests to the web API. Defaults to GET and POST if none are given.
+    #[clap(
+        long,
+        value_name = "METHOD",
+        multiple_occurrences = true,
+        help = "Method allowed in cross-origin requests to the web API. Can be given multiple times. Defaults to GET and POST if none are given."
+    )]
+    pub cors_allowed_method: Vec<String>,
+
+    /// Number of seconds for which browsers may cache CORS preflight responses.
+    #[clap(
+        long,
+        help = "Number of seconds for which browsers may cache CORS preflight responses."
+    )]
+    pub cors_max_age: Option<u64>,
+
+    /// Don't add standard security headers to web API responses.
+    #[clap(
+        long,
+        help = "Don't add standard security headers to web API responses."
+    )]
+    pub disable_security_headers: bool,
+
+    /// Path to a PEM-encoded TLS certificate chain. The web API is served over HTTPS if given.
+    #[clap(
+        long,
+        value_name = "FILE",
+        requires = "tls_key",
+        help = "Path to a PEM-encoded TLS certificate chain. The web API is served over HTTPS if given."
+    )]
+    pub tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key of the TLS certificate.
+    #[clap(
+        long,
+        value_name = "FILE",
+        requires = "tls_cert",
+        help = "Path to the PEM-encoded private key of the TLS certificate."
+    )]
+    pub tls_key: Option<PathBuf>,
+
+    /// Interval (seconds) at which the TLS certificate and key are checked for changes and reloaded.
+    #[clap(
+        long,
+        value_name = "SECONDS",
+        help = "Interval (seconds) at which the TLS certificate and key are checked for changes and reloaded."
+    )]
+    pub tls_reload_interval: Option<u64>,
+
+    /// Number of GraphQL query shapes whose SQL is kept as a prepared statement. Statements aren't prepared if 0.
+    #[clap(
+        long,
+        help = "Number of GraphQL query shapes whose SQL is kept as a prepared statement. Statements aren't prepared if 0.",
+        default_value_t = defaults::STATEMENT_CACHE_SIZE
+    )]
+    pub statement_cache_size: usize,
+
+    /// How the entities matching paginated queries are counted in their `page_info`: `exact` counts them, `estimated` estimates the size of the entity's table, and `omitted` doesn't count them.
+    #[clap(
+        long,
+        value_name = "MODE",
+        default_value = "exact",
+        help = "How the entities matching paginated queries are counted in their `page_info`: `exact` counts them, `estimated` estimates the size of the entity's table, and `omitted` doesn't count them."
+    )]
+    pub page_info_count: PageInfoCount,
+
     /// Run database migrations before starting service.
     #[clap(long, help = "Run database migrations before starting service.")]
     pub run_migrations: bool,
@@ -287,6 +692,30 @@ pub struct ApiServerArgs {
     )]
     pub jwt_expiry: Option<usize>,
 
+    /// Require deployed manifests and WASM modules to be signed by a deployment signer.
+    #[clap(
+        long,
+        help = "Require deployed manifests and WASM modules to be signed by a deployment signer."
+    )]
+    pub require_signed_deployments: bool,
+
+    /// Hex-encoded ed25519 public key allowed to sign deployments.
+    #[clap(
+        long,
+        value_name = "PUBLIC_KEY",
+        multiple_occurrences = true,
+        help = "Hex-encoded ed25519 public key allowed to sign deployments. Can be given multiple times."
+    )]
+    pub deployment_signer: Vec<String>,
+
+    /// Domain of the service, which Sign-In with Fuel messages must name. Sign-In with Fuel is disabled if not specified.
+    #[clap(
+        long,
+        value_name = "DOMAIN",
+        help = "Domain of the service, which Sign-In with Fuel messages must name. Sign-In with Fuel is disabled if not specified."
+    )]
+    pub siwf_domain: Option<String>,
+
     /// Enable verbose logging.
     #[clap(short, long, help = "Enable verbose logging.")]
     pub verbose: bool,
@@ -306,7 +735,132 @@ pub struct ApiServerArgs {
     #[clap(long, help = "Number of seconds over which to allow --rate-limit-rps.")]
     pub rate_limit_window_size: Option<u64>,
 
+    /// Maximum number of GraphQL queries per second from a single client.
+    #[clap(
+        long,
+        help = "Maximum number of GraphQL queries per second from a single client."
+    )]
+    pub rate_limit_client_rps: Option<u64>,
+
+    /// Maximum number of rows per second returned to a single client by GraphQL queries.
+    #[clap(
+        long,
+        help = "Maximum number of rows per second returned to a single client by GraphQL queries."
+    )]
+    pub rate_limit_client_rows: Option<u64>,
+
+    /// Maximum number of GraphQL queries per second to a single indexer.
+    #[clap(
+        long,
+        help = "Maximum number of GraphQL queries per second to a single indexer."
+    )]
+    pub rate_limit_indexer_rps: Option<u64>,
+
+    /// Export traces to an OpenTelemetry collector.
+    #[clap(long, help = "Export traces to an OpenTelemetry collector.")]
+    pub telemetry: bool,
+
+    /// OTLP gRPC endpoint of the OpenTelemetry collector.
+    #[clap(long, help = "OTLP gRPC endpoint of the OpenTelemetry collector.", default_value = defaults::OTLP_ENDPOINT)]
+    pub otlp_endpoint: String,
+
+    /// Ratio of traces to sample, between 0 and 1.
+    #[clap(long, help = "Ratio of traces to sample, between 0 and 1.", default_value_t = defaults::TRACE_SAMPLING_RATIO)]
+    pub trace_sampling_ratio: f64,
+
     /// Allow the web API to accept raw SQL queries.
     #[clap(long, help = "Allow the web API to accept raw SQL queries.")]
     pub accept_sql_queries: bool,
+
+    /// URL of the HTTP API of an IPFS node to which the assets of each deployed indexer are added and pinned. If not specified, assets are not published to IPFS.
+    #[clap(
+        long,
+        value_name = "URL",
+        help = "URL of the HTTP API of an IPFS node to which the assets of each deployed indexer are added and pinned. If not specified, assets are not published to IPFS."
+    )]
+    pub ipfs_api_url: Option<String>,
+}
+
+impl ConfigArgs for ApiServerArgs {
+    fn config_file(&self) -> Option<&PathBuf> {
+        self.config.as_ref()
+    }
+
+    fn settings(&self) -> Vec<(SettingPath, serde_yaml::Value)> {
+        vec![
+            setting(&["log_level"], &self.log_level),
+            setting(&["log_format"], &self.log_format),
+            setting(&["verbose"], self.verbose),
+            setting(&["fuel_node", "host"], &self.fuel_node_host),
+            setting(&["fuel_node", "port"], &self.fuel_node_port),
+            setting(&["web_api", "host"], &self.web_api_host),
+            setting(&["web_api", "port"], &self.web_api_port),
+            setting(&["web_api", "max_body_size"], self.max_body_size),
+            setting(
+                &["web_api", "cors_allowed_origins"],
+                &self.cors_allowed_origin,
+            ),
+            setting(
+                &["web_api", "cors_allowed_methods"],
+                &self.cors_allowed_method,
+            ),
+            setting(&["web_api", "cors_max_age"], self.cors_max_age),
+            setting(
+                &["web_api", "security_headers"],
+                !self.disable_security_headers,
+            ),
+            setting(&["web_api", "tls_cert"], &self.tls_cert),
+            setting(&["web_api", "tls_key"], &self.tls_key),
+            setting(
+                &["web_api", "tls_reload_interval"],
+                self.tls_reload_interval,
+            ),
+            setting(
+                &["web_api", "statement_cache_size"],
+                self.statement_cache_size,
+            ),
+            setting(&["web_api", "page_info_count"], self.page_info_count),
+            setting(&["database", "postgres", "user"], &self.postgres_user),
+            setting(
+                &["database", "postgres", "password"],
+                &self.postgres_password,
+            ),
+            setting(&["database", "postgres", "host"], &self.postgres_host),
+            setting(&["database", "postgres", "port"], &self.postgres_port),
+            setting(
+                &["database", "postgres", "database"],
+                &self.postgres_database,
+            ),
+            setting(&["run_migrations"], self.run_migrations),
+            setting(&["metrics"], self.metrics),
+            setting(&["authentication", "enabled"], self.auth_enabled),
+            setting(&["authentication", "auth_strategy"], &self.auth_strategy),
+            setting(&["authentication", "jwt_secret"], &self.jwt_secret),
+            setting(&["authentication", "jwt_issuer"], &self.jwt_issuer),
+            setting(&["authentication", "jwt_expiry"], self.jwt_expiry),
+            setting(
+                &["authentication", "require_signed_deployments"],
+                self.require_signed_deployments,
+            ),
+            setting(
+                &["authentication", "deployment_signers"],
+                &self.deployment_signer,
+            ),
+            setting(&["authentication", "siwf_domain"], &self.siwf_domain),
+            setting(&["rate_limit", "enabled"], self.rate_limit),
+            setting(
+                &["rate_limit", "request_count"],
+                self.rate_limit_request_count,
+            ),
+            setting(&["rate_limit", "window_size"], self.rate_limit_window_size),
+            setting(&["rate_limit", "client_rps"], self.rate_limit_client_rps),
+            setting(&["rate_limit", "client_rows"], self.rate_limit_client_rows),
+            setting(&["rate_limit", "indexer_rps"], self.rate_limit_indexer_rps),
+            setting(&["telemetry", "enabled"], self.telemetry),
+            setting(&["telemetry", "otlp_endpoint"], &self.otlp_endpoint),
+            setting(&["telemetry", "sampling_ratio"], self.trace_sampling_ratio),
+            setting(&["accept_sql_queries"], self.accept_sql_queries),
+            setting(&["ipfs_api_url"], &self.ipfs_api_url),
+        ]
+    }
 }
@@ -17,6 +17,16 @@ pub struct RateLimitConfig {
 
     /// Number of seconds over which to allow --rate-limit-rps.
     pub window_size: Option<u64>,
+
+    /// Maximum number of GraphQL queries per second from a single client, identified
+    /// by its authenticated subject or IP address.
+    pub client_rps: Option<u64>,
+
+    /// Maximum number of rows per second returned to a single client by GraphQL queries.
+    pub client_rows: Option<u64>,
+
+    /// Maximum number of GraphQL queries per second to a single indexer.
+    pub indexer_rps: Option<u64>,
 }
 
 impl Default for RateLimitConfig {
@@ -25,6 +35,9 @@ impl Default for RateLimitConfig {
             enabled: defaults::RATE_LIMIT_ENABLED,
             request_count: None,
             window_size: None,
+            client_rps: None,
+            client_rows: None,
+            indexer_rps: None,
         }
     }
 }
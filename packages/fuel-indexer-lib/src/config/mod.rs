@@ -3,8 +3,12 @@ pub mod cli;
 pub mod client;
 pub mod database;
 pub mod limit;
+pub mod network;
+pub mod secrets;
+pub mod telemetry;
 pub mod utils;
 pub mod web;
+pub mod webhook;
 
 pub use crate::{
     config::{
@@ -13,13 +17,23 @@ pub use crate::{
         client::FuelClientConfig,
         database::DatabaseConfig,
         limit::RateLimitConfig,
+        network::Network,
+        secrets::{resolve_secret, FileSecretsProvider, SecretsProvider},
+        telemetry::TelemetryConfig,
         web::WebApiConfig,
+        webhook::{WebhookConfig, WebhookEvent},
     },
     defaults,
     utils::*,
 };
-use serde::Deserialize;
-use std::{fs::File, path::Path, str::FromStr};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use strum::{AsRefStr, EnumString};
 use thiserror::Error;
 
@@ -63,6 +77,113 @@ pub enum IndexerConfigError {
     ParseError(#[from] url::ParseError),
     #[error("File IO error: {0:?}")]
     FileIoError(#[from] std::io::Error),
+    #[error("Unknown network '{0}'. Expected one of: beta-3, beta-4, local.")]
+    InvalidNetwork(String),
+    #[error("Unknown log format '{0}'. Expected one of: text, json.")]
+    InvalidLogFormat(String),
+    #[error("Unknown benchmark mode '{0}'. Expected one of: synthetic-load.")]
+    InvalidBenchMode(String),
+    #[error("Unknown conversion error policy '{0}'. Expected one of: fail, skip.")]
+    InvalidConversionErrorPolicy(String),
+    #[error(
+        "Unknown page info count '{0}'. Expected one of: exact, estimated, omitted."
+    )]
+    InvalidPageInfoCount(String),
+    #[error("Invalid CORS method '{0}'.")]
+    InvalidCorsMethod(String),
+    #[error("Secret error: {0}")]
+    SecretError(String),
+}
+
+/// Prefix of environment variables that override configuration settings.
+///
+/// Nested settings are separated by a double underscore, e.g.,
+/// `FUEL_INDEXER__FUEL_NODE__HOST` overrides `fuel_node.host`.
+pub const ENV_OVERRIDE_PREFIX: &str = "FUEL_INDEXER__";
+
+/// Path of a setting in the config file, e.g., `["fuel_node", "host"]`.
+pub type SettingPath = &'static [&'static str];
+
+/// CLI arguments that can be layered on top of the config file and environment.
+pub trait ConfigArgs: Parser {
+    /// Config file given by these arguments, if any.
+    fn config_file(&self) -> Option<&PathBuf>;
+
+    /// Settings given by these arguments.
+    fn settings(&self) -> Vec<(SettingPath, serde_yaml::Value)>;
+
+    /// Settings given by these arguments when no flags are passed.
+    fn default_settings() -> Vec<(SettingPath, serde_yaml::Value)> {
+        Self::parse_from(std::iter::empty::<String>()).settings()
+    }
+}
+
+/// Pair a setting path with its value.
+fn setting(path: SettingPath, value: impl Serialize) -> (SettingPath, serde_yaml::Value) {
+    let value = serde_yaml::to_value(value).expect("Failed to serialize setting.");
+    (path, value)
+}
+
+/// Return a setting value as a string, whether it was given as a string, a number, or a boolean.
+fn yaml_string(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        _ => panic!("Expected a string setting, found: {value:?}."),
+    }
+}
+
+/// Insert a value into the settings at the given path, creating sections as needed.
+fn insert_at_path<S: AsRef<str>>(
+    root: &mut serde_yaml::Value,
+    path: &[S],
+    value: serde_yaml::Value,
+) {
+    match path.split_first() {
+        None => *root = value,
+        Some((key, rest)) => {
+            if !matches!(root, serde_yaml::Value::Mapping(_)) {
+                *root = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
+            }
+
+            if let serde_yaml::Value::Mapping(map) = root {
+                let key = serde_yaml::Value::String(key.as_ref().to_string());
+                let mut section = map.remove(&key).unwrap_or(serde_yaml::Value::Null);
+                insert_at_path(&mut section, rest, value);
+                map.insert(key, section);
+            }
+        }
+    }
+}
+
+/// Apply `FUEL_INDEXER__*` environment variables on top of the given settings.
+fn apply_env_overrides(content: &mut serde_yaml::Value) {
+    for (key, value) in std::env::vars() {
+        let path = match key.strip_prefix(ENV_OVERRIDE_PREFIX) {
+            Some(path) => path,
+            None => continue,
+        };
+
+        let mut path = path
+            .split("__")
+            .map(|section| section.to_lowercase())
+            .collect::<Vec<_>>();
+
+        // Postgres is the only supported database, so its section can be omitted,
+        // e.g., `FUEL_INDEXER__DATABASE__HOST`.
+        if path.len() == 2 && path[0] == "database" {
+            path.insert(1, "postgres".to_string());
+        }
+
+        // Parse scalars so that booleans and numbers are typed as in a config file.
+        let value = match serde_yaml::from_str::<serde_yaml::Value>(&value) {
+            Ok(v @ (serde_yaml::Value::Bool(_) | serde_yaml::Value::Number(_))) => v,
+            _ => serde_yaml::Value::String(value),
+        };
+
+        insert_at_path(content, &path, value);
+    }
 }
 
 /// Used to inject environment variables into configuration.
@@ -75,6 +196,7 @@ impl Default for IndexerArgs {
         Self {
             metering_points: defaults::METERING_POINTS,
             log_level: defaults::LOG_LEVEL.to_string(),
+            log_format: defaults::LOG_FORMAT.to_string(),
             config: None,
             manifest: None,
             fuel_node_host: defaults::FUEL_NODE_HOST.to_string(),
@@ -83,6 +205,15 @@ impl Default for IndexerArgs {
             web_api_port: defaults::WEB_API_PORT.to_string(),
             database: defaults::DATABASE.to_string(),
             max_body_size: defaults::MAX_BODY_SIZE,
+            cors_allowed_origin: Vec::new(),
+            cors_allowed_method: Vec::new(),
+            cors_max_age: None,
+            disable_security_headers: !defaults::SECURITY_HEADERS,
+            tls_cert: None,
+            tls_key: None,
+            tls_reload_interval: None,
+            statement_cache_size: defaults::STATEMENT_CACHE_SIZE,
+            page_info_count: PageInfoCount::default(),
             postgres_user: Some(defaults::POSTGRES_USER.to_string()),
             postgres_database: Some(defaults::POSTGRES_DATABASE.to_string()),
             postgres_password: None,
@@ -97,25 +228,130 @@ impl Default for IndexerArgs {
             jwt_secret: None,
             jwt_issuer: None,
             jwt_expiry: None,
+            require_signed_deployments: defaults::REQUIRE_SIGNED_DEPLOYMENTS,
+            deployment_signer: Vec::new(),
+            siwf_domain: None,
             verbose: defaults::VERBOSE_LOGGING,
             local_fuel_node: defaults::LOCAL_FUEL_NODE,
             indexer_net_config: defaults::INDEXER_NET_CONFIG,
             rate_limit: defaults::RATE_LIMIT_ENABLED,
             rate_limit_request_count: Some(defaults::RATE_LIMIT_REQUEST_COUNT),
             rate_limit_window_size: Some(defaults::RATE_LIMIT_WINDOW_SIZE),
+            rate_limit_client_rps: None,
+            rate_limit_client_rows: None,
+            rate_limit_indexer_rps: None,
+            telemetry: defaults::TELEMETRY_ENABLED,
+            otlp_endpoint: defaults::OTLP_ENDPOINT.to_string(),
+            trace_sampling_ratio: defaults::TRACE_SAMPLING_RATIO,
             replace_indexer: defaults::REPLACE_INDEXER,
             accept_sql_queries: defaults::ACCEPT_SQL,
             block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            module_cache_dir: None,
+            module_cache_size: defaults::MODULE_CACHE_SIZE,
+            block_prefetch_size: defaults::BLOCK_PREFETCH_SIZE,
+            block_archive_dir: None,
+            fallback_fuel_node: Vec::new(),
+            checkpoint_interval: defaults::CHECKPOINT_INTERVAL,
+            max_cpu_share: None,
+            network: None,
+            chain_id: defaults::CHAIN_ID,
+            genesis_height: defaults::GENESIS_HEIGHT,
+            ipfs_api_url: None,
+            bench: None,
+            on_conversion_error: ConversionErrorPolicy::default(),
         }
     }
 }
 
+/// Format of the service's log output.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+
+    /// One JSON object per message, with the same top-level fields across messages.
+    Json,
+}
+
+/// Benchmark run by the service in place of indexing a Fuel node.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum BenchMode {
+    /// Index blocks generated by the service, with synthetic transactions and
+    /// receipts, instead of blocks fetched from a Fuel node.
+    SyntheticLoad,
+}
+
+/// What indexers do with transactions fetched from the Fuel node that can't be
+/// converted to the indexer's types.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    AsRefStr,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ConversionErrorPolicy {
+    /// Stop the indexer, reporting the error in its status.
+    #[default]
+    Fail,
+
+    /// Log the error and index the rest of the block without the transaction.
+    Skip,
+}
+
+/// How the total number of entities matching a paginated query, and the number of
+/// pages they make up, are reported in the query's `page_info`.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    AsRefStr,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum PageInfoCount {
+    /// Count the entities matching the query.
+    #[default]
+    Exact,
+
+    /// Estimate the number of entities in the entity's table from the database's
+    /// statistics, regardless of the query's filters.
+    Estimated,
+
+    /// Don't count entities, reporting the count and number of pages as `null`.
+    Omitted,
+}
+
 /// Fuel indexer service configuration.
 #[derive(Clone, Deserialize, Debug)]
 pub struct IndexerConfig {
     pub metering_points: Option<u64>,
     pub log_level: String,
     #[serde(default)]
+    pub log_format: LogFormat,
+    #[serde(default)]
     pub verbose: bool,
     #[serde(default)]
     pub local_fuel_node: bool,
@@ -132,9 +368,34 @@ pub struct IndexerConfig {
     pub run_migrations: bool,
     pub authentication: AuthenticationConfig,
     pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
     pub replace_indexer: bool,
     pub accept_sql_queries: bool,
     pub node_block_page_size: usize,
+    #[serde(default)]
+    pub module_cache_dir: Option<PathBuf>,
+    pub module_cache_size: usize,
+    pub block_prefetch_size: usize,
+    #[serde(default)]
+    pub block_archive_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub fallback_fuel_nodes: Vec<String>,
+    pub checkpoint_interval: u64,
+    #[serde(default)]
+    pub max_cpu_share: Option<f64>,
+    #[serde(default)]
+    pub network: Option<Network>,
+    pub chain_id: u64,
+    pub genesis_height: u64,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    #[serde(default)]
+    pub ipfs_api_url: Option<String>,
+    #[serde(default)]
+    pub bench: Option<BenchMode>,
+    #[serde(default)]
+    pub on_conversion_error: ConversionErrorPolicy,
 }
 
 impl Default for IndexerConfig {
@@ -142,6 +403,7 @@ impl Default for IndexerConfig {
         Self {
             metering_points: Some(defaults::METERING_POINTS),
             log_level: defaults::LOG_LEVEL.to_string(),
+            log_format: LogFormat::default(),
             verbose: defaults::VERBOSE_LOGGING,
             local_fuel_node: defaults::LOCAL_FUEL_NODE,
             indexer_net_config: defaults::INDEXER_NET_CONFIG,
@@ -153,9 +415,24 @@ impl Default for IndexerConfig {
             run_migrations: defaults::RUN_MIGRATIONS,
             authentication: AuthenticationConfig::default(),
             rate_limit: RateLimitConfig::default(),
+            telemetry: TelemetryConfig::default(),
             replace_indexer: defaults::REPLACE_INDEXER,
             accept_sql_queries: defaults::ACCEPT_SQL,
             node_block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            module_cache_dir: None,
+            module_cache_size: defaults::MODULE_CACHE_SIZE,
+            block_prefetch_size: defaults::BLOCK_PREFETCH_SIZE,
+            block_archive_dir: None,
+            fallback_fuel_nodes: Vec::new(),
+            checkpoint_interval: defaults::CHECKPOINT_INTERVAL,
+            max_cpu_share: None,
+            network: None,
+            chain_id: defaults::CHAIN_ID,
+            genesis_height: defaults::GENESIS_HEIGHT,
+            webhooks: Vec::new(),
+            ipfs_api_url: None,
+            bench: None,
+            on_conversion_error: ConversionErrorPolicy::default(),
         }
     }
 }
@@ -204,6 +481,7 @@ impl From<IndexerArgs> for IndexerConfig {
         let mut config = IndexerConfig {
             metering_points: Some(args.metering_points),
             log_level: args.log_level,
+            log_format: LogFormat::from_str(&args.log_format).unwrap_or_default(),
             verbose: args.verbose,
             local_fuel_node: args.local_fuel_node,
             indexer_net_config: args.indexer_net_config,
@@ -216,6 +494,16 @@ impl From<IndexerArgs> for IndexerConfig {
                 host: args.web_api_host,
                 port: args.web_api_port,
                 max_body_size: args.max_body_size,
+                cors_allowed_origins: args.cors_allowed_origin,
+                cors_allowed_methods: args.cors_allowed_method,
+                cors_max_age: args.cors_max_age,
+                cors_indexer_origins: HashMap::new(),
+                security_headers: !args.disable_security_headers,
+                tls_cert: args.tls_cert,
+                tls_key: args.tls_key,
+                tls_reload_interval: args.tls_reload_interval,
+                statement_cache_size: args.statement_cache_size,
+                page_info_count: args.page_info_count,
             },
             metrics: args.metrics,
             stop_idle_indexers: args.stop_idle_indexers,
@@ -228,17 +516,59 @@ impl From<IndexerArgs> for IndexerConfig {
                 jwt_secret: args.jwt_secret,
                 jwt_issuer: args.jwt_issuer,
                 jwt_expiry: args.jwt_expiry,
+                require_signed_deployments: args.require_signed_deployments,
+                deployment_signers: args.deployment_signer,
+                siwf_domain: args.siwf_domain,
             },
             rate_limit: RateLimitConfig {
                 enabled: args.rate_limit,
                 request_count: args.rate_limit_request_count,
                 window_size: args.rate_limit_window_size,
+                client_rps: args.rate_limit_client_rps,
+                client_rows: args.rate_limit_client_rows,
+                indexer_rps: args.rate_limit_indexer_rps,
+            },
+            telemetry: TelemetryConfig {
+                enabled: args.telemetry,
+                otlp_endpoint: args.otlp_endpoint,
+                sampling_ratio: args.trace_sampling_ratio,
             },
             replace_indexer: args.replace_indexer,
             accept_sql_queries: args.accept_sql_queries,
             node_block_page_size: args.block_page_size,
+            module_cache_dir: args.module_cache_dir,
+            module_cache_size: args.module_cache_size,
+            block_prefetch_size: args.block_prefetch_size,
+            block_archive_dir: args.block_archive_dir,
+            fallback_fuel_nodes: args.fallback_fuel_node,
+            checkpoint_interval: args.checkpoint_interval,
+            max_cpu_share: args.max_cpu_share,
+            network: args.network,
+            chain_id: args.chain_id,
+            genesis_height: args.genesis_height,
+            webhooks: Vec::new(),
+            ipfs_api_url: args.ipfs_api_url,
+            bench: args.bench,
+            on_conversion_error: args.on_conversion_error,
         };
 
+        // Network presets only fill in settings that were left at their defaults.
+        if let Some(network) = args.network {
+            let preset = network.fuel_node();
+            if config.fuel_node.host == defaults::FUEL_NODE_HOST {
+                config.fuel_node.host = preset.host;
+            }
+            if config.fuel_node.port == defaults::FUEL_NODE_PORT {
+                config.fuel_node.port = preset.port;
+            }
+            if config.chain_id == defaults::CHAIN_ID {
+                config.chain_id = network.chain_id();
+            }
+            if config.genesis_height == defaults::GENESIS_HEIGHT {
+                config.genesis_height = network.genesis_height();
+            }
+        }
+
         config
             .inject_opt_env_vars()
             .expect("Failed to inject env vars.");
@@ -291,6 +621,7 @@ impl From<ApiServerArgs> for IndexerConfig {
         let mut config = IndexerConfig {
             metering_points: Some(defaults::METERING_POINTS),
             log_level: args.log_level,
+            log_format: LogFormat::from_str(&args.log_format).unwrap_or_default(),
             verbose: args.verbose,
             local_fuel_node: defaults::LOCAL_FUEL_NODE,
             indexer_net_config: defaults::INDEXER_NET_CONFIG,
@@ -303,6 +634,16 @@ impl From<ApiServerArgs> for IndexerConfig {
                 host: args.web_api_host,
                 port: args.web_api_port,
                 max_body_size: args.max_body_size,
+                cors_allowed_origins: args.cors_allowed_origin,
+                cors_allowed_methods: args.cors_allowed_method,
+                cors_max_age: args.cors_max_age,
+                cors_indexer_origins: HashMap::new(),
+                security_headers: !args.disable_security_headers,
+                tls_cert: args.tls_cert,
+                tls_key: args.tls_key,
+                tls_reload_interval: args.tls_reload_interval,
+                statement_cache_size: args.statement_cache_size,
+                page_info_count: args.page_info_count,
             },
             metrics: args.metrics,
             stop_idle_indexers: defaults::STOP_IDLE_INDEXERS,
@@ -315,15 +656,40 @@ impl From<ApiServerArgs> for IndexerConfig {
                 jwt_secret: args.jwt_secret,
                 jwt_issuer: args.jwt_issuer,
                 jwt_expiry: args.jwt_expiry,
+                require_signed_deployments: args.require_signed_deployments,
+                deployment_signers: args.deployment_signer,
+                siwf_domain: args.siwf_domain,
             },
             rate_limit: RateLimitConfig {
                 enabled: args.rate_limit,
                 request_count: args.rate_limit_request_count,
                 window_size: args.rate_limit_window_size,
+                client_rps: args.rate_limit_client_rps,
+                client_rows: args.rate_limit_client_rows,
+                indexer_rps: args.rate_limit_indexer_rps,
+            },
+            telemetry: TelemetryConfig {
+                enabled: args.telemetry,
+                otlp_endpoint: args.otlp_endpoint,
+                sampling_ratio: args.trace_sampling_ratio,
             },
             replace_indexer: defaults::REPLACE_INDEXER,
             accept_sql_queries: args.accept_sql_queries,
             node_block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            module_cache_dir: None,
+            module_cache_size: defaults::MODULE_CACHE_SIZE,
+            block_prefetch_size: defaults::BLOCK_PREFETCH_SIZE,
+            block_archive_dir: None,
+            fallback_fuel_nodes: Vec::new(),
+            checkpoint_interval: defaults::CHECKPOINT_INTERVAL,
+            max_cpu_share: None,
+            network: None,
+            chain_id: defaults::CHAIN_ID,
+            genesis_height: defaults::GENESIS_HEIGHT,
+            webhooks: Vec::new(),
+            ipfs_api_url: args.ipfs_api_url,
+            bench: None,
+            on_conversion_error: ConversionErrorPolicy::default(),
         };
 
         config
@@ -335,17 +701,75 @@ impl From<ApiServerArgs> for IndexerConfig {
 }
 
 impl IndexerConfig {
-    // When building the config via a file, if any section (e.g., graphql, fuel_node, etc),
-    // or if any individual setting in a section (e.g., fuel_node.host) is empty, replace it
-    // with its respective default value.
+    /// Build a config from the given file, with `FUEL_INDEXER__*` environment
+    /// variables applied on top of it.
     pub fn from_file(path: impl AsRef<Path>) -> IndexerConfigResult<Self> {
         let file = File::open(path)?;
+        let mut content: serde_yaml::Value = serde_yaml::from_reader(file)?;
+        apply_env_overrides(&mut content);
+
+        Self::from_value(content)
+    }
+
+    /// Build a config by layering, from lowest to highest precedence: defaults, the
+    /// config file (if any), `FUEL_INDEXER__*` environment variables, and CLI flags.
+    ///
+    /// Only CLI flags that differ from their default value take precedence over
+    /// the config file and the environment.
+    pub fn load<A: ConfigArgs>(args: &A) -> IndexerConfigResult<Self> {
+        let mut content = match args.config_file() {
+            Some(path) => serde_yaml::from_reader(File::open(path)?)?,
+            None => serde_yaml::Value::Mapping(serde_yaml::Mapping::new()),
+        };
+
+        for (var, key) in [
+            (EnvVar::PostgresUser, "user"),
+            (EnvVar::PostgresPassword, "password"),
+            (EnvVar::PostgresHost, "host"),
+            (EnvVar::PostgresPort, "port"),
+            (EnvVar::PostgresDatabase, "database"),
+        ] {
+            if let Ok(value) = std::env::var(var.as_ref()) {
+                insert_at_path(
+                    &mut content,
+                    &["database", "postgres", key],
+                    serde_yaml::Value::String(value),
+                );
+            }
+        }
+
+        apply_env_overrides(&mut content);
+
+        let defaults = A::default_settings();
+        for (path, value) in args.settings() {
+            if value.is_null() || defaults.contains(&(path, value.clone())) {
+                continue;
+            }
+            insert_at_path(&mut content, path, value);
+        }
 
+        Self::from_value(content)
+    }
+
+    // When building the config from settings, if any section (e.g., graphql, fuel_node, etc),
+    // or if any individual setting in a section (e.g., fuel_node.host) is empty, replace it
+    // with its respective default value.
+    fn from_value(content: serde_yaml::Value) -> IndexerConfigResult<Self> {
         let mut config = IndexerConfig::default();
 
-        let content: serde_yaml::Value = serde_yaml::from_reader(file)?;
+        // Network presets are applied first, so that any other setting overrides them.
+        let network_key = serde_yaml::Value::String("network".into());
+        if let Some(network) = content.get(network_key) {
+            let network = Network::from_str(&yaml_string(network))
+                .map_err(|_| IndexerConfigError::InvalidNetwork(yaml_string(network)))?;
+            config.network = Some(network);
+            config.fuel_node = network.fuel_node();
+            config.chain_id = network.chain_id();
+            config.genesis_height = network.genesis_height();
+        }
 
         let log_level_key = serde_yaml::Value::String("log_level".into());
+        let log_format_key = serde_yaml::Value::String("log_format".into());
         let replace_indexer_key = serde_yaml::Value::String("replace_indexer".into());
         let metering_points_key = serde_yaml::Value::String("metering_points".into());
         let metrics_key = serde_yaml::Value::String("metrics".into());
@@ -363,6 +787,9 @@ impl IndexerConfig {
         let node_block_page_size_key =
             serde_yaml::Value::String("block_page_size".into());
 
+        let module_cache_dir_key = serde_yaml::Value::String("module_cache_dir".into());
+        let module_cache_size_key = serde_yaml::Value::String("module_cache_size".into());
+
         if let Some(accept_sql_queries) = content.get(accept_sql_config_key) {
             config.accept_sql_queries = accept_sql_queries.as_bool().unwrap();
         }
@@ -376,7 +803,14 @@ impl IndexerConfig {
         }
 
         if let Some(log_level) = content.get(log_level_key) {
-            config.log_level = log_level.as_str().unwrap().to_string();
+            config.log_level = yaml_string(log_level);
+        }
+
+        if let Some(log_format) = content.get(log_format_key) {
+            config.log_format =
+                LogFormat::from_str(&yaml_string(log_format)).map_err(|_| {
+                    IndexerConfigError::InvalidLogFormat(yaml_string(log_format))
+                })?;
         }
 
         if let Some(metrics) = content.get(metrics_key) {
@@ -407,34 +841,113 @@ impl IndexerConfig {
             config.node_block_page_size = node_block_page_size.as_u64().unwrap() as usize;
         }
 
+        if let Some(module_cache_dir) = content.get(module_cache_dir_key) {
+            config.module_cache_dir = Some(PathBuf::from(yaml_string(module_cache_dir)));
+        }
+
+        if let Some(module_cache_size) = content.get(module_cache_size_key) {
+            config.module_cache_size = module_cache_size.as_u64().unwrap() as usize;
+        }
+
+        let block_prefetch_size_key =
+            serde_yaml::Value::String("block_prefetch_size".into());
+        if let Some(block_prefetch_size) = content.get(block_prefetch_size_key) {
+            config.block_prefetch_size = block_prefetch_size.as_u64().unwrap() as usize;
+        }
+
+        let block_archive_dir_key = serde_yaml::Value::String("block_archive_dir".into());
+        if let Some(block_archive_dir) = content.get(block_archive_dir_key) {
+            config.block_archive_dir =
+                Some(PathBuf::from(yaml_string(block_archive_dir)));
+        }
+
+        let ipfs_api_url_key = serde_yaml::Value::String("ipfs_api_url".into());
+        if let Some(ipfs_api_url) = content.get(ipfs_api_url_key) {
+            config.ipfs_api_url = Some(yaml_string(ipfs_api_url));
+        }
+
+        let bench_key = serde_yaml::Value::String("bench".into());
+        if let Some(bench) = content.get(bench_key) {
+            let bench = BenchMode::from_str(&yaml_string(bench))
+                .map_err(|_| IndexerConfigError::InvalidBenchMode(yaml_string(bench)))?;
+            config.bench = Some(bench);
+        }
+
+        let on_conversion_error_key =
+            serde_yaml::Value::String("on_conversion_error".into());
+        if let Some(policy) = content.get(on_conversion_error_key) {
+            config.on_conversion_error =
+                ConversionErrorPolicy::from_str(&yaml_string(policy)).map_err(|_| {
+                    IndexerConfigError::InvalidConversionErrorPolicy(yaml_string(policy))
+                })?;
+        }
+
+        let fallback_fuel_nodes_key =
+            serde_yaml::Value::String("fallback_fuel_nodes".into());
+        if let Some(fallback_fuel_nodes) = content.get(fallback_fuel_nodes_key) {
+            config.fallback_fuel_nodes = fallback_fuel_nodes
+                .as_sequence()
+                .expect("fallback_fuel_nodes must be a list.")
+                .iter()
+                .map(yaml_string)
+                .collect();
+        }
+
+        let checkpoint_interval_key =
+            serde_yaml::Value::String("checkpoint_interval".into());
+        if let Some(checkpoint_interval) = content.get(checkpoint_interval_key) {
+            config.checkpoint_interval = checkpoint_interval.as_u64().unwrap();
+        }
+
+        let max_cpu_share_key = serde_yaml::Value::String("max_cpu_share".into());
+        if let Some(max_cpu_share) = content.get(max_cpu_share_key) {
+            config.max_cpu_share = max_cpu_share.as_f64();
+        }
+
+        let chain_id_key = serde_yaml::Value::String("chain_id".into());
+        if let Some(chain_id) = content.get(chain_id_key) {
+            config.chain_id = chain_id.as_u64().unwrap();
+        }
+
+        let genesis_height_key = serde_yaml::Value::String("genesis_height".into());
+        if let Some(genesis_height) = content.get(genesis_height_key) {
+            config.genesis_height = genesis_height.as_u64().unwrap();
+        }
+
+        let webhooks_key = serde_yaml::Value::String("webhooks".into());
+        if let Some(webhooks) = content.get(webhooks_key) {
+            config.webhooks = serde_yaml::from_value(webhooks.clone())?;
+        }
+
         let fuel_config_key = serde_yaml::Value::String("fuel_node".into());
         let web_config_key = serde_yaml::Value::String("web_api".into());
         let database_config_key = serde_yaml::Value::String("database".into());
         let auth_config_key = serde_yaml::Value::String("authentication".into());
         let rate_limit_config_key = serde_yaml::Value::String("rate_limit".into());
+        let telemetry_config_key = serde_yaml::Value::String("telemetry".into());
 
         if let Some(section) = content.get(fuel_config_key) {
             let fuel_node_host = section.get(&serde_yaml::Value::String("host".into()));
 
             if let Some(fuel_node_host) = fuel_node_host {
-                config.fuel_node.host = fuel_node_host.as_str().unwrap().to_string();
+                config.fuel_node.host = yaml_string(fuel_node_host);
             }
             let fuel_node_port = section.get(&serde_yaml::Value::String("port".into()));
 
             if let Some(fuel_node_port) = fuel_node_port {
-                config.fuel_node.port = fuel_node_port.as_u64().unwrap().to_string();
+                config.fuel_node.port = yaml_string(fuel_node_port);
             }
         }
 
         if let Some(section) = content.get(web_config_key) {
             let web_api_host = section.get(&serde_yaml::Value::String("host".into()));
             if let Some(web_api_host) = web_api_host {
-                config.web_api.host = web_api_host.as_str().unwrap().to_string();
+                config.web_api.host = yaml_string(web_api_host);
             }
 
             let web_api_port = section.get(&serde_yaml::Value::String("port".into()));
             if let Some(web_api_port) = web_api_port {
-                config.web_api.port = web_api_port.as_u64().unwrap().to_string();
+                config.web_api.port = yaml_string(web_api_port);
             }
 
             let max_body_size =
@@ -443,6 +956,92 @@ impl IndexerConfig {
             if let Some(max_body_size) = max_body_size {
                 config.web_api.max_body_size = max_body_size.as_u64().unwrap() as usize;
             }
+
+            let yaml_list = |value: &serde_yaml::Value, key: &str| -> Vec<String> {
+                value
+                    .as_sequence()
+                    .unwrap_or_else(|| panic!("{key} must be a list."))
+                    .iter()
+                    .map(yaml_string)
+                    .collect()
+            };
+
+            let cors_allowed_origins =
+                section.get(&serde_yaml::Value::String("cors_allowed_origins".into()));
+            if let Some(cors_allowed_origins) = cors_allowed_origins {
+                config.web_api.cors_allowed_origins =
+                    yaml_list(cors_allowed_origins, "cors_allowed_origins");
+            }
+
+            let cors_allowed_methods =
+                section.get(&serde_yaml::Value::String("cors_allowed_methods".into()));
+            if let Some(cors_allowed_methods) = cors_allowed_methods {
+                config.web_api.cors_allowed_methods =
+                    yaml_list(cors_allowed_methods, "cors_allowed_methods");
+                config.web_api.cors_methods()?;
+            }
+
+            let cors_max_age =
+                section.get(&serde_yaml::Value::String("cors_max_age".into()));
+            if let Some(cors_max_age) = cors_max_age {
+                config.web_api.cors_max_age = cors_max_age.as_u64();
+            }
+
+            let cors_indexer_origins =
+                section.get(&serde_yaml::Value::String("cors_indexer_origins".into()));
+            if let Some(cors_indexer_origins) = cors_indexer_origins {
+                config.web_api.cors_indexer_origins = cors_indexer_origins
+                    .as_mapping()
+                    .expect("cors_indexer_origins must be a mapping.")
+                    .iter()
+                    .map(|(indexer, origins)| {
+                        (
+                            yaml_string(indexer),
+                            yaml_list(origins, "cors_indexer_origins"),
+                        )
+                    })
+                    .collect();
+            }
+
+            let security_headers =
+                section.get(&serde_yaml::Value::String("security_headers".into()));
+            if let Some(security_headers) = security_headers {
+                config.web_api.security_headers = security_headers.as_bool().unwrap();
+            }
+
+            let tls_cert = section.get(&serde_yaml::Value::String("tls_cert".into()));
+            if let Some(tls_cert) = tls_cert {
+                config.web_api.tls_cert = Some(PathBuf::from(yaml_string(tls_cert)));
+            }
+
+            let tls_key = section.get(&serde_yaml::Value::String("tls_key".into()));
+            if let Some(tls_key) = tls_key {
+                config.web_api.tls_key = Some(PathBuf::from(yaml_string(tls_key)));
+            }
+
+            let tls_reload_interval =
+                section.get(&serde_yaml::Value::String("tls_reload_interval".into()));
+            if let Some(tls_reload_interval) = tls_reload_interval {
+                config.web_api.tls_reload_interval = tls_reload_interval.as_u64();
+            }
+
+            let statement_cache_size =
+                section.get(&serde_yaml::Value::String("statement_cache_size".into()));
+            if let Some(statement_cache_size) = statement_cache_size {
+                config.web_api.statement_cache_size =
+                    statement_cache_size.as_u64().unwrap() as usize;
+            }
+
+            let page_info_count =
+                section.get(&serde_yaml::Value::String("page_info_count".into()));
+            if let Some(page_info_count) = page_info_count {
+                config.web_api.page_info_count = PageInfoCount::from_str(&yaml_string(
+                    page_info_count,
+                ))
+                .map_err(|_| {
+                    IndexerConfigError::InvalidPageInfoCount(yaml_string(page_info_count))
+                })?;
+            }
         }
 
         if let Some(section) = content.get(database_config_key) {
@@ -458,31 +1057,46 @@ impl IndexerConfig {
                 let pg_host_value =
                     pg_section.get(&serde_yaml::Value::String("host".into()));
                 if let Some(pg_host_value) = pg_host_value {
-                    pg_host = pg_host_value.as_str().unwrap().to_string();
+                    pg_host = yaml_string(pg_host_value);
                 }
 
                 let pg_port_value =
                     pg_section.get(&serde_yaml::Value::String("port".into()));
                 if let Some(pg_port_value) = pg_port_value {
-                    pg_port = pg_port_value.as_u64().unwrap().to_string();
+                    pg_port = yaml_string(pg_port_value);
                 }
 
                 let pg_username_value =
                     pg_section.get(&serde_yaml::Value::String("user".into()));
                 if let Some(pg_username_value) = pg_username_value {
-                    pg_user = pg_username_value.as_str().unwrap().to_string();
+                    pg_user = yaml_string(pg_username_value);
                 }
 
                 let pg_password_value =
                     pg_section.get(&serde_yaml::Value::String("password".into()));
                 if let Some(pg_password_value) = pg_password_value {
-                    pg_password = pg_password_value.as_str().unwrap().to_string();
+                    pg_password = yaml_string(pg_password_value);
+                }
+
+                // Passwords can be read from a file or a secrets provider, rather
+                // than written in plain text.
+                let pg_password_file_value =
+                    pg_section.get(&serde_yaml::Value::String("password_file".into()));
+                if let Some(pg_password_file_value) = pg_password_file_value {
+                    pg_password = FileSecretsProvider
+                        .get_secret(&yaml_string(pg_password_file_value))?;
+                }
+
+                let pg_password_secret_value =
+                    pg_section.get(&serde_yaml::Value::String("password_secret".into()));
+                if let Some(pg_password_secret_value) = pg_password_secret_value {
+                    pg_password = resolve_secret(&yaml_string(pg_password_secret_value))?;
                 }
 
                 let pg_database_value =
                     pg_section.get(&serde_yaml::Value::String("database".into()));
                 if let Some(pg_database_value) = pg_database_value {
-                    pg_db = pg_database_value.as_str().unwrap().to_string();
+                    pg_db = yaml_string(pg_database_value);
                 }
 
                 config.database = DatabaseConfig::Postgres {
@@ -506,20 +1120,45 @@ impl IndexerConfig {
                 section.get(&serde_yaml::Value::String("auth_strategy".into()));
             if let Some(strategy) = strategy {
                 config.authentication.strategy = Some(
-                    AuthenticationStrategy::from_str(strategy.as_str().unwrap()).unwrap(),
+                    AuthenticationStrategy::from_str(&yaml_string(strategy)).unwrap(),
                 );
             }
 
             let jwt_secret = section.get(&serde_yaml::Value::String("jwt_secret".into()));
             if let Some(jwt_secret) = jwt_secret {
-                config.authentication.jwt_secret =
-                    Some(jwt_secret.as_str().unwrap().to_string());
+                config.authentication.jwt_secret = Some(yaml_string(jwt_secret));
+            }
+
+            let jwt_expiry = section.get(&serde_yaml::Value::String("jwt_expiry".into()));
+            if let Some(jwt_expiry) = jwt_expiry {
+                config.authentication.jwt_expiry =
+                    Some(jwt_expiry.as_u64().unwrap() as usize);
             }
 
             let jwt_issuer = section.get(&serde_yaml::Value::String("jwt_issuer".into()));
             if let Some(jwt_issuer) = jwt_issuer {
-                config.authentication.jwt_issuer =
-                    Some(jwt_issuer.as_str().unwrap().to_string());
+                config.authentication.jwt_issuer = Some(yaml_string(jwt_issuer));
+            }
+
+            let require_signed_deployments = section.get(&serde_yaml::Value::String(
+                "require_signed_deployments".into(),
+            ));
+            if let Some(require_signed_deployments) = require_signed_deployments {
+                config.authentication.require_signed_deployments =
+                    require_signed_deployments.as_bool().unwrap();
+            }
+
+            let deployment_signers =
+                section.get(&serde_yaml::Value::String("deployment_signers".into()));
+            if let Some(deployment_signers) = deployment_signers {
+                config.authentication.deployment_signers =
+                    yaml_list(deployment_signers, "deployment_signers");
+            }
+
+            let siwf_domain =
+                section.get(&serde_yaml::Value::String("siwf_domain".into()));
+            if let Some(siwf_domain) = siwf_domain {
+                config.authentication.siwf_domain = Some(yaml_string(siwf_domain));
             }
         }
 
@@ -540,6 +1179,42 @@ impl IndexerConfig {
             if let Some(window_size) = window_size {
                 config.rate_limit.window_size = Some(window_size.as_u64().unwrap());
             }
+
+            let client_rps = section.get(&serde_yaml::Value::String("client_rps".into()));
+            if let Some(client_rps) = client_rps {
+                config.rate_limit.client_rps = Some(client_rps.as_u64().unwrap());
+            }
+
+            let client_rows =
+                section.get(&serde_yaml::Value::String("client_rows".into()));
+            if let Some(client_rows) = client_rows {
+                config.rate_limit.client_rows = Some(client_rows.as_u64().unwrap());
+            }
+
+            let indexer_rps =
+                section.get(&serde_yaml::Value::String("indexer_rps".into()));
+            if let Some(indexer_rps) = indexer_rps {
+                config.rate_limit.indexer_rps = Some(indexer_rps.as_u64().unwrap());
+            }
+        }
+
+        if let Some(section) = content.get(telemetry_config_key) {
+            let enabled = section.get(&serde_yaml::Value::String("enabled".into()));
+            if let Some(enabled) = enabled {
+                config.telemetry.enabled = enabled.as_bool().unwrap();
+            }
+
+            let otlp_endpoint =
+                section.get(&serde_yaml::Value::String("otlp_endpoint".into()));
+            if let Some(otlp_endpoint) = otlp_endpoint {
+                config.telemetry.otlp_endpoint = yaml_string(otlp_endpoint);
+            }
+
+            let sampling_ratio =
+                section.get(&serde_yaml::Value::String("sampling_ratio".into()));
+            if let Some(sampling_ratio) = sampling_ratio {
+                config.telemetry.sampling_ratio = sampling_ratio.as_f64().unwrap();
+            }
         }
 
         config.inject_opt_env_vars()?;
@@ -611,6 +1286,100 @@ mod tests {
         fs::remove_file(file_path).unwrap();
     }
 
+    #[test]
+    fn test_indexer_config_layers_env_vars_and_cli_flags_over_file() {
+        let file_path: &str = "foo4.yaml";
+        let config_str = r#"
+    block_page_size: 20
+    web_api:
+      max_body_size: 1000
+    "#;
+
+        fs::write(file_path, config_str).unwrap();
+        std::env::set_var("FUEL_INDEXER__WEB_API__MAX_BODY_SIZE", "2000");
+
+        let args = IndexerArgs::parse_from([
+            "fuel-indexer",
+            "--config",
+            file_path,
+            "--module-cache-size",
+            "7",
+        ]);
+        let config = IndexerConfig::load(&args).unwrap();
+
+        std::env::remove_var("FUEL_INDEXER__WEB_API__MAX_BODY_SIZE");
+        fs::remove_file(file_path).unwrap();
+
+        assert_eq!(config.node_block_page_size, 20);
+        assert_eq!(config.web_api.max_body_size, 2000);
+        assert_eq!(config.module_cache_size, 7);
+    }
+
+    #[test]
+    fn test_indexer_config_network_preset_is_overridden_by_explicit_settings() {
+        let file_path: &str = "foo6.yaml";
+        let config_str = r#"
+        network: beta-4
+        fuel_node:
+          port: 4000
+        "#;
+
+        fs::write(file_path, config_str).unwrap();
+        let config = IndexerConfig::from_file(file_path).unwrap();
+        fs::remove_file(file_path).unwrap();
+
+        assert_eq!(config.network, Some(Network::Beta4));
+        assert_eq!(config.fuel_node.host, defaults::BETA_4_FUEL_NODE_HOST);
+        assert_eq!(config.fuel_node.port, "4000");
+        assert_eq!(config.chain_id, defaults::TESTNET_CHAIN_ID);
+    }
+
+    #[test]
+    fn test_indexer_config_reads_password_file_and_redacts_it() {
+        let file_path: &str = "foo5.yaml";
+        let password_path: &str = "foo5_password.txt";
+        let config_str = format!(
+            r#"
+        database:
+          postgres:
+            user: jimmy
+            password_file: {password_path}
+        "#
+        );
+
+        fs::write(file_path, config_str).unwrap();
+        fs::write(password_path, "password_from_file\n").unwrap();
+        let config = IndexerConfig::from_file(file_path).unwrap();
+        fs::remove_file(file_path).unwrap();
+        fs::remove_file(password_path).unwrap();
+
+        let DatabaseConfig::Postgres { ref password, .. } = config.database;
+        assert_eq!(password, "password_from_file");
+        assert!(!format!("{config:?}").contains("password_from_file"));
+    }
+
+    #[test]
+    fn test_indexer_config_reads_webhooks() {
+        let file_path: &str = "foo7.yaml";
+        let config_str = r#"
+        webhooks:
+          - url: https://example.com/hooks/indexer
+            secret: webhook_secret_value
+            events: [caught_up, error]
+          - url: https://example.com/hooks/all
+        "#;
+
+        fs::write(file_path, config_str).unwrap();
+        let config = IndexerConfig::from_file(file_path).unwrap();
+        fs::remove_file(file_path).unwrap();
+
+        assert_eq!(config.webhooks.len(), 2);
+        assert!(config.webhooks[0].accepts(WebhookEvent::Error));
+        assert!(!config.webhooks[0].accepts(WebhookEvent::Started));
+        assert!(config.webhooks[1].accepts(WebhookEvent::Started));
+        assert!(!format!("{config:?}").contains("webhook_secret_value"));
+    }
+
     #[test]
     fn test_indexer_config_will_supplement_individual_config_vars_in_sections() {
         let file_path: &str = "foo3.yaml";
@@ -647,4 +1416,35 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_indexer_config_rejects_invalid_cors_methods() {
+        let file_path: &str = "foo8.yaml";
+        let config_str = r#"
+        web_api:
+          cors_allowed_methods:
+            - get
+            - "NOT A METHOD"
+        "#;
+
+        fs::write(file_path, config_str).unwrap();
+        let result = IndexerConfig::from_file(file_path);
+        fs::remove_file(file_path).unwrap();
+
+        match result {
+            Err(IndexerConfigError::InvalidCorsMethod(method)) => {
+                assert_eq!(method, "NOT A METHOD")
+            }
+            _ => panic!("Expected an invalid CORS method error."),
+        }
+
+        let config = WebApiConfig {
+            cors_allowed_methods: vec!["get".to_string(), "delete".to_string()],
+            ..WebApiConfig::default()
+        };
+        assert_eq!(
+            config.cors_methods().unwrap(),
+            vec![http::Method::GET, http::Method::DELETE]
+        );
+    }
 }
@@ -0,0 +1,69 @@
+use crate::{config::client::FuelClientConfig, defaults};
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, EnumString};
+
+/// A well-known Fuel network, used to populate network-specific settings.
+///
+/// Settings given explicitly, in the config file, environment, or CLI, take
+/// precedence over those populated by the network.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum Network {
+    /// The beta-3 testnet.
+    #[serde(rename = "beta-3")]
+    #[strum(serialize = "beta-3")]
+    Beta3,
+
+    /// The beta-4 testnet.
+    #[serde(rename = "beta-4")]
+    #[strum(serialize = "beta-4")]
+    Beta4,
+
+    /// A Fuel node running on the local machine.
+    Local,
+}
+
+impl Network {
+    /// Fuel node serving this network.
+    pub fn fuel_node(&self) -> FuelClientConfig {
+        let host = match self {
+            Network::Beta3 => defaults::BETA_3_FUEL_NODE_HOST,
+            Network::Beta4 => defaults::BETA_4_FUEL_NODE_HOST,
+            Network::Local => defaults::FUEL_NODE_HOST,
+        };
+        let port = match self {
+            Network::Beta3 | Network::Beta4 => defaults::TESTNET_FUEL_NODE_PORT,
+            Network::Local => defaults::FUEL_NODE_PORT,
+        };
+
+        FuelClientConfig {
+            host: host.to_string(),
+            port: port.to_string(),
+        }
+    }
+
+    /// Chain ID of this network.
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            Network::Beta3 | Network::Beta4 => defaults::TESTNET_CHAIN_ID,
+            Network::Local => defaults::CHAIN_ID,
+        }
+    }
+
+    /// Height of the first block of this network.
+    pub fn genesis_height(&self) -> u64 {
+        match self {
+            Network::Beta3 | Network::Beta4 => defaults::TESTNET_GENESIS_HEIGHT,
+            Network::Local => defaults::GENESIS_HEIGHT,
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
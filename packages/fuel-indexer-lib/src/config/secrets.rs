@@ -0,0 +1,243 @@
+use crate::config::{IndexerConfigError, IndexerConfigResult};
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{thread, time::Duration};
+#[cfg(not(target_arch = "wasm32"))]
+use url::Url;
+
+/// Environment variable holding the address of a Vault-compatible server.
+const VAULT_ADDR: &str = "VAULT_ADDR";
+
+/// Environment variable holding the token used to authenticate with Vault.
+const VAULT_TOKEN: &str = "VAULT_TOKEN";
+
+/// Header holding the token of a request to Vault.
+#[cfg(not(target_arch = "wasm32"))]
+const VAULT_TOKEN_HEADER: &str = "X-Vault-Token";
+
+/// Number of seconds to wait for a connection to Vault.
+#[cfg(not(target_arch = "wasm32"))]
+const VAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+
+/// Number of seconds to wait for a response from Vault.
+#[cfg(not(target_arch = "wasm32"))]
+const VAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// A source of secret values, such as database credentials.
+pub trait SecretsProvider {
+    /// Return the secret stored under the given key.
+    fn get_secret(&self, key: &str) -> IndexerConfigResult<String>;
+}
+
+/// Reads secrets from environment variables, keyed by variable name.
+#[derive(Debug, Default)]
+pub struct EnvSecretsProvider;
+
+impl SecretsProvider for EnvSecretsProvider {
+    fn get_secret(&self, key: &str) -> IndexerConfigResult<String> {
+        std::env::var(key).map_err(|e| {
+            IndexerConfigError::SecretError(format!(
+                "Could not read environment variable '{key}': {e}"
+            ))
+        })
+    }
+}
+
+/// Reads secrets from files, keyed by path.
+///
+/// Trailing newlines are removed, so that secrets written by tools such as
+/// `echo` or mounted by container orchestrators can be used as-is.
+#[derive(Debug, Default)]
+pub struct FileSecretsProvider;
+
+impl SecretsProvider for FileSecretsProvider {
+    fn get_secret(&self, key: &str) -> IndexerConfigResult<String> {
+        let secret = fs::read_to_string(key).map_err(|e| {
+            IndexerConfigError::SecretError(format!(
+                "Could not read secret file '{key}': {e}"
+            ))
+        })?;
+        Ok(secret.trim_end_matches(['\r', '\n']).to_string())
+    }
+}
+
+/// Reads secrets from a Vault-compatible HTTP API, keyed by `<path>#<field>`.
+///
+/// For example, `secret/data/indexer#password` reads the `password` field of the
+/// secret at `secret/data/indexer`. Both KV version 1 and version 2 secrets are
+/// supported. The token is sent in the clear to `http` addresses, so those should
+/// only be used for a local Vault agent.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct VaultSecretsProvider {
+    /// Address of the Vault server.
+    address: Url,
+
+    /// Token used to authenticate with the Vault server.
+    token: String,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl VaultSecretsProvider {
+    /// Create a new `VaultSecretsProvider`.
+    pub fn new(address: Url, token: String) -> Self {
+        Self { address, token }
+    }
+
+    /// Create a new `VaultSecretsProvider` from the `VAULT_ADDR` and `VAULT_TOKEN`
+    /// environment variables.
+    pub fn from_env() -> IndexerConfigResult<Self> {
+        let address = Url::parse(&EnvSecretsProvider.get_secret(VAULT_ADDR)?)?;
+        let token = EnvSecretsProvider.get_secret(VAULT_TOKEN)?;
+        Ok(Self::new(address, token))
+    }
+
+    fn get(&self, path: &str) -> IndexerConfigResult<serde_json::Value> {
+        if !matches!(self.address.scheme(), "http" | "https") {
+            return Err(IndexerConfigError::SecretError(format!(
+                "Unsupported Vault address scheme '{}'. Expected 'http' or 'https'.",
+                self.address.scheme()
+            )));
+        }
+
+        let url = format!("{}/v1/{path}", self.address.as_str().trim_end_matches('/'));
+        let token = self.token.clone();
+
+        // The blocking client can't be used from within an async runtime, which the
+        // config may be loaded from, so the request is made from its own thread.
+        let body = thread::spawn(move || {
+            let client = reqwest::blocking::Client::builder()
+                .connect_timeout(Duration::from_secs(VAULT_CONNECT_TIMEOUT_SECS))
+                .timeout(Duration::from_secs(VAULT_REQUEST_TIMEOUT_SECS))
+                .build()?;
+            client
+                .get(url)
+                .header(VAULT_TOKEN_HEADER, token)
+                .send()?
+                .error_for_status()?
+                .text()
+        })
+        .join()
+        .map_err(|_| {
+            IndexerConfigError::SecretError(format!(
+                "Vault request for '{path}' panicked."
+            ))
+        })?
+        .map_err(|e| {
+            IndexerConfigError::SecretError(format!(
+                "Vault request for '{path}' failed: {e}"
+            ))
+        })?;
+
+        serde_json::from_str(&body).map_err(|e| {
+            IndexerConfigError::SecretError(format!(
+                "Could not parse Vault response for '{path}': {e}"
+            ))
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl SecretsProvider for VaultSecretsProvider {
+    fn get_secret(&self, key: &str) -> IndexerConfigResult<String> {
+        let (path, field) = key.split_once('#').ok_or_else(|| {
+            IndexerConfigError::SecretError(format!(
+                "Vault secret '{key}' must be of the form '<path>#<field>'."
+            ))
+        })?;
+
+        let response = self.get(path.trim_start_matches('/'))?;
+
+        // KV version 2 secrets are nested one level deeper than version 1 secrets.
+        let data = &response["data"];
+        data["data"]
+            .get(field)
+            .or_else(|| data.get(field))
+            .and_then(|value| value.as_str())
+            .map(|value| value.to_string())
+            .ok_or_else(|| {
+                IndexerConfigError::SecretError(format!(
+                    "Vault secret '{path}' has no field '{field}'."
+                ))
+            })
+    }
+}
+
+/// Resolve a secret reference of the form `<provider>:<key>`.
+///
+/// Supported providers are `env` (e.g., `env:POSTGRES_PASSWORD`), `file`
+/// (e.g., `file:/run/secrets/postgres_password`), and `vault`
+/// (e.g., `vault:secret/data/indexer#postgres_password`).
+pub fn resolve_secret(reference: &str) -> IndexerConfigResult<String> {
+    match reference.split_once(':') {
+        Some(("env", key)) => EnvSecretsProvider.get_secret(key),
+        Some(("file", key)) => FileSecretsProvider.get_secret(key),
+        #[cfg(not(target_arch = "wasm32"))]
+        Some(("vault", key)) => VaultSecretsProvider::from_env()?.get_secret(key),
+        _ => Err(IndexerConfigError::SecretError(format!(
+            "Unsupported secret reference '{reference}'. Expected 'env:<name>', 'file:<path>', or 'vault:<path>#<field>'."
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_secret_from_file_trims_trailing_newline() {
+        let file_path = "secret1.txt";
+        fs::write(file_path, "super_secret_password\n").unwrap();
+
+        let secret = resolve_secret(&format!("file:{file_path}")).unwrap();
+        fs::remove_file(file_path).unwrap();
+
+        assert_eq!(secret, "super_secret_password");
+    }
+
+    #[test]
+    fn test_resolve_secret_rejects_unknown_provider() {
+        assert!(resolve_secret("aws:db/password").is_err());
+    }
+
+    #[test]
+    fn test_vault_secrets_are_read_over_http() {
+        use std::{
+            io::{Read, Write},
+            net::TcpListener,
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address =
+            Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let len = stream.read(&mut request).unwrap();
+            let request = String::from_utf8_lossy(&request[..len]).to_lowercase();
+            assert!(request.starts_with("get /v1/secret/data/indexer "));
+            assert!(request.contains("x-vault-token: token"));
+
+            let body = r#"{"data":{"data":{"password":"hunter2"}}}"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+
+        let vault = VaultSecretsProvider::new(address, "token".to_string());
+        assert_eq!(
+            vault.get_secret("secret/data/indexer#password").unwrap(),
+            "hunter2"
+        );
+        server.join().unwrap();
+
+        let vault = VaultSecretsProvider::new(
+            Url::parse("ftp://vault.example.com").unwrap(),
+            "token".to_string(),
+        );
+        assert!(vault.get_secret("secret/data/indexer#password").is_err());
+    }
+}
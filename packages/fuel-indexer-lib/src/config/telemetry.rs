@@ -0,0 +1,35 @@
+use crate::{
+    config::{Env, IndexerConfigResult},
+    defaults,
+};
+use serde::Deserialize;
+
+/// Indexer service OpenTelemetry tracing configuration.
+#[derive(Clone, Deserialize, Debug)]
+pub struct TelemetryConfig {
+    /// Export traces to an OpenTelemetry collector.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// OTLP gRPC endpoint of the collector to which traces are exported.
+    pub otlp_endpoint: String,
+
+    /// Ratio of traces to sample, between 0 and 1.
+    pub sampling_ratio: f64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: defaults::TELEMETRY_ENABLED,
+            otlp_endpoint: defaults::OTLP_ENDPOINT.to_string(),
+            sampling_ratio: defaults::TRACE_SAMPLING_RATIO,
+        }
+    }
+}
+
+impl Env for TelemetryConfig {
+    fn inject_opt_env_vars(&mut self) -> IndexerConfigResult<()> {
+        Ok(())
+    }
+}
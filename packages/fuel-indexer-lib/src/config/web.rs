@@ -1,12 +1,15 @@
 use crate::{
-    config::{utils::derive_http_url, Env, IndexerConfigResult},
+    config::{
+        utils::derive_http_url, Env, IndexerConfigError, IndexerConfigResult,
+        PageInfoCount,
+    },
     defaults,
     utils::derive_socket_addr,
 };
 pub use clap::Parser;
-use http::Uri;
+use http::{Method, Uri};
 use serde::Deserialize;
-use std::net::SocketAddr;
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf};
 
 #[derive(Clone, Deserialize, Debug)]
 pub struct WebApiConfig {
@@ -21,6 +24,75 @@ pub struct WebApiConfig {
     /// Max body size for web API requests.
     #[serde(default)]
     pub max_body_size: usize,
+
+    /// Origins allowed to make cross-origin requests. Any origin is allowed if empty.
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+
+    /// Methods allowed in cross-origin requests. Defaults to `GET` and `POST` if empty.
+    #[serde(default)]
+    pub cors_allowed_methods: Vec<String>,
+
+    /// Number of seconds for which browsers may cache CORS preflight responses.
+    #[serde(default)]
+    pub cors_max_age: Option<u64>,
+
+    /// Origins allowed to make cross-origin requests to the routes of specific
+    /// indexers, keyed by `namespace.identifier`. Overrides `cors_allowed_origins`.
+    #[serde(default)]
+    pub cors_indexer_origins: HashMap<String, Vec<String>>,
+
+    /// Add standard security headers, such as `X-Content-Type-Options`, to responses.
+    #[serde(default)]
+    pub security_headers: bool,
+
+    /// Path to a PEM-encoded TLS certificate chain. The web API is served over HTTPS if set.
+    #[serde(default)]
+    pub tls_cert: Option<PathBuf>,
+
+    /// Path to the PEM-encoded private key of the TLS certificate.
+    #[serde(default)]
+    pub tls_key: Option<PathBuf>,
+
+    /// Interval (seconds) at which the TLS certificate and key are checked for changes
+    /// and reloaded. If not set, they are only read on startup.
+    #[serde(default)]
+    pub tls_reload_interval: Option<u64>,
+
+    /// Number of GraphQL query shapes whose SQL is kept as a prepared statement, so
+    /// that queries differing only in their values aren't planned again. Statements
+    /// aren't prepared if 0.
+    #[serde(default)]
+    pub statement_cache_size: usize,
+
+    /// How the entities matching paginated queries are counted in their `page_info`.
+    #[serde(default)]
+    pub page_info_count: PageInfoCount,
+}
+
+impl WebApiConfig {
+    /// Methods allowed in cross-origin requests, or GET and POST if none are set.
+    pub fn cors_methods(&self) -> IndexerConfigResult<Vec<Method>> {
+        if self.cors_allowed_methods.is_empty() {
+            return Ok(vec![Method::GET, Method::POST]);
+        }
+
+        self.cors_allowed_methods
+            .iter()
+            .map(|method| {
+                Method::from_bytes(method.to_uppercase().as_bytes())
+                    .map_err(|_| IndexerConfigError::InvalidCorsMethod(method.clone()))
+            })
+            .collect()
+    }
+
+    /// Origins allowed to make cross-origin requests to the routes of the given
+    /// indexer. Any origin is allowed if empty.
+    pub fn cors_origins_for(&self, namespace: &str, identifier: &str) -> &[String] {
+        self.cors_indexer_origins
+            .get(&format!("{namespace}.{identifier}"))
+            .unwrap_or(&self.cors_allowed_origins)
+    }
 }
 
 impl std::string::ToString for WebApiConfig {
@@ -44,6 +116,16 @@ impl Default for WebApiConfig {
             host: defaults::WEB_API_HOST.into(),
             port: defaults::WEB_API_PORT.into(),
             max_body_size: defaults::MAX_BODY_SIZE,
+            cors_allowed_origins: Vec::new(),
+            cors_allowed_methods: Vec::new(),
+            cors_max_age: None,
+            cors_indexer_origins: HashMap::new(),
+            security_headers: defaults::SECURITY_HEADERS,
+            tls_cert: None,
+            tls_key: None,
+            tls_reload_interval: None,
+            statement_cache_size: defaults::STATEMENT_CACHE_SIZE,
+            page_info_count: PageInfoCount::default(),
         }
     }
 }
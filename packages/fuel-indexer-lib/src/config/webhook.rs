@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use strum::{AsRefStr, EnumString};
+
+/// Events about an indexer that can be sent to webhooks.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, Hash, EnumString, AsRefStr, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    /// The indexer's executor started.
+    #[strum(serialize = "started")]
+    Started,
+
+    /// The indexer reached the chain tip.
+    #[strum(serialize = "caught_up")]
+    CaughtUp,
+
+    /// The indexer raised an error while fetching or processing blocks.
+    #[strum(serialize = "error")]
+    Error,
+
+    /// The indexer's executor stopped.
+    #[strum(serialize = "stopped")]
+    Stopped,
+}
+
+/// A webhook that is notified of indexer events.
+#[derive(Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WebhookConfig {
+    /// URL to which events are posted.
+    pub url: String,
+
+    /// Secret used to sign the body of each notification with HMAC-SHA256.
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Events sent to the webhook. Every event is sent if none are given.
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+}
+
+impl WebhookConfig {
+    /// Whether the given event should be sent to this webhook.
+    pub fn accepts(&self, event: WebhookEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+impl Debug for WebhookConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookConfig")
+            .field("url", &self.url)
+            .field("secret", &self.secret.as_ref().map(|_| "XXXX"))
+            .field("events", &self.events)
+            .finish()
+    }
+}
@@ -28,8 +28,13 @@ pub const POSTGRES_PORT: &str = "5432";
 /// Postgres password.
 pub const POSTGRES_PASSWORD: &str = "postgres";
 
-/// Number of failed calls to allow before stopping the indexer.
-pub const INDEXER_FAILED_CALLS: usize = 10;
+/// Number of times in a row an indexer can fail to process the same page of blocks
+/// before it's reported as crash looping.
+pub const CRASH_LOOP_FAILURES: u32 = 3;
+
+/// Maximum amount of time to wait before retrying to process a page of blocks, after
+/// repeated failures.
+pub const MAX_RESTART_DELAY_SECS: u64 = 300;
 
 /// Stop indexers that have seen `MAX_EMPTY_BLOCK_REQUESTS`.
 pub const STOP_IDLE_INDEXERS: bool = false;
@@ -37,12 +42,25 @@ pub const STOP_IDLE_INDEXERS: bool = false;
 /// Max body size for GraphQL API requests (5 MB).
 pub const MAX_BODY_SIZE: usize = 5242880;
 
+/// Number of GraphQL query shapes whose SQL is kept as a prepared statement.
+pub const STATEMENT_CACHE_SIZE: usize = 256;
+
+/// Add standard security headers to web API responses.
+pub const SECURITY_HEADERS: bool = true;
+
 /// Size of web-API-to-executor-service channel.
 pub const SERVICE_REQUEST_CHANNEL_SIZE: usize = 100;
 
 /// How long to wait if request to the Fuel GQL client returned no data.
 pub const IDLE_SERVICE_WAIT_SECS: u64 = 1;
 
+/// Number of blocks an indexer can be behind the chain tip before the health check
+/// reports it as lagging.
+pub const MAX_HEALTHY_BLOCKS_BEHIND: u64 = 100;
+
+/// Interval at which an indexer running the synthetic load benchmark logs its throughput.
+pub const BENCH_REPORT_INTERVAL_SECS: u64 = 10;
+
 /// How many times to retry connecting to the database.
 pub const MAX_DATABASE_CONNECTION_ATTEMPTS: usize = 5;
 
@@ -58,6 +76,25 @@ pub const MAX_EMPTY_BLOCK_REQUESTS: usize = 10;
 /// Amount of time to wait before fetching new blocks, if an executor error occurs.
 pub const DELAY_FOR_SERVICE_ERROR: u64 = 1;
 
+/// Maximum amount of time to wait before retrying to fetch blocks, after repeated failures.
+pub const MAX_FETCH_RETRY_DELAY_SECS: u64 = 60;
+
+/// Number of blocks that the Fuel node being indexed can fall behind the other configured
+/// Fuel nodes, before failing over to another node.
+pub const MAX_FUEL_NODE_LAG: u64 = 10;
+
+/// Amount of time between checks of the heights of the configured Fuel nodes.
+pub const FUEL_NODE_HEALTH_CHECK_INTERVAL_SECS: u64 = 30;
+
+/// Maximum number of HTTP requests an indexer may make per second.
+pub const HTTP_MAX_REQUESTS_PER_SECOND: usize = 10;
+
+/// Maximum size of the body of an HTTP response returned to an indexer.
+pub const HTTP_MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+/// Amount of time to wait for an HTTP request made by an indexer.
+pub const HTTP_REQUEST_TIMEOUT_SECS: u64 = 10;
+
 /// Amount of time to wait before fetching new blocks, if the Fuel GQL client returns no data.
 pub const DELAY_FOR_EMPTY_PAGE: u64 = 1;
 
@@ -73,6 +110,12 @@ pub const FUEL_HOME_DIR: &str = ".fuel";
 /// Directory inside `FUEL_HOME_DIR` at which indexer assets and metadata are stored.
 pub const INDEXER_CONFIG_DIR: &str = "indexer";
 
+/// Directory inside `INDEXER_CONFIG_DIR` at which ABIs fetched from URLs or registries are cached.
+pub const ABI_CACHE_DIR: &str = "abis";
+
+/// Registry from which ABI package references (e.g., `pkg:my-contract@0.1.0`) are fetched.
+pub const ABI_REGISTRY_URL: &str = "https://forc.pub";
+
 /// Binary name for `forc index` CLI.
 pub const FORC_INDEX: &str = "forc-index";
 
@@ -82,6 +125,9 @@ pub const AUTH_ENABLED: bool = false;
 /// Amount of time (seconds) before expiring token (if JWT scheme is specified).
 pub const JWT_EXPIRY_SECS: usize = 2592000;
 
+/// Require deployments to be signed by a deployment signer.
+pub const REQUIRE_SIGNED_DEPLOYMENTS: bool = false;
+
 /// Index of account to use for signing.
 pub const ACCOUNT_INDEX: &str = "0";
 
@@ -94,6 +140,36 @@ pub const VERBOSE_DB_LOGGING: &str = "false";
 /// Amount of blocks to return in a request to a Fuel node.
 pub const NODE_BLOCK_PAGE_SIZE: usize = 20;
 
+/// Number of block pages to prefetch from the Fuel node while the executor is busy.
+pub const BLOCK_PREFETCH_SIZE: usize = 4;
+
+/// Number of block pages to process between executor checkpoints.
+pub const CHECKPOINT_INTERVAL: u64 = 10;
+
+/// Maximum number of compiled WASM modules to keep in the module cache.
+pub const MODULE_CACHE_SIZE: usize = 16;
+
+/// Chain ID of the Fuel network being indexed.
+pub const CHAIN_ID: u64 = 0;
+
+/// Height of the first block of the Fuel network being indexed.
+pub const GENESIS_HEIGHT: u64 = 0;
+
+/// Host of the Fuel beta-3 testnet node.
+pub const BETA_3_FUEL_NODE_HOST: &str = "beta-3.fuel.network";
+
+/// Host of the Fuel beta-4 testnet node.
+pub const BETA_4_FUEL_NODE_HOST: &str = "beta-4.fuel.network";
+
+/// Listening port of the Fuel testnet nodes.
+pub const TESTNET_FUEL_NODE_PORT: &str = "443";
+
+/// Chain ID of the Fuel testnets.
+pub const TESTNET_CHAIN_ID: u64 = 0;
+
+/// Height of the first block of the Fuel testnets.
+pub const TESTNET_GENESIS_HEIGHT: u64 = 0;
+
 /// Start a local Fuel node.
 pub const LOCAL_FUEL_NODE: bool = false;
 
@@ -112,9 +188,16 @@ pub const RATE_LIMIT_WINDOW_SIZE: u64 = 5;
 /// Log level.
 pub const LOG_LEVEL: &str = "info";
 
+/// Format of the service's log output.
+pub const LOG_FORMAT: &str = "text";
+
 /// The number of WASM opcodes after which the indexer's `handle_events` function will stop execution.
 pub const METERING_POINTS: u64 = 30_000_000_000;
 
+/// The number of WASM opcodes that an indexer's handlers may run per millisecond of its
+/// manifest's `max_block_time_ms`.
+pub const METERING_POINTS_PER_MS: u64 = 1_000_000;
+
 /// Whether to allow replacing an indexer.
 ///
 /// If this is disabled, then an HTTP 409 Conflict will be returned if an indexer with the same name already exists.
@@ -122,3 +205,12 @@ pub const REPLACE_INDEXER: bool = false;
 
 /// Allow the web API to accept raw SQL queries.
 pub const ACCEPT_SQL: bool = false;
+
+/// Export traces to an OpenTelemetry collector.
+pub const TELEMETRY_ENABLED: bool = false;
+
+/// OTLP gRPC endpoint of the OpenTelemetry collector.
+pub const OTLP_ENDPOINT: &str = "http://localhost:4317";
+
+/// Ratio of traces sampled when exporting to OpenTelemetry.
+pub const TRACE_SAMPLING_RATIO: f64 = 1.0;
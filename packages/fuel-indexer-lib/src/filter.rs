@@ -0,0 +1,349 @@
+//! Filter expressions over the fields of an entity.
+//!
+//! A filter is a list of comparisons of an entity's fields with literals, joined
+//! by `and` and `or`, where `and` binds tighter than `or`, e.g.,
+//! `amount > 1000000 and asset = 'f8f8...' or liquidator = null`.
+//!
+//! Comparisons are made against the JSON representation of an entity, in which
+//! integers too large for a JSON number are decimal strings, so numeric literals
+//! are also compared numerically with fields holding numeric strings.
+
+use serde_json::{Map, Value};
+use std::{cmp::Ordering, fmt};
+use thiserror::Error;
+
+/// Result type returned by parsing filter expressions.
+pub type FilterResult<T> = Result<T, FilterError>;
+
+/// Error type returned by parsing filter expressions.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("Empty filter expression")]
+    Empty,
+    #[error("Unterminated string literal")]
+    UnterminatedString,
+    #[error("Expected {0}, found '{1}'")]
+    Unexpected(&'static str, String),
+    #[error("Expected {0}, found end of expression")]
+    UnexpectedEnd(&'static str),
+}
+
+/// Comparison operators of a filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl FilterOp {
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "=" | "==" => Some(Self::Eq),
+            "!=" | "<>" => Some(Self::Ne),
+            ">" => Some(Self::Gt),
+            ">=" => Some(Self::Gte),
+            "<" => Some(Self::Lt),
+            "<=" => Some(Self::Lte),
+            _ => None,
+        }
+    }
+
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            Self::Eq => ordering == Ordering::Equal,
+            Self::Ne => ordering != Ordering::Equal,
+            Self::Gt => ordering == Ordering::Greater,
+            Self::Gte => ordering != Ordering::Less,
+            Self::Lt => ordering == Ordering::Less,
+            Self::Lte => ordering != Ordering::Greater,
+        }
+    }
+}
+
+/// A literal that fields are compared with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Literal {
+    Null,
+    Bool(bool),
+    String(String),
+
+    /// A number, kept as written so that large integers are compared exactly.
+    Number(String),
+}
+
+impl Literal {
+    /// Parse an unquoted literal.
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "true" => Some(Self::Bool(true)),
+            "false" => Some(Self::Bool(false)),
+            "null" => Some(Self::Null),
+            _ if word.parse::<i128>().is_ok() || word.parse::<f64>().is_ok() => {
+                Some(Self::Number(word.to_string()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A comparison of an entity's field with a literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparison {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: Literal,
+}
+
+impl Comparison {
+    /// Whether the given entity satisfies this comparison.
+    ///
+    /// Fields that are missing are `null`. Values of different types are only
+    /// ever unequal, and `null` is only equal to `null`.
+    fn matches(&self, entity: &Map<String, Value>) -> bool {
+        let field = entity.get(&self.field).unwrap_or(&Value::Null);
+
+        let ordering = match (field, &self.value) {
+            (Value::Null, Literal::Null) => Some(Ordering::Equal),
+            (Value::Bool(a), Literal::Bool(b)) => Some(a.cmp(b)),
+            (Value::String(a), Literal::String(b)) => Some(a.cmp(b)),
+            (a, Literal::Number(b)) => compare_numbers(a, b),
+            _ => None,
+        };
+
+        match ordering {
+            Some(ordering) => self.op.accepts(ordering),
+            None => self.op == FilterOp::Ne,
+        }
+    }
+}
+
+/// Compare a field with a numeric literal, as integers if both are integers.
+fn compare_numbers(field: &Value, literal: &str) -> Option<Ordering> {
+    let field = match field {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => return None,
+    };
+
+    match (field.parse::<i128>(), literal.parse::<i128>()) {
+        (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+        _ => {
+            let a = field.parse::<f64>().ok()?;
+            let b = literal.parse::<f64>().ok()?;
+            a.partial_cmp(&b)
+        }
+    }
+}
+
+/// A parsed filter expression, as the disjunction of conjunctions of comparisons.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    any: Vec<Vec<Comparison>>,
+}
+
+impl Filter {
+    /// Parse a filter expression.
+    pub fn parse(expression: &str) -> FilterResult<Self> {
+        let tokens = tokenize(expression)?;
+        if tokens.is_empty() {
+            return Err(FilterError::Empty);
+        }
+
+        let mut tokens = tokens.into_iter();
+        let mut any = vec![Vec::new()];
+        loop {
+            let field = match tokens.next() {
+                Some(Token::Word(word)) => word,
+                Some(token) => {
+                    return Err(FilterError::Unexpected(
+                        "a field name",
+                        token.to_string(),
+                    ))
+                }
+                None => return Err(FilterError::UnexpectedEnd("a field name")),
+            };
+
+            let op = match tokens.next() {
+                Some(Token::Op(op)) => FilterOp::from_token(&op)
+                    .ok_or(FilterError::Unexpected("an operator", op))?,
+                Some(token) => {
+                    return Err(FilterError::Unexpected("an operator", token.to_string()))
+                }
+                None => return Err(FilterError::UnexpectedEnd("an operator")),
+            };
+
+            let value = match tokens.next() {
+                Some(Token::Str(s)) => Literal::String(s),
+                Some(Token::Word(word)) => Literal::parse(&word)
+                    .ok_or(FilterError::Unexpected("a literal", word))?,
+                Some(token) => {
+                    return Err(FilterError::Unexpected("a literal", token.to_string()))
+                }
+                None => return Err(FilterError::UnexpectedEnd("a literal")),
+            };
+
+            any.last_mut()
+                .expect("There is always a conjunction.")
+                .push(Comparison { field, op, value });
+
+            match tokens.next() {
+                None => break,
+                Some(Token::Word(word)) if word.eq_ignore_ascii_case("and") => {}
+                Some(Token::Word(word)) if word.eq_ignore_ascii_case("or") => {
+                    any.push(Vec::new())
+                }
+                Some(token) => {
+                    return Err(FilterError::Unexpected(
+                        "'and' or 'or'",
+                        token.to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(Self { any })
+    }
+
+    /// Whether the given entity, as a JSON object of its fields, matches this filter.
+    pub fn matches(&self, entity: &Map<String, Value>) -> bool {
+        self.any
+            .iter()
+            .any(|all| all.iter().all(|comparison| comparison.matches(entity)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Op(String),
+    Str(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(s) | Token::Op(s) => write!(f, "{s}"),
+            Token::Str(s) => write!(f, "'{s}'"),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> FilterResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '\'' || c == '"' {
+            chars.next();
+            let mut s = String::new();
+            loop {
+                match chars.next() {
+                    Some(next) if next == c => break,
+                    Some(next) => s.push(next),
+                    None => return Err(FilterError::UnterminatedString),
+                }
+            }
+            tokens.push(Token::Str(s));
+        } else if "=!<>".contains(c) {
+            let mut op = String::new();
+            while let Some(&next) = chars.peek() {
+                if !"=!<>".contains(next) {
+                    break;
+                }
+                op.push(next);
+                chars.next();
+            }
+            tokens.push(Token::Op(op));
+        } else {
+            let mut word = String::new();
+            while let Some(&next) = chars.peek() {
+                if next.is_whitespace() || "=!<>'\"".contains(next) {
+                    break;
+                }
+                word.push(next);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entity(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_filter_compares_numbers_and_numeric_strings() {
+        let filter = Filter::parse("amount > 1000000").unwrap();
+        assert!(filter.matches(&entity(json!({ "amount": 1000001 }))));
+        assert!(!filter.matches(&entity(json!({ "amount": 1000000 }))));
+
+        // 128-bit integers are encoded as decimal strings.
+        let filter =
+            Filter::parse("amount >= 340282366920938463463374607431768211455").unwrap();
+        assert!(filter.matches(&entity(json!({
+            "amount": u128::MAX.to_string()
+        }))));
+        assert!(!filter.matches(&entity(json!({ "amount": "5" }))));
+    }
+
+    #[test]
+    fn test_filter_and_binds_tighter_than_or() {
+        let filter =
+            Filter::parse("kind = 'full' and amount > 10 or liquidator = null").unwrap();
+        assert!(filter.matches(&entity(json!({ "kind": "full", "amount": 11 }))));
+        assert!(!filter.matches(&entity(json!({
+            "kind": "partial",
+            "amount": 11,
+            "liquidator": "abc"
+        }))));
+        assert!(filter.matches(&entity(json!({ "kind": "partial", "amount": 1 }))));
+    }
+
+    #[test]
+    fn test_filter_values_of_different_types_are_unequal() {
+        let filter = Filter::parse("flag != true").unwrap();
+        assert!(filter.matches(&entity(json!({ "flag": "true" }))));
+        assert!(!filter.matches(&entity(json!({ "flag": true }))));
+
+        let filter = Filter::parse("amount > 1").unwrap();
+        assert!(!filter.matches(&entity(json!({ "amount": null }))));
+    }
+
+    #[test]
+    fn test_filter_parse_errors() {
+        assert_eq!(Filter::parse("  "), Err(FilterError::Empty));
+        assert_eq!(
+            Filter::parse("amount >"),
+            Err(FilterError::UnexpectedEnd("a literal"))
+        );
+        assert_eq!(
+            Filter::parse("amount ~ 1"),
+            Err(FilterError::Unexpected("an operator", "~".to_string()))
+        );
+        assert_eq!(
+            Filter::parse("name = 'abc"),
+            Err(FilterError::UnterminatedString)
+        );
+        assert_eq!(
+            Filter::parse("amount > 1 amount < 5"),
+            Err(FilterError::Unexpected(
+                "'and' or 'or'",
+                "amount".to_string()
+            ))
+        );
+    }
+}
@@ -5,6 +5,7 @@ lazy_static! {
 
     /// Set of internal indexer entities.
     pub static ref INTERNAL_INDEXER_ENTITIES: HashSet<&'static str> = HashSet::from([
+        "BlockMetadataEntity",
         "IndexMetadataEntity",
     ]);
 
@@ -74,10 +75,13 @@ lazy_static! {
         "Call",
         "Log",
         "LogData",
+        "MessageIn",
         "MessageOut",
         "Panic",
+        "PredicateSpend",
         "Return",
         "Revert",
+        "ScriptCall",
         "ScriptResult",
         "Transfer",
         "TransferOut",
@@ -150,6 +154,7 @@ lazy_static! {
         "Color",
         "ContractId",
         "HexString",
+        "I128",
         "ID",
         "Identity",
         "Int1",
@@ -165,6 +170,7 @@ lazy_static! {
         "Tai64Timestamp",
         "Timestamp",
         "TxId",
+        "U128",
         "UInt1",
         "UInt16",
         "UInt4",
@@ -183,9 +189,12 @@ lazy_static! {
         "LogData",
         "Maturity",
         "MessageId",
+        "MessageIn",
         "Outputs",
+        "PredicateSpend",
         "ReceiptsRoot",
         "Script",
+        "ScriptCall",
         "ScriptData",
         "ScriptResult",
         "StorageSlots",
@@ -1,9 +1,11 @@
 pub mod constants;
 pub mod parser;
+pub mod rollup;
 pub mod types;
 pub mod validator;
 
-pub use parser::{JoinTableMeta, ParsedError, ParsedGraphQLSchema};
+pub use parser::{JoinTableMeta, ParsedError, ParsedGraphQLSchema, ParsedResult};
+pub use rollup::Rollup;
 pub use validator::GraphQLSchemaValidator;
 
 use async_graphql_parser::types::FieldDefinition;
@@ -23,6 +25,51 @@ pub fn schema_version(schema: &str) -> String {
     format!("{:x}", Sha256::digest(schema.as_bytes()))
 }
 
+/// Derive a hash of a GraphQL query that is the same for queries differing only
+/// in whitespace, commas and comments.
+pub fn query_hash(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    let mut pending_space = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            // Commas are insignificant in GraphQL, like whitespace.
+            c if c.is_whitespace() || c == ',' => pending_space = true,
+            '#' => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+                pending_space = true;
+            }
+            '"' => {
+                pending_space = false;
+                normalized.push(c);
+                while let Some(c) = chars.next() {
+                    normalized.push(c);
+                    match c {
+                        '\\' => normalized.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            c => {
+                // Spaces are only kept between names, numbers and variables.
+                let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+                if pending_space
+                    && normalized.chars().next_back().map_or(false, is_word)
+                    && is_word(c)
+                {
+                    normalized.push(' ');
+                }
+                pending_space = false;
+                normalized.push(c);
+            }
+        }
+    }
+
+    schema_version(&normalized)
+}
+
 /// Native GraphQL `TypeDefinition` used to keep track of chain metadata.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct IndexMetadata {
@@ -54,9 +101,65 @@ type IndexMetadataEntity @entity {
     }
 }
 
+/// Native GraphQL `TypeDefinition` used to expose the metadata of each indexed block.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BlockMetadata {
+    /// Block height, used as the metadata identifier.
+    pub id: u64,
+
+    /// Block height.
+    pub block_height: u64,
+
+    /// Block ID.
+    pub block_id: String,
+
+    /// Time of the block.
+    pub time: u64,
+
+    /// Height of the data availability layer up to which the block's messages are processed.
+    pub da_height: u64,
+
+    /// Public key hash of the block producer, if any.
+    pub producer: Option<String>,
+
+    /// Number of transactions in the block.
+    pub transactions_count: u64,
+
+    /// Number of output messages in the block.
+    pub output_messages_count: u64,
+
+    /// Merkle root of the block's application header.
+    pub application_hash: String,
+}
+
+impl BlockMetadata {
+    /// Return the GraphQL schema fragment for the `BlockMetadata` type.
+    pub fn schema_fragment() -> &'static str {
+        r#"
+
+type BlockMetadataEntity @entity {
+    id: ID!
+    block_height: UInt8!
+    block_id: Bytes32!
+    time: UInt8!
+    da_height: UInt8!
+    producer: Bytes32
+    transactions_count: UInt8!
+    output_messages_count: UInt8!
+    application_hash: Bytes32!
+}
+"#
+    }
+}
+
 /// Inject native entities into the GraphQL schema.
 fn inject_native_entities_into_schema(schema: &str) -> String {
-    format!("{}{}", schema, IndexMetadata::schema_fragment())
+    format!(
+        "{}{}{}",
+        schema,
+        IndexMetadata::schema_fragment(),
+        BlockMetadata::schema_fragment()
+    )
 }
 
 /// Wrapper for GraphQL schema content.
@@ -170,3 +273,32 @@ pub fn field_type_name(f: &FieldDefinition) -> String {
 pub fn list_field_type_name(f: &FieldDefinition) -> String {
     f.ty.to_string().replace(['!'], "")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_hash_ignores_formatting() {
+        let query = r#"query { block(id: 1) { id height } }"#;
+        let formatted = r#"
+            # Blocks by ID.
+            query {
+                block(id: 1) {
+                    id,
+                    height
+                }
+            }
+        "#;
+
+        assert_eq!(query_hash(query), query_hash(formatted));
+        assert_ne!(
+            query_hash(query),
+            query_hash("query { block(id: 2) { id height } }")
+        );
+        assert_ne!(
+            query_hash(r#"query { a(name: "x  y") { id } }"#),
+            query_hash(r#"query { a(name: "x y") { id } }"#)
+        );
+    }
+}
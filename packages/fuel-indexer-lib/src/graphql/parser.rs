@@ -7,7 +7,8 @@ use crate::{
     fully_qualified_namespace,
     graphql::{
         extract_foreign_key_info, field_id, field_type_name, is_list_type,
-        list_field_type_name, GraphQLSchema, GraphQLSchemaValidator, IdCol, BASE_SCHEMA,
+        list_field_type_name, GraphQLSchema, GraphQLSchemaValidator, IdCol, Rollup,
+        BASE_SCHEMA,
     },
     join_table_name, ExecutionSource,
 };
@@ -40,6 +41,8 @@ pub enum ParsedError {
     InconsistentVirtualUnion(String),
     #[error("Union member not found in parsed TypeDefintions. {0:?}")]
     UnionMemberNotFound(String),
+    #[error("Invalid rollup {0:?}: {1}")]
+    InvalidRollup(String, String),
 }
 
 /// Represents metadata related to a many-to-many relationship in the GraphQL schema.
@@ -264,6 +267,9 @@ pub struct ParsedGraphQLSchema {
     /// This allows us to create SQL tables where the columns are ordered - mirroring the order of the fields
     /// on the object `TypeDefinition` derived from a union.
     object_ordered_fields: HashMap<String, Vec<OrderedField>>,
+
+    /// Rollup entities declared in the schema.
+    rollups: Vec<Rollup>,
 }
 
 impl Default for ParsedGraphQLSchema {
@@ -297,6 +303,7 @@ impl Default for ParsedGraphQLSchema {
             unions: HashMap::new(),
             join_table_meta: HashMap::new(),
             object_ordered_fields: HashMap::new(),
+            rollups: Vec::new(),
         }
     }
 }
@@ -596,6 +603,14 @@ impl ParsedGraphQLSchema {
             }
         }
 
+        let mut rollups = Vec::new();
+        for (name, obj) in objects.iter() {
+            if let Some(typ) = type_defs.get(name) {
+                rollups.extend(Rollup::from_typedef(typ, obj, &objects)?);
+            }
+        }
+        rollups.sort_by(|a, b| a.entity.cmp(&b.entity));
+
         let typedef_names_to_types = type_defs
             .iter()
             .filter(|(_, t)| !matches!(&t.kind, TypeKind::Enum(_)))
@@ -631,6 +646,7 @@ impl ParsedGraphQLSchema {
             join_table_meta,
             typedef_names_to_types,
             object_ordered_fields,
+            rollups,
         })
     }
 
@@ -696,6 +712,11 @@ impl ParsedGraphQLSchema {
         &self.object_field_mappings
     }
 
+    /// Rollup entities declared in the schema.
+    pub fn rollups(&self) -> &[Rollup] {
+        &self.rollups
+    }
+
     /// Metadata related to many-to-many relationships in the GraphQL schema.
     pub fn join_table_meta(&self) -> &HashMap<String, Vec<JoinTableMeta>> {
         &self.join_table_meta
@@ -930,4 +951,64 @@ union Storage = Safe | Vault
             JoinTableMeta::new("storage", "id", "user", "id", Some(3))
         );
     }
+
+    #[test]
+    fn test_parser_parses_rollups() {
+        let schema = r#"
+type Transfer @entity {
+    id: ID!
+    pool: ContractId!
+    amount: UInt8!
+    time: UInt8!
+}
+
+type PoolVolume @entity @rollup(source: "Transfer", timestamp: "time", interval: 3600) {
+    id: ID!
+    pool: ContractId!
+    bucket: UInt8!
+    volume: UInt8! @aggregate(fn: "sum", field: "amount")
+    transfers: UInt8! @aggregate(fn: "count")
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let rollup = &parsed.rollups()[0];
+        assert_eq!(rollup.source, "Transfer");
+        assert_eq!(rollup.interval, 3600);
+        assert_eq!(rollup.group_by, vec!["pool".to_string()]);
+        assert_eq!(rollup.aggregates.len(), 2);
+
+        let query = rollup.refresh_query("test_test", &["1".to_string()]);
+        assert!(query.starts_with(
+            "INSERT INTO test_test.poolvolume (id, pool, bucket, volume, transfers, object)"
+        ));
+        assert!(query.contains("SUM(amount), COUNT(*)"));
+        assert!(query.contains("WHERE id IN (1)"));
+        assert!(query.ends_with(
+            "ON CONFLICT (id) DO UPDATE SET volume = EXCLUDED.volume, transfers = EXCLUDED.transfers"
+        ));
+
+        // Group keys must be fields of the source.
+        let invalid = schema.replace(
+            "pool: ContractId!\n    bucket",
+            "asset: AssetId!\n    bucket",
+        );
+        let err = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(invalid)),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, ParsedError::InvalidRollup(entity, _) if entity == "PoolVolume")
+        );
+    }
 }
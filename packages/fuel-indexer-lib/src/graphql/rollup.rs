@@ -0,0 +1,356 @@
+//! # fuel_indexer_lib::graphql::rollup
+//!
+//! Rollup entities, whose rows aggregate the rows of another entity by time bucket.
+//!
+//! ```graphql
+//! type PoolVolume @entity @rollup(source: "Transfer", timestamp: "time", interval: 3600) {
+//!     id: ID!
+//!     pool: ContractId!
+//!     bucket: UInt8!
+//!     volume: UInt8! @aggregate(fn: "sum", field: "amount")
+//!     transfers: UInt8! @aggregate(fn: "count")
+//! }
+//! ```
+//!
+//! The `bucket` field holds the start of each bucket, every field with an `@aggregate`
+//! directive holds an aggregate of the source's rows in that bucket, and every other
+//! field is a key the source's rows are grouped by, copied from its field of the same name.
+
+use crate::graphql::{field_type_name, ParsedError, ParsedResult};
+use async_graphql_parser::types::{ObjectType, TypeDefinition};
+use async_graphql_value::ConstValue;
+use std::{collections::HashMap, str::FromStr};
+
+/// Types of the fields holding timestamps and buckets.
+const INTEGER_TYPES: [&str; 5] = ["Int4", "Int8", "UInt4", "UInt8", "BlockHeight"];
+
+/// Types of the fields a rollup may sum or average.
+const NUMERIC_TYPES: [&str; 10] = [
+    "Int1", "Int4", "Int8", "Int16", "I128", "UInt1", "UInt4", "UInt8", "UInt16", "U128",
+];
+
+/// Aggregate function of a rollup field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFn {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+impl FromStr for AggregateFn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "count" => Ok(Self::Count),
+            "sum" => Ok(Self::Sum),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            "avg" => Ok(Self::Avg),
+            _ => Err(format!(
+                "Unknown aggregate function '{s}'. Expected count, sum, min, max or avg."
+            )),
+        }
+    }
+}
+
+/// A field of a rollup holding an aggregate of its source's rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    /// Name of the rollup's field.
+    pub field: String,
+
+    /// Aggregate function.
+    pub function: AggregateFn,
+
+    /// Name of the source's field that is aggregated, which `count` doesn't need.
+    pub source_field: Option<String>,
+}
+
+impl Aggregate {
+    fn sql(&self) -> String {
+        let column = self
+            .source_field
+            .as_deref()
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+        match self.function {
+            AggregateFn::Count => "COUNT(*)".to_string(),
+            AggregateFn::Sum => format!("SUM({column})"),
+            AggregateFn::Min => format!("MIN({column})"),
+            AggregateFn::Max => format!("MAX({column})"),
+            AggregateFn::Avg => format!("AVG({column})"),
+        }
+    }
+}
+
+/// A rollup entity, declared with the `@rollup` directive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rollup {
+    /// Name of the rollup entity.
+    pub entity: String,
+
+    /// Name of the entity whose rows are aggregated.
+    pub source: String,
+
+    /// Name of the source's field holding the UNIX timestamp rows are bucketed by.
+    pub timestamp: String,
+
+    /// Length of each bucket, in seconds.
+    pub interval: u64,
+
+    /// Name of the rollup's field holding the start of each bucket.
+    pub bucket: String,
+
+    /// Names of the fields rows are grouped by, besides their bucket.
+    pub group_by: Vec<String>,
+
+    /// Fields holding aggregates of the source's rows.
+    pub aggregates: Vec<Aggregate>,
+}
+
+fn string_arg(value: &ConstValue) -> Option<String> {
+    match value {
+        ConstValue::String(s) => Some(s.clone()),
+        ConstValue::Enum(s) => Some(s.to_string()),
+        _ => None,
+    }
+}
+
+impl Rollup {
+    /// Parse the rollup declared by the given entity, if it has a `@rollup` directive,
+    /// checking it against the fields of its source.
+    pub fn from_typedef(
+        typ: &TypeDefinition,
+        obj: &ObjectType,
+        objects: &HashMap<String, ObjectType>,
+    ) -> ParsedResult<Option<Self>> {
+        let directive = match typ
+            .directives
+            .iter()
+            .find(|d| d.node.name.node.as_str() == "rollup")
+        {
+            Some(d) => &d.node,
+            None => return Ok(None),
+        };
+
+        let entity = typ.name.to_string();
+        let invalid = |reason: String| ParsedError::InvalidRollup(entity.clone(), reason);
+        let arg = |name: &str| {
+            directive
+                .get_argument(name)
+                .map(|v| v.node.clone())
+                .ok_or_else(|| {
+                    invalid(format!("`@rollup` requires a `{name}` argument."))
+                })
+        };
+
+        let source = string_arg(&arg("source")?)
+            .ok_or_else(|| invalid("`source` must be an entity name.".to_string()))?;
+        let timestamp = string_arg(&arg("timestamp")?)
+            .ok_or_else(|| invalid("`timestamp` must be a field name.".to_string()))?;
+        let interval = match arg("interval")? {
+            ConstValue::Number(n) => n.as_u64().filter(|n| *n > 0),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            invalid("`interval` must be a positive number of seconds.".to_string())
+        })?;
+        let bucket = match directive.get_argument("bucket") {
+            Some(v) => string_arg(&v.node)
+                .ok_or_else(|| invalid("`bucket` must be a field name.".to_string()))?,
+            None => "bucket".to_string(),
+        };
+
+        let source_obj = objects
+            .get(&source)
+            .ok_or_else(|| invalid(format!("Source entity '{source}' not found.")))?;
+        let source_fields = source_obj
+            .fields
+            .iter()
+            .map(|f| (f.node.name.to_string(), field_type_name(&f.node)))
+            .collect::<HashMap<_, _>>();
+
+        match source_fields.get(&timestamp) {
+            Some(ty) if INTEGER_TYPES.contains(&ty.as_str()) => {}
+            Some(ty) => return Err(invalid(format!(
+                "Timestamp field '{timestamp}' of '{source}' is a {ty}, not an integer."
+            ))),
+            None => {
+                return Err(invalid(format!(
+                    "Timestamp field '{timestamp}' not found in '{source}'."
+                )))
+            }
+        }
+
+        let mut has_bucket = false;
+        let mut group_by = Vec::new();
+        let mut aggregates = Vec::new();
+        for f in obj.fields.iter() {
+            let field = f.node.name.to_string();
+            let ty = field_type_name(&f.node);
+
+            if field == "id" {
+                continue;
+            }
+
+            if field == bucket {
+                if !INTEGER_TYPES.contains(&ty.as_str()) {
+                    return Err(invalid(format!(
+                        "Bucket field '{bucket}' is a {ty}, not an integer."
+                    )));
+                }
+                has_bucket = true;
+                continue;
+            }
+
+            let aggregate = f
+                .node
+                .directives
+                .iter()
+                .find(|d| d.node.name.node.as_str() == "aggregate");
+            match aggregate {
+                Some(d) => {
+                    let function = d
+                        .node
+                        .get_argument("fn")
+                        .and_then(|v| string_arg(&v.node))
+                        .ok_or_else(|| {
+                            invalid(format!("`@aggregate` of '{field}' requires `fn`."))
+                        })?
+                        .parse::<AggregateFn>()
+                        .map_err(invalid)?;
+                    let source_field = d
+                        .node
+                        .get_argument("field")
+                        .and_then(|v| string_arg(&v.node));
+
+                    match (&function, &source_field) {
+                        (AggregateFn::Count, _) => {}
+                        (_, None) => {
+                            return Err(invalid(format!(
+                                "`@aggregate` of '{field}' requires `field`."
+                            )))
+                        }
+                        (function, Some(name)) => match source_fields.get(name) {
+                            None => {
+                                return Err(invalid(format!(
+                                    "Aggregated field '{name}' not found in '{source}'."
+                                )))
+                            }
+                            Some(ty)
+                                if matches!(function, AggregateFn::Sum | AggregateFn::Avg)
+                                    && !NUMERIC_TYPES.contains(&ty.as_str()) =>
+                            {
+                                return Err(invalid(format!(
+                                    "Aggregated field '{name}' of '{source}' is a {ty}, which can't be summed or averaged."
+                                )))
+                            }
+                            Some(_) => {}
+                        },
+                    }
+
+                    aggregates.push(Aggregate {
+                        field,
+                        function,
+                        source_field,
+                    });
+                }
+                None => match source_fields.get(&field) {
+                    Some(source_ty) if *source_ty == ty => group_by.push(field),
+                    Some(source_ty) => {
+                        return Err(invalid(format!(
+                            "Field '{field}' is a {ty}, but is a {source_ty} in '{source}'."
+                        )))
+                    }
+                    None => {
+                        return Err(invalid(format!(
+                            "Field '{field}' is neither an aggregate nor a field of '{source}'."
+                        )))
+                    }
+                },
+            }
+        }
+
+        if !has_bucket {
+            return Err(invalid(format!("Bucket field '{bucket}' not found.")));
+        }
+
+        Ok(Some(Self {
+            entity,
+            source,
+            timestamp,
+            interval,
+            bucket,
+            group_by,
+            aggregates,
+        }))
+    }
+
+    /// Return the query that recomputes the buckets of this rollup holding any of
+    /// the given rows of its source, in the given fully qualified namespace.
+    ///
+    /// Rows of the rollup are keyed by a hash of their group and bucket, so that
+    /// each bucket is recomputed in place.
+    pub fn refresh_query(&self, namespace: &str, source_ids: &[String]) -> String {
+        let bucket = format!(
+            "div({}::numeric, {interval}) * {interval}",
+            self.timestamp.to_lowercase(),
+            interval = self.interval
+        );
+        let group_by = self
+            .group_by
+            .iter()
+            .map(|f| f.to_lowercase())
+            .collect::<Vec<_>>();
+        let keys = group_by
+            .iter()
+            .cloned()
+            .chain(std::iter::once(bucket.clone()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let key_text = group_by
+            .iter()
+            .cloned()
+            .chain(std::iter::once(bucket.clone()))
+            .map(|k| format!("({k})::text"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut columns = vec!["id".to_string()];
+        columns.extend(group_by.iter().cloned());
+        columns.push(self.bucket.to_lowercase());
+        columns.extend(self.aggregates.iter().map(|a| a.field.to_lowercase()));
+        columns.push("object".to_string());
+
+        let mut values = vec![format!(
+            "('x' || left(md5(concat_ws('|', {key_text})), 15))::bit(60)::bigint"
+        )];
+        values.extend(group_by.iter().cloned());
+        values.push(bucket);
+        values.extend(self.aggregates.iter().map(Aggregate::sql));
+        values.push("''::bytea".to_string());
+
+        let updates = self
+            .aggregates
+            .iter()
+            .map(|a| format!("{0} = EXCLUDED.{0}", a.field.to_lowercase()))
+            .collect::<Vec<_>>();
+        let conflict = if updates.is_empty() {
+            "DO NOTHING".to_string()
+        } else {
+            format!("DO UPDATE SET {}", updates.join(", "))
+        };
+
+        let source = format!("{namespace}.{}", self.source.to_lowercase());
+        format!(
+            "INSERT INTO {namespace}.{rollup} ({columns}) SELECT {values} FROM {source} WHERE ({keys}) IN (SELECT DISTINCT {keys} FROM {source} WHERE id IN ({ids})) GROUP BY {keys} ON CONFLICT (id) {conflict}",
+            rollup = self.entity.to_lowercase(),
+            columns = columns.join(", "),
+            values = values.join(", "),
+            ids = source_ids.join(", "),
+        )
+    }
+}
@@ -5,8 +5,12 @@
 #![deny(unused_crate_dependencies)]
 pub mod config;
 pub mod defaults;
+pub mod filter;
 pub mod graphql;
 pub mod manifest;
+pub mod mapping;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod telemetry;
 pub mod utils;
 
 use proc_macro2::TokenStream;
@@ -50,6 +54,17 @@ pub fn fully_qualified_namespace(namespace: &str, identifier: &str) -> String {
     format!("{}_{}", namespace, identifier)
 }
 
+/// Return the identifier under which a version of an indexer is deployed.
+///
+/// Version `v2` of `my_indexer`, given as `my_indexer@v2`, is deployed as the indexer
+/// `my_indexer_v2`. Identifiers without a version are returned unchanged.
+pub fn versioned_identifier(identifier: &str) -> String {
+    match identifier.split_once('@') {
+        Some((identifier, version)) => format!("{identifier}_{version}"),
+        None => identifier.to_string(),
+    }
+}
+
 /// Return the name of the join table for the given entities.
 pub fn join_table_name(a: &str, b: &str) -> String {
     format!("{}s_{}s", a, b)
@@ -1,7 +1,12 @@
-use crate::{graphql::GraphQLSchema, ExecutionSource};
+use crate::{
+    defaults, filter::Filter, graphql::GraphQLSchema, utils::sha256_digest,
+    ExecutionSource,
+};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Formatter},
     fs::File,
     io::{Read, Write},
     path::{Path, PathBuf},
@@ -17,10 +22,16 @@ type ManifestResult<T> = Result<T, ManifestError>;
 pub enum ManifestError {
     #[error("Compiler error: {0:#?}")]
     YamlError(#[from] serde_yaml::Error),
+    #[error("TOML parsing error: {0:#?}")]
+    TomlError(#[from] toml::de::Error),
+    #[error("TOML serialization error: {0:#?}")]
+    TomlSerializeError(#[from] toml::ser::Error),
     #[error("Native module bytes not supported.")]
     NativeModuleError,
     #[error("File IO error: {0} {1:?}.")]
     FileError(String, #[source] std::io::Error),
+    #[error("Invalid manifest: {}", .0.join("; "))]
+    ValidationError(Vec<String>),
 }
 
 /// Specifies which type of module is used to create this indexer.
@@ -35,6 +46,9 @@ pub enum ManifestError {
 pub enum Module {
     Wasm(String),
     Native,
+
+    /// A compiled `MappingModule` of a declarative indexer, which runs no user code.
+    Declarative(String),
 }
 
 impl From<PathBuf> for Module {
@@ -46,7 +60,7 @@ impl From<PathBuf> for Module {
 impl ToString for Module {
     fn to_string(&self) -> String {
         match self {
-            Self::Wasm(o) => o.to_string(),
+            Self::Wasm(o) | Self::Declarative(o) => o.to_string(),
             Self::Native => "native".to_string(),
         }
     }
@@ -55,7 +69,7 @@ impl ToString for Module {
 impl AsRef<Path> for Module {
     fn as_ref(&self) -> &Path {
         match self {
-            Self::Wasm(o) => Path::new(o),
+            Self::Wasm(o) | Self::Declarative(o) => Path::new(o),
             Self::Native => {
                 unimplemented!("Only WASM execution supports module path access.")
             }
@@ -63,6 +77,397 @@ impl AsRef<Path> for Module {
     }
 }
 
+/// Host capabilities granted to an indexer.
+///
+/// Each capability controls whether the corresponding host functions are
+/// exposed to the indexer's WASM module. A module that imports `ff_http_get`
+/// without `allow_http` will fail to instantiate. Every module imports the logging
+/// and many-to-many host functions, so without their capabilities, log messages are
+/// discarded, and saving many-to-many records stops the indexer.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Capabilities {
+    /// Whether the indexer may make outbound HTTP requests.
+    #[serde(default)]
+    pub allow_http: bool,
+
+    /// Whether the indexer may execute raw SQL statements (e.g., many-to-many records).
+    #[serde(default = "enabled")]
+    pub allow_raw_sql: bool,
+
+    /// Whether the indexer may emit log messages.
+    #[serde(default = "enabled")]
+    pub allow_logging: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            allow_http: false,
+            allow_raw_sql: true,
+            allow_logging: true,
+        }
+    }
+}
+
+/// A template for contracts that are discovered at runtime.
+///
+/// Handlers that observe the creation of a new contract (e.g., a factory's
+/// `InstanceCreated` event) can register the contract against a template, after
+/// which the receipts of that contract are decoded using the template's ABI.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct ContractTemplate {
+    /// Name of the template.
+    pub name: String,
+
+    /// Filepath to the Sway contract ABI of contracts created from this template.
+    pub abi: String,
+}
+
+/// A declarative mapping from a type logged by the indexed contract to a GraphQL entity.
+///
+/// Each `LogData` receipt of the logged type is saved as an entity, whose fields are
+/// read from the event's fields (e.g., `amount` or `to.value`) or from the block and
+/// transaction containing it (`$block.height`, `$block.id`, `$block.time`, `$tx.id`,
+/// and `$contract_id`).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct EntityMapping {
+    /// Name of the logged type in the contract ABI.
+    pub event: String,
+
+    /// Name of the GraphQL entity to save.
+    pub entity: String,
+
+    /// Entity fields, and where their values are read from.
+    ///
+    /// If `id` is not mapped, an ID unique to the event's receipt is used.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Resource limits for an indexer.
+///
+/// Limits are applied per block. When a batch of blocks exceeds its limits, the
+/// batch is reverted and the indexer is stopped.
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct ResourceLimits {
+    /// Maximum size, in megabytes, of the indexer's WASM memory.
+    #[serde(default)]
+    pub max_memory_mb: Option<u64>,
+
+    /// Maximum time, in milliseconds, that handlers may spend on a block.
+    #[serde(default)]
+    pub max_block_time_ms: Option<u64>,
+
+    /// Maximum number of database rows that handlers may write for a block.
+    #[serde(default)]
+    pub max_db_rows_per_block: Option<u64>,
+}
+
+/// Format in which entity changes are published to a sink.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SinkFormat {
+    /// A JSON object of the entity's fields.
+    #[default]
+    Json,
+
+    /// An Avro record of the entity's fields, with the record's schema in the
+    /// `avro.schema` header of each message.
+    Avro,
+}
+
+/// A Kafka cluster to which entity changes are published.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct KafkaSinkConfig {
+    /// Comma-separated list of Kafka brokers (e.g., `localhost:9092`).
+    pub brokers: String,
+
+    /// Prefix of the topic names, to which the entity name is appended.
+    ///
+    /// Defaults to `<namespace>.<identifier>.`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic_prefix: Option<String>,
+
+    /// Format of the published messages.
+    #[serde(default)]
+    pub format: SinkFormat,
+
+    /// Entities whose changes are published. Every entity is published if none are given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<String>,
+
+    /// Additional producer properties (e.g., `security.protocol`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>,
+}
+
+/// A NATS server to which entity changes are published.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct NatsSinkConfig {
+    /// URL of the NATS server (e.g., `nats://localhost:4222`).
+    pub url: String,
+
+    /// Prefix of the subjects, to which the entity name is appended.
+    ///
+    /// Defaults to `<namespace>.<identifier>.`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_prefix: Option<String>,
+
+    /// Format of the published messages.
+    #[serde(default)]
+    pub format: SinkFormat,
+
+    /// Entities whose changes are published. Every entity is published if none are given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<String>,
+}
+
+/// A Redis server to whose streams entity changes are appended.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct RedisSinkConfig {
+    /// URL of the Redis server (e.g., `redis://localhost:6379`).
+    pub url: String,
+
+    /// Prefix of the stream keys, to which the entity name is appended.
+    ///
+    /// Defaults to `<namespace>.<identifier>.`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_prefix: Option<String>,
+
+    /// Approximate maximum length of each stream, past which the oldest entries are trimmed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_len: Option<u64>,
+
+    /// Format of the published entries.
+    #[serde(default)]
+    pub format: SinkFormat,
+
+    /// Entities whose changes are published. Every entity is published if none are given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<String>,
+}
+
+/// A webhook to which entity changes are posted, optionally only those matching a filter.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct WebhookSinkConfig {
+    /// URL to which each change is posted.
+    pub url: String,
+
+    /// Secret used to sign the body of each request with HMAC-SHA256.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+
+    /// Entities whose changes are posted. Every entity is posted if none are given.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<String>,
+
+    /// Filter expressions, keyed by entity, that changes to the entity must match
+    /// to be posted (e.g., `amount > 1000000`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub filters: BTreeMap<String, String>,
+}
+
+impl Debug for WebhookSinkConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookSinkConfig")
+            .field("url", &self.url)
+            .field("secret", &self.secret.as_ref().map(|_| "XXXX"))
+            .field("entities", &self.entities)
+            .field("filters", &self.filters)
+            .finish()
+    }
+}
+
+/// A ClickHouse server into which append-only entities are mirrored for analytics.
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct ClickHouseSinkConfig {
+    /// URL of the HTTP interface of the ClickHouse server (e.g., `http://localhost:8123`).
+    pub url: String,
+
+    /// Database into which entities are mirrored, created if it doesn't exist.
+    ///
+    /// Defaults to `<namespace>_<identifier>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// User to authenticate as.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+
+    /// Password of the user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    /// Maximum number of rows inserted into a table at once.
+    #[serde(default = "ClickHouseSinkConfig::default_batch_size")]
+    pub batch_size: usize,
+
+    /// Maximum number of seconds that rows are buffered before they are inserted.
+    #[serde(default = "ClickHouseSinkConfig::default_flush_interval_secs")]
+    pub flush_interval_secs: u64,
+
+    /// Entities that are mirrored, which should only ever be inserted, never updated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<String>,
+}
+
+impl ClickHouseSinkConfig {
+    fn default_batch_size() -> usize {
+        10_000
+    }
+
+    fn default_flush_interval_secs() -> u64 {
+        5
+    }
+}
+
+impl Debug for ClickHouseSinkConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClickHouseSinkConfig")
+            .field("url", &self.url)
+            .field("database", &self.database)
+            .field("user", &self.user)
+            .field("password", &self.password.as_ref().map(|_| "XXXX"))
+            .field("batch_size", &self.batch_size)
+            .field("flush_interval_secs", &self.flush_interval_secs)
+            .field("entities", &self.entities)
+            .finish()
+    }
+}
+
+/// An external system to which the entity changes committed by an indexer are published.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SinkConfig {
+    /// Publish entity changes to Kafka, with one topic per entity.
+    Kafka(KafkaSinkConfig),
+
+    /// Publish entity changes to NATS, with one subject per entity.
+    Nats(NatsSinkConfig),
+
+    /// Append entity changes to Redis Streams, with one stream per entity.
+    Redis(RedisSinkConfig),
+
+    /// Post entity changes that match a filter to a webhook.
+    Webhook(WebhookSinkConfig),
+
+    /// Mirror append-only entities into ClickHouse tables, in batches.
+    ClickHouse(ClickHouseSinkConfig),
+}
+
+impl SinkConfig {
+    /// Name of the kind of sink (e.g., `kafka`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Kafka(_) => "kafka",
+            Self::Nats(_) => "nats",
+            Self::Redis(_) => "redis",
+            Self::Webhook(_) => "webhook",
+            Self::ClickHouse(_) => "clickhouse",
+        }
+    }
+
+    /// Entities whose changes are published. Every entity is published if none are given.
+    pub fn entities(&self) -> &[String] {
+        match self {
+            Self::Kafka(config) => &config.entities,
+            Self::Nats(config) => &config.entities,
+            Self::Redis(config) => &config.entities,
+            Self::Webhook(config) => &config.entities,
+            Self::ClickHouse(config) => &config.entities,
+        }
+    }
+}
+
+/// Replica identity of an indexer's tables, which determines the old values that
+/// logical replication records for updated rows.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplicaIdentity {
+    /// Record the old values of every column.
+    #[default]
+    Full,
+
+    /// Record only the old value of the primary key.
+    Default,
+}
+
+impl ReplicaIdentity {
+    /// The `REPLICA IDENTITY` clause of this replica identity.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Self::Full => "FULL",
+            Self::Default => "DEFAULT",
+        }
+    }
+}
+
+/// A Postgres publication of an indexer's tables, to which logical replication
+/// consumers (e.g., Debezium) can subscribe.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct ReplicationConfig {
+    /// Name of the publication.
+    ///
+    /// Defaults to `<namespace>_<identifier>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication: Option<String>,
+
+    /// Replica identity of the indexer's tables.
+    #[serde(default)]
+    pub replica_identity: ReplicaIdentity,
+}
+
+/// Prefix of ABI references that name a package in an ABI registry, e.g.,
+/// `pkg:my-contract@0.1.0`.
+pub const ABI_PACKAGE_PREFIX: &str = "pkg:";
+
+/// Environment variable used to override the ABI registry URL.
+pub const ABI_REGISTRY_ENV_VAR: &str = "FUEL_INDEXER_ABI_REGISTRY";
+
+/// Return the URL from which the given ABI is fetched, or `None` if the ABI is a local file.
+///
+/// ABIs can be given as an `http(s)://` URL, or as a registry package reference of
+/// the form `pkg:<name>@<version>`, which resolves to `<registry>/<name>/<version>/abi.json`.
+pub fn remote_abi_url(abi: &str) -> Option<String> {
+    if abi.starts_with("https://") || abi.starts_with("http://") {
+        return Some(abi.to_string());
+    }
+
+    let package = abi.strip_prefix(ABI_PACKAGE_PREFIX)?;
+    let (name, version) = package.split_once('@').unwrap_or((package, "latest"));
+    let registry = std::env::var(ABI_REGISTRY_ENV_VAR)
+        .unwrap_or_else(|_| defaults::ABI_REGISTRY_URL.to_string());
+
+    Some(format!(
+        "{}/{name}/{version}/abi.json",
+        registry.trim_end_matches('/')
+    ))
+}
+
+/// Return the path at which an ABI fetched from the given URL is cached.
+pub fn abi_cache_path(url: &str) -> PathBuf {
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+
+    home.join(defaults::FUEL_HOME_DIR)
+        .join(defaults::INDEXER_CONFIG_DIR)
+        .join(defaults::ABI_CACHE_DIR)
+        .join(format!("{}.json", sha256_digest(&url)))
+}
+
+/// Return the local path of the given ABI, resolving remote ABIs to their cached copies.
+pub fn local_abi_path(abi: &str) -> String {
+    match remote_abi_url(abi) {
+        Some(url) => abi_cache_path(&url).to_string_lossy().to_string(),
+        None => abi.to_string(),
+    }
+}
+
 /// Represents the indexer manifest file.
 ///
 /// This manifest file is a simple YAML file that is read and passed
@@ -77,8 +482,10 @@ pub struct Manifest {
     /// Identifier of indexer.
     identifier: String,
 
-    /// Filepath to Sway contract ABI.
-    abi: Option<String>,
+    /// Filepath(s) to Sway contract ABI(s).
+    ///
+    /// When multiple ABIs are given, `contract_id` must list one contract ID per ABI.
+    abi: Option<ContractAbis>,
 
     /// URL to Fuel client.
     ///
@@ -96,11 +503,24 @@ pub struct Manifest {
 
     /// Set of contract IDs this indexer should subscribe to.
     #[serde(
+        default,
         serialize_with = "ContractIds::serialize",
         deserialize_with = "ContractIds::deserialize"
     )]
     contract_id: ContractIds,
 
+    /// Roots of the predicates whose spends this indexer should subscribe to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    predicates: Vec<String>,
+
+    /// SHA-256 hashes of the script bytecodes this indexer should subscribe to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    scripts: Vec<String>,
+
+    /// Recipients of the messages this indexer should subscribe to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    message_recipients: Vec<String>,
+
     /// Block at which indexer should start.
     start_block: Option<u64>,
 
@@ -110,6 +530,42 @@ pub struct Manifest {
     /// When set to true, the indexer will resume from the block height at which it last stopped.
     #[serde(default)]
     resumable: Option<bool>,
+    /// When set to true, a native indexer processes the transactions of each block
+    /// in parallel.
+    #[serde(default)]
+    parallel_transactions: Option<bool>,
+
+    /// Host capabilities granted to this indexer.
+    #[serde(default)]
+    capabilities: Capabilities,
+
+    /// Templates for contracts that are discovered at runtime.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    templates: Vec<ContractTemplate>,
+
+    /// Resource limits for this indexer.
+    #[serde(default)]
+    resources: ResourceLimits,
+
+    /// Event-to-entity mappings of a declarative indexer.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    mappings: Vec<EntityMapping>,
+
+    /// Maximum level of the log messages of this indexer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    log_level: Option<String>,
+
+    /// Fields added to every log message of this indexer.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    log_fields: BTreeMap<String, String>,
+
+    /// Sinks to which committed entity changes are published.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sinks: Vec<SinkConfig>,
+
+    /// Postgres publication of this indexer's tables, for logical replication.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replication: Option<ReplicationConfig>,
 }
 
 impl Manifest {
@@ -122,7 +578,25 @@ impl Manifest {
         file.read_to_string(&mut content).map_err(|e| {
             ManifestError::FileError(path.as_ref().display().to_string(), e)
         })?;
-        Self::try_from(content.as_str())
+
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&content),
+            _ => Self::try_from(content.as_str()),
+        }
+    }
+
+    /// Derive an indexer manifest from a TOML string.
+    pub fn from_toml(content: &str) -> ManifestResult<Self> {
+        let manifest: Manifest = toml::from_str(content)?;
+        Ok(manifest)
+    }
+
+    /// Serialize this manifest to a TOML string.
+    pub fn to_toml(&self) -> ManifestResult<String> {
+        // Serializing through `toml::Value` ensures that plain values are written
+        // before tables (e.g., `module`), as TOML requires.
+        let value = toml::Value::try_from(self)?;
+        Ok(toml::to_string(&value)?)
     }
 
     /// Return the raw GraphQL schema string for an indexer manifest.
@@ -144,7 +618,8 @@ impl Manifest {
     pub fn execution_source(&self) -> ExecutionSource {
         match &self.module {
             Module::Native => ExecutionSource::Native,
-            Module::Wasm(_o) => ExecutionSource::Wasm,
+            // Declarative indexers store their data exactly as WASM indexers do.
+            Module::Wasm(_o) | Module::Declarative(_o) => ExecutionSource::Wasm,
         }
     }
 
@@ -155,7 +630,7 @@ impl Manifest {
     /// no way to read module bytes if native execution is specified.
     pub fn module_bytes(&self) -> ManifestResult<Vec<u8>> {
         match &self.module {
-            Module::Wasm(p) => {
+            Module::Wasm(p) | Module::Declarative(p) => {
                 let mut bytes = Vec::<u8>::new();
                 let mut file = File::open(p)
                     .map_err(|err| ManifestError::FileError(p.clone(), err))?;
@@ -187,6 +662,11 @@ impl Manifest {
         self.start_block = Some(block);
     }
 
+    /// Set the end block for this indexer.
+    pub fn set_end_block(&mut self, block: u64) {
+        self.end_block = Some(block);
+    }
+
     /// Set the executor module for this indexer.
     pub fn set_module(&mut self, module: Module) {
         self.module = module;
@@ -199,7 +679,7 @@ impl Manifest {
 
     /// Set the contract ABI for this indexer.
     pub fn set_abi(&mut self, abi: String) {
-        self.abi = Some(abi);
+        self.abi = Some(ContractAbis::Single(abi));
     }
 
     pub fn namespace(&self) -> &str {
@@ -222,8 +702,31 @@ impl Manifest {
         &self.contract_id
     }
 
+    /// Return the contract ABI, if this manifest specifies exactly one.
     pub fn abi(&self) -> Option<&str> {
-        self.abi.as_deref()
+        match &self.abi {
+            Some(ContractAbis::Single(abi)) => Some(abi),
+            _ => None,
+        }
+    }
+
+    /// Return all contract ABIs specified in this manifest.
+    pub fn abis(&self) -> Vec<&str> {
+        match &self.abi {
+            Some(ContractAbis::Single(abi)) => vec![abi.as_str()],
+            Some(ContractAbis::Multiple(abis)) => {
+                abis.iter().map(|abi| abi.as_str()).collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// Return all contract ABIs specified in this manifest, followed by the ABIs of its templates.
+    pub fn all_abis(&self) -> Vec<&str> {
+        self.abis()
+            .into_iter()
+            .chain(self.templates.iter().map(|template| template.abi.as_str()))
+            .collect()
     }
 
     pub fn fuel_client(&self) -> Option<&str> {
@@ -241,6 +744,524 @@ impl Manifest {
     pub fn resumable(&self) -> Option<bool> {
         self.resumable
     }
+
+    pub fn parallel_transactions(&self) -> Option<bool> {
+        self.parallel_transactions
+    }
+
+    pub fn predicates(&self) -> &[String] {
+        &self.predicates
+    }
+
+    pub fn scripts(&self) -> &[String] {
+        &self.scripts
+    }
+
+    pub fn message_recipients(&self) -> &[String] {
+        &self.message_recipients
+    }
+
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.capabilities
+    }
+
+    pub fn templates(&self) -> &[ContractTemplate] {
+        &self.templates
+    }
+
+    pub fn resources(&self) -> &ResourceLimits {
+        &self.resources
+    }
+
+    pub fn mappings(&self) -> &[EntityMapping] {
+        &self.mappings
+    }
+
+    pub fn log_level(&self) -> Option<&str> {
+        self.log_level.as_deref()
+    }
+
+    pub fn log_fields(&self) -> &BTreeMap<String, String> {
+        &self.log_fields
+    }
+
+    pub fn sinks(&self) -> &[SinkConfig] {
+        &self.sinks
+    }
+
+    pub fn replication(&self) -> Option<&ReplicationConfig> {
+        self.replication.as_ref()
+    }
+
+    /// Name of the Postgres publication of this indexer's tables, if replication is enabled.
+    ///
+    /// Custom names are prefixed with `<namespace>_<identifier>_`, so that an indexer
+    /// can't name the publication of another indexer, or any other publication.
+    pub fn publication_name(&self) -> Option<String> {
+        self.replication.as_ref().map(|replication| {
+            let prefix = format!("{}_{}", self.namespace, self.identifier);
+            match &replication.publication {
+                Some(publication) => format!("{prefix}_{publication}"),
+                None => prefix,
+            }
+        })
+    }
+
+    /// Validate the combination of fields in this manifest.
+    ///
+    /// This catches mistakes that would otherwise only surface when the indexer
+    /// is built or deployed. All problems found are reported at once.
+    pub fn validate(&self) -> ManifestResult<()> {
+        let mut errors = Vec::new();
+
+        for (field, value) in [
+            ("namespace", &self.namespace),
+            ("identifier", &self.identifier),
+        ] {
+            if !is_valid_name(value) {
+                errors.push(format!(
+                    "`{field}` must start with a letter or underscore and contain only ASCII letters, digits, and underscores, found '{value}'"
+                ));
+            }
+        }
+
+        if self.graphql_schema.trim().is_empty() {
+            errors.push("`graphql_schema` must not be empty".to_string());
+        }
+
+        match &self.module {
+            Module::Wasm(path) if path.trim().is_empty() => {
+                errors.push("`module.wasm` must be a path to a WASM module".to_string())
+            }
+            Module::Wasm(_) if self.parallel_transactions.unwrap_or(false) => errors
+                .push(
+                    "`parallel_transactions` is only supported for native modules"
+                        .to_string(),
+                ),
+            Module::Declarative(_) if self.mappings.is_empty() => errors
+                .push("`mappings` must not be empty for declarative modules".to_string()),
+            Module::Declarative(_) if self.abis().len() != 1 => errors
+                .push("declarative modules must specify exactly one `abi`".to_string()),
+            Module::Wasm(_) | Module::Native if !self.mappings.is_empty() => errors.push(
+                "`mappings` are only supported for declarative modules".to_string(),
+            ),
+            _ => {}
+        }
+
+        let abis = self.abis();
+        if abis.len() > 1 {
+            match &self.contract_id {
+                ContractIds::Multiple(ids) if ids.len() == abis.len() => {}
+                _ => errors.push(
+                    "`contract_id` must list one contract ID per ABI when multiple ABIs are specified"
+                        .to_string(),
+                ),
+            }
+        }
+
+        for root in &self.predicates {
+            if !is_valid_b256(root) {
+                errors.push(format!(
+                    "Invalid predicate root '{root}', expected a 32-byte hex string"
+                ));
+            }
+        }
+
+        for hash in &self.scripts {
+            if !is_valid_b256(hash) {
+                errors.push(format!(
+                    "Invalid script bytecode hash '{hash}', expected a 32-byte hex string"
+                ));
+            }
+        }
+
+        for recipient in &self.message_recipients {
+            if !is_valid_b256(recipient) {
+                errors.push(format!(
+                    "Invalid message recipient '{recipient}', expected a 32-byte hex string"
+                ));
+            }
+        }
+
+        if let (Some(start), Some(end)) = (self.start_block, self.end_block) {
+            if start > end {
+                errors.push(format!(
+                    "`start_block` ({start}) must not be greater than `end_block` ({end})"
+                ));
+            }
+        }
+
+        for (field, value) in [
+            ("max_memory_mb", self.resources.max_memory_mb),
+            ("max_block_time_ms", self.resources.max_block_time_ms),
+            (
+                "max_db_rows_per_block",
+                self.resources.max_db_rows_per_block,
+            ),
+        ] {
+            if value == Some(0) {
+                errors.push(format!("`resources.{field}` must be greater than zero"));
+            }
+        }
+
+        if let Some(level) = &self.log_level {
+            if tracing::level_filters::LevelFilter::from_str(level).is_err() {
+                errors.push(format!(
+                    "`log_level` must be one of 'off', 'error', 'warn', 'info', 'debug', or 'trace', found '{level}'"
+                ));
+            }
+        }
+
+        for key in self.log_fields.keys() {
+            if !is_valid_name(key) {
+                errors.push(format!("Invalid log field name '{key}'"));
+            }
+        }
+
+        for sink in &self.sinks {
+            let (field, value) = match sink {
+                SinkConfig::Kafka(kafka) => ("brokers", &kafka.brokers),
+                SinkConfig::Nats(nats) => ("url", &nats.url),
+                SinkConfig::Redis(redis) => ("url", &redis.url),
+                SinkConfig::Webhook(webhook) => ("url", &webhook.url),
+                SinkConfig::ClickHouse(clickhouse) => ("url", &clickhouse.url),
+            };
+            if value.trim().is_empty() {
+                errors.push(format!(
+                    "`sinks.{field}` must not be empty for {} sinks",
+                    sink.kind()
+                ));
+            }
+            if let SinkConfig::Redis(RedisSinkConfig {
+                max_len: Some(0), ..
+            }) = sink
+            {
+                errors.push("`sinks.max_len` must be greater than zero".to_string());
+            }
+            if let SinkConfig::ClickHouse(clickhouse) = sink {
+                if clickhouse.entities.is_empty() {
+                    errors.push(
+                        "`sinks.entities` must list the append-only entities to mirror for clickhouse sinks"
+                            .to_string(),
+                    );
+                }
+                if clickhouse.batch_size == 0 {
+                    errors
+                        .push("`sinks.batch_size` must be greater than zero".to_string());
+                }
+                if clickhouse.flush_interval_secs == 0 {
+                    errors.push(
+                        "`sinks.flush_interval_secs` must be greater than zero"
+                            .to_string(),
+                    );
+                }
+                if let Some(database) = &clickhouse.database {
+                    if !is_valid_name(database) {
+                        errors.push(format!(
+                            "Invalid ClickHouse database name '{database}' in `sinks.database`"
+                        ));
+                    }
+                }
+            }
+            if let SinkConfig::Webhook(webhook) = sink {
+                for (entity, filter) in webhook.filters.iter() {
+                    if let Err(e) = Filter::parse(filter) {
+                        errors.push(format!(
+                            "Invalid filter of entity '{entity}' in `sinks.filters`: {e}"
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(ReplicationConfig {
+            publication: Some(publication),
+            ..
+        }) = &self.replication
+        {
+            if !is_valid_name(publication) {
+                errors.push(format!(
+                    "Invalid publication name '{publication}' in `replication`"
+                ));
+            }
+        }
+
+        let mut template_names = std::collections::HashSet::new();
+        for template in &self.templates {
+            if !is_valid_name(&template.name) {
+                errors.push(format!("Invalid template name '{}'", template.name));
+            }
+            if !template_names.insert(template.name.as_str()) {
+                errors.push(format!("Duplicate template name '{}'", template.name));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ManifestError::ValidationError(errors))
+        }
+    }
+
+    /// Validate that the files referenced by this manifest exist, resolving
+    /// relative paths against the given project root.
+    pub fn validate_files(&self, root: impl AsRef<Path>) -> ManifestResult<()> {
+        let paths = self
+            .abis()
+            .into_iter()
+            .map(|abi| ("abi", abi))
+            .chain(
+                self.templates
+                    .iter()
+                    .map(|template| ("templates.abi", template.abi.as_str())),
+            )
+            .chain(std::iter::once((
+                "graphql_schema",
+                self.graphql_schema.as_str(),
+            )));
+
+        // Remote ABIs are checked for in the ABI cache.
+        let errors = paths
+            .filter_map(|(field, path)| {
+                let remote_url = match field {
+                    "graphql_schema" => None,
+                    _ => remote_abi_url(path),
+                };
+                match remote_url {
+                    Some(url) if !abi_cache_path(&url).exists() => Some(format!(
+                        "`{field}` '{path}' has not been fetched from {url}"
+                    )),
+                    None if !root.as_ref().join(path).exists() => {
+                        Some(format!("`{field}` file '{path}' does not exist"))
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ManifestError::ValidationError(errors))
+        }
+    }
+
+    /// Return a JSON Schema describing the manifest file, for use by editors.
+    pub fn json_schema() -> serde_json::Value {
+        let string_or_null = serde_json::json!({ "type": ["string", "null"] });
+        let string_or_list = serde_json::json!({
+            "oneOf": [
+                { "type": "string" },
+                { "type": "array", "items": { "type": "string" } },
+                { "type": "null" }
+            ]
+        });
+
+        serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Fuel indexer manifest",
+            "type": "object",
+            "required": ["namespace", "identifier", "graphql_schema", "module"],
+            "additionalProperties": false,
+            "properties": {
+                "namespace": {
+                    "type": "string",
+                    "pattern": NAME_PATTERN,
+                    "description": "Namespace of the indexer."
+                },
+                "identifier": {
+                    "type": "string",
+                    "pattern": NAME_PATTERN,
+                    "description": "Identifier of the indexer."
+                },
+                "abi": string_or_list,
+                "fuel_client": string_or_null,
+                "graphql_schema": {
+                    "type": "string",
+                    "description": "Filepath to the indexer's GraphQL schema."
+                },
+                "module": {
+                    "type": "object",
+                    "oneOf": [
+                        {
+                            "required": ["wasm"],
+                            "properties": { "wasm": string_or_null },
+                            "additionalProperties": false
+                        },
+                        {
+                            "required": ["native"],
+                            "properties": { "native": { "type": "null" } },
+                            "additionalProperties": false
+                        },
+                        {
+                            "required": ["declarative"],
+                            "properties": { "declarative": { "type": "string" } },
+                            "additionalProperties": false
+                        }
+                    ]
+                },
+                "metrics": { "type": ["boolean", "null"] },
+                "contract_id": string_or_list,
+                "predicates": {
+                    "type": "array",
+                    "items": { "type": "string", "pattern": B256_PATTERN }
+                },
+                "scripts": {
+                    "type": "array",
+                    "items": { "type": "string", "pattern": B256_PATTERN }
+                },
+                "message_recipients": {
+                    "type": "array",
+                    "items": { "type": "string", "pattern": B256_PATTERN }
+                },
+                "start_block": { "type": ["integer", "null"], "minimum": 0 },
+                "end_block": { "type": ["integer", "null"], "minimum": 0 },
+                "resumable": { "type": ["boolean", "null"] },
+                "parallel_transactions": { "type": ["boolean", "null"] },
+                "capabilities": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "allow_http": { "type": "boolean", "default": false },
+                        "allow_raw_sql": { "type": "boolean", "default": true },
+                        "allow_logging": { "type": "boolean", "default": true }
+                    }
+                },
+                "resources": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "max_memory_mb": { "type": ["integer", "null"], "minimum": 1 },
+                        "max_block_time_ms": { "type": ["integer", "null"], "minimum": 1 },
+                        "max_db_rows_per_block": { "type": ["integer", "null"], "minimum": 1 }
+                    }
+                },
+                "templates": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["name", "abi"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "name": { "type": "string", "pattern": NAME_PATTERN },
+                            "abi": { "type": "string" }
+                        }
+                    }
+                },
+                "log_level": {
+                    "type": ["string", "null"],
+                    "enum": ["off", "error", "warn", "info", "debug", "trace", null]
+                },
+                "log_fields": {
+                    "type": "object",
+                    "propertyNames": { "pattern": NAME_PATTERN },
+                    "additionalProperties": { "type": "string" }
+                },
+                "sinks": {
+                    "type": "array",
+                    "items": {
+                        "oneOf": [
+                            {
+                                "type": "object",
+                                "required": ["type", "brokers"],
+                                "additionalProperties": false,
+                                "properties": {
+                                    "type": { "const": "kafka" },
+                                    "brokers": { "type": "string", "minLength": 1 },
+                                    "topic_prefix": { "type": ["string", "null"] },
+                                    "format": { "type": "string", "enum": ["json", "avro"], "default": "json" },
+                                    "entities": { "type": "array", "items": { "type": "string" } },
+                                    "properties": {
+                                        "type": "object",
+                                        "additionalProperties": { "type": "string" }
+                                    }
+                                }
+                            },
+                            {
+                                "type": "object",
+                                "required": ["type", "url"],
+                                "additionalProperties": false,
+                                "properties": {
+                                    "type": { "const": "nats" },
+                                    "url": { "type": "string", "minLength": 1 },
+                                    "subject_prefix": { "type": ["string", "null"] },
+                                    "format": { "type": "string", "enum": ["json", "avro"], "default": "json" },
+                                    "entities": { "type": "array", "items": { "type": "string" } }
+                                }
+                            },
+                            {
+                                "type": "object",
+                                "required": ["type", "url"],
+                                "additionalProperties": false,
+                                "properties": {
+                                    "type": { "const": "redis" },
+                                    "url": { "type": "string", "minLength": 1 },
+                                    "stream_prefix": { "type": ["string", "null"] },
+                                    "max_len": { "type": ["integer", "null"], "minimum": 1 },
+                                    "format": { "type": "string", "enum": ["json", "avro"], "default": "json" },
+                                    "entities": { "type": "array", "items": { "type": "string" } }
+                                }
+                            },
+                            {
+                                "type": "object",
+                                "required": ["type", "url"],
+                                "additionalProperties": false,
+                                "properties": {
+                                    "type": { "const": "webhook" },
+                                    "url": { "type": "string", "minLength": 1 },
+                                    "secret": { "type": ["string", "null"] },
+                                    "entities": { "type": "array", "items": { "type": "string" } },
+                                    "filters": {
+                                        "type": "object",
+                                        "additionalProperties": { "type": "string" }
+                                    }
+                                }
+                            },
+                            {
+                                "type": "object",
+                                "required": ["type", "url", "entities"],
+                                "additionalProperties": false,
+                                "properties": {
+                                    "type": { "const": "clickhouse" },
+                                    "url": { "type": "string", "minLength": 1 },
+                                    "database": { "type": ["string", "null"], "pattern": NAME_PATTERN },
+                                    "user": { "type": ["string", "null"] },
+                                    "password": { "type": ["string", "null"] },
+                                    "batch_size": { "type": "integer", "minimum": 1, "default": 10000 },
+                                    "flush_interval_secs": { "type": "integer", "minimum": 1, "default": 5 },
+                                    "entities": { "type": "array", "items": { "type": "string" }, "minItems": 1 }
+                                }
+                            }
+                        ]
+                    }
+                },
+                "replication": {
+                    "type": ["object", "null"],
+                    "additionalProperties": false,
+                    "properties": {
+                        "publication": { "type": ["string", "null"], "pattern": NAME_PATTERN },
+                        "replica_identity": { "type": "string", "enum": ["full", "default"], "default": "full" }
+                    }
+                },
+                "mappings": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["event", "entity"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "event": { "type": "string" },
+                            "entity": { "type": "string" },
+                            "fields": {
+                                "type": "object",
+                                "additionalProperties": { "type": "string" }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
 }
 
 impl TryFrom<&str> for Manifest {
@@ -263,12 +1284,54 @@ impl TryInto<Vec<u8>> for Manifest {
 impl TryFrom<&Vec<u8>> for Manifest {
     type Error = ManifestError;
 
+    /// Parse a manifest from YAML or TOML bytes.
+    ///
+    /// Manifests uploaded to the web API don't carry a file extension, so both
+    /// formats are attempted, with YAML taking precedence.
     fn try_from(val: &Vec<u8>) -> ManifestResult<Self> {
-        let manifest: Manifest = serde_yaml::from_slice(val)?;
-        Ok(manifest)
+        match serde_yaml::from_slice(val) {
+            Ok(manifest) => Ok(manifest),
+            Err(e) => match std::str::from_utf8(val) {
+                Ok(content) => Self::from_toml(content).map_err(|_| e.into()),
+                Err(_) => Err(e.into()),
+            },
+        }
+    }
+}
+
+/// Pattern that namespaces, identifiers, and template names must match.
+const NAME_PATTERN: &str = "^[A-Za-z_][A-Za-z0-9_]*$";
+
+/// Whether the given name matches `NAME_PATTERN`.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        _ => false,
     }
 }
 
+/// Pattern that predicate roots, script bytecode hashes, and message recipients must match.
+const B256_PATTERN: &str = "^(0x)?[0-9a-fA-F]{64}$";
+
+/// Whether the given value matches `B256_PATTERN`.
+fn is_valid_b256(value: &str) -> bool {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    digits.len() == 64 && digits.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Represents contract ABIs in a `Manifest` struct.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum ContractAbis {
+    /// Single represents a single contract ABI path.
+    Single(String),
+    /// Multiple represents a vector of contract ABI paths, one per subscribed contract.
+    Multiple(Vec<String>),
+}
+
 /// Represents contract IDs in a `Manifest` struct.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(untagged)]
@@ -281,6 +1344,12 @@ pub enum ContractIds {
     Multiple(Vec<String>),
 }
 
+impl Default for ContractIds {
+    fn default() -> Self {
+        ContractIds::Single(None)
+    }
+}
+
 impl ContractIds {
     fn serialize<S>(ids: &ContractIds, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -332,3 +1401,187 @@ impl FromStr for ContractIds {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST_YAML: &str = r#"
+namespace: test_namespace
+identifier: test_identifier
+abi: ~
+fuel_client: ~
+graphql_schema: schema.graphql
+module:
+  wasm: module.wasm
+metrics: ~
+contract_id: fuel1vh4p3wzajltkq2zsswemddh3j3f9dvq6dgqnf58pxtwvsdpxgjxq8k0lgg
+start_block: ~
+end_block: ~
+resumable: true
+"#;
+
+    #[test]
+    fn test_remote_abi_url() {
+        assert_eq!(remote_abi_url("contracts/out/debug/abi.json"), None);
+        assert_eq!(
+            remote_abi_url("https://example.com/abi.json"),
+            Some("https://example.com/abi.json".to_string())
+        );
+
+        std::env::remove_var(ABI_REGISTRY_ENV_VAR);
+        assert_eq!(
+            remote_abi_url("pkg:my-contract@0.1.0"),
+            Some(format!(
+                "{}/my-contract/0.1.0/abi.json",
+                defaults::ABI_REGISTRY_URL
+            ))
+        );
+    }
+
+    #[test]
+    fn test_manifest_validate() {
+        let manifest = Manifest::try_from(MANIFEST_YAML).unwrap();
+        assert!(manifest.validate().is_ok());
+
+        let content = MANIFEST_YAML
+            .replace("test_namespace", "test-namespace")
+            .replace("start_block: ~", "start_block: 10")
+            .replace("end_block: ~", "end_block: 5")
+            + "log_level: loud\n";
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        match manifest.validate() {
+            Err(ManifestError::ValidationError(errors)) => assert_eq!(errors.len(), 3),
+            _ => panic!("Expected manifest validation to fail."),
+        }
+    }
+
+    #[test]
+    fn test_manifest_toml_roundtrip() {
+        let manifest = Manifest::try_from(MANIFEST_YAML).unwrap();
+        let content = manifest.to_toml().unwrap();
+
+        let from_toml = Manifest::from_toml(&content).unwrap();
+        assert_eq!(from_toml.namespace(), "test_namespace");
+        assert_eq!(from_toml.resumable(), Some(true));
+        assert!(matches!(from_toml.module(), Module::Wasm(m) if m == "module.wasm"));
+
+        let from_bytes = Manifest::try_from(&content.as_bytes().to_vec()).unwrap();
+        assert_eq!(from_bytes.identifier(), "test_identifier");
+    }
+
+    #[test]
+    fn test_manifest_sinks() {
+        let content = MANIFEST_YAML.to_string()
+            + r#"
+sinks:
+  - type: kafka
+    brokers: localhost:9092
+    format: avro
+    entities: [Transfer]
+"#;
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        assert!(manifest.validate().is_ok());
+        match &manifest.sinks()[0] {
+            SinkConfig::Kafka(kafka) => {
+                assert_eq!(kafka.brokers, "localhost:9092");
+                assert_eq!(kafka.format, SinkFormat::Avro);
+                assert_eq!(kafka.topic_prefix, None);
+            }
+            _ => panic!("Expected a Kafka sink."),
+        }
+        assert_eq!(manifest.sinks()[0].entities(), ["Transfer".to_string()]);
+
+        let from_toml = Manifest::from_toml(&manifest.to_toml().unwrap()).unwrap();
+        assert_eq!(from_toml.sinks(), manifest.sinks());
+    }
+
+    #[test]
+    fn test_manifest_replication() {
+        let manifest = Manifest::try_from(MANIFEST_YAML).unwrap();
+        assert_eq!(manifest.publication_name(), None);
+
+        let content = MANIFEST_YAML.to_string()
+            + r#"
+replication:
+  replica_identity: default
+"#;
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        assert!(manifest.validate().is_ok());
+        assert_eq!(
+            manifest.publication_name(),
+            Some("test_namespace_test_identifier".to_string())
+        );
+        assert_eq!(
+            manifest.replication().unwrap().replica_identity,
+            ReplicaIdentity::Default
+        );
+
+        let content = MANIFEST_YAML.to_string()
+            + r#"
+replication:
+  publication: pub
+"#;
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        assert_eq!(
+            manifest.publication_name(),
+            Some("test_namespace_test_identifier_pub".to_string())
+        );
+
+        let content = MANIFEST_YAML.to_string()
+            + r#"
+replication:
+  publication: "bad-name"
+"#;
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        assert!(manifest.validate().is_err());
+    }
+
+    #[test]
+    fn test_manifest_webhook_sink_filters() {
+        let content = MANIFEST_YAML.to_string()
+            + r#"
+sinks:
+  - type: webhook
+    url: https://example.com/hook
+    secret: abc
+    entities: [Liquidation]
+    filters:
+      Liquidation: amount > 1000000
+"#;
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        assert!(manifest.validate().is_ok());
+        assert!(!format!("{manifest:?}").contains("abc"));
+
+        let content = content.replace("amount > 1000000", "amount >");
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        assert!(manifest.validate().is_err());
+    }
+
+    #[test]
+    fn test_manifest_clickhouse_sink() {
+        let content = MANIFEST_YAML.to_string()
+            + r#"
+sinks:
+  - type: clickhouse
+    url: http://localhost:8123
+    password: abc
+    entities: [Transfer]
+"#;
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        assert!(manifest.validate().is_ok());
+        assert!(!format!("{manifest:?}").contains("abc"));
+        match &manifest.sinks()[0] {
+            SinkConfig::ClickHouse(config) => {
+                assert_eq!(config.batch_size, 10_000);
+                assert_eq!(config.flush_interval_secs, 5);
+            }
+            sink => panic!("Expected a clickhouse sink, found {sink:?}"),
+        }
+
+        // Only the listed append-only entities are mirrored.
+        let content = content.replace("    entities: [Transfer]\n", "");
+        let manifest = Manifest::try_from(content.as_str()).unwrap();
+        assert!(manifest.validate().is_err());
+    }
+}
@@ -0,0 +1,634 @@
+//! Declarative event-to-entity mappings.
+//!
+//! Indexers with a `declarative` module do not run any user code. Instead, the
+//! manifest lists `mappings` from logged contract types to GraphQL entities, which
+//! `forc index build` compiles (together with the contract ABI) into a
+//! `MappingModule`. The indexer service decodes `LogData` receipts using the module,
+//! and saves one entity per matching receipt.
+
+use crate::{graphql::ParsedGraphQLSchema, manifest::EntityMapping};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use thiserror::Error;
+
+/// Size, in bytes, of a word in the Fuel ABI encoding.
+const WORD_SIZE: usize = 8;
+
+/// Result type returned from mapping operations.
+pub type MappingResult<T> = Result<T, MappingError>;
+
+/// Error type returned from mapping operations.
+#[derive(Error, Debug)]
+pub enum MappingError {
+    #[error("Invalid ABI: {0}")]
+    AbiError(String),
+    #[error("Unsupported ABI type '{0}'")]
+    UnsupportedType(String),
+    #[error("No logged type named '{0}' in the ABI")]
+    UnknownEvent(String),
+    #[error("Entity '{0}' is not defined in the GraphQL schema")]
+    UnknownEntity(String),
+    #[error("Entity '{0}' has no field '{1}'")]
+    UnknownEntityField(String, String),
+    #[error("Event '{0}' has no field '{1}'")]
+    UnknownEventField(String, String),
+    #[error("Could not decode '{0}': {1}")]
+    DecodeError(String, String),
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+}
+
+/// A type in the Fuel ABI, as needed to decode logged values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AbiType {
+    Unit,
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    B256,
+    Str(usize),
+    Array(Box<AbiType>, usize),
+    Tuple(Vec<AbiType>),
+    Struct(String, Vec<(String, AbiType)>),
+    Enum(String, Vec<(String, AbiType)>),
+}
+
+impl AbiType {
+    /// Resolve the type with the given ID from the `types` of a JSON ABI.
+    fn from_abi(
+        types: &HashMap<u64, &serde_json::Value>,
+        id: u64,
+    ) -> MappingResult<Self> {
+        let decl = types
+            .get(&id)
+            .ok_or_else(|| MappingError::AbiError(format!("unknown type ID {id}")))?;
+        let name = decl["type"]
+            .as_str()
+            .ok_or_else(|| MappingError::AbiError(format!("type {id} has no name")))?;
+
+        let components = || -> MappingResult<Vec<(String, AbiType)>> {
+            decl["components"]
+                .as_array()
+                .map(|components| components.as_slice())
+                .unwrap_or_default()
+                .iter()
+                .map(|component| {
+                    if component["typeArguments"]
+                        .as_array()
+                        .map_or(false, |args| !args.is_empty())
+                    {
+                        return Err(MappingError::UnsupportedType(name.to_string()));
+                    }
+                    let field = component["name"].as_str().unwrap_or_default();
+                    let ty = component["type"].as_u64().ok_or_else(|| {
+                        MappingError::AbiError(format!("invalid component of '{name}'"))
+                    })?;
+                    Ok((field.to_string(), AbiType::from_abi(types, ty)?))
+                })
+                .collect()
+        };
+
+        let ty = match name {
+            "()" => AbiType::Unit,
+            "bool" => AbiType::Bool,
+            "u8" => AbiType::U8,
+            "u16" => AbiType::U16,
+            "u32" => AbiType::U32,
+            "u64" => AbiType::U64,
+            "b256" => AbiType::B256,
+            _ if name.starts_with("str[") => {
+                let len = name
+                    .trim_start_matches("str[")
+                    .trim_end_matches(']')
+                    .parse()
+                    .map_err(|_| MappingError::UnsupportedType(name.to_string()))?;
+                AbiType::Str(len)
+            }
+            _ if name.starts_with("[_; ") => {
+                let len = name
+                    .trim_start_matches("[_; ")
+                    .trim_end_matches(']')
+                    .parse()
+                    .map_err(|_| MappingError::UnsupportedType(name.to_string()))?;
+                let (_, element) = components()?
+                    .pop()
+                    .ok_or_else(|| MappingError::UnsupportedType(name.to_string()))?;
+                AbiType::Array(Box::new(element), len)
+            }
+            _ if name.starts_with('(') => {
+                AbiType::Tuple(components()?.into_iter().map(|(_, ty)| ty).collect())
+            }
+            _ if decl["typeParameters"]
+                .as_array()
+                .map_or(false, |params| !params.is_empty()) =>
+            {
+                return Err(MappingError::UnsupportedType(name.to_string()))
+            }
+            _ => match name.split_once(' ') {
+                Some(("struct", struct_name)) => {
+                    AbiType::Struct(struct_name.to_string(), components()?)
+                }
+                Some(("enum", enum_name)) => {
+                    AbiType::Enum(enum_name.to_string(), components()?)
+                }
+                _ => return Err(MappingError::UnsupportedType(name.to_string())),
+            },
+        };
+
+        Ok(ty)
+    }
+
+    /// Name of this type, if it is a struct or an enum.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            AbiType::Struct(name, _) | AbiType::Enum(name, _) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Size, in bytes, of an encoded value of this type.
+    fn width(&self) -> usize {
+        match self {
+            AbiType::Unit
+            | AbiType::Bool
+            | AbiType::U8
+            | AbiType::U16
+            | AbiType::U32
+            | AbiType::U64 => WORD_SIZE,
+            AbiType::B256 => 32,
+            AbiType::Str(len) => (len + WORD_SIZE - 1) / WORD_SIZE * WORD_SIZE,
+            AbiType::Array(element, len) => element.width() * len,
+            AbiType::Tuple(elements) => elements.iter().map(AbiType::width).sum(),
+            AbiType::Struct(_, fields) => fields.iter().map(|(_, ty)| ty.width()).sum(),
+            AbiType::Enum(_, variants) => {
+                WORD_SIZE
+                    + variants
+                        .iter()
+                        .map(|(_, ty)| ty.width())
+                        .max()
+                        .unwrap_or_default()
+            }
+        }
+    }
+
+    /// Decode a value of this type from its ABI encoding.
+    pub fn decode(&self, bytes: &[u8]) -> MappingResult<DecodedValue> {
+        if bytes.len() < self.width() {
+            return Err(self.decode_error(format!(
+                "expected {} bytes, found {}",
+                self.width(),
+                bytes.len()
+            )));
+        }
+
+        let word = || {
+            let mut buf = [0u8; WORD_SIZE];
+            buf.copy_from_slice(&bytes[..WORD_SIZE]);
+            u64::from_be_bytes(buf)
+        };
+
+        let value = match self {
+            AbiType::Unit => DecodedValue::Unit,
+            AbiType::Bool => DecodedValue::Bool(word() != 0),
+            AbiType::U8 | AbiType::U16 | AbiType::U32 | AbiType::U64 => {
+                DecodedValue::U64(word())
+            }
+            AbiType::B256 => {
+                let mut buf = [0u8; 32];
+                buf.copy_from_slice(&bytes[..32]);
+                DecodedValue::B256(buf)
+            }
+            AbiType::Str(len) => DecodedValue::Str(
+                String::from_utf8(bytes[..*len].to_vec())
+                    .map_err(|e| self.decode_error(e.to_string()))?,
+            ),
+            AbiType::Array(element, len) => DecodedValue::Array(
+                (0..*len)
+                    .map(|i| element.decode(&bytes[i * element.width()..]))
+                    .collect::<MappingResult<_>>()?,
+            ),
+            AbiType::Tuple(elements) => {
+                let mut offset = 0;
+                let mut values = Vec::with_capacity(elements.len());
+                for element in elements {
+                    values.push(element.decode(&bytes[offset..])?);
+                    offset += element.width();
+                }
+                DecodedValue::Array(values)
+            }
+            AbiType::Struct(_, fields) => {
+                let mut offset = 0;
+                let mut values = Vec::with_capacity(fields.len());
+                for (name, ty) in fields {
+                    values.push((name.clone(), ty.decode(&bytes[offset..])?));
+                    offset += ty.width();
+                }
+                DecodedValue::Struct(values)
+            }
+            AbiType::Enum(_, variants) => {
+                let discriminant = word() as usize;
+                let (name, ty) = variants.get(discriminant).ok_or_else(|| {
+                    self.decode_error(format!("invalid discriminant {discriminant}"))
+                })?;
+                // Variant data is left-padded to the width of the widest variant.
+                let padding = self.width() - WORD_SIZE - ty.width();
+                let value = ty.decode(&bytes[WORD_SIZE + padding..])?;
+                DecodedValue::Enum(name.clone(), Box::new(value))
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn decode_error(&self, reason: String) -> MappingError {
+        MappingError::DecodeError(format!("{self:?}"), reason)
+    }
+}
+
+/// A value decoded from its Fuel ABI encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedValue {
+    Unit,
+    Bool(bool),
+    U64(u64),
+    B256([u8; 32]),
+    Str(String),
+    Array(Vec<DecodedValue>),
+    Struct(Vec<(String, DecodedValue)>),
+    Enum(String, Box<DecodedValue>),
+}
+
+impl DecodedValue {
+    /// Return the value of the struct field at the given path.
+    fn get(&self, path: &[String]) -> Option<&DecodedValue> {
+        match path.split_first() {
+            None => Some(self),
+            Some((field, rest)) => match self {
+                DecodedValue::Struct(fields) => fields
+                    .iter()
+                    .find(|(name, _)| name == field)
+                    .and_then(|(_, value)| value.get(rest)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Unwrap single-field structs (e.g., `Address` or `ContractId`) to their inner value.
+    pub fn unwrap_newtype(&self) -> &DecodedValue {
+        match self {
+            DecodedValue::Struct(fields) if fields.len() == 1 => {
+                fields[0].1.unwrap_newtype()
+            }
+            _ => self,
+        }
+    }
+
+    /// Convert this value to a string, e.g., for `Charfield` entity fields.
+    pub fn to_plain_string(&self) -> String {
+        match self {
+            DecodedValue::Str(s) => s.clone(),
+            DecodedValue::B256(bytes) => hex_string(bytes),
+            DecodedValue::Enum(variant, value) if **value == DecodedValue::Unit => {
+                variant.clone()
+            }
+            _ => self.to_json().to_string(),
+        }
+    }
+
+    /// Convert this value to JSON, e.g., for `Json` entity fields.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            DecodedValue::Unit => serde_json::Value::Null,
+            DecodedValue::Bool(b) => serde_json::Value::Bool(*b),
+            DecodedValue::U64(n) => serde_json::Value::from(*n),
+            DecodedValue::B256(bytes) => serde_json::Value::String(hex_string(bytes)),
+            DecodedValue::Str(s) => serde_json::Value::String(s.clone()),
+            DecodedValue::Array(values) => {
+                serde_json::Value::Array(values.iter().map(|v| v.to_json()).collect())
+            }
+            DecodedValue::Struct(fields) => serde_json::Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_json()))
+                    .collect(),
+            ),
+            DecodedValue::Enum(variant, value) => match **value {
+                DecodedValue::Unit => serde_json::Value::String(variant.clone()),
+                ref value => serde_json::Value::Object(
+                    std::iter::once((variant.clone(), value.to_json())).collect(),
+                ),
+            },
+        }
+    }
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Where the value of a mapped entity field is read from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldSource {
+    /// A field of the logged event, given as a path of field names.
+    Event(Vec<String>),
+
+    /// Height of the block containing the event (`$block.height`).
+    BlockHeight,
+
+    /// ID of the block containing the event (`$block.id`).
+    BlockId,
+
+    /// Timestamp of the block containing the event (`$block.time`).
+    BlockTime,
+
+    /// ID of the transaction containing the event (`$tx.id`).
+    TxId,
+
+    /// ID of the contract that logged the event (`$contract_id`).
+    ContractId,
+}
+
+impl FieldSource {
+    fn parse(source: &str) -> Option<Self> {
+        match source {
+            "$block.height" => Some(FieldSource::BlockHeight),
+            "$block.id" => Some(FieldSource::BlockId),
+            "$block.time" => Some(FieldSource::BlockTime),
+            "$tx.id" => Some(FieldSource::TxId),
+            "$contract_id" => Some(FieldSource::ContractId),
+            _ if source.starts_with('$') => None,
+            _ => Some(FieldSource::Event(
+                source.split('.').map(|field| field.to_string()).collect(),
+            )),
+        }
+    }
+}
+
+/// Block and transaction metadata of a logged event.
+#[derive(Debug, Clone)]
+pub struct EventContext {
+    pub block_height: u64,
+    pub block_id: [u8; 32],
+    pub block_time: i64,
+    pub tx_id: [u8; 32],
+    pub contract_id: [u8; 32],
+
+    /// Index of the event's receipt within its transaction.
+    pub receipt_index: usize,
+}
+
+impl EventContext {
+    /// Entity ID used when a mapping does not map the `id` field, unique to the event's receipt.
+    pub fn default_id(&self) -> u64 {
+        let mut hasher = Sha256::new();
+        hasher.update(self.tx_id);
+        hasher.update((self.receipt_index as u64).to_be_bytes());
+        let digest = hasher.finalize();
+
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(bytes)
+    }
+}
+
+/// A mapping from a logged type to an entity, resolved against the contract ABI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompiledMapping {
+    /// Log IDs under which the event is logged.
+    pub log_ids: Vec<u64>,
+
+    /// Name of the logged type.
+    pub event: String,
+
+    /// ABI type of the logged event.
+    pub event_type: AbiType,
+
+    /// Name of the GraphQL entity to save.
+    pub entity: String,
+
+    /// Entity fields, and where their values are read from.
+    pub fields: BTreeMap<String, FieldSource>,
+}
+
+impl CompiledMapping {
+    /// Return the values of the mapped entity fields for the given event.
+    pub fn field_values(
+        &self,
+        event: &DecodedValue,
+        ctx: &EventContext,
+    ) -> HashMap<String, DecodedValue> {
+        let mut values = HashMap::new();
+        for (field, source) in &self.fields {
+            let value = match source {
+                FieldSource::Event(path) => match event.get(path) {
+                    Some(value) => value.clone(),
+                    None => continue,
+                },
+                FieldSource::BlockHeight => DecodedValue::U64(ctx.block_height),
+                FieldSource::BlockId => DecodedValue::B256(ctx.block_id),
+                FieldSource::BlockTime => DecodedValue::U64(ctx.block_time as u64),
+                FieldSource::TxId => DecodedValue::B256(ctx.tx_id),
+                FieldSource::ContractId => DecodedValue::B256(ctx.contract_id),
+            };
+            values.insert(field.clone(), value);
+        }
+
+        values
+            .entry("id".to_string())
+            .or_insert_with(|| DecodedValue::U64(ctx.default_id()));
+
+        values
+    }
+}
+
+/// The compiled module of a declarative indexer.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MappingModule {
+    pub mappings: Vec<CompiledMapping>,
+}
+
+impl MappingModule {
+    /// Compile the given mappings against a JSON contract ABI and the indexer's GraphQL schema.
+    pub fn compile(
+        mappings: &[EntityMapping],
+        abi: &str,
+        schema: &ParsedGraphQLSchema,
+    ) -> MappingResult<Self> {
+        let abi: serde_json::Value = serde_json::from_str(abi)?;
+
+        let types = abi["types"]
+            .as_array()
+            .ok_or_else(|| MappingError::AbiError("missing `types`".to_string()))?
+            .iter()
+            .filter_map(|decl| Some((decl["typeId"].as_u64()?, decl)))
+            .collect::<HashMap<_, _>>();
+
+        // Log IDs of each logged struct or enum, by type name.
+        let mut logged_types: HashMap<String, (AbiType, Vec<u64>)> = HashMap::new();
+        for logged in abi["loggedTypes"].as_array().into_iter().flatten() {
+            let (log_id, type_id) = match (
+                logged["logId"].as_u64(),
+                logged["loggedType"]["type"].as_u64(),
+            ) {
+                (Some(log_id), Some(type_id)) => (log_id, type_id),
+                _ => return Err(MappingError::AbiError("invalid `loggedTypes`".into())),
+            };
+
+            // Logged types that cannot be decoded are only an error if they are mapped.
+            if let Ok(ty) = AbiType::from_abi(&types, type_id) {
+                if let Some(name) = ty.name().map(|name| name.to_string()) {
+                    logged_types
+                        .entry(name)
+                        .or_insert_with(|| (ty, Vec::new()))
+                        .1
+                        .push(log_id);
+                }
+            }
+        }
+
+        let mappings = mappings
+            .iter()
+            .map(|mapping| {
+                let (event_type, log_ids) = logged_types
+                    .get(&mapping.event)
+                    .cloned()
+                    .ok_or_else(|| MappingError::UnknownEvent(mapping.event.clone()))?;
+
+                let entity_fields = schema
+                    .object_field_mappings()
+                    .get(&mapping.entity)
+                    .ok_or_else(|| {
+                    MappingError::UnknownEntity(mapping.entity.clone())
+                })?;
+
+                let mut fields = BTreeMap::new();
+                for (field, source) in &mapping.fields {
+                    if !entity_fields.contains_key(field) {
+                        return Err(MappingError::UnknownEntityField(
+                            mapping.entity.clone(),
+                            field.clone(),
+                        ));
+                    }
+
+                    let unknown_source = || {
+                        MappingError::UnknownEventField(
+                            mapping.event.clone(),
+                            source.clone(),
+                        )
+                    };
+                    let source = FieldSource::parse(source).ok_or_else(unknown_source)?;
+                    if let FieldSource::Event(path) = &source {
+                        if !has_field(&event_type, path) {
+                            return Err(unknown_source());
+                        }
+                    }
+
+                    fields.insert(field.clone(), source);
+                }
+
+                Ok(CompiledMapping {
+                    log_ids,
+                    event: mapping.event.clone(),
+                    event_type,
+                    entity: mapping.entity.clone(),
+                    fields,
+                })
+            })
+            .collect::<MappingResult<Vec<_>>>()?;
+
+        Ok(Self { mappings })
+    }
+
+    /// Return the mappings for events logged under the given log ID.
+    pub fn mappings_for(&self, log_id: u64) -> impl Iterator<Item = &CompiledMapping> {
+        self.mappings
+            .iter()
+            .filter(move |mapping| mapping.log_ids.contains(&log_id))
+    }
+
+    /// Serialize this module to the bytes stored as the indexer's module asset.
+    pub fn to_bytes(&self) -> MappingResult<Vec<u8>> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Deserialize a module from the indexer's module asset.
+    pub fn from_bytes(bytes: &[u8]) -> MappingResult<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+fn has_field(ty: &AbiType, path: &[String]) -> bool {
+    match path.split_first() {
+        None => true,
+        Some((field, rest)) => match ty {
+            AbiType::Struct(_, fields) => fields
+                .iter()
+                .any(|(name, ty)| name == field && has_field(ty, rest)),
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_struct_with_enum_and_string() {
+        let ty = AbiType::Struct(
+            "TransferEvent".to_string(),
+            vec![
+                (
+                    "to".to_string(),
+                    AbiType::Struct(
+                        "Address".to_string(),
+                        vec![("value".to_string(), AbiType::B256)],
+                    ),
+                ),
+                ("amount".to_string(), AbiType::U64),
+                (
+                    "kind".to_string(),
+                    AbiType::Enum(
+                        "Kind".to_string(),
+                        vec![
+                            ("Mint".to_string(), AbiType::Unit),
+                            ("Fee".to_string(), AbiType::B256),
+                        ],
+                    ),
+                ),
+                ("memo".to_string(), AbiType::Str(3)),
+            ],
+        );
+
+        let mut bytes = vec![7u8; 32];
+        bytes.extend(42u64.to_be_bytes());
+        bytes.extend(0u64.to_be_bytes());
+        bytes.extend([0u8; 32]);
+        bytes.extend(b"abc\0\0\0\0\0");
+
+        let value = ty.decode(&bytes).unwrap();
+        let path = |p: &str| p.split('.').map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            value.get(&path("to")).unwrap().unwrap_newtype(),
+            &DecodedValue::B256([7u8; 32])
+        );
+        assert_eq!(value.get(&path("amount")), Some(&DecodedValue::U64(42)));
+        assert_eq!(
+            value.get(&path("kind")),
+            Some(&DecodedValue::Enum(
+                "Mint".to_string(),
+                Box::new(DecodedValue::Unit)
+            ))
+        );
+        assert_eq!(
+            value.get(&path("memo")),
+            Some(&DecodedValue::Str("abc".to_string()))
+        );
+        assert!(has_field(&ty, &path("to.value")));
+        assert!(!has_field(&ty, &path("from")));
+    }
+}
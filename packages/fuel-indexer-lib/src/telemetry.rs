@@ -0,0 +1,71 @@
+//! Export of the service's spans to an OpenTelemetry collector.
+
+use crate::config::TelemetryConfig;
+use opentelemetry::{
+    runtime,
+    sdk::{
+        trace::{self, Sampler, Tracer},
+        Resource,
+    },
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use tracing::{info, Span, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Name under which the service's traces are exported.
+const SERVICE_NAME: &str = "fuel-indexer";
+
+/// Build a layer exporting spans to the OpenTelemetry collector in the given
+/// config, if telemetry is enabled.
+pub fn layer<S>(
+    config: &TelemetryConfig,
+) -> anyhow::Result<Option<OpenTelemetryLayer<S, Tracer>>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(&config.otlp_endpoint),
+        )
+        .with_trace_config(
+            trace::config()
+                .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                    config.sampling_ratio,
+                ))))
+                .with_resource(Resource::new(vec![KeyValue::new(
+                    "service.name",
+                    SERVICE_NAME,
+                )])),
+        )
+        .install_batch(runtime::Tokio)?;
+
+    info!(
+        "Exporting traces to {} with a sampling ratio of {}.",
+        config.otlp_endpoint, config.sampling_ratio
+    );
+
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+/// Make the given span the root of a new trace, while keeping its parent for
+/// logging purposes.
+///
+/// Long-lived spans, such as an indexer's executor span, would otherwise hold
+/// every span created within them in a single trace.
+pub fn start_trace(span: &Span) {
+    span.set_parent(opentelemetry::Context::new());
+}
+
+/// Flush the spans that haven't been exported yet, and stop exporting traces.
+pub fn shutdown() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
@@ -1,5 +1,9 @@
-use crate::{config::IndexerConfig, defaults};
+use crate::{
+    config::{IndexerConfig, LogFormat},
+    defaults,
+};
 use anyhow::Result;
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::{
@@ -11,14 +15,41 @@ use std::{
     str::FromStr,
 };
 use tokio::time::{sleep, Duration};
-use tracing::{debug, info, warn};
-use tracing_subscriber::filter::EnvFilter;
+use tracing::{
+    debug,
+    field::{Field, Visit},
+    info,
+    level_filters::LevelFilter,
+    span::{Attributes, Id},
+    subscriber::Interest,
+    warn, Event, Metadata, Subscriber,
+};
+use tracing_subscriber::{
+    filter::EnvFilter,
+    fmt::{
+        format::{FormatEvent, FormatFields, JsonFields, Writer},
+        time::FormatTime,
+        FmtContext, FormattedFields,
+    },
+    layer::{Context, Layer, SubscriberExt},
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+};
 
 const RUST_LOG: &str = "RUST_LOG";
 const HUMAN_LOGGING: &str = "HUMAN_LOGGING";
 
 const ROOT_DIRECTORY_NAME: &str = "fuel-indexer";
 
+/// Replaces the filter of the global log subscriber.
+type LogFilterReloader = Box<dyn Fn(EnvFilter) -> Result<()> + Send + Sync>;
+
+lazy_static! {
+    /// Set once logging is initialized, so that the log level can be changed at runtime.
+    static ref LOG_FILTER_RELOADER: std::sync::Mutex<Option<LogFilterReloader>> =
+        std::sync::Mutex::new(None);
+}
+
 /// Serialize a generic byte array reference.
 pub fn serialize(obj: &impl Serialize) -> Vec<u8> {
     bincode::serialize(obj).expect("Serialize failed")
@@ -91,11 +122,62 @@ pub struct StopRequest {
     pub identifier: String,
 }
 
+/// Request to restart the specified indexer executor from its latest checkpoint,
+/// after it was paused.
+///
+/// Sent from API server to indexer service.
+#[derive(Debug)]
+pub struct ResumeRequest {
+    /// The namespace of the indexer being resumed.
+    pub namespace: String,
+
+    /// The identifier of the indexer being resumed.
+    pub identifier: String,
+}
+
+/// Request to clear the data of the specified indexer and restart its executor
+/// from the given block height.
+///
+/// Sent from API server to indexer service.
+#[derive(Debug)]
+pub struct ReindexRequest {
+    /// The namespace of the indexer being reindexed.
+    pub namespace: String,
+
+    /// The identifier of the indexer being reindexed.
+    pub identifier: String,
+
+    /// The block height from which the indexer is reindexed.
+    pub start_block: u64,
+}
+
+/// Request to reprocess a range of blocks of the specified indexer in place, keeping
+/// the rest of its data, and then restart its executor from its latest checkpoint.
+///
+/// Sent from API server to indexer service.
+#[derive(Debug)]
+pub struct ReindexRangeRequest {
+    /// The namespace of the indexer being repaired.
+    pub namespace: String,
+
+    /// The identifier of the indexer being repaired.
+    pub identifier: String,
+
+    /// The first block height to reprocess.
+    pub start_block: u64,
+
+    /// The last block height to reprocess.
+    pub end_block: u64,
+}
+
 /// A general request sent from the API server to the indexer service.
 #[derive(Debug)]
 pub enum ServiceRequest {
     Reload(ReloadRequest),
     Stop(StopRequest),
+    Resume(ResumeRequest),
+    Reindex(ReindexRequest),
+    ReindexRange(ReindexRangeRequest),
 }
 
 /// Returns the lower hex representation of a [`sha2::SHA256`] digest of the provided input.
@@ -221,32 +303,238 @@ pub async fn init_logging(config: &IndexerConfig) -> anyhow::Result<()> {
         None => EnvFilter::new("info"),
     };
 
+    // `HUMAN_LOGGING=false` is still honored for deployments that predate `log_format`.
     let human_logging = env::var_os(HUMAN_LOGGING)
         .map(|s| {
             bool::from_str(s.to_str().unwrap())
                 .expect("Expected `true` or `false` to be provided for `HUMAN_LOGGING`")
         })
-        .unwrap_or(true);
+        .unwrap_or(true)
+        && config.log_format == LogFormat::Text;
 
     let sub = tracing_subscriber::fmt::Subscriber::builder()
         .with_writer(std::io::stderr)
         .with_env_filter(filter);
 
-    if human_logging {
-        sub.with_ansi(true)
+    let reloader: LogFilterReloader = if human_logging {
+        let sub = sub
+            .with_ansi(true)
             .with_level(true)
             .with_line_number(true)
-            .init();
+            .with_filter_reloading();
+        let handle = sub.reload_handle();
+        let sub = sub.finish().with(IndexerLogLevelLayer);
+        #[cfg(not(target_arch = "wasm32"))]
+        let sub = sub.with(crate::telemetry::layer(&config.telemetry)?);
+        sub.init();
+        Box::new(move |filter: EnvFilter| -> Result<()> {
+            handle.reload(filter)?;
+            Ok(())
+        })
     } else {
-        sub.with_ansi(false)
-            .with_level(true)
-            .with_line_number(true)
-            .json()
-            .init();
-    }
+        let sub = sub
+            .with_ansi(false)
+            .fmt_fields(JsonFields::new())
+            .event_format(JsonLogFormat)
+            .with_filter_reloading();
+        let handle = sub.reload_handle();
+        let sub = sub.finish().with(IndexerLogLevelLayer);
+        #[cfg(not(target_arch = "wasm32"))]
+        let sub = sub.with(crate::telemetry::layer(&config.telemetry)?);
+        sub.init();
+        Box::new(move |filter: EnvFilter| -> Result<()> {
+            handle.reload(filter)?;
+            Ok(())
+        })
+    };
+
+    *LOG_FILTER_RELOADER
+        .lock()
+        .expect("Log filter reloader lock poisoned.") = Some(reloader);
+
     Ok(())
 }
 
+/// Formats log messages as JSON objects, so that they can be ingested without
+/// parsing free-form text.
+///
+/// Besides `timestamp`, `level`, `module` and `message`, each object holds the
+/// fields of the message and of the spans it was logged in, such as
+/// `block_height` and `duration_ms`. The `uid` of an indexer's span is given as
+/// `indexer`.
+struct JsonLogFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonLogFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let mut timestamp = String::new();
+        tracing_subscriber::fmt::time::SystemTime
+            .format_time(&mut Writer::new(&mut timestamp))?;
+
+        let metadata = event.metadata();
+        let mut log = serde_json::Map::new();
+        log.insert("timestamp".to_string(), timestamp.into());
+        log.insert("level".to_string(), metadata.level().as_str().into());
+        log.insert("module".to_string(), metadata.target().into());
+
+        // Span fields are formatted as JSON objects by `JsonFields`. Fields of
+        // inner spans take precedence over those of outer spans.
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                let fields = extensions
+                    .get::<FormattedFields<N>>()
+                    .and_then(|fields| {
+                        serde_json::from_str::<serde_json::Map<_, _>>(&fields.fields).ok()
+                    })
+                    .unwrap_or_default();
+
+                for (key, value) in fields {
+                    match (span.name(), key.as_str()) {
+                        ("indexer", "uid") => log.insert("indexer".to_string(), value),
+                        _ => log.insert(key, value),
+                    };
+                }
+            }
+        }
+
+        event.record(&mut JsonLogVisitor(&mut log));
+
+        writeln!(writer, "{}", serde_json::Value::Object(log))
+    }
+}
+
+/// Records the fields of a log message into a JSON object.
+struct JsonLogVisitor<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonLogVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}").into());
+    }
+}
+
+/// Log level of an indexer, stored in the extensions of its executor's span.
+struct IndexerLogLevel(LevelFilter);
+
+/// Reads the `log_level` field of an indexer span.
+struct IndexerLogLevelVisitor(Option<LevelFilter>);
+
+impl Visit for IndexerLogLevelVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "log_level" {
+            self.0 = LevelFilter::from_str(value).ok();
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "log_level" {
+            self.0 = LevelFilter::from_str(&format!("{value:?}")).ok();
+        }
+    }
+}
+
+/// Filters the log messages of each indexer by the `log_level` in its manifest.
+///
+/// Executors run in an `indexer` span whose `log_level` field is the indexer's log
+/// level. Messages logged within the span that are more verbose than this level are
+/// dropped. Since the global filter is applied first, an indexer's log level can
+/// quiet it, but cannot make it more verbose than the service.
+struct IndexerLogLevelLayer;
+
+impl<S> Layer<S> for IndexerLogLevelLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // Whether a message is enabled depends on the span it is logged in.
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, ctx: Context<'_, S>) -> bool {
+        if !metadata.is_event() {
+            return true;
+        }
+
+        let level = ctx.lookup_current().and_then(|span| {
+            span.scope().find_map(|span| {
+                span.extensions()
+                    .get::<IndexerLogLevel>()
+                    .map(|level| level.0)
+            })
+        });
+
+        match level {
+            Some(level) => level >= *metadata.level(),
+            None => true,
+        }
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if attrs.metadata().name() != "indexer" {
+            return;
+        }
+
+        let mut visitor = IndexerLogLevelVisitor(None);
+        attrs.record(&mut visitor);
+
+        if let (Some(level), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(IndexerLogLevel(level));
+        }
+    }
+}
+
+/// Apply the log level of the given config to the global log subscriber.
+///
+/// This has no effect if logging has not been initialized with `init_logging`.
+pub fn reload_logging(config: &IndexerConfig) -> Result<()> {
+    let directives = if config.verbose {
+        config.log_level.clone()
+    } else {
+        format!(
+            "{},wasmer_compiler_cranelift=warn,regalloc=warn,cranelift_codegen=warn",
+            config.log_level
+        )
+    };
+
+    match LOG_FILTER_RELOADER
+        .lock()
+        .expect("Log filter reloader lock poisoned.")
+        .as_ref()
+    {
+        Some(reload) => reload(EnvFilter::try_new(directives)?),
+        None => Ok(()),
+    }
+}
+
 /// Format a SQL query for logging.
 pub fn format_sql_query(s: String) -> String {
     s.replace('\n', " ")
@@ -5,6 +5,7 @@ lazy_static! {
 
     /// Set of internal indexer entities.
     pub static ref INTERNAL_INDEXER_ENTITIES: HashSet<&'static str> = HashSet::from([
+        "BlockMetadataEntity",
         "IndexMetadataEntity",
     ]);
 
@@ -74,10 +75,13 @@ lazy_static! {
         "Call",
         "Log",
         "LogData",
+        "MessageIn",
         "MessageOut",
         "Panic",
+        "PredicateSpend",
         "Return",
         "Revert",
+        "ScriptCall",
         "ScriptResult",
         "Transfer",
         "TransferOut",
@@ -151,6 +155,7 @@ lazy_static! {
         "Color",
         "ContractId",
         "HexString",
+        "I128",
         "ID",
         "Identity",
         "Int1",
@@ -166,6 +171,7 @@ lazy_static! {
         "Tai64Timestamp",
         "Timestamp",
         "TxId",
+        "U128",
         "UInt1",
         "UInt16",
         "UInt4",
@@ -184,9 +190,12 @@ lazy_static! {
         "LogData",
         "Maturity",
         "MessageId",
+        "MessageIn",
         "Outputs",
+        "PredicateSpend",
         "ReceiptsRoot",
         "Script",
+        "ScriptCall",
         "ScriptData",
         "ScriptResult",
         "StorageSlots",
@@ -199,3 +208,9 @@ lazy_static! {
         "Witnesses",
     ]);
 }
+
+/// Offset between the type IDs of consecutive ABIs, when an indexer uses several ABIs.
+pub const ABI_TYPE_ID_OFFSET: usize = 1 << 16;
+
+/// Namespace of the type IDs of user-defined types deriving `IndexerJson`.
+pub const JSON_TYPES_NAMESPACE: &str = "json";
@@ -478,6 +478,9 @@ pub struct ObjectDecoder {
     /// Tokens used to create fields in the `Entity::to_row` function.
     to_row: TokenStream,
 
+    /// Tokens for the filter and ordering methods of the entity's query builder.
+    query_methods: TokenStream,
+
     /// Tokens for the parameters of the `Entity::new` function.
     impl_decoder: ImplementationDecoder,
 
@@ -486,6 +489,9 @@ pub struct ObjectDecoder {
 
     /// The unique ID of this GraphQL type.
     type_id: i64,
+
+    /// Whether `Entity::save` runs the entity's `EntityHooks`.
+    hooks: bool,
 }
 
 impl Default for ObjectDecoder {
@@ -496,9 +502,11 @@ impl Default for ObjectDecoder {
             field_extractors: quote! {},
             from_row: quote! {},
             to_row: quote! {},
+            query_methods: quote! {},
             exec_source: ExecutionSource::Wasm,
             impl_decoder: ImplementationDecoder::default(),
             type_id: std::i64::MAX,
+            hooks: false,
         }
     }
 }
@@ -519,6 +527,7 @@ impl Decoder for ObjectDecoder {
                 let mut field_extractors = quote! {};
                 let mut from_row = quote! {};
                 let mut to_row = quote! {};
+                let mut query_methods = quote! {};
 
                 let mut fields_map = BTreeMap::new();
 
@@ -570,6 +579,14 @@ impl Decoder for ObjectDecoder {
                         #to_row
                         #field_decoder
                     };
+
+                    let field_query_methods =
+                        query_method_tokens(&field_name_ident, &processed_type_result);
+
+                    query_methods = quote! {
+                        #query_methods
+                        #field_query_methods
+                    };
                 }
 
                 Self {
@@ -578,9 +595,11 @@ impl Decoder for ObjectDecoder {
                     field_extractors,
                     from_row,
                     to_row,
+                    query_methods,
                     exec_source: parsed.exec_source().clone(),
                     impl_decoder: ImplementationDecoder::from_typedef(typ, parsed),
                     type_id,
+                    hooks: has_entity_hooks(typ),
                 }
             }
             TypeKind::Union(u) => {
@@ -737,12 +756,17 @@ impl From<ObjectDecoder> for TokenStream {
             field_extractors,
             from_row,
             to_row,
+            query_methods,
             impl_decoder,
             exec_source,
             type_id,
+            hooks,
             ..
         } = decoder;
 
+        let query_ident = format_ident!("{ident}Query");
+        let query_doc = format!("Typed query builder for `{ident}` entities.");
+
         let impl_json = quote! {
 
             impl From<#ident> for Json {
@@ -798,6 +822,65 @@ impl From<ObjectDecoder> for TokenStream {
             quote! { None }
         };
 
+        let save_failed = format!("Failed to save {ident}: {{e}}");
+        let load_failed = format!("Failed to load {ident}: {{e}}");
+        let find_failed = format!("Failed to find {ident}: {{e}}");
+
+        let native_put = |entity: TokenStream| {
+            quote! {
+                unsafe {
+                    match &db {
+                        Some(d) => {
+                            #entity.save_many_to_many().await;
+                            // Failed writes are reverted by the executor once the
+                            // handlers return.
+                            if let Err(e) = d.lock().await.put_object(
+                                Self::TYPE_ID,
+                                #entity.to_row(),
+                                serialize(&#entity.to_row())
+                            ).await {
+                                Logger::error(&format!(#save_failed));
+                            }
+                        }
+                        None => {},
+                    }
+                }
+            }
+        };
+
+        let before_save_failed = format!("Not saving {ident}: {{e}}");
+
+        let native_save = if hooks {
+            let put = native_put(quote! { entity });
+            quote! {
+                let mut entity = Self::from_row(self.to_row());
+                if let Err(e) = entity.before_save() {
+                    Logger::error(&format!(#before_save_failed));
+                    return;
+                }
+                #put
+                entity.after_save();
+            }
+        } else {
+            native_put(quote! { self })
+        };
+
+        let wasm_save = if hooks {
+            quote! {
+                fn save(&self) {
+                    let mut entity = Self::from_row(self.to_row());
+                    if let Err(e) = entity.before_save() {
+                        Logger::error(&format!(#before_save_failed));
+                        return;
+                    }
+                    entity.put();
+                    entity.after_save();
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         let impl_entity = match exec_source {
             ExecutionSource::Native => quote! {
                 #[derive(Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -837,7 +920,9 @@ impl From<ObjectDecoder> for TokenStream {
                                             .map(|query| query.to_string())
                                             .collect::<Vec<_>>();
 
-                                        d.lock().await.put_many_to_many_record(queries).await;
+                                        if let Err(e) = d.lock().await.put_many_to_many_record(queries).await {
+                                            Logger::error(&format!(#save_failed));
+                                        }
                                     }
                                 }
                                 None => {}
@@ -850,12 +935,16 @@ impl From<ObjectDecoder> for TokenStream {
                             match &db {
                                 Some(d) => {
                                     match d.lock().await.get_object(Self::TYPE_ID, id).await {
-                                        Some(bytes) => {
+                                        Ok(Some(bytes)) => {
                                             let columns: Vec<FtColumn> = bincode::deserialize(&bytes).expect("Failed to deserialize Vec<FtColumn> for Entity::load.");
                                             let obj = Self::from_row(columns);
                                             Some(obj)
                                         },
-                                        None => None,
+                                        Ok(None) => None,
+                                        Err(e) => {
+                                            Logger::error(&format!(#load_failed));
+                                            None
+                                        }
                                     }
                                 }
                                 None => None,
@@ -863,21 +952,70 @@ impl From<ObjectDecoder> for TokenStream {
                         }
                     }
 
-                    async fn save(&self) {
+                    async fn find_many(query: EntityQuery) -> Vec<Self> {
                         unsafe {
                             match &db {
                                 Some(d) => {
-                                    self.save_many_to_many().await;
-                                    d.lock().await.put_object(
-                                        Self::TYPE_ID,
-                                        self.to_row(),
-                                        serialize(&self.to_row())
-                                    ).await;
+                                    d.lock()
+                                        .await
+                                        .find_objects(Self::TYPE_ID, &query)
+                                        .await
+                                        .unwrap_or_else(|e| {
+                                            Logger::error(&format!(#find_failed));
+                                            Vec::new()
+                                        })
+                                        .iter()
+                                        .map(|bytes| {
+                                            let columns: Vec<FtColumn> = bincode::deserialize(bytes).expect("Failed to deserialize Vec<FtColumn> for Entity::find_many.");
+                                            Self::from_row(columns)
+                                        })
+                                        .collect()
                                 }
-                                None => {},
+                                None => Vec::new(),
                             }
                         }
                     }
+
+                    async fn save(&self) {
+                        #native_save
+                    }
+                }
+
+                impl #ident {
+                    /// Create a query for entities of this type.
+                    pub fn query() -> #query_ident {
+                        #query_ident { query: EntityQuery::default() }
+                    }
+                }
+
+                #[doc = #query_doc]
+                pub struct #query_ident {
+                    query: EntityQuery,
+                }
+
+                impl #query_ident {
+                    #query_methods
+
+                    pub fn limit(mut self, limit: u64) -> Self {
+                        self.query.limit = Some(limit);
+                        self
+                    }
+
+                    pub fn offset(mut self, offset: u64) -> Self {
+                        self.query.offset = Some(offset);
+                        self
+                    }
+
+                    /// Load all entities that match the query.
+                    pub async fn find_many(self) -> Vec<#ident> {
+                        #ident::find_many(self.query).await
+                    }
+
+                    /// Load the first entity that matches the query.
+                    pub async fn find(mut self) -> Option<#ident> {
+                        self.query.limit = Some(1);
+                        #ident::find_many(self.query).await.into_iter().next()
+                    }
                 }
             },
             ExecutionSource::Wasm => quote! {
@@ -903,6 +1041,44 @@ impl From<ObjectDecoder> for TokenStream {
                         ]
                     }
 
+                    #wasm_save
+                }
+
+                impl #ident {
+                    /// Create a query for entities of this type.
+                    pub fn query() -> #query_ident {
+                        #query_ident { query: EntityQuery::default() }
+                    }
+                }
+
+                #[doc = #query_doc]
+                pub struct #query_ident {
+                    query: EntityQuery,
+                }
+
+                impl #query_ident {
+                    #query_methods
+
+                    pub fn limit(mut self, limit: u64) -> Self {
+                        self.query.limit = Some(limit);
+                        self
+                    }
+
+                    pub fn offset(mut self, offset: u64) -> Self {
+                        self.query.offset = Some(offset);
+                        self
+                    }
+
+                    /// Load all entities that match the query.
+                    pub fn find_many(self) -> Vec<#ident> {
+                        #ident::find_many(self.query)
+                    }
+
+                    /// Load the first entity that matches the query.
+                    pub fn find(mut self) -> Option<#ident> {
+                        self.query.limit = Some(1);
+                        #ident::find_many(self.query).into_iter().next()
+                    }
                 }
             },
         };
@@ -1039,6 +1215,10 @@ type Person @entity {
         assert!(tokenstream.contains("pub fn get_or_create (self) -> Self"));
         assert!(tokenstream.contains("fn from_row (mut vec : Vec < FtColumn >) -> Self"));
         assert!(tokenstream.contains("fn to_row (& self) -> Vec < FtColumn >"));
+        assert!(tokenstream.contains("pub struct PersonQuery"));
+        assert!(tokenstream.contains("pub fn age_gt (mut self , value : UInt1) -> Self"));
+        assert!(tokenstream.contains("pub fn order_by_name_desc (mut self) -> Self"));
+        assert!(!tokenstream.contains("before_save"));
     }
 
     #[test]
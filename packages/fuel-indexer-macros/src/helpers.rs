@@ -1,9 +1,9 @@
 use std::collections::HashSet;
 
 use crate::constants::*;
-use async_graphql_parser::types::{BaseType, FieldDefinition, Type};
-use async_graphql_value::Name;
-use fuel_abi_types::abi::program::{ProgramABI, TypeDeclaration};
+use async_graphql_parser::types::{BaseType, FieldDefinition, Type, TypeDefinition};
+use async_graphql_value::{ConstValue, Name};
+use fuel_abi_types::abi::program::{ProgramABI, TypeApplication, TypeDeclaration};
 use fuel_indexer_lib::graphql::{
     list_field_type_name, types::IdCol, ParsedGraphQLSchema,
 };
@@ -12,6 +12,18 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 use syn::Ident;
 
+/// Whether the `@entity` directive of the given type definition enables `EntityHooks`,
+/// i.e., `@entity(hooks: true)`.
+pub fn has_entity_hooks(typ: &TypeDefinition) -> bool {
+    typ.directives
+        .iter()
+        .filter(|d| d.node.name.node.as_str() == "entity")
+        .flat_map(|d| d.node.arguments.iter())
+        .any(|(name, value)| {
+            name.node.as_str() == "hooks" && value.node == ConstValue::Boolean(true)
+        })
+}
+
 /// Provides a TokenStream to be used for unwrapping `Option`s for external types.
 ///
 /// This is done because traits cannot be implemented on external types due to the orphan rule.
@@ -78,6 +90,42 @@ pub fn get_json_abi(abi_path: Option<String>) -> Option<ProgramABI> {
     }
 }
 
+/// Offset all type IDs in a `ProgramABI`, so that the types of several ABIs can be
+/// decoded by the same indexer without their IDs colliding.
+pub fn offset_abi_type_ids(abi: &mut ProgramABI, offset: usize) {
+    fn offset_application(application: &mut TypeApplication, offset: usize) {
+        application.type_id += offset;
+        for arg in application.type_arguments.iter_mut().flatten() {
+            offset_application(arg, offset);
+        }
+    }
+
+    for typ in abi.types.iter_mut() {
+        typ.type_id += offset;
+        for component in typ.components.iter_mut().flatten() {
+            offset_application(component, offset);
+        }
+        for param in typ.type_parameters.iter_mut().flatten() {
+            *param += offset;
+        }
+    }
+
+    for function in abi.functions.iter_mut() {
+        for input in function.inputs.iter_mut() {
+            offset_application(input, offset);
+        }
+        offset_application(&mut function.output, offset);
+    }
+
+    for typ in abi.logged_types.iter_mut().flatten() {
+        offset_application(&mut typ.application, offset);
+    }
+
+    for typ in abi.messages_types.iter_mut().flatten() {
+        offset_application(&mut typ.application, offset);
+    }
+}
+
 /// Whether this TypeDeclaration should be used in the codgen
 pub fn is_ignored_type(typ: &TypeDeclaration) -> bool {
     is_tuple_type(typ)
@@ -155,8 +203,11 @@ pub fn rust_type_token(ty: &TypeDeclaration) -> proc_macro2::TokenStream {
             "Identity" => quote! { Identity },
             "Log" => quote! { Log },
             "LogData" => quote! { LogData },
+            "MessageIn" => quote! { MessageIn },
             "MessageOut" => quote! { MessageOut },
+            "PredicateSpend" => quote! { PredicateSpend },
             "Return" => quote! { Return },
+            "ScriptCall" => quote! { ScriptCall },
             "ScriptResult" => quote! { ScriptResult },
             "Transfer" => quote! { Transfer },
             "TransferOut" => quote! { TransferOut },
@@ -179,6 +230,24 @@ pub fn is_fuel_primitive(ty: &proc_macro2::TokenStream) -> bool {
     FUEL_PRIMITIVES.contains(ident_str.as_str())
 }
 
+/// Whether or not the given handler argument type is `&mut BlockContext`
+pub fn is_block_context_type(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(reference) if reference.mutability.is_some() => {
+            match &*reference.elem {
+                syn::Type::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident == "BlockContext")
+                    .unwrap_or(false),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
 /// Whether or not the given token is a Rust primitive
 pub fn is_rust_primitive(ty: &proc_macro2::TokenStream) -> bool {
     let ident_str = ty.to_string();
@@ -192,11 +261,13 @@ pub fn decode_snippet(
     ty: &proc_macro2::TokenStream,
     name: &Ident,
 ) -> proc_macro2::TokenStream {
+    let key = name.to_string();
     if is_fuel_primitive(ty) {
         quote! {
             #ty_id => {
                 let obj: #ty = bincode::deserialize(&data).expect("Bad bincode.");
                 self.#name.push(obj);
+                self.push_contract_id(#key);
             }
         }
     } else if is_rust_primitive(ty) {
@@ -211,6 +282,7 @@ pub fn decode_snippet(
                 let decoded = ABIDecoder::decode_single(&#ty::param_type(), &data).expect("Failed decoding.");
                 let obj = #ty::from_token(decoded).expect("Failed detokenizing.");
                 self.#name.push(obj);
+                self.push_contract_id(#key);
             }
         }
     }
@@ -692,6 +764,64 @@ pub fn field_decoder_tokens(
     }
 }
 
+/// Get tokens for the query builder methods of a field: a method for each `Comparison`,
+/// e.g. `amount_gt(value)`, and methods ordering by the field.
+///
+/// List and JSON fields can't be compared, so no methods are generated for them.
+pub fn query_method_tokens(
+    field_name: &Ident,
+    processed_type_result: &ProcessedFieldType,
+) -> TokenStream {
+    let ProcessedFieldType {
+        field_type_tokens,
+        field_type_ident,
+        base_type,
+        nullable,
+        ..
+    } = processed_type_result;
+
+    if matches!(base_type, FieldBaseType::List)
+        || ["Json", "Virtual"].contains(&field_type_ident.to_string().as_str())
+    {
+        return quote! {};
+    }
+
+    let column = field_name.to_string();
+    let value = if *nullable {
+        quote! { FtColumn::#field_type_ident(value) }
+    } else {
+        quote! { FtColumn::#field_type_ident(Some(value)) }
+    };
+
+    let comparisons = ["Eq", "Ne", "Gt", "Ge", "Lt", "Le"].iter().map(|comparison| {
+        let method = format_ident! {"{}_{}", field_name, comparison.to_lowercase()};
+        let comparison = format_ident! {"{}", comparison};
+        quote! {
+            pub fn #method(mut self, value: #field_type_tokens) -> Self {
+                self.query.filters.push(Filter::new(#column, Comparison::#comparison, #value));
+                self
+            }
+        }
+    });
+
+    let order_by_asc = format_ident! {"order_by_{}_asc", field_name};
+    let order_by_desc = format_ident! {"order_by_{}_desc", field_name};
+
+    quote! {
+        #(#comparisons)*
+
+        pub fn #order_by_asc(mut self) -> Self {
+            self.query.order_by = Some((#column.to_string(), false));
+            self
+        }
+
+        pub fn #order_by_desc(mut self) -> Self {
+            self.query.order_by = Some((#column.to_string(), true));
+            self
+        }
+    }
+}
+
 /// Whether a given field is eligible for autogenerated ID, where the ID
 /// will be derived from the struct's field's values.
 pub fn can_derive_id(field_set: &HashSet<String>, field_name: &str) -> bool {
@@ -1,11 +1,19 @@
 use crate::{
-    constants::*, helpers::*, native::handler_block_native, parse::IndexerConfig,
-    schema::process_graphql_schema, wasm::handler_block_wasm,
+    constants::*,
+    helpers::*,
+    native::handler_block_native,
+    parse::{HandlerConfig, IndexerConfig, HANDLER_ATTRIBUTE},
+    schema::process_graphql_schema,
+    validation::{check_contract_templates, check_handler_entities, module_type_names},
+    wasm::handler_block_wasm,
 };
 use fuel_abi_types::abi::program::TypeDeclaration;
 use fuel_indexer_lib::{
-    graphql::GraphQLSchemaValidator, manifest::ContractIds, manifest::Manifest, type_id,
-    utils::local_repository_root, ExecutionSource,
+    graphql::{GraphQLSchemaValidator, ParsedGraphQLSchema},
+    manifest::{local_abi_path, remote_abi_url, ContractIds, Manifest},
+    type_id,
+    utils::local_repository_root,
+    ExecutionSource,
 };
 use fuel_indexer_types::FUEL_TYPES_NAMESPACE;
 use fuels::{core::codec::resolve_fn_selector, types::param_types::ParamType};
@@ -14,11 +22,12 @@ use proc_macro::TokenStream;
 use quote::quote;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use syn::{parse_macro_input, FnArg, Item, ItemMod, PatType, Type};
+use syn::{parse_macro_input, FnArg, Ident, Item, ItemMod, PatType, Type};
 
 fn process_fn_items(
     manifest: &Manifest,
-    abi_path: Option<String>,
+    schema: &ParsedGraphQLSchema,
+    abi_paths: Vec<String>,
     indexer_module: ItemMod,
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
     if indexer_module.content.is_none()
@@ -34,15 +43,41 @@ fn process_fn_items(
         )
     }
 
-    let abi = get_json_abi(abi_path);
+    // Logs and messages are decoded with the ABI of the contract that emitted
+    // them, so each ABI must be paired with a contract ID. Template ABIs follow
+    // the ABIs of the manifest's contracts in `abi_paths`.
+    let main_abi_count = manifest.abis().len();
+    let multiple_abis = main_abi_count > 1;
+    if multiple_abis {
+        match manifest.contract_id() {
+            ContractIds::Multiple(contract_ids) if contract_ids.len() == main_abi_count => {}
+            _ => proc_macro_error::abort_call_site!(
+                "When multiple ABIs are specified, `contract_id` must list one contract ID per ABI."
+            ),
+        }
+    }
+
+    let abis = abi_paths
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, abi_path)| {
+            let mut abi = get_json_abi(Some(abi_path))?;
+            offset_abi_type_ids(&mut abi, i * ABI_TYPE_ID_OFFSET);
+            Some(abi)
+        })
+        .collect::<Vec<_>>();
 
     let mut decoded_abi_types = HashSet::new();
-    let mut abi_dispatchers = Vec::new();
+    let mut abi_dispatchers: Vec<HandlerDispatcher> = Vec::new();
 
-    let funcs = abi.clone().unwrap_or_default().functions;
-    let abi_types = abi.clone().unwrap_or_default().types;
-    let abi_log_types = abi.clone().unwrap_or_default().logged_types;
-    let abi_msg_types = abi.unwrap_or_default().messages_types;
+    let funcs = abis
+        .iter()
+        .flat_map(|abi| abi.functions.clone())
+        .collect::<Vec<_>>();
+    let abi_types = abis
+        .iter()
+        .flat_map(|abi| abi.types.clone())
+        .collect::<Vec<_>>();
     let fuel_types = FUEL_PRIMITIVES
         .iter()
         .map(|x| {
@@ -73,37 +108,51 @@ fn process_fn_items(
         .map(|typ| (typ.type_id, typ.clone()))
         .collect::<HashMap<usize, TypeDeclaration>>();
 
-    let log_type_decoders = abi_log_types
+    // Handlers are called once for each value of a logged type, rather than once
+    // per block, so that every event logged in a block is handled.
+    let logged_type_names = abis
         .iter()
-        .flatten()
-        .map(|typ| {
-            let ty_id = typ.application.type_id;
-            let log_id = typ.log_id as usize;
+        .flat_map(|abi| abi.logged_types.iter().flatten())
+        .filter_map(|typ| abi_types_tyid.get(&typ.application.type_id))
+        .filter(|typ| !is_non_decodable_type(typ))
+        .map(|typ| typ.rust_type_token().to_string())
+        .collect::<HashSet<String>>();
 
-            quote! {
-                #log_id => {
-                    self.decode_type(#ty_id, data);
+    let log_type_decoders = abis
+        .iter()
+        .enumerate()
+        .flat_map(|(abi_index, abi)| {
+            abi.logged_types.iter().flatten().map(move |typ| {
+                let ty_id = typ.application.type_id;
+                let log_id = typ.log_id as usize;
+
+                quote! {
+                    (#abi_index, #log_id) => {
+                        self.decode_type(#ty_id, data);
+                    }
                 }
-            }
+            })
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
-    let message_types_decoders = abi_msg_types
+    let message_types_decoders = abis
         .iter()
-        .flatten()
-        .map(|typ| {
-            let message_type_id = typ.message_id;
-            let ty_id = typ.application.type_id;
+        .enumerate()
+        .flat_map(|(abi_index, abi)| {
+            abi.messages_types.iter().flatten().map(move |typ| {
+                let message_type_id = typ.message_id;
+                let ty_id = typ.application.type_id;
 
-            quote! {
-                #message_type_id => {
-                    self.decode_type(#ty_id, data);
+                quote! {
+                    (#abi_index, #message_type_id) => {
+                        self.decode_type(#ty_id, data);
+                    }
                 }
-            }
+            })
         })
         .chain(
             vec![quote! {
-                u64::MAX => {
+                (_, u64::MAX) => {
                     {}
                 }
             }]
@@ -149,6 +198,8 @@ fn process_fn_items(
 
     let decoders = [fuel_type_decoders, abi_type_decoders].concat();
 
+    // Types shared between several ABIs only need a single field.
+    let mut abi_struct_field_names = HashSet::new();
     let abi_struct_fields = abi_types
         .iter()
         .filter_map(|typ| {
@@ -159,6 +210,10 @@ fn process_fn_items(
             let name = typ.rust_type_ident();
             let ty = typ.rust_type_token();
 
+            if !abi_struct_field_names.insert(name.to_string()) {
+                return None;
+            }
+
             if is_fuel_primitive(&ty) {
                 proc_macro_error::abort_call_site!("'{}' is a reserved Fuel type.", ty)
             }
@@ -260,27 +315,92 @@ fn process_fn_items(
                 .iter()
                 .map(|id| {
                     quote! {
-                        let id_bytes = <[u8; 32]>::try_from(ContractId::from(#id)).expect("Could not convert contract ID into bytes");
-                        Bech32ContractId::new("fuel", id_bytes)
+                        {
+                            let id_bytes = <[u8; 32]>::from(ContractId::from(#id));
+                            Bech32ContractId::new("fuel", id_bytes)
+                        }
                     }
                 })
                 .collect::<Vec<proc_macro2::TokenStream>>();
 
+            let contract_abi_indexes = if multiple_abis {
+                let abi_indexes = 0..contract_ids.len();
+                quote! {
+                    let contract_abi_indexes: HashMap<Bech32ContractId, usize> = HashMap::from([#((#contract_ids, #abi_indexes)),*]);
+                }
+            } else {
+                quote! {}
+            };
+
             quote! {
                 let contract_ids = HashSet::from([#(#contract_ids),*]);
+                #contract_abi_indexes
             }
         }
     };
 
+    let templates = manifest.templates();
+    let has_templates = !templates.is_empty();
+
+    // Index of the ABI used to decode the logs and messages of a receipt's contract.
+    let (log_abi_index, message_abi_index) = if multiple_abis || has_templates {
+        let manifest_abi_index = if multiple_abis {
+            quote! { contract_abi_indexes.get(&abi_contract_id).copied() }
+        } else {
+            quote! { None::<usize> }
+        };
+        let discovered_abi_index = if has_templates {
+            quote! {
+                .or_else(|| discovered_contracts_lock().as_ref().and_then(|contracts| contracts.get(&abi_contract_id).copied()))
+            }
+        } else {
+            quote! {}
+        };
+        let default_abi_index = if multiple_abis {
+            quote! { usize::MAX }
+        } else {
+            quote! { 0 }
+        };
+
+        (
+            quote! {
+                let abi_index = {
+                    let id_bytes = <[u8; 32]>::from(id);
+                    let abi_contract_id = Bech32ContractId::new("fuel", id_bytes);
+                    #manifest_abi_index #discovered_abi_index .unwrap_or(#default_abi_index)
+                };
+            },
+            quote! {
+                let abi_index = {
+                    let sender_bytes = <[u8; 32]>::from(sender.clone());
+                    let abi_contract_id = Bech32ContractId::new("fuel", sender_bytes);
+                    #manifest_abi_index #discovered_abi_index .unwrap_or(#default_abi_index)
+                };
+            },
+        )
+    } else {
+        (quote! { let abi_index = 0; }, quote! { let abi_index = 0; })
+    };
+
+    // Contracts registered against a template at runtime are indexed alongside
+    // the contracts listed in the manifest.
+    let is_discovered_contract = if has_templates {
+        quote! {
+            && !discovered_contracts_lock().as_ref().map_or(false, |contracts| contracts.contains_key(&bech32_id))
+        }
+    } else {
+        quote! {}
+    };
+
     let check_if_subscribed_to_contract = match &manifest.contract_id() {
         ContractIds::Single(contract_id) => match contract_id {
             Some(contract_id) => {
                 quote! {
                     // TODO: Temporary conversion; remove once we update back to latest fuel-types version.
-                    let id_bytes = <[u8; 32]>::try_from(id).expect("Could not convert contract ID into bytes");
+                    let id_bytes = <[u8; 32]>::from(id);
                     let bech32_id = Bech32ContractId::new("fuel", id_bytes);
                     let manifest_contract_id = Bech32ContractId::from_str(#contract_id).expect("Failed to parse manifest 'contract_id' as Bech32ContractId");
-                    if bech32_id != manifest_contract_id {
+                    if bech32_id != manifest_contract_id #is_discovered_contract {
                         debug!("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
                         continue;
                     }
@@ -291,10 +411,10 @@ fn process_fn_items(
         ContractIds::Multiple(_) => {
             quote! {
                 // TODO: Temporary conversion; remove once we update back to latest fuel-types version.
-                let id_bytes = <[u8; 32]>::try_from(id).expect("Could not convert contract ID into bytes");
+                let id_bytes = <[u8; 32]>::from(id);
                 let bech32_id = Bech32ContractId::new("fuel", id_bytes);
 
-                if !contract_ids.contains(&bech32_id) {
+                if !contract_ids.contains(&bech32_id) #is_discovered_contract {
                     debug!("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
                     continue;
                 }
@@ -302,13 +422,238 @@ fn process_fn_items(
         }
     };
 
+    let predicate_roots = manifest.predicates();
+    let check_if_subscribed_to_predicate = if predicate_roots.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            let subscribed = [#(#predicate_roots),*].iter().any(|root| {
+                Address::from_str(root).expect("Failed to parse manifest predicate root as Address") == spend.predicate_root
+            });
+            if !subscribed {
+                debug!("Not subscribed to this predicate. Will skip this predicate spend. <('-'<)");
+                continue;
+            }
+        }
+    };
+
+    let script_hashes = manifest.scripts();
+    let is_subscribed_to_script = if script_hashes.is_empty() {
+        quote! { true }
+    } else {
+        quote! {
+            [#(#script_hashes),*].iter().any(|hash| {
+                Bytes32::from_str(hash).expect("Failed to parse manifest script hash as Bytes32") == script_call.script_hash
+            })
+        }
+    };
+
+    let message_recipients = manifest.message_recipients();
+    let check_if_subscribed_to_recipient = |recipient: proc_macro2::TokenStream| {
+        if message_recipients.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                let subscribed = [#(#message_recipients),*].iter().any(|recipient| {
+                    Address::from_str(recipient).expect("Failed to parse manifest message recipient as Address") == #recipient
+                });
+                if !subscribed {
+                    debug!("Not subscribed to this message recipient. Will skip this message. <('-'<)");
+                    continue;
+                }
+            }
+        }
+    };
+    let check_if_subscribed_to_message_in =
+        check_if_subscribed_to_recipient(quote! { message.recipient });
+    let check_if_subscribed_to_message_out = check_if_subscribed_to_recipient(
+        quote! { Address::from(<[u8; 32]>::from(recipient.clone())) },
+    );
+
     let (asyncness, awaitness) = manifest.execution_source().async_awaitness();
 
+    // Template registration functions that handlers can call, and the loading of
+    // previously discovered contracts when the indexer (re)starts.
+    let (template_fns, load_discovered_contracts) = if has_templates {
+        let template_names = templates
+            .iter()
+            .map(|template| template.name.as_str())
+            .collect::<Vec<_>>();
+        let template_abi_indexes = (0..templates.len())
+            .map(|i| main_abi_count + i)
+            .collect::<Vec<_>>();
+        let default_template = template_names[0];
+
+        let (persist_contract, discovered_contracts) = match manifest.execution_source() {
+            ExecutionSource::Native => (
+                quote! {
+                    if let Some(d) = unsafe { &db } {
+                        if let Err(e) = d.lock().await.register_contract(template.to_string(), bech32_id.to_string()).await {
+                            Logger::error(&format!("Failed to register contract({bech32_id}): {e}"));
+                        }
+                    }
+                },
+                quote! {
+                    match unsafe { &db } {
+                        Some(d) => d.lock().await.discovered_contracts().await?,
+                        None => Vec::new(),
+                    }
+                },
+            ),
+            ExecutionSource::Wasm => (
+                quote! {
+                    register_discovered_contract(template, &bech32_id.to_string());
+                },
+                quote! { discovered_contracts() },
+            ),
+        };
+
+        (
+            quote! {
+                // Contracts discovered at runtime, mapped to the index of their template's ABI.
+                // Transactions handled in parallel register contracts concurrently.
+                static DISCOVERED_CONTRACTS: std::sync::Mutex<Option<HashMap<Bech32ContractId, usize>>> =
+                    std::sync::Mutex::new(None);
+
+                // Whether contracts were registered since the discovered contracts were
+                // loaded. Registrations are only persisted if their transaction commits.
+                static DISCOVERED_CONTRACTS_CHANGED: std::sync::atomic::AtomicBool =
+                    std::sync::atomic::AtomicBool::new(false);
+
+                /// Lock the contracts discovered at runtime. The lock is never held while
+                /// handlers run, so a poisoned lock still guards a consistent map.
+                fn discovered_contracts_lock() -> std::sync::MutexGuard<'static, Option<HashMap<Bech32ContractId, usize>>> {
+                    DISCOVERED_CONTRACTS.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+                }
+
+                /// Index the given contract using the ABI of the named template, starting
+                /// with the next receipt.
+                ///
+                /// Templates are checked against the manifest when the indexer is built.
+                #[allow(dead_code)]
+                #asyncness fn register_contract_with_template(template: &str, id: ContractId) {
+                    let abi_index = match template {
+                        #(#template_names => #template_abi_indexes,)*
+                        _ => {
+                            Logger::error(&format!("Unknown contract template({template}), not registering contract."));
+                            return;
+                        }
+                    };
+
+                    let id_bytes = <[u8; 32]>::from(id);
+                    let bech32_id = Bech32ContractId::new("fuel", id_bytes);
+
+                    #persist_contract
+
+                    discovered_contracts_lock().get_or_insert_with(HashMap::new).insert(bech32_id, abi_index);
+                    DISCOVERED_CONTRACTS_CHANGED.store(true, std::sync::atomic::Ordering::SeqCst);
+                }
+
+                /// Index the given contract using the ABI of the first template, starting
+                /// with the next receipt.
+                #[allow(dead_code)]
+                #asyncness fn register_contract(id: ContractId) {
+                    register_contract_with_template(#default_template, id)#awaitness;
+                }
+            },
+            quote! {
+                // Once contracts have been registered, they're reloaded from the database,
+                // so that those registered by a page that was reverted are dropped.
+                let changed = DISCOVERED_CONTRACTS_CHANGED.swap(false, std::sync::atomic::Ordering::SeqCst);
+                let loaded = discovered_contracts_lock().is_some();
+                if !loaded || changed {
+                    let mut contracts = HashMap::new();
+                    for (template, contract_id) in #discovered_contracts {
+                        let abi_index = match template.as_str() {
+                            #(#template_names => #template_abi_indexes,)*
+                            _ => {
+                                warn!("Skipping discovered contract({contract_id}) with unknown template({template}).");
+                                continue;
+                            }
+                        };
+                        let bech32_id = Bech32ContractId::from_str(&contract_id).expect("Failed to parse discovered contract ID as Bech32ContractId");
+                        contracts.insert(bech32_id, abi_index);
+                    }
+
+                    *discovered_contracts_lock() = Some(contracts);
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    let parallel_transactions = manifest.parallel_transactions().unwrap_or(false);
+    if parallel_transactions
+        && matches!(manifest.execution_source(), ExecutionSource::Wasm)
+    {
+        proc_macro_error::abort_call_site!(
+            "`parallel_transactions` is only supported for native execution."
+        )
+    }
+
+    if has_templates {
+        let template_names = templates
+            .iter()
+            .map(|template| template.name.as_str())
+            .collect::<Vec<_>>();
+        check_contract_templates(&contents, &template_names);
+    }
+
+    let module_types = module_type_names(&contents);
+
     for item in contents {
         match item {
-            Item::Fn(fn_item) => {
+            Item::Fn(mut fn_item) => {
                 let mut input_checks = Vec::new();
                 let mut arg_list = Vec::new();
+                let mut takes_block_data = false;
+                let mut takes_transaction_data = false;
+                let mut iterated_arg = None;
+
+                let HandlerConfig {
+                    priority,
+                    every_n_blocks,
+                    every_seconds,
+                    contract,
+                    log_type,
+                } = HandlerConfig::from_attrs(&fn_item.attrs).unwrap_or_else(|e| {
+                    proc_macro_error::abort_call_site!(
+                        "Could not parse handler attribute: {:?}.",
+                        e
+                    )
+                });
+                fn_item
+                    .attrs
+                    .retain(|attr| !attr.path().is_ident(HANDLER_ATTRIBUTE));
+
+                check_handler_entities(&fn_item, schema, module_types.as_ref());
+
+                // With a contract filter, handler arguments are taken from copies of
+                // the decoded values that only hold the values of that contract.
+                let mut filtered_names: Vec<Ident> = Vec::new();
+                let mut bind_filtered = |name: Ident| match &contract {
+                    Some(_) => {
+                        if !filtered_names.contains(&name) {
+                            filtered_names.push(name.clone());
+                        }
+                        quote! { #name }
+                    }
+                    None => quote! { self.#name },
+                };
+
+                if let Some(log_type) = &log_type {
+                    let log_type = log_type.to_string();
+                    if !logged_type_names.contains(&log_type) {
+                        proc_macro_error::abort_call_site!(
+                            "Handler log_type '{}' is not a logged type in the ABI.",
+                            log_type
+                        )
+                    }
+                    let values = bind_filtered(decoded_ident(&log_type));
+                    input_checks.push(quote! { #values.len() > 0 });
+                    takes_transaction_data = true;
+                }
 
                 for inp in &fn_item.sig.inputs {
                     match inp {
@@ -318,7 +663,9 @@ fn process_fn_items(
                             )
                         }
                         FnArg::Typed(PatType { ty, .. }) => {
-                            if let Type::Path(path) = &**ty {
+                            if is_block_context_type(ty) {
+                                arg_list.push(quote! { &mut *ctx });
+                            } else if let Type::Path(path) = &**ty {
                                 let path = path
                                     .path
                                     .segments
@@ -343,9 +690,23 @@ fn process_fn_items(
                                     )
                                 }
 
-                                input_checks.push(quote! { self.#name.len() > 0 });
+                                if path_ident == "BlockData" {
+                                    takes_block_data = true;
+                                } else {
+                                    takes_transaction_data = true;
+                                }
+
+                                let values = bind_filtered(name);
+                                input_checks.push(quote! { #values.len() > 0 });
 
-                                arg_list.push(quote! { self.#name[0].clone() });
+                                if iterated_arg.is_none()
+                                    && logged_type_names.contains(&path_ident)
+                                {
+                                    arg_list.push(quote! { item.clone() });
+                                    iterated_arg = Some(values);
+                                } else {
+                                    arg_list.push(quote! { #values[0].clone() });
+                                }
                             } else {
                                 proc_macro_error::abort_call_site!(
                                     "Arguments must be types defined in the ABI."
@@ -357,12 +718,54 @@ fn process_fn_items(
 
                 let fn_name = &fn_item.sig.ident;
 
-                abi_dispatchers.push(quote! {
-                    if ( #(#input_checks)&&* ) {
-                        #fn_name(#(#arg_list),*)#awaitness;
+                let filtered_bindings = contract.map(|contract| {
+                    let keys = filtered_names.iter().map(|name| name.to_string());
+                    quote! {
+                        let contract = ContractId::from_str(#contract).expect("Bad handler contract ID.");
+                        #(let #filtered_names = self.from_contract(#keys, &self.#filtered_names, &contract);)*
                     }
                 });
 
+                input_checks.extend(schedule_checks(every_n_blocks, every_seconds));
+
+                // Handlers that only take a `BlockContext` run for every block.
+                if input_checks.is_empty() {
+                    input_checks.push(quote! { true });
+                }
+
+                // When transactions are processed in parallel, each transaction is
+                // decoded separately from its block, so a handler can't take both.
+                if parallel_transactions && takes_block_data && takes_transaction_data {
+                    proc_macro_error::abort_call_site!(
+                        "Handler '{}' cannot take both `BlockData` and transaction data when `parallel_transactions` is enabled.",
+                        fn_name
+                    )
+                }
+
+                let call = match iterated_arg {
+                    Some(values) => quote! {
+                        for item in #values.iter() {
+                            #fn_name(#(#arg_list),*)#awaitness;
+                        }
+                    },
+                    None => quote! {
+                        #fn_name(#(#arg_list),*)#awaitness;
+                    },
+                };
+
+                abi_dispatchers.push((
+                    priority,
+                    takes_transaction_data,
+                    quote! {
+                        {
+                            #filtered_bindings
+                            if ( #(#input_checks)&&* ) {
+                                #call
+                            }
+                        }
+                    },
+                ));
+
                 handler_fns.push(fn_item);
             }
             i => {
@@ -374,13 +777,79 @@ fn process_fn_items(
         }
     }
 
+    order_by_priority(&mut abi_dispatchers);
+
+    let dispatch_fns = if parallel_transactions {
+        let (transaction_dispatchers, block_dispatchers): (Vec<_>, Vec<_>) =
+            abi_dispatchers
+                .into_iter()
+                .partition(|(_, takes_transaction_data, _)| *takes_transaction_data);
+        let transaction_dispatchers = transaction_dispatchers
+            .into_iter()
+            .map(|(_, _, dispatcher)| dispatcher);
+        let block_dispatchers = block_dispatchers
+            .into_iter()
+            .map(|(_, _, dispatcher)| dispatcher);
+
+        quote! {
+            #[allow(unused_variables)]
+            pub #asyncness fn dispatch_transaction(&self, ctx: &mut BlockContext) {
+                #(#transaction_dispatchers)*
+            }
+
+            #[allow(unused_variables)]
+            pub #asyncness fn dispatch_block(&self, ctx: &mut BlockContext) {
+                #(#block_dispatchers)*
+            }
+        }
+    } else {
+        let abi_dispatchers = abi_dispatchers
+            .into_iter()
+            .map(|(_, _, dispatcher)| dispatcher);
+
+        quote! {
+            #[allow(unused_variables)]
+            pub #asyncness fn dispatch(&self, ctx: &mut BlockContext) {
+                #(#abi_dispatchers)*
+            }
+        }
+    };
+
     let decoder_struct = quote! {
         #[derive(Default)]
         struct Decoders {
-            #(#decoder_struct_fields),*
+            #(#decoder_struct_fields),*,
+
+            /// Contract whose receipt is currently being decoded, if any.
+            current_contract: Option<ContractId>,
+
+            /// Contract of each decoded value, keyed by the name of its decoder field.
+            contract_ids: HashMap<&'static str, Vec<Option<ContractId>>>,
         }
 
         impl Decoders {
+            fn push_contract_id(&mut self, key: &'static str) {
+                let contract = self.current_contract;
+                self.contract_ids.entry(key).or_default().push(contract);
+            }
+
+            /// Values of a decoder field that were decoded from receipts of the given
+            /// contract. Values that weren't decoded from a contract's receipt are kept.
+            fn from_contract<T: Clone>(&self, key: &'static str, values: &[T], contract: &ContractId) -> Vec<T> {
+                let contract_ids = self.contract_ids.get(key);
+                values
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| {
+                        match contract_ids.and_then(|ids| ids.get(*i)).copied().flatten() {
+                            Some(id) => id == *contract,
+                            None => true,
+                        }
+                    })
+                    .map(|(_, value)| value.clone())
+                    .collect()
+            }
+
             fn selector_to_type_id(&self, sel: u64) -> usize {
                 match sel {
                     #(#abi_selectors)*
@@ -434,197 +903,354 @@ fn process_fn_items(
                 self.decode_type(ty_id, data);
             }
 
-            pub fn decode_logdata(&mut self, rb: usize, data: Vec<u8>) {
-                match rb {
+            pub fn decode_logdata(&mut self, abi_index: usize, rb: usize, data: Vec<u8>) {
+                match (abi_index, rb) {
                     #(#log_type_decoders),*
                     _ => debug!("Unknown logged type ID; check ABI to make sure that logged types are correct.")
                 }
             }
 
-            pub fn decode_messagedata(&mut self, type_id: u64, data: Vec<u8>) {
-                match type_id {
+            pub fn decode_messagedata(&mut self, abi_index: usize, type_id: u64, data: Vec<u8>) {
+                match (abi_index, type_id) {
                     #(#message_types_decoders),*
                     _ => debug!("Unknown message type ID; check ABI to make sure that message types are correct.")
                 }
             }
 
-            pub #asyncness fn dispatch(&self) {
-                #(#abi_dispatchers)*
+            #dispatch_fns
+        }
+    };
+
+    let transaction_decoder = quote! {
+        let mut return_types = Vec::new();
+        let mut callees = HashSet::new();
+        decoder.current_contract = None;
+
+        if let Some(script_call) = tx.script_call() {
+            if #is_subscribed_to_script {
+                let ty_id = ScriptCall::type_id();
+                let data = serialize(&script_call);
+                decoder.decode_type(ty_id, data);
+            } else {
+                debug!("Not subscribed to this script. Will skip this script call. <('-'<)");
+            }
+        }
+
+        for message in tx.messages_in() {
+            #check_if_subscribed_to_message_in
+            let ty_id = MessageIn::type_id();
+            let data = serialize(&message);
+            decoder.decode_type(ty_id, data);
+        }
+
+        for spend in tx.predicate_spends() {
+            #check_if_subscribed_to_predicate
+            let ty_id = PredicateSpend::type_id();
+            let data = serialize(&spend);
+            decoder.decode_type(ty_id, data);
+        }
+
+        for receipt in tx.receipts {
+            // Values decoded from this receipt are attributed to the contract that
+            // emitted it, or to the callee for calls.
+            decoder.current_contract = match &receipt {
+                fuel::Receipt::Call { to, .. } => Some(<[u8; 32]>::from(*to).into()),
+                receipt => receipt.id().map(|id| <[u8; 32]>::from(*id).into()),
+            };
+
+            match receipt {
+                fuel::Receipt::Call { id: contract_id, amount, asset_id, gas, param1, to: id, .. } => {
+                    #check_if_subscribed_to_contract
+
+                    let fn_name = decoder.selector_to_fn_name(param1);
+                    return_types.push(param1);
+                    callees.insert(id);
+
+                    let data = serialize(&Call { contract_id: <[u8; 32]>::from(contract_id).into(), to: <[u8; 32]>::from(id).into(), amount, asset_id: <[u8; 32]>::from(asset_id).into(), gas, fn_name });
+                    let ty_id = Call::type_id();
+                    decoder.decode_type(ty_id, data);
+                }
+                fuel::Receipt::Log { id, ra, rb, .. } => {
+                    #check_if_subscribed_to_contract
+                    let ty_id = Log::type_id();
+                    let data = serialize(&Log{ contract_id: <[u8; 32]>::from(id).into(), ra, rb });
+                    decoder.decode_type(ty_id, data);
+                }
+                fuel::Receipt::LogData { rb, data, ptr, len, id, .. } => {
+                    #check_if_subscribed_to_contract
+                    #log_abi_index
+                    decoder.decode_logdata(abi_index, rb as usize, data);
+
+                }
+                fuel::Receipt::Return { id, val, pc, is } => {
+                    #check_if_subscribed_to_contract
+                    if callees.contains(&id) {
+                        let ty_id = Return::type_id();
+                        let data = serialize(&Return{ contract_id: <[u8; 32]>::from(id).into(), val, pc, is });
+                        decoder.decode_type(ty_id, data);
+                    }
+                }
+                fuel::Receipt::ReturnData { data, id, .. } => {
+                    #check_if_subscribed_to_contract
+                    if callees.contains(&id) {
+                        let selector = return_types.pop().expect("No return type available. <('-'<)");
+                        decoder.decode_return_type(selector, data);
+                    }
+                }
+                fuel::Receipt::MessageOut { sender, recipient, amount, nonce, len, digest, data, .. } => {
+                    #check_if_subscribed_to_message_out
+
+                    // TODO: Temporary conversion; remove once we update back to latest fuel-types version.
+                    let nonce_bytes = <[u8; 32]>::from(nonce);
+                    let nonce = Nonce::from(nonce_bytes);
+
+                    let message_id = decoder.compute_message_id(&<[u8; 32]>::from(sender.clone()).into(), &<[u8; 32]>::from(recipient.clone()).into(), nonce, amount, &data[..]);
+
+                    // It's possible that the data field was generated from an empty Sway `Bytes` array
+                    // in the send_message() instruction in which case the data field in the receipt will
+                    // have no type information or data to decode, so we decode an empty vector to a unit struct
+                    let type_id = data
+                        .get(..8)
+                        .map(|buffer| {
+                            u64::from_be_bytes(
+                                <[u8; 8]>::try_from(&buffer[..])
+                                    .expect("Could not get type ID for data in MessageOut receipt"),
+                            )
+                        })
+                        .unwrap_or(u64::MAX);
+
+                    let data = data
+                        .get(8..)
+                        .map(|buffer| buffer.to_vec())
+                        .unwrap_or(Vec::<u8>::new());
+
+                    #message_abi_index
+                    decoder.decode_messagedata(abi_index, type_id, data.clone());
+
+                    let ty_id = MessageOut::type_id();
+                    let data = serialize(&MessageOut{ message_id, sender: <[u8; 32]>::from(sender).into(), recipient: <[u8; 32]>::from(recipient.clone()).into(), amount, nonce, len, digest: <[u8; 32]>::from(digest).into(), data });
+                    decoder.decode_type(ty_id, data);
+                }
+                fuel::Receipt::ScriptResult { result, gas_used } => {
+                    let ty_id = ScriptResult::type_id();
+                    let data = serialize(&ScriptResult{ result: u64::from(result), gas_used });
+                    decoder.decode_type(ty_id, data);
+                }
+                fuel::Receipt::Transfer { id, to, asset_id, amount, pc, is, .. } => {
+                    #check_if_subscribed_to_contract
+                    let ty_id = Transfer::type_id();
+                    let data = serialize(&Transfer{ contract_id: <[u8; 32]>::from(id).into(), to: <[u8; 32]>::from(to).into(), asset_id: <[u8; 32]>::from(asset_id).into(), amount, pc, is });
+                    decoder.decode_type(ty_id, data);
+                }
+                fuel::Receipt::TransferOut { id, to, asset_id, amount, pc, is, .. } => {
+                    #check_if_subscribed_to_contract
+                    let ty_id = TransferOut::type_id();
+                    let data = serialize(&TransferOut{ contract_id: <[u8; 32]>::from(id).into(), to: <[u8; 32]>::from(to).into(), asset_id: <[u8; 32]>::from(asset_id).into(), amount, pc, is });
+                    decoder.decode_type(ty_id, data);
+                }
+                fuel::Receipt::Panic { id, reason, .. } => {
+                    #check_if_subscribed_to_contract
+                    let ty_id = Panic::type_id();
+                    let data = serialize(&Panic{ contract_id: <[u8; 32]>::from(id).into(), reason: *reason.reason() as u32 });
+                    decoder.decode_type(ty_id, data);
+                }
+                fuel::Receipt::Revert { id, ra, .. } => {
+                    #check_if_subscribed_to_contract
+                    let ty_id = Revert::type_id();
+                    let data = serialize(&Revert{ contract_id: <[u8; 32]>::from(id).into(), error_val: u64::from(ra & 0xF) });
+                    decoder.decode_type(ty_id, data);
+                }
+                _ => {
+                    info!("This type is not handled yet. (>'.')>");
+                }
+            }
+        }
+
+        decoder.current_contract = None;
+    };
+
+    let block_handler = if parallel_transactions {
+        // Each transaction is decoded and dispatched in its own task. Writes made
+        // by these tasks are buffered, then merged in transaction order before the
+        // block-level handlers run.
+        let clone_subscribed_contract_ids = match &manifest.contract_id() {
+            ContractIds::Single(_) => quote! {},
+            ContractIds::Multiple(_) if multiple_abis => quote! {
+                let contract_ids = contract_ids.clone();
+                let contract_abi_indexes = contract_abi_indexes.clone();
+            },
+            ContractIds::Multiple(_) => quote! {
+                let contract_ids = contract_ids.clone();
+            },
+        };
+
+        quote! {
+            unsafe {
+                if let Some(d) = &db {
+                    d.lock().await.start_deferred_writes();
+                }
+            }
+
+            let mut tasks = Vec::with_capacity(block.transactions.len());
+
+            for (tx_index, tx) in block.transactions.into_iter().enumerate() {
+                #clone_subscribed_contract_ids
+                let mut ctx = ctx.for_same_block();
+
+                tasks.push(tokio::spawn(TRANSACTION_INDEX.scope(tx_index, async move {
+                    let mut decoder = Decoders::default();
+
+                    #transaction_decoder
+
+                    decoder.dispatch_transaction(&mut ctx).await;
+                })));
+            }
+
+            for task in tasks {
+                task.await?;
             }
+
+            unsafe {
+                if let Some(d) = &db {
+                    d.lock().await.flush_deferred_writes().await?;
+                }
+            }
+
+            decoder.dispatch_block(&mut ctx).await;
+        }
+    } else {
+        quote! {
+            for tx in block.transactions {
+                #transaction_decoder
+            }
+
+            decoder.dispatch(&mut ctx)#awaitness;
         }
     };
+
     (
         quote! {
             #subscribed_contract_ids
 
+            #load_discovered_contracts
+
+            // Timestamp of the previous block, loaded from its persisted metadata so
+            // that time-based handler triggers carry over restarts and reloads.
+            let mut prev_block_time: Option<i64> = None;
+
             for block in blocks {
 
                 #start_block
 
+                set_log_block_height(block.height);
+
+                if prev_block_time.is_none() {
+                    if let Some(prev_height) = block.height.checked_sub(1) {
+                        prev_block_time = BlockMetadataEntity::load(prev_height)#awaitness
+                            .map(|metadata| metadata.time as i64);
+                    }
+                }
+
                 let mut decoder = Decoders::default();
+                let mut ctx = BlockContext::new(&block, prev_block_time);
 
                 let ty_id = BlockData::type_id();
                 let data = serialize(&block);
                 decoder.decode_type(ty_id, data);
 
-                for tx in block.transactions {
-
-                    let mut return_types = Vec::new();
-                    let mut callees = HashSet::new();
-
-                    for receipt in tx.receipts {
-                        match receipt {
-                            fuel::Receipt::Call { id: contract_id, amount, asset_id, gas, param1, to: id, .. } => {
-                                #check_if_subscribed_to_contract
-
-                                let fn_name = decoder.selector_to_fn_name(param1);
-                                return_types.push(param1);
-                                callees.insert(id);
-
-                                let data = serialize(&Call { contract_id: <[u8; 32]>::try_from(contract_id).unwrap().into(), to: <[u8; 32]>::try_from(id).unwrap().into(), amount, asset_id: <[u8; 32]>::try_from(asset_id).unwrap().into(), gas, fn_name });
-                                let ty_id = Call::type_id();
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::Log { id, ra, rb, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = Log::type_id();
-                                let data = serialize(&Log{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), ra, rb });
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::LogData { rb, data, ptr, len, id, .. } => {
-                                #check_if_subscribed_to_contract
-                                decoder.decode_logdata(rb as usize, data);
-
-                            }
-                            fuel::Receipt::Return { id, val, pc, is } => {
-                                #check_if_subscribed_to_contract
-                                if callees.contains(&id) {
-                                    let ty_id = Return::type_id();
-                                    let data = serialize(&Return{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), val, pc, is });
-                                    decoder.decode_type(ty_id, data);
-                                }
-                            }
-                            fuel::Receipt::ReturnData { data, id, .. } => {
-                                #check_if_subscribed_to_contract
-                                if callees.contains(&id) {
-                                    let selector = return_types.pop().expect("No return type available. <('-'<)");
-                                    decoder.decode_return_type(selector, data);
-                                }
-                            }
-                            fuel::Receipt::MessageOut { sender, recipient, amount, nonce, len, digest, data, .. } => {
-                                // TODO: Temporary conversion; remove once we update back to latest fuel-types version.
-                                let nonce_bytes = <[u8; 32]>::try_from(nonce).expect("Could not convert nonce to bytes");
-                                let nonce = Nonce::from(nonce_bytes);
-
-                                let message_id = decoder.compute_message_id(&<[u8; 32]>::try_from(sender.clone()).unwrap().into(), &<[u8; 32]>::try_from(recipient.clone()).unwrap().into(), nonce, amount, &data[..]);
-
-                                // It's possible that the data field was generated from an empty Sway `Bytes` array
-                                // in the send_message() instruction in which case the data field in the receipt will
-                                // have no type information or data to decode, so we decode an empty vector to a unit struct
-                                let type_id = data
-                                    .get(..8)
-                                    .map(|buffer| {
-                                        u64::from_be_bytes(
-                                            <[u8; 8]>::try_from(&buffer[..])
-                                                .expect("Could not get type ID for data in MessageOut receipt"),
-                                        )
-                                    })
-                                    .unwrap_or(u64::MAX);
-
-                                let data = data
-                                    .get(8..)
-                                    .map(|buffer| buffer.to_vec())
-                                    .unwrap_or(Vec::<u8>::new());
-
-                                decoder.decode_messagedata(type_id, data.clone());
-
-                                let ty_id = MessageOut::type_id();
-                                let data = serialize(&MessageOut{ message_id, sender: <[u8; 32]>::try_from(sender).unwrap().into(), recipient: <[u8; 32]>::try_from(recipient.clone()).unwrap().into(), amount, nonce, len, digest: <[u8; 32]>::try_from(digest).unwrap().into(), data });
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::ScriptResult { result, gas_used } => {
-                                let ty_id = ScriptResult::type_id();
-                                let data = serialize(&ScriptResult{ result: u64::from(result), gas_used });
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::Transfer { id, to, asset_id, amount, pc, is, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = Transfer::type_id();
-                                let data = serialize(&Transfer{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), to: <[u8; 32]>::try_from(to).unwrap().into(), asset_id: <[u8; 32]>::try_from(asset_id).unwrap().into(), amount, pc, is });
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::TransferOut { id, to, asset_id, amount, pc, is, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = TransferOut::type_id();
-                                let data = serialize(&TransferOut{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), to: <[u8; 32]>::try_from(to).unwrap().into(), asset_id: <[u8; 32]>::try_from(asset_id).unwrap().into(), amount, pc, is });
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::Panic { id, reason, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = Panic::type_id();
-                                let data = serialize(&Panic{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), reason: *reason.reason() as u32 });
-                                decoder.decode_type(ty_id, data);
-                            }
-                            fuel::Receipt::Revert { id, ra, .. } => {
-                                #check_if_subscribed_to_contract
-                                let ty_id = Revert::type_id();
-                                let data = serialize(&Revert{ contract_id: <[u8; 32]>::try_from(id).unwrap().into(), error_val: u64::from(ra & 0xF) });
-                                decoder.decode_type(ty_id, data);
-                            }
-                            _ => {
-                                info!("This type is not handled yet. (>'.')>");
-                            }
-                        }
-                    }
-                }
-                decoder.dispatch()#awaitness;
+                #block_handler
 
                 let metadata = IndexMetadataEntity::new(block.time as u64, block.header.height, block.id);
                 metadata.save()#awaitness;
+
+                let block_metadata = BlockMetadataEntity {
+                    id: block.height,
+                    block_height: block.height,
+                    block_id: block.id,
+                    time: block.time as u64,
+                    da_height: block.header.da_height,
+                    producer: block.producer,
+                    transactions_count: block.header.transactions_count,
+                    output_messages_count: block.header.output_messages_count,
+                    application_hash: block.header.application_hash,
+                };
+                block_metadata.save()#awaitness;
+
+                prev_block_time = Some(block.time);
             }
         },
         quote! {
             #decoder_struct
 
+            #template_fns
+
             #(#handler_fns)*
         },
     )
 }
 
+/// Dispatch code of a handler, with its priority and whether it takes transaction data.
+type HandlerDispatcher = (i64, bool, proc_macro2::TokenStream);
+
+/// Order handler dispatchers so that handlers with a higher priority are dispatched
+/// first. The sort is stable, so handlers with equal priority keep their declaration
+/// order.
+fn order_by_priority(dispatchers: &mut [HandlerDispatcher]) {
+    dispatchers.sort_by_key(|(priority, _, _)| std::cmp::Reverse(*priority));
+}
+
+/// Return the checks that gate a handler on its `every_n_blocks` and `every_seconds`
+/// schedule, evaluated against the `BlockContext` of the block being processed.
+fn schedule_checks(
+    every_n_blocks: Option<u64>,
+    every_seconds: Option<u64>,
+) -> Vec<proc_macro2::TokenStream> {
+    let mut checks = Vec::new();
+    if let Some(n) = every_n_blocks {
+        checks.push(quote! { ctx.block_interval_elapsed(#n) });
+    }
+    if let Some(secs) = every_seconds {
+        checks.push(quote! { ctx.time_interval_elapsed(#secs) });
+    }
+    checks
+}
+
 pub fn prefix_abi_and_schema_paths(
-    abi: Option<&str>,
+    abis: Vec<&str>,
     schema: &str,
-) -> (Option<String>, String) {
-    if let Some(abi) = abi {
-        match std::env::var("COMPILE_TEST_PREFIX") {
-            Ok(prefix) => {
-                let prefixed = std::path::Path::new(&prefix).join(abi);
-                let abi_string = prefixed
-                    .into_os_string()
-                    .to_str()
-                    .expect("Could not parse prefixed ABI path.")
-                    .to_string();
-                let prefixed = std::path::Path::new(&prefix).join(schema);
-                let schema = prefixed
-                    .into_os_string()
-                    .to_str()
-                    .expect("Could not parse prefixed GraphQL schema path.")
-                    .to_string();
-
-                return (Some(abi_string), schema);
-            }
-            Err(_) => {
-                return (Some(abi.into()), schema.to_string());
-            }
-        };
+) -> (Vec<String>, String) {
+    match std::env::var("COMPILE_TEST_PREFIX") {
+        Ok(prefix) if !abis.is_empty() => {
+            let abis = abis
+                .into_iter()
+                .map(|abi| {
+                    std::path::Path::new(&prefix)
+                        .join(abi)
+                        .into_os_string()
+                        .to_str()
+                        .expect("Could not parse prefixed ABI path.")
+                        .to_string()
+                })
+                .collect();
+            let prefixed = std::path::Path::new(&prefix).join(schema);
+            let schema = prefixed
+                .into_os_string()
+                .to_str()
+                .expect("Could not parse prefixed GraphQL schema path.")
+                .to_string();
+
+            (abis, schema)
+        }
+        _ => (
+            abis.into_iter().map(|abi| abi.to_string()).collect(),
+            schema.to_string(),
+        ),
     }
-
-    (None, schema.to_string())
 }
 
 pub fn get_abi_tokens(
     namespace: &str,
-    abi: &str,
+    abis: &[String],
     exec_source: ExecutionSource,
 ) -> proc_macro2::TokenStream {
     let no_std = match exec_source {
@@ -632,14 +1258,22 @@ pub fn get_abi_tokens(
         ExecutionSource::Wasm => true,
     };
 
-    match Abigen::generate(
-        vec![AbigenTarget {
-            name: namespace.to_string(),
+    // Each ABI needs a distinct target name.
+    let targets = abis
+        .iter()
+        .enumerate()
+        .map(|(i, abi)| AbigenTarget {
+            name: if abis.len() > 1 {
+                format!("{namespace}_{i}")
+            } else {
+                namespace.to_string()
+            },
             abi: abi.to_owned(),
             program_type: ProgramType::Contract,
-        }],
-        no_std,
-    ) {
+        })
+        .collect::<Vec<_>>();
+
+    match Abigen::generate(targets, no_std) {
         Ok(tokens) => tokens,
         Err(e) => {
             proc_macro_error::abort_call_site!(
@@ -663,18 +1297,37 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
 
     let indexer_module = parse_macro_input!(item as ItemMod);
 
-    let (abi, schema_string) =
-        prefix_abi_and_schema_paths(manifest.abi(), manifest.graphql_schema());
+    // Template ABIs are generated and decoded alongside the manifest's own ABIs.
+    // ABIs given as a URL or registry package are read from the ABI cache, which
+    // is populated by `forc index build`.
+    let abi_paths = manifest
+        .all_abis()
+        .into_iter()
+        .map(|abi| {
+            let path = local_abi_path(abi);
+            if remote_abi_url(abi).is_some() && !Path::new(&path).exists() {
+                proc_macro_error::abort_call_site!(
+                    "ABI '{}' has not been fetched. Run `forc index build` to fetch remote ABIs.",
+                    abi
+                );
+            }
+            path
+        })
+        .collect::<Vec<_>>();
+
+    let (abis, schema_string) = prefix_abi_and_schema_paths(
+        abi_paths.iter().map(|abi| abi.as_str()).collect(),
+        manifest.graphql_schema(),
+    );
 
-    let abi_tokens = match abi {
-        Some(ref abi_path) => {
-            get_abi_tokens(manifest.namespace(), abi_path, manifest.execution_source())
-        }
-        None => proc_macro2::TokenStream::new(),
+    let abi_tokens = if abis.is_empty() {
+        proc_macro2::TokenStream::new()
+    } else {
+        get_abi_tokens(manifest.namespace(), &abis, manifest.execution_source())
     };
 
     // NOTE: https://nickb.dev/blog/cargo-workspace-and-the-feature-unification-pitfall/
-    let graphql_tokens = process_graphql_schema(
+    let (graphql_tokens, schema) = process_graphql_schema(
         manifest.namespace(),
         manifest.identifier(),
         &schema_string,
@@ -684,7 +1337,7 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
     let output = match manifest.execution_source() {
         ExecutionSource::Native => {
             let (handler_block, fn_items) =
-                process_fn_items(&manifest, abi, indexer_module);
+                process_fn_items(&manifest, &schema, abis, indexer_module);
             let handler_block = handler_block_native(handler_block);
 
             quote! {
@@ -705,11 +1358,7 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
                     let IndexerArgs { manifest, .. } = args.clone();
 
 
-                    let config = args
-                    .config
-                    .as_ref()
-                    .map(IndexerConfig::from_file)
-                    .unwrap_or(Ok(IndexerConfig::from(args)))?;
+                    let config = IndexerConfig::load(&args)?;
 
                     init_logging(&config).await?;
 
@@ -748,7 +1397,7 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
         }
         ExecutionSource::Wasm => {
             let (handler_block, fn_items) =
-                process_fn_items(&manifest, abi, indexer_module);
+                process_fn_items(&manifest, &schema, abis, indexer_module);
             let handler_block = handler_block_wasm(handler_block);
 
             quote! {
@@ -766,3 +1415,52 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
 
     proc_macro::TokenStream::from(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handlers_are_ordered_by_priority_then_declaration() {
+        let mut dispatchers: Vec<HandlerDispatcher> = vec![
+            (0, false, quote! { first_default }),
+            (10, true, quote! { high }),
+            (-5, false, quote! { low }),
+            (0, true, quote! { second_default }),
+            (10, false, quote! { second_high }),
+        ];
+        order_by_priority(&mut dispatchers);
+
+        let order = dispatchers
+            .iter()
+            .map(|(_, _, dispatcher)| dispatcher.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![
+                "high",
+                "second_high",
+                "first_default",
+                "second_default",
+                "low"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_schedule_checks_gate_handlers_on_their_intervals() {
+        assert!(schedule_checks(None, None).is_empty());
+
+        let checks = schedule_checks(Some(100), Some(60))
+            .iter()
+            .map(|check| check.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            checks,
+            vec![
+                quote! { ctx.block_interval_elapsed(100u64) }.to_string(),
+                quote! { ctx.time_interval_elapsed(60u64) }.to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use crate::constants::JSON_TYPES_NAMESPACE;
+use fuel_indexer_lib::type_id;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Implement `From<T> for Json`, `From<Json> for T`, and `TypeId` for the given type.
+pub fn process_indexer_json_derive(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let name = ident.to_string();
+    let type_id = type_id(JSON_TYPES_NAMESPACE, &name) as usize;
+    let serialize_error = format!("Failed to serialize {name}.");
+    let deserialize_error = format!("Failed to deserialize {name}.");
+
+    let output = quote! {
+        impl #impl_generics From<#ident #ty_generics> for fuel_indexer_utils::plugin::types::Json #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                let s = fuel_indexer_utils::plugin::serde_json::to_string(&value).expect(#serialize_error);
+                Self(s)
+            }
+        }
+
+        impl #impl_generics From<fuel_indexer_utils::plugin::types::Json> for #ident #ty_generics #where_clause {
+            fn from(json: fuel_indexer_utils::plugin::types::Json) -> Self {
+                fuel_indexer_utils::plugin::serde_json::from_str(&json.0).expect(#deserialize_error)
+            }
+        }
+
+        impl #impl_generics fuel_indexer_utils::plugin::types::TypeId for #ident #ty_generics #where_clause {
+            fn type_id() -> usize {
+                #type_id
+            }
+        }
+    };
+
+    TokenStream::from(output)
+}
@@ -5,12 +5,15 @@ pub(crate) mod constants;
 pub(crate) mod decoder;
 pub(crate) mod helpers;
 pub(crate) mod indexer;
+pub(crate) mod json;
 pub(crate) mod native;
 pub(crate) mod parse;
 pub(crate) mod schema;
+pub(crate) mod validation;
 pub(crate) mod wasm;
 
 use indexer::process_indexer_module;
+use json::process_indexer_json_derive;
 use proc_macro::TokenStream;
 
 #[proc_macro_error::proc_macro_error]
@@ -19,6 +22,15 @@ pub fn indexer(attrs: TokenStream, item: TokenStream) -> TokenStream {
     process_indexer_module(attrs, item)
 }
 
+/// Derive `Json` conversions and a `TypeId` for a user-defined type, so that it can be
+/// stored in `Json` and `Virtual` fields of entities. The type must also derive
+/// `Serialize` and `Deserialize`.
+#[proc_macro_error::proc_macro_error]
+#[proc_macro_derive(IndexerJson)]
+pub fn indexer_json(item: TokenStream) -> TokenStream {
+    process_indexer_json_derive(item)
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused)]
@@ -44,5 +56,8 @@ mod tests {
             macro_data_root.join("fail_if_attribute_abi_arg_includes_invalid_type.rs"),
         );
         t.compile_fail(macro_data_root.join("fail_if_indexer_module_is_empty.rs"));
+        t.compile_fail(
+            macro_data_root.join("fail_if_handler_uses_unknown_entity_field.rs"),
+        );
     }
 }
@@ -37,6 +37,44 @@ fn native_prelude() -> proc_macro2::TokenStream {
 
         static mut db: Option<Arc<Mutex<Database>>> = None;
 
+        /// Return the balance of the given asset owned by the given address.
+        ///
+        /// The balance is queried from the Fuel node and cached until the next block.
+        #[allow(dead_code)]
+        async fn get_balance(owner: &Address, asset_id: &AssetId) -> u64 {
+            match unsafe { &db } {
+                Some(d) => d.lock().await.balance(*owner, *asset_id).await,
+                None => 0,
+            }
+        }
+
+        /// Return the unspent coins of the given asset owned by the given address.
+        ///
+        /// The coins are queried from the Fuel node and cached until the next block.
+        #[allow(dead_code)]
+        async fn get_coins(owner: &Address, asset_id: &AssetId) -> Vec<fuel::Coin> {
+            match unsafe { &db } {
+                Some(d) => d.lock().await.coins(*owner, *asset_id).await,
+                None => Vec::new(),
+            }
+        }
+
+        /// Fetch the body of the given URL with an HTTP GET request.
+        ///
+        /// Requires the `allow_http` capability. Requests are rate-limited, and `None`
+        /// is returned if the request fails, the URL's host is not a public address,
+        /// or the response is too large.
+        #[allow(dead_code)]
+        async fn http_get(url: &str) -> Option<Vec<u8>> {
+            match unsafe { &db } {
+                Some(d) => {
+                    let http = d.lock().await.http_client();
+                    http.get(url).await
+                }
+                None => None,
+            }
+        }
+
         use fuel_indexer_utils::plugin::types::*;
         use fuel_indexer_utils::plugin::native::*;
         use fuel_indexer_utils::plugin::{serde_json, serialize, deserialize, bincode};
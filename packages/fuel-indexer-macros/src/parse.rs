@@ -1,6 +1,9 @@
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{LitStr, Token};
+use syn::{Attribute, Ident, LitInt, LitStr, Meta, Token};
+
+/// Name of the attribute used to configure handler functions.
+pub(crate) const HANDLER_ATTRIBUTE: &str = "handler";
 
 mod kw {
     syn::custom_keyword!(manifest);
@@ -80,3 +83,92 @@ impl Parse for Manifest {
         Ok(Manifest { name })
     }
 }
+
+/// Configuration of a single handler function, parsed from an optional
+/// `#[handler(...)]` attribute, e.g. `#[handler(priority = 10, every_n_blocks = 100)]`.
+#[derive(Debug, Default)]
+pub(crate) struct HandlerConfig {
+    /// Handlers with a higher priority are dispatched first within a block.
+    /// Handlers with equal priority are dispatched in declaration order.
+    pub(crate) priority: i64,
+
+    /// Only dispatch the handler for blocks whose height is a multiple of this value.
+    pub(crate) every_n_blocks: Option<u64>,
+
+    /// Only dispatch the handler for the first block of each interval of this many
+    /// seconds of chain time.
+    pub(crate) every_seconds: Option<u64>,
+
+    /// Only pass the handler values that were decoded from receipts of this contract.
+    pub(crate) contract: Option<String>,
+
+    /// Only dispatch the handler when values of this logged type were decoded.
+    pub(crate) log_type: Option<Ident>,
+}
+
+impl HandlerConfig {
+    /// Parse the handler configuration from the attributes of a handler function.
+    pub(crate) fn from_attrs(attrs: &[Attribute]) -> syn::Result<HandlerConfig> {
+        let mut config = HandlerConfig::default();
+
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident(HANDLER_ATTRIBUTE))
+        {
+            if let Meta::Path(_) = attr.meta {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("priority") {
+                    let value: LitInt = meta.value()?.parse()?;
+                    config.priority = value.base10_parse()?;
+                    Ok(())
+                } else if meta.path.is_ident("every_n_blocks") {
+                    config.every_n_blocks = Some(parse_interval(meta.value()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("every_seconds") {
+                    config.every_seconds = Some(parse_interval(meta.value()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("contract") {
+                    config.contract = Some(parse_contract_id(meta.value()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("log_type") {
+                    config.log_type = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("Unsupported handler attribute."))
+                }
+            })?;
+        }
+
+        Ok(config)
+    }
+}
+
+/// Parse a non-zero handler interval.
+fn parse_interval(input: ParseStream) -> syn::Result<u64> {
+    let value: LitInt = input.parse()?;
+    let interval = value.base10_parse::<u64>()?;
+    if interval == 0 {
+        return Err(syn::Error::new(
+            value.span(),
+            "Handler interval must be greater than zero.",
+        ));
+    }
+    Ok(interval)
+}
+
+/// Parse a hex-encoded contract ID, with or without a `0x` prefix.
+fn parse_contract_id(input: ParseStream) -> syn::Result<String> {
+    let value: LitStr = input.parse()?;
+    let id = value.value();
+    let hex = id.strip_prefix("0x").unwrap_or(&id);
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(syn::Error::new(
+            value.span(),
+            "Handler contract must be a 32-byte hex-encoded contract ID.",
+        ));
+    }
+    Ok(hex.to_string())
+}
@@ -41,13 +41,14 @@ fn process_definition(
     }
 }
 
-/// Process user-supplied GraphQL schema into code for indexer module.
+/// Process user-supplied GraphQL schema into code for indexer module, returning the
+/// code along with the parsed schema.
 pub(crate) fn process_graphql_schema(
     namespace: &str,
     identifier: &str,
     schema_path: &str,
     exec_source: ExecutionSource,
-) -> proc_macro2::TokenStream {
+) -> (proc_macro2::TokenStream, ParsedGraphQLSchema) {
     let namespace_tokens = const_item("NAMESPACE", namespace);
     let identifer_tokens = const_item("IDENTIFIER", identifier);
 
@@ -91,5 +92,5 @@ pub(crate) fn process_graphql_schema(
         }
     }
 
-    output
+    (output, schema)
 }
@@ -0,0 +1,431 @@
+use fuel_indexer_lib::graphql::{field_id, ParsedGraphQLSchema};
+use proc_macro2::Span;
+use std::collections::HashSet;
+use syn::{
+    spanned::Spanned, visit::Visit, Expr, ExprCall, ExprLit, ExprPath, ExprStruct, Item,
+    ItemFn, Lit, Member, Path, UseTree,
+};
+
+/// Associated functions of generated entities. Calling one of these on a type that
+/// isn't an entity of the schema is reported as an error, unless the type is declared
+/// or imported by the indexer module.
+const ENTITY_FUNCTIONS: [&str; 3] = ["load", "query", "find_many"];
+
+/// Name of the generated function that registers a contract against a named template.
+const REGISTER_WITH_TEMPLATE: &str = "register_contract_with_template";
+
+/// GraphQL scalar types that integer literals can be assigned to.
+const INTEGER_TYPES: [&str; 13] = [
+    "BlockHeight",
+    "I128",
+    "ID",
+    "Int1",
+    "Int4",
+    "Int8",
+    "Int16",
+    "Timestamp",
+    "U128",
+    "UInt1",
+    "UInt4",
+    "UInt8",
+    "UInt16",
+];
+
+/// Names of the types declared or imported by the items of an indexer module, or
+/// `None` if the module has a glob import, whose names can't be known.
+pub(crate) fn module_type_names(items: &[Item]) -> Option<HashSet<String>> {
+    let mut names = HashSet::new();
+    for item in items {
+        let ident = match item {
+            Item::Struct(item) => &item.ident,
+            Item::Enum(item) => &item.ident,
+            Item::Union(item) => &item.ident,
+            Item::Type(item) => &item.ident,
+            Item::Trait(item) => &item.ident,
+            Item::Use(item) => {
+                if !use_tree_names(&item.tree, &mut names) {
+                    return None;
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        names.insert(ident.to_string());
+    }
+    Some(names)
+}
+
+/// Add the names imported by a `use` tree, returning `false` if it has a glob.
+fn use_tree_names(tree: &UseTree, names: &mut HashSet<String>) -> bool {
+    match tree {
+        UseTree::Path(path) => use_tree_names(&path.tree, names),
+        UseTree::Name(name) => {
+            names.insert(name.ident.to_string());
+            true
+        }
+        UseTree::Rename(rename) => {
+            names.insert(rename.rename.to_string());
+            true
+        }
+        UseTree::Glob(_) => false,
+        UseTree::Group(group) => group.items.iter().fold(true, |resolved, tree| {
+            use_tree_names(tree, names) && resolved
+        }),
+    }
+}
+
+/// Check that the entities used in the body of a handler match the GraphQL schema,
+/// emitting a spanned compile error for each mismatch.
+///
+/// Entities are generated from the schema, so most mismatches would fail to compile
+/// anyway, but with errors about generated code rather than the schema. Associated
+/// functions are only checked on types that aren't among the given `module_types`,
+/// and not at all if those are unknown.
+pub(crate) fn check_handler_entities(
+    fn_item: &ItemFn,
+    schema: &ParsedGraphQLSchema,
+    module_types: Option<&HashSet<String>>,
+) {
+    for (span, message) in handler_entity_errors(fn_item, schema, module_types) {
+        proc_macro_error::emit_error!(span, "{}", message);
+    }
+}
+
+/// Return the span and message of each mismatch between the entities used in the
+/// body of a handler and the GraphQL schema.
+fn handler_entity_errors(
+    fn_item: &ItemFn,
+    schema: &ParsedGraphQLSchema,
+    module_types: Option<&HashSet<String>>,
+) -> Vec<(Span, String)> {
+    let mut visitor = EntityVisitor {
+        schema,
+        module_types,
+        errors: Vec::new(),
+    };
+    visitor.visit_item_fn(fn_item);
+    visitor.errors
+}
+
+struct EntityVisitor<'a> {
+    schema: &'a ParsedGraphQLSchema,
+
+    /// Types declared or imported by the indexer module, if they're known.
+    module_types: Option<&'a HashSet<String>>,
+
+    /// Span and message of each mismatch found.
+    errors: Vec<(Span, String)>,
+}
+
+impl EntityVisitor<'_> {
+    fn is_entity(&self, name: &str) -> bool {
+        self.schema.objects().contains_key(name) || self.schema.is_union_typedef(name)
+    }
+
+    /// Whether an associated function called on the given type must be a function of
+    /// an entity, i.e., whether the type can only name an entity.
+    fn must_be_entity(&self, ty: &str) -> bool {
+        let is_type_name = ty.chars().next().map_or(false, char::is_uppercase);
+        match self.module_types {
+            Some(module_types) => {
+                is_type_name && ty != "Self" && !module_types.contains(ty)
+            }
+            None => false,
+        }
+    }
+
+    /// Check a literal assigned to a field of an entity against the field's type.
+    fn check_literal(&mut self, entity: &str, field: &str, lit: &ExprLit) {
+        let schema = self.schema;
+        let field_def = match schema.field_defs().get(&field_id(entity, field)) {
+            Some((field_def, _)) => field_def,
+            None => return,
+        };
+        let field_type = schema.scalar_type_for(field_def);
+
+        let expected = match &lit.lit {
+            Lit::Str(_) => field_type == "Charfield",
+            Lit::Bool(_) => field_type == "Boolean",
+            Lit::Int(_) => INTEGER_TYPES.contains(&field_type.as_str()),
+            _ => true,
+        };
+
+        if !expected {
+            self.errors.push((
+                lit.span(),
+                format!(
+                    "Field '{field}' of entity '{entity}' has type '{field_type}' in the schema."
+                ),
+            ));
+        }
+    }
+}
+
+/// Check that each call to `register_contract_with_template` in the given items names
+/// one of the manifest's templates, emitting a spanned compile error otherwise.
+///
+/// Templates must be named with string literals, so that every call can be checked.
+pub(crate) fn check_contract_templates(items: &[Item], templates: &[&str]) {
+    for (span, message) in contract_template_errors(items, templates) {
+        proc_macro_error::emit_error!(span, "{}", message);
+    }
+}
+
+/// Return the span and message of each call to `register_contract_with_template`
+/// that doesn't name one of the given templates.
+fn contract_template_errors(items: &[Item], templates: &[&str]) -> Vec<(Span, String)> {
+    let mut visitor = TemplateVisitor {
+        templates,
+        errors: Vec::new(),
+    };
+    for item in items {
+        visitor.visit_item(item);
+    }
+    visitor.errors
+}
+
+struct TemplateVisitor<'a> {
+    /// Names of the manifest's templates.
+    templates: &'a [&'a str],
+
+    /// Span and message of each call that doesn't name a template.
+    errors: Vec<(Span, String)>,
+}
+
+impl<'ast> Visit<'ast> for TemplateVisitor<'_> {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        let is_register_call = match &*call.func {
+            Expr::Path(ExprPath { path, .. }) => path
+                .segments
+                .last()
+                .map_or(false, |segment| segment.ident == REGISTER_WITH_TEMPLATE),
+            _ => false,
+        };
+
+        if is_register_call {
+            match call.args.first() {
+                Some(Expr::Lit(ExprLit {
+                    lit: Lit::Str(template),
+                    ..
+                })) => {
+                    if !self.templates.contains(&template.value().as_str()) {
+                        self.errors.push((
+                            template.span(),
+                            format!(
+                                "Unknown contract template '{}'. Available templates: {}.",
+                                template.value(),
+                                self.templates.join(", ")
+                            ),
+                        ));
+                    }
+                }
+                Some(arg) => self.errors.push((
+                    arg.span(),
+                    format!("The template of `{REGISTER_WITH_TEMPLATE}` must be a string literal."),
+                )),
+                None => {}
+            }
+        }
+
+        syn::visit::visit_expr_call(self, call);
+    }
+}
+
+/// Name of the type of an associated function path, e.g. `Account` for `Account::load`.
+///
+/// Only paths made of a type and a function are considered, since entities are
+/// referred to by their bare names; longer paths name types of other modules.
+fn associated_type(path: &Path) -> Option<(String, String)> {
+    if path.leading_colon.is_some() || path.segments.len() != 2 {
+        return None;
+    }
+    let ty = path.segments[0].ident.to_string();
+    let function = path.segments[1].ident.to_string();
+    Some((ty, function))
+}
+
+impl<'ast> Visit<'ast> for EntityVisitor<'_> {
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Path(ExprPath { path, .. }) = &*call.func {
+            if let Some((ty, function)) = associated_type(path) {
+                if ENTITY_FUNCTIONS.contains(&function.as_str())
+                    && self.must_be_entity(&ty)
+                    && !self.is_entity(&ty)
+                {
+                    self.errors.push((
+                        path.span(),
+                        format!("'{ty}' is not an entity in the GraphQL schema."),
+                    ));
+                }
+            }
+        }
+
+        syn::visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_struct(&mut self, expr: &'ast ExprStruct) {
+        let entity = expr
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default();
+
+        let schema = self.schema;
+        if let Some(fields) = schema.object_field_mappings().get(&entity) {
+            for field_value in &expr.fields {
+                let field = match &field_value.member {
+                    Member::Named(ident) => ident.to_string(),
+                    Member::Unnamed(_) => continue,
+                };
+
+                if !fields.contains_key(&field) {
+                    self.errors.push((
+                        field_value.member.span(),
+                        format!(
+                            "Entity '{entity}' has no field '{field}' in the GraphQL schema. Available fields: {}.",
+                            fields.keys().cloned().collect::<Vec<_>>().join(", ")
+                        ),
+                    ));
+                } else if let Expr::Lit(lit) = &field_value.expr {
+                    self.check_literal(&entity, &field, lit);
+                }
+            }
+        }
+
+        syn::visit::visit_expr_struct(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+
+    fn errors(module: &str) -> Vec<String> {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    name: Charfield!
+    balance: UInt8!
+}"#;
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let module: syn::ItemMod = syn::parse_str(module).unwrap();
+        let items = module.content.unwrap().1;
+        let module_types = module_type_names(&items);
+        items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(fn_item) => Some(fn_item),
+                _ => None,
+            })
+            .flat_map(|fn_item| {
+                handler_entity_errors(fn_item, &schema, module_types.as_ref())
+            })
+            .map(|(_, message)| message)
+            .collect()
+    }
+
+    #[test]
+    fn test_handler_entity_errors_report_functions_of_unknown_types() {
+        let module = r#"
+mod indexer {
+    fn handle(id: UID) {
+        let account = Account::load(id.clone());
+        let thing = Thing::load(id);
+    }
+}"#;
+
+        assert_eq!(
+            errors(module),
+            vec!["'Thing' is not an entity in the GraphQL schema.".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_handler_entity_errors_ignore_functions_of_module_and_other_types() {
+        let module = r#"
+mod indexer {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use crate::config::Settings as Config;
+
+    struct Cache;
+
+    fn handle(counter: AtomicU64, id: UID) {
+        let count = AtomicU64::load(&counter, Ordering::Relaxed);
+        let config = Config::load();
+        let cached = Cache::load(id.clone());
+        let other = std::sync::atomic::AtomicBool::load(&flag, Ordering::Relaxed);
+        let this = Self::load(id);
+        let account = Account::load(id);
+    }
+}"#;
+
+        assert!(errors(module).is_empty());
+
+        // Types brought in by glob imports can't be known, so none are checked.
+        let module = r#"
+mod indexer {
+    use std::sync::atomic::*;
+
+    fn handle(counter: AtomicU64) {
+        let count = AtomicU64::load(&counter, Ordering::Relaxed);
+    }
+}"#;
+
+        assert!(errors(module).is_empty());
+    }
+
+    #[test]
+    fn test_handler_entity_errors_report_fields_and_literals() {
+        let module = r#"
+mod indexer {
+    fn handle(id: UID) {
+        let account = Account { id, name: 5, balance: 1, owner: "me".to_string() };
+    }
+}"#;
+
+        let errors = errors(module);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0],
+            "Field 'name' of entity 'Account' has type 'Charfield' in the schema."
+        );
+        assert!(errors[1].starts_with("Entity 'Account' has no field 'owner'"));
+    }
+
+    #[test]
+    fn test_contract_template_errors_report_unknown_and_dynamic_templates() {
+        let module = r#"
+mod indexer {
+    fn handle(id: ContractId, name: String) {
+        register_contract_with_template("pool", id);
+        register_contract_with_template("pol", id);
+        register_contract_with_template(&name, id);
+    }
+}"#;
+
+        let module: syn::ItemMod = syn::parse_str(module).unwrap();
+        let items = module.content.unwrap().1;
+        let errors = contract_template_errors(&items, &["pool", "vault"])
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                "Unknown contract template 'pol'. Available templates: pool, vault."
+                    .to_string(),
+                "The template of `register_contract_with_template` must be a string literal."
+                    .to_string(),
+            ]
+        );
+    }
+}
@@ -11,19 +11,16 @@ pub fn handler_block_wasm(
         #wasm_prelude
 
         #[no_mangle]
-        fn handle_events(blob: *mut u8, len: usize) {
-            use fuel_indexer_utils::plugin::deserialize;
-            let bytes = unsafe { Vec::from_raw_parts(blob, len, len) };
-            let blocks: Vec<BlockData> = match deserialize(&bytes) {
-                Ok(blocks) => blocks,
-                Err(msg) => {
-                    // TODO: probably need some error codes to send back to runtime.
-                    core::mem::forget(bytes);
-                    Logger::error(&msg);
-                    return;
-                }
-            };
-            core::mem::forget(bytes);
+        fn get_ffi_version() -> u32 {
+            FFI_VERSION
+        }
+
+        #[no_mangle]
+        fn handle_blocks(num_blocks: u32) {
+            // Blocks are pulled from the indexer service one at a time, so only the
+            // block being handled is held in memory.
+            let blocks = (0..num_blocks)
+                .map(|index| get_block(index).expect("Block missing from page."));
 
             #handler_block
         }
@@ -2,15 +2,31 @@ use lazy_static::lazy_static;
 use prometheus::{self, register_int_counter, IntCounter};
 use prometheus_client::{
     encoding::EncodeLabelSet,
-    metrics::{family::Family, histogram::Histogram},
+    metrics::{
+        counter::Counter,
+        family::Family,
+        gauge::Gauge,
+        histogram::{exponential_buckets, Histogram},
+    },
     registry::Registry,
 };
+use std::sync::atomic::AtomicU64;
 
 lazy_static! {
     pub static ref TIMING_HISTOGRAM_BUCKETS: Vec<f64> =
         vec![0., 10., 100., 1000., 10000., 50000., 100000., 500000., 1000000.];
 }
 
+/// Buckets for latencies in seconds, from 0.5ms to ~16s.
+fn latency_buckets() -> impl Iterator<Item = f64> {
+    exponential_buckets(0.0005, 2., 16)
+}
+
+/// Buckets for counts of entities, from 1 to ~260k.
+fn count_buckets() -> impl Iterator<Item = f64> {
+    exponential_buckets(1., 4., 10)
+}
+
 pub trait Metric {
     fn init() -> Self;
 }
@@ -20,6 +36,46 @@ pub struct Label {
     path: String,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct IndexerLabel {
+    indexer: String,
+}
+
+impl IndexerLabel {
+    pub fn new(indexer: &str) -> Self {
+        Self {
+            indexer: indexer.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct IndexerNameLabel {
+    namespace: String,
+    identifier: String,
+}
+
+impl IndexerNameLabel {
+    pub fn new(namespace: &str, identifier: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            identifier: identifier.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct RateLimitLabel {
+    limit: String,
+    indexer: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct QueryUsageLabel {
+    indexer: String,
+    api_key: String,
+}
+
 pub struct Postgres {
     pub registry: Registry,
     requests: Family<Label, Histogram>,
@@ -79,6 +135,11 @@ impl Metric for Database {
 pub struct Web {
     pub registry: Registry,
     requests: Family<Label, Histogram>,
+    rate_limited: Family<RateLimitLabel, Counter>,
+    queries: Family<QueryUsageLabel, Counter>,
+    query_rows: Family<QueryUsageLabel, Counter>,
+    query_compute_seconds: Family<QueryUsageLabel, Counter<f64, AtomicU64>>,
+    query_duration: Family<IndexerNameLabel, Histogram>,
 }
 
 impl Metric for Web {
@@ -88,7 +149,49 @@ impl Metric for Web {
             Histogram::new(TIMING_HISTOGRAM_BUCKETS.iter().cloned())
         });
         registry.register("web_request_duration", "", requests.clone());
-        Self { registry, requests }
+        let rate_limited = Family::<RateLimitLabel, Counter>::default();
+        registry.register(
+            "web_rate_limited_requests",
+            "Number of GraphQL queries rejected by a rate limit.",
+            rate_limited.clone(),
+        );
+        let queries = Family::<QueryUsageLabel, Counter>::default();
+        registry.register(
+            "web_queries",
+            "Number of GraphQL queries served, by indexer and API key.",
+            queries.clone(),
+        );
+        let query_rows = Family::<QueryUsageLabel, Counter>::default();
+        registry.register(
+            "web_query_rows",
+            "Number of rows returned by GraphQL queries, by indexer and API key.",
+            query_rows.clone(),
+        );
+        let query_compute_seconds =
+            Family::<QueryUsageLabel, Counter<f64, AtomicU64>>::default();
+        registry.register(
+            "web_query_compute_seconds",
+            "Time spent executing GraphQL queries, by indexer and API key.",
+            query_compute_seconds.clone(),
+        );
+        let query_duration =
+            Family::<IndexerNameLabel, Histogram>::new_with_constructor(|| {
+                Histogram::new(latency_buckets())
+            });
+        registry.register(
+            "web_query_duration_seconds",
+            "Time taken to execute GraphQL queries, by indexer.",
+            query_duration.clone(),
+        );
+        Self {
+            registry,
+            requests,
+            rate_limited,
+            queries,
+            query_rows,
+            query_compute_seconds,
+            query_duration,
+        }
     }
 }
 
@@ -99,11 +202,205 @@ impl Web {
         });
         histogram.observe(time);
     }
+
+    pub fn record_rate_limited(&self, limit: &str, indexer: &str) {
+        self.rate_limited
+            .get_or_create(&RateLimitLabel {
+                limit: limit.to_string(),
+                indexer: indexer.to_string(),
+            })
+            .inc();
+    }
+
+    pub fn record_query_usage(
+        &self,
+        indexer: &str,
+        api_key: &str,
+        rows: u64,
+        compute_seconds: f64,
+    ) {
+        let label = QueryUsageLabel {
+            indexer: indexer.to_string(),
+            api_key: api_key.to_string(),
+        };
+        self.queries.get_or_create(&label).inc();
+        self.query_rows.get_or_create(&label).inc_by(rows);
+        self.query_compute_seconds
+            .get_or_create(&label)
+            .inc_by(compute_seconds);
+    }
+
+    pub fn record_query_duration(&self, namespace: &str, identifier: &str, seconds: f64) {
+        self.query_duration
+            .get_or_create(&IndexerNameLabel::new(namespace, identifier))
+            .observe(seconds);
+    }
+}
+
+pub struct Executor {
+    pub registry: Registry,
+    prefetch_buffer_size: Family<IndexerLabel, Gauge>,
+    block_processing_duration: Family<IndexerNameLabel, Histogram>,
+    handler_duration: Family<IndexerNameLabel, Histogram>,
+    entities_per_block: Family<IndexerNameLabel, Histogram>,
+    db_write_duration: Family<IndexerNameLabel, Histogram>,
+    wasm_memory_bytes: Family<IndexerNameLabel, Gauge>,
+    cpu_seconds: Family<IndexerNameLabel, Counter<f64, AtomicU64>>,
+}
+
+impl Metric for Executor {
+    fn init() -> Self {
+        let mut registry = Registry::default();
+        let prefetch_buffer_size = Family::<IndexerLabel, Gauge>::default();
+        registry.register(
+            "executor_prefetch_buffer_size",
+            "Number of block pages fetched but not yet processed by the executor.",
+            prefetch_buffer_size.clone(),
+        );
+        let block_processing_duration =
+            Family::<IndexerNameLabel, Histogram>::new_with_constructor(|| {
+                Histogram::new(latency_buckets())
+            });
+        registry.register(
+            "executor_block_processing_duration_seconds",
+            "Time taken to process each block, including committing its writes.",
+            block_processing_duration.clone(),
+        );
+        let handler_duration =
+            Family::<IndexerNameLabel, Histogram>::new_with_constructor(|| {
+                Histogram::new(latency_buckets())
+            });
+        registry.register(
+            "executor_handler_duration_seconds",
+            "Time taken by an indexer's handlers to process a page of blocks.",
+            handler_duration.clone(),
+        );
+        let entities_per_block =
+            Family::<IndexerNameLabel, Histogram>::new_with_constructor(|| {
+                Histogram::new(count_buckets())
+            });
+        registry.register(
+            "executor_entities_written_per_block",
+            "Number of entities written to the database for each block.",
+            entities_per_block.clone(),
+        );
+        let db_write_duration =
+            Family::<IndexerNameLabel, Histogram>::new_with_constructor(|| {
+                Histogram::new(latency_buckets())
+            });
+        registry.register(
+            "executor_db_write_duration_seconds",
+            "Time taken to write an entity to the database.",
+            db_write_duration.clone(),
+        );
+        let wasm_memory_bytes = Family::<IndexerNameLabel, Gauge>::default();
+        registry.register(
+            "executor_wasm_memory_bytes",
+            "Size of a WASM indexer's linear memory.",
+            wasm_memory_bytes.clone(),
+        );
+        let cpu_seconds = Family::<IndexerNameLabel, Counter<f64, AtomicU64>>::default();
+        registry.register(
+            "executor_cpu_seconds",
+            "CPU time spent running a WASM indexer's handlers.",
+            cpu_seconds.clone(),
+        );
+        Self {
+            registry,
+            prefetch_buffer_size,
+            block_processing_duration,
+            handler_duration,
+            entities_per_block,
+            db_write_duration,
+            wasm_memory_bytes,
+            cpu_seconds,
+        }
+    }
+}
+
+impl Executor {
+    pub fn record_prefetch_buffer_size(&self, indexer: &str, size: usize) {
+        self.prefetch_buffer_size
+            .get_or_create(&IndexerLabel::new(indexer))
+            .set(size as i64);
+    }
+
+    /// Record a page of blocks buffered by the fetcher.
+    pub fn record_page_prefetched(&self, indexer: &str) {
+        self.prefetch_buffer_size
+            .get_or_create(&IndexerLabel::new(indexer))
+            .inc();
+    }
+
+    /// Record a buffered page of blocks received by the executor.
+    pub fn record_page_received(&self, indexer: &str) {
+        self.prefetch_buffer_size
+            .get_or_create(&IndexerLabel::new(indexer))
+            .dec();
+    }
+
+    /// Record a page of `num_blocks` blocks that was processed in `seconds`, and
+    /// wrote `entities` entities.
+    pub fn record_blocks_processed(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        num_blocks: usize,
+        seconds: f64,
+        entities: u64,
+    ) {
+        if num_blocks == 0 {
+            return;
+        }
+
+        let label = IndexerNameLabel::new(namespace, identifier);
+        let block_duration = self.block_processing_duration.get_or_create(&label);
+        let block_entities = self.entities_per_block.get_or_create(&label);
+        for _ in 0..num_blocks {
+            block_duration.observe(seconds / num_blocks as f64);
+            block_entities.observe(entities as f64 / num_blocks as f64);
+        }
+    }
+
+    pub fn record_handler_duration(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        seconds: f64,
+    ) {
+        self.handler_duration
+            .get_or_create(&IndexerNameLabel::new(namespace, identifier))
+            .observe(seconds);
+    }
+
+    pub fn record_db_write_duration(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        seconds: f64,
+    ) {
+        self.db_write_duration
+            .get_or_create(&IndexerNameLabel::new(namespace, identifier))
+            .observe(seconds);
+    }
+
+    pub fn record_wasm_memory(&self, namespace: &str, identifier: &str, bytes: u64) {
+        self.wasm_memory_bytes
+            .get_or_create(&IndexerNameLabel::new(namespace, identifier))
+            .set(bytes as i64);
+    }
+
+    pub fn record_cpu_time(&self, namespace: &str, identifier: &str, seconds: f64) {
+        self.cpu_seconds
+            .get_or_create(&IndexerNameLabel::new(namespace, identifier))
+            .inc_by(seconds);
+    }
 }
 
 pub struct Metrics {
     pub web: Web,
     pub db: Database,
+    pub executor: Executor,
 }
 
 impl Metric for Metrics {
@@ -111,6 +408,7 @@ impl Metric for Metrics {
         Self {
             web: Web::init(),
             db: Database::init(),
+            executor: Executor::init(),
         }
     }
 }
@@ -0,0 +1,257 @@
+use fuel_indexer_types::{
+    fuel::{BlockData, Consensus, Header},
+    scalar::Bytes32,
+};
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+};
+
+/// Per-block state shared between the handlers of an indexer module.
+///
+/// A fresh `BlockContext` is created for each block and passed (by mutable reference)
+/// to every handler that declares a `&mut BlockContext` argument. Because handlers are
+/// dispatched in priority order, a handler can store values in the context that are
+/// then read by handlers running later in the same block.
+#[derive(Debug)]
+pub struct BlockContext {
+    /// Height of the block being processed.
+    pub height: u64,
+
+    /// ID of the block being processed.
+    pub id: Bytes32,
+
+    /// Timestamp of the block being processed.
+    pub time: i64,
+
+    /// Timestamp of the previously processed block, if known.
+    pub prev_time: Option<i64>,
+
+    /// Producer of the block being processed, if known.
+    pub producer: Option<Bytes32>,
+
+    /// DA height of the block being processed.
+    pub da_height: u64,
+
+    /// Header of the block being processed.
+    pub header: Header,
+
+    /// Consensus info of the block being processed.
+    pub consensus: Consensus,
+
+    /// Values stored by handlers, keyed by their type.
+    values: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl BlockContext {
+    /// Create a new `BlockContext` for the given block.
+    pub fn new(block: &BlockData, prev_time: Option<i64>) -> Self {
+        Self {
+            height: block.height,
+            id: block.id,
+            time: block.time,
+            prev_time,
+            producer: block.producer,
+            da_height: block.header.da_height,
+            header: block.header.clone(),
+            consensus: block.consensus.clone(),
+            values: HashMap::new(),
+        }
+    }
+
+    /// Create a new `BlockContext` for the same block as this one, without any
+    /// of the values stored in this context.
+    pub fn for_same_block(&self) -> Self {
+        Self {
+            height: self.height,
+            id: self.id,
+            time: self.time,
+            prev_time: self.prev_time,
+            producer: self.producer,
+            da_height: self.da_height,
+            header: self.header.clone(),
+            consensus: self.consensus.clone(),
+            values: HashMap::new(),
+        }
+    }
+
+    /// Whether this block's height is a multiple of `n`.
+    pub fn block_interval_elapsed(&self, n: u64) -> bool {
+        n != 0 && self.height % n == 0
+    }
+
+    /// Whether this block is the first block of a new `secs`-second interval of chain time.
+    ///
+    /// If the previous block is not known, the interval is considered elapsed.
+    pub fn time_interval_elapsed(&self, secs: u64) -> bool {
+        if secs == 0 {
+            return false;
+        }
+        let secs = secs as i64;
+        match self.prev_time {
+            Some(prev_time) => prev_time.div_euclid(secs) != self.time.div_euclid(secs),
+            None => true,
+        }
+    }
+
+    /// Store a value in the context, returning the previous value of the same type, if any.
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|prev| prev.downcast::<T>().ok())
+            .map(|prev| *prev)
+    }
+
+    /// Return a reference to the stored value of the given type, if any.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+
+    /// Return a mutable reference to the stored value of the given type, if any.
+    pub fn get_mut<T: Any + Send + Sync>(&mut self) -> Option<&mut T> {
+        self.values
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut::<T>())
+    }
+
+    /// Return a mutable reference to the stored value of the given type, inserting
+    /// the default value if none exists.
+    pub fn get_or_default<T: Any + Send + Sync + Default>(&mut self) -> &mut T {
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(T::default()))
+            .downcast_mut::<T>()
+            .expect("BlockContext value has unexpected type.")
+    }
+
+    /// Remove and return the stored value of the given type, if any.
+    pub fn remove<T: Any + Send + Sync>(&mut self) -> Option<T> {
+        self.values
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast::<T>().ok())
+            .map(|value| *value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(height: u64, time: i64, prev_time: Option<i64>) -> BlockContext {
+        BlockContext {
+            height,
+            id: Bytes32::default(),
+            time,
+            prev_time,
+            producer: None,
+            da_height: 0,
+            header: Header {
+                id: Bytes32::default(),
+                da_height: 0,
+                transactions_count: 0,
+                output_messages_count: 0,
+                transactions_root: Bytes32::default(),
+                output_messages_root: Bytes32::default(),
+                height,
+                prev_root: Bytes32::default(),
+                time,
+                application_hash: Bytes32::default(),
+            },
+            consensus: Consensus::default(),
+            values: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_block_interval_elapsed_on_multiples_of_the_interval() {
+        let elapsed = (1..=10)
+            .filter(|height| context(*height, 0, None).block_interval_elapsed(5))
+            .collect::<Vec<_>>();
+        assert_eq!(elapsed, vec![5, 10]);
+
+        assert!(context(7, 0, None).block_interval_elapsed(1));
+        assert!(!context(5, 0, None).block_interval_elapsed(0));
+    }
+
+    #[test]
+    fn test_time_interval_elapsed_once_per_interval() {
+        // Blocks every 20 seconds trigger a 60-second handler once per minute.
+        let times = [0, 20, 40, 60, 80, 100, 120];
+        let elapsed = times
+            .windows(2)
+            .filter(|pair| context(0, pair[1], Some(pair[0])).time_interval_elapsed(60))
+            .map(|pair| pair[1])
+            .collect::<Vec<_>>();
+        assert_eq!(elapsed, vec![60, 120]);
+
+        // Without a previous block, the interval is considered elapsed.
+        assert!(context(0, 20, None).time_interval_elapsed(60));
+    }
+
+    #[test]
+    fn test_time_interval_elapsed_at_interval_boundaries() {
+        // The first block at or past a multiple of the interval starts a new interval.
+        assert!(context(0, 60, Some(59)).time_interval_elapsed(60));
+        assert!(!context(0, 119, Some(60)).time_interval_elapsed(60));
+        assert!(context(0, 120, Some(119)).time_interval_elapsed(60));
+
+        // Blocks with the same time, or within the same interval, don't.
+        assert!(!context(0, 60, Some(60)).time_interval_elapsed(60));
+        assert!(!context(0, 61, Some(60)).time_interval_elapsed(60));
+
+        // Skipping several intervals only starts one.
+        assert!(context(0, 600, Some(59)).time_interval_elapsed(60));
+
+        // Intervals are aligned on multiples of the interval, including before the epoch.
+        assert!(context(0, 0, Some(-1)).time_interval_elapsed(60));
+        assert!(!context(0, -1, Some(-60)).time_interval_elapsed(60));
+        assert!(context(0, -60, Some(-61)).time_interval_elapsed(60));
+
+        // An interval of one second elapses on every new second.
+        assert!(context(0, 2, Some(1)).time_interval_elapsed(1));
+        assert!(!context(0, 2, Some(2)).time_interval_elapsed(1));
+
+        assert!(!context(0, 60, Some(59)).time_interval_elapsed(0));
+    }
+
+    #[test]
+    fn test_values_are_keyed_by_type() {
+        #[derive(Debug, Default, PartialEq)]
+        struct Total(u64);
+
+        #[derive(Debug, Default, PartialEq)]
+        struct Count(u64);
+
+        let mut ctx = context(1, 0, None);
+        assert_eq!(ctx.insert(Total(5)), None);
+
+        // Values of another type aren't found, even with the same layout.
+        assert_eq!(ctx.get::<Count>(), None);
+        assert_eq!(ctx.get_mut::<Count>(), None);
+        assert_eq!(ctx.get::<u64>(), None);
+        assert_eq!(ctx.remove::<Count>(), None);
+        assert_eq!(ctx.get::<Total>(), Some(&Total(5)));
+
+        // Inserting a value of the same type replaces it.
+        assert_eq!(ctx.insert(Total(7)), Some(Total(5)));
+        ctx.get_mut::<Total>().unwrap().0 += 1;
+        assert_eq!(ctx.get::<Total>(), Some(&Total(8)));
+
+        // A default value is only inserted for types without a value.
+        ctx.get_or_default::<Count>().0 += 1;
+        ctx.get_or_default::<Count>().0 += 1;
+        assert_eq!(ctx.get::<Count>(), Some(&Count(2)));
+        assert_eq!(ctx.get_or_default::<Total>(), &Total(8));
+
+        assert_eq!(ctx.remove::<Total>(), Some(Total(8)));
+        assert_eq!(ctx.get::<Total>(), None);
+        assert_eq!(ctx.get::<Count>(), Some(&Count(2)));
+
+        // Values aren't carried over to a context for the same block.
+        let same_block = ctx.for_same_block();
+        assert_eq!(same_block.get::<Count>(), None);
+        assert_eq!(same_block.height, ctx.height);
+    }
+}
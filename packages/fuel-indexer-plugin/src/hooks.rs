@@ -0,0 +1,14 @@
+/// Hooks run by the generated `Entity::save` of entities declared with
+/// `@entity(hooks: true)`.
+///
+/// Entities with hooks must implement this trait, overriding the hooks they need.
+pub trait EntityHooks {
+    /// Called with a copy of the entity before it is saved. Changes made to the
+    /// entity are saved, and returning an error skips the save.
+    fn before_save(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called with the saved entity after it is saved.
+    fn after_save(&self) {}
+}
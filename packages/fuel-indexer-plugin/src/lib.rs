@@ -1,15 +1,24 @@
 #![deny(unused_crate_dependencies)]
 
+pub mod context;
+pub mod hooks;
+pub mod logging;
 #[cfg(feature = "native-execution")]
 pub mod native;
 pub mod wasm;
 
 pub mod types {
+    pub use crate::context::BlockContext;
+    pub use crate::hooks::EntityHooks;
+    pub use crate::logging::set_log_block_height;
     pub use fuel_indexer_schema::{
         join::{JoinMetadata, RawQuery},
+        query::{Comparison, EntityQuery, Filter},
         FtColumn,
     };
-    pub use fuel_indexer_types::fuel::{BlockData, TxId};
+    pub use fuel_indexer_types::fuel::{
+        BlockData, MessageIn, PredicateSpend, ScriptCall, TxId,
+    };
 
     // Traits needed to access client type fields. Could also include this as a sub-module
     // of `fuel_indexer_types::fuel`.
@@ -46,8 +55,17 @@ pub mod prelude {
     pub use crate::{debug, error, info, trace, warn};
 }
 
+// The logging macros take an optional list of `key = value` fields, separated from the
+// message by a semicolon, e.g. `info!(amount = transfer.amount; "Indexed transfer")`.
 #[macro_export]
 macro_rules! error {
+    ($($key:ident = $value:expr),+ ; $($arg:tt)+) => {{
+        Logger::log(
+            LOG_LEVEL_ERROR,
+            &format!($($arg)+),
+            vec![$((stringify!($key).to_string(), format!("{}", $value))),+],
+        )
+    }};
     ($($arg:tt)*) => {{
         Logger::error(&format!($($arg)*))
     }};
@@ -55,6 +73,13 @@ macro_rules! error {
 
 #[macro_export]
 macro_rules! warn {
+    ($($key:ident = $value:expr),+ ; $($arg:tt)+) => {{
+        Logger::log(
+            LOG_LEVEL_WARN,
+            &format!($($arg)+),
+            vec![$((stringify!($key).to_string(), format!("{}", $value))),+],
+        )
+    }};
     ($($arg:tt)*) => {{
         Logger::warn(&format!($($arg)*))
     }};
@@ -62,6 +87,13 @@ macro_rules! warn {
 
 #[macro_export]
 macro_rules! info {
+    ($($key:ident = $value:expr),+ ; $($arg:tt)+) => {{
+        Logger::log(
+            LOG_LEVEL_INFO,
+            &format!($($arg)+),
+            vec![$((stringify!($key).to_string(), format!("{}", $value))),+],
+        )
+    }};
     ($($arg:tt)*) => {{
         Logger::info(&format!($($arg)*))
     }};
@@ -69,6 +101,13 @@ macro_rules! info {
 
 #[macro_export]
 macro_rules! debug {
+    ($($key:ident = $value:expr),+ ; $($arg:tt)+) => {{
+        Logger::log(
+            LOG_LEVEL_DEBUG,
+            &format!($($arg)+),
+            vec![$((stringify!($key).to_string(), format!("{}", $value))),+],
+        )
+    }};
     ($($arg:tt)*) => {{
         Logger::debug(&format!($($arg)*))
     }};
@@ -76,6 +115,13 @@ macro_rules! debug {
 
 #[macro_export]
 macro_rules! trace {
+    ($($key:ident = $value:expr),+ ; $($arg:tt)+) => {{
+        Logger::log(
+            LOG_LEVEL_TRACE,
+            &format!($($arg)+),
+            vec![$((stringify!($key).to_string(), format!("{}", $value))),+],
+        )
+    }};
     ($($arg:tt)*) => {{
         Logger::trace(&format!($($arg)*))
     }};
@@ -0,0 +1,21 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Marker for when no block is being processed.
+const NO_BLOCK_HEIGHT: u64 = u64::MAX;
+
+/// Height of the block being processed, attached to structured log records.
+static BLOCK_HEIGHT: AtomicU64 = AtomicU64::new(NO_BLOCK_HEIGHT);
+
+/// Set the height of the block being processed. Called by the generated indexer
+/// code before the handlers of each block run.
+pub fn set_log_block_height(height: u64) {
+    BLOCK_HEIGHT.store(height, Ordering::Relaxed);
+}
+
+/// Height of the block being processed, if any.
+pub fn log_block_height() -> Option<u64> {
+    match BLOCK_HEIGHT.load(Ordering::Relaxed) {
+        NO_BLOCK_HEIGHT => None,
+        height => Some(height),
+    }
+}
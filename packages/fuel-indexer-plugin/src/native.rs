@@ -1,13 +1,17 @@
 extern crate alloc;
 
+use crate::logging::log_block_height;
 use alloc::vec::Vec;
 use async_trait;
-use fuel_indexer_schema::{join::JoinMetadata, FtColumn};
+use fuel_indexer_schema::{join::JoinMetadata, query::EntityQuery, FtColumn};
+use fuel_indexer_types::ffi::{
+    LOG_LEVEL_DEBUG, LOG_LEVEL_ERROR, LOG_LEVEL_INFO, LOG_LEVEL_TRACE, LOG_LEVEL_WARN,
+};
 
 pub use anyhow;
 pub use fuel_indexer::prelude::{
     Arc, Database, IndexerError, IndexerResult, IndexerService, Mutex,
-    NativeIndexExecutor,
+    NativeIndexExecutor, TRANSACTION_INDEX,
 };
 pub use fuel_indexer_api_server::api::WebApi;
 pub use fuel_indexer_database::{queries, IndexerConnectionPool};
@@ -50,6 +54,35 @@ impl Logger {
     pub fn trace(log: &str) {
         tracing::trace!(log);
     }
+
+    /// Log a message with key-value fields, tagged with the height of the block
+    /// being processed.
+    pub fn log(level: u32, message: &str, fields: Vec<(String, String)>) {
+        let block_height = log_block_height();
+        let fields = fields
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        match level {
+            LOG_LEVEL_ERROR => {
+                tracing::error!(block_height = ?block_height, fields = %fields, "{message}")
+            }
+            LOG_LEVEL_WARN => {
+                tracing::warn!(block_height = ?block_height, fields = %fields, "{message}")
+            }
+            LOG_LEVEL_INFO => {
+                tracing::info!(block_height = ?block_height, fields = %fields, "{message}")
+            }
+            LOG_LEVEL_DEBUG => {
+                tracing::debug!(block_height = ?block_height, fields = %fields, "{message}")
+            }
+            _ => {
+                tracing::trace!(block_height = ?block_height, fields = %fields, "{message}")
+            }
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -69,5 +102,8 @@ pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
 
     async fn load(id: u64) -> Option<Self>;
 
+    /// Load all entities that match the given query.
+    async fn find_many(query: EntityQuery) -> Vec<Self>;
+
     async fn save(&self);
 }
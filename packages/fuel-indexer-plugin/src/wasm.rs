@@ -1,5 +1,6 @@
 extern crate alloc;
 
+use crate::logging::log_block_height;
 use alloc::vec::Vec;
 use fuel_indexer_lib::{
     graphql::MAX_FOREIGN_KEY_LIST_FIELDS,
@@ -7,9 +8,13 @@ use fuel_indexer_lib::{
 };
 use fuel_indexer_schema::{
     join::{JoinMetadata, RawQuery},
+    query::EntityQuery,
     FtColumn,
 };
-use fuel_indexer_types::ffi::*;
+use fuel_indexer_types::{
+    ffi::*,
+    fuel::{Address, AssetId, BlockData, Coin},
+};
 
 pub use bincode;
 pub use hex::FromHex;
@@ -19,9 +24,107 @@ pub use std::collections::{HashMap, HashSet};
 extern "C" {
     // TODO: error codes? or just panic and let the runtime handle it?
     fn ff_get_object(type_id: i64, ptr: *const u8, len: *mut u8) -> *mut u8;
+    fn ff_find_objects(
+        type_id: i64,
+        ptr: *const u8,
+        len: u32,
+        out_len: *mut u8,
+    ) -> *mut u8;
     fn ff_put_object(type_id: i64, ptr: *const u8, len: u32);
     fn ff_put_many_to_many_record(ptr: *const u8, len: u32);
     fn ff_log_data(ptr: *const u8, len: u32, log_level: u32);
+    fn ff_log_record(ptr: *const u8, len: u32);
+    fn ff_register_contract(ptr: *const u8, len: u32);
+    fn ff_get_discovered_contracts(len: *mut u8) -> *mut u8;
+    fn ff_get_balance(ptr: *const u8, len: u32) -> u64;
+    fn ff_get_coins(ptr: *const u8, len: u32, out_len: *mut u8) -> *mut u8;
+    fn ff_http_get(ptr: *const u8, len: u32, out_len: *mut u8) -> *mut u8;
+    fn ff_get_block(index: u32, out_len: *mut u8) -> *mut u8;
+}
+
+/// Pull the block at the given index of the page being handled from the indexer
+/// service, or `None` if the page has no such block.
+pub fn get_block(index: u32) -> Option<BlockData> {
+    unsafe {
+        let mut buflen = 0u32.to_le_bytes();
+        let ptr = ff_get_block(index, buflen.as_mut_ptr());
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        let len = u32::from_le_bytes(buflen) as usize;
+        let bytes = Vec::from_raw_parts(ptr, len, len);
+        Some(deserialize(&bytes).expect("Bad serialization."))
+    }
+}
+
+/// Persist a contract discovered at runtime, to be indexed using the given template.
+pub fn register_discovered_contract(template: &str, contract_id: &str) {
+    let bytes = serialize(&(template.to_string(), contract_id.to_string()));
+    unsafe { ff_register_contract(bytes.as_ptr(), bytes.len() as u32) }
+}
+
+/// Return all contracts discovered at runtime, as `(template, contract_id)` pairs.
+pub fn discovered_contracts() -> Vec<(String, String)> {
+    unsafe {
+        let mut buflen = 0u32.to_le_bytes();
+        let ptr = ff_get_discovered_contracts(buflen.as_mut_ptr());
+
+        if ptr.is_null() {
+            return Vec::new();
+        }
+
+        let len = u32::from_le_bytes(buflen) as usize;
+        let bytes = Vec::from_raw_parts(ptr, len, len);
+        deserialize(&bytes).expect("Bad serialization.")
+    }
+}
+
+/// Return the balance of the given asset owned by the given address.
+///
+/// The balance is queried from the Fuel node and cached until the next block.
+pub fn get_balance(owner: &Address, asset_id: &AssetId) -> u64 {
+    let bytes = serialize(&(owner, asset_id));
+    unsafe { ff_get_balance(bytes.as_ptr(), bytes.len() as u32) }
+}
+
+/// Return the unspent coins of the given asset owned by the given address.
+///
+/// The coins are queried from the Fuel node and cached until the next block.
+pub fn get_coins(owner: &Address, asset_id: &AssetId) -> Vec<Coin> {
+    let bytes = serialize(&(owner, asset_id));
+    unsafe {
+        let mut buflen = 0u32.to_le_bytes();
+        let ptr = ff_get_coins(bytes.as_ptr(), bytes.len() as u32, buflen.as_mut_ptr());
+
+        if ptr.is_null() {
+            return Vec::new();
+        }
+
+        let len = u32::from_le_bytes(buflen) as usize;
+        let bytes = Vec::from_raw_parts(ptr, len, len);
+        deserialize(&bytes).expect("Bad serialization.")
+    }
+}
+
+/// Fetch the body of the given URL with an HTTP GET request.
+///
+/// Requires the `allow_http` capability. Requests are rate-limited by the indexer
+/// service, and `None` is returned if the request fails, the URL's host is not a
+/// public address, or the response is too large.
+pub fn http_get(url: &str) -> Option<Vec<u8>> {
+    unsafe {
+        let mut buflen = 0u32.to_le_bytes();
+        let ptr = ff_http_get(url.as_ptr(), url.len() as u32, buflen.as_mut_ptr());
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        let len = u32::from_le_bytes(buflen) as usize;
+        Some(Vec::from_raw_parts(ptr, len, len))
+    }
 }
 
 // TODO: more to do here, hook up to 'impl log::Log for Logger'
@@ -47,6 +150,19 @@ impl Logger {
     pub fn trace(log: &str) {
         unsafe { ff_log_data(log.as_ptr(), log.len() as u32, LOG_LEVEL_TRACE) }
     }
+
+    /// Log a message with key-value fields, tagged with the height of the block
+    /// being processed.
+    pub fn log(level: u32, message: &str, fields: Vec<(String, String)>) {
+        let record = LogRecord {
+            level,
+            message: message.to_string(),
+            fields,
+            block_height: log_block_height(),
+        };
+        let bytes = serialize(&record);
+        unsafe { ff_log_record(bytes.as_ptr(), bytes.len() as u32) }
+    }
 }
 
 pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
@@ -94,7 +210,41 @@ pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
         }
     }
 
+    /// Load all entities that match the given query.
+    fn find_many(query: EntityQuery) -> Vec<Self> {
+        let bytes = serialize(&query);
+        unsafe {
+            let mut buflen = 0u32.to_le_bytes();
+            let ptr = ff_find_objects(
+                Self::TYPE_ID,
+                bytes.as_ptr(),
+                bytes.len() as u32,
+                buflen.as_mut_ptr(),
+            );
+
+            if ptr.is_null() {
+                return Vec::new();
+            }
+
+            let len = u32::from_le_bytes(buflen) as usize;
+            let bytes = Vec::from_raw_parts(ptr, len, len);
+            let objects: Vec<Vec<u8>> = deserialize(&bytes).expect("Bad serialization.");
+
+            objects
+                .iter()
+                .map(|object| {
+                    Self::from_row(deserialize(object).expect("Bad serialization."))
+                })
+                .collect()
+        }
+    }
+
     fn save(&self) {
+        self.put();
+    }
+
+    /// Write the entity, without running any `EntityHooks`.
+    fn put(&self) {
         unsafe {
             let buf = serialize(&self.to_row());
             ff_put_object(Self::TYPE_ID, buf.as_ptr(), buf.len() as u32)
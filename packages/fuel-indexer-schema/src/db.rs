@@ -8,6 +8,7 @@
 //! to WebAssembly (WASM).
 
 pub mod manager;
+pub mod migration;
 pub mod tables;
 
 use fuel_indexer_database::IndexerDatabaseError;
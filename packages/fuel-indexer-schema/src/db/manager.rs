@@ -3,7 +3,7 @@
 //! A wrapper object that encapsulates `IndexerSchema` and provides stateful database
 //! connectivity.
 
-use crate::db::{tables::IndexerSchema, IndexerSchemaDbResult};
+use crate::db::{migration::MigrationPlan, tables::IndexerSchema, IndexerSchemaDbResult};
 use fuel_indexer_database::{queries, IndexerConnection, IndexerConnectionPool};
 use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
 use tracing::info;
@@ -46,6 +46,36 @@ impl SchemaManager {
         Ok(())
     }
 
+    /// Plan the deployment of a new schema for the given indexer, without
+    /// changing the database.
+    pub async fn plan_schema(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        schema: GraphQLSchema,
+        exec_source: ExecutionSource,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<MigrationPlan> {
+        let new = IndexerSchema::new(
+            namespace,
+            identifier,
+            &schema,
+            self.pool.database_type(),
+            exec_source,
+        )?;
+
+        let current = if queries::get_indexer_id(conn, namespace, identifier)
+            .await
+            .is_ok()
+        {
+            Some(self.load_schema(namespace, identifier).await?)
+        } else {
+            None
+        };
+
+        Ok(MigrationPlan::new(current.as_ref(), &new))
+    }
+
     /// Load an existing schema for the given indexer.
     pub async fn load_schema(
         &self,
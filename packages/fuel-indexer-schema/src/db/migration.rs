@@ -0,0 +1,289 @@
+//! # fuel_indexer_schema::db::migration
+//!
+//! Compares a new version of an indexer's GraphQL schema to the version that is
+//! deployed, so that the SQL executed by a deployment can be reviewed beforehand.
+
+use crate::db::tables::IndexerSchema;
+use fuel_indexer_database::types::{Column, SqlFragment, SqlNamed, Table};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+/// A change made to an indexer's tables by a new version of its schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum SchemaChange {
+    /// A table is added.
+    AddTable { table: String },
+
+    /// A table is removed, along with its data.
+    DropTable { table: String },
+
+    /// A column is added to an existing table.
+    AddColumn { table: String, column: String },
+
+    /// A column is removed from an existing table, along with its data.
+    DropColumn { table: String, column: String },
+
+    /// The definition of an existing column changes.
+    AlterColumn {
+        table: String,
+        column: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl SchemaChange {
+    /// Whether this change removes or rewrites data that is already indexed.
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            SchemaChange::DropTable { .. }
+                | SchemaChange::DropColumn { .. }
+                | SchemaChange::AlterColumn { .. }
+        )
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::AddTable { table } => write!(f, "+ table {table}"),
+            SchemaChange::DropTable { table } => write!(f, "- table {table}"),
+            SchemaChange::AddColumn { table, column } => {
+                write!(f, "+ column {table}.{column}")
+            }
+            SchemaChange::DropColumn { table, column } => {
+                write!(f, "- column {table}.{column}")
+            }
+            SchemaChange::AlterColumn {
+                table,
+                column,
+                from,
+                to,
+            } => write!(f, "~ column {table}.{column}: {from} -> {to}"),
+        }
+    }
+}
+
+/// The SQL that deploying a version of an indexer's schema would execute, and how
+/// it changes the indexer's tables.
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationPlan {
+    /// Version of the deployed schema, if the indexer is deployed.
+    pub current_version: Option<String>,
+
+    /// Version of the new schema.
+    pub version: String,
+
+    /// Changes made to the indexer's tables.
+    pub changes: Vec<SchemaChange>,
+
+    /// Whether any of the changes are destructive.
+    pub destructive: bool,
+
+    /// SQL statements executed when the new schema is deployed, in order.
+    pub statements: Vec<String>,
+}
+
+impl MigrationPlan {
+    /// Plan the deployment of `new`, given the `current` schema of the indexer.
+    pub fn new(current: Option<&IndexerSchema>, new: &IndexerSchema) -> Self {
+        let changes = match current {
+            Some(current) => diff(current.tables(), new.tables()),
+            None => tables(new.tables())
+                .into_keys()
+                .map(|table| SchemaChange::AddTable { table })
+                .collect(),
+        };
+
+        let mut statements = Vec::new();
+
+        // Redeploying an indexer replaces all of its tables, which are then
+        // rebuilt as the indexer reindexes from its start block.
+        if current.is_some() {
+            statements.push(format!(
+                "DROP SCHEMA IF EXISTS {} CASCADE;",
+                new.parsed().fully_qualified_namespace()
+            ));
+        }
+        statements.extend(new.statements());
+
+        Self {
+            current_version: current.map(|schema| schema.version().to_string()),
+            version: new.version().to_string(),
+            destructive: changes.iter().any(SchemaChange::is_destructive),
+            changes,
+            statements,
+        }
+    }
+}
+
+/// Return the persisted tables by name, sorted by name.
+fn tables(tables: &[Table]) -> BTreeMap<String, &Table> {
+    tables
+        .iter()
+        .filter(|table| !table.create().is_empty())
+        .map(|table| (table.sql_name(), table))
+        .collect()
+}
+
+/// Definition of a column, excluding its name.
+fn column_definition(column: &Column) -> String {
+    let mut definition = column.sql_type();
+    if !column.nullable {
+        definition.push_str(" not null");
+    }
+    if column.unique {
+        definition.push_str(" unique");
+    }
+    definition
+}
+
+/// Return the changes made to `current` by `new`.
+fn diff(current: &[Table], new: &[Table]) -> Vec<SchemaChange> {
+    let current = tables(current);
+    let new = tables(new);
+    let mut changes = Vec::new();
+
+    for (name, table) in new.iter() {
+        let existing = match current.get(name) {
+            Some(existing) => existing,
+            None => {
+                changes.push(SchemaChange::AddTable {
+                    table: name.clone(),
+                });
+                continue;
+            }
+        };
+
+        let existing_columns = existing
+            .columns()
+            .iter()
+            .map(|column| (column.name.as_str(), column))
+            .collect::<HashMap<_, _>>();
+
+        for column in table.columns() {
+            match existing_columns.get(column.name.as_str()) {
+                None => changes.push(SchemaChange::AddColumn {
+                    table: name.clone(),
+                    column: column.name.clone(),
+                }),
+                Some(existing) => {
+                    let (from, to) =
+                        (column_definition(existing), column_definition(column));
+                    if from != to {
+                        changes.push(SchemaChange::AlterColumn {
+                            table: name.clone(),
+                            column: column.name.clone(),
+                            from,
+                            to,
+                        });
+                    }
+                }
+            }
+        }
+
+        for column in existing.columns() {
+            if !table.columns().iter().any(|c| c.name == column.name) {
+                changes.push(SchemaChange::DropColumn {
+                    table: name.clone(),
+                    column: column.name.clone(),
+                });
+            }
+        }
+    }
+
+    for name in current.keys() {
+        if !new.contains_key(name) {
+            changes.push(SchemaChange::DropTable {
+                table: name.clone(),
+            });
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_database::DbType;
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+
+    fn indexer_schema(schema: &str) -> IndexerSchema {
+        IndexerSchema::new(
+            "test_namespace",
+            "index1",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_migration_plan_flags_destructive_changes() {
+        let current = indexer_schema(
+            r#"
+type Thing @entity {
+    id: ID!
+    name: Charfield!
+    count: UInt4!
+}
+
+type Other @entity {
+    id: ID!
+}"#,
+        );
+        let new = indexer_schema(
+            r#"
+type Thing @entity {
+    id: ID!
+    count: UInt8!
+    note: Charfield
+}
+
+type Another @entity {
+    id: ID!
+}"#,
+        );
+
+        let plan = MigrationPlan::new(Some(&current), &new);
+
+        assert!(plan.destructive);
+        assert_eq!(
+            plan.changes,
+            vec![
+                SchemaChange::AddTable {
+                    table: "another".to_string()
+                },
+                SchemaChange::AlterColumn {
+                    table: "thing".to_string(),
+                    column: "count".to_string(),
+                    from: "integer not null".to_string(),
+                    to: "numeric(20, 0) not null".to_string(),
+                },
+                SchemaChange::AddColumn {
+                    table: "thing".to_string(),
+                    column: "note".to_string(),
+                },
+                SchemaChange::DropColumn {
+                    table: "thing".to_string(),
+                    column: "name".to_string(),
+                },
+                SchemaChange::DropTable {
+                    table: "other".to_string(),
+                },
+            ]
+        );
+        assert!(plan.statements[0].starts_with("DROP SCHEMA"));
+
+        let plan = MigrationPlan::new(None, &new);
+        assert!(!plan.destructive);
+        assert!(plan.statements[0].starts_with("CREATE SCHEMA"));
+    }
+}
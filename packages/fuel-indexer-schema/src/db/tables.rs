@@ -46,18 +46,17 @@ impl IndexerSchema {
         db_type: DbType,
         exec_source: ExecutionSource,
     ) -> IndexerSchemaDbResult<Self> {
+        let parsed =
+            ParsedGraphQLSchema::new(namespace, identifier, exec_source, Some(schema))?;
+        let tables = schema_tables(&parsed);
+
         Ok(IndexerSchema {
             db_type,
             namespace: namespace.to_string(),
             identifier: identifier.to_string(),
             schema: schema.to_owned(),
-            parsed: ParsedGraphQLSchema::new(
-                namespace,
-                identifier,
-                exec_source,
-                Some(schema),
-            )?,
-            tables: Vec::new(),
+            parsed,
+            tables,
         })
     }
 
@@ -85,7 +84,6 @@ impl IndexerSchema {
             Some(schema),
         )?;
 
-        let mut statements = Vec::new();
         self.schema = schema.to_owned();
         self.parsed = parsed_schema;
 
@@ -99,17 +97,6 @@ impl IndexerSchema {
 
         queries::new_graph_root(conn, root).await?;
 
-        // TODO: Abstract this into a SQLSchema (or named something else)?
-        match self.db_type {
-            DbType::Postgres => {
-                let create = format!(
-                    "CREATE SCHEMA IF NOT EXISTS {};",
-                    self.parsed.fully_qualified_namespace(),
-                );
-                statements.push(create);
-            }
-        }
-
         let mut type_ids = self
             .parsed
             .type_defs()
@@ -136,24 +123,7 @@ impl IndexerSchema {
 
         queries::type_id_insert(conn, type_ids).await?;
 
-        let mut tables = self
-            .parsed
-            .non_enum_typdefs()
-            .iter()
-            .map(|(_, t)| Table::from_typedef(t, &self.parsed))
-            .collect::<Vec<Table>>();
-
-        let mut join_tables = self
-            .parsed
-            .join_table_meta()
-            .iter()
-            .flat_map(|(_, meta)| {
-                meta.iter()
-                    .map(|m| Table::from_join_meta(m.to_owned(), &self.parsed))
-            })
-            .collect::<Vec<Table>>();
-
-        tables.append(&mut join_tables);
+        let tables = schema_tables(&self.parsed);
 
         let columns = tables
             .iter()
@@ -163,7 +133,45 @@ impl IndexerSchema {
 
         queries::new_column_insert(conn, columns).await?;
 
-        let table_stmnts = tables
+        self.tables = tables;
+
+        let statements = self.statements();
+
+        for stmnt in statements.iter() {
+            queries::execute_query(conn, stmnt.to_owned()).await?;
+        }
+
+        Ok(self)
+    }
+
+    /// Return the version of this schema.
+    pub fn version(&self) -> &str {
+        self.schema.version()
+    }
+
+    /// Return the tables of this schema.
+    pub fn tables(&self) -> &Vec<Table> {
+        &self.tables
+    }
+
+    /// Return the SQL statements that create this schema's tables and their
+    /// constraints, in the order in which they must be executed.
+    pub fn statements(&self) -> Vec<String> {
+        let mut statements = Vec::new();
+
+        // TODO: Abstract this into a SQLSchema (or named something else)?
+        match self.db_type {
+            DbType::Postgres => {
+                let create = format!(
+                    "CREATE SCHEMA IF NOT EXISTS {};",
+                    self.parsed.fully_qualified_namespace(),
+                );
+                statements.push(create);
+            }
+        }
+
+        let table_stmnts = self
+            .tables
             .iter()
             .filter_map(|t| {
                 let stmnt = t.create();
@@ -175,7 +183,8 @@ impl IndexerSchema {
             .collect::<Vec<String>>();
         statements.extend(table_stmnts);
 
-        let constraint_stmnts = tables
+        let constraint_stmnts = self
+            .tables
             .iter()
             .flat_map(|t| t.constraints())
             .map(|c| c.create())
@@ -183,13 +192,16 @@ impl IndexerSchema {
 
         statements.extend(constraint_stmnts);
 
-        for stmnt in statements.iter() {
-            queries::execute_query(conn, stmnt.to_owned()).await?;
-        }
-
-        self.tables = tables;
+        statements
+    }
 
-        Ok(self)
+    /// Return the DDL that creates this schema: its statements, in the order in
+    /// which they must be executed, separated by blank lines.
+    ///
+    /// Tables are ordered by name, so the DDL generated for a schema is always the
+    /// same, and can be checked in to review how schema generation changes.
+    pub fn ddl(&self) -> String {
+        self.statements().join("\n\n")
     }
 
     /// Load a `IndexerSchema` from the database.
@@ -219,11 +231,7 @@ impl IndexerSchema {
             Some(&schema),
         )?;
 
-        let tables = parsed
-            .non_enum_typdefs()
-            .iter()
-            .map(|(_, t)| Table::from_typedef(t, &parsed))
-            .collect::<Vec<Table>>();
+        let tables = schema_tables(&parsed);
 
         Ok(IndexerSchema {
             namespace: root.schema_name,
@@ -235,3 +243,66 @@ impl IndexerSchema {
         })
     }
 }
+
+/// Return the tables, including join tables, generated from the given schema,
+/// ordered by name.
+pub(crate) fn schema_tables(parsed: &ParsedGraphQLSchema) -> Vec<Table> {
+    let mut tables = parsed
+        .non_enum_typdefs()
+        .iter()
+        .map(|(_, t)| Table::from_typedef(t, parsed))
+        .collect::<Vec<Table>>();
+
+    let mut join_tables = parsed
+        .join_table_meta()
+        .iter()
+        .flat_map(|(_, meta)| {
+            meta.iter()
+                .map(|m| Table::from_join_meta(m.to_owned(), parsed))
+        })
+        .collect::<Vec<Table>>();
+
+    tables.append(&mut join_tables);
+
+    // Type definitions are kept in a map, so order tables by name to generate the
+    // same statements from the same schema every time.
+    tables.sort_by_key(|table| table.sql_name());
+
+    tables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_ddl_matches_golden_file() {
+        let schema = r#"
+type Block @entity {
+    id: ID!
+    height: UInt8! @indexed
+    hash: Bytes32! @unique
+    time: Int8
+}
+
+type Tx @entity {
+    id: ID!
+    block: Block!
+    input_data: Json!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "ddl",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        // A change to this snapshot is a change to the tables of every deployed
+        // indexer, so review it as such before accepting it.
+        insta::assert_snapshot!(schema.ddl());
+    }
+}
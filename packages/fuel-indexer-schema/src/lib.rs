@@ -16,6 +16,7 @@ use thiserror::Error;
 pub mod db;
 
 pub mod join;
+pub mod query;
 
 /// Placeholder value for SQL `NULL` values.
 const NULL_VALUE: &str = "NULL";
@@ -84,6 +85,10 @@ pub enum FtColumn {
     Virtual(Option<Virtual>),
     BlockId(Option<BlockId>),
     Array(Option<Vec<FtColumn>>),
+    // Columns are serialized by the index of their variant when they're passed out
+    // of WASM modules, so new variants are added last.
+    I128(Option<I128>),
+    U128(Option<U128>),
 }
 
 impl FtColumn {
@@ -180,6 +185,14 @@ impl FtColumn {
                 Some(val) => format!("{val}"),
                 None => String::from(NULL_VALUE),
             },
+            FtColumn::I128(value) => match value {
+                Some(val) => format!("{val}"),
+                None => String::from(NULL_VALUE),
+            },
+            FtColumn::U128(value) => match value {
+                Some(val) => format!("{val}"),
+                None => String::from(NULL_VALUE),
+            },
             FtColumn::Timestamp(value) => match value {
                 Some(val) => format!("{val}"),
                 None => String::from(NULL_VALUE),
@@ -316,6 +329,8 @@ mod tests {
         let identity = FtColumn::Identity(Some(Identity::Address(
             Address::try_from([0x12; 32]).unwrap(),
         )));
+        let i128 = FtColumn::I128(Some(i128::MIN));
+        let u128 = FtColumn::U128(Some(u128::MAX));
 
         insta::assert_yaml_snapshot!(id.query_fragment());
         insta::assert_yaml_snapshot!(addr.query_fragment());
@@ -336,6 +351,8 @@ mod tests {
         insta::assert_yaml_snapshot!(charfield.query_fragment());
         insta::assert_yaml_snapshot!(json.query_fragment());
         insta::assert_yaml_snapshot!(identity.query_fragment());
+        insta::assert_yaml_snapshot!(i128.query_fragment());
+        insta::assert_yaml_snapshot!(u128.query_fragment());
     }
 
     #[test]
@@ -390,4 +407,29 @@ mod tests {
 
         insta::assert_yaml_snapshot!(id_none.query_fragment());
     }
+
+    #[test]
+    fn test_entity_query_to_sql() {
+        use super::query::*;
+        use super::*;
+
+        let query = EntityQuery {
+            filters: vec![
+                Filter::new("amount", Comparison::Gt, FtColumn::UInt8(Some(100))),
+                Filter::new("memo", Comparison::Eq, FtColumn::Charfield(None)),
+            ],
+            order_by: Some(("amount".to_string(), true)),
+            limit: Some(10),
+            offset: None,
+        };
+
+        assert_eq!(
+            query.to_sql("fuel_indexer_test_index1.transfer"),
+            "SELECT object FROM fuel_indexer_test_index1.transfer WHERE amount > 100 AND memo IS NULL ORDER BY amount DESC LIMIT 10"
+        );
+        assert_eq!(
+            query.columns().collect::<Vec<_>>(),
+            vec!["amount", "memo", "amount"]
+        );
+    }
 }
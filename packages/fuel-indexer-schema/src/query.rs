@@ -0,0 +1,128 @@
+use crate::{FtColumn, NULL_VALUE};
+use serde::{Deserialize, Serialize};
+
+extern crate alloc;
+
+/// Comparison applied by a `Filter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Comparison {
+    /// SQL operator for this comparison.
+    fn operator(&self) -> &'static str {
+        match self {
+            Comparison::Eq => "=",
+            Comparison::Ne => "<>",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+        }
+    }
+}
+
+/// A comparison between an entity column and a value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Filter {
+    /// Name of the column being compared.
+    pub column: String,
+
+    /// Comparison applied to the column.
+    pub comparison: Comparison,
+
+    /// Value the column is compared to.
+    pub value: FtColumn,
+}
+
+impl Filter {
+    /// Create a new `Filter`.
+    pub fn new(column: &str, comparison: Comparison, value: FtColumn) -> Self {
+        Self {
+            column: column.to_string(),
+            comparison,
+            value,
+        }
+    }
+
+    /// SQL fragment for this filter. Comparing for (in)equality with a null value
+    /// is translated to `IS [NOT] NULL`.
+    fn query_fragment(&self) -> String {
+        let value = self.value.query_fragment();
+        match (self.comparison, value.as_str()) {
+            (Comparison::Eq, NULL_VALUE) => format!("{} IS NULL", self.column),
+            (Comparison::Ne, NULL_VALUE) => format!("{} IS NOT NULL", self.column),
+            (comparison, value) => {
+                format!("{} {} {value}", self.column, comparison.operator())
+            }
+        }
+    }
+}
+
+/// A query for the entities of a single type, built by the typed query builders
+/// that are generated for each entity, and translated to SQL by the indexer service.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntityQuery {
+    /// Filters that the entities must all match.
+    pub filters: Vec<Filter>,
+
+    /// Column to order the entities by, and whether the order is descending.
+    pub order_by: Option<(String, bool)>,
+
+    /// Maximum number of entities to return.
+    pub limit: Option<u64>,
+
+    /// Number of entities to skip.
+    pub offset: Option<u64>,
+}
+
+impl EntityQuery {
+    /// Names of the columns referenced by this query.
+    pub fn columns(&self) -> impl Iterator<Item = &str> {
+        self.filters
+            .iter()
+            .map(|f| f.column.as_str())
+            .chain(self.order_by.iter().map(|(column, _)| column.as_str()))
+    }
+
+    /// SQL query selecting the serialized objects of the matching entities from
+    /// the given table.
+    ///
+    /// Column names are not escaped, so they should be checked against the table's
+    /// columns before the query is run.
+    pub fn to_sql(&self, table: &str) -> String {
+        let mut query = format!("SELECT object FROM {table}");
+
+        if !self.filters.is_empty() {
+            let filters = self
+                .filters
+                .iter()
+                .map(Filter::query_fragment)
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            query.push_str(&format!(" WHERE {filters}"));
+        }
+
+        match &self.order_by {
+            Some((column, true)) => query.push_str(&format!(" ORDER BY {column} DESC")),
+            Some((column, false)) => query.push_str(&format!(" ORDER BY {column} ASC")),
+            None => query.push_str(" ORDER BY id ASC"),
+        }
+
+        if let Some(limit) = self.limit {
+            query.push_str(&format!(" LIMIT {limit}"));
+        }
+
+        if let Some(offset) = self.offset {
+            query.push_str(&format!(" OFFSET {offset}"));
+        }
+
+        query
+    }
+}
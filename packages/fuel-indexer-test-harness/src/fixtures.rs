@@ -0,0 +1,138 @@
+use fuel_indexer_types::fuel::{
+    BlockData, Consensus, Header, Receipt, Transaction, TransactionData,
+    TransactionStatus,
+};
+
+/// Builder of a synthetic block, holding successful script transactions with the
+/// given receipts.
+///
+/// IDs and roots that handlers rarely look at are derived from the block's height
+/// and each transaction's position, so that they are distinct across fixtures.
+#[derive(Debug, Clone)]
+pub struct BlockFixture {
+    /// Height of the block.
+    height: u64,
+
+    /// UNIX timestamp of the block.
+    time: i64,
+
+    /// Fork of the chain that the block belongs to.
+    fork: u64,
+
+    /// Receipts of each transaction of the block.
+    transactions: Vec<Vec<Receipt>>,
+}
+
+impl BlockFixture {
+    /// Create a builder of an empty block at the given height.
+    pub fn new(height: u64) -> Self {
+        Self {
+            height,
+            time: height as i64,
+            fork: 0,
+            transactions: Vec::new(),
+        }
+    }
+
+    /// Set the UNIX timestamp of the block, which defaults to its height.
+    pub fn time(mut self, time: i64) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Set the fork of the chain that the block belongs to, which defaults to zero.
+    ///
+    /// Blocks of different forks have distinct IDs, so that blocks replacing others
+    /// in a reorg can be told apart from them.
+    pub fn fork(mut self, fork: u64) -> Self {
+        self.fork = fork;
+        self
+    }
+
+    /// Add a transaction with the given receipts to the block.
+    pub fn transaction(mut self, receipts: Vec<Receipt>) -> Self {
+        self.transactions.push(receipts);
+        self
+    }
+
+    /// Build the block.
+    pub fn build(self) -> BlockData {
+        let id = digest(self.height, self.fork, 0);
+        let transactions = self
+            .transactions
+            .into_iter()
+            .enumerate()
+            .map(|(i, receipts)| TransactionData {
+                transaction: Transaction::default(),
+                status: TransactionStatus::Success {
+                    block: id.into(),
+                    time: self.time as u64,
+                    program_state: None,
+                },
+                receipts,
+                id: digest(self.height, self.fork, i as u64 + 1).into(),
+                gas_used: 0,
+                fee: 0,
+            })
+            .collect::<Vec<_>>();
+
+        BlockData {
+            height: self.height,
+            id: id.into(),
+            header: Header {
+                id: id.into(),
+                da_height: 0,
+                transactions_count: transactions.len() as u64,
+                output_messages_count: 0,
+                transactions_root: [0u8; 32].into(),
+                output_messages_root: [0u8; 32].into(),
+                height: self.height,
+                prev_root: digest(self.height.saturating_sub(1), self.fork, 0).into(),
+                time: self.time,
+                application_hash: [0u8; 32].into(),
+            },
+            producer: None,
+            time: self.time,
+            consensus: Consensus::default(),
+            transactions,
+        }
+    }
+
+    /// Return a `LogData` receipt logged by the given contract, whose `rb` register
+    /// identifies the type of the logged data.
+    pub fn log_data(contract_id: [u8; 32], rb: u64, data: Vec<u8>) -> Receipt {
+        Receipt::LogData {
+            id: contract_id.into(),
+            ra: 0,
+            rb,
+            ptr: 0,
+            len: data.len() as u64,
+            digest: [0u8; 32].into(),
+            data,
+            pc: 0,
+            is: 0,
+        }
+    }
+
+    /// Return a `ReturnData` receipt of a call to the given contract.
+    pub fn return_data(contract_id: [u8; 32], data: Vec<u8>) -> Receipt {
+        Receipt::ReturnData {
+            id: contract_id.into(),
+            ptr: 0,
+            len: data.len() as u64,
+            digest: [0u8; 32].into(),
+            data,
+            pc: 0,
+            is: 0,
+        }
+    }
+}
+
+/// Return a 32-byte value identifying the given height, fork, and position.
+fn digest(height: u64, fork: u64, position: u64) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&height.to_be_bytes());
+    bytes[8..16].copy_from_slice(&position.to_be_bytes());
+    bytes[16..24].copy_from_slice(&fork.to_be_bytes());
+    bytes
+}
@@ -0,0 +1,267 @@
+//! # Fuel Indexer Test Harness
+//!
+//! Runs an indexer's handlers in-process against synthetic blocks, with its entities
+//! held in memory, so that indexers can be unit tested without Postgres or a Fuel node.
+//!
+//! ```no_run
+//! use fuel_indexer_test_harness::{BlockFixture, TestHarness};
+//!
+//! # async fn test() -> fuel_indexer::IndexerResult<()> {
+//! let mut harness = TestHarness::wasm("hello_indexer.manifest.yaml").await?;
+//!
+//! let block = BlockFixture::new(1)
+//!     .transaction(vec![BlockFixture::log_data([1u8; 32], 0, vec![0u8; 8])])
+//!     .build();
+//! harness.run(vec![block]).await?;
+//!
+//! let greetings = harness.entities("Greeting").await;
+//! assert_eq!(greetings.len(), 1);
+//! assert_eq!(greetings[0].id(), Some(1));
+//! # Ok(())
+//! # }
+//! ```
+
+mod fixtures;
+mod mock_node;
+
+pub use fixtures::BlockFixture;
+pub use mock_node::MockNode;
+
+use fuel_indexer::{
+    block_recording::BlockRecording,
+    block_source::BlockSource,
+    prelude::{Arc, Mutex},
+    Database, DeclarativeIndexExecutor, Executor, FtColumn, IndexerConfig, IndexerError,
+    IndexerResult, Manifest, NativeIndexExecutor, WasmIndexExecutor,
+};
+use fuel_indexer_lib::{defaults, graphql::GraphQLSchema};
+use fuel_indexer_types::fuel::BlockData;
+use std::{future::Future, path::Path};
+
+/// A row of an entity's table, as written by an indexer's handlers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityRow {
+    /// Names and values of the row's columns, in the order of the table's columns.
+    columns: Vec<(String, FtColumn)>,
+}
+
+impl EntityRow {
+    /// Return the value of the given column.
+    pub fn get(&self, column: &str) -> Option<&FtColumn> {
+        self.columns
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(column))
+            .map(|(_, value)| value)
+    }
+
+    /// Return the ID of the entity.
+    pub fn id(&self) -> Option<u64> {
+        match self.get("id") {
+            Some(FtColumn::ID(id)) => *id,
+            _ => None,
+        }
+    }
+
+    /// Return the names and values of the row's columns.
+    pub fn columns(&self) -> &[(String, FtColumn)] {
+        &self.columns
+    }
+}
+
+/// Runs the handlers of an indexer against blocks, with its entities held in memory.
+///
+/// Each call to `run` handles its blocks in one transaction, as the indexer service
+/// does, so entities written by handlers that fail are discarded.
+pub struct TestHarness<E: Executor> {
+    /// Manifest of the indexer.
+    manifest: Manifest,
+
+    /// Executor running the indexer's handlers.
+    executor: E,
+
+    /// Cursor of the last block synced from a node.
+    cursor: Option<String>,
+}
+
+/// Load the manifest at the given path, and create an in-memory database for its schema.
+fn load(
+    manifest_path: impl AsRef<Path>,
+    config: &IndexerConfig,
+) -> IndexerResult<(Manifest, Database)> {
+    let manifest = Manifest::from_file(manifest_path)?;
+    let schema: GraphQLSchema = manifest.graphql_schema_content()?;
+    let db = Database::in_memory(&manifest, config, &schema)?;
+    Ok((manifest, db))
+}
+
+impl TestHarness<WasmIndexExecutor> {
+    /// Create a harness for the WASM indexer with the given manifest, whose module
+    /// must have been built.
+    pub async fn wasm(manifest_path: impl AsRef<Path>) -> IndexerResult<Self> {
+        let config = IndexerConfig::default();
+        let (manifest, db) = load(manifest_path, &config)?;
+        let bytes = manifest.module_bytes()?;
+        let executor =
+            WasmIndexExecutor::with_database(&config, &manifest, bytes, db).await?;
+        Ok(Self::new(manifest, executor))
+    }
+}
+
+impl<F> TestHarness<NativeIndexExecutor<F>>
+where
+    F: Future<Output = IndexerResult<()>> + Send,
+{
+    /// Create a harness for the native indexer with the given manifest, whose
+    /// handlers are run by the given `handle_events` function.
+    pub fn native(
+        manifest_path: impl AsRef<Path>,
+        handle_events: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
+    ) -> IndexerResult<Self> {
+        let config = IndexerConfig::default();
+        let (manifest, db) = load(manifest_path, &config)?;
+        let executor = NativeIndexExecutor::with_database(&manifest, db, handle_events);
+        Ok(Self::new(manifest, executor))
+    }
+}
+
+impl TestHarness<DeclarativeIndexExecutor> {
+    /// Create a harness for the declarative indexer with the given manifest, whose
+    /// mapping module must have been built.
+    pub fn declarative(manifest_path: impl AsRef<Path>) -> IndexerResult<Self> {
+        let config = IndexerConfig::default();
+        let (manifest, db) = load(manifest_path, &config)?;
+        let bytes = manifest.module_bytes()?;
+        let executor = DeclarativeIndexExecutor::with_database(&manifest, bytes, db)?;
+        Ok(Self::new(manifest, executor))
+    }
+}
+
+impl<E: Executor + Send> TestHarness<E> {
+    /// Create a harness running the given executor, which syncs from the manifest's
+    /// start block.
+    fn new(manifest: Manifest, executor: E) -> Self {
+        let cursor = manifest
+            .start_block()
+            .filter(|start_block| *start_block > 1)
+            .map(|start_block| (start_block - 1).to_string());
+        Self {
+            manifest,
+            executor,
+            cursor,
+        }
+    }
+
+    /// Return the manifest of the indexer.
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Run the indexer's handlers against the given blocks.
+    pub async fn run(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
+        self.executor.handle_events(blocks).await
+    }
+
+    /// Replay the blocks recorded in the given file with `fuel-indexer record-blocks`,
+    /// in order of height and in pages, as the indexer service would handle them.
+    ///
+    /// Returns the number of blocks replayed.
+    pub async fn replay(&mut self, path: impl AsRef<Path>) -> IndexerResult<usize> {
+        let blocks = BlockRecording::read(path).await?.into_blocks();
+        let count = blocks.len();
+        for page in blocks.chunks(defaults::NODE_BLOCK_PAGE_SIZE) {
+            self.run(page.to_vec()).await?;
+        }
+        Ok(count)
+    }
+
+    /// Sync with the given node: run the handlers against each page of blocks after
+    /// the last block synced, until the node has no new blocks or the manifest's end
+    /// block is reached.
+    ///
+    /// Returns the number of blocks run. If the node fails, its error is returned and
+    /// the next sync resumes after the last page that was run.
+    pub async fn sync(&mut self, node: &mut impl BlockSource) -> IndexerResult<usize> {
+        let end_block = self.manifest.end_block();
+        let mut count = 0;
+        loop {
+            let (blocks, cursor) = match node
+                .next_page(&self.cursor, defaults::NODE_BLOCK_PAGE_SIZE, end_block)
+                .await
+            {
+                Ok(page) => page,
+                Err(IndexerError::EndBlockMet) => break,
+                Err(e) => return Err(e),
+            };
+            if cursor.is_none() {
+                break;
+            }
+
+            count += blocks.len();
+            self.run(blocks).await?;
+            self.cursor = cursor;
+        }
+        Ok(count)
+    }
+
+    /// Return the rows of the given entity, ordered by ID.
+    pub async fn entities(&self, entity: &str) -> Vec<EntityRow> {
+        self.executor
+            .database()
+            .lock()
+            .await
+            .memory_rows(entity)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|columns| EntityRow { columns })
+            .collect()
+    }
+
+    /// Return the row of the given entity with the given ID.
+    pub async fn entity(&self, entity: &str, id: u64) -> Option<EntityRow> {
+        self.entities(entity)
+            .await
+            .into_iter()
+            .find(|row| row.id() == Some(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entity_row_columns_are_looked_up_by_name() {
+        let row = EntityRow {
+            columns: vec![
+                ("id".to_string(), FtColumn::ID(Some(7))),
+                ("amount".to_string(), FtColumn::UInt8(Some(100))),
+            ],
+        };
+
+        assert_eq!(row.id(), Some(7));
+        assert_eq!(row.get("Amount"), Some(&FtColumn::UInt8(Some(100))));
+        assert_eq!(row.get("missing"), None);
+    }
+
+    #[test]
+    fn test_block_recording_round_trips() {
+        let blocks = vec![
+            BlockFixture::new(2).build(),
+            BlockFixture::new(1)
+                .transaction(vec![BlockFixture::log_data([1u8; 32], 3, vec![7u8; 8])])
+                .build(),
+        ];
+
+        let recording = BlockRecording::new(blocks);
+        let decoded = BlockRecording::decode(&recording.encode()).unwrap();
+        let heights = decoded
+            .blocks()
+            .iter()
+            .map(|block| block.height)
+            .collect::<Vec<_>>();
+        assert_eq!(heights, vec![1, 2]);
+        assert_eq!(decoded.blocks()[0].transactions[0].receipts.len(), 1);
+
+        assert!(BlockRecording::decode(b"not a recording").is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::BlockFixture;
+use async_trait::async_trait;
+use fuel_indexer::{
+    block_source::{BlockPage, BlockSource},
+    IndexerError, IndexerResult,
+};
+use fuel_indexer_types::fuel::{BlockData, Receipt};
+use std::{
+    collections::VecDeque,
+    io::{Error, ErrorKind},
+    sync::{Arc, Mutex},
+};
+
+/// Chain of a mock node, and the failures scripted for its next requests.
+#[derive(Debug, Default)]
+struct MockChain {
+    /// Blocks of the chain, in order of height, starting at height 1.
+    blocks: Vec<BlockData>,
+
+    /// Fork of the chain that new blocks belong to, incremented by each reorg.
+    fork: u64,
+
+    /// Errors returned by the next requests, in order.
+    failures: VecDeque<String>,
+
+    /// Number of requests made to the node.
+    requests: usize,
+}
+
+/// A mock Fuel node, whose blocks are produced on demand by the test driving it.
+///
+/// The node is a `BlockSource`, so it can be handed to an executor with
+/// `run_executor_with_source`, or synced by a `TestHarness`. Clones of a node share
+/// its chain, so a test can keep producing blocks, reorganizing the chain, and
+/// scripting failures while an executor reads from it.
+#[derive(Debug, Clone, Default)]
+pub struct MockNode {
+    chain: Arc<Mutex<MockChain>>,
+}
+
+impl MockNode {
+    /// Create a node with an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Produce a block holding a successful script transaction for each list of
+    /// receipts given, and return its height.
+    pub fn produce(&self, transactions: Vec<Vec<Receipt>>) -> u64 {
+        let mut chain = self.chain.lock().unwrap();
+        let height = chain.blocks.len() as u64 + 1;
+        let block = transactions
+            .into_iter()
+            .fold(
+                BlockFixture::new(height).fork(chain.fork),
+                |block, receipts| block.transaction(receipts),
+            )
+            .build();
+        chain.blocks.push(block);
+        height
+    }
+
+    /// Produce the given number of empty blocks, and return the height of the last one.
+    pub fn produce_empty(&self, count: u64) -> u64 {
+        for _ in 0..count {
+            self.produce(Vec::new());
+        }
+        self.height()
+    }
+
+    /// Append the given block to the chain, e.g., one replayed from a recording.
+    ///
+    /// Panics if the block doesn't directly follow the chain's last block.
+    pub fn push(&self, block: BlockData) {
+        let mut chain = self.chain.lock().unwrap();
+        let expected = chain.blocks.len() as u64 + 1;
+        assert_eq!(
+            block.height, expected,
+            "Block {} doesn't follow the chain's last block.",
+            block.height
+        );
+        chain.blocks.push(block);
+    }
+
+    /// Drop the given number of blocks from the tip of the chain, as in a reorg.
+    ///
+    /// Blocks produced afterwards belong to a new fork, so they have IDs distinct
+    /// from the blocks they replace.
+    pub fn reorg(&self, depth: u64) {
+        let mut chain = self.chain.lock().unwrap();
+        let height = chain.blocks.len().saturating_sub(depth as usize);
+        chain.blocks.truncate(height);
+        chain.fork += 1;
+    }
+
+    /// Make the next `count` requests to the node fail with the given error, as if
+    /// the node couldn't be reached.
+    pub fn fail_next(&self, count: usize, error: impl Into<String>) {
+        let error = error.into();
+        let mut chain = self.chain.lock().unwrap();
+        chain.failures.extend(std::iter::repeat(error).take(count));
+    }
+
+    /// Return the height of the chain's last block, or zero if it has none.
+    pub fn height(&self) -> u64 {
+        self.chain.lock().unwrap().blocks.len() as u64
+    }
+
+    /// Return the block of the chain at the given height.
+    pub fn block(&self, height: u64) -> Option<BlockData> {
+        let chain = self.chain.lock().unwrap();
+        let index = height.checked_sub(1)? as usize;
+        chain.blocks.get(index).cloned()
+    }
+
+    /// Return the number of requests made to the node, including failed ones.
+    pub fn requests(&self) -> usize {
+        self.chain.lock().unwrap().requests
+    }
+}
+
+#[async_trait]
+impl BlockSource for MockNode {
+    async fn next_page(
+        &mut self,
+        cursor: &Option<String>,
+        page_size: usize,
+        end_block: Option<u64>,
+    ) -> IndexerResult<BlockPage> {
+        let mut chain = self.chain.lock().unwrap();
+        chain.requests += 1;
+
+        if let Some(error) = chain.failures.pop_front() {
+            return Err(IndexerError::IoError(Error::new(
+                ErrorKind::ConnectionRefused,
+                error,
+            )));
+        }
+
+        let after = match cursor {
+            Some(cursor) => cursor.parse::<u64>().map_err(|e| {
+                IndexerError::Unknown(format!("Invalid cursor {cursor}: {e}."))
+            })?,
+            None => 0,
+        };
+        if end_block.map_or(false, |end_block| after >= end_block) {
+            return Err(IndexerError::EndBlockMet);
+        }
+
+        let page = chain
+            .blocks
+            .iter()
+            .skip(after as usize)
+            .take(page_size)
+            .take_while(|block| end_block.map_or(true, |end| block.height <= end))
+            .cloned()
+            .collect::<Vec<_>>();
+        let next_cursor = page.last().map(|block| block.height.to_string());
+
+        Ok((page, next_cursor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_node_pages_reorgs_and_failures() {
+        let mut node = MockNode::new();
+        assert_eq!(node.produce_empty(3), 3);
+
+        let (page, cursor) = node.next_page(&None, 2, None).await.unwrap();
+        assert_eq!(
+            page.iter().map(|b| b.height).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(cursor.as_deref(), Some("2"));
+
+        let (page, cursor) = node.next_page(&cursor, 2, Some(3)).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert!(matches!(
+            node.next_page(&cursor, 2, Some(3)).await,
+            Err(IndexerError::EndBlockMet)
+        ));
+
+        let replaced = node.block(3).unwrap();
+        node.reorg(1);
+        node.produce(vec![vec![BlockFixture::return_data([1u8; 32], vec![1])]]);
+        let replacement = node.block(3).unwrap();
+        assert_ne!(replaced.id, replacement.id);
+        assert_eq!(replacement.transactions.len(), 1);
+
+        node.fail_next(1, "node is down");
+        assert!(matches!(
+            node.next_page(&None, 2, None).await,
+            Err(IndexerError::IoError(_))
+        ));
+        let (page, _) = node
+            .next_page(&Some("3".to_string()), 2, None)
+            .await
+            .unwrap();
+        assert!(page.is_empty());
+        assert_eq!(node.requests(), 5);
+    }
+}
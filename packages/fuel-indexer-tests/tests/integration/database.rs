@@ -4,6 +4,7 @@ use fuel_indexer_lib::{
     fully_qualified_namespace, graphql::GraphQLSchema, manifest::Manifest, type_id,
 };
 use fuel_indexer_schema::db::manager::SchemaManager;
+use fuel_indexer_tests::fixtures::TestPostgresDb;
 use wasmer::{imports, AsStoreMut, Cranelift, Instance, Module, Store};
 
 fn compiler() -> Cranelift {
@@ -48,7 +49,9 @@ async fn load_wasm_module(
     );
 
     let mut import_object = imports! {};
-    for (export_name, export) in ffi::get_exports(&mut store, &env) {
+    for (export_name, export) in
+        ffi::get_exports(&mut store, &env, manifest.capabilities())
+    {
         import_object.define("env", &export_name, export.clone());
     }
 
@@ -129,7 +132,9 @@ async fn generate_schema_then_load_schema_from_wasm_module(database_url: &str) {
     let version = ffi::get_version(&mut store.as_store_mut(), &instance)
         .expect("Could not get version");
 
-    let mut db = Database::new(pool.clone(), &manifest, &config).await;
+    let mut db = Database::new(pool.clone(), &manifest, &config)
+        .await
+        .unwrap();
     db.load_schema(version.clone())
         .await
         .expect("Could not load db schema");
@@ -156,7 +161,9 @@ async fn generate_schema_then_load_schema_from_wasm_module(database_url: &str) {
     db.start_transaction()
         .await
         .expect("Start transaction failed");
-    db.put_object(thing1_ty_id, columns, bytes.clone()).await;
+    db.put_object(thing1_ty_id, columns, bytes.clone())
+        .await
+        .expect("put_object failed");
 
     db.commit_transaction()
         .await
@@ -166,12 +173,236 @@ async fn generate_schema_then_load_schema_from_wasm_module(database_url: &str) {
         .await
         .expect("Start transaction failed");
 
-    let obj = db.get_object(thing1_ty_id, object_id).await;
+    let obj = db
+        .get_object(thing1_ty_id, object_id)
+        .await
+        .expect("get_object failed");
 
     assert!(obj.is_some());
     let obj = obj.expect("Failed to get object from database");
 
     assert_eq!(obj, bytes);
 
-    assert_eq!(db.get_object(thing1_ty_id, 90).await, None);
+    assert_eq!(db.get_object(thing1_ty_id, 90).await.unwrap(), None);
+}
+
+/// Create the schema of the simple-wasm indexer in a new test database, and return
+/// a `Database` for it, along with the `TypeId` of its `Thing1` entity.
+async fn setup_simple_wasm_database() -> (TestPostgresDb, Database, i64) {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+
+    let manifest = Manifest::try_from(SIMPLE_WASM_MANIFEST).unwrap();
+    let schema = GraphQLSchema::new(SIMPLE_WASM_GRAPHQL_SCHEMA.to_owned());
+    let version = schema.version().to_owned();
+    SchemaManager::new(pool.clone())
+        .new_schema(
+            TEST_NAMESPACE,
+            TEST_INDENTIFIER,
+            schema,
+            manifest.execution_source(),
+            &mut conn,
+        )
+        .await
+        .unwrap();
+
+    let mut db = Database::new(pool, &manifest, &IndexerConfig::default())
+        .await
+        .unwrap();
+    db.load_schema(version).await.unwrap();
+
+    let thing1 = type_id(
+        &fully_qualified_namespace(TEST_NAMESPACE, TEST_INDENTIFIER),
+        "Thing1",
+    );
+    (test_db, db, thing1)
+}
+
+fn thing1_columns(id: u64) -> Vec<FtColumn> {
+    vec![
+        FtColumn::ID(Some(id)),
+        FtColumn::Address(Some(fuel_indexer_types::fuel::Address::from([0x04; 32]))),
+    ]
+}
+
+#[tokio::test]
+async fn test_database_reverts_transaction_when_queued_writes_fail_postgres() {
+    let (_test_db, mut db, thing1) = setup_simple_wasm_database().await;
+
+    db.start_transaction().await.unwrap();
+    db.put_object(thing1, thing1_columns(1), vec![1])
+        .await
+        .unwrap();
+    // Writes are queued, so a write with too few columns only fails once the queue
+    // is sent to the database.
+    db.put_object(thing1, vec![FtColumn::ID(Some(2))], vec![2])
+        .await
+        .unwrap();
+
+    // Reads fail rather than reading from the aborted transaction.
+    assert!(db.get_object(thing1, 1).await.is_err());
+    assert!(db.get_object(thing1, 1).await.is_err());
+
+    // The transaction is reverted rather than committed, so that its blocks are
+    // handled again.
+    assert!(db.commit_transaction().await.is_err());
+
+    db.start_transaction().await.unwrap();
+    assert_eq!(db.get_object(thing1, 1).await.unwrap(), None);
+    db.put_object(thing1, thing1_columns(1), vec![1])
+        .await
+        .unwrap();
+    db.commit_transaction().await.unwrap();
+
+    db.start_transaction().await.unwrap();
+    assert_eq!(db.get_object(thing1, 1).await.unwrap(), Some(vec![1]));
+    db.commit_transaction().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_database_sends_queued_writes_once_queue_is_full_postgres() {
+    let (_test_db, mut db, thing1) = setup_simple_wasm_database().await;
+
+    // Writes are sent to the database once 1000 of them are queued.
+    db.start_transaction().await.unwrap();
+    for id in 1..1000 {
+        db.put_object(thing1, thing1_columns(id), vec![1])
+            .await
+            .unwrap();
+    }
+    assert!(db
+        .put_object(thing1, vec![FtColumn::ID(Some(1000))], vec![1])
+        .await
+        .is_err());
+    assert!(db.commit_transaction().await.is_err());
+
+    db.start_transaction().await.unwrap();
+    for id in 1..=1000 {
+        db.put_object(thing1, thing1_columns(id), vec![1])
+            .await
+            .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    db.start_transaction().await.unwrap();
+    assert_eq!(db.get_object(thing1, 1000).await.unwrap(), Some(vec![1]));
+    db.commit_transaction().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_api_keys_are_verified_by_their_secret_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+
+    // Scopes are stored as given, even if they contain commas.
+    let scopes = vec!["query:*".to_string(), "deploy:a,b".to_string()];
+    let (api_key, key) = queries::create_api_key(&mut conn, "ci", "owner", &scopes)
+        .await
+        .unwrap();
+    assert_eq!(api_key.scopes, scopes);
+
+    let (key_id, secret) = key.split_once('.').unwrap();
+    assert_eq!(key_id, api_key.id);
+    assert_eq!(secret.len(), 64);
+
+    let verified = queries::verify_api_key(&mut conn, &key)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(verified.id, api_key.id);
+    assert_eq!(verified.scopes, scopes);
+
+    let wrong_secret = format!("{key_id}.{}", "0".repeat(64));
+    assert!(queries::verify_api_key(&mut conn, &wrong_secret)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(queries::verify_api_key(&mut conn, key_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    queries::revoke_api_key(&mut conn, key_id).await.unwrap();
+    assert!(queries::verify_api_key(&mut conn, &key)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_nonces_are_consumed_once_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+
+    let nonce = queries::create_nonce(&mut conn).await.unwrap();
+
+    // Of concurrent attempts to redeem the same nonce, only one succeeds.
+    let mut first = pool.acquire().await.unwrap();
+    let mut second = pool.acquire().await.unwrap();
+    let (a, b) = tokio::join!(
+        queries::consume_nonce(&mut first, &nonce.uid),
+        queries::consume_nonce(&mut second, &nonce.uid)
+    );
+    let redeemed = [a.unwrap(), b.unwrap()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    assert_eq!(redeemed.len(), 1);
+    assert_eq!(redeemed[0].uid, nonce.uid);
+    assert!(queries::consume_nonce(&mut conn, &nonce.uid)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Expired and unknown nonces can't be redeemed, nor can nonces that look like SQL.
+    sqlx::query("INSERT INTO nonce (uid, expiry) VALUES ('expired', 1)")
+        .execute(&test_db.pool)
+        .await
+        .unwrap();
+    assert!(queries::consume_nonce(&mut conn, "expired")
+        .await
+        .unwrap()
+        .is_none());
+    assert!(queries::consume_nonce(&mut conn, "' OR '1' = '1")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_publications_of_other_schemas_are_not_replaced_postgres() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+
+    for schema in ["pub_owner", "pub_other"] {
+        queries::execute_batch(
+            &mut conn,
+            format!(
+                "CREATE SCHEMA {schema}; CREATE TABLE {schema}.thing (id VARCHAR(64) PRIMARY KEY)"
+            ),
+        )
+        .await
+        .unwrap();
+    }
+
+    let owned = vec!["pub_owner.thing".to_string()];
+    queries::put_publication(&mut conn, "owner_pub", &owned, "FULL")
+        .await
+        .unwrap();
+
+    // The publication's own tables can be replaced, but another schema's indexer
+    // can't take it over.
+    queries::put_publication(&mut conn, "owner_pub", &owned, "FULL")
+        .await
+        .unwrap();
+    let other = vec!["pub_other.thing".to_string()];
+    assert!(
+        queries::put_publication(&mut conn, "owner_pub", &other, "FULL")
+            .await
+            .is_err()
+    );
 }
@@ -4,7 +4,7 @@ use fuel_indexer_graphql::{
     graphql::*,
     queries::{QueryElement, UserQuery},
 };
-use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+use fuel_indexer_lib::{config::PageInfoCount, graphql::GraphQLSchema, ExecutionSource};
 use fuel_indexer_schema::db::tables::IndexerSchema;
 use std::collections::HashMap;
 
@@ -96,6 +96,7 @@ fn test_query_builder_parses_correctly() {
                 limit: None,
             },
             alias: None,
+            page_info_count: PageInfoCount::Exact,
         },
         UserQuery {
             elements: vec![
@@ -126,6 +127,7 @@ fn test_query_builder_parses_correctly() {
                 limit: None,
             },
             alias: None,
+            page_info_count: PageInfoCount::Exact,
         },
     ];
 
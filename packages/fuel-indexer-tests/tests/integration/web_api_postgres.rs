@@ -1,4 +1,5 @@
 use fuel_indexer::IndexerConfig;
+use fuel_indexer_database::{queries, IndexerConnectionPool};
 use fuel_indexer_lib::config::{
     auth::AuthenticationStrategy, defaults as config_defaults, AuthenticationConfig,
 };
@@ -146,7 +147,8 @@ async fn test_signature_route_validates_signature_expires_nonce_and_creates_jwt(
             strategy: Some(AuthenticationStrategy::JWT),
             jwt_secret: Some("6906573247652854078288872150120717701634680141358560585446649749925714230966".to_string()),
             jwt_issuer: Some("FuelLabs".to_string()),
-            jwt_expiry: Some(config_defaults::JWT_EXPIRY_SECS)
+            jwt_expiry: Some(config_defaults::JWT_EXPIRY_SECS),
+            ..AuthenticationConfig::default()
         },
         ..IndexerConfig::default()
     };
@@ -185,6 +187,19 @@ async fn test_signature_route_validates_signature_expires_nonce_and_creates_jwt(
     assert!(res.token.is_some());
     assert!(res.token.unwrap().len() > 300);
 
+    // The nonce was consumed, so the same signature can't be redeemed again.
+    let resp = http_client()
+        .post("http://localhost:29987/api/auth/signature")
+        .header(CONTENT_TYPE, "application/json".to_owned())
+        .json(&SignatureRequest {
+            signature: SIGNATURE.to_string(),
+            message: NONCE.to_string(),
+        })
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+
     let _ = sqlx::QueryBuilder::new("DELETE FROM nonce WHERE uid = $1")
         .build()
         .bind(NONCE)
@@ -296,3 +311,208 @@ async fn test_querying_sql_endpoint_when_sql_is_enabled_returns_error_for_non_su
 
     server.abort();
 }
+
+#[actix_web::test]
+async fn test_export_endpoint_does_not_read_schemas_of_other_namespaces() {
+    let WebTestComponents { server, .. } = setup_web_test_components(None).await;
+
+    let client = http_client();
+    let resp = client
+        .get("http://127.0.0.1:29987/api/export/fuel_indexer_test/index1/pingentity")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+
+    // `fuel_indexer.test_index1` shares its schema name with the registered
+    // `fuel_indexer_test.index1`, but isn't an indexer.
+    let resp = client
+        .get("http://127.0.0.1:29987/api/export/fuel_indexer/test_index1/pingentity")
+        .send()
+        .await
+        .unwrap();
+
+    server.abort();
+
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn test_graph_endpoint_rejects_requests_without_token_when_authentication_is_enabled(
+) {
+    let config = IndexerConfig {
+        authentication: AuthenticationConfig {
+            enabled: true,
+            strategy: Some(AuthenticationStrategy::JWT),
+            jwt_secret: Some("secret".to_string()),
+            jwt_issuer: Some("FuelLabs".to_string()),
+            jwt_expiry: Some(config_defaults::JWT_EXPIRY_SECS),
+            ..AuthenticationConfig::default()
+        },
+        ..IndexerConfig::default()
+    };
+
+    let WebTestComponents { server, .. } = setup_web_test_components(Some(config)).await;
+
+    let client = http_client();
+    let query = r#"{ "query": "query { block(id: 1) { id height } }" }"#;
+    let without_header = client
+        .post("http://127.0.0.1:29987/api/graph/fuel_indexer_test/index1")
+        .header(CONTENT_TYPE, "application/json".to_owned())
+        .body(query)
+        .send()
+        .await
+        .unwrap();
+    let with_empty_header = client
+        .post("http://127.0.0.1:29987/api/graph/fuel_indexer_test/index1")
+        .header(CONTENT_TYPE, "application/json".to_owned())
+        .header(hyper::header::AUTHORIZATION, "")
+        .body(query)
+        .send()
+        .await
+        .unwrap();
+
+    server.abort();
+
+    assert_eq!(without_header.status(), 401);
+    assert_eq!(with_empty_header.status(), 401);
+}
+
+#[actix_web::test]
+async fn test_tokens_minted_from_a_revoked_api_key_are_rejected() {
+    let config = IndexerConfig {
+        authentication: AuthenticationConfig {
+            enabled: true,
+            strategy: Some(AuthenticationStrategy::JWT),
+            jwt_secret: Some("secret".to_string()),
+            jwt_issuer: Some("FuelLabs".to_string()),
+            jwt_expiry: Some(config_defaults::JWT_EXPIRY_SECS),
+            ..AuthenticationConfig::default()
+        },
+        ..IndexerConfig::default()
+    };
+
+    let WebTestComponents { server, db, .. } =
+        setup_web_test_components(Some(config)).await;
+
+    let pool = IndexerConnectionPool::Postgres(db.pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+    let (api_key, key) = queries::create_api_key(&mut conn, "ci", "owner", &[])
+        .await
+        .unwrap();
+
+    let client = http_client();
+    let resp: Value = client
+        .post("http://127.0.0.1:29987/api/auth/token")
+        .header(CONTENT_TYPE, "application/json".to_owned())
+        .json(&serde_json::json!({ "key": key }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let token = resp["token"].as_str().unwrap().to_string();
+
+    let before = client
+        .get("http://127.0.0.1:29987/api/auth/keys")
+        .header(hyper::header::AUTHORIZATION, &token)
+        .send()
+        .await
+        .unwrap();
+
+    queries::revoke_api_key(&mut conn, &api_key.id)
+        .await
+        .unwrap();
+
+    let after = client
+        .get("http://127.0.0.1:29987/api/auth/keys")
+        .header(hyper::header::AUTHORIZATION, &token)
+        .send()
+        .await
+        .unwrap();
+
+    server.abort();
+
+    assert_eq!(before.status(), 200);
+    assert_eq!(after.status(), 401);
+}
+
+#[tokio::test]
+async fn test_rollback_keeps_the_signer_of_the_deployment_rolled_back_to() {
+    let WebTestComponents {
+        server,
+        db,
+        rx: _rx,
+        ..
+    } = setup_web_test_components(None).await;
+
+    let form = multipart::Form::new()
+        .part(
+            "manifest",
+            multipart::Part::stream(assets::SIMPLE_WASM_MANIFEST)
+                .file_name("simple_wasm.yaml"),
+        )
+        .part(
+            "schema",
+            multipart::Part::stream(assets::SIMPLE_WASM_SCHEMA)
+                .file_name("simple_wasm.graphql"),
+        )
+        .part(
+            "wasm",
+            multipart::Part::stream(assets::SIMPLE_WASM_WASM)
+                .file_name("simple_wasm.wasm"),
+        );
+
+    let client = http_client();
+    let resp = client
+        .post("http://localhost:29987/api/index/test_namespace/simple_wasm_executor")
+        .multipart(form)
+        .header(CONTENT_TYPE, "multipart/form-data".to_owned())
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+
+    let pool = IndexerConnectionPool::Postgres(db.pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+    let index_id =
+        queries::get_indexer_id(&mut conn, "test_namespace", "simple_wasm_executor")
+            .await
+            .unwrap();
+    let assets = queries::latest_assets_for_indexer(&mut conn, &index_id)
+        .await
+        .unwrap();
+    let signed = queries::record_deployment(
+        &mut conn,
+        "test_namespace",
+        "simple_wasm_executor",
+        &assets,
+        None,
+        Some("signer"),
+    )
+    .await
+    .unwrap();
+
+    let resp = client
+        .post(format!(
+            "http://localhost:29987/api/index/test_namespace/simple_wasm_executor/rollback/{signed}"
+        ))
+        .send()
+        .await
+        .unwrap();
+
+    server.abort();
+
+    assert!(resp.status().is_success());
+
+    let deployments =
+        queries::indexer_deployments(&mut conn, "test_namespace", "simple_wasm_executor")
+            .await
+            .unwrap();
+    let rollback = deployments
+        .iter()
+        .find(|deployment| deployment.rollback_of == Some(signed))
+        .unwrap();
+    assert_eq!(rollback.signer.as_deref(), Some("signer"));
+}
@@ -68,6 +68,8 @@ fn main() {
                 },
             ],
             transaction: Transaction::default(),
+            gas_used: 0,
+            fee: 0,
         }],
     }];
 
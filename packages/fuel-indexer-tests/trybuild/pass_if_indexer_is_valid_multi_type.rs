@@ -7,6 +7,23 @@ fn ff_log_data(_inp: ()) {}
 #[no_mangle]
 fn ff_put_object(_inp: ()) {}
 
+static mut BLOCKS: Vec<Vec<u8>> = Vec::new();
+
+#[no_mangle]
+fn ff_get_block(index: u32, len: *mut u8) -> *mut u8 {
+    unsafe {
+        match BLOCKS.get(index as usize) {
+            Some(bytes) => {
+                let bytes = bytes.clone().into_boxed_slice();
+                let size = (bytes.len() as u32).to_le_bytes();
+                std::ptr::copy_nonoverlapping(size.as_ptr(), len, size.len());
+                Box::into_raw(bytes) as *mut u8
+            }
+            None => std::ptr::null_mut(),
+        }
+    }
+}
+
 #[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm.yaml")]
 mod indexer {
     fn function_one(event: SomeEvent) {
@@ -108,13 +125,14 @@ fn main() {
                 },
             ],
             transaction: fuel::Transaction::default(),
+            gas_used: 0,
+            fee: 0,
         }],
     }];
 
-    let mut bytes = serialize(&data);
-
-    let ptr = bytes.as_mut_ptr();
-    let len = bytes.len();
+    unsafe {
+        BLOCKS = data.iter().map(serialize).collect();
+    }
 
-    handle_events(ptr, len);
+    handle_blocks(data.len() as u32);
 }
@@ -1,5 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Version of the interface between the indexer service and indexer WASM modules.
+///
+/// This must be bumped whenever the signature of a host function, or the encoding of
+/// data passed between the service and modules, changes.
+pub const FFI_VERSION: u32 = 2;
+
+/// Oldest interface version the indexer service can still run. Modules built before
+/// the interface was versioned report version 0.
+pub const MIN_FFI_VERSION: u32 = 0;
+
+/// First interface version whose modules pull the blocks of a page from the indexer
+/// service one at a time, rather than being passed the whole page at once.
+pub const PULL_BLOCKS_FFI_VERSION: u32 = 2;
+
 pub const LOG_LEVEL_ERROR: u32 = 0;
 pub const LOG_LEVEL_WARN: u32 = 1;
 pub const LOG_LEVEL_INFO: u32 = 2;
 pub const LOG_LEVEL_DEBUG: u32 = 3;
 pub const LOG_LEVEL_TRACE: u32 = 4;
+
+/// A structured log record sent by an indexer to the indexer service.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// One of the `LOG_LEVEL_*` constants.
+    pub level: u32,
+
+    /// The log message.
+    pub message: String,
+
+    /// Key-value fields attached to the message.
+    pub fields: Vec<(String, String)>,
+
+    /// Height of the block being processed when the message was logged, if any.
+    pub block_height: Option<u64>,
+}
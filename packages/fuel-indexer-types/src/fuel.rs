@@ -2,7 +2,9 @@
 //
 // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
 
+use crate::receipt::message_id;
 pub use crate::{scalar::*, TypeId, FUEL_TYPES_NAMESPACE};
+use fuel_crypto::{Hasher, Message, Signature};
 use fuel_indexer_lib::type_id;
 pub use fuel_tx::ScriptExecutionResult;
 pub use fuel_tx::{
@@ -10,7 +12,12 @@ pub use fuel_tx::{
     Transaction as ClientTransaction, TxPointer as ClientTxPointer,
 };
 pub use fuel_tx::{Receipt, TxId, UtxoId, Witness, Word};
+use fuels::core::{
+    codec::try_from_bytes,
+    traits::{Parameterize, Tokenizable},
+};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 pub mod field {
     pub use fuel_tx::field::{
@@ -22,6 +29,39 @@ pub mod field {
 
 pub type RawInstruction = u32;
 
+/// Error raised when data received from a Fuel node, or stored by an indexer, can't be
+/// converted to one of these types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeConversionError {
+    /// Name of the type being converted to.
+    pub type_name: &'static str,
+
+    /// Why the conversion failed.
+    pub reason: String,
+}
+
+impl TypeConversionError {
+    /// Create a new `TypeConversionError`.
+    pub fn new(type_name: &'static str, reason: impl fmt::Display) -> Self {
+        Self {
+            type_name,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TypeConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Could not convert to {}: {}",
+            self.type_name, self.reason
+        )
+    }
+}
+
+impl std::error::Error for TypeConversionError {}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct StorageSlot {
     pub key: Bytes32,
@@ -76,11 +116,12 @@ impl From<CommonMetadata> for Json {
     }
 }
 
-impl From<Json> for CommonMetadata {
-    fn from(json: Json) -> Self {
-        let metadata: CommonMetadata =
-            serde_json::from_str(&json.0).expect("Failed to deserialize CommonMetadata.");
-        metadata
+impl TryFrom<Json> for CommonMetadata {
+    type Error = TypeConversionError;
+
+    fn try_from(json: Json) -> Result<Self, Self::Error> {
+        serde_json::from_str(&json.0)
+            .map_err(|e| TypeConversionError::new("CommonMetadata", e))
     }
 }
 
@@ -107,16 +148,17 @@ pub struct ScriptMetadata {
 impl From<ScriptMetadata> for Json {
     fn from(metadata: ScriptMetadata) -> Self {
         let s = serde_json::to_string(&metadata)
-            .expect("Failed to deserialize MintMetadata.");
+            .expect("Failed to serialize ScriptMetadata.");
         Self(s)
     }
 }
 
-impl From<Json> for ScriptMetadata {
-    fn from(json: Json) -> Self {
-        let metadata: ScriptMetadata =
-            serde_json::from_str(&json.0).expect("Failed to deserialize ScriptMetadata.");
-        metadata
+impl TryFrom<Json> for ScriptMetadata {
+    type Error = TypeConversionError;
+
+    fn try_from(json: Json) -> Result<Self, Self::Error> {
+        serde_json::from_str(&json.0)
+            .map_err(|e| TypeConversionError::new("ScriptMetadata", e))
     }
 }
 
@@ -142,11 +184,32 @@ impl From<MintMetadata> for Json {
     }
 }
 
-impl From<Json> for MintMetadata {
-    fn from(json: Json) -> Self {
-        let metadata: MintMetadata =
-            serde_json::from_str(&json.0).expect("Failed to deserialize MintMetadata.");
-        metadata
+impl TryFrom<Json> for MintMetadata {
+    type Error = TypeConversionError;
+
+    fn try_from(json: Json) -> Result<Self, Self::Error> {
+        serde_json::from_str(&json.0)
+            .map_err(|e| TypeConversionError::new("MintMetadata", e))
+    }
+}
+
+/// The consensus parameters used to calculate transaction fees.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeParameters {
+    pub gas_price_factor: u64,
+    pub gas_per_byte: u64,
+}
+
+impl FeeParameters {
+    /// The fee paid by a transaction of `tx_bytes` bytes that used `gas_used` gas at `gas_price`.
+    ///
+    /// Each byte of the transaction is charged `gas_per_byte` gas, and the total gas is
+    /// converted to the base asset by `gas_price / gas_price_factor`, rounding up.
+    pub fn fee(&self, tx_bytes: u64, gas_used: u64, gas_price: u64) -> u64 {
+        let gas = self.gas_per_byte as u128 * tx_bytes as u128 + gas_used as u128;
+        let factor = self.gas_price_factor.max(1) as u128;
+        let fee = (gas * gas_price as u128 + factor - 1) / factor;
+        u64::try_from(fee).unwrap_or(u64::MAX)
     }
 }
 
@@ -156,6 +219,275 @@ pub struct TransactionData {
     pub status: TransactionStatus,
     pub receipts: Vec<Receipt>,
     pub id: TxId,
+
+    /// Gas used by the transaction's script, as reported by its `ScriptResult` receipt.
+    pub gas_used: u64,
+
+    /// Fee paid for the transaction, in the base asset.
+    ///
+    /// The current protocol version has no tips, so this is the full fee paid.
+    pub fee: u64,
+}
+
+impl TransactionData {
+    /// The receipts of this transaction, converted to typed receipts.
+    pub fn typed_receipts(&self) -> Vec<crate::receipt::Receipt> {
+        self.receipts.iter().cloned().map(Into::into).collect()
+    }
+
+    /// The script executed by this transaction, if it is a script transaction.
+    pub fn script_call(&self) -> Option<ScriptCall> {
+        match &self.transaction {
+            Transaction::Script(Script {
+                script,
+                script_data,
+                ..
+            }) => Some(ScriptCall {
+                tx_id: self.id,
+                script_hash: Bytes32::from(*Hasher::hash(script)),
+                script_data: script_data.clone(),
+                success: matches!(self.status, TransactionStatus::Success { .. }),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The messages spent by this transaction.
+    pub fn messages_in(&self) -> Vec<MessageIn> {
+        let inputs = match &self.transaction {
+            Transaction::Script(Script { inputs, .. })
+            | Transaction::Create(Create { inputs, .. }) => inputs,
+            Transaction::Mint(_) => return Vec::new(),
+        };
+
+        let success = matches!(self.status, TransactionStatus::Success { .. });
+
+        inputs
+            .iter()
+            .filter_map(|input| match input {
+                Input::Message(message) => Some(MessageIn {
+                    tx_id: self.id,
+                    message_id: message_id(
+                        &message.sender,
+                        &message.recipient,
+                        &message.nonce,
+                        message.amount,
+                        &message.data,
+                    ),
+                    sender: message.sender,
+                    recipient: message.recipient,
+                    amount: message.amount,
+                    nonce: message.nonce,
+                    data: message.data.to_vec(),
+                    success,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The inputs of this transaction that are spent by a predicate.
+    pub fn predicate_spends(&self) -> Vec<PredicateSpend> {
+        let inputs = match &self.transaction {
+            Transaction::Script(Script { inputs, .. })
+            | Transaction::Create(Create { inputs, .. }) => inputs,
+            Transaction::Mint(_) => return Vec::new(),
+        };
+
+        let success = matches!(self.status, TransactionStatus::Success { .. });
+
+        inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(input_index, input)| {
+                // The owner of a coin, or the recipient of a message, spent by a
+                // predicate is the root of the predicate's bytecode.
+                let (predicate_root, amount, predicate, predicate_data) = match input {
+                    Input::Coin(coin) if !coin.predicate.is_empty() => (
+                        coin.owner,
+                        coin.amount,
+                        &coin.predicate,
+                        &coin.predicate_data,
+                    ),
+                    Input::Message(message) if !message.predicate.is_empty() => (
+                        message.recipient,
+                        message.amount,
+                        &message.predicate,
+                        &message.predicate_data,
+                    ),
+                    _ => return None,
+                };
+
+                Some(PredicateSpend {
+                    tx_id: self.id,
+                    input_index: input_index as u64,
+                    predicate_root,
+                    amount,
+                    predicate: predicate.clone(),
+                    predicate_data: predicate_data.clone(),
+                    success,
+                })
+            })
+            .collect()
+    }
+
+    /// The addresses that signed this transaction, recovered from the witnesses of its
+    /// signed coin and message inputs. Each address is returned once.
+    pub fn signers(&self) -> Vec<Address> {
+        let (inputs, witnesses) = match &self.transaction {
+            Transaction::Script(Script {
+                inputs, witnesses, ..
+            })
+            | Transaction::Create(Create {
+                inputs, witnesses, ..
+            }) => (inputs, witnesses),
+            Transaction::Mint(_) => return Vec::new(),
+        };
+
+        let mut signers = Vec::new();
+        for input in inputs {
+            // Inputs spent by a predicate aren't signed.
+            let witness_index = match input {
+                Input::Coin(coin) if coin.predicate.is_empty() => coin.witness_index,
+                Input::Message(message) if message.predicate.is_empty() => {
+                    message.witness_index
+                }
+                _ => continue,
+            };
+
+            let signer = witnesses
+                .get(witness_index as usize)
+                .and_then(|witness| recover_signer(&self.id, witness.as_ref()));
+            if let Some(signer) = signer {
+                if !signers.contains(&signer) {
+                    signers.push(signer);
+                }
+            }
+        }
+
+        signers
+    }
+}
+
+/// Recover the address that signed the transaction with the given ID, from the
+/// signature in one of its witnesses.
+///
+/// Returns `None` if the witness is not a valid signature.
+pub fn recover_signer(tx_id: &TxId, witness: &[u8]) -> Option<Address> {
+    let signature = Signature::from_bytes(<[u8; 64]>::try_from(witness).ok()?);
+
+    // SAFETY: The transaction ID is a hash, so it can be signed as-is.
+    let message = unsafe { Message::from_bytes_unchecked(**tx_id) };
+    let public_key = signature.recover(&message).ok()?;
+
+    // Addresses are the hash of their owner's public key.
+    Some(Address::from(<[u8; 32]>::from(public_key.hash())))
+}
+
+/// A message, e.g., one sent from the L1 bridge, that is spent by a transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MessageIn {
+    /// ID of the spending transaction.
+    pub tx_id: TxId,
+
+    /// ID of the message.
+    pub message_id: MessageId,
+
+    /// Sender of the message.
+    pub sender: Address,
+
+    /// Recipient of the message.
+    pub recipient: Address,
+
+    /// Amount of base asset sent with the message.
+    pub amount: u64,
+
+    /// Nonce of the message.
+    pub nonce: Nonce,
+
+    /// Data of the message.
+    pub data: Vec<u8>,
+
+    /// Whether the spending transaction executed successfully.
+    pub success: bool,
+}
+
+impl TypeId for MessageIn {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "MessageIn") as usize
+    }
+}
+
+/// An input of a transaction that is spent by a predicate.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PredicateSpend {
+    /// ID of the spending transaction.
+    pub tx_id: TxId,
+
+    /// Index of the input in the spending transaction.
+    pub input_index: u64,
+
+    /// Root of the predicate's bytecode.
+    pub predicate_root: Address,
+
+    /// Amount of the spent coin or message.
+    pub amount: u64,
+
+    /// Bytecode of the predicate.
+    pub predicate: HexString,
+
+    /// Data supplied to the predicate.
+    pub predicate_data: HexString,
+
+    /// Whether the spending transaction executed successfully.
+    pub success: bool,
+}
+
+impl TypeId for PredicateSpend {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "PredicateSpend") as usize
+    }
+}
+
+/// The execution of a script by a script transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScriptCall {
+    /// ID of the script transaction.
+    pub tx_id: TxId,
+
+    /// SHA-256 hash of the script's bytecode.
+    pub script_hash: Bytes32,
+
+    /// Data passed to the script.
+    pub script_data: Vec<u8>,
+
+    /// Whether the script transaction executed successfully.
+    pub success: bool,
+}
+
+impl ScriptCall {
+    /// Decode the script data as the given type, e.g., the arguments of the
+    /// script's `main` function.
+    pub fn decode_data<T: Parameterize + Tokenizable>(&self) -> Option<T> {
+        try_from_bytes(&self.script_data).ok()
+    }
+}
+
+impl TypeId for ScriptCall {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "ScriptCall") as usize
+    }
+}
+
+/// An unspent coin.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Coin {
+    pub utxo_id: UtxoId,
+    pub owner: Address,
+    pub amount: u64,
+    pub asset_id: AssetId,
+    pub maturity: u64,
+    pub block_created: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -189,6 +521,16 @@ impl TypeId for BlockData {
     }
 }
 
+impl BlockData {
+    /// The inputs of this block's transactions that are spent by a predicate.
+    pub fn predicate_spends(&self) -> Vec<PredicateSpend> {
+        self.transactions
+            .iter()
+            .flat_map(TransactionData::predicate_spends)
+            .collect()
+    }
+}
+
 impl From<ClientTxPointer> for TxPointer {
     fn from(tx_pointer: ClientTxPointer) -> Self {
         TxPointer {
@@ -219,15 +561,9 @@ impl From<ClientInput> for Input {
                 ..
             } => Input::Coin(InputCoin {
                 utxo_id,
-                owner: Address::from(
-                    <[u8; 32]>::try_from(owner)
-                        .expect("Could not convert owner address to bytes"),
-                ),
+                owner: Address::from(<[u8; 32]>::from(owner)),
                 amount,
-                asset_id: AssetId::from(
-                    <[u8; 32]>::try_from(asset_id)
-                        .expect("Could not convert asset ID to bytes"),
-                ),
+                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
                 tx_pointer: tx_pointer.into(),
                 witness_index,
                 maturity,
@@ -246,15 +582,9 @@ impl From<ClientInput> for Input {
                 ..
             } => Input::Coin(InputCoin {
                 utxo_id,
-                owner: Address::from(
-                    <[u8; 32]>::try_from(owner)
-                        .expect("Could not convert owner address to bytes"),
-                ),
+                owner: Address::from(<[u8; 32]>::from(owner)),
                 amount,
-                asset_id: AssetId::from(
-                    <[u8; 32]>::try_from(asset_id)
-                        .expect("Could not convert asset ID to bytes"),
-                ),
+                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
                 tx_pointer: tx_pointer.into(),
                 witness_index: 0,
                 maturity,
@@ -269,19 +599,10 @@ impl From<ClientInput> for Input {
                 contract_id,
             } => Input::Contract(InputContract {
                 utxo_id,
-                balance_root: Bytes32::from(
-                    <[u8; 32]>::try_from(balance_root)
-                        .expect("Could not convert balance root to bytes"),
-                ),
-                state_root: Bytes32::from(
-                    <[u8; 32]>::try_from(state_root)
-                        .expect("Could not convert state root to bytes"),
-                ),
+                balance_root: Bytes32::from(<[u8; 32]>::from(balance_root)),
+                state_root: Bytes32::from(<[u8; 32]>::from(state_root)),
                 tx_pointer: tx_pointer.into(),
-                contract_id: ContractId::from(
-                    <[u8; 32]>::try_from(contract_id)
-                        .expect("Could not convert contract ID to bytes"),
-                ),
+                contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
             }),
             // ClientInput::MessageSigned {
             //     amount,
@@ -311,14 +632,8 @@ impl From<ClientInput> for Input {
             } => Input::Message(InputMessage {
                 amount,
                 nonce: nonce.into(),
-                recipient: Address::from(
-                    <[u8; 32]>::try_from(recipient)
-                        .expect("Could not convert recipient to bytes"),
-                ),
-                sender: Address::from(
-                    <[u8; 32]>::try_from(sender)
-                        .expect("Could not convert sender to bytes"),
-                ),
+                recipient: Address::from(<[u8; 32]>::from(recipient)),
+                sender: Address::from(<[u8; 32]>::from(sender)),
                 witness_index,
                 data: data.into(),
                 predicate: "".into(),
@@ -352,14 +667,8 @@ impl From<ClientInput> for Input {
                 data,
                 ..
             } => Input::Message(InputMessage {
-                sender: Address::from(
-                    <[u8; 32]>::try_from(sender)
-                        .expect("Could not convert sender to bytes"),
-                ),
-                recipient: Address::from(
-                    <[u8; 32]>::try_from(recipient)
-                        .expect("Could not convert recipient to bytes"),
-                ),
+                sender: Address::from(<[u8; 32]>::from(sender)),
+                recipient: Address::from(<[u8; 32]>::from(recipient)),
                 amount,
                 nonce: nonce.into(),
                 witness_index: 0,
@@ -465,14 +774,9 @@ impl From<ClientOutput> for Output {
                 amount,
                 asset_id,
             } => Output::CoinOutput(CoinOutput {
-                to: Address::from(
-                    <[u8; 32]>::try_from(to).expect("Could not convert 'to' to bytes"),
-                ),
+                to: Address::from(<[u8; 32]>::from(to)),
                 amount,
-                asset_id: AssetId::from(
-                    <[u8; 32]>::try_from(asset_id)
-                        .expect("Could not convert asset ID to bytes"),
-                ),
+                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
             }),
             ClientOutput::Contract {
                 input_index,
@@ -480,63 +784,38 @@ impl From<ClientOutput> for Output {
                 state_root,
             } => Output::ContractOutput(ContractOutput {
                 input_index: input_index.into(),
-                balance_root: Bytes32::from(
-                    <[u8; 32]>::try_from(balance_root)
-                        .expect("Could not convert balance root to bytes"),
-                ),
-                state_root: Bytes32::from(
-                    <[u8; 32]>::try_from(state_root)
-                        .expect("Could not convert state root to bytes"),
-                ),
+                balance_root: Bytes32::from(<[u8; 32]>::from(balance_root)),
+                state_root: Bytes32::from(<[u8; 32]>::from(state_root)),
             }),
             ClientOutput::Change {
                 to,
                 amount,
                 asset_id,
             } => Output::ChangeOutput(ChangeOutput {
-                to: Address::from(
-                    <[u8; 32]>::try_from(to).expect("Could not convert 'to' to bytes"),
-                ),
+                to: Address::from(<[u8; 32]>::from(to)),
                 amount,
-                asset_id: AssetId::from(
-                    <[u8; 32]>::try_from(asset_id)
-                        .expect("Could not convert asset ID to bytes"),
-                ),
+                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
             }),
             ClientOutput::Variable {
                 to,
                 amount,
                 asset_id,
             } => Output::VariableOutput(VariableOutput {
-                to: Address::from(
-                    <[u8; 32]>::try_from(to).expect("Could not convert 'to' to bytes"),
-                ),
+                to: Address::from(<[u8; 32]>::from(to)),
                 amount,
-                asset_id: AssetId::from(
-                    <[u8; 32]>::try_from(asset_id)
-                        .expect("Could not convert asset ID to bytes"),
-                ),
+                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
             }),
             ClientOutput::ContractCreated {
                 contract_id,
                 state_root,
             } => Output::ContractCreated(ContractCreated {
-                contract_id: ContractId::from(
-                    <[u8; 32]>::try_from(contract_id)
-                        .expect("Could not convert contract ID to bytes"),
-                ),
-                state_root: Bytes32::from(
-                    <[u8; 32]>::try_from(state_root)
-                        .expect("Could not convert state root to bytes"),
-                ),
+                contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
+                state_root: Bytes32::from(<[u8; 32]>::from(state_root)),
             }),
             ClientOutput::Message { recipient, amount } => {
                 Output::Message(MessageOutput {
                     amount,
-                    recipient: Address::from(
-                        <[u8; 32]>::try_from(recipient)
-                            .expect("Could not convert recipient to bytes"),
-                    ),
+                    recipient: Address::from(<[u8; 32]>::from(recipient)),
                 })
             }
         }
@@ -624,10 +903,11 @@ impl From<ProgramState> for Json {
     }
 }
 
-impl From<Json> for ProgramState {
-    fn from(json: Json) -> Self {
-        let state: ProgramState =
-            serde_json::from_str(&json.0).expect("Failed to deserialize ProgramState.");
-        state
+impl TryFrom<Json> for ProgramState {
+    type Error = TypeConversionError;
+
+    fn try_from(json: Json) -> Result<Self, Self::Error> {
+        serde_json::from_str(&json.0)
+            .map_err(|e| TypeConversionError::new("ProgramState", e))
     }
 }
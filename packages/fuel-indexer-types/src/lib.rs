@@ -119,4 +119,73 @@ mod tests {
         assert_eq!(as_json, Json("1234567890".to_string()));
         assert_eq!(as_bytes, Blob(vec![210, 2, 150, 73, 0, 0, 0, 0]));
     }
+
+    #[test]
+    fn test_receipts_from_contract() {
+        let contract = ContractId::from([1u8; 32]);
+        let other = ContractId::from([2u8; 32]);
+        let log = |contract_id| {
+            Receipt::Log(Log {
+                contract_id,
+                ra: 1,
+                rb: 2,
+            })
+        };
+        let receipts = vec![
+            log(contract),
+            log(other),
+            Receipt::ScriptResult(ScriptResult {
+                result: 0,
+                gas_used: 100,
+            }),
+            Receipt::Transfer(Transfer {
+                contract_id: contract,
+                to: other,
+                amount: 10,
+                asset_id: AssetId::default(),
+                pc: 0,
+                is: 0,
+            }),
+        ];
+
+        assert_eq!(receipts.logs_from(&contract).len(), 1);
+        assert_eq!(receipts.logs_from(&other).len(), 1);
+        assert_eq!(
+            receipts.transfers_from(&contract),
+            vec![(AssetId::default(), 10)]
+        );
+        assert!(receipts.transfers_from(&other).is_empty());
+        assert_eq!(receipts.by_contract(&contract).count(), 2);
+    }
+
+    #[test]
+    fn test_transaction_fee() {
+        let params = fuel::FeeParameters {
+            gas_price_factor: 1_000,
+            gas_per_byte: 4,
+        };
+
+        // (4 * 100 + 1_000) * 3 / 1_000 = 4.2, rounded up.
+        assert_eq!(params.fee(100, 1_000, 3), 5);
+        assert_eq!(params.fee(100, 1_000, 0), 0);
+        assert_eq!(fuel::FeeParameters::default().fee(100, 1_000, 1), 1_000);
+    }
+
+    #[test]
+    fn test_program_state_json_conversion_is_fallible() {
+        let state = fuel::ProgramState {
+            return_type: fuel::ReturnType::Return,
+            data: HexString::from(vec![1u8, 2, 3]),
+        };
+        let json: Json = state.into();
+        let decoded = fuel::ProgramState::try_from(json.clone()).unwrap();
+        assert_eq!(Json::from(decoded), json);
+
+        let e =
+            fuel::ProgramState::try_from(Json("not a state".to_string())).unwrap_err();
+        assert_eq!(e.type_name, "ProgramState");
+        assert!(e
+            .to_string()
+            .starts_with("Could not convert to ProgramState: "));
+    }
 }
@@ -2,6 +2,7 @@ use crate::{
     scalar::{Address, AssetId, Bytes32, ContractId, MessageId, Nonce},
     TypeId, FUEL_TYPES_NAMESPACE,
 };
+use fuel_crypto::Hasher;
 use fuel_indexer_lib::type_id;
 use serde::{Deserialize, Serialize};
 
@@ -109,6 +110,20 @@ impl TypeId for Return {
     }
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReturnData {
+    pub contract_id: ContractId,
+    pub data: Vec<u8>,
+    pub ptr: u64,
+    pub len: u64,
+}
+
+impl TypeId for ReturnData {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "ReturnData") as usize
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Call {
     pub contract_id: ContractId,
@@ -148,3 +163,275 @@ impl TypeId for Revert {
         type_id(FUEL_TYPES_NAMESPACE, "Revert") as usize
     }
 }
+
+/// A transaction receipt, converted to the typed receipts of this module.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum Receipt {
+    Call(Call),
+    Return(Return),
+    ReturnData(ReturnData),
+    Panic(Panic),
+    Revert(Revert),
+    Log(Log),
+    LogData(LogData),
+    Transfer(Transfer),
+    TransferOut(TransferOut),
+    ScriptResult(ScriptResult),
+    MessageOut(MessageOut),
+}
+
+impl Receipt {
+    /// ID of the contract that produced this receipt, if any.
+    pub fn contract_id(&self) -> Option<&ContractId> {
+        match self {
+            Receipt::Call(Call { contract_id, .. })
+            | Receipt::Return(Return { contract_id, .. })
+            | Receipt::ReturnData(ReturnData { contract_id, .. })
+            | Receipt::Panic(Panic { contract_id, .. })
+            | Receipt::Revert(Revert { contract_id, .. })
+            | Receipt::Log(Log { contract_id, .. })
+            | Receipt::LogData(LogData { contract_id, .. })
+            | Receipt::Transfer(Transfer { contract_id, .. })
+            | Receipt::TransferOut(TransferOut { contract_id, .. }) => Some(contract_id),
+            Receipt::ScriptResult(_) | Receipt::MessageOut(_) => None,
+        }
+    }
+}
+
+/// Compute the ID of a message from its fields.
+pub fn message_id(
+    sender: &Address,
+    recipient: &Address,
+    nonce: &Nonce,
+    amount: u64,
+    data: &[u8],
+) -> MessageId {
+    let preimage = [
+        &sender[..],
+        &recipient[..],
+        &nonce[..],
+        &amount.to_be_bytes()[..],
+        data,
+    ]
+    .concat();
+
+    MessageId::from(*Hasher::hash(preimage))
+}
+
+// TODO: Temporary conversions; remove once we update back to latest fuel-types version.
+fn to_bytes32(bytes: impl Into<[u8; 32]>) -> [u8; 32] {
+    bytes.into()
+}
+
+impl From<fuel_tx::Receipt> for Receipt {
+    /// Convert a raw receipt.
+    ///
+    /// The function name of a `Call` is left empty, since it can only be derived
+    /// from the ABI of the called contract.
+    fn from(receipt: fuel_tx::Receipt) -> Self {
+        match receipt {
+            fuel_tx::Receipt::Call {
+                id,
+                to,
+                amount,
+                asset_id,
+                gas,
+                ..
+            } => Receipt::Call(Call {
+                contract_id: to_bytes32(id).into(),
+                to: to_bytes32(to).into(),
+                amount,
+                asset_id: to_bytes32(asset_id).into(),
+                gas,
+                fn_name: String::new(),
+            }),
+            fuel_tx::Receipt::Return { id, val, pc, is } => Receipt::Return(Return {
+                contract_id: to_bytes32(id).into(),
+                val,
+                pc,
+                is,
+            }),
+            fuel_tx::Receipt::ReturnData {
+                id, ptr, len, data, ..
+            } => Receipt::ReturnData(ReturnData {
+                contract_id: to_bytes32(id).into(),
+                data,
+                ptr,
+                len,
+            }),
+            fuel_tx::Receipt::Panic { id, reason, .. } => Receipt::Panic(Panic {
+                contract_id: to_bytes32(id).into(),
+                reason: *reason.reason() as u32,
+            }),
+            fuel_tx::Receipt::Revert { id, ra, .. } => Receipt::Revert(Revert {
+                contract_id: to_bytes32(id).into(),
+                error_val: u64::from(ra & 0xF),
+            }),
+            fuel_tx::Receipt::Log { id, ra, rb, .. } => Receipt::Log(Log {
+                contract_id: to_bytes32(id).into(),
+                ra,
+                rb,
+            }),
+            fuel_tx::Receipt::LogData {
+                id,
+                rb,
+                ptr,
+                len,
+                data,
+                ..
+            } => Receipt::LogData(LogData {
+                contract_id: to_bytes32(id).into(),
+                data,
+                rb,
+                len,
+                ptr,
+            }),
+            fuel_tx::Receipt::Transfer {
+                id,
+                to,
+                amount,
+                asset_id,
+                pc,
+                is,
+            } => Receipt::Transfer(Transfer {
+                contract_id: to_bytes32(id).into(),
+                to: to_bytes32(to).into(),
+                amount,
+                asset_id: to_bytes32(asset_id).into(),
+                pc,
+                is,
+            }),
+            fuel_tx::Receipt::TransferOut {
+                id,
+                to,
+                amount,
+                asset_id,
+                pc,
+                is,
+            } => Receipt::TransferOut(TransferOut {
+                contract_id: to_bytes32(id).into(),
+                to: to_bytes32(to).into(),
+                amount,
+                asset_id: to_bytes32(asset_id).into(),
+                pc,
+                is,
+            }),
+            fuel_tx::Receipt::ScriptResult { result, gas_used } => {
+                Receipt::ScriptResult(ScriptResult {
+                    result: u64::from(result),
+                    gas_used,
+                })
+            }
+            fuel_tx::Receipt::MessageOut {
+                sender,
+                recipient,
+                amount,
+                nonce,
+                len,
+                digest,
+                data,
+            } => {
+                let sender = Address::from(to_bytes32(sender));
+                let recipient = Address::from(to_bytes32(recipient));
+                let nonce = Nonce::from(to_bytes32(nonce));
+
+                Receipt::MessageOut(MessageOut {
+                    message_id: message_id(&sender, &recipient, &nonce, amount, &data),
+                    sender,
+                    recipient,
+                    amount,
+                    nonce,
+                    len,
+                    digest: to_bytes32(digest).into(),
+                    data,
+                })
+            }
+        }
+    }
+}
+
+/// Helpers for finding the receipts of a contract in a list of receipts.
+pub trait ReceiptsExt {
+    /// Receipts produced by the given contract.
+    fn by_contract<'a>(
+        &'a self,
+        contract_id: &ContractId,
+    ) -> Box<dyn Iterator<Item = &'a Receipt> + 'a>;
+
+    /// `Log` receipts produced by the given contract.
+    fn logs_from(&self, contract_id: &ContractId) -> Vec<&Log> {
+        self.by_contract(contract_id)
+            .filter_map(|receipt| match receipt {
+                Receipt::Log(log) => Some(log),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `LogData` receipts produced by the given contract.
+    fn log_data_from(&self, contract_id: &ContractId) -> Vec<&LogData> {
+        self.by_contract(contract_id)
+            .filter_map(|receipt| match receipt {
+                Receipt::LogData(log) => Some(log),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `Transfer` and `TransferOut` receipts produced by the given contract, as
+    /// `(asset_id, amount)` pairs.
+    fn transfers_from(&self, contract_id: &ContractId) -> Vec<(AssetId, u64)> {
+        self.by_contract(contract_id)
+            .filter_map(|receipt| match receipt {
+                Receipt::Transfer(transfer) => Some((transfer.asset_id, transfer.amount)),
+                Receipt::TransferOut(transfer) => {
+                    Some((transfer.asset_id, transfer.amount))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `Call` receipts produced by calls made by the given contract.
+    fn calls_from(&self, contract_id: &ContractId) -> Vec<&Call> {
+        self.by_contract(contract_id)
+            .filter_map(|receipt| match receipt {
+                Receipt::Call(call) => Some(call),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `Panic` receipts produced by the given contract.
+    fn panics_from(&self, contract_id: &ContractId) -> Vec<&Panic> {
+        self.by_contract(contract_id)
+            .filter_map(|receipt| match receipt {
+                Receipt::Panic(panic) => Some(panic),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// `Revert` receipts produced by the given contract.
+    fn reverts_from(&self, contract_id: &ContractId) -> Vec<&Revert> {
+        self.by_contract(contract_id)
+            .filter_map(|receipt| match receipt {
+                Receipt::Revert(revert) => Some(revert),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl ReceiptsExt for [Receipt] {
+    fn by_contract<'a>(
+        &'a self,
+        contract_id: &ContractId,
+    ) -> Box<dyn Iterator<Item = &'a Receipt> + 'a> {
+        let contract_id = *contract_id;
+        Box::new(
+            self.iter()
+                .filter(move |receipt| receipt.contract_id() == Some(&contract_id)),
+        )
+    }
+}
@@ -16,7 +16,7 @@ mod utilities;
 
 pub mod prelude {
     pub use crate::utilities::*;
-    pub use fuel_indexer_macros::indexer;
+    pub use fuel_indexer_macros::{indexer, IndexerJson};
     pub use fuel_indexer_plugin::prelude::*;
 }
 
@@ -0,0 +1,69 @@
+use async_std::fs;
+use fuel_indexer_lib::utils::{deserialize, serialize};
+use fuel_indexer_types::fuel::BlockData;
+use std::{
+    io::{Error, ErrorKind, Result},
+    path::PathBuf,
+};
+
+/// A local archive of blocks fetched from a Fuel node, stored as one file per block.
+///
+/// Indexers that are configured with an archive read blocks from it before falling
+/// back to the Fuel node, and archive the blocks that they fetch from the node. This
+/// makes backfills after the first one (e.g., when reindexing, or deploying a new
+/// indexer over the same range) much faster than fetching blocks over GraphQL.
+#[derive(Debug, Clone)]
+pub struct BlockArchive {
+    /// Directory in which archived blocks are stored.
+    dir: PathBuf,
+}
+
+impl BlockArchive {
+    /// Create a new `BlockArchive` in the given directory. The directory is created
+    /// when blocks are first written to it.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Path of the file in which the block at the given height is archived.
+    fn path(&self, height: u64) -> PathBuf {
+        self.dir.join(format!("{height}.block"))
+    }
+
+    /// Read up to `count` consecutive blocks, starting at the given height.
+    ///
+    /// Reading stops at the first block that is not in the archive, so the returned
+    /// blocks never skip a height.
+    pub async fn read(&self, start: u64, count: usize) -> Result<Vec<BlockData>> {
+        let mut blocks = Vec::with_capacity(count);
+
+        for height in (start..).take(count) {
+            let bytes = match fs::read(self.path(height)).await {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == ErrorKind::NotFound => break,
+                Err(e) => return Err(e),
+            };
+            let block: BlockData =
+                deserialize(&bytes).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            blocks.push(block);
+        }
+
+        Ok(blocks)
+    }
+
+    /// Archive the given blocks, replacing any archived blocks at the same heights.
+    pub async fn write(&self, blocks: &[BlockData]) -> Result<()> {
+        fs::create_dir_all(&self.dir).await?;
+
+        for block in blocks {
+            // Write to a temporary file first, so that a crash mid-write never leaves
+            // a partial block in the archive.
+            let path = self.path(block.height);
+            let tmp = path.with_extension("tmp");
+            fs::write(&tmp, serialize(block)).await?;
+            fs::rename(&tmp, &path).await?;
+        }
+
+        Ok(())
+    }
+}
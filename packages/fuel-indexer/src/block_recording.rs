@@ -0,0 +1,124 @@
+use crate::{block_source::BlockSource, IndexerResult};
+use async_std::fs;
+use fuel_indexer_lib::utils::{deserialize, serialize};
+use fuel_indexer_types::fuel::BlockData;
+use std::{
+    io::{Error, ErrorKind, Result},
+    path::Path,
+};
+
+/// Bytes with which every recording starts.
+const MAGIC: &[u8; 4] = b"FIBR";
+
+/// Version of the recording format.
+const FORMAT_VERSION: u8 = 1;
+
+/// Blocks recorded from a Fuel node, to be replayed through an executor in tests.
+///
+/// Recordings are stored as a single file holding the blocks, with their
+/// transactions and receipts, encoded with bincode. Blocks are kept in order of
+/// height, so replaying a recording always handles them in the same order.
+#[derive(Debug, Clone, Default)]
+pub struct BlockRecording {
+    /// Recorded blocks, in order of height.
+    blocks: Vec<BlockData>,
+}
+
+impl BlockRecording {
+    /// Create a recording of the given blocks.
+    pub fn new(mut blocks: Vec<BlockData>) -> Self {
+        blocks.sort_by_key(|block| block.height);
+        blocks.dedup_by_key(|block| block.height);
+        Self { blocks }
+    }
+
+    /// Record the blocks from `start` to `end`, inclusive, fetched from the given source
+    /// in pages of `page_size` blocks.
+    pub async fn record(
+        source: &mut dyn BlockSource,
+        start: u64,
+        end: u64,
+        page_size: usize,
+    ) -> IndexerResult<Self> {
+        let mut blocks = Vec::new();
+        // Pages start at the block after the cursor.
+        let mut cursor = start.checked_sub(1).map(|height| height.to_string());
+
+        loop {
+            let (page, next_cursor) = source.next_page(&cursor, page_size, None).await?;
+            let done = page.is_empty()
+                || next_cursor.is_none()
+                || page.iter().any(|block| block.height >= end);
+            blocks.extend(page.into_iter().filter(|block| block.height <= end));
+
+            if done {
+                break;
+            }
+            cursor = next_cursor;
+        }
+
+        Ok(Self::new(blocks))
+    }
+
+    /// Return the recorded blocks, in order of height.
+    pub fn blocks(&self) -> &[BlockData] {
+        &self.blocks
+    }
+
+    /// Return the recorded blocks, in order of height.
+    pub fn into_blocks(self) -> Vec<BlockData> {
+        self.blocks
+    }
+
+    /// Encode the recording.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_VERSION);
+        bytes.extend(serialize(&self.blocks));
+        bytes
+    }
+
+    /// Decode a recording encoded with `encode`.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let invalid = |message: String| Error::new(ErrorKind::InvalidData, message);
+
+        let data = bytes
+            .strip_prefix(MAGIC.as_slice())
+            .ok_or_else(|| invalid("Not a block recording.".to_string()))?;
+        let (version, data) = data
+            .split_first()
+            .ok_or_else(|| invalid("Truncated block recording.".to_string()))?;
+        if *version != FORMAT_VERSION {
+            return Err(invalid(format!(
+                "Unsupported block recording version {version}, expected {FORMAT_VERSION}."
+            )));
+        }
+
+        let blocks: Vec<BlockData> = deserialize(data).map_err(invalid)?;
+        if blocks
+            .windows(2)
+            .any(|pair| pair[0].height >= pair[1].height)
+        {
+            return Err(invalid(
+                "Blocks of the recording are not in order of height.".to_string(),
+            ));
+        }
+
+        Ok(Self { blocks })
+    }
+
+    /// Read the recording in the given file.
+    pub async fn read(path: impl AsRef<Path>) -> Result<Self> {
+        Self::decode(&fs::read(path.as_ref()).await?)
+    }
+
+    /// Write the recording to the given file, replacing it if it exists.
+    pub async fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        // Write to a temporary file first, so that a failed write never leaves a
+        // partial recording behind.
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, self.encode()).await?;
+        fs::rename(&tmp, path).await
+    }
+}
@@ -0,0 +1,496 @@
+use crate::{
+    block_archive::BlockArchive,
+    executor::{fuel_node_addr, retrieve_blocks_from_node, retrieve_fee_parameters},
+    IndexerConfig, IndexerError, IndexerResult,
+};
+use async_trait::async_trait;
+use fuel_core_client::client::FuelClient;
+use fuel_indexer_lib::{
+    config::{BenchMode, ConversionErrorPolicy},
+    defaults::{FUEL_NODE_HEALTH_CHECK_INTERVAL_SECS, MAX_FUEL_NODE_LAG},
+    manifest::{ContractIds, Manifest},
+};
+use fuel_indexer_types::{
+    fuel::{
+        BlockData, Consensus, FeeParameters, Header, Receipt, ScriptExecutionResult,
+        Transaction, TransactionData, TransactionStatus,
+    },
+    Bech32ContractId,
+};
+use futures::future::join_all;
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+
+/// A page of blocks, along with the cursor of its last block.
+pub type BlockPage = (Vec<BlockData>, Option<String>);
+
+/// A source of blocks for an executor.
+///
+/// Sources are paginated by cursor: a cursor is the height of the last block of a page,
+/// and the next page starts at the block after it.
+#[async_trait]
+pub trait BlockSource: Send {
+    /// Retrieve up to `page_size` blocks after the given cursor, or from the first block
+    /// if there is no cursor.
+    ///
+    /// The returned cursor is `None` if there are no new blocks. If `end_block` is given,
+    /// blocks past it are never returned, and `IndexerError::EndBlockMet` is returned once
+    /// there are no blocks left to return.
+    async fn next_page(
+        &mut self,
+        cursor: &Option<String>,
+        page_size: usize,
+        end_block: Option<u64>,
+    ) -> IndexerResult<BlockPage>;
+}
+
+/// Create the block source configured for the indexer of the given manifest.
+pub fn from_config(config: &IndexerConfig, manifest: &Manifest) -> Box<dyn BlockSource> {
+    if let Some(BenchMode::SyntheticLoad) = config.bench {
+        return Box::new(SyntheticBlockSource::new(manifest));
+    }
+
+    let indexer_uid = manifest.uid();
+    let client =
+        FuelClient::from_str(&fuel_node_addr(config, manifest)).unwrap_or_else(|e| {
+            panic!("Indexer({indexer_uid}) client node connection failed: {e}.")
+        });
+
+    let source: Box<dyn BlockSource> = if config.fallback_fuel_nodes.is_empty() {
+        Box::new(GraphQLBlockSource::new(
+            client,
+            indexer_uid,
+            config.on_conversion_error,
+        ))
+    } else {
+        let mut nodes = vec![(
+            fuel_node_addr(config, manifest),
+            GraphQLBlockSource::new(
+                client,
+                indexer_uid.clone(),
+                config.on_conversion_error,
+            ),
+        )];
+        for addr in &config.fallback_fuel_nodes {
+            let client = FuelClient::from_str(addr).unwrap_or_else(|e| {
+                panic!("Indexer({indexer_uid}) client node connection failed: {e}.")
+            });
+            nodes.push((
+                addr.clone(),
+                GraphQLBlockSource::new(
+                    client,
+                    indexer_uid.clone(),
+                    config.on_conversion_error,
+                ),
+            ));
+        }
+        Box::new(FailoverBlockSource::new(nodes, indexer_uid))
+    };
+
+    match &config.block_archive_dir {
+        Some(dir) => Box::new(ArchivedBlockSource::new(BlockArchive::new(dir), source)),
+        None => source,
+    }
+}
+
+/// Block source that fetches blocks from a Fuel node's GraphQL API.
+pub struct GraphQLBlockSource {
+    client: FuelClient,
+    indexer_uid: String,
+
+    /// What to do with transactions that can't be converted.
+    on_conversion_error: ConversionErrorPolicy,
+
+    /// Fee parameters of the node's chain, fetched with the first page.
+    fee_params: Option<FeeParameters>,
+}
+
+impl GraphQLBlockSource {
+    /// Create a new `GraphQLBlockSource`.
+    pub fn new(
+        client: FuelClient,
+        indexer_uid: impl Into<String>,
+        on_conversion_error: ConversionErrorPolicy,
+    ) -> Self {
+        Self {
+            client,
+            indexer_uid: indexer_uid.into(),
+            on_conversion_error,
+            fee_params: None,
+        }
+    }
+}
+
+impl GraphQLBlockSource {
+    /// Height of the latest block of the node.
+    pub async fn chain_height(&self) -> IndexerResult<u64> {
+        let chain_info = self.client.chain_info().await?;
+        Ok(chain_info.latest_block.header.height.0)
+    }
+}
+
+#[async_trait]
+impl BlockSource for GraphQLBlockSource {
+    async fn next_page(
+        &mut self,
+        cursor: &Option<String>,
+        page_size: usize,
+        end_block: Option<u64>,
+    ) -> IndexerResult<BlockPage> {
+        let fee_params = match self.fee_params {
+            Some(fee_params) => fee_params,
+            None => {
+                let fee_params = retrieve_fee_parameters(&self.client).await?;
+                self.fee_params = Some(fee_params);
+                fee_params
+            }
+        };
+
+        retrieve_blocks_from_node(
+            &self.client,
+            page_size,
+            cursor,
+            end_block,
+            &fee_params,
+            self.on_conversion_error,
+            &self.indexer_uid,
+        )
+        .await
+    }
+}
+
+/// Block source that fetches blocks from one of several Fuel nodes, failing over to the
+/// next node when the current one returns an error or falls behind the others.
+pub struct FailoverBlockSource {
+    /// Addresses of the nodes, along with their block sources.
+    nodes: Vec<(String, GraphQLBlockSource)>,
+
+    /// Index of the node that blocks are fetched from.
+    current: usize,
+
+    indexer_uid: String,
+
+    /// Time at which the heights of the nodes were last checked.
+    last_health_check: Option<Instant>,
+}
+
+impl FailoverBlockSource {
+    /// Create a new `FailoverBlockSource`. Blocks are fetched from the first node
+    /// until it fails or falls behind.
+    pub fn new(nodes: Vec<(String, GraphQLBlockSource)>, indexer_uid: String) -> Self {
+        assert!(!nodes.is_empty(), "At least one Fuel node is required.");
+        Self {
+            nodes,
+            current: 0,
+            indexer_uid,
+            last_health_check: None,
+        }
+    }
+
+    /// Check the heights of the nodes, and fail over to the highest one if the
+    /// current node is unreachable or more than `MAX_FUEL_NODE_LAG` blocks behind it.
+    async fn health_check(&mut self) {
+        self.last_health_check = Some(Instant::now());
+
+        let heights = join_all(self.nodes.iter().map(|(_, node)| node.chain_height()))
+            .await
+            .into_iter()
+            .map(Result::ok)
+            .collect::<Vec<_>>();
+
+        let best = heights
+            .iter()
+            .enumerate()
+            .filter_map(|(i, height)| height.map(|height| (i, height)))
+            .max_by_key(|(_, height)| *height);
+
+        if let Some((best, best_height)) = best {
+            let healthy = heights[self.current]
+                .map_or(false, |height| height + MAX_FUEL_NODE_LAG >= best_height);
+            if !healthy {
+                warn!(
+                    "Indexer({}) Fuel node at {} is unreachable or behind, failing over to {}.",
+                    self.indexer_uid, self.nodes[self.current].0, self.nodes[best].0
+                );
+                self.current = best;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl BlockSource for FailoverBlockSource {
+    async fn next_page(
+        &mut self,
+        cursor: &Option<String>,
+        page_size: usize,
+        end_block: Option<u64>,
+    ) -> IndexerResult<BlockPage> {
+        let health_check_due = self.last_health_check.map_or(true, |checked| {
+            checked.elapsed() >= Duration::from_secs(FUEL_NODE_HEALTH_CHECK_INTERVAL_SECS)
+        });
+        if health_check_due {
+            self.health_check().await;
+        }
+
+        // Try each node once, starting with the current one.
+        let mut result = Err(IndexerError::Unknown("No Fuel nodes".to_string()));
+        for _ in 0..self.nodes.len() {
+            let (addr, node) = &mut self.nodes[self.current];
+            result = node.next_page(cursor, page_size, end_block).await;
+            match &result {
+                Ok(_) | Err(IndexerError::EndBlockMet) => break,
+                Err(e) => {
+                    warn!(
+                        "Indexer({}) failed to fetch blocks from Fuel node at {addr}: {e}.",
+                        self.indexer_uid
+                    );
+                    self.current = (self.current + 1) % self.nodes.len();
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Block source that reads blocks from a block archive, falling back to another source
+/// once the archive runs out of blocks. Blocks retrieved from the other source are added
+/// to the archive.
+pub struct ArchivedBlockSource {
+    archive: BlockArchive,
+    fallback: Box<dyn BlockSource>,
+}
+
+impl ArchivedBlockSource {
+    /// Create a new `ArchivedBlockSource`.
+    pub fn new(archive: BlockArchive, fallback: Box<dyn BlockSource>) -> Self {
+        Self { archive, fallback }
+    }
+}
+
+#[async_trait]
+impl BlockSource for ArchivedBlockSource {
+    async fn next_page(
+        &mut self,
+        cursor: &Option<String>,
+        page_size: usize,
+        end_block: Option<u64>,
+    ) -> IndexerResult<BlockPage> {
+        let start = cursor
+            .as_ref()
+            .and_then(|cursor| cursor.parse::<u64>().ok())
+            .map_or(0, |height| height + 1);
+
+        let mut blocks = self.archive.read(start, page_size).await?;
+        if !blocks.is_empty() {
+            debug!(
+                "Read {} blocks from the block archive at {start}",
+                blocks.len()
+            );
+
+            if let Some(end_block) = end_block {
+                blocks.retain(|block| block.height <= end_block);
+                if blocks.is_empty() {
+                    return Err(IndexerError::EndBlockMet);
+                }
+            }
+
+            let cursor = blocks.last().map(|block| block.height.to_string());
+            return Ok((blocks, cursor));
+        }
+
+        let (blocks, cursor) = self
+            .fallback
+            .next_page(cursor, page_size, end_block)
+            .await?;
+
+        // The archive is only an optimization, so failing to write to it isn't fatal.
+        if let Err(e) = self.archive.write(&blocks).await {
+            warn!("Failed to archive blocks: {e}.");
+        }
+
+        Ok((blocks, cursor))
+    }
+}
+
+/// Number of transactions in each block generated by a `SyntheticBlockSource`.
+const SYNTHETIC_TRANSACTIONS_PER_BLOCK: usize = 10;
+
+/// Block source that generates blocks instead of fetching them, used to benchmark the
+/// service with `--bench synthetic-load`.
+///
+/// The generated chain never ends: every request returns a full page of blocks, unless
+/// the manifest's end block is reached. Each block holds successful script transactions
+/// whose receipts are a call to the manifest's contract, a transfer, a return, and the
+/// script's result, so indexers handling those receipts write entities for each block.
+pub struct SyntheticBlockSource {
+    /// Contract that the generated receipts are emitted by.
+    contract_id: [u8; 32],
+}
+
+impl SyntheticBlockSource {
+    /// Create a new `SyntheticBlockSource` for the indexer of the given manifest.
+    pub fn new(manifest: &Manifest) -> Self {
+        let contract_id = match manifest.contract_id() {
+            ContractIds::Single(Some(id)) => Some(id.as_str()),
+            ContractIds::Multiple(ids) => ids.first().map(String::as_str),
+            ContractIds::Single(None) => None,
+        }
+        .and_then(|id| Bech32ContractId::from_str(id).ok())
+        .map_or([1u8; 32], |id| *id.hash());
+
+        Self { contract_id }
+    }
+
+    /// Generate the block at the given height.
+    pub fn block(&self, height: u64) -> BlockData {
+        let id = synthetic_digest(height, 0);
+        let time = height as i64;
+        let transactions = (0..SYNTHETIC_TRANSACTIONS_PER_BLOCK as u64)
+            .map(|i| TransactionData {
+                transaction: Transaction::default(),
+                status: TransactionStatus::Success {
+                    block: id.into(),
+                    time: time as u64,
+                    program_state: None,
+                },
+                receipts: self.receipts(height, i),
+                id: synthetic_digest(height, i + 1).into(),
+                gas_used: 0,
+                fee: 0,
+            })
+            .collect::<Vec<_>>();
+
+        BlockData {
+            height,
+            id: id.into(),
+            header: Header {
+                id: id.into(),
+                da_height: 0,
+                transactions_count: transactions.len() as u64,
+                output_messages_count: 0,
+                transactions_root: [0u8; 32].into(),
+                output_messages_root: [0u8; 32].into(),
+                height,
+                prev_root: synthetic_digest(height.saturating_sub(1), 0).into(),
+                time,
+                application_hash: [0u8; 32].into(),
+            },
+            producer: None,
+            time,
+            consensus: Consensus::default(),
+            transactions,
+        }
+    }
+
+    /// Generate the receipts of a transaction of the block at the given height.
+    fn receipts(&self, height: u64, position: u64) -> Vec<Receipt> {
+        let recipient = synthetic_digest(height, position + 1);
+        let amount = height * 1_000 + position;
+        vec![
+            Receipt::Call {
+                id: [0u8; 32].into(),
+                to: self.contract_id.into(),
+                amount,
+                asset_id: [0u8; 32].into(),
+                gas: 1_000,
+                param1: 0,
+                param2: 0,
+                pc: 0,
+                is: 0,
+            },
+            Receipt::Transfer {
+                id: self.contract_id.into(),
+                to: recipient.into(),
+                amount,
+                asset_id: [0u8; 32].into(),
+                pc: 0,
+                is: 0,
+            },
+            Receipt::Return {
+                id: self.contract_id.into(),
+                val: amount,
+                pc: 0,
+                is: 0,
+            },
+            Receipt::ScriptResult {
+                result: ScriptExecutionResult::Success,
+                gas_used: 1_000,
+            },
+        ]
+    }
+}
+
+#[async_trait]
+impl BlockSource for SyntheticBlockSource {
+    async fn next_page(
+        &mut self,
+        cursor: &Option<String>,
+        page_size: usize,
+        end_block: Option<u64>,
+    ) -> IndexerResult<BlockPage> {
+        let start = cursor
+            .as_ref()
+            .and_then(|cursor| cursor.parse::<u64>().ok())
+            .map_or(1, |height| height + 1);
+        let mut end = start + page_size.max(1) as u64 - 1;
+        if let Some(end_block) = end_block {
+            if start > end_block {
+                return Err(IndexerError::EndBlockMet);
+            }
+            end = end.min(end_block);
+        }
+
+        let blocks = (start..=end)
+            .map(|height| self.block(height))
+            .collect::<Vec<_>>();
+        Ok((blocks, Some(end.to_string())))
+    }
+}
+
+/// Return a 32-byte value identifying the given height and position.
+fn synthetic_digest(height: u64, position: u64) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(&height.to_be_bytes());
+    bytes[8..16].copy_from_slice(&position.to_be_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_synthetic_block_source_pages_until_end_block() {
+        let manifest = Manifest::try_from(
+            r#"
+namespace: bench
+identifier: synthetic
+graphql_schema: schema.graphql
+module:
+  wasm: bench.wasm
+"#,
+        )
+        .unwrap();
+        let mut source = SyntheticBlockSource::new(&manifest);
+
+        let (page, cursor) = source.next_page(&None, 3, Some(4)).await.unwrap();
+        assert_eq!(
+            page.iter().map(|b| b.height).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(page[0].transactions.len(), SYNTHETIC_TRANSACTIONS_PER_BLOCK);
+        assert_eq!(page[0].transactions[0].receipts.len(), 4);
+
+        let (page, cursor) = source.next_page(&cursor, 3, Some(4)).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert!(matches!(
+            source.next_page(&cursor, 3, Some(4)).await,
+            Err(IndexerError::EndBlockMet)
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-pub(crate) use crate::commands::run;
+pub(crate) use crate::commands::{check_config, dump_ddl, record_blocks, run};
 use clap::{Parser, Subcommand};
 use fuel_indexer_lib::config::IndexerArgs;
 
@@ -19,6 +19,9 @@ pub struct Opt {
 #[derive(Debug, Subcommand)]
 pub enum Indexer {
     Run(IndexerArgs),
+    CheckConfig(IndexerArgs),
+    RecordBlocks(record_blocks::RecordBlocksArgs),
+    DumpDdl(dump_ddl::DumpDdlArgs),
 }
 
 pub async fn run_cli() -> anyhow::Result<()> {
@@ -26,6 +29,9 @@ pub async fn run_cli() -> anyhow::Result<()> {
     match opt {
         Ok(opt) => match opt.command {
             Indexer::Run(args) => run::exec(args).await,
+            Indexer::CheckConfig(args) => check_config::exec(args).await,
+            Indexer::RecordBlocks(args) => record_blocks::exec(args).await,
+            Indexer::DumpDdl(args) => dump_ddl::exec(args),
         },
         Err(e) => e.exit(),
     }
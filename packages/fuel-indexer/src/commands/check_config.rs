@@ -0,0 +1,68 @@
+use fuel_core_client::client::FuelClient;
+use fuel_indexer_database::IndexerConnectionPool;
+use fuel_indexer_lib::config::{
+    utils::derive_http_url, DatabaseConfig, IndexerArgs, IndexerConfig,
+};
+use std::str::FromStr;
+
+// Loads the configuration from all of its sources, prints the effective configuration,
+// and checks that the database and the Fuel node can be reached.
+pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
+    let config = IndexerConfig::load(&args)?;
+
+    // Secrets are redacted by the `Debug` implementations of the configuration.
+    println!("Effective configuration:\n{config:#?}\n");
+
+    let mut ok = true;
+
+    match check_database(&config).await {
+        Ok(()) => println!("✅ Database is reachable at {}.", database_addr(&config)),
+        Err(e) => {
+            ok = false;
+            println!(
+                "❌ Database is not reachable at {}: {e}.",
+                database_addr(&config)
+            );
+        }
+    }
+
+    let fuel_node_addr = derive_http_url(&config.fuel_node.host, &config.fuel_node.port);
+    match check_fuel_node(&fuel_node_addr).await {
+        Ok(height) => println!(
+            "✅ Fuel node is reachable at {fuel_node_addr} (latest block height {height})."
+        ),
+        Err(e) => {
+            ok = false;
+            println!("❌ Fuel node is not reachable at {fuel_node_addr}: {e}.");
+        }
+    }
+
+    if !ok {
+        anyhow::bail!("Configuration check failed.");
+    }
+
+    Ok(())
+}
+
+fn database_addr(config: &IndexerConfig) -> String {
+    match &config.database {
+        DatabaseConfig::Postgres {
+            host,
+            port,
+            database,
+            ..
+        } => format!("{host}:{port}/{database}"),
+    }
+}
+
+async fn check_database(config: &IndexerConfig) -> anyhow::Result<()> {
+    let pool = IndexerConnectionPool::connect(&config.database.to_string()).await?;
+    let _conn = pool.acquire().await?;
+    Ok(())
+}
+
+async fn check_fuel_node(addr: &str) -> anyhow::Result<u32> {
+    let client = FuelClient::from_str(addr)?;
+    let chain_info = client.chain_info().await?;
+    Ok(chain_info.latest_block.header.height.0)
+}
@@ -0,0 +1,65 @@
+use clap::Parser;
+use fuel_indexer_database::DbType;
+use fuel_indexer_lib::manifest::Manifest;
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use std::{fs, path::PathBuf};
+
+/// Print the SQL DDL generated for an indexer's schema, or check it against a file.
+#[derive(Debug, Parser)]
+pub struct DumpDdlArgs {
+    /// Path to the indexer's manifest.
+    #[clap(short, long, help = "Path to the indexer's manifest.")]
+    pub manifest: PathBuf,
+
+    /// Path of the file to write the DDL to. Printed to stdout if not specified.
+    #[clap(short, long, help = "Path of the file to write the DDL to.")]
+    pub output: Option<PathBuf>,
+
+    /// Check that the DDL matches the file given by --output, rather than writing it.
+    #[clap(
+        long,
+        requires = "output",
+        help = "Check that the DDL matches the file given by --output, rather than writing it."
+    )]
+    pub check: bool,
+}
+
+pub fn exec(args: DumpDdlArgs) -> anyhow::Result<()> {
+    let DumpDdlArgs {
+        manifest,
+        output,
+        check,
+    } = args;
+
+    let manifest = Manifest::from_file(&manifest)?;
+    let schema = IndexerSchema::new(
+        manifest.namespace(),
+        manifest.identifier(),
+        &manifest.graphql_schema_content()?,
+        DbType::Postgres,
+        manifest.execution_source(),
+    )?;
+    let ddl = format!("{}\n", schema.ddl());
+
+    match output {
+        Some(path) if check => {
+            let expected = fs::read_to_string(&path).map_err(|e| {
+                anyhow::anyhow!("Failed to read {}: {e}.", path.display())
+            })?;
+            if expected != ddl {
+                anyhow::bail!(
+                    "The DDL generated for the schema differs from {}. Run without --check to update it.",
+                    path.display()
+                );
+            }
+            println!("✅ DDL matches {}.", path.display());
+        }
+        Some(path) => {
+            fs::write(&path, ddl)?;
+            println!("✅ Wrote DDL to {}.", path.display());
+        }
+        None => print!("{ddl}"),
+    }
+
+    Ok(())
+}
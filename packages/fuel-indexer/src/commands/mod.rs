@@ -1 +1,4 @@
+pub mod check_config;
+pub mod dump_ddl;
+pub mod record_blocks;
 pub mod run;
@@ -0,0 +1,69 @@
+use crate::{block_recording::BlockRecording, block_source::GraphQLBlockSource};
+use clap::Parser;
+use fuel_core_client::client::FuelClient;
+use fuel_indexer_lib::{
+    config::{utils::derive_http_url, ConversionErrorPolicy},
+    defaults,
+};
+use std::{path::PathBuf, str::FromStr};
+
+/// Record blocks from a Fuel node into a file, to be replayed through an indexer in tests.
+#[derive(Debug, Parser)]
+pub struct RecordBlocksArgs {
+    /// Host of the Fuel node.
+    #[clap(long, default_value = defaults::FUEL_NODE_HOST, help = "Host of the Fuel node.")]
+    pub fuel_node_host: String,
+
+    /// Listening port of the Fuel node.
+    #[clap(long, default_value = defaults::FUEL_NODE_PORT, help = "Listening port of the Fuel node.")]
+    pub fuel_node_port: String,
+
+    /// Height of the first block to record.
+    #[clap(long, help = "Height of the first block to record.")]
+    pub start_block: u64,
+
+    /// Height of the last block to record.
+    #[clap(long, help = "Height of the last block to record.")]
+    pub end_block: u64,
+
+    /// Path of the file to write the blocks to.
+    #[clap(short, long, help = "Path of the file to write the blocks to.")]
+    pub output: PathBuf,
+}
+
+pub async fn exec(args: RecordBlocksArgs) -> anyhow::Result<()> {
+    let RecordBlocksArgs {
+        fuel_node_host,
+        fuel_node_port,
+        start_block,
+        end_block,
+        output,
+    } = args;
+
+    if start_block > end_block {
+        anyhow::bail!("--start-block must not be greater than --end-block.");
+    }
+
+    let fuel_node_addr = derive_http_url(&fuel_node_host, &fuel_node_port);
+    let client = FuelClient::from_str(&fuel_node_addr)
+        .map_err(|e| anyhow::anyhow!("Invalid Fuel node address: {e}."))?;
+    let mut source =
+        GraphQLBlockSource::new(client, "record-blocks", ConversionErrorPolicy::Fail);
+
+    let recording = BlockRecording::record(
+        &mut source,
+        start_block,
+        end_block,
+        defaults::NODE_BLOCK_PAGE_SIZE,
+    )
+    .await?;
+    recording.write(&output).await?;
+
+    println!(
+        "Recorded {} blocks from {fuel_node_addr} to {}.",
+        recording.blocks().len(),
+        output.display()
+    );
+
+    Ok(())
+}
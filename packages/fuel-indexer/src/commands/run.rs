@@ -4,12 +4,13 @@ use fuel_indexer_lib::{
     config::{IndexerArgs, IndexerConfig},
     defaults,
     manifest::Manifest,
-    utils::{init_logging, ServiceRequest},
+    telemetry,
+    utils::{init_logging, reload_logging, ServiceRequest},
 };
 use tokio::signal::unix::{signal, Signal, SignalKind};
 use tokio::sync::mpsc::channel;
 use tokio_util::sync::CancellationToken;
-use tracing::info;
+use tracing::{error, info, warn};
 
 #[cfg(feature = "api-server")]
 use fuel_indexer_api_server::api::WebApi;
@@ -19,7 +20,6 @@ use fuel_indexer_api_server::api::WebApi;
 async fn shutdown_signal_handler() -> anyhow::Result<CancellationToken> {
     let cancel_token = tokio_util::sync::CancellationToken::new();
 
-    let mut sighup: Signal = signal(SignalKind::hangup())?;
     let mut sigterm: Signal = signal(SignalKind::terminate())?;
     let mut sigint: Signal = signal(SignalKind::interrupt())?;
 
@@ -30,9 +30,6 @@ async fn shutdown_signal_handler() -> anyhow::Result<CancellationToken> {
             #[cfg(unix)]
             {
                 tokio::select! {
-                    _ = sighup.recv() => {
-                        info!("Received SIGHUP. Stopping services.");
-                    }
                     _ = sigterm.recv() => {
                         info!("Received SIGTERM. Stopping services.");
                     }
@@ -54,6 +51,51 @@ async fn shutdown_signal_handler() -> anyhow::Result<CancellationToken> {
     Ok(cancel_token)
 }
 
+// Reloads the configuration whenever a SIGHUP is received. Settings that can be
+// changed at runtime are applied; changes to any other setting require a restart.
+fn config_reload_handler(
+    args: IndexerArgs,
+    mut config: IndexerConfig,
+) -> anyhow::Result<()> {
+    let mut sighup: Signal = signal(SignalKind::hangup())?;
+
+    tokio::spawn(async move {
+        while sighup.recv().await.is_some() {
+            info!("Received SIGHUP. Reloading configuration.");
+
+            let new_config = match IndexerConfig::load(&args) {
+                Ok(new_config) => new_config,
+                Err(e) => {
+                    error!("Failed to reload configuration: {e}.");
+                    continue;
+                }
+            };
+
+            if let Err(e) = reload_logging(&new_config) {
+                error!("Failed to apply reloaded log level: {e}.");
+                continue;
+            }
+            info!("Log level set to '{}'.", new_config.log_level);
+
+            let requires_restart = {
+                let mut unchanged = new_config.clone();
+                unchanged.log_level = config.log_level.clone();
+                unchanged.verbose = config.verbose;
+                format!("{unchanged:?}") != format!("{config:?}")
+            };
+            if requires_restart {
+                warn!("Some changed settings only take effect after a restart.");
+            }
+
+            // Only the reloadable settings of the running service have changed.
+            config.log_level = new_config.log_level;
+            config.verbose = new_config.verbose;
+        }
+    });
+
+    Ok(())
+}
+
 pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
     // for graceful shutdown
     let cancel_token = shutdown_signal_handler().await?;
@@ -98,16 +140,28 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
         forc_postgres::commands::create::exec(create_db_cmd).await?;
     }
 
-    let config = args
-        .config
-        .clone()
-        .map(IndexerConfig::from_file)
-        .unwrap_or(Ok(IndexerConfig::from(args)))?;
+    let config = IndexerConfig::load(&args)?;
 
     init_logging(&config).await?;
 
+    config_reload_handler(args, config.clone())?;
+
     info!("Configuration: {:?}", config);
 
+    if let Some(network) = config.network {
+        info!(
+            "Using the {network} network preset (chain ID {}, genesis height {}).",
+            config.chain_id, config.genesis_height
+        );
+    }
+
+    if let Some(bench) = config.bench {
+        info!(
+            "Running the {} benchmark: indexers index generated blocks instead of blocks from the Fuel node.",
+            bench.as_ref()
+        );
+    }
+
     #[allow(unused)]
     let (tx, rx) = channel::<ServiceRequest>(defaults::SERVICE_REQUEST_CHANNEL_SIZE);
 
@@ -176,6 +230,8 @@ pub async fn exec(args: IndexerArgs) -> anyhow::Result<()> {
 
     cancel_token.cancelled().await;
 
+    telemetry::shutdown();
+
     if embedded_database {
         let name = postgres_database.unwrap_or(defaults::POSTGRES_DATABASE.to_string());
 
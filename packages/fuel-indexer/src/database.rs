@@ -1,21 +1,99 @@
-use crate::{IndexerConfig, IndexerResult, Manifest};
-use fuel_indexer_database::{queries, IndexerConnection, IndexerConnectionPool};
+use crate::{
+    executor::fuel_node_addr,
+    http::HttpClient,
+    memory::MemoryStore,
+    sink::{EntityChange, EntitySinks},
+    IndexerConfig, IndexerError, IndexerResult, Manifest,
+};
+use fuel_core_client::client::{
+    schema::coin::CoinStatus, FuelClient, PageDirection, PaginationRequest,
+};
+use fuel_indexer_database::{
+    queries, types::SqlNamed, DbType, IndexerConnection, IndexerConnectionPool,
+};
 use fuel_indexer_lib::{
-    fully_qualified_namespace, graphql::types::IdCol, utils::format_sql_query,
+    fully_qualified_namespace,
+    graphql::{types::IdCol, GraphQLSchema, ParsedGraphQLSchema, Rollup},
+    manifest::ReplicaIdentity,
+    utils::format_sql_query,
+    ExecutionSource,
+};
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
+use fuel_indexer_schema::{
+    db::{tables::IndexerSchema, IndexerSchemaDbError},
+    query::EntityQuery,
+    FtColumn,
+};
+use fuel_indexer_types::fuel::{Address, AssetId, Coin, UtxoId};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    str::FromStr,
+    time::Instant,
 };
-use fuel_indexer_schema::FtColumn;
-use std::collections::HashMap;
-use tracing::{debug, error, info};
+use tracing::{debug, error, field::Empty, info, info_span, Span};
+
+tokio::task_local! {
+    /// Index (within its block) of the transaction whose handlers are running in
+    /// the current task, when a native indexer dispatches transactions in parallel.
+    pub static TRANSACTION_INDEX: usize;
+}
+
+/// Writes buffered while the transactions of a block are processed in parallel.
+///
+/// Object writes are keyed by entity, and then by transaction index, so that the
+/// merged result matches sequential processing regardless of task scheduling.
+/// Handlers of these transactions may only write entities: a read would depend on
+/// which of the transactions before it had already been handled.
+#[derive(Debug, Default)]
+struct DeferredWrites {
+    /// Object writes, keyed by `(type_id, object_id)` and transaction index.
+    objects: BTreeMap<(i64, String), BTreeMap<usize, (Vec<FtColumn>, Vec<u8>)>>,
+
+    /// Many-to-many queries, keyed by transaction index.
+    many_to_many: BTreeMap<usize, Vec<String>>,
+
+    /// Whether a handler tried to read entities, which fails the block.
+    read_denied: bool,
+}
+
+/// Number of writes queued in a transaction after which they're written without
+/// waiting for the handlers to return.
+const MAX_QUEUED_WRITES: usize = 1000;
+
+/// Writes made by handlers in the current transaction that haven't been sent to the
+/// database yet.
+///
+/// Writes are queued rather than awaited one at a time, and are sent together in a
+/// single round trip once the handlers return, or before anything reads from the
+/// transaction.
+#[derive(Debug, Default)]
+struct QueuedWrites {
+    /// Statements of the writes, in the order they were made.
+    statements: Vec<String>,
+
+    /// Entity changes of the object writes, recorded once they're written.
+    changes: Vec<EntityChange>,
+
+    /// Entities and IDs of the rows of rollup sources written.
+    rollup_changes: Vec<(String, String)>,
+}
 
 /// Database for an executor instance, with schema info.
 #[derive(Debug)]
 pub struct Database {
-    /// Connection pool for the database.
-    pool: IndexerConnectionPool,
+    /// Connection pool for the database, which in-memory databases don't have.
+    pool: Option<IndexerConnectionPool>,
+
+    /// Entities held in memory, for databases created with `Database::in_memory`.
+    memory: Option<MemoryStore>,
 
     /// Stashed connection for the current transaction.
     stashed: Option<IndexerConnection>,
 
+    /// Span covering the current transaction, closed once it is committed or reverted.
+    transaction_span: Option<Span>,
+
     /// Namespace of the indexer.
     namespace: String,
 
@@ -31,8 +109,57 @@ pub struct Database {
     /// Mapping of `TypeId`s to tables.
     tables: HashMap<i64, String>,
 
+    /// Column types of each table, in the same order as `schema`.
+    column_types: HashMap<String, Vec<String>>,
+
     /// Indexer configuration.
     config: IndexerConfig,
+
+    /// Writes buffered while transactions are processed in parallel.
+    deferred: Option<DeferredWrites>,
+
+    /// Writes of the current transaction that haven't been sent to the database.
+    queued: QueuedWrites,
+
+    /// Error of the queued writes that failed in the current transaction, which
+    /// leaves it aborted, so that it's reverted rather than committed.
+    write_error: Option<String>,
+
+    /// Maximum number of rows that may be written in the current transaction.
+    row_limit: Option<u64>,
+
+    /// Number of rows written in the current transaction.
+    rows_written: u64,
+
+    /// Address of the Fuel node that chain state is queried from.
+    fuel_node_addr: String,
+
+    /// Balances queried while processing the current block, keyed by owner and asset.
+    balances: HashMap<(Address, AssetId), u64>,
+
+    /// Coins queried while processing the current block, keyed by owner and asset.
+    coins: HashMap<(Address, AssetId), Vec<Coin>>,
+
+    /// Client for the indexer's outbound HTTP requests.
+    http: HttpClient,
+
+    /// Sinks to which committed entity changes are published.
+    sinks: EntitySinks,
+
+    /// Entity changes made in the current transaction, published once it is committed.
+    changes: Vec<EntityChange>,
+
+    /// Name and replica identity of the publication of the indexer's tables, if any.
+    publication: Option<(String, ReplicaIdentity)>,
+
+    /// Method of execution of the indexer, used to parse its schema.
+    exec_source: ExecutionSource,
+
+    /// Rollup entities of the indexer's schema.
+    rollups: Vec<Rollup>,
+
+    /// IDs of the rows of each rollup source table written in the current transaction.
+    rollup_changes: HashMap<String, BTreeSet<String>>,
 }
 
 // TODO: https://github.com/FuelLabs/fuel-indexer/issues/1139
@@ -44,28 +171,183 @@ fn is_id_only_upsert(columns: &[String]) -> bool {
     columns.len() == 2 && columns[0] == IdCol::to_lowercase_string()
 }
 
+/// Return a literal of the given bytes, for statements that can't bind them.
+fn bytea_literal(bytes: &[u8]) -> String {
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("decode('{hex}', 'hex')")
+}
+
 impl Database {
     /// Create a new `Database`.
     pub async fn new(
         pool: IndexerConnectionPool,
         manifest: &Manifest,
         config: &IndexerConfig,
-    ) -> Database {
-        Database {
-            pool,
+    ) -> IndexerResult<Database> {
+        Ok(Database {
+            pool: Some(pool),
+            memory: None,
             stashed: None,
+            transaction_span: None,
             namespace: manifest.namespace().to_string(),
             identifier: manifest.identifier().to_string(),
             version: Default::default(),
             schema: Default::default(),
             tables: Default::default(),
+            column_types: Default::default(),
             config: config.clone(),
+            deferred: None,
+            queued: QueuedWrites::default(),
+            write_error: None,
+            row_limit: None,
+            rows_written: 0,
+            fuel_node_addr: fuel_node_addr(config, manifest),
+            balances: Default::default(),
+            coins: Default::default(),
+            http: HttpClient::new(manifest.capabilities().allow_http),
+            sinks: EntitySinks::new(manifest).await?,
+            changes: Vec::new(),
+            publication: manifest.publication_name().zip(
+                manifest
+                    .replication()
+                    .map(|replication| replication.replica_identity),
+            ),
+            exec_source: manifest.execution_source(),
+            rollups: Vec::new(),
+            rollup_changes: HashMap::new(),
+        })
+    }
+
+    /// Create a `Database` that holds the entities of the given schema in memory,
+    /// to run an indexer's handlers without a Postgres database.
+    ///
+    /// Entities are written and queried as they would be in Postgres, but rollups
+    /// aren't maintained, and entity changes aren't published to the manifest's sinks.
+    pub fn in_memory(
+        manifest: &Manifest,
+        config: &IndexerConfig,
+        schema: &GraphQLSchema,
+    ) -> IndexerResult<Database> {
+        let namespace = manifest.namespace().to_string();
+        let identifier = manifest.identifier().to_string();
+        let exec_source = manifest.execution_source();
+        let indexer_schema = IndexerSchema::new(
+            &namespace,
+            &identifier,
+            schema,
+            DbType::Postgres,
+            exec_source.clone(),
+        )?;
+
+        let fqn = fully_qualified_namespace(&namespace, &identifier);
+        let mut tables = HashMap::new();
+        let mut column_names = HashMap::new();
+        let mut column_types = HashMap::new();
+        for table in indexer_schema.tables() {
+            let name = format!("{fqn}.{}", table.sql_name());
+            for column in table.columns() {
+                tables.entry(column.type_id).or_insert_with(|| name.clone());
+                column_names
+                    .entry(name.clone())
+                    .or_insert_with(Vec::new)
+                    .push(column.name.clone());
+                column_types
+                    .entry(name.clone())
+                    .or_insert_with(Vec::new)
+                    .push(column.coltype.to_string());
+            }
         }
+
+        Ok(Database {
+            pool: None,
+            memory: Some(MemoryStore::default()),
+            stashed: None,
+            transaction_span: None,
+            namespace,
+            identifier,
+            version: schema.version().to_string(),
+            schema: column_names,
+            tables,
+            column_types,
+            config: config.clone(),
+            deferred: None,
+            queued: QueuedWrites::default(),
+            write_error: None,
+            row_limit: None,
+            rows_written: 0,
+            fuel_node_addr: fuel_node_addr(config, manifest),
+            balances: Default::default(),
+            coins: Default::default(),
+            http: HttpClient::new(manifest.capabilities().allow_http),
+            sinks: EntitySinks::default(),
+            changes: Vec::new(),
+            publication: None,
+            exec_source,
+            rollups: indexer_schema.parsed().rollups().to_vec(),
+            rollup_changes: HashMap::new(),
+        })
+    }
+
+    /// Return the committed rows of the given entity, as `(column, value)` pairs
+    /// ordered by ID, if this database holds its entities in memory.
+    pub fn memory_rows(&self, entity: &str) -> Option<Vec<Vec<(String, FtColumn)>>> {
+        let memory = self.memory.as_ref()?;
+        let table = format!(
+            "{}.{}",
+            fully_qualified_namespace(&self.namespace, &self.identifier),
+            entity.to_lowercase()
+        );
+        let names = self.schema.get(&table)?;
+        Some(
+            memory
+                .committed()
+                .rows(&table)
+                .into_iter()
+                .map(|row| names.iter().cloned().zip(row.columns.clone()).collect())
+                .collect(),
+        )
+    }
+
+    /// Set the maximum number of rows that may be written in each transaction.
+    pub fn set_row_limit(&mut self, row_limit: Option<u64>) {
+        self.row_limit = row_limit;
+    }
+
+    /// Whether more rows were written in the current transaction than allowed.
+    pub fn row_limit_exceeded(&self) -> bool {
+        self.row_limit
+            .map(|limit| self.rows_written > limit)
+            .unwrap_or(false)
+    }
+
+    /// Number of rows written in the current transaction.
+    pub fn rows_written(&self) -> u64 {
+        self.rows_written
     }
 
     /// Open a database transaction.
     pub async fn start_transaction(&mut self) -> IndexerResult<usize> {
-        let conn = self.pool.acquire().await?;
+        self.rows_written = 0;
+        self.queued = QueuedWrites::default();
+        self.write_error = None;
+        self.changes.clear();
+        self.rollup_changes.clear();
+        self.transaction_span = Some(info_span!("db_transaction", outcome = Empty));
+        self.balances.clear();
+        self.coins.clear();
+        if let Some(memory) = self.memory.as_mut() {
+            memory.start_transaction();
+            return Ok(0);
+        }
+        let conn = self
+            .pool
+            .as_ref()
+            .expect("Databases without a pool hold their entities in memory.")
+            .acquire()
+            .await?;
         self.stashed = Some(conn);
         debug!("Connection stashed as: {:?}", self.stashed);
         let conn = self.stashed.as_mut().expect(
@@ -77,24 +359,171 @@ impl Database {
 
     /// Commit transaction to database.
     pub async fn commit_transaction(&mut self) -> IndexerResult<usize> {
+        if let Some(memory) = self.memory.as_mut() {
+            memory.commit_transaction();
+            self.rollup_changes.clear();
+            self.changes.clear();
+            self.end_transaction_span("commit");
+            return Ok(0);
+        }
+        // Writes that fail leave the transaction aborted, so it's reverted rather
+        // than committed, even if the failure was reported to a handler that went on.
+        if let Err(e) = self.flush_writes().await {
+            self.revert_transaction().await?;
+            return Err(e);
+        }
+        self.refresh_rollups().await?;
         let conn = self
             .stashed
             .as_mut()
             .expect("No stashed connection for commit. Was a transaction started?");
         let res = queries::commit_transaction(conn).await?;
+        self.end_transaction_span("commit");
+        if !self.changes.is_empty() {
+            self.sinks.publish(std::mem::take(&mut self.changes)).await;
+        }
         Ok(res)
     }
 
     /// Revert open transaction.
     pub async fn revert_transaction(&mut self) -> IndexerResult<usize> {
+        if let Some(memory) = self.memory.as_mut() {
+            memory.revert_transaction();
+            self.changes.clear();
+            self.rollup_changes.clear();
+            self.end_transaction_span("revert");
+            return Ok(0);
+        }
         let conn = self
             .stashed
             .as_mut()
             .expect("No stashed connection for revert. Was a transaction started?");
         let res = queries::revert_transaction(conn).await?;
+        self.queued = QueuedWrites::default();
+        self.write_error = None;
+        self.changes.clear();
+        self.rollup_changes.clear();
+        self.end_transaction_span("revert");
         Ok(res)
     }
 
+    /// Recompute the buckets of each rollup that hold rows of its source written in
+    /// the current transaction, so that rollups are committed along with their sources.
+    async fn refresh_rollups(&mut self) -> IndexerResult<()> {
+        if self.rollup_changes.is_empty() {
+            return Ok(());
+        }
+
+        let namespace = fully_qualified_namespace(&self.namespace, &self.identifier);
+        let changes = std::mem::take(&mut self.rollup_changes);
+        let conn = self
+            .stashed
+            .as_mut()
+            .expect("No stashed connection for rollups. Was a transaction started?");
+
+        for rollup in self.rollups.iter() {
+            let ids = match changes.get(&rollup.source.to_lowercase()) {
+                Some(ids) => ids.iter().cloned().collect::<Vec<_>>(),
+                None => continue,
+            };
+
+            let query = rollup.refresh_query(&namespace, &ids);
+            if self.config.verbose {
+                info!("{query}");
+            }
+            queries::execute_query(conn, query).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Send the writes queued in the current transaction to the database, in a
+    /// single round trip.
+    ///
+    /// Once writes have failed, the transaction is aborted, so this fails until the
+    /// transaction is reverted, and the blocks of the transaction are handled again.
+    async fn flush_writes(&mut self) -> IndexerResult<()> {
+        if let Some(e) = &self.write_error {
+            return Err(IndexerError::WriteError(e.clone()));
+        }
+
+        if self.queued.statements.is_empty() {
+            return Ok(());
+        }
+
+        let queued = std::mem::take(&mut self.queued);
+        let conn = self
+            .stashed
+            .as_mut()
+            .expect("No stashed connection for flush. Was a transaction started?");
+
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+
+        let result = queries::execute_batch(conn, queued.statements.join(";\n")).await;
+
+        #[cfg(feature = "metrics")]
+        METRICS.executor.record_db_write_duration(
+            &self.namespace,
+            &self.identifier,
+            start.elapsed().as_secs_f64(),
+        );
+
+        if let Err(e) = result {
+            let e = e.to_string();
+            self.write_error = Some(e.clone());
+            return Err(IndexerError::WriteError(e));
+        }
+        self.changes.extend(queued.changes);
+        for (entity, id) in queued.rollup_changes {
+            self.rollup_changes.entry(entity).or_default().insert(id);
+        }
+
+        Ok(())
+    }
+
+    /// Send the queued writes to the database before reading from the current
+    /// transaction, logging and returning the error if they fail.
+    async fn flush_writes_before(&mut self, operation: &str) -> IndexerResult<()> {
+        self.flush_writes().await.map_err(|e| {
+            error!("Failed to write entities before {operation}: {e:?}");
+            e
+        })
+    }
+
+    /// Close the span of the current transaction, recording how it ended.
+    fn end_transaction_span(&mut self, outcome: &str) {
+        if let Some(span) = self.transaction_span.take() {
+            span.record("outcome", outcome);
+        }
+    }
+
+    /// Save a checkpoint of the executor's progress for this indexer.
+    ///
+    /// Checkpoints are written outside of the current transaction, so they should
+    /// only be taken once the blocks they cover have been committed.
+    pub async fn put_checkpoint(
+        &mut self,
+        block_height: u64,
+        snapshot: Vec<u8>,
+    ) -> IndexerResult<()> {
+        // In-memory databases don't record the indexer's progress.
+        let pool = match &self.pool {
+            Some(pool) => pool,
+            None => return Ok(()),
+        };
+        let mut conn = pool.acquire().await?;
+        queries::put_checkpoint(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+            block_height,
+            snapshot,
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Build an upsert query using a set of columns, insert values, update values, and a table name.
     fn upsert_query(
         &self,
@@ -102,20 +531,23 @@ impl Database {
         columns: &[String],
         inserts: Vec<String>,
         updates: Vec<String>,
+        object: String,
     ) -> String {
         if is_id_only_upsert(columns) {
             format!(
-                "INSERT INTO {} ({}) VALUES ({}, $1::bytea) ON CONFLICT(id) DO NOTHING",
+                "INSERT INTO {} ({}) VALUES ({}, {}) ON CONFLICT(id) DO NOTHING",
                 table,
                 columns.join(", "),
                 inserts.join(", "),
+                object,
             )
         } else {
             format!(
-                "INSERT INTO {} ({}) VALUES ({}, $1::bytea) ON CONFLICT(id) DO UPDATE SET {}",
+                "INSERT INTO {} ({}) VALUES ({}, {}) ON CONFLICT(id) DO UPDATE SET {}",
                 table,
                 columns.join(", "),
                 inserts.join(", "),
+                object,
                 updates.join(", "),
             )
         }
@@ -130,13 +562,123 @@ impl Database {
         q
     }
 
+    /// Save the sync progress of this indexer.
+    pub async fn put_status(
+        &mut self,
+        current_height: u64,
+        chain_tip: u64,
+        blocks_per_second: f64,
+        rows_per_minute: f64,
+        cpu_seconds: f64,
+    ) -> IndexerResult<()> {
+        // In-memory databases don't record the indexer's progress.
+        let pool = match &self.pool {
+            Some(pool) => pool,
+            None => return Ok(()),
+        };
+        let mut conn = pool.acquire().await?;
+        queries::put_indexer_status(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+            current_height,
+            chain_tip,
+            blocks_per_second,
+            rows_per_minute,
+            cpu_seconds,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Save the last error raised by this indexer while processing blocks, along
+    /// with the block it failed on and how many times in a row it has failed on it.
+    pub async fn put_error(
+        &mut self,
+        error: &str,
+        failed_height: Option<u64>,
+        consecutive_failures: u32,
+    ) -> IndexerResult<()> {
+        // In-memory databases don't record the indexer's progress.
+        let pool = match &self.pool {
+            Some(pool) => pool,
+            None => return Ok(()),
+        };
+        let mut conn = pool.acquire().await?;
+        queries::put_indexer_error(
+            &mut conn,
+            &self.namespace,
+            &self.identifier,
+            error,
+            failed_height,
+            consecutive_failures,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Start buffering writes made by handlers running in a `TRANSACTION_INDEX` scope.
+    ///
+    /// Any previously buffered writes are discarded.
+    pub fn start_deferred_writes(&mut self) {
+        self.deferred = Some(DeferredWrites::default());
+    }
+
+    /// Stop buffering writes, and write the merged result to the database.
+    ///
+    /// For each entity, the write from the latest transaction wins, as it would
+    /// if the transactions had been processed sequentially.
+    pub async fn flush_deferred_writes(&mut self) -> IndexerResult<()> {
+        let deferred = match self.deferred.take() {
+            Some(deferred) => deferred,
+            None => return Ok(()),
+        };
+
+        // Handlers don't fail when a read is denied, so the block fails here.
+        if deferred.read_denied {
+            return Err(IndexerError::ParallelTransactionRead);
+        }
+
+        for ((type_id, _), mut writes) in deferred.objects {
+            if let Some((_, (columns, bytes))) = writes.pop_last() {
+                self.put_object(type_id, columns, bytes).await?;
+            }
+        }
+
+        let queries = deferred.many_to_many.into_values().flatten().collect();
+        self.put_many_to_many_record(queries).await
+    }
+
     /// Put an object into the database.
+    ///
+    /// Writes are queued, so this only fails if the queue had to be sent to the
+    /// database and failed, in which case the transaction must be reverted.
     pub async fn put_object(
         &mut self,
         type_id: i64,
         columns: Vec<FtColumn>,
         bytes: Vec<u8>,
-    ) {
+    ) -> IndexerResult<()> {
+        if let Some(deferred) = self.deferred.as_mut() {
+            if let Ok(tx_index) = TRANSACTION_INDEX.try_with(|i| *i) {
+                // The first column of every entity is its ID.
+                let object_id = columns[0].query_fragment();
+                deferred
+                    .objects
+                    .entry((type_id, object_id))
+                    .or_default()
+                    .insert(tx_index, (columns, bytes));
+                return Ok(());
+            }
+        }
+
+        self.rows_written += 1;
+        if self.row_limit_exceeded() {
+            // The executor reverts the transaction once the handler returns.
+            debug!("Row limit exceeded, skipping put_object for TypeId({type_id}).");
+            return Ok(());
+        }
+
         let table = match self.tables.get(&type_id) {
             Some(t) => t,
             None => {
@@ -150,10 +692,29 @@ Do your WASM modules need to be rebuilt?
 "#,
                     self.tables,
                 );
-                return;
+                return Ok(());
             }
         };
 
+        // The first column of every entity is its ID.
+        let change = (!self.sinks.is_empty()).then(|| EntityChange {
+            entity: table.rsplit('.').next().unwrap_or(table).to_string(),
+            id: columns[0].query_fragment(),
+            fields: self.schema[table]
+                .iter()
+                .cloned()
+                .zip(columns.iter().cloned())
+                .collect(),
+        });
+
+        // Rows of rollup sources are recorded so that their buckets are recomputed.
+        let entity = table.rsplit('.').next().unwrap_or(table);
+        let rollup_change = self
+            .rollups
+            .iter()
+            .any(|r| r.source.eq_ignore_ascii_case(entity))
+            .then(|| (entity.to_string(), columns[0].query_fragment()));
+
         let inserts: Vec<_> = columns.iter().map(|col| col.query_fragment()).collect();
         let updates: Vec<_> = self.schema[table]
             .iter()
@@ -161,43 +722,135 @@ Do your WASM modules need to be rebuilt?
             .map(|(colname, value)| format!("{colname} = {}", value.query_fragment()))
             .collect();
 
-        let columns = self.schema[table].clone();
+        if let Some(memory) = self.memory.as_mut() {
+            memory.pending().put(table, columns, bytes);
+            return Ok(());
+        }
 
-        let query_text =
-            format_sql_query(self.upsert_query(table, &columns, inserts, updates));
+        let columns = self.schema[table].clone();
 
-        let conn = self
-            .stashed
-            .as_mut()
-            .expect("No stashed connection for put. Was a transaction started?");
+        let query_text = format_sql_query(self.upsert_query(
+            table,
+            &columns,
+            inserts,
+            updates,
+            bytea_literal(&bytes),
+        ));
 
         if self.config.verbose {
             info!("{query_text}");
         }
 
-        if let Err(e) = queries::put_object(conn, query_text, bytes).await {
-            error!("Failed to put_object: {e:?}");
+        self.queued.statements.push(query_text);
+        self.queued.changes.extend(change);
+        self.queued.rollup_changes.extend(rollup_change);
+
+        if self.queued.statements.len() >= MAX_QUEUED_WRITES {
+            self.flush_writes_before("put_object").await?;
         }
+
+        Ok(())
+    }
+
+    /// Deny reads made by handlers of transactions processed in parallel.
+    fn deny_parallel_read(&mut self) -> IndexerResult<()> {
+        if let Some(deferred) = self.deferred.as_mut() {
+            if TRANSACTION_INDEX.try_with(|_| ()).is_ok() {
+                deferred.read_denied = true;
+                return Err(IndexerError::ParallelTransactionRead);
+            }
+        }
+        Ok(())
     }
 
     /// Get an object from the database.
-    pub async fn get_object(&mut self, type_id: i64, object_id: u64) -> Option<Vec<u8>> {
+    ///
+    /// Fails if the writes queued before it failed, in which case the transaction
+    /// must be reverted, or if it's called by the handler of a transaction
+    /// processed in parallel.
+    pub async fn get_object(
+        &mut self,
+        type_id: i64,
+        object_id: u64,
+    ) -> IndexerResult<Option<Vec<u8>>> {
+        self.deny_parallel_read()?;
+
         let table = &self.tables[&type_id];
+        if let Some(memory) = self.memory.as_mut() {
+            return Ok(memory
+                .pending()
+                .get(table, &object_id.to_string())
+                .map(|row| row.object.clone()));
+        }
+
         let query = self.get_query(table, object_id);
+        self.flush_writes_before("get_object").await?;
         let conn = self
             .stashed
             .as_mut()
             .expect("No stashed connection for get. Was a transaction started?");
 
         match queries::get_object(conn, query).await {
-            Ok(v) => Some(v),
+            Ok(v) => Ok(Some(v)),
             Err(e) => {
                 if let sqlx::Error::RowNotFound = e {
                     debug!("Row not found for object ID: {object_id}");
                 } else {
                     error!("Failed to get_object: {e:?}");
                 }
-                None
+                Ok(None)
+            }
+        }
+    }
+
+    /// Get the objects of all entities of the given type that match a query.
+    ///
+    /// Fails if the writes queued before it failed, in which case the transaction
+    /// must be reverted, or if it's called by the handler of a transaction
+    /// processed in parallel.
+    pub async fn find_objects(
+        &mut self,
+        type_id: i64,
+        query: &EntityQuery,
+    ) -> IndexerResult<Vec<Vec<u8>>> {
+        self.deny_parallel_read()?;
+
+        let table = &self.tables[&type_id];
+
+        // Column names are interpolated into the query, so only known columns are allowed.
+        if let Some(column) = query
+            .columns()
+            .find(|column| !self.schema[table].iter().any(|c| c == column))
+        {
+            error!("Failed to find_objects: unknown column '{column}' in table {table}.");
+            return Ok(Vec::new());
+        }
+
+        if let Some(memory) = self.memory.as_mut() {
+            return Ok(memory
+                .pending()
+                .find(table, &self.schema[table], query)
+                .into_iter()
+                .map(|row| row.object.clone())
+                .collect());
+        }
+
+        let query = query.to_sql(table);
+        if self.config.verbose {
+            info!("{query}");
+        }
+
+        self.flush_writes_before("find_objects").await?;
+        let conn = self
+            .stashed
+            .as_mut()
+            .expect("No stashed connection for find. Was a transaction started?");
+
+        match queries::get_objects(conn, query).await {
+            Ok(objects) => Ok(objects),
+            Err(e) => {
+                error!("Failed to find_objects: {e:?}");
+                Ok(Vec::new())
             }
         }
     }
@@ -206,12 +859,22 @@ Do your WASM modules need to be rebuilt?
     pub async fn load_schema(&mut self, version: String) -> IndexerResult<()> {
         self.version = version;
 
+        // In-memory databases load their schema when they are created.
+        if self.memory.is_some() {
+            return Ok(());
+        }
+
         info!(
             "Database loading schema for Indexer({}.{}) with Version({}).",
             self.namespace, self.identifier, self.version
         );
 
-        let mut conn = self.pool.acquire().await?;
+        let mut conn = self
+            .pool
+            .as_ref()
+            .expect("Databases without a pool hold their entities in memory.")
+            .acquire()
+            .await?;
         let columns = queries::columns_get_schema(
             &mut conn,
             &self.namespace,
@@ -231,6 +894,11 @@ Do your WASM modules need to be rebuilt?
                 .entry(column.type_id)
                 .or_insert_with(|| table.to_string());
 
+            self.column_types
+                .entry(table.to_string())
+                .or_insert_with(Vec::new)
+                .push(column.column_type);
+
             let columns = self
                 .schema
                 .entry(table.to_string())
@@ -239,9 +907,54 @@ Do your WASM modules need to be rebuilt?
             columns.push(column.column_name);
         }
 
+        let root =
+            queries::graph_root_latest(&mut conn, &self.namespace, &self.identifier)
+                .await?;
+        let parsed = ParsedGraphQLSchema::new(
+            &self.namespace,
+            &self.identifier,
+            self.exec_source.clone(),
+            Some(&GraphQLSchema::new(root.schema)),
+        )
+        .map_err(IndexerSchemaDbError::from)?;
+        self.rollups = parsed.rollups().to_vec();
+
+        if let Some((publication, replica_identity)) = &self.publication {
+            let mut tables = self.schema.keys().cloned().collect::<Vec<_>>();
+            tables.sort();
+
+            info!(
+                "Database publishing {} tables of Indexer({}.{}) in Publication({publication}).",
+                tables.len(),
+                self.namespace,
+                self.identifier
+            );
+            queries::put_publication(
+                &mut conn,
+                publication,
+                &tables,
+                replica_identity.as_sql(),
+            )
+            .await?;
+        }
+
         Ok(())
     }
 
+    /// Return the names and types of the columns of the entity with the given `TypeId`.
+    pub fn columns(&self, type_id: i64) -> Option<Vec<(&str, &str)>> {
+        let table = self.tables.get(&type_id)?;
+        let names = self.schema.get(table)?;
+        let types = self.column_types.get(table)?;
+        Some(
+            names
+                .iter()
+                .zip(types.iter())
+                .map(|(name, ty)| (name.as_str(), ty.as_str()))
+                .collect(),
+        )
+    }
+
     pub fn namespace(&self) -> &str {
         &self.namespace
     }
@@ -264,20 +977,265 @@ Do your WASM modules need to be rebuilt?
     ///
     /// There are multiple queries here because a single parent `TypeDefinition` can have several
     /// many-to-many relationships with children `TypeDefinition`s.
-    pub async fn put_many_to_many_record(&mut self, queries: Vec<String>) {
+    pub async fn put_many_to_many_record(
+        &mut self,
+        queries: Vec<String>,
+    ) -> IndexerResult<()> {
+        if let Some(deferred) = self.deferred.as_mut() {
+            if let Ok(tx_index) = TRANSACTION_INDEX.try_with(|i| *i) {
+                deferred
+                    .many_to_many
+                    .entry(tx_index)
+                    .or_default()
+                    .extend(queries);
+                return Ok(());
+            }
+        }
+
+        if let Some(memory) = self.memory.as_mut() {
+            memory.pending().many_to_many.extend(queries);
+            return Ok(());
+        }
+
+        for query in queries {
+            if self.config.verbose {
+                info!("{query}");
+            }
+
+            self.queued.statements.push(query);
+        }
+
+        if self.queued.statements.len() >= MAX_QUEUED_WRITES {
+            self.flush_writes_before("put_many_to_many_record").await?;
+        }
+
+        Ok(())
+    }
+
+    /// Register a contract discovered at runtime, to be indexed using the given template.
+    ///
+    /// The registration is part of the current transaction, so it is only persisted
+    /// if the block in which the contract was discovered is committed.
+    pub async fn register_contract(
+        &mut self,
+        template: String,
+        contract_id: String,
+    ) -> IndexerResult<()> {
+        if let Some(memory) = self.memory.as_mut() {
+            memory.pending().contracts.push((template, contract_id));
+            return Ok(());
+        }
+
+        self.flush_writes_before("register_contract").await?;
         let conn = self
             .stashed
             .as_mut()
             .expect("No stashed connection for put. Was a transaction started?");
 
-        for query in queries {
-            if self.config.verbose {
-                info!("{query}");
+        if let Err(e) = queries::register_contract(
+            conn,
+            &self.namespace,
+            &self.identifier,
+            &template,
+            &contract_id,
+        )
+        .await
+        {
+            error!("Failed to register_contract: {e:?}");
+        }
+
+        Ok(())
+    }
+
+    /// Return all contracts discovered at runtime, as `(template, contract_id)` pairs.
+    pub async fn discovered_contracts(&mut self) -> IndexerResult<Vec<(String, String)>> {
+        if let Some(memory) = self.memory.as_mut() {
+            return Ok(memory.pending().contracts.clone());
+        }
+
+        self.flush_writes_before("discovered_contracts").await?;
+        let conn = self
+            .stashed
+            .as_mut()
+            .expect("No stashed connection for get. Was a transaction started?");
+
+        match queries::discovered_contracts(conn, &self.namespace, &self.identifier).await
+        {
+            Ok(contracts) => Ok(contracts
+                .into_iter()
+                .map(|c| (c.template, c.contract_id))
+                .collect()),
+            Err(e) => {
+                error!("Failed to get discovered_contracts: {e:?}");
+                Ok(Vec::new())
             }
+        }
+    }
 
-            if let Err(e) = queries::put_many_to_many_record(conn, query).await {
-                error!("Failed to put_many_to_many_record: {e:?}");
+    /// Return the balance of the given asset owned by the given address.
+    ///
+    /// The balance is queried from the Fuel node, and reflects the node's latest
+    /// state rather than the state at the block being processed. It is cached
+    /// until the next block.
+    pub async fn balance(&mut self, owner: Address, asset_id: AssetId) -> u64 {
+        if let Some(balance) = self.balances.get(&(owner, asset_id)) {
+            return *balance;
+        }
+
+        let balance = match self.fuel_client() {
+            Some(client) => client
+                .balance(&owner.to_string(), Some(&asset_id.to_string()))
+                .await
+                .unwrap_or_else(|e| {
+                    error!("Failed to get balance: {e:?}");
+                    0
+                }),
+            None => 0,
+        };
+
+        self.balances.insert((owner, asset_id), balance);
+        balance
+    }
+
+    /// Return the unspent coins of the given asset owned by the given address.
+    ///
+    /// Like `balance`, the coins reflect the Fuel node's latest state, and are
+    /// cached until the next block.
+    pub async fn coins(&mut self, owner: Address, asset_id: AssetId) -> Vec<Coin> {
+        if let Some(coins) = self.coins.get(&(owner, asset_id)) {
+            return coins.clone();
+        }
+
+        let coins = match self.fuel_client() {
+            Some(client) => Self::query_coins(&client, owner, asset_id)
+                .await
+                .unwrap_or_else(|e| {
+                    error!("Failed to get coins: {e:?}");
+                    Vec::new()
+                }),
+            None => Vec::new(),
+        };
+
+        self.coins.insert((owner, asset_id), coins.clone());
+        coins
+    }
+
+    /// Return the client for the indexer's outbound HTTP requests.
+    ///
+    /// The client is shared with the database, including its rate limit, so that
+    /// requests can be made after releasing the database lock.
+    pub fn http_client(&self) -> HttpClient {
+        self.http.clone()
+    }
+
+    fn fuel_client(&self) -> Option<FuelClient> {
+        FuelClient::from_str(&self.fuel_node_addr)
+            .map_err(|e| error!("Failed to create Fuel client: {e:?}"))
+            .ok()
+    }
+
+    async fn query_coins(
+        client: &FuelClient,
+        owner: Address,
+        asset_id: AssetId,
+    ) -> std::io::Result<Vec<Coin>> {
+        let owner = owner.to_string();
+        let asset_id = asset_id.to_string();
+
+        let mut coins = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = client
+                .coins(
+                    &owner,
+                    Some(&asset_id),
+                    PaginationRequest {
+                        cursor,
+                        results: COINS_PAGE_SIZE,
+                        direction: PageDirection::Forward,
+                    },
+                )
+                .await?;
+
+            for coin in page.results {
+                if !matches!(coin.status, CoinStatus::Unspent) {
+                    continue;
+                }
+                coins.push(Coin {
+                    utxo_id: UtxoId::from_str(&coin.utxo_id.to_string())
+                        .map_err(|e| invalid_data(format!("Invalid UTXO ID: {e:?}")))?,
+                    owner: Address::from_str(&coin.owner.to_string())
+                        .map_err(|e| invalid_data(format!("Invalid owner: {e:?}")))?,
+                    amount: coin.amount.0.into(),
+                    asset_id: AssetId::from_str(&coin.asset_id.to_string())
+                        .map_err(|e| invalid_data(format!("Invalid asset ID: {e:?}")))?,
+                    maturity: coin.maturity.0.into(),
+                    block_created: coin.block_created.0.into(),
+                });
+            }
+
+            if !page.has_next_page {
+                break;
             }
+            cursor = page.cursor;
         }
+
+        Ok(coins)
+    }
+}
+
+/// Number of coins requested from the Fuel node per page.
+const COINS_PAGE_SIZE: usize = 100;
+
+fn invalid_data(message: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_lib::graphql::GraphQLSchema;
+
+    #[tokio::test]
+    async fn test_transactions_processed_in_parallel_cannot_read_entities() {
+        let manifest = Manifest::try_from(
+            r#"
+namespace: test
+identifier: parallel
+graphql_schema: schema.graphql
+module:
+  wasm: parallel.wasm
+"#,
+        )
+        .unwrap();
+        let schema = GraphQLSchema::new("type Thing @entity { id: ID! }".to_string());
+        let mut db =
+            Database::in_memory(&manifest, &IndexerConfig::default(), &schema).unwrap();
+        db.start_transaction().await.unwrap();
+        let type_id = *db.tables.keys().next().unwrap();
+
+        db.start_deferred_writes();
+        let read = TRANSACTION_INDEX
+            .scope(0, async {
+                db.find_objects(type_id, &EntityQuery::default())
+                    .await
+                    .map(|_| ())
+            })
+            .await;
+        assert!(matches!(read, Err(IndexerError::ParallelTransactionRead)));
+
+        // The denied read fails the block, even if the handler ignored it.
+        assert!(matches!(
+            db.flush_deferred_writes().await,
+            Err(IndexerError::ParallelTransactionRead)
+        ));
+
+        // Block-level handlers run outside of a transaction's scope, and can read.
+        db.start_deferred_writes();
+        assert!(db
+            .find_objects(type_id, &EntityQuery::default())
+            .await
+            .is_ok());
+        db.flush_deferred_writes().await.unwrap();
     }
 }
@@ -1,6 +1,12 @@
 use crate::{
-    database::Database, ffi, queries::ClientExt, IndexerConfig, IndexerError,
-    IndexerResult,
+    block_source::{self, BlockPage, BlockSource},
+    database::Database,
+    ffi,
+    module_cache::ModuleCache,
+    queries::ClientExt,
+    tunables::LimitingTunables,
+    webhook::WebhookNotifier,
+    FtColumn, IndexerConfig, IndexerError, IndexerResult,
 };
 use async_std::{
     fs::File,
@@ -8,37 +14,59 @@ use async_std::{
     sync::{Arc, Mutex},
 };
 use async_trait::async_trait;
+use cpu_time::ThreadTime;
 use fuel_core_client::client::{
-    schema::block::{Consensus as ClientConsensus, Genesis as ClientGenesis},
+    schema::{
+        block::{Consensus as ClientConsensus, Genesis as ClientGenesis},
+        tx::OpaqueTransaction,
+    },
     types::TransactionStatus as ClientTransactionStatus,
     FuelClient, PageDirection, PaginatedResult, PaginationRequest,
 };
 use fuel_indexer_database::IndexerConnectionPool;
-use fuel_indexer_lib::{defaults::*, manifest::Manifest, utils::serialize};
+use fuel_indexer_lib::{
+    config::{utils::derive_http_url, BenchMode, ConversionErrorPolicy, WebhookEvent},
+    defaults::*,
+    fully_qualified_namespace,
+    manifest::{ContractIds, Manifest},
+    mapping::{DecodedValue, EventContext, MappingModule},
+    telemetry::start_trace,
+    type_id,
+    utils::serialize,
+};
+#[cfg(feature = "metrics")]
+use fuel_indexer_metrics::METRICS;
 use fuel_indexer_types::{
     fuel::{field::*, *},
-    scalar::{Bytes32, HexString},
+    scalar::{Bytes32, HexString, Json},
+    Bech32ContractId,
 };
 use fuel_tx::UniqueIdentifier;
 use fuel_vm::prelude::Deserializable;
 use fuel_vm::state::ProgramState as ClientProgramState;
 use futures::Future;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hasher},
     marker::{Send, Sync},
     path::Path,
     str::FromStr,
     sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
 };
 use thiserror::Error;
 use tokio::{
+    sync::mpsc::{channel, Sender},
     task::{spawn_blocking, JoinHandle},
-    time::{sleep, Duration},
+    time::{sleep, timeout, Duration},
 };
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, field::Empty, info, info_span, warn, Instrument, Span};
 use wasmer::{
-    imports, CompilerConfig, Cranelift, FunctionEnv, Instance, Memory, Module,
-    RuntimeError, Store, TypedFunction,
+    imports, AsStoreMut, BaseTunables, CompilerConfig, Cranelift, Engine, FunctionEnv,
+    Instance, Memory, Module, NativeEngineExt, RuntimeError, Store, Target,
+    TypedFunction,
 };
 use wasmer_middlewares::metering::MeteringPoints;
 
@@ -66,15 +94,58 @@ impl ExecutorSource {
     }
 }
 
+/// Executor state that is periodically saved to the database, so that a restarted
+/// executor can resume from its last checkpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutorSnapshot {
+    /// Height of the last block processed.
+    pub block_height: u64,
+
+    /// Cursor from which the next page of blocks will be fetched.
+    pub cursor: Option<String>,
+
+    /// Schema version of the indexer.
+    pub schema_version: String,
+}
+
+/// Address of the Fuel node that the indexer of the given manifest reads from.
+pub(crate) fn fuel_node_addr(config: &IndexerConfig, manifest: &Manifest) -> String {
+    // Include the scheme, so that nodes served over TLS (e.g., network presets)
+    // are reached over HTTPS.
+    let default_fuel_node_addr =
+        derive_http_url(&config.fuel_node.host, &config.fuel_node.port);
+    if config.indexer_net_config {
+        manifest
+            .fuel_client()
+            .map(|x| x.to_string())
+            .unwrap_or(default_fuel_node_addr)
+    } else {
+        default_fuel_node_addr
+    }
+}
+
 /// Run the executor task until the kill switch is flipped, or until some other
-/// stop criteria is met.
+/// stop criteria is met, using the block source set in the configuration.
+pub fn run_executor<T: 'static + Executor + Send + Sync>(
+    config: &IndexerConfig,
+    manifest: &Manifest,
+    executor: T,
+    kill_switch: Arc<AtomicBool>,
+) -> impl Future<Output = ()> {
+    let source = block_source::from_config(config, manifest);
+    run_executor_with_source(config, manifest, executor, source, kill_switch)
+}
+
+/// Run the executor task until the kill switch is flipped, or until some other
+/// stop criteria is met, reading blocks from the given block source.
 //
 // In general the logic in this function isn't very idiomatic, but that's because
 // types in `fuel_core_client` don't compile to WASM.
-pub fn run_executor<T: 'static + Executor + Send + Sync>(
+pub fn run_executor_with_source<T: 'static + Executor + Send + Sync>(
     config: &IndexerConfig,
     manifest: &Manifest,
     mut executor: T,
+    source: Box<dyn BlockSource>,
     kill_switch: Arc<AtomicBool>,
 ) -> impl Future<Output = ()> {
     // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
@@ -88,19 +159,26 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
     }
     let stop_idle_indexers = config.stop_idle_indexers;
     let indexer_uid = manifest.uid();
+    #[cfg(feature = "metrics")]
+    let (namespace, identifier) = (
+        manifest.namespace().to_string(),
+        manifest.identifier().to_string(),
+    );
 
-    let fuel_node_addr = if config.indexer_net_config {
-        manifest
-            .fuel_client()
-            .map(|x| x.to_string())
-            .unwrap_or(config.fuel_node.to_string())
-    } else {
-        config.fuel_node.to_string()
-    };
+    let fuel_node_addr = fuel_node_addr(config, manifest);
 
     let node_block_page_size = config.node_block_page_size;
+    let checkpoint_interval = config.checkpoint_interval;
+    let max_cpu_share = config.max_cpu_share.filter(|share| *share > 0.0);
 
-    let mut next_cursor = if start_block > 1 {
+    // Blocks generated for a benchmark aren't on the Fuel node, so the indexer's
+    // status isn't saved, and its throughput is logged instead.
+    let synthetic_load = matches!(config.bench, Some(BenchMode::SyntheticLoad));
+
+    // A channel with a capacity of zero is not allowed.
+    let block_prefetch_size = config.block_prefetch_size.max(1);
+
+    let next_cursor = if start_block > 1 {
         let decremented = start_block - 1;
         Some(decremented.to_string())
     } else {
@@ -113,8 +191,45 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
         panic!("Indexer({indexer_uid}) client node connection failed: {e}.")
     });
 
+    let webhooks = WebhookNotifier::new(&indexer_uid, &config.webhooks);
+
+    // Everything the executor logs is within this span, so the manifest's log level
+    // and fields apply to it. The span itself is at `ERROR` so that it is never
+    // filtered out, which would drop its fields.
+    let span = tracing::error_span!(
+        "indexer",
+        uid = %indexer_uid,
+        log_level = manifest.log_level(),
+        fields = %manifest
+            .log_fields()
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .join(" "),
+    );
+
     async move {
-        let mut retry_count = 0;
+        webhooks.notify(WebhookEvent::Started, None, None);
+
+        // Blocks are fetched by a separate task and buffered in a bounded channel, so
+        // that the next pages are retrieved while the executor processes the current
+        // one. Once the buffer is full, the fetcher waits for the executor to catch up.
+        let (block_tx, mut block_rx) = channel(block_prefetch_size);
+
+        let fetcher = tokio::spawn(
+            fetch_blocks(
+                source,
+                node_block_page_size,
+                next_cursor,
+                end_block,
+                indexer_uid.clone(),
+                block_tx,
+                kill_switch.clone(),
+            )
+            .in_current_span(),
+        );
+
+        // Number of times in a row the current page has failed to be processed.
+        let mut consecutive_failures = 0;
 
         // If we're testing or running on CI, we don't want indexers to run forever. But in production
         // let the index operators decide if they want to stop idle indexers. Maybe we can eventually
@@ -126,70 +241,217 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
         };
         let mut num_empty_block_reqs = 0;
 
-        loop {
+        let mut pages_since_checkpoint = 0;
+
+        // Used to report sync progress.
+        let started = Instant::now();
+        let mut blocks_processed = 0;
+        let mut rows_written = 0;
+        let mut current_height = None;
+
+        // CPU time of the handlers that has been added to the indexer's status.
+        let mut cpu_time_saved = Duration::ZERO;
+
+        // Whether the indexer has caught up to the chain tip since it last fell behind.
+        let mut caught_up = false;
+
+        // Time at which the throughput of a benchmark was last logged.
+        let mut last_bench_report = Instant::now();
+
+        'pages: while let Some((page, page_span)) = block_rx.recv().await {
+            #[cfg(feature = "metrics")]
+            METRICS.executor.record_page_received(&indexer_uid);
+
             if kill_switch.load(Ordering::SeqCst) {
                 info!("Kill switch flipped, stopping Indexer({indexer_uid}). <('.')>");
                 break;
             }
 
-            let (block_info, cursor) = match retrieve_blocks_from_node(
-                &client,
-                node_block_page_size,
-                &next_cursor,
-                end_block,
-                &indexer_uid,
-            )
-            .await
-            {
+            let (block_info, cursor) = match page {
                 Ok((block_info, cursor)) => (block_info, cursor),
                 Err(e) => {
                     error!("Fetching blocks failed: {e:?}",);
+                    let failed_height = current_height.map(|height| height + 1);
+                    save_error(&executor, &indexer_uid, &e, failed_height, 1).await;
+                    webhooks.notify(
+                        WebhookEvent::Error,
+                        current_height,
+                        Some(&e.to_string()),
+                    );
                     break;
                 }
             };
 
-            let result = executor.handle_events(block_info).await;
+            let page_start = Instant::now();
+            loop {
+                let result = executor
+                    .handle_events(block_info.clone())
+                    .instrument(info_span!(parent: &page_span, "execute_handlers"))
+                    .await;
+
+                if let Err(e) = result {
+                    consecutive_failures += 1;
+                    let failed_height = block_info.first().map(|block| block.height);
+                    save_error(
+                        &executor,
+                        &indexer_uid,
+                        &e,
+                        failed_height,
+                        consecutive_failures,
+                    )
+                    .await;
+
+                    // Webhooks are only notified of the first failure, and of the one
+                    // at which the indexer is found to be crash looping.
+                    if consecutive_failures == 1
+                        || consecutive_failures == CRASH_LOOP_FAILURES
+                    {
+                        webhooks.notify(
+                            WebhookEvent::Error,
+                            current_height,
+                            Some(&e.to_string()),
+                        );
+                    }
 
-            if let Err(e) = result {
-                // Run time metering is deterministic. There is no point in retrying.
-                if let IndexerError::RunTimeLimitExceededError = e {
-                    error!("Indexer({indexer_uid}) executor run time limit exceeded. Giving up. <('.')>. Consider increasing metering points");
-                    break;
-                }
-                error!("Indexer({indexer_uid}) executor failed {e:?}, retrying.");
-                match e {
-                    IndexerError::SqlxError(sqlx::Error::Database(inner)) => {
+                    // Run time metering is deterministic. There is no point in retrying.
+                    if let IndexerError::RunTimeLimitExceededError = e {
+                        error!("Indexer({indexer_uid}) executor run time limit exceeded. Giving up. <('.')>. Consider increasing metering points");
+                        break 'pages;
+                    }
+                    // The indexer's capabilities only change when it's redeployed.
+                    if let IndexerError::CapabilityNotGranted(..) = e {
+                        error!("Indexer({indexer_uid}) {e} Giving up. <('.')>. Consider granting it in the manifest's `capabilities` section.");
+                        break 'pages;
+                    }
+                    if let IndexerError::ResourceLimitExceeded(_) = e {
+                        error!("Indexer({indexer_uid}) {e} Giving up. <('.')>. Consider raising the limits in the manifest's `resources` section.");
+                        break 'pages;
+                    }
+                    if let IndexerError::SqlxError(sqlx::Error::Database(inner)) = &e {
                         // sqlx v0.7 let's you determine if this was specifically a unique constraint violation
                         // but sqlx v0.6 does not so we use a best guess.
                         //
                         // TODO: https://github.com/FuelLabs/fuel-indexer/issues/1093
                         if inner.constraint().is_some() {
-                            // Just bump the cursor and keep going
+                            // Just move on to the next page and keep going
                             warn!("Constraint violation. Continuing...");
-
-                            next_cursor = cursor;
-                        } else {
-                            error!("Database error: {inner}.");
-                            sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERROR)).await;
-                            retry_count += 1;
+                            break;
                         }
                     }
-                    _ => {
-                        sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERROR)).await;
-                        retry_count += 1;
+
+                    // Retries of a page that keeps failing are backed off exponentially,
+                    // and only the first failure and the crash loop are logged as errors,
+                    // so that a crash looping indexer doesn't flood the logs.
+                    let delay =
+                        retry_delay(consecutive_failures - 1, MAX_RESTART_DELAY_SECS);
+                    if consecutive_failures == 1 {
+                        error!("Indexer({indexer_uid}) executor failed {e:?}, retrying in {delay:?}.");
+                    } else if consecutive_failures == CRASH_LOOP_FAILURES {
+                        error!(
+                            "Indexer({indexer_uid}) is crash looping, having failed {consecutive_failures} times in a row on block {}, retrying in {delay:?}: {e}",
+                            failed_height.unwrap_or_default()
+                        );
+                    } else {
+                        warn!("Indexer({indexer_uid}) failed {consecutive_failures} times in a row, retrying in {delay:?}.");
                     }
-                }
 
-                if retry_count < INDEXER_FAILED_CALLS {
-                    warn!("Indexer({indexer_uid}) retrying handler after {retry_count} failed attempts.");
+                    if sleep_unless_killed(delay, &kill_switch).await {
+                        info!("Kill switch flipped, stopping Indexer({indexer_uid}). <('.')>");
+                        break 'pages;
+                    }
 
-                    // Try to fetch the page again using same cursor.
+                    // Try to process the same page again.
                     continue;
+                }
+
+                let page_rows = executor.database().lock().await.rows_written();
+                rows_written += page_rows;
+
+                #[cfg(feature = "metrics")]
+                METRICS.executor.record_blocks_processed(
+                    &namespace,
+                    &identifier,
+                    block_info.len(),
+                    page_start.elapsed().as_secs_f64(),
+                    page_rows,
+                );
+
+                break;
+            }
+
+            if let Some(block) = block_info.last() {
+                blocks_processed += block_info.len();
+                current_height = Some(block.height);
+
+                debug!(
+                    block_height = block.height,
+                    duration_ms = page_start.elapsed().as_millis() as u64,
+                    "Indexer({indexer_uid}) processed {} block(s).",
+                    block_info.len()
+                );
+            }
+
+            if let Some(current_height) = current_height {
+                let blocks_per_second =
+                    blocks_processed as f64 / started.elapsed().as_secs_f64();
+                let rows_per_minute =
+                    rows_written as f64 * 60.0 / started.elapsed().as_secs_f64();
+
+                if synthetic_load {
+                    if last_bench_report.elapsed()
+                        >= Duration::from_secs(BENCH_REPORT_INTERVAL_SECS)
+                    {
+                        info!(
+                            "Indexer({indexer_uid}) synthetic load: {blocks_processed} blocks and {rows_written} rows in {:.1}s ({blocks_per_second:.1} blocks/s, {rows_per_minute:.0} rows/min).",
+                            started.elapsed().as_secs_f64()
+                        );
+                        last_bench_report = Instant::now();
+                    }
                 } else {
-                    error!(
-                        "Indexer({indexer_uid}) failed after retries, giving up. <('.')>"
-                    );
-                    break;
+                    let cpu_time = executor.cpu_time();
+                    let chain_tip = save_status(
+                        &executor,
+                        &client,
+                        &indexer_uid,
+                        current_height,
+                        blocks_per_second,
+                        rows_per_minute,
+                        cpu_time.saturating_sub(cpu_time_saved),
+                    )
+                    .await;
+
+                    if chain_tip.is_some() {
+                        cpu_time_saved = cpu_time;
+                    }
+
+                    if let Some(chain_tip) = chain_tip {
+                        if current_height >= chain_tip && !caught_up {
+                            info!("Indexer({indexer_uid}) caught up to the chain tip at block {chain_tip}.");
+                            webhooks.notify(WebhookEvent::CaughtUp, Some(current_height), None);
+                            caught_up = true;
+                        } else if chain_tip.saturating_sub(current_height)
+                            > MAX_HEALTHY_BLOCKS_BEHIND
+                        {
+                            caught_up = false;
+                        }
+                    }
+                }
+            }
+
+            if checkpoint_interval > 0 && cursor.is_some() {
+                pages_since_checkpoint += 1;
+
+                if pages_since_checkpoint >= checkpoint_interval {
+                    if let Some(block) = block_info.last() {
+                        save_checkpoint(
+                            &executor,
+                            &indexer_uid,
+                            block.height,
+                            cursor.clone(),
+                        )
+                        .await;
+                    }
+                    pages_since_checkpoint = 0;
                 }
             }
 
@@ -199,14 +461,12 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 info!(
                     "Indexer({indexer_uid}) has no new blocks to process, sleeping. zzZZ"
                 );
-                sleep(Duration::from_secs(DELAY_FOR_EMPTY_PAGE)).await;
 
                 if num_empty_block_reqs == max_empty_block_reqs {
                     error!("No blocks being produced, Indexer({indexer_uid}) giving up. <('.')>");
                     break;
                 }
             } else {
-                next_cursor = cursor;
                 num_empty_block_reqs = 0;
             }
 
@@ -215,11 +475,250 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 break;
             }
 
-            retry_count = 0;
+            // Indexers that use more than their share of CPU time are delayed, so
+            // that they don't starve the other indexers of the service.
+            if let Some(max_cpu_share) = max_cpu_share {
+                let delay = cpu_throttle_delay(
+                    executor.cpu_time(),
+                    started.elapsed(),
+                    max_cpu_share,
+                );
+                if !delay.is_zero() {
+                    debug!("Indexer({indexer_uid}) exceeded its share of CPU time, delaying it by {delay:?}.");
+                    if sleep_unless_killed(delay, &kill_switch).await {
+                        info!("Kill switch flipped, stopping Indexer({indexer_uid}). <('.')>");
+                        break;
+                    }
+                }
+            }
+
+            consecutive_failures = 0;
+        }
+
+        if synthetic_load {
+            info!(
+                "Indexer({indexer_uid}) synthetic load finished: {blocks_processed} blocks and {rows_written} rows in {:.1}s ({:.1} blocks/s).",
+                started.elapsed().as_secs_f64(),
+                blocks_processed as f64 / started.elapsed().as_secs_f64()
+            );
+        }
+
+        webhooks.notify(WebhookEvent::Stopped, current_height, None);
+
+        // The fetcher may be sleeping or waiting on the node, so stop it explicitly.
+        fetcher.abort();
+        let _ = fetcher.await;
+
+        // Pages still buffered are dropped along with the channel.
+        #[cfg(feature = "metrics")]
+        METRICS.executor.record_prefetch_buffer_size(&indexer_uid, 0);
+    }
+    .instrument(span)
+}
+
+/// Save a checkpoint of the executor's progress.
+///
+/// A failed checkpoint only means that recovery will start from an older block,
+/// so errors are logged rather than stopping the executor.
+async fn save_checkpoint<T: Executor>(
+    executor: &T,
+    indexer_uid: &str,
+    block_height: u64,
+    cursor: Option<String>,
+) {
+    let db = executor.database();
+    let mut db = db.lock().await;
+
+    let snapshot = ExecutorSnapshot {
+        block_height,
+        cursor,
+        schema_version: db.version().to_string(),
+    };
+
+    match db.put_checkpoint(block_height, serialize(&snapshot)).await {
+        Ok(()) => debug!("Indexer({indexer_uid}) checkpointed at block {block_height}."),
+        Err(e) => warn!("Indexer({indexer_uid}) failed to save checkpoint: {e:?}."),
+    }
+}
+
+/// Save the sync progress of the executor, for reporting through the web API,
+/// returning the chain tip if it could be fetched.
+///
+/// Like checkpoints, errors are logged rather than stopping the executor.
+async fn save_status<T: Executor>(
+    executor: &T,
+    client: &FuelClient,
+    indexer_uid: &str,
+    current_height: u64,
+    blocks_per_second: f64,
+    rows_per_minute: f64,
+    cpu_time: Duration,
+) -> Option<u64> {
+    let chain_tip = match client.chain_info().await {
+        Ok(chain_info) => chain_info.latest_block.header.height.0,
+        Err(e) => {
+            warn!("Indexer({indexer_uid}) failed to fetch chain tip: {e}.");
+            return None;
+        }
+    };
+
+    let db = executor.database();
+    let mut db = db.lock().await;
+
+    if let Err(e) = db
+        .put_status(
+            current_height,
+            chain_tip,
+            blocks_per_second,
+            rows_per_minute,
+            cpu_time.as_secs_f64(),
+        )
+        .await
+    {
+        warn!("Indexer({indexer_uid}) failed to save status: {e:?}.");
+    }
+
+    Some(chain_tip)
+}
+
+/// Save the last error raised by the executor, so that it's reported by the
+/// web API's health check.
+///
+/// Like statuses, errors are logged rather than stopping the executor.
+async fn save_error<T: Executor>(
+    executor: &T,
+    indexer_uid: &str,
+    error: &IndexerError,
+    failed_height: Option<u64>,
+    consecutive_failures: u32,
+) {
+    let db = executor.database();
+    let mut db = db.lock().await;
+
+    if let Err(e) = db
+        .put_error(&error.to_string(), failed_height, consecutive_failures)
+        .await
+    {
+        warn!("Indexer({indexer_uid}) failed to save error: {e:?}.");
+    }
+}
+
+/// Fetch pages of blocks from a block source and send them to the executor, until
+/// the end block is met, the executor stops receiving, or the kill switch is flipped.
+async fn fetch_blocks(
+    mut source: Box<dyn BlockSource>,
+    block_page_size: usize,
+    mut next_cursor: Option<String>,
+    end_block: Option<u64>,
+    indexer_uid: String,
+    block_tx: Sender<(IndexerResult<BlockPage>, Span)>,
+    kill_switch: Arc<AtomicBool>,
+) {
+    let mut failed_attempts = 0;
+
+    loop {
+        if kill_switch.load(Ordering::SeqCst) {
+            break;
+        }
+
+        // Each page of blocks is traced from when it is fetched until it is processed.
+        let page_span = info_span!("blocks", start_height = Empty, end_height = Empty);
+        start_trace(&page_span);
+
+        let page = source
+            .next_page(&next_cursor, block_page_size, end_block)
+            .instrument(info_span!(parent: &page_span, "fetch_blocks"))
+            .await;
+
+        if let Ok((blocks, _)) = &page {
+            if let (Some(first), Some(last)) = (blocks.first(), blocks.last()) {
+                page_span.record("start_height", first.height);
+                page_span.record("end_height", last.height);
+            }
+        }
+
+        let (done, empty) = match &page {
+            Ok((_, cursor)) => {
+                if cursor.is_some() {
+                    next_cursor = cursor.clone();
+                }
+                failed_attempts = 0;
+                (false, cursor.is_none())
+            }
+            Err(IndexerError::EndBlockMet) => (true, false),
+            Err(e) => {
+                let delay = retry_delay(failed_attempts, MAX_FETCH_RETRY_DELAY_SECS);
+                failed_attempts += 1;
+                warn!("Indexer({indexer_uid}) failed to fetch blocks, retrying in {delay:?}: {e}");
+                sleep(delay).await;
+                continue;
+            }
+        };
+
+        if block_tx.send((page, page_span)).await.is_err() {
+            // The executor has stopped.
+            break;
+        }
+
+        #[cfg(feature = "metrics")]
+        METRICS.executor.record_page_prefetched(&indexer_uid);
+
+        if done {
+            break;
+        }
+
+        if empty {
+            sleep(Duration::from_secs(DELAY_FOR_EMPTY_PAGE)).await;
         }
     }
 }
 
+/// Retrieve the consensus parameters used to calculate transaction fees from a client node.
+pub async fn retrieve_fee_parameters(
+    client: &FuelClient,
+) -> IndexerResult<FeeParameters> {
+    let params = client.chain_info().await?.consensus_parameters;
+    Ok(FeeParameters {
+        gas_price_factor: params.gas_price_factor.0,
+        gas_per_byte: params.gas_per_byte.0,
+    })
+}
+
+/// Delay before retrying after the given number of failed attempts.
+///
+/// The delay grows exponentially up to `max_delay_secs`, with random jitter so
+/// that indexers reading from the same node don't retry in lockstep.
+fn retry_delay(failed_attempts: u32, max_delay_secs: u64) -> Duration {
+    let delay = Duration::from_secs(DELAY_FOR_SERVICE_ERROR)
+        .saturating_mul(2u32.saturating_pow(failed_attempts))
+        .min(Duration::from_secs(max_delay_secs));
+
+    // `RandomState` is randomly seeded, which is enough randomness for jitter.
+    let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+    delay.mul_f64(0.5 + random / 2.0)
+}
+
+/// Delay after which an indexer's handlers will have used no more than `max_share` of
+/// a CPU core on average, given the CPU time they have used in the elapsed time.
+fn cpu_throttle_delay(cpu_time: Duration, elapsed: Duration, max_share: f64) -> Duration {
+    cpu_time.div_f64(max_share).saturating_sub(elapsed)
+}
+
+/// Sleep for the given delay, waking up early if the kill switch is flipped.
+///
+/// Returns whether the kill switch was flipped.
+async fn sleep_unless_killed(delay: Duration, kill_switch: &AtomicBool) -> bool {
+    let deadline = Instant::now() + delay;
+    while !kill_switch.load(Ordering::SeqCst) {
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        sleep((deadline - now).min(Duration::from_secs(1))).await;
+    }
+    true
+}
+
 /// Retrieve blocks from a client node.
 ///
 // This was abstracted out of `run_executor` in order to allow for
@@ -229,9 +728,11 @@ pub async fn retrieve_blocks_from_node(
     block_page_size: usize,
     next_cursor: &Option<String>,
     end_block: Option<u64>,
+    fee_params: &FeeParameters,
+    on_conversion_error: ConversionErrorPolicy,
     indexer_uid: &str,
 ) -> IndexerResult<(Vec<BlockData>, Option<String>)> {
-    debug!("Fetching paginated results from {next_cursor:?}");
+    debug!("Indexer({indexer_uid}) fetching paginated results from {next_cursor:?}");
 
     let PaginatedResult {
         cursor, results, ..
@@ -241,16 +742,7 @@ pub async fn retrieve_blocks_from_node(
             results: block_page_size,
             direction: PageDirection::Forward,
         })
-        .await
-        .unwrap_or_else(|e| {
-            error!("Indexer({indexer_uid}) Failed to retrieve blocks: {e}");
-            PaginatedResult {
-                cursor: None,
-                results: vec![],
-                has_next_page: false,
-                has_previous_page: false,
-            }
-        });
+        .await?;
 
     let mut block_info = Vec::new();
     for block in results.into_iter() {
@@ -266,128 +758,22 @@ pub async fn retrieve_blocks_from_node(
 
         let mut transactions = Vec::new();
 
-        for trans in block.transactions {
-            let receipts = trans
-                .receipts
-                .unwrap_or_default()
-                .into_iter()
-                .map(TryInto::try_into)
-                .try_collect()
-                .expect("Bad receipts.");
-
-            let status = trans.status.expect("Bad transaction status.");
-            // NOTE: https://github.com/FuelLabs/fuel-indexer/issues/286
-            let status = match status.try_into().unwrap() {
-                ClientTransactionStatus::Success {
-                    block_id,
-                    time,
-                    program_state,
-                } => {
-                    let program_state = program_state.map(|p| match p {
-                        ClientProgramState::Return(w) => ProgramState {
-                            return_type: ReturnType::Return,
-                            data: HexString::from(w.to_le_bytes().to_vec()),
-                        },
-                        ClientProgramState::ReturnData(d) => ProgramState {
-                            return_type: ReturnType::ReturnData,
-                            data: HexString::from(d.to_vec()),
-                        },
-                        ClientProgramState::Revert(w) => ProgramState {
-                            return_type: ReturnType::Revert,
-                            data: HexString::from(w.to_le_bytes().to_vec()),
-                        },
-                        // Either `cargo watch` complains that this is unreachable, or `clippy` complains
-                        // that all patterns are not matched. These other program states are only used in
-                        // debug modes.
-                        #[allow(unreachable_patterns)]
-                        _ => unreachable!("Bad program state."),
-                    });
-                    TransactionStatus::Success {
-                        block: block_id.parse().expect("Bad block height."),
-                        time: time.to_unix() as u64,
-                        program_state,
-                    }
-                }
-                ClientTransactionStatus::Failure {
-                    block_id,
-                    time,
-                    reason,
-                    program_state,
-                } => {
-                    let program_state = program_state.map(|p| match p {
-                        ClientProgramState::Return(w) => ProgramState {
-                            return_type: ReturnType::Return,
-                            data: HexString::from(w.to_le_bytes().to_vec()),
-                        },
-                        ClientProgramState::ReturnData(d) => ProgramState {
-                            return_type: ReturnType::ReturnData,
-                            data: HexString::from(d.to_vec()),
-                        },
-                        ClientProgramState::Revert(w) => ProgramState {
-                            return_type: ReturnType::Revert,
-                            data: HexString::from(w.to_le_bytes().to_vec()),
-                        },
-                        // Either `cargo watch` complains that this is unreachable, or `clippy` complains
-                        // that all patterns are not matched. These other program states are only used in
-                        // debug modes.
-                        #[allow(unreachable_patterns)]
-                        _ => unreachable!("Bad program state."),
-                    });
-                    TransactionStatus::Failure {
-                        block: block_id.parse().expect("Bad block ID."),
-                        time: time.to_unix() as u64,
-                        program_state,
-                        reason,
-                    }
-                }
-                ClientTransactionStatus::Submitted { submitted_at } => {
-                    TransactionStatus::Submitted {
-                        submitted_at: submitted_at.to_unix() as u64,
+        for (index, trans) in block.transactions.into_iter().enumerate() {
+            match transaction_data(trans, fee_params) {
+                Ok(tx_data) => transactions.push(tx_data),
+                Err(e) => {
+                    let height = block.header.height.0;
+                    match on_conversion_error {
+                        ConversionErrorPolicy::Fail => {
+                            error!("Indexer({indexer_uid}) failed to convert transaction {index} of block {height}: {e}.");
+                            return Err(e.into());
+                        }
+                        ConversionErrorPolicy::Skip => {
+                            warn!("Indexer({indexer_uid}) skipping transaction {index} of block {height}: {e}.");
+                        }
                     }
                 }
-                ClientTransactionStatus::SqueezedOut { reason } => {
-                    TransactionStatus::SqueezedOut { reason }
-                }
-            };
-
-            let transaction =
-                fuel_tx::Transaction::from_bytes(trans.raw_payload.0 .0.as_slice())
-                    .expect("Bad transaction.");
-
-            let id = transaction.id();
-
-            let transaction = match transaction {
-                ClientTransaction::Create(tx) => Transaction::Create(Create {
-                    gas_price: *tx.gas_price(),
-                    gas_limit: *tx.gas_limit(),
-                    maturity: *tx.maturity(),
-                    bytecode_length: *tx.bytecode_length(),
-                    bytecode_witness_index: *tx.bytecode_witness_index(),
-                    storage_slots: tx
-                        .storage_slots()
-                        .iter()
-                        .map(|x| StorageSlot {
-                            key: <[u8; 32]>::from(*x.key()).into(),
-                            value: <[u8; 32]>::from(*x.value()).into(),
-                        })
-                        .collect(),
-                    inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
-                    outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
-                    witnesses: tx.witnesses().to_vec(),
-                    salt: <[u8; 32]>::from(*tx.salt()).into(),
-                    metadata: None,
-                }),
-                _ => Transaction::default(),
-            };
-
-            let tx_data = TransactionData {
-                receipts,
-                status,
-                transaction,
-                id,
-            };
-
-            transactions.push(tx_data);
+            }
         }
 
         // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
@@ -453,12 +839,168 @@ pub async fn retrieve_blocks_from_node(
     Ok((block_info, cursor))
 }
 
+/// Convert a transaction fetched from a Fuel node.
+fn transaction_data(
+    trans: OpaqueTransaction,
+    fee_params: &FeeParameters,
+) -> Result<TransactionData, TypeConversionError> {
+    let receipts: Vec<Receipt> = trans
+        .receipts
+        .unwrap_or_default()
+        .into_iter()
+        .map(TryInto::try_into)
+        .try_collect()
+        .map_err(|e| TypeConversionError::new("Receipt", format!("{e:?}")))?;
+
+    let status = trans.status.ok_or_else(|| {
+        TypeConversionError::new("TransactionStatus", "missing from the response")
+    })?;
+    // NOTE: https://github.com/FuelLabs/fuel-indexer/issues/286
+    let status = match ClientTransactionStatus::try_from(status)
+        .map_err(|e| TypeConversionError::new("TransactionStatus", format!("{e:?}")))?
+    {
+        ClientTransactionStatus::Success {
+            block_id,
+            time,
+            program_state,
+        } => TransactionStatus::Success {
+            block: parse_block_id(&block_id)?,
+            time: time.to_unix() as u64,
+            program_state: program_state.map(convert_program_state).transpose()?,
+        },
+        ClientTransactionStatus::Failure {
+            block_id,
+            time,
+            reason,
+            program_state,
+        } => TransactionStatus::Failure {
+            block: parse_block_id(&block_id)?,
+            time: time.to_unix() as u64,
+            program_state: program_state.map(convert_program_state).transpose()?,
+            reason,
+        },
+        ClientTransactionStatus::Submitted { submitted_at } => {
+            TransactionStatus::Submitted {
+                submitted_at: submitted_at.to_unix() as u64,
+            }
+        }
+        ClientTransactionStatus::SqueezedOut { reason } => {
+            TransactionStatus::SqueezedOut { reason }
+        }
+    };
+
+    let tx_bytes = trans.raw_payload.0 .0.len() as u64;
+    let transaction = fuel_tx::Transaction::from_bytes(trans.raw_payload.0 .0.as_slice())
+        .map_err(|e| TypeConversionError::new("Transaction", e))?;
+
+    let id = transaction.id();
+
+    let transaction = match transaction {
+        ClientTransaction::Create(tx) => Transaction::Create(Create {
+            gas_price: *tx.gas_price(),
+            gas_limit: *tx.gas_limit(),
+            maturity: *tx.maturity(),
+            bytecode_length: *tx.bytecode_length(),
+            bytecode_witness_index: *tx.bytecode_witness_index(),
+            storage_slots: tx
+                .storage_slots()
+                .iter()
+                .map(|x| StorageSlot {
+                    key: <[u8; 32]>::from(*x.key()).into(),
+                    value: <[u8; 32]>::from(*x.value()).into(),
+                })
+                .collect(),
+            inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
+            outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
+            witnesses: tx.witnesses().to_vec(),
+            salt: <[u8; 32]>::from(*tx.salt()).into(),
+            metadata: None,
+        }),
+        ClientTransaction::Script(tx) => Transaction::Script(Script {
+            gas_price: *tx.gas_price(),
+            gas_limit: *tx.gas_limit(),
+            maturity: *tx.maturity(),
+            script: tx.script().clone(),
+            script_data: tx.script_data().clone(),
+            inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
+            outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
+            witnesses: tx.witnesses().to_vec(),
+            receipts_root: <[u8; 32]>::from(*tx.receipts_root()).into(),
+            metadata: None,
+        }),
+        _ => Transaction::default(),
+    };
+
+    let gas_used = receipts
+        .iter()
+        .find_map(Receipt::gas_used)
+        .unwrap_or_default();
+    let gas_price = match &transaction {
+        Transaction::Script(Script { gas_price, .. })
+        | Transaction::Create(Create { gas_price, .. }) => *gas_price,
+        Transaction::Mint(_) => 0,
+    };
+
+    Ok(TransactionData {
+        receipts,
+        status,
+        transaction,
+        id,
+        gas_used,
+        fee: fee_params.fee(tx_bytes, gas_used, gas_price),
+    })
+}
+
+/// Convert the state of a program run by a transaction fetched from a Fuel node.
+fn convert_program_state(
+    state: ClientProgramState,
+) -> Result<ProgramState, TypeConversionError> {
+    match state {
+        ClientProgramState::Return(w) => Ok(ProgramState {
+            return_type: ReturnType::Return,
+            data: HexString::from(w.to_le_bytes().to_vec()),
+        }),
+        ClientProgramState::ReturnData(d) => Ok(ProgramState {
+            return_type: ReturnType::ReturnData,
+            data: HexString::from(d.to_vec()),
+        }),
+        ClientProgramState::Revert(w) => Ok(ProgramState {
+            return_type: ReturnType::Revert,
+            data: HexString::from(w.to_le_bytes().to_vec()),
+        }),
+        // Either `cargo watch` complains that this is unreachable, or `clippy` complains
+        // that all patterns are not matched. These other program states are only used in
+        // debug modes.
+        #[allow(unreachable_patterns)]
+        state => Err(TypeConversionError::new(
+            "ProgramState",
+            format!("unsupported program state {state:?}"),
+        )),
+    }
+}
+
+/// Parse the ID of the block that a transaction fetched from a Fuel node belongs to.
+fn parse_block_id(block_id: &str) -> Result<Bytes32, TypeConversionError> {
+    block_id
+        .parse()
+        .map_err(|e| TypeConversionError::new("Bytes32", format!("{e:?}")))
+}
+
 #[async_trait]
 pub trait Executor
 where
     Self: Sized,
 {
     async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()>;
+
+    /// Return the database used by this executor.
+    fn database(&self) -> Arc<Mutex<Database>>;
+
+    /// Return the CPU time spent running the indexer's handlers since this executor
+    /// was created, for executors that measure it.
+    fn cpu_time(&self) -> Duration {
+        Duration::ZERO
+    }
 }
 
 #[derive(Error, Debug)]
@@ -473,6 +1015,9 @@ pub struct IndexEnv {
     pub alloc: Option<TypedFunction<u32, u32>>,
     pub dealloc: Option<TypedFunction<(u32, u32), ()>>,
     pub db: Arc<Mutex<Database>>,
+
+    /// Page of blocks being handled by the module, which pulls them one at a time.
+    pub blocks: Vec<BlockData>,
 }
 
 impl IndexEnv {
@@ -481,13 +1026,19 @@ impl IndexEnv {
         manifest: &Manifest,
         config: &IndexerConfig,
     ) -> IndexerResult<IndexEnv> {
-        let db = Database::new(pool, manifest, config).await;
-        Ok(IndexEnv {
+        let db = Database::new(pool, manifest, config).await?;
+        Ok(IndexEnv::from_database(db))
+    }
+
+    /// Create an `IndexEnv` using the given database.
+    pub fn from_database(db: Database) -> IndexEnv {
+        IndexEnv {
             memory: None,
             alloc: None,
             dealloc: None,
             db: Arc::new(Mutex::new(db)),
-        })
+            blocks: Vec::new(),
+        }
     }
 }
 
@@ -522,7 +1073,7 @@ where
         config: &IndexerConfig,
         handle_events_fn: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
     ) -> IndexerResult<Self> {
-        let mut db = Database::new(pool.clone(), manifest, config).await;
+        let mut db = Database::new(pool.clone(), manifest, config).await?;
         let mut conn = pool.acquire().await?;
         let version = fuel_indexer_database::queries::type_id_latest(
             &mut conn,
@@ -531,11 +1082,21 @@ where
         )
         .await?;
         db.load_schema(version).await?;
-        Ok(Self {
+        Ok(Self::with_database(manifest, db, handle_events_fn))
+    }
+
+    /// Create a new `NativeIndexExecutor` using the given database, whose schema
+    /// is already loaded.
+    pub fn with_database(
+        manifest: &Manifest,
+        db: Database,
+        handle_events_fn: fn(Vec<BlockData>, Arc<Mutex<Database>>) -> F,
+    ) -> Self {
+        Self {
             db: Arc::new(Mutex::new(db)),
             manifest: manifest.to_owned(),
             handle_events_fn,
-        })
+        }
     }
 
     /// Create a new `NativeIndexExecutor`.
@@ -559,24 +1120,140 @@ where
     }
 }
 
+/// Record how long an indexer's handlers took to handle a batch of blocks, and
+/// how large its module's memory grew.
+#[allow(unused_variables)]
+fn record_handler_metrics(
+    manifest: &Manifest,
+    elapsed: Duration,
+    memory_bytes: Option<u64>,
+) {
+    #[cfg(feature = "metrics")]
+    {
+        METRICS.executor.record_handler_duration(
+            manifest.namespace(),
+            manifest.identifier(),
+            elapsed.as_secs_f64(),
+        );
+        if let Some(memory_bytes) = memory_bytes {
+            METRICS.executor.record_wasm_memory(
+                manifest.namespace(),
+                manifest.identifier(),
+                memory_bytes,
+            );
+        }
+    }
+}
+
+/// Check the manifest's resource limits for a batch of blocks that has been handled.
+///
+/// Limits are given per block, so the budget for the batch scales with its size.
+fn check_resource_limits(
+    manifest: &Manifest,
+    num_blocks: usize,
+    elapsed: Duration,
+    memory_bytes: Option<u64>,
+    row_limit_exceeded: bool,
+) -> IndexerResult<()> {
+    let resources = manifest.resources();
+    let num_blocks = num_blocks as u64;
+
+    if let Some(max_block_time_ms) = resources.max_block_time_ms {
+        if elapsed.as_millis() > (max_block_time_ms * num_blocks) as u128 {
+            return Err(IndexerError::ResourceLimitExceeded(format!(
+                "handlers took {}ms for {num_blocks} block(s), limit is {max_block_time_ms}ms per block",
+                elapsed.as_millis()
+            )));
+        }
+    }
+
+    if let (Some(max_memory_mb), Some(memory_bytes)) =
+        (resources.max_memory_mb, memory_bytes)
+    {
+        if memory_bytes > max_memory_mb * 1024 * 1024 {
+            return Err(IndexerError::ResourceLimitExceeded(format!(
+                "module memory grew to {}MB, limit is {max_memory_mb}MB",
+                memory_bytes / (1024 * 1024)
+            )));
+        }
+    }
+
+    if row_limit_exceeded {
+        return Err(IndexerError::ResourceLimitExceeded(format!(
+            "handlers wrote more than {} database rows per block",
+            resources.max_db_rows_per_block.unwrap_or_default()
+        )));
+    }
+
+    Ok(())
+}
+
 #[async_trait]
 impl<F> Executor for NativeIndexExecutor<F>
 where
     F: Future<Output = IndexerResult<()>> + Send,
 {
     async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
-        self.db.lock().await.start_transaction().await?;
-        let res = (self.handle_events_fn)(blocks, self.db.clone()).await;
+        let num_blocks = blocks.len();
+        let resources = self.manifest.resources().clone();
+
+        {
+            let mut db = self.db.lock().await;
+            db.set_row_limit(
+                resources
+                    .max_db_rows_per_block
+                    .map(|rows| rows * num_blocks as u64),
+            );
+            db.start_transaction().await?;
+        }
+
+        let start = Instant::now();
+        let fut = (self.handle_events_fn)(blocks, self.db.clone());
+        let res = match resources.max_block_time_ms {
+            // Native handlers can be cancelled, so the time limit is enforced
+            // while they run rather than after they return.
+            Some(max_block_time_ms) => {
+                let limit = Duration::from_millis(max_block_time_ms * num_blocks as u64);
+                match timeout(limit, fut).await {
+                    Ok(res) => res,
+                    Err(_) => {
+                        self.db.lock().await.revert_transaction().await?;
+                        return Err(IndexerError::ResourceLimitExceeded(format!(
+                            "handlers took longer than {max_block_time_ms}ms per block"
+                        )));
+                    }
+                }
+            }
+            None => fut.await,
+        };
         let uid = self.manifest.uid();
         if let Err(e) = res {
             error!("NativeIndexExecutor({uid}) handle_events failed: {e}.");
             self.db.lock().await.revert_transaction().await?;
             return Err(IndexerError::NativeExecutionRuntimeError);
-        } else {
-            self.db.lock().await.commit_transaction().await?;
         }
+
+        let elapsed = start.elapsed();
+        record_handler_metrics(&self.manifest, elapsed, None);
+
+        let row_limit_exceeded = self.db.lock().await.row_limit_exceeded();
+        if let Err(e) = check_resource_limits(
+            &self.manifest,
+            num_blocks,
+            elapsed,
+            None,
+            row_limit_exceeded,
+        ) {
+            self.db.lock().await.revert_transaction().await?;
+            return Err(e);
+        }
+
+        self.db.lock().await.commit_transaction().await?;
         Ok(())
     }
+    fn database(&self) -> Arc<Mutex<Database>> {
+        self.db.clone()
+    }
 }
 
 /// Responsible for loading a single indexer module, triggering events.
@@ -585,9 +1262,20 @@ pub struct WasmIndexExecutor {
     instance: Instance,
     _module: Module,
     store: Arc<Mutex<Store>>,
+    env: FunctionEnv<IndexEnv>,
     db: Arc<Mutex<Database>>,
     metering_points: Option<u64>,
     manifest: Manifest,
+
+    /// Whether the module is metered, either to limit its run time or to stop it
+    /// once it has spent its `max_block_time_ms`.
+    metered: bool,
+
+    /// Version of the FFI the module was built with.
+    ffi_version: u32,
+
+    /// CPU time spent running the module's handlers.
+    cpu_time: Duration,
 }
 
 impl WasmIndexExecutor {
@@ -598,9 +1286,29 @@ impl WasmIndexExecutor {
         wasm_bytes: impl AsRef<[u8]>,
         pool: IndexerConnectionPool,
     ) -> IndexerResult<Self> {
-        let mut compiler_config = Cranelift::new();
+        let db = Database::new(pool, manifest, config).await?;
+        Self::with_database(config, manifest, wasm_bytes, db).await
+    }
 
-        if let Some(metering_points) = config.metering_points {
+    /// Create a new `WasmIndexExecutor` using the given database.
+    pub async fn with_database(
+        config: &IndexerConfig,
+        manifest: &Manifest,
+        wasm_bytes: impl AsRef<[u8]>,
+        db: Database,
+    ) -> IndexerResult<Self> {
+        let mut compiler_config = Cranelift::new();
+        let resources = manifest.resources();
+
+        // Modules with a time limit are metered even if metering isn't configured, so
+        // that they're stopped once they've spent their time rather than after they
+        // return. Their points are set for each call by `handle_events`.
+        let metering_points = config.metering_points.or_else(|| {
+            resources
+                .max_block_time_ms
+                .map(|ms| ms.saturating_mul(METERING_POINTS_PER_MS))
+        });
+        if let Some(metering_points) = metering_points {
             // `Metering` needs to be configured with a limit and a cost
             // function. For each `Operator`, the metering middleware will call
             // the cost function and subtract the cost from the remaining points.
@@ -609,24 +1317,55 @@ impl WasmIndexExecutor {
             compiler_config.push_middleware(metering);
         }
 
-        let idx_env = IndexEnv::new(pool, manifest, config).await?;
+        let idx_env = IndexEnv::from_database(db);
         let db: Arc<Mutex<Database>> = idx_env.db.clone();
 
-        let mut store = Store::new(compiler_config);
+        let mut engine: Engine = compiler_config.into();
+        if let Some(max_memory_mb) = resources.max_memory_mb {
+            engine.set_tunables(LimitingTunables::new(
+                BaseTunables::for_target(&Target::default()),
+                max_memory_mb,
+            ));
+        }
+        let mut store = Store::new(engine);
 
-        let module = Module::new(&store, &wasm_bytes)?;
+        let module = match ModuleCache::from_config(config) {
+            Some(cache) => cache.load_or_compile(&store, &wasm_bytes, metering_points)?,
+            None => Module::new(&store, &wasm_bytes)?,
+        };
 
         let env = FunctionEnv::new(&mut store, idx_env);
+        let exports = ffi::get_exports(&mut store, &env, manifest.capabilities());
+
+        // Give a clear error for host functions that exist but were not granted,
+        // rather than a generic link error.
+        for import in module.imports().functions() {
+            if import.module() == "env" && !exports.contains(import.name()) {
+                if let Some(capability) = ffi::required_capability(import.name()) {
+                    return Err(IndexerError::CapabilityNotGranted(
+                        import.name().to_string(),
+                        capability.to_string(),
+                    ));
+                }
+            }
+        }
+
         let mut imports = imports! {};
-        for (export_name, export) in ffi::get_exports(&mut store, &env) {
-            imports.define("env", &export_name, export.clone());
+        for (export_name, export) in exports.iter() {
+            imports.define("env", export_name, export.clone());
         }
 
         let instance = Instance::new(&mut store, &module, &imports)?;
 
+        let ffi_version = {
+            let mut store_mut = store.as_store_mut();
+            ffi::get_ffi_version(&mut store_mut, &instance)?
+        };
+        ffi::check_ffi_version(ffi_version)?;
+
         if !instance
             .exports
-            .contains(ffi::MODULE_ENTRYPOINT.to_string())
+            .contains(ffi::module_entrypoint(ffi_version).to_string())
         {
             return Err(IndexerError::MissingHandler);
         }
@@ -658,9 +1397,13 @@ impl WasmIndexExecutor {
             instance,
             _module: module,
             store: Arc::new(Mutex::new(store)),
+            env,
             db: db.clone(),
             metering_points: config.metering_points,
             manifest: manifest.clone(),
+            metered: metering_points.is_some(),
+            ffi_version,
+            cpu_time: Duration::ZERO,
         })
     }
 
@@ -711,6 +1454,7 @@ impl WasmIndexExecutor {
                 crate::Module::Native => {
                     Err(IndexerError::NativeExecutionInstantiationError)
                 }
+                crate::Module::Declarative(_) => Err(IndexerError::ExecutorInitError),
             },
             ExecutorSource::Registry(bytes) => {
                 let executor =
@@ -729,7 +1473,7 @@ impl WasmIndexExecutor {
 
     /// Returns true if metering is enabled.
     pub fn metering_enabled(&self) -> bool {
-        self.metering_points.is_some()
+        self.metered
     }
 
     /// Returns true if metering is enabled metering points are exhausted.
@@ -784,61 +1528,896 @@ impl Executor for WasmIndexExecutor {
             return Ok(());
         }
 
-        if let Some(metering_points) = self.metering_points {
-            self.set_metering_points(metering_points).await?
-        }
-        let bytes = serialize(&blocks);
         let uid = self.manifest.uid();
+        let num_blocks = blocks.len();
 
-        let mut arg = {
-            let mut store_guard = self.store.lock().await;
-            ffi::WasmArg::new(
-                &mut store_guard,
-                &self.instance,
-                bytes,
-                self.metering_points.is_some(),
-            )?
+        // Handlers are given the points of their time limit, if they're fewer than
+        // the configured points, so that they're stopped once it's spent.
+        let time_points = self.manifest.resources().max_block_time_ms.map(|ms| {
+            ms.saturating_mul(num_blocks as u64)
+                .saturating_mul(METERING_POINTS_PER_MS)
+        });
+        let metering_points = match (self.metering_points, time_points) {
+            (Some(points), Some(time_points)) => Some(points.min(time_points)),
+            (points, time_points) => points.or(time_points),
         };
+        if let Some(metering_points) = metering_points {
+            self.set_metering_points(metering_points).await?
+        }
 
         let fun = {
             let store_guard = self.store.lock().await;
-            self.instance.exports.get_typed_function::<(u32, u32), ()>(
-                &store_guard,
-                ffi::MODULE_ENTRYPOINT,
-            )?
+            ffi::Entrypoint::new(&store_guard, &self.instance, self.ffi_version)?
         };
 
-        let _ = self.db.lock().await.start_transaction().await?;
+        // Modules that pull blocks are only passed the number of blocks in the page,
+        // and each block is serialized into the module's memory when it's pulled.
+        // Older modules are passed the whole page at once.
+        let mut arg = {
+            let mut store_guard = self.store.lock().await;
+            match fun {
+                ffi::Entrypoint::PullBlocks(_) => {
+                    self.env.as_mut(&mut *store_guard).blocks = blocks;
+                    None
+                }
+                ffi::Entrypoint::Page(_) => Some(ffi::WasmArg::new(
+                    &mut store_guard,
+                    &self.instance,
+                    serialize(&blocks),
+                    self.metering_enabled(),
+                )?),
+            }
+        };
+        {
+            let mut db = self.db.lock().await;
+            db.set_row_limit(
+                self.manifest
+                    .resources()
+                    .max_db_rows_per_block
+                    .map(|rows| rows * num_blocks as u64),
+            );
+            let _ = db.start_transaction().await?;
+        }
 
-        let ptr = arg.get_ptr();
-        let len = arg.get_len();
+        let (ptr, len) = arg
+            .as_ref()
+            .map_or((0, 0), |arg| (arg.get_ptr(), arg.get_len()));
 
-        let res = spawn_blocking({
+        let start = Instant::now();
+        let (res, cpu_time) = spawn_blocking({
             let store = self.store.clone();
+            // Keep the indexer's span, so that the module's log messages are filtered
+            // by its log level.
+            let span = tracing::Span::current();
             move || {
+                let _entered = span.enter();
                 let mut store_guard =
                     tokio::runtime::Handle::current().block_on(store.lock());
-                fun.call(&mut store_guard, ptr, len)
+                // The module runs on this thread alone, so the thread's CPU time is
+                // the module's. Time spent waiting on the database isn't counted.
+                let cpu_start = ThreadTime::now();
+                let res = match &fun {
+                    ffi::Entrypoint::Page(fun) => fun.call(&mut store_guard, ptr, len),
+                    ffi::Entrypoint::PullBlocks(fun) => {
+                        fun.call(&mut store_guard, num_blocks as u32)
+                    }
+                };
+                (res, cpu_start.elapsed())
             }
         })
         .await?;
 
+        // Blocks that were pulled by the module are no longer needed.
+        self.env
+            .as_mut(&mut *self.store.lock().await)
+            .blocks
+            .clear();
+
+        self.cpu_time += cpu_time;
+        #[cfg(feature = "metrics")]
+        METRICS.executor.record_cpu_time(
+            self.manifest.namespace(),
+            self.manifest.identifier(),
+            cpu_time.as_secs_f64(),
+        );
+
         if let Err(e) = res {
             if self.metering_points_exhausted().await {
                 self.db.lock().await.revert_transaction().await?;
-                return Err(IndexerError::RunTimeLimitExceededError);
+                return match (time_points, self.manifest.resources().max_block_time_ms) {
+                    (Some(time_points), Some(max_block_time_ms))
+                        if metering_points == Some(time_points) =>
+                    {
+                        Err(IndexerError::ResourceLimitExceeded(format!(
+                            "handlers ran out of time for {num_blocks} block(s), limit is {max_block_time_ms}ms per block"
+                        )))
+                    }
+                    _ => Err(IndexerError::RunTimeLimitExceededError),
+                };
+            } else if let Some(ffi::FFIError::CapabilityNotGranted(name, capability)) =
+                e.downcast_ref::<ffi::FFIError>()
+            {
+                self.db.lock().await.revert_transaction().await?;
+                return Err(IndexerError::CapabilityNotGranted(
+                    name.clone(),
+                    capability.clone(),
+                ));
             } else {
                 error!("WasmIndexExecutor({uid}) WASM execution failed: {e}.");
                 self.db.lock().await.revert_transaction().await?;
                 return Err(IndexerError::from(e));
             }
-        } else {
-            let _ = self.db.lock().await.commit_transaction().await?;
         }
 
-        let mut store_guard = self.store.lock().await;
-        arg.drop(&mut store_guard);
+        // Memory is capped and time is metered while handlers run, but time spent in
+        // host functions, such as waiting on the database, is only counted here.
+        let elapsed = start.elapsed();
+        let memory_bytes = {
+            let store_guard = self.store.lock().await;
+            self.instance
+                .exports
+                .get_memory("memory")?
+                .view(&store_guard)
+                .data_size()
+        };
+        record_handler_metrics(&self.manifest, elapsed, Some(memory_bytes));
+
+        let row_limit_exceeded = self.db.lock().await.row_limit_exceeded();
+
+        if let Err(e) = check_resource_limits(
+            &self.manifest,
+            num_blocks,
+            elapsed,
+            Some(memory_bytes),
+            row_limit_exceeded,
+        ) {
+            self.db.lock().await.revert_transaction().await?;
+            return Err(e);
+        }
+
+        let _ = self.db.lock().await.commit_transaction().await?;
+
+        if let Some(arg) = arg.as_mut() {
+            let mut store_guard = self.store.lock().await;
+            arg.drop(&mut store_guard);
+        }
+
+        Ok(())
+    }
+
+    fn database(&self) -> Arc<Mutex<Database>> {
+        self.db.clone()
+    }
+
+    fn cpu_time(&self) -> Duration {
+        self.cpu_time
+    }
+}
+
+/// Executes a declarative indexer, saving entities according to the manifest's
+/// event-to-entity mappings rather than running user code.
+pub struct DeclarativeIndexExecutor {
+    db: Arc<Mutex<Database>>,
+    manifest: Manifest,
+    module: MappingModule,
+
+    /// Contracts the indexer is subscribed to, or `None` for all contracts.
+    contract_ids: Option<Vec<Bech32ContractId>>,
+}
+
+impl DeclarativeIndexExecutor {
+    /// Create a new `DeclarativeIndexExecutor` from a compiled mapping module.
+    pub async fn new(
+        config: &IndexerConfig,
+        manifest: &Manifest,
+        module_bytes: impl AsRef<[u8]>,
+        pool: IndexerConnectionPool,
+    ) -> IndexerResult<Self> {
+        let mut db = Database::new(pool.clone(), manifest, config).await?;
+        let mut conn = pool.acquire().await?;
+        let version = fuel_indexer_database::queries::type_id_latest(
+            &mut conn,
+            manifest.namespace(),
+            manifest.identifier(),
+        )
+        .await?;
+        db.load_schema(version).await?;
+
+        Self::with_database(manifest, module_bytes, db)
+    }
+
+    /// Create a new `DeclarativeIndexExecutor` from a compiled mapping module, using
+    /// the given database, whose schema is already loaded.
+    pub fn with_database(
+        manifest: &Manifest,
+        module_bytes: impl AsRef<[u8]>,
+        db: Database,
+    ) -> IndexerResult<Self> {
+        let module = MappingModule::from_bytes(module_bytes.as_ref())?;
+
+        let contract_ids = match manifest.contract_id() {
+            ContractIds::Single(None) => None,
+            ContractIds::Single(Some(id)) => Some(vec![parse_contract_id(id)?]),
+            ContractIds::Multiple(ids) => Some(
+                ids.iter()
+                    .map(|id| parse_contract_id(id))
+                    .collect::<IndexerResult<Vec<_>>>()?,
+            ),
+        };
+
+        Ok(Self {
+            db: Arc::new(Mutex::new(db)),
+            manifest: manifest.to_owned(),
+            module,
+            contract_ids,
+        })
+    }
+
+    /// Create a new `DeclarativeIndexExecutor`.
+    pub async fn create(
+        config: &IndexerConfig,
+        manifest: &Manifest,
+        exec_source: ExecutorSource,
+        pool: IndexerConnectionPool,
+    ) -> IndexerResult<(JoinHandle<()>, ExecutorSource, Arc<AtomicBool>)> {
+        let killer = Arc::new(AtomicBool::new(false));
+
+        let bytes = match exec_source {
+            ExecutorSource::Manifest => manifest.module_bytes()?,
+            ExecutorSource::Registry(bytes) => bytes,
+        };
+
+        let executor =
+            DeclarativeIndexExecutor::new(config, manifest, &bytes, pool).await?;
+        let handle =
+            tokio::spawn(run_executor(config, manifest, executor, killer.clone()));
+
+        Ok((handle, ExecutorSource::Registry(bytes), killer))
+    }
+
+    fn is_subscribed(&self, contract_id: &ContractId) -> bool {
+        match &self.contract_ids {
+            Some(ids) => ids.contains(&Bech32ContractId::new("fuel", **contract_id)),
+            None => true,
+        }
+    }
+
+    /// Save the entities mapped from the events of a single block.
+    async fn handle_block(&self, block: &BlockData) -> IndexerResult<()> {
+        for tx in block.transactions.iter() {
+            for (receipt_index, receipt) in tx.receipts.iter().enumerate() {
+                let (rb, data, id) = match receipt {
+                    Receipt::LogData { rb, data, id, .. } => (rb, data, id),
+                    _ => continue,
+                };
+
+                if !self.is_subscribed(id) {
+                    continue;
+                }
+
+                let ctx = EventContext {
+                    block_height: block.height,
+                    block_id: *block.id,
+                    block_time: block.time,
+                    tx_id: *tx.id,
+                    contract_id: **id,
+                    receipt_index,
+                };
+
+                for mapping in self.module.mappings_for(*rb) {
+                    let event = mapping.event_type.decode(data)?;
+                    let values = mapping.field_values(&event, &ctx);
+                    self.save_entity(&mapping.entity, &values).await?;
+                }
+            }
+        }
+
+        let metadata = HashMap::from([
+            ("id".to_string(), DecodedValue::U64(block.height)),
+            ("time".to_string(), DecodedValue::U64(block.time as u64)),
+            ("block_height".to_string(), DecodedValue::U64(block.height)),
+            ("block_id".to_string(), DecodedValue::B256(*block.id)),
+        ]);
+        self.save_entity("IndexMetadataEntity", &metadata).await?;
+
+        let mut block_metadata = HashMap::from([
+            ("id".to_string(), DecodedValue::U64(block.height)),
+            ("block_height".to_string(), DecodedValue::U64(block.height)),
+            ("block_id".to_string(), DecodedValue::B256(*block.id)),
+            ("time".to_string(), DecodedValue::U64(block.time as u64)),
+            (
+                "da_height".to_string(),
+                DecodedValue::U64(block.header.da_height),
+            ),
+            (
+                "transactions_count".to_string(),
+                DecodedValue::U64(block.header.transactions_count),
+            ),
+            (
+                "output_messages_count".to_string(),
+                DecodedValue::U64(block.header.output_messages_count),
+            ),
+            (
+                "application_hash".to_string(),
+                DecodedValue::B256(*block.header.application_hash),
+            ),
+        ]);
+        if let Some(producer) = block.producer {
+            block_metadata.insert("producer".to_string(), DecodedValue::B256(*producer));
+        }
+        self.save_entity("BlockMetadataEntity", &block_metadata)
+            .await
+    }
+
+    /// Save an entity, converting the mapped values to the types of its columns.
+    async fn save_entity(
+        &self,
+        entity: &str,
+        values: &HashMap<String, DecodedValue>,
+    ) -> IndexerResult<()> {
+        let type_id = type_id(
+            &fully_qualified_namespace(
+                self.manifest.namespace(),
+                self.manifest.identifier(),
+            ),
+            entity,
+        );
+
+        let mut db = self.db.lock().await;
+        let columns = db
+            .columns(type_id)
+            .ok_or_else(|| {
+                IndexerError::Unknown(format!("Entity '{entity}' is not in the schema"))
+            })?
+            .into_iter()
+            // The serialized entity is passed to `put_object` separately.
+            .filter(|(_, column_type)| *column_type != "Object")
+            .map(|(name, column_type)| {
+                mapped_column(column_type, values.get(name))
+                    .map_err(|e| IndexerError::Unknown(format!("{entity}.{name}: {e}")))
+            })
+            .collect::<IndexerResult<Vec<FtColumn>>>()?;
+
+        let bytes = bincode::serialize(&columns).map_err(|e| {
+            IndexerError::Unknown(format!("Failed to serialize {entity}: {e}"))
+        })?;
+        db.put_object(type_id, columns, bytes).await
+    }
+}
+
+fn parse_contract_id(id: &str) -> IndexerResult<Bech32ContractId> {
+    Bech32ContractId::from_str(id).map_err(|e| {
+        IndexerError::Unknown(format!("Invalid manifest contract ID '{id}': {e}"))
+    })
+}
+
+/// Convert a mapped value to a column of the given type.
+///
+/// Values that are not mapped are saved as `NULL`, except for the entity's `ID`.
+fn mapped_column(
+    column_type: &str,
+    value: Option<&DecodedValue>,
+) -> Result<FtColumn, String> {
+    let value = value.map(|v| v.unwrap_newtype());
+    let unsupported = || format!("cannot save {value:?} as a {column_type} column");
+    let int = || {
+        value
+            .map(|v| match v {
+                DecodedValue::U64(n) => Ok(*n),
+                DecodedValue::Bool(b) => Ok(*b as u64),
+                _ => Err(unsupported()),
+            })
+            .transpose()
+    };
+    // 128-bit values are decoded as `U128` structs, or read from decimal strings.
+    let u128_value = |v: &DecodedValue| match v {
+        DecodedValue::U64(n) => Some(*n as u128),
+        DecodedValue::Bool(b) => Some(*b as u128),
+        DecodedValue::Str(s) => s.parse().ok(),
+        DecodedValue::Struct(fields) => match fields.as_slice() {
+            [(upper, DecodedValue::U64(high)), (lower, DecodedValue::U64(low))]
+                if upper == "upper" && lower == "lower" =>
+            {
+                Some((*high as u128) << 64 | *low as u128)
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    let wide_uint = || {
+        value
+            .map(|v| u128_value(v).ok_or_else(unsupported))
+            .transpose()
+    };
+    let wide_int = || {
+        value
+            .map(|v| {
+                let n = match v {
+                    DecodedValue::Str(s) => s.parse().ok(),
+                    _ => u128_value(v).and_then(|n| i128::try_from(n).ok()),
+                };
+                n.ok_or_else(unsupported)
+            })
+            .transpose()
+    };
+    let b256 = || {
+        value
+            .map(|v| match v {
+                DecodedValue::B256(bytes) => Ok(*bytes),
+                _ => Err(unsupported()),
+            })
+            .transpose()
+    };
+
+    let column = match column_type {
+        "ID" => FtColumn::ID(Some(int()?.ok_or_else(unsupported)?)),
+        "UInt8" | "ForeignKey" => FtColumn::UInt8(int()?),
+        // Values that don't fit a narrower column are rejected rather than wrapped.
+        "UInt4" => FtColumn::UInt4(
+            int()?
+                .map(u32::try_from)
+                .transpose()
+                .map_err(|_| unsupported())?,
+        ),
+        "UInt1" => FtColumn::UInt1(
+            int()?
+                .map(u8::try_from)
+                .transpose()
+                .map_err(|_| unsupported())?,
+        ),
+        "UInt16" => FtColumn::UInt16(wide_uint()?),
+        "Int8" => FtColumn::Int8(
+            int()?
+                .map(i64::try_from)
+                .transpose()
+                .map_err(|_| unsupported())?,
+        ),
+        "Int4" => FtColumn::Int4(
+            int()?
+                .map(i32::try_from)
+                .transpose()
+                .map_err(|_| unsupported())?,
+        ),
+        "Int1" => FtColumn::Int1(
+            int()?
+                .map(i8::try_from)
+                .transpose()
+                .map_err(|_| unsupported())?,
+        ),
+        "Int16" => FtColumn::Int16(wide_int()?),
+        "U128" => FtColumn::U128(wide_uint()?),
+        "I128" => FtColumn::I128(wide_int()?),
+        "Timestamp" => FtColumn::Timestamp(
+            int()?
+                .map(i64::try_from)
+                .transpose()
+                .map_err(|_| unsupported())?,
+        ),
+        "BlockHeight" => FtColumn::BlockHeight(
+            int()?
+                .map(u32::try_from)
+                .transpose()
+                .map_err(|_| unsupported())?
+                .map(BlockHeight::from),
+        ),
+        "Boolean" => FtColumn::Boolean(int()?.map(|n| n != 0)),
+        "Address" => FtColumn::Address(b256()?.map(Address::from)),
+        "AssetId" => FtColumn::AssetId(b256()?.map(AssetId::from)),
+        "ContractId" => FtColumn::ContractId(b256()?.map(ContractId::from)),
+        "Bytes32" => FtColumn::Bytes32(b256()?.map(Bytes32::from)),
+        "BlockId" => FtColumn::BlockId(b256()?.map(Bytes32::from)),
+        "TxId" => FtColumn::TxId(b256()?.map(Bytes32::from)),
+        "MessageId" => FtColumn::MessageId(b256()?.map(MessageId::from)),
+        "Nonce" => FtColumn::Nonce(b256()?.map(Nonce::from)),
+        "Salt" => FtColumn::Salt(b256()?.map(Salt::from)),
+        "Charfield" => FtColumn::Charfield(value.map(|v| v.to_plain_string())),
+        "Enum" => FtColumn::Enum(value.map(|v| v.to_plain_string())),
+        "Json" => FtColumn::Json(value.map(|v| Json(v.to_json().to_string()))),
+        _ => return Err(unsupported()),
+    };
+
+    Ok(column)
+}
+
+#[async_trait]
+impl Executor for DeclarativeIndexExecutor {
+    /// Save the entities mapped from the events in a batch of blocks.
+    async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let num_blocks = blocks.len();
+        {
+            let mut db = self.db.lock().await;
+            db.set_row_limit(
+                self.manifest
+                    .resources()
+                    .max_db_rows_per_block
+                    .map(|rows| rows * num_blocks as u64),
+            );
+            db.start_transaction().await?;
+        }
+
+        let start = Instant::now();
+        for block in blocks.iter() {
+            if let Err(e) = self.handle_block(block).await {
+                error!(
+                    "DeclarativeIndexExecutor({}) failed to handle block {}: {e}.",
+                    self.manifest.uid(),
+                    block.height
+                );
+                self.db.lock().await.revert_transaction().await?;
+                return Err(e);
+            }
+        }
+
+        let elapsed = start.elapsed();
+        record_handler_metrics(&self.manifest, elapsed, None);
+
+        let row_limit_exceeded = self.db.lock().await.row_limit_exceeded();
+        if let Err(e) = check_resource_limits(
+            &self.manifest,
+            num_blocks,
+            elapsed,
+            None,
+            row_limit_exceeded,
+        ) {
+            self.db.lock().await.revert_transaction().await?;
+            return Err(e);
+        }
 
+        self.db.lock().await.commit_transaction().await?;
         Ok(())
     }
+
+    fn database(&self) -> Arc<Mutex<Database>> {
+        self.db.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_source::SyntheticBlockSource;
+    use fuel_indexer_lib::graphql::GraphQLSchema;
+    use fuel_indexer_types::ffi::{FFI_VERSION, LOG_LEVEL_INFO};
+
+    fn synthetic_source() -> Box<dyn BlockSource> {
+        let manifest = Manifest::try_from(
+            r#"
+namespace: bench
+identifier: synthetic
+graphql_schema: schema.graphql
+module:
+  wasm: bench.wasm
+"#,
+        )
+        .unwrap();
+        Box::new(SyntheticBlockSource::new(&manifest))
+    }
+
+    #[tokio::test]
+    async fn test_fetch_blocks_sends_pages_in_order() {
+        let (block_tx, mut block_rx) = channel(1);
+        let fetcher = tokio::spawn(fetch_blocks(
+            synthetic_source(),
+            2,
+            None,
+            Some(7),
+            "bench.synthetic".to_string(),
+            block_tx,
+            Arc::new(AtomicBool::new(false)),
+        ));
+
+        let mut heights = Vec::new();
+        let mut end_block_met = false;
+        while let Some((page, _)) = block_rx.recv().await {
+            match page {
+                Ok((blocks, _)) => heights.extend(blocks.iter().map(|b| b.height)),
+                Err(IndexerError::EndBlockMet) => end_block_met = true,
+                Err(e) => panic!("Unexpected error fetching blocks: {e}"),
+            }
+        }
+
+        assert_eq!(heights, (1..=7).collect::<Vec<_>>());
+        assert!(end_block_met);
+        fetcher.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fetch_blocks_stops_on_shutdown() {
+        let kill_switch = Arc::new(AtomicBool::new(false));
+        let (block_tx, mut block_rx) = channel(1);
+        let fetcher = tokio::spawn(fetch_blocks(
+            synthetic_source(),
+            2,
+            None,
+            None,
+            "bench.synthetic".to_string(),
+            block_tx,
+            kill_switch.clone(),
+        ));
+
+        assert!(block_rx.recv().await.is_some());
+        kill_switch.store(true, Ordering::SeqCst);
+
+        // The fetcher stops after sending at most the pages it had already fetched,
+        // closing the channel.
+        let drained = timeout(Duration::from_secs(5), async {
+            let mut pages = 0;
+            while block_rx.recv().await.is_some() {
+                pages += 1;
+            }
+            pages
+        })
+        .await
+        .expect("Fetcher did not stop on shutdown.");
+        assert!(drained <= 2);
+        timeout(Duration::from_secs(5), fetcher)
+            .await
+            .expect("Fetcher did not stop on shutdown.")
+            .unwrap();
+
+        // A fetcher also stops once the executor stops receiving.
+        let (block_tx, block_rx) = channel(1);
+        let fetcher = tokio::spawn(fetch_blocks(
+            synthetic_source(),
+            2,
+            None,
+            None,
+            "bench.synthetic".to_string(),
+            block_tx,
+            Arc::new(AtomicBool::new(false)),
+        ));
+        drop(block_rx);
+        timeout(Duration::from_secs(5), fetcher)
+            .await
+            .expect("Fetcher did not stop once the receiver was dropped.")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_mapped_column_keeps_128_bit_values() {
+        let u128_struct = |upper: u64, lower: u64| {
+            DecodedValue::Struct(vec![
+                ("upper".to_string(), DecodedValue::U64(upper)),
+                ("lower".to_string(), DecodedValue::U64(lower)),
+            ])
+        };
+        let above_u64 = u64::MAX as u128 + 5;
+
+        assert_eq!(
+            mapped_column("U128", Some(&u128_struct(1, 4))).unwrap(),
+            FtColumn::U128(Some(above_u64))
+        );
+        assert_eq!(
+            mapped_column("UInt16", Some(&u128_struct(u64::MAX, u64::MAX))).unwrap(),
+            FtColumn::UInt16(Some(u128::MAX))
+        );
+        assert_eq!(
+            mapped_column("U128", Some(&DecodedValue::Str(above_u64.to_string())))
+                .unwrap(),
+            FtColumn::U128(Some(above_u64))
+        );
+        assert_eq!(
+            mapped_column("U128", Some(&DecodedValue::U64(7))).unwrap(),
+            FtColumn::U128(Some(7))
+        );
+
+        assert_eq!(
+            mapped_column("I128", Some(&u128_struct(1, 4))).unwrap(),
+            FtColumn::I128(Some(above_u64 as i128))
+        );
+        assert_eq!(
+            mapped_column("Int16", Some(&DecodedValue::Str(format!("-{above_u64}"))))
+                .unwrap(),
+            FtColumn::Int16(Some(-(above_u64 as i128)))
+        );
+
+        // Values that don't fit are rejected rather than wrapped.
+        assert!(mapped_column("I128", Some(&u128_struct(u64::MAX, 0))).is_err());
+        assert!(
+            mapped_column("U128", Some(&DecodedValue::Str("-1".to_string()))).is_err()
+        );
+        assert_eq!(mapped_column("U128", None).unwrap(), FtColumn::U128(None));
+    }
+
+    #[test]
+    fn test_mapped_column_rejects_values_that_do_not_fit() {
+        let n = |n: u64| DecodedValue::U64(n);
+
+        assert_eq!(
+            mapped_column("UInt4", Some(&n(u32::MAX as u64))).unwrap(),
+            FtColumn::UInt4(Some(u32::MAX))
+        );
+        assert_eq!(
+            mapped_column("Int1", Some(&n(127))).unwrap(),
+            FtColumn::Int1(Some(127))
+        );
+        assert_eq!(
+            mapped_column("BlockHeight", Some(&n(7))).unwrap(),
+            FtColumn::BlockHeight(Some(BlockHeight::from(7u32)))
+        );
+
+        for (column_type, value) in [
+            ("UInt4", u32::MAX as u64 + 1),
+            ("UInt1", 256),
+            ("Int8", i64::MAX as u64 + 1),
+            ("Int4", i32::MAX as u64 + 1),
+            ("Int1", 128),
+            ("Timestamp", u64::MAX),
+            ("BlockHeight", u32::MAX as u64 + 1),
+        ] {
+            assert!(
+                mapped_column(column_type, Some(&n(value))).is_err(),
+                "{value} is saved as a {column_type} column"
+            );
+        }
+    }
+
+    /// Create an executor for a module that logs and saves an empty list of
+    /// many-to-many records for each page, with the given capabilities.
+    async fn capability_executor(capabilities: &str) -> IndexerResult<WasmIndexExecutor> {
+        let manifest = Manifest::try_from(
+            format!(
+                r#"
+namespace: test
+identifier: capabilities
+graphql_schema: schema.graphql
+module:
+  wasm: capabilities.wasm
+capabilities:
+{capabilities}
+"#
+            )
+            .as_str(),
+        )
+        .unwrap();
+        let config = IndexerConfig::default();
+        let schema = GraphQLSchema::new("type Thing @entity { id: ID! }".to_string());
+        let db = Database::in_memory(&manifest, &config, &schema)?;
+
+        // The data segment holds the version, and an empty list serialized with
+        // bincode as a little-endian length.
+        let wat = format!(
+            r#"
+(module
+  (import "env" "ff_log_data" (func $log_data (param i32 i32 i32)))
+  (import "env" "ff_log_record" (func $log_record (param i32 i32)))
+  (import "env" "ff_put_many_to_many_record" (func $put_many_to_many (param i32 i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "v1")
+  (data (i32.const 8) "\00\00\00\00\00\00\00\00")
+  (func (export "get_version_ptr") (result i32) i32.const 0)
+  (func (export "get_version_len") (result i32) i32.const 2)
+  (func (export "get_ffi_version") (result i32) i32.const {FFI_VERSION})
+  (func (export "alloc_fn") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc_fn") (param i32 i32))
+  (func (export "handle_blocks") (param i32)
+    (call $log_data (i32.const 0) (i32.const 2) (i32.const {LOG_LEVEL_INFO}))
+    (call $put_many_to_many (i32.const 8) (i32.const 8))))
+"#
+        );
+
+        WasmIndexExecutor::with_database(&config, &manifest, wat, db).await
+    }
+
+    /// Create an executor for a module with the given resource limits, whose handler
+    /// runs the given instructions.
+    async fn resource_executor(
+        resources: &str,
+        handler: &str,
+    ) -> IndexerResult<WasmIndexExecutor> {
+        let manifest = Manifest::try_from(
+            format!(
+                r#"
+namespace: test
+identifier: resources
+graphql_schema: schema.graphql
+module:
+  wasm: resources.wasm
+resources:
+{resources}
+"#
+            )
+            .as_str(),
+        )
+        .unwrap();
+        let config = IndexerConfig::default();
+        let schema = GraphQLSchema::new("type Thing @entity { id: ID! }".to_string());
+        let db = Database::in_memory(&manifest, &config, &schema)?;
+
+        let wat = format!(
+            r#"
+(module
+  (memory (export "memory") 1)
+  (data (i32.const 0) "v1")
+  (func (export "get_version_ptr") (result i32) i32.const 0)
+  (func (export "get_version_len") (result i32) i32.const 2)
+  (func (export "get_ffi_version") (result i32) i32.const {FFI_VERSION})
+  (func (export "alloc_fn") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc_fn") (param i32 i32))
+  (func (export "handle_blocks") (param i32)
+    {handler}))
+"#
+        );
+
+        WasmIndexExecutor::with_database(&config, &manifest, wat, db).await
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_resource_limits_stop_modules_while_they_run() {
+        let block = SyntheticBlockSource::new(
+            &Manifest::try_from(
+                "namespace: test\nidentifier: resources\ngraphql_schema: schema.graphql\nmodule:\n  wasm: resources.wasm\n",
+            )
+            .unwrap(),
+        )
+        .block(1);
+
+        // A handler that never returns is stopped once it has spent its time.
+        let mut executor =
+            resource_executor("  max_block_time_ms: 1", "(loop $spin (br $spin))")
+                .await
+                .unwrap();
+        assert!(matches!(
+            executor.handle_events(vec![block.clone()]).await,
+            Err(IndexerError::ResourceLimitExceeded(_))
+        ));
+
+        // Memory can't grow past its cap, of 16 pages per megabyte.
+        let mut executor = resource_executor(
+            "  max_memory_mb: 1",
+            "(if (i32.eq (memory.grow (i32.const 100)) (i32.const -1)) (then unreachable))",
+        )
+        .await
+        .unwrap();
+        assert!(executor.handle_events(vec![block.clone()]).await.is_err());
+
+        let mut executor = resource_executor(
+            "  max_memory_mb: 1",
+            "(if (i32.eq (memory.grow (i32.const 15)) (i32.const -1)) (then unreachable))",
+        )
+        .await
+        .unwrap();
+        executor.handle_events(vec![block]).await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_modules_instantiate_without_optional_capabilities() {
+        let block = SyntheticBlockSource::new(
+            &Manifest::try_from(
+                "namespace: test\nidentifier: capabilities\ngraphql_schema: schema.graphql\nmodule:\n  wasm: capabilities.wasm\n",
+            )
+            .unwrap(),
+        )
+        .block(1);
+
+        // Log messages are discarded without `allow_logging`.
+        let mut executor =
+            capability_executor("  allow_logging: false\n  allow_raw_sql: true")
+                .await
+                .unwrap();
+        executor.handle_events(vec![block.clone()]).await.unwrap();
+
+        // Saving many-to-many records stops the indexer without `allow_raw_sql`.
+        let mut executor =
+            capability_executor("  allow_logging: true\n  allow_raw_sql: false")
+                .await
+                .unwrap();
+        assert!(matches!(
+            executor.handle_events(vec![block.clone()]).await,
+            Err(IndexerError::CapabilityNotGranted(..))
+        ));
+
+        let mut executor =
+            capability_executor("  allow_logging: false\n  allow_raw_sql: false")
+                .await
+                .unwrap();
+        assert!(matches!(
+            executor.handle_events(vec![block]).await,
+            Err(IndexerError::CapabilityNotGranted(..))
+        ));
+    }
 }
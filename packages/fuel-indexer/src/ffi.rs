@@ -1,21 +1,25 @@
-use fuel_indexer_lib::defaults;
-use fuel_indexer_schema::{join::RawQuery, FtColumn};
+use fuel_indexer_lib::{defaults, manifest::Capabilities};
+use fuel_indexer_schema::{join::RawQuery, query::EntityQuery, FtColumn};
 use fuel_indexer_types::ffi::{
-    LOG_LEVEL_DEBUG, LOG_LEVEL_ERROR, LOG_LEVEL_INFO, LOG_LEVEL_TRACE, LOG_LEVEL_WARN,
+    LogRecord, FFI_VERSION, LOG_LEVEL_DEBUG, LOG_LEVEL_ERROR, LOG_LEVEL_INFO,
+    LOG_LEVEL_TRACE, LOG_LEVEL_WARN, MIN_FFI_VERSION, PULL_BLOCKS_FFI_VERSION,
 };
 use thiserror::Error;
 use tracing::{debug, error, info, trace, warn};
 use wasmer::{
     ExportError, Exports, Function, FunctionEnvMut, Instance, MemoryView, RuntimeError,
-    Store, StoreMut, WasmPtr,
+    Store, StoreMut, TypedFunction, WasmPtr,
 };
 use wasmer_middlewares::metering::{
     get_remaining_points, set_remaining_points, MeteringPoints,
 };
 
-use crate::{IndexEnv, IndexerResult};
+use crate::{IndexEnv, IndexerError, IndexerResult};
 pub const MODULE_ENTRYPOINT: &str = "handle_events";
 
+/// Entrypoint of modules that pull the blocks of a page with `ff_get_block`.
+pub const PULL_BLOCKS_ENTRYPOINT: &str = "handle_blocks";
+
 #[derive(Debug, Error)]
 pub enum FFIError {
     #[error("Invalid memory access")]
@@ -26,6 +30,21 @@ pub enum FFIError {
     Export(#[from] ExportError),
     #[error("Expected result from call {0:?}")]
     None(String),
+    /// Returned by host functions to trap the module when the database fails, e.g.,
+    /// once the writes of the current transaction failed, so that the executor
+    /// reverts the transaction and handles its blocks again.
+    #[error("Database error {0}")]
+    Database(String),
+    /// Returned by host functions that the indexer's capabilities don't allow it to
+    /// call, to stop the indexer rather than silently dropping its writes.
+    #[error("Host function {0} requires the `{1}` capability")]
+    CapabilityNotGranted(String, String),
+}
+
+impl From<IndexerError> for FFIError {
+    fn from(e: IndexerError) -> Self {
+        FFIError::Database(e.to_string())
+    }
 }
 
 pub fn get_version(
@@ -48,6 +67,80 @@ pub fn get_version(
     Ok(version)
 }
 
+/// Return the FFI version the module was built with. Modules built before the FFI
+/// was versioned don't export it, and are reported as version 0.
+pub fn get_ffi_version(
+    store: &mut StoreMut,
+    instance: &Instance,
+) -> Result<u32, FFIError> {
+    let f = match instance.exports.get_function("get_ffi_version") {
+        Ok(f) => f,
+        Err(ExportError::Missing(_)) => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    let version = f.call(store, &[])?[0]
+        .i32()
+        .ok_or_else(|| FFIError::None("get_ffi_version".to_string()))?;
+
+    Ok(version as u32)
+}
+
+/// Check that a module built with the given FFI version can be run by this service.
+pub fn check_ffi_version(version: u32) -> IndexerResult<()> {
+    if version > FFI_VERSION {
+        return Err(IndexerError::IncompatibleFfiVersion(format!(
+            "module uses FFI version {version}, but this service only supports up to version {FFI_VERSION}; upgrade the indexer service"
+        )));
+    }
+
+    if version < MIN_FFI_VERSION {
+        return Err(IndexerError::IncompatibleFfiVersion(format!(
+            "module uses FFI version {version}, but this service requires at least version {MIN_FFI_VERSION}; rebuild the indexer with a newer fuel-indexer-utils"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Return the name of the entrypoint of a module built with the given FFI version.
+pub fn module_entrypoint(ffi_version: u32) -> &'static str {
+    if ffi_version >= PULL_BLOCKS_FFI_VERSION {
+        PULL_BLOCKS_ENTRYPOINT
+    } else {
+        MODULE_ENTRYPOINT
+    }
+}
+
+/// Entrypoint of a module, through which it handles a page of blocks.
+pub(crate) enum Entrypoint {
+    /// Passed the whole page, serialized into the module's memory.
+    Page(TypedFunction<(u32, u32), ()>),
+
+    /// Passed the number of blocks in the page, which it pulls with `ff_get_block`.
+    PullBlocks(TypedFunction<u32, ()>),
+}
+
+impl Entrypoint {
+    /// Return the entrypoint of a module built with the given FFI version.
+    pub fn new(
+        store: &Store,
+        instance: &Instance,
+        ffi_version: u32,
+    ) -> Result<Self, ExportError> {
+        let name = module_entrypoint(ffi_version);
+        if ffi_version >= PULL_BLOCKS_FFI_VERSION {
+            Ok(Self::PullBlocks(
+                instance.exports.get_typed_function(store, name)?,
+            ))
+        } else {
+            Ok(Self::Page(
+                instance.exports.get_typed_function(store, name)?,
+            ))
+        }
+    }
+}
+
 fn get_string(mem: &MemoryView, ptr: u32, len: u32) -> Result<String, FFIError> {
     let result = WasmPtr::<u8>::new(ptr)
         .read_utf8_string(mem, len)
@@ -83,12 +176,67 @@ fn log_data(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32, log_level: u3
     }
 }
 
+fn log_record(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32) {
+    let (idx_env, store) = env.data_and_store_mut();
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized.")
+        .view(&store);
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let LogRecord {
+        level,
+        message,
+        fields,
+        block_height,
+    } = match bincode::deserialize(&bytes) {
+        Ok(record) => record,
+        Err(e) => {
+            error!("Failed to deserialize log record: {e:?}");
+            return;
+        }
+    };
+
+    // The indexer's span already tags the record with the indexer's namespace and identifier.
+    let fields = fields
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    match level {
+        LOG_LEVEL_ERROR => {
+            error!(block_height = ?block_height, fields = %fields, "{message}")
+        }
+        LOG_LEVEL_WARN => {
+            warn!(block_height = ?block_height, fields = %fields, "{message}")
+        }
+        LOG_LEVEL_INFO => {
+            info!(block_height = ?block_height, fields = %fields, "{message}")
+        }
+        LOG_LEVEL_DEBUG => {
+            debug!(block_height = ?block_height, fields = %fields, "{message}")
+        }
+        LOG_LEVEL_TRACE => {
+            trace!(block_height = ?block_height, fields = %fields, "{message}")
+        }
+        l => error!("Invalid log level: {l}"),
+    }
+}
+
 fn get_object(
     mut env: FunctionEnvMut<IndexEnv>,
     type_id: i64,
     ptr: u32,
     len_ptr: u32,
-) -> u32 {
+) -> Result<u32, FFIError> {
     let (idx_env, mut store) = env.data_and_store_mut();
 
     let id = {
@@ -102,7 +250,7 @@ fn get_object(
 
     let rt = tokio::runtime::Handle::current();
     let bytes =
-        rt.block_on(async { idx_env.db.lock().await.get_object(type_id, id).await });
+        rt.block_on(async { idx_env.db.lock().await.get_object(type_id, id).await })?;
 
     if let Some(bytes) = bytes {
         let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
@@ -125,13 +273,84 @@ fn get_object(
             mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
         }
 
-        result
+        Ok(result)
     } else {
-        0
+        Ok(0)
     }
 }
 
-fn put_object(mut env: FunctionEnvMut<IndexEnv>, type_id: i64, ptr: u32, len: u32) {
+fn find_objects(
+    mut env: FunctionEnvMut<IndexEnv>,
+    type_id: i64,
+    ptr: u32,
+    len: u32,
+    len_ptr: u32,
+) -> Result<u32, FFIError> {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    let bytes = {
+        let mem = idx_env
+            .memory
+            .as_mut()
+            .expect("Memory unitialized")
+            .view(&store);
+
+        let mut bytes = Vec::with_capacity(len as usize);
+        let range = ptr as usize..ptr as usize + len as usize;
+
+        unsafe {
+            bytes.extend_from_slice(&mem.data_unchecked()[range]);
+        }
+        bytes
+    };
+
+    let query: EntityQuery = match bincode::deserialize(&bytes) {
+        Ok(query) => query,
+        Err(e) => {
+            error!("Failed to deserialize query for find_objects: {e:?}");
+            return Ok(0);
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    let objects = rt.block_on(async {
+        idx_env.db.lock().await.find_objects(type_id, &query).await
+    })?;
+
+    if objects.is_empty() {
+        return Ok(0);
+    }
+
+    let bytes = bincode::serialize(&objects).expect("Failed to serialize objects.");
+    let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+    let size = bytes.len() as u32;
+    let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+    let range = result as usize..result as usize + size as usize;
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized.")
+        .view(&store);
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(&mem)
+        .write(size)
+        .expect("Failed to write length to memory.");
+
+    unsafe {
+        mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+    }
+
+    Ok(result)
+}
+
+fn put_object(
+    mut env: FunctionEnvMut<IndexEnv>,
+    type_id: i64,
+    ptr: u32,
+    len: u32,
+) -> Result<(), FFIError> {
     let (idx_env, store) = env.data_and_store_mut();
     let mem = idx_env
         .memory
@@ -153,7 +372,7 @@ fn put_object(mut env: FunctionEnvMut<IndexEnv>, type_id: i64, ptr: u32, len: u3
                 "Failed to deserialize Vec<FtColumn> for put_object: {:?}",
                 e
             );
-            return;
+            return Ok(());
         }
     };
 
@@ -165,10 +384,16 @@ fn put_object(mut env: FunctionEnvMut<IndexEnv>, type_id: i64, ptr: u32, len: u3
             .await
             .put_object(type_id, columns, bytes)
             .await
-    });
+    })?;
+
+    Ok(())
 }
 
-fn put_many_to_many_record(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32) {
+fn put_many_to_many_record(
+    mut env: FunctionEnvMut<IndexEnv>,
+    ptr: u32,
+    len: u32,
+) -> Result<(), FFIError> {
     let (idx_env, store) = env.data_and_store_mut();
     let mem = idx_env
         .memory
@@ -194,25 +419,363 @@ fn put_many_to_many_record(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32
             .await
             .put_many_to_many_record(queries)
             .await
-    });
+    })?;
+
+    Ok(())
+}
+
+fn register_contract(
+    mut env: FunctionEnvMut<IndexEnv>,
+    ptr: u32,
+    len: u32,
+) -> Result<(), FFIError> {
+    let (idx_env, store) = env.data_and_store_mut();
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized")
+        .view(&store);
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let (template, contract_id): (String, String) = match bincode::deserialize(&bytes) {
+        Ok(contract) => contract,
+        Err(e) => {
+            error!("Failed to deserialize contract for register_contract: {e:?}");
+            return Ok(());
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async {
+        idx_env
+            .db
+            .lock()
+            .await
+            .register_contract(template, contract_id)
+            .await
+    })?;
+
+    Ok(())
+}
+
+fn get_discovered_contracts(
+    mut env: FunctionEnvMut<IndexEnv>,
+    len_ptr: u32,
+) -> Result<u32, FFIError> {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    let rt = tokio::runtime::Handle::current();
+    let contracts =
+        rt.block_on(async { idx_env.db.lock().await.discovered_contracts().await })?;
+
+    if contracts.is_empty() {
+        return Ok(0);
+    }
+
+    let bytes = bincode::serialize(&contracts)
+        .expect("Failed to serialize discovered contracts.");
+    let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+    let size = bytes.len() as u32;
+    let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+    let range = result as usize..result as usize + size as usize;
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized.")
+        .view(&store);
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(&mem)
+        .write(size)
+        .expect("Failed to write length to memory.");
+
+    unsafe {
+        mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+    }
+
+    Ok(result)
 }
 
-pub fn get_exports(store: &mut Store, env: &wasmer::FunctionEnv<IndexEnv>) -> Exports {
+fn get_balance(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32) -> u64 {
+    let (idx_env, store) = env.data_and_store_mut();
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized")
+        .view(&store);
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let (owner, asset_id) = match bincode::deserialize(&bytes) {
+        Ok(query) => query,
+        Err(e) => {
+            error!("Failed to deserialize query for get_balance: {e:?}");
+            return 0;
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { idx_env.db.lock().await.balance(owner, asset_id).await })
+}
+
+fn get_coins(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32, len_ptr: u32) -> u32 {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    let bytes = {
+        let mem = idx_env
+            .memory
+            .as_mut()
+            .expect("Memory unitialized")
+            .view(&store);
+
+        let mut bytes = Vec::with_capacity(len as usize);
+        let range = ptr as usize..ptr as usize + len as usize;
+
+        unsafe {
+            bytes.extend_from_slice(&mem.data_unchecked()[range]);
+        }
+        bytes
+    };
+
+    let (owner, asset_id) = match bincode::deserialize(&bytes) {
+        Ok(query) => query,
+        Err(e) => {
+            error!("Failed to deserialize query for get_coins: {e:?}");
+            return 0;
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    let coins =
+        rt.block_on(async { idx_env.db.lock().await.coins(owner, asset_id).await });
+
+    if coins.is_empty() {
+        return 0;
+    }
+
+    let bytes = bincode::serialize(&coins).expect("Failed to serialize coins.");
+    let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+    let size = bytes.len() as u32;
+    let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+    let range = result as usize..result as usize + size as usize;
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized.")
+        .view(&store);
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(&mem)
+        .write(size)
+        .expect("Failed to write length to memory.");
+
+    unsafe {
+        mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+    }
+
+    result
+}
+
+fn http_get(mut env: FunctionEnvMut<IndexEnv>, ptr: u32, len: u32, len_ptr: u32) -> u32 {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    let url = {
+        let mem = idx_env
+            .memory
+            .as_mut()
+            .expect("Memory unitialized")
+            .view(&store);
+
+        match get_string(&mem, ptr, len) {
+            Ok(url) => url,
+            Err(e) => {
+                error!("Failed to read URL for http_get: {e:?}");
+                return 0;
+            }
+        }
+    };
+
+    // The database lock is released before the request is made, so that requests
+    // and their rate limit never hold up the indexer's other host functions.
+    let rt = tokio::runtime::Handle::current();
+    let bytes = match rt.block_on(async {
+        let http = idx_env.db.lock().await.http_client();
+        http.get(&url).await
+    }) {
+        Some(bytes) => bytes,
+        None => return 0,
+    };
+
+    let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+
+    let size = bytes.len() as u32;
+    let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+    let range = result as usize..result as usize + size as usize;
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized.")
+        .view(&store);
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(&mem)
+        .write(size)
+        .expect("Failed to write length to memory.");
+
+    unsafe {
+        mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+    }
+
+    result
+}
+
+/// Serialize the block at the given index of the page being handled into the module's
+/// memory, and return its address, or 0 if the page has no such block.
+fn get_block(mut env: FunctionEnvMut<IndexEnv>, index: u32, len_ptr: u32) -> u32 {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    let block = match idx_env.blocks.get(index as usize) {
+        Some(block) => block,
+        None => return 0,
+    };
+
+    let size = match bincode::serialized_size(block) {
+        Ok(size) => size as u32,
+        Err(e) => {
+            error!("Failed to serialize block for get_block: {e:?}");
+            return 0;
+        }
+    };
+    let alloc_fn = idx_env.alloc.as_mut().expect("Alloc export is missing.");
+    let result = alloc_fn.call(&mut store, size).expect("Alloc failed.");
+    let range = result as usize..result as usize + size as usize;
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .expect("Memory unitialized.")
+        .view(&store);
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(&mem)
+        .write(size)
+        .expect("Failed to write length to memory.");
+
+    // The block is serialized straight into the module's memory, rather than into a
+    // buffer that is then copied.
+    let bytes = unsafe { &mut mem.data_unchecked_mut()[range] };
+    bincode::serialize_into(bytes, block).expect("Failed to serialize block.");
+
+    result
+}
+
+/// Stand-in for `log_data` when the indexer may not emit log messages.
+fn ignore_log_data(_env: FunctionEnvMut<IndexEnv>, _ptr: u32, _len: u32, _level: u32) {}
+
+/// Stand-in for `log_record` when the indexer may not emit log messages.
+fn ignore_log_record(_env: FunctionEnvMut<IndexEnv>, _ptr: u32, _len: u32) {}
+
+/// Stand-in for `put_many_to_many_record` when the indexer may not execute raw SQL.
+fn deny_many_to_many_record(
+    _env: FunctionEnvMut<IndexEnv>,
+    _ptr: u32,
+    _len: u32,
+) -> Result<(), FFIError> {
+    Err(FFIError::CapabilityNotGranted(
+        "ff_put_many_to_many_record".to_string(),
+        "allow_raw_sql".to_string(),
+    ))
+}
+
+/// Return the capability required to import the given host function, if any.
+///
+/// Only host functions that are left out of a module's imports without their
+/// capability are listed. Every indexer imports the logging and many-to-many host
+/// functions, so those are always exported, and are ignored or denied when called
+/// without their capability.
+pub fn required_capability(export_name: &str) -> Option<&'static str> {
+    match export_name {
+        "ff_http_get" => Some("allow_http"),
+        _ => None,
+    }
+}
+
+/// Return the host functions that are exposed to an indexer with the given capabilities.
+pub fn get_exports(
+    store: &mut Store,
+    env: &wasmer::FunctionEnv<IndexEnv>,
+    capabilities: &Capabilities,
+) -> Exports {
     let mut exports = Exports::new();
 
     let f_get_obj = Function::new_typed_with_env(store, env, get_object);
     let f_put_obj = Function::new_typed_with_env(store, env, put_object);
-    let f_log_data = Function::new_typed_with_env(store, env, log_data);
-    let f_put_many_to_many_record =
-        Function::new_typed_with_env(store, env, put_many_to_many_record);
+
+    let f_find_objs = Function::new_typed_with_env(store, env, find_objects);
 
     exports.insert("ff_get_object".to_string(), f_get_obj);
     exports.insert("ff_put_object".to_string(), f_put_obj);
+    exports.insert("ff_find_objects".to_string(), f_find_objs);
+
+    let f_register_contract = Function::new_typed_with_env(store, env, register_contract);
+    let f_get_discovered_contracts =
+        Function::new_typed_with_env(store, env, get_discovered_contracts);
+
+    exports.insert("ff_register_contract".to_string(), f_register_contract);
+    exports.insert(
+        "ff_get_discovered_contracts".to_string(),
+        f_get_discovered_contracts,
+    );
+
+    let f_get_balance = Function::new_typed_with_env(store, env, get_balance);
+    let f_get_coins = Function::new_typed_with_env(store, env, get_coins);
+
+    exports.insert("ff_get_balance".to_string(), f_get_balance);
+    exports.insert("ff_get_coins".to_string(), f_get_coins);
+
+    let f_get_block = Function::new_typed_with_env(store, env, get_block);
+    exports.insert("ff_get_block".to_string(), f_get_block);
+
+    let f_put_many_to_many_record = if capabilities.allow_raw_sql {
+        Function::new_typed_with_env(store, env, put_many_to_many_record)
+    } else {
+        Function::new_typed_with_env(store, env, deny_many_to_many_record)
+    };
     exports.insert(
         "ff_put_many_to_many_record".to_string(),
         f_put_many_to_many_record,
     );
+
+    if capabilities.allow_http {
+        let f_http_get = Function::new_typed_with_env(store, env, http_get);
+        exports.insert("ff_http_get".to_string(), f_http_get);
+    }
+
+    let (f_log_data, f_log_record) = if capabilities.allow_logging {
+        (
+            Function::new_typed_with_env(store, env, log_data),
+            Function::new_typed_with_env(store, env, log_record),
+        )
+    } else {
+        (
+            Function::new_typed_with_env(store, env, ignore_log_data),
+            Function::new_typed_with_env(store, env, ignore_log_record),
+        )
+    };
     exports.insert("ff_log_data".to_string(), f_log_data);
+    exports.insert("ff_log_record".to_string(), f_log_record);
 
     exports
 }
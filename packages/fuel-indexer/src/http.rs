@@ -0,0 +1,402 @@
+use fuel_indexer_lib::defaults::{
+    HTTP_MAX_REQUESTS_PER_SECOND, HTTP_MAX_RESPONSE_BYTES, HTTP_REQUEST_TIMEOUT_SECS,
+};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect::Policy,
+    Url,
+};
+use std::{
+    collections::VecDeque,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use tracing::{error, warn};
+
+/// Maximum number of redirects followed by a request.
+const HTTP_MAX_REDIRECTS: usize = 10;
+
+/// Client for the outbound HTTP requests of an indexer.
+///
+/// Requests are rate-limited per indexer. The client is cheap to clone, and clones
+/// share the same rate limit, so that requests can be made without holding the
+/// indexer's database lock.
+///
+/// Requests to loopback, private, link-local and other non-public addresses are
+/// denied, so that indexers can't reach services on the indexer's host or network.
+/// Host names are checked once resolved, as are the targets of redirects.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    /// Whether the indexer may make outbound HTTP requests.
+    allowed: bool,
+
+    /// Whether requests to non-public addresses are allowed.
+    allow_local: bool,
+
+    /// Client used to send requests.
+    client: reqwest::Client,
+
+    /// Times of the requests made within the last second.
+    requests: Arc<Mutex<VecDeque<Instant>>>,
+}
+
+impl HttpClient {
+    /// Create a new `HttpClient`, making requests only if `allowed` is set.
+    pub fn new(allowed: bool) -> Self {
+        Self::with_local_access(allowed, false)
+    }
+
+    fn with_local_access(allowed: bool, allow_local: bool) -> Self {
+        let builder = if allow_local {
+            reqwest::Client::builder()
+        } else {
+            public_client_builder()
+        };
+        let builder = builder.timeout(Duration::from_secs(HTTP_REQUEST_TIMEOUT_SECS));
+
+        Self {
+            allowed,
+            allow_local,
+            client: builder.build().expect("Failed to build HTTP client."),
+            requests: Default::default(),
+        }
+    }
+
+    /// Fetch the body of the given URL with an HTTP GET request.
+    ///
+    /// Requests are only made if the indexer has the `allow_http` capability, and are
+    /// limited to `HTTP_MAX_REQUESTS_PER_SECOND`; further requests wait for the limit.
+    /// Responses that are unsuccessful or larger than `HTTP_MAX_RESPONSE_BYTES` are
+    /// discarded.
+    pub async fn get(&self, url: &str) -> Option<Vec<u8>> {
+        if !self.allowed {
+            warn!("HTTP request to {url} denied: the indexer lacks the `allow_http` capability.");
+            return None;
+        }
+
+        let parsed = match Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => parsed,
+            _ => {
+                error!("HTTP request to {url} denied: only HTTP(S) URLs are supported.");
+                return None;
+            }
+        };
+
+        if !self.allow_local && is_denied_url(&parsed) {
+            warn!("HTTP request to {url} denied: the address is not public.");
+            return None;
+        }
+
+        self.wait_for_rate_limit().await;
+
+        let mut response = match self.client.get(parsed).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                warn!("HTTP request to {url} failed: {}.", response.status());
+                return None;
+            }
+            Err(e) => {
+                warn!("HTTP request to {url} failed: {e}.");
+                return None;
+            }
+        };
+
+        let mut body = Vec::new();
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    if body.len() + chunk.len() > HTTP_MAX_RESPONSE_BYTES {
+                        warn!("HTTP response from {url} exceeds {HTTP_MAX_RESPONSE_BYTES} bytes.");
+                        return None;
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                Ok(None) => return Some(body),
+                Err(e) => {
+                    warn!("HTTP request to {url} failed: {e}.");
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Wait until another request can be made without exceeding the rate limit.
+    async fn wait_for_rate_limit(&self) {
+        let window = Duration::from_secs(1);
+        loop {
+            let wait = {
+                let mut requests = self.requests.lock().await;
+                while requests
+                    .front()
+                    .map_or(false, |sent| sent.elapsed() >= window)
+                {
+                    requests.pop_front();
+                }
+                if requests.len() < HTTP_MAX_REQUESTS_PER_SECOND {
+                    requests.push_back(Instant::now());
+                    return;
+                }
+                window.saturating_sub(requests[0].elapsed())
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Create a builder of clients that only make requests to public addresses, as
+/// `HttpClient` does, for requests to URLs given by manifests.
+///
+/// Host names are checked once resolved, as are the targets of redirects. URLs
+/// whose host is an address aren't resolved, and must be checked with
+/// `check_public_url` before requests are made to them.
+pub(crate) fn public_client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .dns_resolver(Arc::new(PublicResolver))
+        .redirect(Policy::custom(|attempt| {
+            if attempt.previous().len() >= HTTP_MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if is_denied_url(attempt.url()) {
+                attempt.error("redirect to a non-public address")
+            } else {
+                attempt.follow()
+            }
+        }))
+}
+
+/// Check that the given URL is an HTTP(S) URL whose host isn't a non-public
+/// address, so that requests can be made to it with a client created by
+/// `public_client_builder`.
+pub(crate) fn check_public_url(url: &str) -> Result<(), String> {
+    match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+            if is_denied_url(&parsed) {
+                Err(format!("{url} is not a public address"))
+            } else {
+                Ok(())
+            }
+        }
+        _ => Err(format!("{url} is not an HTTP(S) URL")),
+    }
+}
+
+/// Check that the host of the given URL resolves only to public addresses, for
+/// connections that aren't made through `public_client_builder`, e.g., those of
+/// NATS or Redis clients.
+///
+/// Host names are resolved again when the connection is made, so this doesn't
+/// guard against names whose addresses change in between.
+#[cfg_attr(
+    not(any(feature = "kafka", feature = "nats", feature = "redis")),
+    allow(dead_code)
+)]
+pub(crate) async fn check_public_host(url: &Url) -> Result<(), String> {
+    let host = match url.host_str() {
+        Some(host) if !host.is_empty() => host,
+        _ => return Err(format!("{url} has no host")),
+    };
+    if is_denied_url(url) {
+        return Err(format!("{host} is not a public address"));
+    }
+
+    let port = url.port_or_known_default().unwrap_or(0);
+    let addrs = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve {host}: {e}"))?
+        .collect::<Vec<SocketAddr>>();
+    if addrs.is_empty() {
+        return Err(format!("{host} has no address"));
+    }
+    if addrs.iter().any(|addr| is_denied_address(addr.ip())) {
+        return Err(format!("{host} resolves to a non-public address"));
+    }
+    Ok(())
+}
+
+/// Resolves host names, keeping only their public addresses.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| !is_denied_address(addr.ip()))
+                .collect::<Vec<SocketAddr>>();
+            if addrs.is_empty() {
+                return Err(format!("{host} has no public address").into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Whether the host of the given URL is an address that requests are denied to.
+///
+/// Host names are checked by `PublicResolver` once resolved.
+fn is_denied_url(url: &Url) -> bool {
+    match url.host_str() {
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .map_or(false, is_denied_address),
+        None => true,
+    }
+}
+
+/// Whether the given address is not public, e.g., a loopback, private or
+/// link-local address.
+fn is_denied_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_denied_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_denied_ipv4(ip),
+            None => is_denied_ipv6(ip),
+        },
+    }
+}
+
+fn is_denied_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        // "This network", 0.0.0.0/8.
+        || a == 0
+        // Shared address space used for carrier-grade NAT, 100.64.0.0/10.
+        || (a == 100 && (64..128).contains(&b))
+        // Reserved for future use, 240.0.0.0/4.
+        || a >= 240
+}
+
+fn is_denied_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local addresses, fc00::/7.
+        || (first & 0xfe00) == 0xfc00
+        // Link-local addresses, fe80::/10.
+        || (first & 0xffc0) == 0xfe80
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Serve a single response with a body of the given size, and return its URL.
+    async fn serve_body(size: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {size}\r\nConnection: close\r\n\r\n"
+            );
+            let _ = socket.write_all(header.as_bytes()).await;
+            let _ = socket.write_all(&vec![b'a'; size]).await;
+        });
+        format!("http://{addr}/")
+    }
+
+    #[test]
+    fn test_non_public_addresses_are_denied() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(is_denied_address(ip.parse().unwrap()), "{ip} is not denied");
+        }
+
+        for ip in ["1.1.1.1", "93.184.216.34", "100.128.0.1", "2606:4700::1111"] {
+            assert!(!is_denied_address(ip.parse().unwrap()), "{ip} is denied");
+        }
+
+        assert!(is_denied_url(
+            &Url::parse("http://127.0.0.1:8080/").unwrap()
+        ));
+        assert!(is_denied_url(&Url::parse("http://[::1]/").unwrap()));
+        assert!(!is_denied_url(&Url::parse("https://example.com/").unwrap()));
+
+        assert!(check_public_url("http://169.254.169.254/latest/meta-data").is_err());
+        assert!(check_public_url("file:///etc/passwd").is_err());
+        assert!(check_public_url("https://example.com/hook").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_hosts_of_connections_must_be_public() {
+        for url in [
+            "redis://127.0.0.1:6379",
+            "nats://localhost:4222",
+            "unix:///var/run/redis.sock",
+        ] {
+            assert!(
+                check_public_host(&Url::parse(url).unwrap()).await.is_err(),
+                "{url} is allowed"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_get_requires_capability_and_public_address() {
+        let url = serve_body(4).await;
+
+        assert_eq!(
+            HttpClient::with_local_access(false, true).get(&url).await,
+            None
+        );
+        assert_eq!(HttpClient::new(true).get(&url).await, None);
+        assert_eq!(
+            HttpClient::new(true)
+                .get(&url.replace("127.0.0.1", "localhost"))
+                .await,
+            None
+        );
+        assert_eq!(HttpClient::new(true).get("file:///etc/passwd").await, None);
+
+        assert_eq!(
+            HttpClient::with_local_access(true, true).get(&url).await,
+            Some(b"aaaa".to_vec())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_get_discards_oversized_responses() {
+        let client = HttpClient::with_local_access(true, true);
+
+        let url = serve_body(HTTP_MAX_RESPONSE_BYTES + 1).await;
+        assert_eq!(client.get(&url).await, None);
+
+        let url = serve_body(HTTP_MAX_RESPONSE_BYTES).await;
+        assert_eq!(
+            client.get(&url).await.map(|body| body.len()),
+            Some(HTTP_MAX_RESPONSE_BYTES)
+        );
+    }
+}
@@ -1,14 +1,25 @@
 #![deny(unused_crate_dependencies)]
+pub mod block_archive;
+pub mod block_recording;
+pub mod block_source;
 pub mod cli;
 pub(crate) mod commands;
 mod database;
 pub mod executor;
 pub mod ffi;
+pub mod http;
+mod memory;
+pub mod module_cache;
 pub(crate) mod queries;
 mod service;
+pub mod sink;
+mod tunables;
+pub mod webhook;
 
-pub use database::Database;
-pub use executor::{Executor, IndexEnv, NativeIndexExecutor, WasmIndexExecutor};
+pub use database::{Database, TRANSACTION_INDEX};
+pub use executor::{
+    DeclarativeIndexExecutor, Executor, IndexEnv, NativeIndexExecutor, WasmIndexExecutor,
+};
 pub use fuel_indexer_database::IndexerDatabaseError;
 pub use fuel_indexer_lib::{
     config::IndexerConfig,
@@ -24,9 +35,9 @@ use openssl as _;
 
 pub mod prelude {
     pub use super::{
-        Database, Executor, FtColumn, IndexEnv, IndexerConfig, IndexerError,
-        IndexerResult, IndexerService, Manifest, Module, NativeIndexExecutor,
-        WasmIndexExecutor,
+        Database, DeclarativeIndexExecutor, Executor, FtColumn, IndexEnv, IndexerConfig,
+        IndexerError, IndexerResult, IndexerService, Manifest, Module,
+        NativeIndexExecutor, WasmIndexExecutor, TRANSACTION_INDEX,
     };
     pub use async_std::sync::{Arc, Mutex};
     pub use fuel_indexer_lib::config::{DatabaseConfig, FuelClientConfig, WebApiConfig};
@@ -85,4 +96,20 @@ pub enum IndexerError {
     Elapsed(#[from] tokio::time::error::Elapsed),
     #[error("Indexer end block has been stopping execution.")]
     EndBlockMet,
+    #[error("Indexer module uses {0}, which requires the `{1}` capability.")]
+    CapabilityNotGranted(String, String),
+    #[error("Incompatible indexer module: {0}.")]
+    IncompatibleFfiVersion(String),
+    #[error("Resource limit exceeded: {0}.")]
+    ResourceLimitExceeded(String),
+    #[error("Declarative mapping error: {0}")]
+    MappingError(#[from] fuel_indexer_lib::mapping::MappingError),
+    #[error("Sink error: {0}.")]
+    SinkError(String),
+    #[error("Failed to write entities: {0}.")]
+    WriteError(String),
+    #[error("Entities can't be read by handlers of transactions processed in parallel.")]
+    ParallelTransactionRead,
+    #[error("{0}.")]
+    TypeConversionError(#[from] fuel_indexer_types::fuel::TypeConversionError),
 }
@@ -0,0 +1,272 @@
+use crate::sink::column_value;
+use fuel_indexer_schema::{
+    query::{Comparison, EntityQuery},
+    FtColumn,
+};
+use serde_json::Value;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+/// A row of an entity's table held in memory.
+#[derive(Debug, Clone)]
+pub(crate) struct MemoryRow {
+    /// Values of the row's columns, in the order of the table's columns.
+    pub columns: Vec<FtColumn>,
+
+    /// Serialized entity, as stored in the `object` column.
+    pub object: Vec<u8>,
+}
+
+/// Entities and other indexer state held in memory.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MemoryState {
+    /// Rows of each table, keyed by table and then by ID.
+    pub tables: HashMap<String, BTreeMap<String, MemoryRow>>,
+
+    /// Queries that would have written many-to-many records, in order.
+    pub many_to_many: Vec<String>,
+
+    /// Contracts discovered at runtime, as `(template, contract_id)` pairs.
+    pub contracts: Vec<(String, String)>,
+}
+
+/// In-memory storage of an indexer's entities, used in place of a database to run
+/// its handlers in tests.
+///
+/// Transactions are emulated by working on a copy of the committed state, which
+/// replaces it on commit and is dropped on revert.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryStore {
+    /// State as of the last committed transaction.
+    committed: MemoryState,
+
+    /// State of the open transaction, if any.
+    pending: Option<MemoryState>,
+}
+
+impl MemoryStore {
+    /// Open a transaction.
+    pub fn start_transaction(&mut self) {
+        self.pending = Some(self.committed.clone());
+    }
+
+    /// Commit the open transaction, if any.
+    pub fn commit_transaction(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            self.committed = pending;
+        }
+    }
+
+    /// Revert the open transaction, if any.
+    pub fn revert_transaction(&mut self) {
+        self.pending = None;
+    }
+
+    /// Return the committed state.
+    pub fn committed(&self) -> &MemoryState {
+        &self.committed
+    }
+
+    /// Return the state of the open transaction.
+    pub fn pending(&mut self) -> &mut MemoryState {
+        self.pending
+            .as_mut()
+            .expect("No open transaction in memory. Was a transaction started?")
+    }
+}
+
+impl MemoryState {
+    /// Insert or replace a row of the given table.
+    pub fn put(&mut self, table: &str, columns: Vec<FtColumn>, object: Vec<u8>) {
+        // The first column of every entity is its ID.
+        let id = columns[0].query_fragment();
+        self.tables
+            .entry(table.to_string())
+            .or_default()
+            .insert(id, MemoryRow { columns, object });
+    }
+
+    /// Return the row of the given table with the given ID.
+    pub fn get(&self, table: &str, id: &str) -> Option<&MemoryRow> {
+        self.tables.get(table)?.get(id)
+    }
+
+    /// Return the rows of the given table, ordered by ID.
+    pub fn rows(&self, table: &str) -> Vec<&MemoryRow> {
+        let mut rows = self
+            .tables
+            .get(table)
+            .map(|rows| rows.values().collect::<Vec<_>>())
+            .unwrap_or_default();
+        rows.sort_by(|a, b| {
+            compare(&a.columns[0], &b.columns[0]).unwrap_or(Ordering::Equal)
+        });
+        rows
+    }
+
+    /// Return the rows of the given table that match a query, whose columns
+    /// are named by `column_names`.
+    pub fn find(
+        &self,
+        table: &str,
+        column_names: &[String],
+        query: &EntityQuery,
+    ) -> Vec<&MemoryRow> {
+        let position = |name: &str| column_names.iter().position(|c| c == name);
+
+        let mut rows = self
+            .tables
+            .get(table)
+            .map(|rows| {
+                rows.values()
+                    .filter(|row| {
+                        query.filters.iter().all(|filter| {
+                            position(&filter.column)
+                                .and_then(|i| row.columns.get(i))
+                                .map(|value| {
+                                    matches(value, filter.comparison, &filter.value)
+                                })
+                                .unwrap_or(false)
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let (order_by, descending) = match &query.order_by {
+            Some((column, descending)) => (column.as_str(), *descending),
+            None => ("id", false),
+        };
+        if let Some(i) = position(order_by) {
+            rows.sort_by(|a, b| {
+                let ordering =
+                    compare(&a.columns[i], &b.columns[i]).unwrap_or(Ordering::Equal);
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        rows.into_iter()
+            .skip(query.offset.unwrap_or(0) as usize)
+            .take(query.limit.map(|n| n as usize).unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+/// Whether a value matches a comparison, with SQL semantics: no comparison with a
+/// null value matches, except testing it for (in)equality with null.
+fn matches(value: &FtColumn, comparison: Comparison, other: &FtColumn) -> bool {
+    let (value, other_value) = (column_value(value), column_value(other));
+    match (comparison, value.is_null(), other_value.is_null()) {
+        (Comparison::Eq, is_null, true) => is_null,
+        (Comparison::Ne, is_null, true) => !is_null,
+        (_, true, _) | (_, _, true) => false,
+        _ => match compare_values(&value, &other_value) {
+            Some(ordering) => match comparison {
+                Comparison::Eq => ordering == Ordering::Equal,
+                Comparison::Ne => ordering != Ordering::Equal,
+                Comparison::Gt => ordering == Ordering::Greater,
+                Comparison::Ge => ordering != Ordering::Less,
+                Comparison::Lt => ordering == Ordering::Less,
+                Comparison::Le => ordering != Ordering::Greater,
+            },
+            None => false,
+        },
+    }
+}
+
+/// Compare two column values, ordering null values last, as Postgres does.
+fn compare(a: &FtColumn, b: &FtColumn) -> Option<Ordering> {
+    let (a, b) = (column_value(a), column_value(b));
+    match (a.is_null(), b.is_null()) {
+        (true, true) => Some(Ordering::Equal),
+        (true, false) => Some(Ordering::Greater),
+        (false, true) => Some(Ordering::Less),
+        (false, false) => compare_values(&a, &b),
+    }
+}
+
+/// Compare two non-null JSON values of the same type.
+///
+/// Large integers are encoded as strings, so strings holding integers are
+/// compared as numbers.
+fn compare_values(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => match (a.as_i64(), b.as_i64()) {
+            (Some(a), Some(b)) => Some(a.cmp(&b)),
+            _ => match (a.as_u64(), b.as_u64()) {
+                (Some(a), Some(b)) => Some(a.cmp(&b)),
+                _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+            },
+        },
+        (Value::String(a), Value::String(b)) => {
+            match (a.parse::<i128>(), b.parse::<i128>()) {
+                (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+                _ => match (a.parse::<u128>(), b.parse::<u128>()) {
+                    (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+                    _ => Some(a.cmp(b)),
+                },
+            }
+        }
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (a, b) if a == b => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_schema::query::Filter;
+
+    fn row(id: u64, amount: Option<u64>) -> Vec<FtColumn> {
+        vec![FtColumn::ID(Some(id)), FtColumn::UInt8(amount)]
+    }
+
+    #[test]
+    fn test_memory_store_transactions_and_queries() {
+        let table = "ns_id.transfer";
+        let columns = vec!["id".to_string(), "amount".to_string()];
+
+        let mut store = MemoryStore::default();
+        store.start_transaction();
+        store.pending().put(table, row(1, Some(30)), vec![1]);
+        store.pending().put(table, row(2, Some(10)), vec![2]);
+        store.pending().put(table, row(3, None), vec![3]);
+        store.commit_transaction();
+
+        store.start_transaction();
+        store.pending().put(table, row(4, Some(20)), vec![4]);
+        store.revert_transaction();
+        assert_eq!(store.committed().tables[table].len(), 3);
+
+        store.start_transaction();
+        let query = EntityQuery {
+            filters: vec![Filter::new(
+                "amount",
+                Comparison::Gt,
+                FtColumn::UInt8(Some(5)),
+            )],
+            order_by: Some(("amount".to_string(), true)),
+            ..Default::default()
+        };
+        let objects = store
+            .pending()
+            .find(table, &columns, &query)
+            .into_iter()
+            .map(|row| row.object.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(objects, vec![vec![1], vec![2]]);
+
+        let query = EntityQuery {
+            filters: vec![Filter::new("amount", Comparison::Eq, FtColumn::UInt8(None))],
+            ..Default::default()
+        };
+        assert_eq!(store.pending().find(table, &columns, &query).len(), 1);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{IndexerConfig, IndexerError, IndexerResult};
+use fuel_indexer_lib::utils::sha256_digest;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, warn};
+use wasmer::{Module, Store};
+
+/// File extension used for serialized modules in the cache directory.
+const MODULE_CACHE_EXTENSION: &str = "wasmu";
+
+/// File extension used for the digests of serialized modules in the cache directory.
+const MODULE_DIGEST_EXTENSION: &str = "sha256";
+
+/// An on-disk cache of compiled WASM modules.
+///
+/// Compiled artifacts are keyed by a digest of the module bytes (and the metering
+/// configuration, since metering is compiled into the artifact), so that service
+/// restarts and executor respawns can skip recompilation.
+///
+/// Deserializing an artifact runs its native code unchecked, so the cache directory
+/// must only be writable by the indexer service. The digest stored alongside each
+/// artifact is verified before it is deserialized, which guards against truncated or
+/// corrupted artifacts and against files not written by the cache, but not against
+/// anyone able to write both.
+#[derive(Debug, Clone)]
+pub struct ModuleCache {
+    /// Directory in which compiled modules are stored.
+    dir: PathBuf,
+
+    /// Maximum number of compiled modules to keep.
+    size: usize,
+}
+
+impl ModuleCache {
+    /// Create a new `ModuleCache` at the given directory.
+    pub fn new(dir: impl AsRef<Path>, size: usize) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            size,
+        }
+    }
+
+    /// Create a new `ModuleCache` from the given config, if caching is enabled.
+    pub fn from_config(config: &IndexerConfig) -> Option<Self> {
+        match &config.module_cache_dir {
+            Some(dir) if config.module_cache_size > 0 => {
+                Some(Self::new(dir, config.module_cache_size))
+            }
+            _ => None,
+        }
+    }
+
+    /// Return the cache key for the given module bytes.
+    pub fn key(wasm_bytes: impl AsRef<[u8]>, metering_points: Option<u64>) -> String {
+        let digest = sha256_digest(&wasm_bytes.as_ref());
+        match metering_points {
+            Some(points) => format!("{digest}-{points}"),
+            None => format!("{digest}-unmetered"),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.{MODULE_CACHE_EXTENSION}"))
+    }
+
+    fn digest_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.{MODULE_DIGEST_EXTENSION}"))
+    }
+
+    /// Load a compiled module from the cache, or compile it and add it to the cache.
+    ///
+    /// Failures to read from or write to the cache are logged and never prevent
+    /// the module from being compiled.
+    pub fn load_or_compile(
+        &self,
+        store: &Store,
+        wasm_bytes: impl AsRef<[u8]>,
+        metering_points: Option<u64>,
+    ) -> IndexerResult<Module> {
+        let key = Self::key(&wasm_bytes, metering_points);
+
+        if let Some(module) = self.load(store, &key) {
+            debug!("Loaded compiled module({key}) from cache.");
+            return Ok(module);
+        }
+
+        let module = Module::new(store, &wasm_bytes)?;
+
+        if let Err(e) = self.store(&key, &module) {
+            warn!("Failed to cache compiled module({key}): {e}.");
+        }
+
+        Ok(module)
+    }
+
+    /// Load the compiled module with the given key, if it's in the cache and its
+    /// artifact matches its stored digest.
+    fn load(&self, store: &Store, key: &str) -> Option<Module> {
+        let path = self.path(key);
+        if !path.exists() {
+            return None;
+        }
+
+        let verified = fs::read(&path).and_then(|artifact| {
+            let digest = fs::read_to_string(self.digest_path(key))?;
+            Ok((sha256_digest(&artifact) == digest.trim()).then_some(artifact))
+        });
+        let artifact = match verified {
+            Ok(Some(artifact)) => artifact,
+            Ok(None) => {
+                warn!("Compiled module({key}) does not match its cached digest.");
+                self.remove(key);
+                return None;
+            }
+            Err(e) => {
+                warn!("Failed to read compiled module({key}) from cache: {e}.");
+                self.remove(key);
+                return None;
+            }
+        };
+
+        // SAFETY: The artifact matches the digest written along with it by `store`,
+        // the cache directory is trusted not to be written to by anyone else, and
+        // `deserialize` verifies that the artifact was produced by a compatible engine.
+        match unsafe { Module::deserialize(store, artifact) } {
+            Ok(module) => Some(module),
+            Err(e) => {
+                warn!("Failed to load compiled module({key}) from cache: {e}.");
+                self.remove(key);
+                None
+            }
+        }
+    }
+
+    fn store(&self, key: &str, module: &Module) -> IndexerResult<()> {
+        fs::create_dir_all(&self.dir)?;
+        let artifact = module
+            .serialize()
+            .map_err(|e| IndexerError::Unknown(e.to_string()))?;
+        fs::write(self.path(key), &artifact)?;
+        fs::write(self.digest_path(key), sha256_digest(&artifact))?;
+        self.evict()?;
+        Ok(())
+    }
+
+    /// Remove the compiled module with the given key, and its digest.
+    fn remove(&self, key: &str) {
+        let _ = fs::remove_file(self.path(key));
+        let _ = fs::remove_file(self.digest_path(key));
+    }
+
+    /// Remove the oldest compiled modules until the cache is within its size limit.
+    fn evict(&self) -> std::io::Result<()> {
+        let mut entries = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.path().extension().and_then(|ext| ext.to_str())
+                    == Some(MODULE_CACHE_EXTENSION)
+            })
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((modified, entry.path()))
+            })
+            .collect::<Vec<_>>();
+
+        if entries.len() <= self.size {
+            return Ok(());
+        }
+
+        entries.sort();
+        let excess = entries.len() - self.size;
+        for (_, path) in entries.into_iter().take(excess) {
+            debug!("Evicting compiled module at {} from cache.", path.display());
+            fs::remove_file(&path)?;
+            let _ = fs::remove_file(path.with_extension(MODULE_DIGEST_EXTENSION));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{thread::sleep, time::Duration};
+
+    /// The smallest valid WASM module.
+    const EMPTY_MODULE: &[u8] = b"\0asm\x01\0\0\0";
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "fuel-indexer-module-cache-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_module_cache_key_includes_metering_points() {
+        let key = ModuleCache::key(EMPTY_MODULE, Some(100));
+        let digest = sha256_digest(&EMPTY_MODULE);
+
+        assert_eq!(key, format!("{digest}-100"));
+        assert_eq!(key, ModuleCache::key(EMPTY_MODULE.to_vec(), Some(100)));
+        assert_ne!(key, ModuleCache::key(EMPTY_MODULE, Some(200)));
+        assert_eq!(
+            ModuleCache::key(EMPTY_MODULE, None),
+            format!("{digest}-unmetered")
+        );
+        assert_ne!(key, ModuleCache::key(b"\0asm\x01\0\0\0\0", Some(100)));
+    }
+
+    #[test]
+    fn test_module_cache_loads_only_verified_modules() {
+        let dir = cache_dir("verified");
+        let cache = ModuleCache::new(&dir, 4);
+        let store = Store::default();
+        let key = ModuleCache::key(EMPTY_MODULE, None);
+
+        // A miss compiles the module and stores it along with its digest.
+        assert!(cache.load(&store, &key).is_none());
+        cache.load_or_compile(&store, EMPTY_MODULE, None).unwrap();
+        assert!(cache.path(&key).exists());
+        assert!(cache.digest_path(&key).exists());
+        assert!(cache.load(&store, &key).is_some());
+
+        // An artifact that doesn't match its digest is discarded, and recompiled.
+        let mut artifact = fs::read(cache.path(&key)).unwrap();
+        artifact.push(0);
+        fs::write(cache.path(&key), artifact).unwrap();
+        assert!(cache.load(&store, &key).is_none());
+        assert!(!cache.path(&key).exists());
+        cache.load_or_compile(&store, EMPTY_MODULE, None).unwrap();
+        assert!(cache.load(&store, &key).is_some());
+
+        // So is an artifact without a digest.
+        fs::remove_file(cache.digest_path(&key)).unwrap();
+        assert!(cache.load(&store, &key).is_none());
+        assert!(!cache.path(&key).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_module_cache_evicts_oldest_modules() {
+        let dir = cache_dir("eviction");
+        let cache = ModuleCache::new(&dir, 2);
+        let store = Store::default();
+        let module = Module::new(&store, EMPTY_MODULE).unwrap();
+
+        for key in ["first", "second", "third"] {
+            cache.store(key, &module).unwrap();
+            // Ensure that each artifact has a distinct modification time.
+            sleep(Duration::from_millis(20));
+        }
+
+        assert!(!cache.path("first").exists());
+        assert!(!cache.digest_path("first").exists());
+        for key in ["second", "third"] {
+            assert!(cache.load(&store, key).is_some());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
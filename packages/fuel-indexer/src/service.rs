@@ -1,12 +1,20 @@
 use crate::{
-    executor::{ExecutorSource, NativeIndexExecutor, WasmIndexExecutor},
-    Database, IndexerConfig, IndexerError, IndexerResult, Manifest,
+    executor::{
+        DeclarativeIndexExecutor, ExecutorSnapshot, ExecutorSource, NativeIndexExecutor,
+        WasmIndexExecutor,
+    },
+    Database, IndexerConfig, IndexerError, IndexerResult, Manifest, Module,
 };
 use async_std::sync::{Arc, Mutex};
 use fuel_indexer_database::{
-    queries, types::IndexerAssetType, IndexerConnection, IndexerConnectionPool,
+    queries,
+    types::{IndexerAssetType, IndexerState},
+    IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_lib::{
+    defaults,
+    utils::{deserialize, ServiceRequest},
 };
-use fuel_indexer_lib::{defaults, utils::ServiceRequest};
 use fuel_indexer_schema::db::manager::SchemaManager;
 use fuel_indexer_types::fuel::BlockData;
 use futures::{
@@ -122,10 +130,10 @@ impl IndexerService {
             )
             .await?;
 
-        let start_block = get_start_block(&mut conn, &manifest).await?;
+        let start_block = get_start_block(&mut conn, &manifest, &self.config).await?;
         manifest.set_start_block(start_block);
 
-        let (handle, exec_source, killer) = WasmIndexExecutor::create(
+        let (handle, exec_source, killer) = create_executor(
             &self.config,
             &manifest,
             ExecutorSource::Manifest,
@@ -177,13 +185,23 @@ impl IndexerService {
         let mut conn = self.pool.acquire().await?;
         let indices = queries::all_registered_indexers(&mut conn).await?;
         for index in indices {
+            let state =
+                queries::indexer_state(&mut conn, &index.namespace, &index.identifier)
+                    .await?;
+            if state == IndexerState::Paused {
+                info!("Skipping paused Indexer({}).", index.uid());
+                continue;
+            }
+
             let assets = queries::latest_assets_for_indexer(&mut conn, &index.id).await?;
             let mut manifest = Manifest::try_from(&assets.manifest.bytes)?;
 
-            let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
+            let start_block = get_start_block(&mut conn, &manifest, &self.config)
+                .await
+                .unwrap_or(self.config.genesis_height + 1);
             manifest.set_start_block(start_block);
 
-            let (handle, _module_bytes, killer) = WasmIndexExecutor::create(
+            let (handle, _module_bytes, killer) = create_executor(
                 &self.config,
                 &manifest,
                 ExecutorSource::Registry(assets.wasm.bytes),
@@ -226,7 +244,9 @@ impl IndexerService {
             )
             .await?;
 
-        let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
+        let start_block = get_start_block(&mut conn, &manifest, &self.config)
+            .await
+            .unwrap_or(self.config.genesis_height + 1);
         manifest.set_start_block(start_block);
 
         let uid = manifest.uid();
@@ -276,6 +296,22 @@ impl IndexerService {
     }
 }
 
+/// An executor reprocessing a range of an indexer's blocks, after which the
+/// indexer's executor is restarted from its latest checkpoint.
+struct Repair {
+    /// The namespace of the indexer being repaired.
+    namespace: String,
+
+    /// The identifier of the indexer being repaired.
+    identifier: String,
+
+    /// Handle to the executor reprocessing the blocks.
+    handle: JoinHandle<()>,
+
+    /// Killer used to stop the executor reprocessing the blocks.
+    killer: Arc<AtomicBool>,
+}
+
 /// Create a tokio task used to listen to service messages primarily coming from the web API.
 async fn create_service_task(
     mut rx: Receiver<ServiceRequest>,
@@ -284,8 +320,68 @@ async fn create_service_task(
     futs: Arc<Mutex<FuturesUnordered<JoinHandle<()>>>>,
     mut killers: HashMap<String, Arc<AtomicBool>>,
 ) -> IndexerResult<()> {
+    let mut repairs: Vec<Repair> = Vec::new();
+
     loop {
         let futs = futs.lock().await;
+
+        let (done, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut repairs)
+            .into_iter()
+            .partition(|repair| repair.handle.is_finished());
+        repairs = pending;
+
+        for repair in done {
+            let uid = format!("{}.{}", repair.namespace, repair.identifier);
+
+            // A repair that was stopped, e.g., because the indexer was paused or
+            // replaced, leaves the indexer as it is.
+            if repair.killer.load(Ordering::SeqCst) {
+                continue;
+            }
+
+            if killers
+                .get(&uid)
+                .map_or(false, |killer| Arc::ptr_eq(killer, &repair.killer))
+            {
+                killers.remove(&uid);
+            }
+
+            let mut conn = pool.acquire().await?;
+
+            let id = match queries::get_indexer_id(
+                &mut conn,
+                &repair.namespace,
+                &repair.identifier,
+            )
+            .await
+            {
+                Ok(id) => id,
+                Err(e) => {
+                    error!("Failed to find Indexer({uid}): {e}");
+
+                    continue;
+                }
+            };
+
+            let start_block =
+                queries::get_checkpoint(&mut conn, &repair.namespace, &repair.identifier)
+                    .await?
+                    .map(|checkpoint| checkpoint.block_height + 1);
+
+            info!("Repair of Indexer({uid}) is done, restarting it.");
+
+            start_executor_from_registry(
+                &mut conn,
+                &config,
+                &pool,
+                &futs,
+                &mut killers,
+                &id,
+                start_block,
+            )
+            .await?;
+        }
+
         match rx.try_recv() {
             Ok(service_request) => match service_request {
                 ServiceRequest::Reload(request) => {
@@ -299,34 +395,16 @@ async fn create_service_task(
                     .await
                     {
                         Ok(id) => {
-                            let assets =
-                                queries::latest_assets_for_indexer(&mut conn, &id)
-                                    .await?;
-                            let mut manifest =
-                                Manifest::try_from(&assets.manifest.bytes)?;
-
-                            let start_block =
-                                get_start_block(&mut conn, &manifest).await?;
-                            manifest.set_start_block(start_block);
-
-                            let (handle, _module_bytes, killer) =
-                                WasmIndexExecutor::create(
-                                    &config,
-                                    &manifest,
-                                    ExecutorSource::Registry(assets.wasm.bytes),
-                                    pool.clone(),
-                                )
-                                .await?;
-
-                            futs.push(handle);
-
-                            if let Some(killer_for_prev_executor) =
-                                killers.insert(manifest.uid(), killer)
-                            {
-                                let uid = manifest.uid();
-                                info!("Indexer({uid}) was replaced. Stopping previous version of Indexer({uid}).");
-                                killer_for_prev_executor.store(true, Ordering::SeqCst);
-                            }
+                            start_executor_from_registry(
+                                &mut conn,
+                                &config,
+                                &pool,
+                                &futs,
+                                &mut killers,
+                                &id,
+                                None,
+                            )
+                            .await?;
                         }
                         Err(e) => {
                             error!(
@@ -338,6 +416,163 @@ async fn create_service_task(
                         }
                     }
                 }
+                ServiceRequest::Resume(request) => {
+                    let mut conn = pool.acquire().await?;
+
+                    match queries::get_indexer_id(
+                        &mut conn,
+                        &request.namespace,
+                        &request.identifier,
+                    )
+                    .await
+                    {
+                        Ok(id) => {
+                            // Resume from the latest checkpoint whether or not the
+                            // indexer is resumable, since its data was kept.
+                            let start_block = queries::get_checkpoint(
+                                &mut conn,
+                                &request.namespace,
+                                &request.identifier,
+                            )
+                            .await?
+                            .map(|checkpoint| checkpoint.block_height + 1);
+
+                            start_executor_from_registry(
+                                &mut conn,
+                                &config,
+                                &pool,
+                                &futs,
+                                &mut killers,
+                                &id,
+                                start_block,
+                            )
+                            .await?;
+                        }
+                        Err(e) => {
+                            error!(
+                                "Failed to find Indexer({}.{}): {}",
+                                &request.namespace, &request.identifier, e
+                            );
+
+                            continue;
+                        }
+                    }
+                }
+                ServiceRequest::Reindex(request) => {
+                    let uid = format!("{}.{}", request.namespace, request.identifier);
+
+                    if let Some(killer) = killers.remove(&uid) {
+                        info!("Stopping Indexer({uid}) to reindex it.");
+                        killer.store(true, Ordering::SeqCst);
+                    }
+
+                    let mut conn = pool.acquire().await?;
+
+                    let id = match queries::get_indexer_id(
+                        &mut conn,
+                        &request.namespace,
+                        &request.identifier,
+                    )
+                    .await
+                    {
+                        Ok(id) => id,
+                        Err(e) => {
+                            error!("Failed to find Indexer({uid}): {e}");
+
+                            continue;
+                        }
+                    };
+
+                    queries::start_transaction(&mut conn).await?;
+
+                    if let Err(e) = queries::reset_indexer_data(
+                        &mut conn,
+                        &request.namespace,
+                        &request.identifier,
+                    )
+                    .await
+                    {
+                        error!("Failed to reset the data of Indexer({uid}): {e}");
+                        queries::revert_transaction(&mut conn).await?;
+
+                        continue;
+                    }
+
+                    queries::commit_transaction(&mut conn).await?;
+
+                    info!(
+                        "Reindexing Indexer({uid}) from block {}.",
+                        request.start_block
+                    );
+
+                    start_executor_from_registry(
+                        &mut conn,
+                        &config,
+                        &pool,
+                        &futs,
+                        &mut killers,
+                        &id,
+                        Some(request.start_block),
+                    )
+                    .await?;
+                }
+                ServiceRequest::ReindexRange(request) => {
+                    let uid = format!("{}.{}", request.namespace, request.identifier);
+
+                    let mut conn = pool.acquire().await?;
+
+                    let id = match queries::get_indexer_id(
+                        &mut conn,
+                        &request.namespace,
+                        &request.identifier,
+                    )
+                    .await
+                    {
+                        Ok(id) => id,
+                        Err(e) => {
+                            error!("Failed to find Indexer({uid}): {e}");
+
+                            continue;
+                        }
+                    };
+
+                    // The repair is created before the indexer's executor is stopped,
+                    // so that the executor keeps running if the repair can't start.
+                    let (handle, killer) = match create_repair_executor(
+                        &mut conn,
+                        &config,
+                        &pool,
+                        &id,
+                        request.start_block,
+                        request.end_block,
+                    )
+                    .await
+                    {
+                        Ok(repair) => repair,
+                        Err(e) => {
+                            error!("Failed to start repair of Indexer({uid}): {e}");
+
+                            continue;
+                        }
+                    };
+
+                    if let Some(killer) = killers.insert(uid.clone(), killer.clone()) {
+                        info!("Stopping Indexer({uid}) to repair it.");
+                        killer.store(true, Ordering::SeqCst);
+                    }
+
+                    info!(
+                        "Reindexing blocks {} to {} of Indexer({uid}).",
+                        request.start_block, request.end_block
+                    );
+
+                    repairs.push(Repair {
+                        namespace: request.namespace,
+                        identifier: request.identifier,
+                        handle,
+                        killer,
+                    });
+                }
                 ServiceRequest::Stop(request) => {
                     let uid = format!("{}.{}", request.namespace, request.identifier);
 
@@ -356,19 +591,123 @@ async fn create_service_task(
     }
 }
 
+/// Create an executor for the registered indexer with the given ID from its latest
+/// assets, replacing any executor that is already running for it.
+///
+/// The executor starts at `start_block` if given, and otherwise at the block
+/// determined by `get_start_block`.
+async fn start_executor_from_registry(
+    conn: &mut IndexerConnection,
+    config: &IndexerConfig,
+    pool: &IndexerConnectionPool,
+    futs: &FuturesUnordered<JoinHandle<()>>,
+    killers: &mut HashMap<String, Arc<AtomicBool>>,
+    index_id: &i64,
+    start_block: Option<u64>,
+) -> IndexerResult<()> {
+    let assets = queries::latest_assets_for_indexer(conn, index_id).await?;
+    let mut manifest = Manifest::try_from(&assets.manifest.bytes)?;
+
+    let start_block = match start_block {
+        Some(start_block) => start_block,
+        None => get_start_block(conn, &manifest, config).await?,
+    };
+    manifest.set_start_block(start_block);
+
+    let (handle, _module_bytes, killer) = create_executor(
+        config,
+        &manifest,
+        ExecutorSource::Registry(assets.wasm.bytes),
+        pool.clone(),
+    )
+    .await?;
+
+    futs.push(handle);
+
+    if let Some(killer_for_prev_executor) = killers.insert(manifest.uid(), killer) {
+        let uid = manifest.uid();
+        info!(
+            "Indexer({uid}) was replaced. Stopping previous version of Indexer({uid})."
+        );
+        killer_for_prev_executor.store(true, Ordering::SeqCst);
+    }
+
+    Ok(())
+}
+
+/// Create an executor that reprocesses the blocks from `start_block` to `end_block`
+/// of the registered indexer with the given ID, from its latest assets.
+///
+/// Entities are saved by ID, so reprocessed blocks overwrite the entities they
+/// previously wrote.
+async fn create_repair_executor(
+    conn: &mut IndexerConnection,
+    config: &IndexerConfig,
+    pool: &IndexerConnectionPool,
+    index_id: &i64,
+    start_block: u64,
+    end_block: u64,
+) -> IndexerResult<(JoinHandle<()>, Arc<AtomicBool>)> {
+    let assets = queries::latest_assets_for_indexer(conn, index_id).await?;
+    let mut manifest = Manifest::try_from(&assets.manifest.bytes)?;
+    manifest.set_start_block(start_block);
+    manifest.set_end_block(end_block);
+
+    // Checkpoints are left to the indexer's executor, which restarts from its latest
+    // checkpoint once the repair is done.
+    let config = IndexerConfig {
+        checkpoint_interval: 0,
+        ..config.clone()
+    };
+
+    let (handle, _module_bytes, killer) = create_executor(
+        &config,
+        &manifest,
+        ExecutorSource::Registry(assets.wasm.bytes),
+        pool.clone(),
+    )
+    .await?;
+
+    Ok((handle, killer))
+}
+
 /// Determine the starting block for this indexer.
+///
+/// Indexers without a `start_block` start at the first block after the network's
+/// genesis block.
 async fn get_start_block(
     conn: &mut IndexerConnection,
     manifest: &Manifest,
+    config: &IndexerConfig,
 ) -> Result<u64, IndexerError> {
     match &manifest.resumable() {
         Some(resumable) => {
-            let last = queries::last_block_height_for_indexer(
+            let last_indexed = queries::last_block_height_for_indexer(
+                conn,
+                manifest.namespace(),
+                manifest.identifier(),
+            )
+            .await?;
+            let checkpoint = queries::get_checkpoint(
                 conn,
                 manifest.namespace(),
                 manifest.identifier(),
             )
             .await?;
+            let schema_version = queries::type_id_latest(
+                conn,
+                manifest.namespace(),
+                manifest.identifier(),
+            )
+            .await
+            .ok();
+            let last = resume_block(
+                checkpoint
+                    .as_ref()
+                    .map(|checkpoint| checkpoint.snapshot.as_slice()),
+                schema_version.as_deref(),
+                last_indexed,
+            );
             let start = manifest.start_block().unwrap_or(last);
             let block = if *resumable {
                 std::cmp::max(start, last)
@@ -381,6 +720,82 @@ async fn get_start_block(
             info!("{action} Indexer({}) from block {block}", manifest.uid());
             Ok(block)
         }
-        None => Ok(manifest.start_block().unwrap_or(1)),
+        None => Ok(manifest.start_block().unwrap_or(config.genesis_height + 1)),
+    }
+}
+
+/// Determine the block from which a resumable indexer continues, given the snapshot
+/// of its latest checkpoint and the last block recorded in its metadata table.
+///
+/// Checkpoints are saved outside of the transactions that commit blocks, so the
+/// metadata table can be ahead of the latest checkpoint, and the indexer resumes
+/// from whichever is further along. Checkpoints taken for an earlier version of the
+/// indexer's schema are ignored.
+fn resume_block(
+    snapshot: Option<&[u8]>,
+    schema_version: Option<&str>,
+    last_indexed: u64,
+) -> u64 {
+    let checkpointed = snapshot
+        .and_then(|snapshot| {
+            deserialize::<ExecutorSnapshot>(snapshot)
+                .map_err(|e| warn!("Failed to read checkpoint snapshot: {e}."))
+                .ok()
+        })
+        .filter(|snapshot| Some(snapshot.schema_version.as_str()) == schema_version)
+        .map(|snapshot| snapshot.block_height + 1);
+
+    checkpointed.map_or(last_indexed, |block| block.max(last_indexed))
+}
+
+/// Create the executor for an indexer, based on the kind of module in its manifest.
+///
+/// The module of a declarative indexer is its compiled mappings, which are stored in
+/// the registry in place of a WASM module.
+async fn create_executor(
+    config: &IndexerConfig,
+    manifest: &Manifest,
+    exec_source: ExecutorSource,
+    pool: IndexerConnectionPool,
+) -> IndexerResult<(JoinHandle<()>, ExecutorSource, Arc<AtomicBool>)> {
+    match manifest.module() {
+        Module::Declarative(_) => {
+            DeclarativeIndexExecutor::create(config, manifest, exec_source, pool).await
+        }
+        _ => WasmIndexExecutor::create(config, manifest, exec_source, pool).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_lib::utils::serialize;
+
+    fn snapshot(block_height: u64, schema_version: &str) -> Vec<u8> {
+        serialize(&ExecutorSnapshot {
+            block_height,
+            cursor: Some(block_height.to_string()),
+            schema_version: schema_version.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_resume_block_prefers_the_furthest_progress() {
+        // Blocks committed after the latest checkpoint are not reprocessed.
+        let behind = snapshot(10, "v1");
+        assert_eq!(resume_block(Some(&behind), Some("v1"), 25), 25);
+
+        let ahead = snapshot(30, "v1");
+        assert_eq!(resume_block(Some(&ahead), Some("v1"), 25), 31);
+
+        assert_eq!(resume_block(None, Some("v1"), 25), 25);
+    }
+
+    #[test]
+    fn test_resume_block_ignores_unusable_checkpoints() {
+        let ahead = snapshot(30, "v1");
+        assert_eq!(resume_block(Some(&ahead), Some("v2"), 25), 25);
+        assert_eq!(resume_block(Some(&ahead), None, 25), 25);
+        assert_eq!(resume_block(Some(&[1, 2, 3]), Some("v1"), 25), 25);
     }
 }
@@ -0,0 +1,79 @@
+use super::{column_value, EntityChange};
+use apache_avro::{types::Value as AvroValue, AvroResult, Schema};
+use fuel_indexer_schema::FtColumn;
+use serde_json::{json, Value};
+
+/// Return the Avro type of a column.
+///
+/// Integers that may not fit in an Avro `long`, byte types, and JSON are
+/// encoded as strings.
+fn avro_type(column: &FtColumn) -> &'static str {
+    match column {
+        FtColumn::Boolean(_) => "boolean",
+        FtColumn::Int1(_) | FtColumn::Int4(_) => "int",
+        FtColumn::Int8(_)
+        | FtColumn::Timestamp(_)
+        | FtColumn::UInt1(_)
+        | FtColumn::UInt4(_)
+        | FtColumn::BlockHeight(_) => "long",
+        _ => "string",
+    }
+}
+
+/// Return the Avro schema of the record published for an entity.
+///
+/// Every field is nullable, as a union of `null` and the field's type.
+pub(crate) fn entity_schema(
+    namespace: &str,
+    identifier: &str,
+    change: &EntityChange,
+) -> AvroResult<Schema> {
+    let fields = change
+        .fields
+        .iter()
+        .map(|(name, column)| {
+            json!({ "name": name, "type": ["null", avro_type(column)], "default": null })
+        })
+        .collect::<Vec<_>>();
+
+    Schema::parse(&json!({
+        "type": "record",
+        "name": change.entity,
+        "namespace": format!("{namespace}.{identifier}"),
+        "fields": fields,
+    }))
+}
+
+/// Encode an entity change as an Avro datum of the given schema.
+pub(crate) fn encode(schema: &Schema, change: &EntityChange) -> AvroResult<Vec<u8>> {
+    let fields = change
+        .fields
+        .iter()
+        .map(|(name, column)| {
+            let value = match (avro_type(column), column_value(column)) {
+                (_, Value::Null) => AvroValue::Union(0, Box::new(AvroValue::Null)),
+                ("boolean", value) => AvroValue::Union(
+                    1,
+                    Box::new(AvroValue::Boolean(value.as_bool().unwrap_or_default())),
+                ),
+                ("int", value) => AvroValue::Union(
+                    1,
+                    Box::new(AvroValue::Int(value.as_i64().unwrap_or_default() as i32)),
+                ),
+                ("long", value) => AvroValue::Union(
+                    1,
+                    Box::new(AvroValue::Long(value.as_i64().unwrap_or_default())),
+                ),
+                (_, Value::String(value)) => {
+                    AvroValue::Union(1, Box::new(AvroValue::String(value)))
+                }
+                (_, value) => {
+                    AvroValue::Union(1, Box::new(AvroValue::String(value.to_string())))
+                }
+            };
+            (name.clone(), value)
+        })
+        .collect();
+
+    apache_avro::to_avro_datum(schema, AvroValue::Record(fields))
+}
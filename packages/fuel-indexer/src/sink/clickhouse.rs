@@ -0,0 +1,332 @@
+use super::{column_value, EntityChange, Sink};
+use crate::{
+    http::{check_public_url, public_client_builder},
+    webhook::DELIVERY_TIMEOUT_SECS,
+    IndexerError, IndexerResult,
+};
+use async_trait::async_trait;
+use fuel_indexer_lib::manifest::ClickHouseSinkConfig;
+use fuel_indexer_schema::FtColumn;
+use serde_json::{Map, Value};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+use tracing::{debug, error};
+
+/// Number of batches of rows that may be buffered for a table while ClickHouse
+/// is unavailable, before the oldest rows are dropped.
+const MAX_BUFFERED_BATCHES: usize = 10;
+
+/// Rows of a table waiting to be inserted.
+struct Buffer {
+    /// Definitions of the table's columns, taken from the first row buffered.
+    columns: Vec<String>,
+
+    /// Rows, encoded as `JSONEachRow` lines.
+    rows: Vec<String>,
+
+    /// When the rows were last flushed, or the first row was buffered.
+    since: Instant,
+}
+
+/// Mirrors append-only entities of an indexer into ClickHouse tables.
+///
+/// Rows are buffered in memory and inserted in batches, once a table has
+/// `batch_size` rows waiting or they have waited `flush_interval_secs`. Each table is
+/// created the first time rows are inserted into it, and is a `ReplacingMergeTree`
+/// ordered by ID, so that an entity saved more than once is eventually deduplicated.
+pub(crate) struct ClickHouseSink {
+    /// UID of the indexer whose entities are mirrored.
+    indexer_uid: String,
+
+    /// URL of the HTTP interface of the ClickHouse server.
+    url: String,
+
+    /// Database into which entities are mirrored.
+    database: String,
+
+    /// User to authenticate as.
+    user: Option<String>,
+
+    /// Password of the user.
+    password: Option<String>,
+
+    /// Maximum number of rows inserted into a table at once.
+    batch_size: usize,
+
+    /// Maximum time that rows are buffered before they are inserted.
+    flush_interval: Duration,
+
+    /// Whether the database has been created.
+    database_created: bool,
+
+    /// Tables that have been created.
+    tables: HashSet<String>,
+
+    /// Rows waiting to be inserted, keyed by table name.
+    buffers: HashMap<String, Buffer>,
+
+    /// Client used to send queries.
+    client: reqwest::Client,
+}
+
+impl ClickHouseSink {
+    /// Create a sink for the given ClickHouse server, which must be at a public
+    /// address.
+    pub(crate) fn new(
+        namespace: &str,
+        identifier: &str,
+        config: &ClickHouseSinkConfig,
+    ) -> IndexerResult<Self> {
+        check_public_url(&config.url).map_err(|e| {
+            IndexerError::SinkError(format!("Invalid ClickHouse URL: {e}"))
+        })?;
+        let client = public_client_builder()
+            .timeout(Duration::from_secs(DELIVERY_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| {
+                IndexerError::SinkError(format!(
+                    "Failed to create ClickHouse client: {e}"
+                ))
+            })?;
+
+        Ok(Self {
+            indexer_uid: format!("{namespace}.{identifier}"),
+            url: config.url.clone(),
+            database: config
+                .database
+                .clone()
+                .unwrap_or_else(|| format!("{namespace}_{identifier}")),
+            user: config.user.clone(),
+            password: config.password.clone(),
+            batch_size: config.batch_size.max(1),
+            flush_interval: Duration::from_secs(config.flush_interval_secs),
+            database_created: false,
+            tables: HashSet::new(),
+            buffers: HashMap::new(),
+            client,
+        })
+    }
+
+    /// Send a query to ClickHouse, with the given data as its body.
+    async fn execute(&self, query: &str, data: String) -> Result<(), String> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .query(&[("query", query)])
+            .body(data);
+        if let Some(user) = &self.user {
+            request = request.header("X-ClickHouse-User", user);
+        }
+        if let Some(password) = &self.password {
+            request = request.header("X-ClickHouse-Key", password);
+        }
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{status}: {}",
+                response.text().await.unwrap_or_default().trim()
+            ))
+        }
+    }
+
+    /// Create the database and the given table, unless they have been created already.
+    async fn create_table(
+        &mut self,
+        table: &str,
+        columns: &[String],
+    ) -> Result<(), String> {
+        if !self.database_created {
+            self.execute(
+                &format!("CREATE DATABASE IF NOT EXISTS `{}`", self.database),
+                String::new(),
+            )
+            .await?;
+            self.database_created = true;
+        }
+
+        if !self.tables.contains(table) {
+            self.execute(
+                &format!(
+                    "CREATE TABLE IF NOT EXISTS `{}`.`{table}` ({}) ENGINE = ReplacingMergeTree ORDER BY id",
+                    self.database,
+                    columns.join(", ")
+                ),
+                String::new(),
+            )
+            .await?;
+            self.tables.insert(table.to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Insert the rows buffered for the given table, in batches. Rows that can't be
+    /// inserted are kept to be retried, up to a limit.
+    async fn flush_table(&mut self, table: &str) {
+        let mut buffer = match self.buffers.remove(table) {
+            Some(buffer) => buffer,
+            None => return,
+        };
+
+        let mut result = self.create_table(table, &buffer.columns).await;
+        while result.is_ok() && !buffer.rows.is_empty() {
+            let count = buffer.rows.len().min(self.batch_size);
+            let data = buffer.rows[..count].join("\n");
+            result = self
+                .execute(
+                    &format!(
+                        "INSERT INTO `{}`.`{table}` FORMAT JSONEachRow",
+                        self.database
+                    ),
+                    data,
+                )
+                .await;
+            if result.is_ok() {
+                buffer.rows.drain(..count);
+                debug!(
+                    "Inserted {count} {table} rows of Indexer({}) into ClickHouse.",
+                    self.indexer_uid
+                );
+            }
+        }
+
+        if let Err(e) = result {
+            error!(
+                "Failed to insert {} {table} rows of Indexer({}) into ClickHouse: {e}.",
+                buffer.rows.len(),
+                self.indexer_uid
+            );
+
+            let limit = self.batch_size.saturating_mul(MAX_BUFFERED_BATCHES);
+            if buffer.rows.len() > limit {
+                let dropped = buffer.rows.len() - limit;
+                buffer.rows.drain(..dropped);
+                error!(
+                    "Dropped {dropped} {table} rows of Indexer({}), which could not be inserted into ClickHouse.",
+                    self.indexer_uid
+                );
+            }
+        }
+
+        if !buffer.rows.is_empty() {
+            buffer.since = Instant::now();
+            self.buffers.insert(table.to_string(), buffer);
+        }
+    }
+}
+
+#[async_trait]
+impl Sink for ClickHouseSink {
+    async fn publish(&mut self, changes: &[&EntityChange]) {
+        let mut full = Vec::new();
+        for change in changes {
+            let row = match serde_json::to_string(&row(change)) {
+                Ok(row) => row,
+                Err(e) => {
+                    error!(
+                        "Failed to encode {} change of Indexer({}): {e}.",
+                        change.entity, self.indexer_uid
+                    );
+                    continue;
+                }
+            };
+
+            let buffer = self
+                .buffers
+                .entry(change.entity.clone())
+                .or_insert_with(|| Buffer {
+                    columns: column_definitions(change),
+                    rows: Vec::new(),
+                    since: Instant::now(),
+                });
+            buffer.rows.push(row);
+            if buffer.rows.len() >= self.batch_size && !full.contains(&change.entity) {
+                full.push(change.entity.clone());
+            }
+        }
+
+        for table in full {
+            self.flush_table(&table).await;
+        }
+    }
+
+    async fn flush(&mut self) {
+        let due = self
+            .buffers
+            .iter()
+            .filter(|(_, buffer)| buffer.since.elapsed() >= self.flush_interval)
+            .map(|(table, _)| table.clone())
+            .collect::<Vec<_>>();
+        for table in due {
+            self.flush_table(&table).await;
+        }
+    }
+
+    async fn close(&mut self) {
+        let tables = self.buffers.keys().cloned().collect::<Vec<_>>();
+        for table in tables {
+            self.flush_table(&table).await;
+        }
+    }
+}
+
+/// Return the ClickHouse type of a column.
+fn column_type(column: &FtColumn) -> &'static str {
+    match column {
+        FtColumn::ID(_) => "UInt64",
+        FtColumn::Int1(_) => "Int8",
+        FtColumn::Int4(_) => "Int32",
+        FtColumn::Int8(_) | FtColumn::Timestamp(_) => "Int64",
+        FtColumn::Int16(_) | FtColumn::I128(_) => "Int128",
+        FtColumn::UInt1(_) => "UInt8",
+        FtColumn::UInt4(_) | FtColumn::BlockHeight(_) => "UInt32",
+        FtColumn::UInt8(_) => "UInt64",
+        FtColumn::UInt16(_) | FtColumn::U128(_) => "UInt128",
+        FtColumn::Boolean(_) => "Bool",
+        _ => "String",
+    }
+}
+
+/// Return the definitions of the columns of an entity's table.
+///
+/// Every column besides the ID is nullable, since ClickHouse can't tell from the
+/// values of one row which of its fields are optional.
+fn column_definitions(change: &EntityChange) -> Vec<String> {
+    change
+        .fields
+        .iter()
+        .map(|(name, column)| match column {
+            FtColumn::ID(_) => format!("`{name}` UInt64"),
+            _ => format!("`{name}` Nullable({})", column_type(column)),
+        })
+        .collect()
+}
+
+/// Return an entity change as a `JSONEachRow` row.
+///
+/// JSON, virtual, and array fields are stored as JSON strings.
+fn row(change: &EntityChange) -> Value {
+    Value::Object(
+        change
+            .fields
+            .iter()
+            .map(|(name, column)| {
+                let value = match (column, column_value(column)) {
+                    (_, Value::Null) => Value::Null,
+                    (
+                        FtColumn::Json(_) | FtColumn::Virtual(_) | FtColumn::Array(_),
+                        value,
+                    ) => Value::String(value.to_string()),
+                    (_, value) => value,
+                };
+                (name.clone(), value)
+            })
+            .collect::<Map<_, _>>(),
+    )
+}
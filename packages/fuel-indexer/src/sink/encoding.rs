@@ -0,0 +1,99 @@
+use super::{avro, EntityChange};
+use apache_avro::Schema;
+use fuel_indexer_lib::manifest::SinkFormat;
+use std::{collections::HashMap, sync::Arc};
+use tracing::error;
+
+/// An entity change encoded in a sink's format.
+pub(crate) struct EncodedChange {
+    /// Name of the entity's table.
+    pub entity: String,
+
+    /// ID of the entity, by which messages are keyed.
+    pub key: String,
+
+    /// The encoded entity.
+    pub payload: Vec<u8>,
+
+    /// Avro schema of the payload in canonical form, for the `avro` format.
+    pub schema: Option<Arc<String>>,
+}
+
+/// Encodes the entity changes of an indexer in a sink's format.
+pub(crate) struct Encoder {
+    /// UID of the indexer whose changes are encoded.
+    indexer_uid: String,
+
+    /// Namespace of the indexer.
+    namespace: String,
+
+    /// Identifier of the indexer.
+    identifier: String,
+
+    /// Format of the encoded changes.
+    format: SinkFormat,
+
+    /// Avro schema of each entity, and its canonical form.
+    schemas: HashMap<String, (Schema, Arc<String>)>,
+}
+
+impl Encoder {
+    /// Create a new `Encoder` for the given indexer.
+    pub(crate) fn new(namespace: &str, identifier: &str, format: SinkFormat) -> Self {
+        Self {
+            indexer_uid: format!("{namespace}.{identifier}"),
+            namespace: namespace.to_string(),
+            identifier: identifier.to_string(),
+            format,
+            schemas: HashMap::new(),
+        }
+    }
+
+    /// UID of the indexer whose changes are encoded.
+    pub(crate) fn indexer_uid(&self) -> &str {
+        &self.indexer_uid
+    }
+
+    /// Encode the given changes, logging the ones that could not be encoded.
+    pub(crate) fn encode_all(&mut self, changes: &[&EntityChange]) -> Vec<EncodedChange> {
+        let mut encoded = Vec::with_capacity(changes.len());
+        for change in changes {
+            match self.encode(change) {
+                Ok(change) => encoded.push(change),
+                Err(e) => error!(
+                    "Failed to encode {} change of Indexer({}): {e}.",
+                    change.entity, self.indexer_uid
+                ),
+            }
+        }
+        encoded
+    }
+
+    /// Encode an entity change.
+    fn encode(
+        &mut self,
+        change: &EntityChange,
+    ) -> Result<EncodedChange, Box<dyn std::error::Error + Send + Sync>> {
+        let (payload, schema) = match self.format {
+            SinkFormat::Json => (serde_json::to_vec(&change.to_json())?, None),
+            SinkFormat::Avro => {
+                if !self.schemas.contains_key(&change.entity) {
+                    let schema =
+                        avro::entity_schema(&self.namespace, &self.identifier, change)?;
+                    let canonical = Arc::new(schema.canonical_form());
+                    self.schemas
+                        .insert(change.entity.clone(), (schema, canonical));
+                }
+                let (schema, canonical) = &self.schemas[&change.entity];
+                (avro::encode(schema, change)?, Some(canonical.clone()))
+            }
+        };
+
+        Ok(EncodedChange {
+            entity: change.entity.clone(),
+            key: change.id.clone(),
+            payload,
+            schema,
+        })
+    }
+}
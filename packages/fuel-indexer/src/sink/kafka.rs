@@ -0,0 +1,108 @@
+use super::{
+    check_public_servers, destination_prefix,
+    encoding::{EncodedChange, Encoder},
+    EntityChange, Sink,
+};
+use crate::{IndexerError, IndexerResult};
+use async_trait::async_trait;
+use fuel_indexer_lib::manifest::KafkaSinkConfig;
+use futures::future::join_all;
+use rdkafka::{
+    message::{Header, OwnedHeaders},
+    producer::{FutureProducer, FutureRecord},
+    util::Timeout,
+    ClientConfig,
+};
+use tracing::{debug, error};
+
+/// Header holding the Avro schema of a message, in canonical form.
+const AVRO_SCHEMA_HEADER: &str = "avro.schema";
+
+/// Publishes an indexer's entity changes to Kafka, with one topic per entity.
+pub(crate) struct KafkaSink {
+    /// Encoder of the published changes.
+    encoder: Encoder,
+
+    /// Prefix of the topic names.
+    topic_prefix: String,
+
+    /// Producer used to publish messages.
+    producer: FutureProducer,
+}
+
+impl KafkaSink {
+    /// Create a new `KafkaSink` for the given indexer, whose brokers must be at
+    /// public addresses.
+    pub(crate) async fn connect(
+        namespace: &str,
+        identifier: &str,
+        config: &KafkaSinkConfig,
+    ) -> IndexerResult<Self> {
+        check_public_servers(&config.brokers, "kafka").await?;
+
+        let mut client_config = ClientConfig::new();
+        client_config.set("bootstrap.servers", &config.brokers);
+        for (key, value) in config.properties.iter() {
+            // Brokers are only given by `brokers`, so that they're checked.
+            if matches!(key.as_str(), "bootstrap.servers" | "metadata.broker.list") {
+                return Err(IndexerError::SinkError(format!(
+                    "Kafka brokers must be given by `brokers`, not the `{key}` property"
+                )));
+            }
+            client_config.set(key, value);
+        }
+
+        let producer = client_config.create::<FutureProducer>().map_err(|e| {
+            IndexerError::SinkError(format!("Failed to create Kafka producer: {e}"))
+        })?;
+
+        Ok(Self {
+            encoder: Encoder::new(namespace, identifier, config.format),
+            topic_prefix: destination_prefix(
+                config.topic_prefix.as_deref(),
+                namespace,
+                identifier,
+            ),
+            producer,
+        })
+    }
+
+    /// Send an encoded change to its entity's topic.
+    async fn send(&self, change: &EncodedChange) {
+        let topic = format!("{}{}", self.topic_prefix, change.entity);
+        let mut record = FutureRecord::to(&topic)
+            .key(&change.key)
+            .payload(&change.payload);
+        if let Some(schema) = &change.schema {
+            record = record.headers(OwnedHeaders::new().insert(Header {
+                key: AVRO_SCHEMA_HEADER,
+                value: Some(schema.as_str()),
+            }));
+        }
+
+        match self.producer.send(record, Timeout::Never).await {
+            Ok(_) => debug!(
+                "Published change of {} to {topic} for Indexer({}).",
+                change.key,
+                self.encoder.indexer_uid()
+            ),
+            Err((e, _)) => error!(
+                "Failed to publish change of {} to {topic} for Indexer({}): {e}.",
+                change.key,
+                self.encoder.indexer_uid()
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Sink for KafkaSink {
+    /// Publish changes to Kafka.
+    ///
+    /// Messages are queued in order, so that changes to an entity keep their
+    /// order within its partition.
+    async fn publish(&mut self, changes: &[&EntityChange]) {
+        let changes = self.encoder.encode_all(changes);
+        join_all(changes.iter().map(|change| self.send(change))).await;
+    }
+}
@@ -0,0 +1,302 @@
+#[cfg(feature = "sinks")]
+mod avro;
+mod clickhouse;
+#[cfg(feature = "sinks")]
+mod encoding;
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+#[cfg(feature = "redis")]
+mod redis_streams;
+mod webhook;
+
+use crate::{IndexerResult, Manifest};
+use async_trait::async_trait;
+use fuel_indexer_lib::manifest::SinkConfig;
+use fuel_indexer_schema::FtColumn;
+use serde_json::{Map, Value};
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+    time::Duration,
+};
+use tokio::{sync::mpsc, time::interval};
+use tracing::error;
+
+/// Number of committed transactions whose changes may be queued for a sink
+/// before commits wait for the sink to catch up.
+const SINK_QUEUE_CAPACITY: usize = 64;
+
+/// Number of seconds between flushes of the changes buffered by a sink.
+const SINK_FLUSH_INTERVAL_SECS: u64 = 1;
+
+/// An entity inserted or updated by an indexer.
+#[derive(Debug, Clone)]
+pub struct EntityChange {
+    /// Name of the entity's table (e.g., `transfer`).
+    pub entity: String,
+
+    /// ID of the entity.
+    pub id: String,
+
+    /// Names and values of the entity's fields.
+    pub fields: Vec<(String, FtColumn)>,
+}
+
+impl EntityChange {
+    /// Return the entity's fields as a JSON object.
+    pub fn to_json(&self) -> Value {
+        Value::Object(
+            self.fields
+                .iter()
+                .map(|(name, value)| (name.clone(), column_value(value)))
+                .collect::<Map<_, _>>(),
+        )
+    }
+}
+
+/// Return the JSON representation of a column's value.
+///
+/// Byte types are encoded as hex strings, and 128-bit integers as decimal
+/// strings, since they do not fit in a JSON number.
+pub(crate) fn column_value(column: &FtColumn) -> Value {
+    match column {
+        FtColumn::Boolean(value) => value.map(Value::Bool).unwrap_or(Value::Null),
+        FtColumn::Charfield(value) | FtColumn::Enum(value) => {
+            value.clone().map(Value::String).unwrap_or(Value::Null)
+        }
+        FtColumn::Json(value) | FtColumn::Virtual(value) => match value {
+            Some(json) => serde_json::from_str(&json.0)
+                .unwrap_or_else(|_| Value::String(json.0.clone())),
+            None => Value::Null,
+        },
+        FtColumn::Array(value) => match value {
+            Some(items) => Value::Array(items.iter().map(column_value).collect()),
+            None => Value::Null,
+        },
+        _ => {
+            let fragment = column.query_fragment();
+            match column {
+                _ if fragment == "NULL" => Value::Null,
+                FtColumn::ID(_)
+                | FtColumn::Int1(_)
+                | FtColumn::Int4(_)
+                | FtColumn::Int8(_)
+                | FtColumn::Timestamp(_)
+                | FtColumn::UInt1(_)
+                | FtColumn::UInt4(_)
+                | FtColumn::UInt8(_)
+                | FtColumn::BlockHeight(_) => {
+                    serde_json::from_str(&fragment).unwrap_or(Value::String(fragment))
+                }
+                _ => Value::String(fragment.trim_matches('\'').to_string()),
+            }
+        }
+    }
+}
+
+/// A system to which entity changes are published.
+#[async_trait]
+pub trait Sink: Send {
+    /// Publish the entity changes of a committed transaction.
+    ///
+    /// Changes that cannot be published are logged and dropped.
+    async fn publish(&mut self, changes: &[&EntityChange]);
+
+    /// Publish any changes the sink has buffered for long enough.
+    ///
+    /// Called periodically, for sinks that publish changes in batches.
+    async fn flush(&mut self) {}
+
+    /// Publish all the changes the sink has buffered, since no more will be published.
+    async fn close(&mut self) {
+        self.flush().await
+    }
+}
+
+/// Publishes the entity changes committed by an indexer to the sinks in its manifest.
+///
+/// Each sink publishes from a background task, in the order in which changes
+/// were committed. Changes are published after they are committed, so a change
+/// may be lost if the service stops before it is published.
+#[derive(Default)]
+pub struct EntitySinks {
+    /// Queues of the background tasks publishing to each sink.
+    senders: Vec<mpsc::Sender<Arc<Vec<EntityChange>>>>,
+}
+
+impl EntitySinks {
+    /// Connect to the sinks in the given manifest, and start publishing to them.
+    pub async fn new(manifest: &Manifest) -> IndexerResult<Self> {
+        let mut senders = Vec::new();
+
+        for config in manifest.sinks() {
+            let sink = connect(manifest, config).await?;
+            let (sender, receiver) = mpsc::channel(SINK_QUEUE_CAPACITY);
+            tokio::spawn(run(sink, config.entities().to_vec(), receiver));
+            senders.push(sender);
+        }
+
+        Ok(Self { senders })
+    }
+
+    /// Whether any sinks are configured.
+    pub fn is_empty(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    /// Queue the changes of a committed transaction for publishing.
+    pub async fn publish(&self, changes: Vec<EntityChange>) {
+        let changes = Arc::new(changes);
+        for sender in self.senders.iter() {
+            if sender.send(changes.clone()).await.is_err() {
+                error!("Entity sink stopped, dropping {} changes.", changes.len());
+            }
+        }
+    }
+}
+
+/// Create the sink for the given configuration.
+#[cfg_attr(not(feature = "sinks"), allow(unused_variables))]
+async fn connect(
+    manifest: &Manifest,
+    config: &SinkConfig,
+) -> IndexerResult<Box<dyn Sink>> {
+    let (namespace, identifier) = (manifest.namespace(), manifest.identifier());
+    match config {
+        #[cfg(feature = "kafka")]
+        SinkConfig::Kafka(config) => Ok(Box::new(
+            kafka::KafkaSink::connect(namespace, identifier, config).await?,
+        )),
+        #[cfg(feature = "nats")]
+        SinkConfig::Nats(config) => Ok(Box::new(
+            nats::NatsSink::connect(namespace, identifier, config).await?,
+        )),
+        #[cfg(feature = "redis")]
+        SinkConfig::Redis(config) => Ok(Box::new(
+            redis_streams::RedisStreamSink::connect(namespace, identifier, config)
+                .await?,
+        )),
+        SinkConfig::Webhook(config) => Ok(Box::new(webhook::WebhookSink::new(
+            namespace, identifier, config,
+        )?)),
+        SinkConfig::ClickHouse(config) => Ok(Box::new(clickhouse::ClickHouseSink::new(
+            namespace, identifier, config,
+        )?)),
+        #[allow(unreachable_patterns)]
+        _ => Err(crate::IndexerError::SinkError(format!(
+            "{kind} sinks require fuel-indexer to be built with the `{kind}` feature",
+            kind = config.kind()
+        ))),
+    }
+}
+
+/// Publish the changes of each committed transaction to a sink, in order,
+/// flushing the sink periodically.
+async fn run(
+    mut sink: Box<dyn Sink>,
+    entities: Vec<String>,
+    mut receiver: mpsc::Receiver<Arc<Vec<EntityChange>>>,
+) {
+    let mut flush = interval(Duration::from_secs(SINK_FLUSH_INTERVAL_SECS));
+    loop {
+        let changes = tokio::select! {
+            changes = receiver.recv() => match changes {
+                Some(changes) => changes,
+                None => break,
+            },
+            _ = flush.tick() => {
+                sink.flush().await;
+                continue;
+            }
+        };
+
+        let changes = changes
+            .iter()
+            .filter(|change| {
+                entities.is_empty()
+                    || entities
+                        .iter()
+                        .any(|e| e.eq_ignore_ascii_case(&change.entity))
+            })
+            .collect::<Vec<_>>();
+
+        if !changes.is_empty() {
+            sink.publish(&changes).await;
+        }
+    }
+
+    sink.close().await;
+}
+
+/// Return the prefix of the topics, subjects, or streams that an indexer's
+/// changes are published to, which defaults to `<namespace>.<identifier>.`.
+#[cfg(feature = "sinks")]
+fn destination_prefix(prefix: Option<&str>, namespace: &str, identifier: &str) -> String {
+    prefix
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("{namespace}.{identifier}."))
+}
+
+/// Check that each of the given comma-separated servers of a sink is at a public
+/// address, as the URLs of webhooks must be. Servers without a scheme are parsed
+/// with the given one.
+#[cfg(any(feature = "kafka", feature = "nats", feature = "redis"))]
+async fn check_public_servers(servers: &str, scheme: &str) -> IndexerResult<()> {
+    for server in servers.split(',').map(str::trim) {
+        let url = if server.contains("://") {
+            server.to_string()
+        } else {
+            format!("{scheme}://{server}")
+        };
+        let parsed = reqwest::Url::parse(&url).map_err(|e| {
+            crate::IndexerError::SinkError(format!("Invalid server {server}: {e}"))
+        })?;
+        crate::http::check_public_host(&parsed)
+            .await
+            .map_err(crate::IndexerError::SinkError)?;
+    }
+    Ok(())
+}
+
+impl Debug for EntitySinks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EntitySinks")
+            .field("sinks", &self.senders.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_types::scalar::Json;
+
+    #[test]
+    fn test_entity_change_to_json() {
+        let change = EntityChange {
+            entity: "transfer".to_string(),
+            id: "1".to_string(),
+            fields: vec![
+                ("id".to_string(), FtColumn::ID(Some(1))),
+                ("amount".to_string(), FtColumn::UInt16(Some(u128::MAX))),
+                ("memo".to_string(), FtColumn::Charfield(None)),
+                (
+                    "data".to_string(),
+                    FtColumn::Json(Some(Json(r#"{"a":1}"#.to_string()))),
+                ),
+            ],
+        };
+
+        assert_eq!(
+            change.to_json(),
+            serde_json::json!({
+                "id": 1,
+                "amount": u128::MAX.to_string(),
+                "memo": null,
+                "data": { "a": 1 },
+            })
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use super::{
+    check_public_servers, destination_prefix, encoding::Encoder, EntityChange, Sink,
+};
+use crate::{IndexerError, IndexerResult};
+use async_nats::{Client, HeaderMap};
+use async_trait::async_trait;
+use fuel_indexer_lib::manifest::NatsSinkConfig;
+use tracing::{debug, error};
+
+/// Header holding the ID of the entity a message is about.
+const ENTITY_ID_HEADER: &str = "Entity-Id";
+
+/// Header holding the Avro schema of a message, in canonical form.
+const AVRO_SCHEMA_HEADER: &str = "Avro-Schema";
+
+/// Publishes an indexer's entity changes to NATS, with one subject per entity.
+pub(crate) struct NatsSink {
+    /// Encoder of the published changes.
+    encoder: Encoder,
+
+    /// Prefix of the subjects.
+    subject_prefix: String,
+
+    /// Client used to publish messages.
+    client: Client,
+}
+
+impl NatsSink {
+    /// Connect to the NATS server of the given sink, which must be at a public
+    /// address.
+    pub(crate) async fn connect(
+        namespace: &str,
+        identifier: &str,
+        config: &NatsSinkConfig,
+    ) -> IndexerResult<Self> {
+        check_public_servers(&config.url, "nats").await?;
+        let client = async_nats::connect(&config.url).await.map_err(|e| {
+            IndexerError::SinkError(format!(
+                "Failed to connect to NATS at {}: {e}",
+                config.url
+            ))
+        })?;
+
+        Ok(Self {
+            encoder: Encoder::new(namespace, identifier, config.format),
+            subject_prefix: destination_prefix(
+                config.subject_prefix.as_deref(),
+                namespace,
+                identifier,
+            ),
+            client,
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for NatsSink {
+    async fn publish(&mut self, changes: &[&EntityChange]) {
+        for change in self.encoder.encode_all(changes) {
+            let subject = format!("{}{}", self.subject_prefix, change.entity);
+
+            let mut headers = HeaderMap::new();
+            headers.insert(ENTITY_ID_HEADER, change.key.as_str());
+            if let Some(schema) = &change.schema {
+                headers.insert(AVRO_SCHEMA_HEADER, schema.as_str());
+            }
+
+            match self
+                .client
+                .publish_with_headers(subject.clone(), headers, change.payload.into())
+                .await
+            {
+                Ok(_) => debug!(
+                    "Published change of {} to {subject} for Indexer({}).",
+                    change.key,
+                    self.encoder.indexer_uid()
+                ),
+                Err(e) => error!(
+                    "Failed to publish change of {} to {subject} for Indexer({}): {e}.",
+                    change.key,
+                    self.encoder.indexer_uid()
+                ),
+            }
+        }
+
+        // Messages are buffered by the client, so flush them to bound the
+        // changes that are lost if the service stops.
+        if let Err(e) = self.client.flush().await {
+            error!(
+                "Failed to flush changes to NATS for Indexer({}): {e}.",
+                self.encoder.indexer_uid()
+            );
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use super::{
+    check_public_servers, destination_prefix, encoding::Encoder, EntityChange, Sink,
+};
+use crate::{IndexerError, IndexerResult};
+use async_trait::async_trait;
+use fuel_indexer_lib::manifest::RedisSinkConfig;
+use redis::aio::ConnectionManager;
+use tracing::{debug, error};
+
+/// Appends an indexer's entity changes to Redis Streams, with one stream per entity.
+///
+/// Each entry has an `id` field holding the entity's ID, a `payload` field
+/// holding the encoded entity, and, for the `avro` format, a `schema` field.
+pub(crate) struct RedisStreamSink {
+    /// Encoder of the published changes.
+    encoder: Encoder,
+
+    /// Prefix of the stream keys.
+    stream_prefix: String,
+
+    /// Approximate maximum length of each stream.
+    max_len: Option<u64>,
+
+    /// Connection used to append entries, which reconnects when it is lost.
+    connection: ConnectionManager,
+}
+
+impl RedisStreamSink {
+    /// Connect to the Redis server of the given sink, which must be at a public
+    /// address.
+    pub(crate) async fn connect(
+        namespace: &str,
+        identifier: &str,
+        config: &RedisSinkConfig,
+    ) -> IndexerResult<Self> {
+        check_public_servers(&config.url, "redis").await?;
+        let connect = |e: redis::RedisError| {
+            IndexerError::SinkError(format!(
+                "Failed to connect to Redis at {}: {e}",
+                config.url
+            ))
+        };
+        let client = redis::Client::open(config.url.as_str()).map_err(connect)?;
+        let connection = client
+            .get_tokio_connection_manager()
+            .await
+            .map_err(connect)?;
+
+        Ok(Self {
+            encoder: Encoder::new(namespace, identifier, config.format),
+            stream_prefix: destination_prefix(
+                config.stream_prefix.as_deref(),
+                namespace,
+                identifier,
+            ),
+            max_len: config.max_len,
+            connection,
+        })
+    }
+}
+
+#[async_trait]
+impl Sink for RedisStreamSink {
+    async fn publish(&mut self, changes: &[&EntityChange]) {
+        let changes = self.encoder.encode_all(changes);
+        if changes.is_empty() {
+            return;
+        }
+
+        // The entries of a transaction are appended in a single round trip.
+        let mut pipe = redis::pipe();
+        for change in changes.iter() {
+            let command = pipe
+                .cmd("XADD")
+                .arg(format!("{}{}", self.stream_prefix, change.entity));
+            if let Some(max_len) = self.max_len {
+                command.arg("MAXLEN").arg("~").arg(max_len);
+            }
+            command
+                .arg("*")
+                .arg("id")
+                .arg(&change.key)
+                .arg("payload")
+                .arg(&change.payload);
+            if let Some(schema) = &change.schema {
+                command.arg("schema").arg(schema.as_str());
+            }
+            command.ignore();
+        }
+
+        match pipe.query_async::<_, ()>(&mut self.connection).await {
+            Ok(_) => debug!(
+                "Appended {} changes to Redis for Indexer({}).",
+                changes.len(),
+                self.encoder.indexer_uid()
+            ),
+            Err(e) => error!(
+                "Failed to append {} changes to Redis for Indexer({}): {e}.",
+                changes.len(),
+                self.encoder.indexer_uid()
+            ),
+        }
+    }
+}
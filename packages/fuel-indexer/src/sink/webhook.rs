@@ -0,0 +1,151 @@
+use super::{EntityChange, Sink};
+use crate::{
+    http::{check_public_url, public_client_builder},
+    webhook::{post_signed, DELIVERY_TIMEOUT_SECS},
+    IndexerError, IndexerResult,
+};
+use async_trait::async_trait;
+use fuel_indexer_lib::{filter::Filter, manifest::WebhookSinkConfig};
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+use tracing::error;
+
+/// Body of a request posting an entity change to a webhook.
+#[derive(Debug, Serialize)]
+struct EntityNotification<'a> {
+    /// UID of the indexer that saved the entity.
+    indexer: &'a str,
+
+    /// Name of the entity's table.
+    entity: &'a str,
+
+    /// ID of the entity.
+    id: &'a str,
+
+    /// The entity's fields.
+    data: Value,
+}
+
+/// Posts the entity changes of an indexer that match its filters to a webhook.
+///
+/// Changes are posted one request at a time, in the order in which they were
+/// committed, and each request is retried before the change is dropped.
+pub(crate) struct WebhookSink {
+    /// UID of the indexer whose changes are posted.
+    indexer_uid: String,
+
+    /// URL to which changes are posted.
+    url: String,
+
+    /// Secret used to sign each request.
+    secret: Option<String>,
+
+    /// Filters of each entity, keyed by table name.
+    filters: Vec<(String, Filter)>,
+
+    /// Client used to post changes.
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    /// Create a sink for the given webhook.
+    pub(crate) fn new(
+        namespace: &str,
+        identifier: &str,
+        config: &WebhookSinkConfig,
+    ) -> IndexerResult<Self> {
+        let filters = config
+            .filters
+            .iter()
+            .map(|(entity, filter)| {
+                Filter::parse(filter)
+                    .map(|filter| (entity.to_lowercase(), filter))
+                    .map_err(|e| {
+                        IndexerError::SinkError(format!(
+                            "Invalid filter of entity '{entity}': {e}"
+                        ))
+                    })
+            })
+            .collect::<IndexerResult<Vec<_>>>()?;
+
+        // Webhooks are given by manifests, so they may only be public addresses.
+        check_public_url(&config.url)
+            .map_err(|e| IndexerError::SinkError(format!("Invalid webhook URL: {e}")))?;
+        let client = public_client_builder()
+            .timeout(Duration::from_secs(DELIVERY_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| {
+                IndexerError::SinkError(format!("Failed to create webhook client: {e}"))
+            })?;
+
+        Ok(Self {
+            indexer_uid: format!("{namespace}.{identifier}"),
+            url: config.url.clone(),
+            secret: config.secret.clone(),
+            filters,
+            client,
+        })
+    }
+
+    /// Whether the given change matches the filter of its entity, if any.
+    fn matches(&self, change: &EntityChange, data: &Value) -> bool {
+        match (
+            self.filters
+                .iter()
+                .find(|(entity, _)| entity.eq_ignore_ascii_case(&change.entity)),
+            data,
+        ) {
+            (Some((_, filter)), Value::Object(fields)) => filter.matches(fields),
+            _ => true,
+        }
+    }
+}
+
+#[async_trait]
+impl Sink for WebhookSink {
+    async fn publish(&mut self, changes: &[&EntityChange]) {
+        for change in changes {
+            let data = change.to_json();
+            if !self.matches(change, &data) {
+                continue;
+            }
+
+            let notification = EntityNotification {
+                indexer: &self.indexer_uid,
+                entity: &change.entity,
+                id: &change.id,
+                data,
+            };
+            let body = match serde_json::to_vec(&notification) {
+                Ok(body) => body,
+                Err(e) => {
+                    error!(
+                        "Failed to encode {} change of Indexer({}): {e}.",
+                        change.entity, self.indexer_uid
+                    );
+                    continue;
+                }
+            };
+
+            let description = format!(
+                "change of {} {} of Indexer({})",
+                change.entity, change.id, self.indexer_uid
+            );
+            if !post_signed(
+                &self.client,
+                &self.url,
+                self.secret.as_deref(),
+                &body,
+                &description,
+            )
+            .await
+            {
+                error!(
+                    "Dropped {description}, which could not be posted to {}.",
+                    self.url
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::ptr::NonNull;
+use wasmer::{
+    vm::{
+        MemoryError, MemoryStyle, TableStyle, VMMemory, VMMemoryDefinition, VMTable,
+        VMTableDefinition,
+    },
+    MemoryType, Pages, TableType, Tunables,
+};
+
+/// Number of bytes in a page of WASM memory.
+const WASM_PAGE_BYTES: u64 = 64 * 1024;
+
+/// Tunables that cap the memory of WASM modules at a maximum number of pages.
+///
+/// Memories are created with the cap as their maximum, so that `memory.grow` fails
+/// once a module reaches it rather than the memory being checked after the module's
+/// handlers return. Memory styles are left as the base tunables choose them, so that
+/// modules compile the same with and without a cap.
+pub(crate) struct LimitingTunables<T: Tunables> {
+    /// Maximum number of pages of a memory.
+    limit: Pages,
+
+    /// Tunables that memories and tables are created with.
+    base: T,
+}
+
+impl<T: Tunables> LimitingTunables<T> {
+    /// Create a new `LimitingTunables`, capping memories at the given number of
+    /// megabytes.
+    pub fn new(base: T, max_memory_mb: u64) -> Self {
+        let pages = max_memory_mb.saturating_mul(1024 * 1024) / WASM_PAGE_BYTES;
+        let limit =
+            Pages(u32::try_from(pages).unwrap_or(u32::MAX)).min(Pages::max_value());
+        Self { limit, base }
+    }
+
+    /// Return the given memory type with its maximum capped.
+    fn adjust_memory(&self, requested: &MemoryType) -> MemoryType {
+        let mut adjusted = *requested;
+        adjusted.maximum = Some(
+            requested
+                .maximum
+                .map_or(self.limit, |maximum| maximum.min(self.limit)),
+        );
+        adjusted
+    }
+
+    /// Check that a memory of the given type fits within the cap.
+    fn validate_memory(&self, ty: &MemoryType) -> Result<(), MemoryError> {
+        if ty.minimum > self.limit {
+            return Err(MemoryError::Generic(format!(
+                "memory of {} pages exceeds the limit of {} pages",
+                ty.minimum.0, self.limit.0
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<T: Tunables> Tunables for LimitingTunables<T> {
+    fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
+        self.base.memory_style(memory)
+    }
+
+    fn table_style(&self, table: &TableType) -> TableStyle {
+        self.base.table_style(table)
+    }
+
+    fn create_host_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+    ) -> Result<VMMemory, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        self.base.create_host_memory(&adjusted, style)
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<VMMemory, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+        self.base
+            .create_vm_memory(&adjusted, style, vm_definition_location)
+    }
+
+    fn create_host_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+    ) -> Result<VMTable, String> {
+        self.base.create_host_table(ty, style)
+    }
+
+    unsafe fn create_vm_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<VMTable, String> {
+        self.base.create_vm_table(ty, style, vm_definition_location)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasmer::{BaseTunables, Target};
+
+    #[test]
+    fn test_memories_are_capped_at_the_limit() {
+        let tunables =
+            LimitingTunables::new(BaseTunables::for_target(&Target::default()), 2);
+        assert_eq!(tunables.limit, Pages(32));
+
+        let unbounded = MemoryType::new(1, None, false);
+        assert_eq!(tunables.adjust_memory(&unbounded).maximum, Some(Pages(32)));
+
+        let smaller = MemoryType::new(1, Some(8), false);
+        assert_eq!(tunables.adjust_memory(&smaller).maximum, Some(Pages(8)));
+
+        let larger = MemoryType::new(1, Some(1000), false);
+        assert_eq!(tunables.adjust_memory(&larger).maximum, Some(Pages(32)));
+
+        assert!(tunables
+            .validate_memory(&MemoryType::new(64, None, false))
+            .is_err());
+    }
+}
@@ -0,0 +1,200 @@
+use fuel_indexer_lib::config::{WebhookConfig, WebhookEvent};
+use hmac::{Hmac, Mac, NewMac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+/// Header holding the HMAC-SHA256 signature of a notification's body.
+pub const SIGNATURE_HEADER: &str = "X-Fuel-Indexer-Signature";
+
+/// Number of times a notification is sent before giving up.
+const DELIVERY_ATTEMPTS: u32 = 3;
+
+/// Time after which a webhook that hasn't responded is considered failed.
+pub(crate) const DELIVERY_TIMEOUT_SECS: u64 = 10;
+
+/// Body of a notification sent to webhooks.
+#[derive(Debug, Serialize)]
+struct Notification<'a> {
+    /// The event that occurred.
+    event: WebhookEvent,
+
+    /// UID of the indexer the event occurred on.
+    indexer: &'a str,
+
+    /// Time at which the event occurred, as a UNIX timestamp.
+    timestamp: u64,
+
+    /// Height of the last block processed by the indexer, if any.
+    block_height: Option<u64>,
+
+    /// Error message, for `error` events.
+    message: Option<&'a str>,
+}
+
+/// Sends notifications of an indexer's events to the configured webhooks.
+///
+/// Notifications are sent in the background, so that a slow or unreachable
+/// webhook never holds up the executor.
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    /// UID of the indexer whose events are sent.
+    indexer_uid: String,
+
+    /// Webhooks to notify.
+    webhooks: Arc<Vec<WebhookConfig>>,
+
+    /// Client used to send notifications.
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    /// Create a new `WebhookNotifier` for the given indexer.
+    pub fn new(indexer_uid: &str, webhooks: &[WebhookConfig]) -> Self {
+        Self {
+            indexer_uid: indexer_uid.to_string(),
+            webhooks: Arc::new(webhooks.to_vec()),
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(DELIVERY_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Notify the webhooks that accept the given event.
+    pub fn notify(
+        &self,
+        event: WebhookEvent,
+        block_height: Option<u64>,
+        message: Option<&str>,
+    ) {
+        if !self.webhooks.iter().any(|webhook| webhook.accepts(event)) {
+            return;
+        }
+
+        let notification = Notification {
+            event,
+            indexer: &self.indexer_uid,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|now| now.as_secs())
+                .unwrap_or_default(),
+            block_height,
+            message,
+        };
+
+        let body = match serde_json::to_vec(&notification) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!("Failed to serialize webhook notification: {e}.");
+                return;
+            }
+        };
+
+        for webhook in self
+            .webhooks
+            .iter()
+            .filter(|webhook| webhook.accepts(event))
+        {
+            tokio::spawn(deliver(
+                self.client.clone(),
+                webhook.clone(),
+                body.clone(),
+                self.indexer_uid.clone(),
+                event,
+            ));
+        }
+    }
+}
+
+/// Post a notification to a webhook, retrying failed deliveries.
+async fn deliver(
+    client: reqwest::Client,
+    webhook: WebhookConfig,
+    body: Vec<u8>,
+    indexer_uid: String,
+    event: WebhookEvent,
+) {
+    let description = format!("{} event of Indexer({indexer_uid})", event.as_ref());
+    post_signed(
+        &client,
+        &webhook.url,
+        webhook.secret.as_deref(),
+        &body,
+        &description,
+    )
+    .await;
+}
+
+/// Post a JSON body to a URL, signed with the given secret if any, retrying
+/// failed deliveries. Returns whether the body was delivered.
+pub(crate) async fn post_signed(
+    client: &reqwest::Client,
+    url: &str,
+    secret: Option<&str>,
+    body: &[u8],
+    description: &str,
+) -> bool {
+    for attempt in 1..=DELIVERY_ATTEMPTS {
+        let mut request = client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_vec());
+
+        if let Some(secret) = secret {
+            request = request.header(SIGNATURE_HEADER, signature(secret, body));
+        }
+
+        match request.send().await.and_then(|res| res.error_for_status()) {
+            Ok(_) => {
+                debug!("Sent {description} to {url}.");
+                return true;
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to send {description} to {url} (attempt {attempt}/{DELIVERY_ATTEMPTS}): {e}."
+                );
+                if attempt < DELIVERY_ATTEMPTS {
+                    sleep(Duration::from_secs(attempt as u64)).await;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Sign the given body with the given secret, as `sha256=<hex digest>`.
+fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts keys of any length.");
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+
+    format!(
+        "sha256={}",
+        digest
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_matches_known_hmac_sha256() {
+        // Test case 2 of RFC 4231.
+        assert_eq!(
+            signature("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
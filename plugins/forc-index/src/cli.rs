@@ -2,9 +2,13 @@
 pub(crate) use crate::commands::{
     auth::Command as AuthCommand, build::Command as BuildCommand,
     check::Command as CheckCommand, deploy::Command as DeployCommand,
-    kill::Command as KillCommand, new::Command as NewCommand,
-    remove::Command as RemoveCommand, start::Command as StartCommand,
-    status::Command as StatusCommand,
+    dev::Command as DevCommand, import_subgraph::Command as ImportSubgraphCommand,
+    install::Command as InstallCommand, kill::Command as KillCommand,
+    manifest_schema::Command as ManifestSchemaCommand,
+    migrate::Command as MigrateCommand, new::Command as NewCommand,
+    remove::Command as RemoveCommand, rollback::Command as RollbackCommand,
+    start::Command as StartCommand, status::Command as StatusCommand,
+    versions::Command as VersionsCommand,
 };
 use clap::{Parser, Subcommand};
 use forc_postgres::{
@@ -33,6 +37,14 @@ pub enum ForcIndex {
     Postgres(ForcPostgresOpt),
     Kill(KillCommand),
     Status(StatusCommand),
+    ManifestSchema(ManifestSchemaCommand),
+    Versions(VersionsCommand),
+    Rollback(RollbackCommand),
+    Migrate(MigrateCommand),
+    Dev(DevCommand),
+    Snapshot(SnapshotCommand),
+    ImportSubgraph(ImportSubgraphCommand),
+    Install(InstallCommand),
 }
 
 pub async fn run_cli() -> Result<(), anyhow::Error> {
@@ -58,5 +70,17 @@ pub async fn run_cli() -> Result<(), anyhow::Error> {
         },
         ForcIndex::Kill(command) => crate::commands::kill::exec(command),
         ForcIndex::Status(command) => crate::commands::status::exec(command).await,
+        ForcIndex::ManifestSchema(command) => {
+            crate::commands::manifest_schema::exec(command)
+        }
+        ForcIndex::Versions(command) => crate::commands::versions::exec(command).await,
+        ForcIndex::Rollback(command) => crate::commands::rollback::exec(command).await,
+        ForcIndex::Migrate(command) => crate::commands::migrate::exec(command).await,
+        ForcIndex::Dev(command) => crate::commands::dev::exec(command).await,
+        ForcIndex::Snapshot(command) => crate::commands::snapshot::exec(command).await,
+        ForcIndex::ImportSubgraph(command) => {
+            crate::commands::import_subgraph::exec(command)
+        }
+        ForcIndex::Install(command) => crate::commands::install::exec(command).await,
     }
 }
@@ -14,6 +14,13 @@ pub struct Command {
     #[clap(long, default_value = ACCOUNT_INDEX, help = "Index of account to use for signing.")]
     pub account: String,
 
+    /// Address of the account, to sign in with a Sign-In with Fuel message rather than a bare nonce.
+    #[clap(
+        long,
+        help = "Address of the account, to sign in with a Sign-In with Fuel message rather than a bare nonce."
+    )]
+    pub address: Option<String>,
+
     /// Verbose output.
     #[clap(short, long, help = "Verbose output.")]
     pub verbose: bool,
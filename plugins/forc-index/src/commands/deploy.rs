@@ -49,6 +49,13 @@ pub struct Command {
     /// Do not build before deploying.
     #[clap(long, help = "Do not build before deploying.")]
     pub skip_build: bool,
+
+    /// Path to a file containing a hex-encoded ed25519 secret key used to sign the manifest and WASM module.
+    #[clap(
+        long,
+        help = "Path to a file containing a hex-encoded ed25519 secret key used to sign the manifest and WASM module."
+    )]
+    pub signing_key: Option<PathBuf>,
 }
 
 impl Default for Command {
@@ -63,6 +70,7 @@ impl Default for Command {
             locked: false,
             native: false,
             skip_build: false,
+            signing_key: None,
         }
     }
 }
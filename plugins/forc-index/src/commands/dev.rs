@@ -0,0 +1,44 @@
+use crate::{defaults, ops::forc_index_dev};
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Run an indexer project against a local indexer service, redeploying it on change.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Database type.
+    #[clap(long, default_value = "postgres", value_parser(["postgres"]), help = "Database type.")]
+    pub database: String,
+
+    /// Host of the Fuel node to index.
+    #[clap(long, default_value = defaults::FUEL_NODE_HOST, help = "Host of the Fuel node to index.")]
+    pub fuel_node_host: String,
+
+    /// Port of the Fuel node to index.
+    #[clap(long, default_value = defaults::FUEL_NODE_PORT, help = "Port of the Fuel node to index.")]
+    pub fuel_node_port: String,
+
+    /// Port on which the indexer service's web API listens.
+    #[clap(long, default_value = defaults::WEB_API_PORT, help = "Port on which the indexer service's web API listens.")]
+    pub web_api_port: String,
+
+    /// Do not open the GraphQL playground in a browser.
+    #[clap(long, help = "Do not open the GraphQL playground in a browser.")]
+    pub no_open: bool,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    forc_index_dev::init(command).await
+}
@@ -0,0 +1,37 @@
+use crate::ops::forc_index_import_subgraph;
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Create a new indexer project from a The Graph subgraph.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Path to the subgraph.yaml manifest of the subgraph.
+    #[clap(help = "Path to the subgraph.yaml manifest of the subgraph.")]
+    pub subgraph: PathBuf,
+
+    /// Path at which to create indexer.
+    #[clap(help = "Path at which to create indexer.")]
+    pub path: PathBuf,
+
+    /// Name of indexer.
+    #[clap(long, help = "Name of indexer.")]
+    pub name: Option<String>,
+
+    /// Namespace to which indexer belongs.
+    #[clap(long, help = "Namespace to which indexer belongs.")]
+    pub namespace: Option<String>,
+
+    /// Initialize an indexer with native execution enabled.
+    #[clap(long, help = "Initialize an indexer with native execution enabled.")]
+    pub native: bool,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub fn exec(command: Command) -> Result<()> {
+    forc_index_import_subgraph::init(command)?;
+    Ok(())
+}
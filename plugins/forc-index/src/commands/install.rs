@@ -0,0 +1,40 @@
+use crate::{defaults, ops::forc_index_install};
+use anyhow::Result;
+use clap::Parser;
+
+/// Install an indexer published to an indexer registry or to IPFS.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Indexer to install, as `namespace/name@version` or `ipfs://<CID>`.
+    #[clap(help = "Indexer to install, as `namespace/name@version` or `ipfs://<CID>`.")]
+    pub indexer: String,
+
+    /// URL of the indexer registry from which indexers are fetched by name.
+    #[clap(
+        long,
+        env = "FORC_INDEX_REGISTRY",
+        help = "URL of the indexer registry from which indexers are fetched by name."
+    )]
+    pub registry: Option<String>,
+
+    /// IPFS gateway from which indexers are fetched by CID.
+    #[clap(long, default_value = defaults::IPFS_GATEWAY, help = "IPFS gateway from which indexers are fetched by CID.")]
+    pub ipfs_gateway: String,
+
+    /// URL at which to deploy the indexer.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which to deploy the indexer.")]
+    pub url: String,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    forc_index_install::init(command).await?;
+    Ok(())
+}
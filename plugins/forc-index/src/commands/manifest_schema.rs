@@ -0,0 +1,15 @@
+use crate::ops::forc_index_manifest_schema;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Generate a JSON Schema for indexer manifests, for use by editors.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Path at which to write the JSON Schema. Printed to stdout if not specified.
+    #[clap(short, long, help = "Path at which to write the JSON Schema.")]
+    pub output: Option<PathBuf>,
+}
+
+pub fn exec(command: Command) -> anyhow::Result<()> {
+    forc_index_manifest_schema::init(command)
+}
@@ -0,0 +1,68 @@
+use crate::{cli::DeployCommand, defaults, ops::forc_index_migrate};
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Review the changes a new version of an indexer's schema makes to its tables.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[clap(subcommand)]
+    pub command: MigrateCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MigrateCommand {
+    /// Show the SQL that deploying the indexer's schema would execute.
+    Plan(MigrateArgs),
+
+    /// Show the tables and columns that deploying the indexer's schema would change.
+    Diff(MigrateArgs),
+
+    /// Deploy the indexer after reviewing its migration plan.
+    Apply(ApplyArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct MigrateArgs {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct ApplyArgs {
+    #[clap(flatten)]
+    pub deploy: DeployCommand,
+
+    /// Apply destructive changes without asking for confirmation.
+    #[clap(
+        short,
+        long,
+        help = "Apply destructive changes without asking for confirmation."
+    )]
+    pub yes: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    match command.command {
+        MigrateCommand::Plan(args) => forc_index_migrate::plan(args).await,
+        MigrateCommand::Diff(args) => forc_index_migrate::diff(args).await,
+        MigrateCommand::Apply(args) => forc_index_migrate::apply(args).await,
+    }
+}
@@ -2,8 +2,16 @@ pub mod auth;
 pub mod build;
 pub mod check;
 pub mod deploy;
+pub mod dev;
+pub mod import_subgraph;
+pub mod install;
 pub mod kill;
+pub mod manifest_schema;
+pub mod migrate;
 pub mod new;
 pub mod remove;
+pub mod rollback;
+pub mod snapshot;
 pub mod start;
 pub mod status;
+pub mod versions;
@@ -1,4 +1,4 @@
-use crate::ops::forc_index_new;
+use crate::{ops::forc_index_new, templates::Template};
 use anyhow::Result;
 use clap::Parser;
 use std::path::PathBuf;
@@ -21,6 +21,22 @@ pub struct Command {
     #[clap(long, help = "Initialize an indexer with native execution enabled.")]
     pub native: bool,
 
+    /// Template from which to create the indexer.
+    #[clap(
+        long,
+        arg_enum,
+        default_value = "default",
+        help = "Template from which to create the indexer."
+    )]
+    pub template: Template,
+
+    /// Path to the JSON ABI of the contract to index, required by contract templates.
+    #[clap(
+        long,
+        help = "Path to the JSON ABI of the contract to index, required by contract templates."
+    )]
+    pub abi: Option<PathBuf>,
+
     /// Resolve indexer asset filepaths using absolute paths.
     #[clap(long, help = "Resolve indexer asset filepaths using absolute paths.")]
     pub absolute_paths: bool,
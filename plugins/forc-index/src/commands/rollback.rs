@@ -0,0 +1,37 @@
+use crate::{defaults, ops::forc_index_rollback};
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Roll back an indexer to a previously deployed version.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Version to roll back to, as listed by `forc index versions`.
+    #[clap(help = "Version to roll back to, as listed by `forc index versions`.")]
+    pub version: i32,
+
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    forc_index_rollback::init(command).await?;
+    Ok(())
+}
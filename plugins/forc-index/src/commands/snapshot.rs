@@ -0,0 +1,74 @@
+use crate::{defaults, ops::forc_index_snapshot};
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Export an indexer's data to a snapshot, or import a snapshot into an indexer.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[clap(subcommand)]
+    pub command: SnapshotCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SnapshotCommand {
+    /// Export the indexer's data and latest checkpoint to a file.
+    Export(ExportArgs),
+
+    /// Replace the paused indexer's data and latest checkpoint with those of a snapshot.
+    Import(ImportArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct SnapshotArgs {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct ExportArgs {
+    #[clap(flatten)]
+    pub snapshot: SnapshotArgs,
+
+    /// File to write the snapshot to.
+    #[clap(
+        short,
+        long,
+        help = "File to write the snapshot to. Defaults to the name suggested by the indexer service."
+    )]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub struct ImportArgs {
+    #[clap(flatten)]
+    pub snapshot: SnapshotArgs,
+
+    /// Snapshot file to import.
+    #[clap(help = "Snapshot file to import.")]
+    pub file: PathBuf,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    match command.command {
+        SnapshotCommand::Export(args) => forc_index_snapshot::export(args).await,
+        SnapshotCommand::Import(args) => forc_index_snapshot::import(args).await,
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{defaults, ops::forc_index_status};
 use clap::Parser;
 
-/// Check the status of a registered indexer.
+/// Check the status of the registered indexers.
 #[derive(Debug, Parser)]
 pub struct Command {
     /// Authentication header value
@@ -12,6 +12,22 @@ pub struct Command {
     #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which to find indexer service.")]
     pub url: String,
 
+    /// Refresh the status continuously until interrupted.
+    #[clap(
+        short,
+        long,
+        help = "Refresh the status continuously until interrupted."
+    )]
+    pub watch: bool,
+
+    /// Number of seconds between refreshes with --watch.
+    #[clap(
+        long,
+        default_value = "5",
+        help = "Number of seconds between refreshes with --watch."
+    )]
+    pub interval: u64,
+
     /// Enable verbose logging.
     #[clap(short, long, help = "Enable verbose logging.")]
     pub verbose: bool,
@@ -1,3 +1,4 @@
+use crate::templates::Template;
 use fuel_indexer_lib::defaults;
 use std::path::PathBuf;
 
@@ -8,7 +9,10 @@ pub const INDEXER_BINARY_FILENAME: &str = "main.rs";
 pub const CARGO_CONFIG_DIR_NAME: &str = ".cargo";
 pub const CARGO_CONFIG_FILENAME: &str = "config";
 pub const INDEXER_SERVICE_HOST: &str = "http://127.0.0.1:29987";
+pub const IPFS_GATEWAY: &str = "https://ipfs.io";
 pub const WEB_API_PORT: &str = defaults::WEB_API_PORT;
+pub const FUEL_NODE_HOST: &str = defaults::FUEL_NODE_HOST;
+pub const FUEL_NODE_PORT: &str = defaults::FUEL_NODE_PORT;
 pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
 pub const MESSAGE_PADDING: usize = 55;
 pub const SUCCESS_EMOJI_PADDING: usize = 3;
@@ -62,6 +66,8 @@ pub fn default_indexer_manifest(
     indexer_name: &str,
     project_path: Option<&PathBuf>,
     is_native: bool,
+    abi_filename: Option<&str>,
+    start_block: Option<u64>,
 ) -> String {
     let schema_path = match project_path {
         Some(p) => p.join("schema").join(schema_filename),
@@ -81,6 +87,18 @@ pub fn default_indexer_manifest(
 
     let schema_path = schema_path.display();
 
+    let abi_path = match abi_filename {
+        Some(abi_filename) => match project_path {
+            Some(p) => p.join("abis").join(abi_filename).display().to_string(),
+            None => format!("abis/{abi_filename}"),
+        },
+        None => "~".to_string(),
+    };
+
+    let start_block = start_block
+        .map(|block| block.to_string())
+        .unwrap_or_else(|| "~".to_string());
+
     format!(
         r#"# A namespace is a logical grouping of declared names. Think of the namespace
 # as an organization identifier
@@ -91,10 +109,10 @@ identifier: {indexer_name}
 
 # The abi option is used to provide a link to the Sway JSON ABI that is generated when you
 # build your project.
-abi: ~
+abi: {abi_path}
 
 # The particular start block after which you'd like your indexer to start indexing events.
-start_block: ~
+start_block: {start_block}
 
 # The particular end block after which you'd like your indexer to stop indexing events.
 end_block: ~
@@ -132,35 +150,13 @@ pub fn default_indexer_lib(
     indexer_name: &str,
     manifest_filename: &str,
     project_path: Option<&PathBuf>,
+    template: Template,
 ) -> String {
-    let manifest_path = match project_path {
-        Some(p) => p.join(manifest_filename),
-        None => PathBuf::from(manifest_filename),
-    };
-
-    let manifest_path = manifest_path.display();
-
-    format!(
-        r#"extern crate alloc;
-use fuel_indexer_utils::prelude::*;
-
-#[indexer(manifest = "{manifest_path}")]
-pub mod {indexer_name}_index_mod {{
-
-    fn {indexer_name}_handler(block_data: BlockData) {{
-        info!("Processing Block#{{}}. (>'.')>", block_data.height);
-
-        let block_id = id8(block_data.id);
-        let block = Block{{ id: block_id, height: block_data.height, hash: block_data.id }};
-        block.save();
-
-        for transaction in block_data.transactions.iter() {{
-            let tx = Transaction{{ id: id8(transaction.id), block: block_data.id, hash: Bytes32::from(<[u8; 32]>::from(transaction.id)) }};
-            tx.save();
-        }}
-    }}
-}}
-"#
+    default_indexer_module(
+        indexer_name,
+        manifest_filename,
+        project_path,
+        &template.handlers(indexer_name, false),
     )
 }
 
@@ -168,6 +164,22 @@ pub fn default_indexer_binary(
     indexer_name: &str,
     manifest_filename: &str,
     project_path: Option<&PathBuf>,
+    template: Template,
+) -> String {
+    default_indexer_module(
+        indexer_name,
+        manifest_filename,
+        project_path,
+        &template.handlers(indexer_name, true),
+    )
+}
+
+/// Return the source of an indexer module with the given handlers.
+pub fn default_indexer_module(
+    indexer_name: &str,
+    manifest_filename: &str,
+    project_path: Option<&PathBuf>,
+    handlers: &str,
 ) -> String {
     let manifest_path = match project_path {
         Some(p) => p.join(manifest_filename),
@@ -183,19 +195,7 @@ use fuel_indexer_utils::prelude::*;
 #[indexer(manifest = "{manifest_path}")]
 pub mod {indexer_name}_index_mod {{
 
-    async fn {indexer_name}_handler(block_data: BlockData) {{
-        info!("Processing Block#{{}}. (>'.')>", block_data.height);
-
-        let block_id = id8(block_data.id);
-        let block = Block{{ id: block_id, height: block_data.height, hash: block_data.id }};
-        block.save().await;
-
-        for transaction in block_data.transactions.iter() {{
-            let tx = Transaction{{ id: id8(transaction.id), block: block_data.id, hash: Bytes32::from(<[u8; 32]>::from(transaction.id)) }};
-            tx.save().await;
-        }}
-    }}
-}}
+{handlers}}}
 "#
     )
 }
@@ -2,4 +2,6 @@ pub mod cli;
 pub mod commands;
 pub(crate) mod defaults;
 pub(crate) mod ops;
+pub(crate) mod subgraph;
+pub(crate) mod templates;
 pub(crate) mod utils;
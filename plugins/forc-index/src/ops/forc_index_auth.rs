@@ -11,6 +11,11 @@ struct NonceResponse {
     expiry: u64,
 }
 
+#[derive(Deserialize, Debug)]
+struct ChallengeResponse {
+    message: String,
+}
+
 #[derive(Deserialize, Debug)]
 struct SignatureResponse {
     token: Option<String>,
@@ -30,10 +35,14 @@ pub async fn init(command: AuthCommand) -> anyhow::Result<()> {
     let AuthCommand {
         url,
         account,
+        address,
         verbose,
     } = command;
 
-    let target = format!("{url}/api/auth/nonce");
+    let target = match &address {
+        Some(address) => format!("{url}/api/auth/challenge?address={address}"),
+        None => format!("{url}/api/auth/nonce"),
+    };
 
     let res = Client::new()
         .get(&target)
@@ -54,14 +63,20 @@ pub async fn init(command: AuthCommand) -> anyhow::Result<()> {
         return Ok(());
     }
 
-    let response: NonceResponse = res.json().await.unwrap();
+    // With an address, the service returns a message naming this service and the
+    // account, holding the nonce, rather than the bare nonce.
+    let message = if address.is_some() {
+        res.json::<ChallengeResponse>().await?.message
+    } else {
+        res.json::<NonceResponse>().await?.uid
+    };
 
     let signature = match Command::new("forc-wallet")
         .arg("sign")
         .arg("--account")
         .arg(&account)
         .arg("string")
-        .arg(&response.uid)
+        .arg(&message)
         .output()
     {
         Ok(o) => {
@@ -79,10 +94,7 @@ pub async fn init(command: AuthCommand) -> anyhow::Result<()> {
 
     let target = format!("{url}/api/auth/signature");
 
-    let body = SignatureRequest {
-        signature,
-        message: response.uid,
-    };
+    let body = SignatureRequest { signature, message };
 
     let res = Client::new()
         .post(&target)
@@ -1,5 +1,12 @@
-use crate::{cli::BuildCommand, defaults, utils::project_dir_info};
-use fuel_indexer_lib::manifest::{Manifest, Module};
+use crate::{
+    cli::BuildCommand, defaults, ops::utils::fetch_remote_abis, utils::project_dir_info,
+};
+use fuel_indexer_lib::{
+    graphql::{GraphQLSchema, ParsedGraphQLSchema},
+    manifest::{local_abi_path, Manifest, Module},
+    mapping::MappingModule,
+    ExecutionSource,
+};
 use indicatif::{ProgressBar, ProgressStyle};
 use serde::Deserialize;
 use std::{
@@ -50,6 +57,17 @@ pub fn init(command: BuildCommand) -> anyhow::Result<()> {
     let (root_dir, manifest, _index_name) =
         project_dir_info(path.as_ref(), manifest.as_ref())?;
 
+    let indexer_manifest_path = root_dir.join(manifest);
+    let mut manifest = Manifest::from_file(&indexer_manifest_path)?;
+    manifest.validate()?;
+    fetch_remote_abis(&manifest)?;
+    manifest.validate_files(&root_dir)?;
+
+    // Declarative indexers have no Rust code, only mappings to compile
+    if let Module::Declarative(module_path) = manifest.module() {
+        return build_declarative(&root_dir, &manifest, module_path);
+    }
+
     // Must be in the directory of the index being built
     let cargo_manifest_path = root_dir.join(defaults::CARGO_MANIFEST_FILE_NAME);
     if !cargo_manifest_path.exists() {
@@ -69,9 +87,6 @@ pub fn init(command: BuildCommand) -> anyhow::Result<()> {
     file.read_to_string(&mut content)?;
     let config: Config = toml::from_str(&content)?;
 
-    let indexer_manifest_path = root_dir.join(manifest);
-    let mut manifest = Manifest::from_file(&indexer_manifest_path)?;
-
     // Construct our build command
     //
     // https://doc.rust-lang.org/cargo/commands/cargo-build.html
@@ -210,3 +225,40 @@ pub fn init(command: BuildCommand) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Compile the mappings of a declarative indexer into the module named in its manifest.
+fn build_declarative(
+    root_dir: &Path,
+    manifest: &Manifest,
+    module_path: &str,
+) -> anyhow::Result<()> {
+    let abi = match manifest.abi() {
+        Some(abi) => std::fs::read_to_string(root_dir.join(local_abi_path(abi)))?,
+        None => anyhow::bail!("❌ Declarative indexers must specify exactly one `abi`."),
+    };
+
+    let schema = std::fs::read_to_string(root_dir.join(manifest.graphql_schema()))?;
+    let schema = ParsedGraphQLSchema::new(
+        manifest.namespace(),
+        manifest.identifier(),
+        ExecutionSource::Wasm,
+        Some(&GraphQLSchema::new(schema)),
+    )?;
+
+    let module = MappingModule::compile(manifest.mappings(), &abi, &schema)
+        .map_err(|e| anyhow::anyhow!("❌ Failed to compile mappings: {e}"))?;
+
+    let module_path = root_dir.join(module_path);
+    if let Some(parent) = module_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&module_path, module.to_bytes()?)?;
+
+    info!(
+        "✅ Compiled {} mapping(s) to {}.",
+        module.mappings.len(),
+        module_path.display()
+    );
+
+    Ok(())
+}
@@ -3,7 +3,8 @@ use crate::{
     commands::build,
     utils::{file_part, project_dir_info},
 };
-use fuel_indexer_lib::manifest::Manifest;
+use ed25519_dalek::{Signer, SigningKey};
+use fuel_indexer_lib::manifest::{Manifest, Module};
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::{
     header::{HeaderMap, AUTHORIZATION, CONNECTION},
@@ -28,6 +29,7 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
         native,
         verbose,
         skip_build,
+        signing_key,
     } = command;
 
     if !skip_build {
@@ -41,17 +43,22 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
         })?;
     }
 
-    let (_root_dir, manifest_path, _index_name) =
+    let (root_dir, manifest_path, _index_name) =
         project_dir_info(path.as_ref(), manifest.as_ref())?;
 
     let mut manifest = Manifest::from_file(&manifest_path)?;
 
     let path = path.unwrap_or(".".into());
 
-    let target_dir: std::path::PathBuf = {
-        let mut target = crate::ops::utils::cargo_target_dir(path.as_path()).unwrap();
-        target.pop();
-        target
+    // Declarative indexers have no cargo project, so their paths are relative to
+    // the project root.
+    let target_dir: std::path::PathBuf = match manifest.module() {
+        Module::Declarative(_) => root_dir,
+        _ => {
+            let mut target = crate::ops::utils::cargo_target_dir(path.as_path()).unwrap();
+            target.pop();
+            target
+        }
     };
 
     manifest.set_graphql_schema(
@@ -62,13 +69,31 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
             .to_string(),
     );
 
-    manifest.set_module(target_dir.join(manifest.module()).into());
+    let module = match manifest.module() {
+        Module::Declarative(module) => {
+            Module::Declarative(target_dir.join(module).display().to_string())
+        }
+        module => target_dir.join(module).into(),
+    };
+    manifest.set_module(module);
 
-    let form = Form::new()
+    let mut form = Form::new()
         .part("manifest", file_part(&manifest_path).await?)
         .part("schema", file_part(manifest.graphql_schema()).await?)
         .part("wasm", file_part(manifest.module().to_string()).await?);
 
+    if let Some(signing_key) = signing_key {
+        let key = read_signing_key(&signing_key)?;
+        let manifest_signature = key.sign(&std::fs::read(&manifest_path)?);
+        let wasm_signature = key.sign(&std::fs::read(manifest.module().to_string())?);
+        form = form
+            .text(
+                "manifest_signature",
+                hex::encode(manifest_signature.to_bytes()),
+            )
+            .text("wasm_signature", hex::encode(wasm_signature.to_bytes()));
+    }
+
     let target = format!(
         "{url}/api/index/{}/{}",
         manifest.namespace(),
@@ -146,3 +171,12 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Read a hex-encoded ed25519 secret key from the given file.
+fn read_signing_key(path: &Path) -> anyhow::Result<SigningKey> {
+    let key = hex::decode(std::fs::read_to_string(path)?.trim())?;
+    let key: [u8; 32] = key
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Signing key must be 32 bytes."))?;
+    Ok(SigningKey::from_bytes(&key))
+}
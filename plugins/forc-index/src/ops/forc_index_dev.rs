@@ -0,0 +1,258 @@
+use crate::{
+    cli::{DeployCommand, DevCommand},
+    commands::deploy,
+    utils::{find_executable, project_dir_info},
+};
+use fuel_indexer_lib::manifest::Manifest;
+use std::{
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    process::Stdio,
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    process::{Child, Command},
+    time::sleep,
+};
+use tracing::{error, info, warn};
+use walkdir::WalkDir;
+
+/// Time between checks of the project's files for changes.
+const WATCH_INTERVAL_MILLIS: u64 = 1000;
+
+/// Number of times the indexer service's health is checked before giving up.
+const SERVICE_READY_ATTEMPTS: u64 = 180;
+
+pub async fn init(command: DevCommand) -> anyhow::Result<()> {
+    let DevCommand {
+        manifest,
+        path,
+        database,
+        fuel_node_host,
+        fuel_node_port,
+        web_api_port,
+        no_open,
+        verbose,
+    } = command;
+
+    let (root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+    let indexer = Manifest::from_file(&manifest_path)?;
+
+    let url = format!("http://localhost:{web_api_port}");
+
+    // Children are killed when they're dropped, so that both the node and the
+    // service stop along with this command.
+    let node = start_fuel_node(&fuel_node_host, &fuel_node_port, verbose)?;
+    let local_fuel_node =
+        node.is_none() && !is_listening(&fuel_node_host, &fuel_node_port);
+
+    let mut service = Command::new("fuel-indexer");
+    service
+        .arg("run")
+        .arg("--fuel-node-host")
+        .arg(&fuel_node_host)
+        .arg("--fuel-node-port")
+        .arg(&fuel_node_port)
+        .arg("--web-api-port")
+        .arg(&web_api_port)
+        .arg("--run-migrations")
+        .arg("--replace-indexer")
+        .kill_on_drop(true);
+
+    match database.as_ref() {
+        "postgres" => {
+            service.arg("--embedded-database");
+        }
+        _ => unreachable!("'postgres' is currently the only supported database option."),
+    }
+
+    if local_fuel_node {
+        // Services built with the `fuel-core-lib` feature can run a node themselves.
+        warn!("No Fuel node at {fuel_node_host}:{fuel_node_port} and no fuel-core executable found. Asking the indexer service to start a local node.");
+        service.arg("--local-fuel-node");
+    }
+
+    if verbose {
+        service.arg("--verbose");
+        info!("{service:?}");
+    } else {
+        service.stdout(Stdio::null());
+    }
+
+    let mut service = service
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("❌ Failed to start fuel-indexer: {e}."))?;
+
+    info!("⏳ Waiting for the indexer service at {url}...");
+    wait_for_service(&url, &mut service).await?;
+
+    deploy_indexer(&url, &root_dir, manifest.as_ref(), verbose).await;
+
+    let playground = format!(
+        "{url}/api/playground/{}/{}",
+        indexer.namespace(),
+        indexer.identifier()
+    );
+    info!("\n✨ ✨ GraphQL Playground at: {playground}");
+    if !no_open {
+        open_in_browser(&playground);
+    }
+
+    info!(
+        "\n👀 Watching {} for changes. Press Ctrl-C to stop.",
+        root_dir.display()
+    );
+
+    let mut last_modified = latest_modification(&root_dir);
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                info!("\nStopping the indexer service...");
+                break;
+            }
+            status = service.wait() => {
+                error!("❌ The indexer service exited: {status:?}.");
+                break;
+            }
+            _ = sleep(Duration::from_millis(WATCH_INTERVAL_MILLIS)) => {
+                let modified = latest_modification(&root_dir);
+                if modified > last_modified {
+                    last_modified = modified;
+                    info!("\n🔁 Change detected. Rebuilding and redeploying...");
+                    deploy_indexer(&url, &root_dir, manifest.as_ref(), verbose).await;
+                    // The build itself may touch files in the project.
+                    last_modified = latest_modification(&root_dir).max(last_modified);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether something is listening at the given host and port.
+fn is_listening(host: &str, port: &str) -> bool {
+    format!("{host}:{port}")
+        .to_socket_addrs()
+        .map(|mut addrs| {
+            addrs.any(|addr| {
+                TcpStream::connect_timeout(&addr, Duration::from_secs(1)).is_ok()
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Start an in-memory `fuel-core` node, unless one is already running at the given
+/// host and port, or no `fuel-core` executable can be found.
+fn start_fuel_node(
+    host: &str,
+    port: &str,
+    verbose: bool,
+) -> anyhow::Result<Option<Child>> {
+    if is_listening(host, port) {
+        info!("🔗 Using the Fuel node at {host}:{port}.");
+        return Ok(None);
+    }
+
+    let (_, path) = find_executable("fuel-core");
+    let path = match path {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    info!("🚀 Starting a local Fuel node at {host}:{port}...");
+    let mut node = Command::new(path);
+    node.arg("run")
+        .arg("--ip")
+        .arg("127.0.0.1")
+        .arg("--port")
+        .arg(port)
+        .arg("--db-type")
+        .arg("in-memory")
+        .kill_on_drop(true);
+
+    if !verbose {
+        node.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+
+    Ok(Some(node.spawn()?))
+}
+
+/// Wait until the indexer service at the given URL is healthy.
+async fn wait_for_service(url: &str, service: &mut Child) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+    let health = format!("{url}/api/health");
+
+    for _ in 0..SERVICE_READY_ATTEMPTS {
+        if let Some(status) = service.try_wait()? {
+            anyhow::bail!("❌ The indexer service exited: {status}.");
+        }
+
+        if let Ok(res) = client.get(&health).send().await {
+            if res.status().is_success() {
+                return Ok(());
+            }
+        }
+
+        sleep(Duration::from_secs(1)).await;
+    }
+
+    anyhow::bail!("❌ Timed out waiting for the indexer service at {url}.")
+}
+
+/// Build the project and deploy it to the indexer service, logging any failure,
+/// so that the next change can be deployed.
+async fn deploy_indexer(
+    url: &str,
+    root_dir: &Path,
+    manifest: Option<&String>,
+    verbose: bool,
+) {
+    let command = DeployCommand {
+        url: url.to_string(),
+        manifest: manifest.cloned(),
+        path: Some(root_dir.to_path_buf()),
+        auth: None,
+        verbose,
+        ..Default::default()
+    };
+
+    if let Err(e) = deploy::exec(command).await {
+        error!("❌ Failed to deploy indexer: {e}");
+    }
+}
+
+/// Return the latest modification time of the project's files, ignoring build
+/// artifacts and hidden files.
+fn latest_modification(root_dir: &Path) -> Option<SystemTime> {
+    WalkDir::new(root_dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0 || (name != "target" && !name.starts_with('.'))
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .max()
+}
+
+/// Open the given URL in the default browser, if possible.
+fn open_in_browser(url: &str) {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    if std::process::Command::new(opener)
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .is_err()
+    {
+        warn!("Could not open a browser. Visit {url} to use the playground.");
+    }
+}
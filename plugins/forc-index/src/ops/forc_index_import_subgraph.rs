@@ -0,0 +1,141 @@
+use crate::{cli::ImportSubgraphCommand, defaults, subgraph::SubgraphManifest, utils::*};
+use anyhow::Context;
+use forc_util::{kebab_to_snake_case, validate_name};
+use std::{fs, path::Path};
+use tracing::{info, warn};
+
+pub fn init(command: ImportSubgraphCommand) -> anyhow::Result<()> {
+    let ImportSubgraphCommand {
+        subgraph,
+        path: project_dir,
+        name,
+        namespace,
+        native,
+        verbose,
+    } = command;
+
+    let manifest = fs::read_to_string(&subgraph).with_context(|| {
+        format!(
+            "❌ Failed to read subgraph manifest at {}.",
+            subgraph.display()
+        )
+    })?;
+    let manifest: SubgraphManifest = serde_yaml::from_str(&manifest)
+        .context("❌ Failed to parse subgraph manifest.")?;
+
+    // The schema's path is relative to the subgraph manifest.
+    let schema_path = subgraph
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(&manifest.schema.file);
+    let schema = fs::read_to_string(&schema_path).with_context(|| {
+        format!(
+            "❌ Failed to read subgraph schema at {}.",
+            schema_path.display()
+        )
+    })?;
+    let (schema, schema_warnings) = crate::subgraph::convert_schema(&schema)
+        .context("❌ Failed to parse subgraph schema.")?;
+
+    if project_dir
+        .join(defaults::CARGO_MANIFEST_FILE_NAME)
+        .exists()
+    {
+        anyhow::bail!(
+            "❌ '{}' already includes a Cargo.toml file.",
+            project_dir.display()
+        );
+    }
+
+    let project_name = match name {
+        Some(name) => name,
+        None => project_dir
+            .file_stem()
+            .context("❌ Failed to infer project name from directory name.")?
+            .to_string_lossy()
+            .into_owned(),
+    };
+
+    // Indexer expects underscores not dashes
+    let project_name = kebab_to_snake_case(&project_name);
+
+    validate_name(&project_name, "project name")?;
+
+    let namespace = namespace.unwrap_or_else(whoami::username);
+
+    fs::create_dir_all(project_dir.join("src"))?;
+
+    let default_toml = if native {
+        defaults::default_native_indexer_cargo_toml(&project_name)
+    } else {
+        defaults::default_indexer_cargo_toml(&project_name)
+    };
+    fs::write(
+        project_dir.join(defaults::CARGO_MANIFEST_FILE_NAME),
+        default_toml,
+    )?;
+
+    let manifest_filename = default_manifest_filename(&project_name);
+    let schema_filename = default_schema_filename(&project_name);
+
+    fs::write(
+        project_dir.join(&manifest_filename),
+        format!(
+            "# Imported from the subgraph at {} with `forc index import-subgraph`.\n\n{}",
+            subgraph.display(),
+            defaults::default_indexer_manifest(
+                &namespace,
+                &schema_filename,
+                &project_name,
+                None,
+                native,
+                None,
+                manifest.start_block(),
+            )
+        ),
+    )?;
+
+    fs::create_dir_all(project_dir.join("schema"))?;
+    fs::write(project_dir.join("schema").join(schema_filename), schema)?;
+
+    let filename = if native {
+        defaults::INDEXER_BINARY_FILENAME
+    } else {
+        defaults::INDEXER_LIB_FILENAME
+    };
+    fs::write(
+        project_dir.join("src").join(filename),
+        defaults::default_indexer_module(
+            &project_name,
+            &manifest_filename,
+            None,
+            &manifest.handlers(native),
+        ),
+    )?;
+
+    if !native {
+        fs::create_dir_all(project_dir.join(defaults::CARGO_CONFIG_DIR_NAME))?;
+        fs::write(
+            project_dir
+                .join(defaults::CARGO_CONFIG_DIR_NAME)
+                .join(defaults::CARGO_CONFIG_FILENAME),
+            defaults::default_cargo_config(),
+        )?;
+    }
+
+    for warning in schema_warnings.iter().chain(manifest.warnings().iter()) {
+        warn!("⚠️  {warning}");
+    }
+
+    if verbose {
+        info!(
+            "\n✅ Successfully imported subgraph {} into indexer {project_name} at {}",
+            subgraph.display(),
+            project_dir.display()
+        );
+    } else {
+        info!("\n✅ Successfully imported subgraph");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use crate::cli::InstallCommand;
+use fuel_indexer_lib::manifest::Manifest;
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION},
+    multipart::{Form, Part},
+    Client, StatusCode,
+};
+use serde_json::{to_string_pretty, value::Value, Map};
+use tracing::{error, info};
+
+/// Version installed when none is given.
+const LATEST_VERSION: &str = "latest";
+
+/// File names of the assets of a published indexer, relative to its base URL.
+const MANIFEST_FILE: &str = "manifest.yaml";
+const SCHEMA_FILE: &str = "schema.graphql";
+const WASM_FILE: &str = "indexer.wasm";
+
+/// An indexer to install.
+enum Source {
+    /// An indexer published to a registry, by name.
+    Registry {
+        namespace: String,
+        name: String,
+        version: String,
+    },
+
+    /// An indexer published to IPFS, as a directory with the given CID.
+    Ipfs(String),
+}
+
+impl Source {
+    /// Parse `namespace/name@version`, where the version is optional, or `ipfs://<CID>`.
+    fn parse(indexer: &str) -> anyhow::Result<Self> {
+        if let Some(cid) = indexer.strip_prefix("ipfs://") {
+            let cid = cid.trim_matches('/');
+            if cid.is_empty() || cid.contains('/') {
+                anyhow::bail!("❌ Invalid IPFS CID '{cid}'.");
+            }
+            return Ok(Self::Ipfs(cid.to_string()));
+        }
+
+        let (name, version) = match indexer.split_once('@') {
+            Some((name, version)) => (name, version),
+            None => (indexer, LATEST_VERSION),
+        };
+        match name.split_once('/') {
+            Some((namespace, name))
+                if !namespace.is_empty()
+                    && !name.is_empty()
+                    && !name.contains('/')
+                    && !version.is_empty() =>
+            {
+                Ok(Self::Registry {
+                    namespace: namespace.to_string(),
+                    name: name.to_string(),
+                    version: version.to_string(),
+                })
+            }
+            _ => anyhow::bail!(
+                "❌ Invalid indexer '{indexer}'. Expected `namespace/name@version` or `ipfs://<CID>`."
+            ),
+        }
+    }
+
+    /// Return the URL under which the assets of the indexer are published.
+    fn base_url(
+        &self,
+        registry: Option<&str>,
+        ipfs_gateway: &str,
+    ) -> anyhow::Result<String> {
+        match self {
+            Self::Registry {
+                namespace,
+                name,
+                version,
+            } => {
+                let registry = registry.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "❌ No registry given. Use --registry or set FORC_INDEX_REGISTRY."
+                    )
+                })?;
+                Ok(format!(
+                    "{}/{namespace}/{name}/{version}",
+                    registry.trim_end_matches('/')
+                ))
+            }
+            Self::Ipfs(cid) => {
+                Ok(format!("{}/ipfs/{cid}", ipfs_gateway.trim_end_matches('/')))
+            }
+        }
+    }
+}
+
+pub async fn init(command: InstallCommand) -> anyhow::Result<()> {
+    let InstallCommand {
+        indexer,
+        registry,
+        ipfs_gateway,
+        url,
+        auth,
+        verbose,
+    } = command;
+
+    let source = Source::parse(&indexer)?;
+    let base_url = source.base_url(registry.as_deref(), &ipfs_gateway)?;
+
+    info!("\n📦 Fetching indexer '{indexer}'.");
+
+    let client = Client::new();
+    let manifest_bytes = fetch(&client, &base_url, MANIFEST_FILE, verbose).await?;
+    let schema = fetch(&client, &base_url, SCHEMA_FILE, verbose).await?;
+    let wasm = fetch(&client, &base_url, WASM_FILE, verbose).await?;
+
+    let manifest = Manifest::try_from(&manifest_bytes)?;
+    manifest.validate()?;
+
+    // A registry can't serve an indexer under someone else's name.
+    if let Source::Registry {
+        namespace, name, ..
+    } = &source
+    {
+        if manifest.namespace() != namespace || manifest.identifier() != name {
+            anyhow::bail!(
+                "❌ Registry returned the manifest of '{}' for '{namespace}/{name}'.",
+                manifest.uid()
+            );
+        }
+    }
+
+    let form = Form::new()
+        .part(
+            "manifest",
+            Part::bytes(manifest_bytes).file_name(MANIFEST_FILE),
+        )
+        .part("schema", Part::bytes(schema).file_name(SCHEMA_FILE))
+        .part("wasm", Part::bytes(wasm).file_name(WASM_FILE));
+
+    let target = format!(
+        "{url}/api/index/{}/{}",
+        manifest.namespace(),
+        manifest.identifier()
+    );
+
+    let mut headers = HeaderMap::new();
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    if verbose {
+        info!("\n🚀 Deploying indexer '{}' to {target}", manifest.uid());
+    } else {
+        info!("\n🚀 Deploying indexer '{}'.", manifest.uid());
+    }
+
+    let res = client
+        .post(&target)
+        .multipart(form)
+        .headers(headers)
+        .send()
+        .await
+        .expect("Failed to deploy indexer.");
+
+    let status = res.status();
+    let res_json = res
+        .json::<Map<String, Value>>()
+        .await
+        .expect("Failed to read JSON response.");
+
+    if status != StatusCode::OK {
+        if verbose {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+        }
+        info!("\n{}", to_string_pretty(&res_json)?);
+
+        return Ok(());
+    }
+
+    if verbose {
+        info!("\n{}", to_string_pretty(&res_json)?);
+    }
+
+    info!(
+        "\n✅ Successfully installed indexer '{}'.\n",
+        manifest.uid()
+    );
+
+    Ok(())
+}
+
+/// Fetch one of the assets of a published indexer.
+async fn fetch(
+    client: &Client,
+    base_url: &str,
+    file: &str,
+    verbose: bool,
+) -> anyhow::Result<Vec<u8>> {
+    let asset_url = format!("{base_url}/{file}");
+    if verbose {
+        info!("Fetching {asset_url}");
+    }
+
+    let res = client.get(&asset_url).send().await?;
+    if !res.status().is_success() {
+        anyhow::bail!(
+            "❌ {asset_url} returned a non-200 response code: {:?}",
+            res.status()
+        );
+    }
+
+    Ok(res.bytes().await?.to_vec())
+}
@@ -0,0 +1,20 @@
+use crate::cli::ManifestSchemaCommand;
+use fuel_indexer_lib::manifest::Manifest;
+use std::fs;
+use tracing::info;
+
+pub fn init(command: ManifestSchemaCommand) -> anyhow::Result<()> {
+    let ManifestSchemaCommand { output } = command;
+
+    let schema = serde_json::to_string_pretty(&Manifest::json_schema())?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, schema)?;
+            info!("✅ Wrote manifest JSON Schema to {}.", path.display());
+        }
+        None => println!("{schema}"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,256 @@
+use crate::{
+    commands::migrate::{ApplyArgs, MigrateArgs},
+    ops::forc_index_deploy,
+    utils::{file_part, project_dir_info},
+};
+use fuel_indexer_lib::manifest::Manifest;
+use owo_colors::OwoColorize;
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION},
+    multipart::Form,
+    Client, StatusCode,
+};
+use serde::Deserialize;
+use serde_json::{to_string_pretty, value::Value, Map};
+use std::io::{self, Write};
+use tracing::{error, info};
+
+/// A change made to an indexer's tables, as returned by the indexer service.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+enum SchemaChange {
+    AddTable {
+        table: String,
+    },
+    DropTable {
+        table: String,
+    },
+    AddColumn {
+        table: String,
+        column: String,
+    },
+    DropColumn {
+        table: String,
+        column: String,
+    },
+    AlterColumn {
+        table: String,
+        column: String,
+        from: String,
+        to: String,
+    },
+}
+
+/// The SQL that deploying an indexer's schema would execute, as returned by the
+/// indexer service.
+#[derive(Debug, Deserialize)]
+struct MigrationPlan {
+    current_version: Option<String>,
+    version: String,
+    changes: Vec<SchemaChange>,
+    destructive: bool,
+    statements: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MigrationPlanResponse {
+    plan: MigrationPlan,
+}
+
+/// Print the SQL that deploying the indexer's schema would execute.
+pub async fn plan(args: MigrateArgs) -> anyhow::Result<()> {
+    if let Some(plan) = fetch_plan(&args).await? {
+        print_summary(&plan);
+        println!();
+        for statement in plan.statements.iter() {
+            println!("{statement}");
+        }
+        print_warning(&plan);
+    }
+    Ok(())
+}
+
+/// Print the changes that deploying the indexer's schema would make to its tables.
+pub async fn diff(args: MigrateArgs) -> anyhow::Result<()> {
+    if let Some(plan) = fetch_plan(&args).await? {
+        print_summary(&plan);
+        println!();
+        print_changes(&plan);
+        print_warning(&plan);
+    }
+    Ok(())
+}
+
+/// Deploy the indexer once its migration plan has been reviewed.
+pub async fn apply(ApplyArgs { deploy, yes }: ApplyArgs) -> anyhow::Result<()> {
+    let args = MigrateArgs {
+        url: deploy.url.clone(),
+        manifest: deploy.manifest.clone(),
+        path: deploy.path.clone(),
+        auth: deploy.auth.clone(),
+        verbose: deploy.verbose,
+    };
+
+    let plan = match fetch_plan(&args).await? {
+        Some(plan) => plan,
+        None => return Ok(()),
+    };
+
+    print_summary(&plan);
+    println!();
+    print_changes(&plan);
+    print_warning(&plan);
+
+    if plan.destructive && !yes && !confirm("Apply these changes?")? {
+        info!("\nMigration cancelled.");
+        return Ok(());
+    }
+
+    forc_index_deploy::init(deploy).await
+}
+
+/// Fetch the migration plan of the indexer's schema from the indexer service.
+///
+/// Returns `None` if the service rejected the request.
+async fn fetch_plan(
+    MigrateArgs {
+        url,
+        manifest,
+        path,
+        auth,
+        verbose,
+    }: &MigrateArgs,
+) -> anyhow::Result<Option<MigrationPlan>> {
+    let (root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest = Manifest::from_file(manifest_path.as_path())?;
+    let schema_path = root_dir.join(manifest.graphql_schema());
+
+    let target = format!(
+        "{url}/api/index/{}/{}/migrate/plan",
+        manifest.namespace(),
+        manifest.identifier()
+    );
+
+    if *verbose {
+        info!(
+            "Planning migration of indexer '{}' to {} at {target}.",
+            manifest.uid(),
+            schema_path.display()
+        );
+    }
+
+    let mut headers = HeaderMap::new();
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    let form = Form::new().part("schema", file_part(&schema_path).await?);
+
+    let res = Client::new()
+        .post(&target)
+        .multipart(form)
+        .headers(headers)
+        .send()
+        .await
+        .expect("Failed to plan migration.");
+
+    let status = res.status();
+
+    if status != StatusCode::OK {
+        let res_json = res
+            .json::<Map<String, Value>>()
+            .await
+            .expect("Failed to read JSON response.");
+
+        if *verbose {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+        }
+        info!("\n{}", to_string_pretty(&res_json)?);
+
+        return Ok(None);
+    }
+
+    let res_json = res
+        .json::<MigrationPlanResponse>()
+        .await
+        .expect("Failed to read JSON response.");
+
+    Ok(Some(res_json.plan))
+}
+
+fn print_summary(plan: &MigrationPlan) {
+    match &plan.current_version {
+        Some(current) if *current == plan.version => {
+            println!("Schema version {} is already deployed.", plan.version)
+        }
+        Some(current) => {
+            println!("Migrating schema version {current} to {}.", plan.version)
+        }
+        None => println!(
+            "Indexer is not deployed. Creating schema version {}.",
+            plan.version
+        ),
+    }
+}
+
+fn print_changes(plan: &MigrationPlan) {
+    if plan.changes.is_empty() {
+        println!("No changes to tables.");
+        return;
+    }
+
+    for change in plan.changes.iter() {
+        let line = match change {
+            SchemaChange::AddTable { table } => {
+                format!("+ table {table}").green().to_string()
+            }
+            SchemaChange::DropTable { table } => {
+                format!("- table {table}").red().to_string()
+            }
+            SchemaChange::AddColumn { table, column } => {
+                format!("+ column {table}.{column}").green().to_string()
+            }
+            SchemaChange::DropColumn { table, column } => {
+                format!("- column {table}.{column}").red().to_string()
+            }
+            SchemaChange::AlterColumn {
+                table,
+                column,
+                from,
+                to,
+            } => format!("~ column {table}.{column}: {from} -> {to}")
+                .yellow()
+                .to_string(),
+        };
+        println!("{line}");
+    }
+}
+
+fn print_warning(plan: &MigrationPlan) {
+    if plan.current_version.is_some() {
+        println!(
+            "\nDeploying replaces the indexer's tables, which are rebuilt as it reindexes from its start block."
+        );
+    }
+    if plan.destructive {
+        println!(
+            "{}",
+            "⚠️  This migration drops or alters existing tables or columns."
+                .red()
+                .bold()
+        );
+    }
+}
+
+/// Ask the user to confirm an action on the terminal.
+fn confirm(prompt: &str) -> anyhow::Result<bool> {
+    print!("\n{prompt} [y/N] ");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
@@ -1,8 +1,9 @@
-use crate::{cli::NewCommand, defaults, utils::*};
+use crate::{cli::NewCommand, defaults, templates::Template, utils::*};
 use anyhow::Context;
+use clap::ArgEnum;
 use forc_util::{kebab_to_snake_case, validate_name};
 use std::{fs, path::Path};
-use tracing::info;
+use tracing::{info, warn};
 
 fn print_welcome_message() {
     let read_the_docs = format!(
@@ -69,10 +70,19 @@ pub fn create_indexer(command: NewCommand) -> anyhow::Result<()> {
         path: project_dir,
         namespace,
         native,
+        template,
+        abi,
         absolute_paths,
         verbose,
     } = command;
 
+    if template.requires_abi() && abi.is_none() {
+        anyhow::bail!(
+            "❌ The '{}' template indexes a contract, so its ABI must be given with --abi.",
+            template.to_possible_value().map(|v| v.get_name()).unwrap_or_default()
+        );
+    }
+
     std::fs::create_dir_all(&project_dir)?;
 
     if project_dir
@@ -134,6 +144,29 @@ pub fn create_indexer(command: NewCommand) -> anyhow::Result<()> {
         whoami::username()
     };
 
+    // Copy the contract's ABI into the project
+    let abi_filename = match &abi {
+        Some(abi) => {
+            let abi_filename = abi
+                .file_name()
+                .context("❌ Failed to infer ABI filename from path.")?
+                .to_string_lossy()
+                .into_owned();
+            let abi_json = fs::read_to_string(abi).with_context(|| {
+                format!("❌ Failed to read ABI at {}.", abi.display())
+            })?;
+            warn_missing_abi_events(&abi_json, template)?;
+
+            fs::create_dir_all(Path::new(&project_dir).join("abis"))?;
+            fs::write(
+                Path::new(&project_dir).join("abis").join(&abi_filename),
+                abi_json,
+            )?;
+            Some(abi_filename)
+        }
+        None => None,
+    };
+
     let manifest_filename = default_manifest_filename(&project_name);
     let schema_filename = default_schema_filename(&project_name);
 
@@ -146,6 +179,8 @@ pub fn create_indexer(command: NewCommand) -> anyhow::Result<()> {
             &project_name,
             proj_abspath.as_ref(),
             native,
+            abi_filename.as_deref(),
+            None,
         ),
     )?;
 
@@ -153,7 +188,7 @@ pub fn create_indexer(command: NewCommand) -> anyhow::Result<()> {
     fs::create_dir_all(Path::new(&project_dir).join("schema"))?;
     fs::write(
         Path::new(&project_dir).join("schema").join(schema_filename),
-        defaults::default_indexer_schema(),
+        template.schema(),
     )?;
 
     // What content are we writing?
@@ -164,6 +199,7 @@ pub fn create_indexer(command: NewCommand) -> anyhow::Result<()> {
                 &project_name,
                 &manifest_filename,
                 proj_abspath.as_ref(),
+                template,
             ),
         )
     } else {
@@ -173,6 +209,7 @@ pub fn create_indexer(command: NewCommand) -> anyhow::Result<()> {
                 &project_name,
                 &manifest_filename,
                 proj_abspath.as_ref(),
+                template,
             ),
         )
     };
@@ -201,6 +238,33 @@ pub fn create_indexer(command: NewCommand) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Warn about the events expected by the given template that the given ABI
+/// doesn't declare, since the template's handlers won't compile without them.
+fn warn_missing_abi_events(abi_json: &str, template: Template) -> anyhow::Result<()> {
+    let abi: serde_json::Value =
+        serde_json::from_str(abi_json).context("❌ Failed to parse ABI as JSON.")?;
+
+    let declared = abi["types"]
+        .as_array()
+        .map(|types| {
+            types
+                .iter()
+                .filter_map(|t| t["type"].as_str())
+                .filter_map(|t| t.strip_prefix("struct "))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for event in template.abi_events() {
+        if !declared.iter().any(|name| name == event) {
+            warn!("⚠️  ABI does not declare a '{event}' struct, which the template's handlers expect.");
+        }
+    }
+
+    Ok(())
+}
+
 /// Execute the command for `forc_index_new`.
 pub fn init(command: NewCommand) -> anyhow::Result<()> {
     create_indexer(command)?;
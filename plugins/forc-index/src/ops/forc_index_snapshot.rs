@@ -0,0 +1,136 @@
+use crate::{
+    commands::snapshot::{ExportArgs, ImportArgs, SnapshotArgs},
+    utils::project_dir_info,
+};
+use fuel_indexer_lib::manifest::Manifest;
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_TYPE},
+    Client, Response, StatusCode,
+};
+use serde_json::{to_string_pretty, value::Value, Map};
+use std::path::PathBuf;
+use tracing::{error, info};
+
+/// Export the indexer's data and latest checkpoint to a file.
+pub async fn export(ExportArgs { snapshot, output }: ExportArgs) -> anyhow::Result<()> {
+    let (target, headers) = snapshot_target(&snapshot)?;
+
+    if snapshot.verbose {
+        info!("Exporting snapshot from {target}.");
+    }
+
+    let res = Client::new()
+        .get(&target)
+        .headers(headers)
+        .send()
+        .await
+        .expect("Failed to export snapshot.");
+
+    if res.status() != StatusCode::OK {
+        return print_error(res, &target, snapshot.verbose).await;
+    }
+
+    let output = output.unwrap_or_else(|| {
+        let filename = res
+            .headers()
+            .get(CONTENT_DISPOSITION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split("filename=").nth(1))
+            .map(|filename| filename.trim_matches('"').to_string())
+            .unwrap_or_else(|| "snapshot.sql".to_string());
+        PathBuf::from(filename)
+    });
+
+    let bytes = res.bytes().await?;
+    std::fs::write(&output, &bytes)?;
+
+    info!(
+        "\n✅ Exported snapshot to {} ({} bytes).",
+        output.display(),
+        bytes.len()
+    );
+
+    Ok(())
+}
+
+/// Replace the paused indexer's data and latest checkpoint with those of a snapshot.
+pub async fn import(ImportArgs { snapshot, file }: ImportArgs) -> anyhow::Result<()> {
+    let (target, headers) = snapshot_target(&snapshot)?;
+
+    if snapshot.verbose {
+        info!("Importing snapshot {} to {target}.", file.display());
+    }
+
+    let body = std::fs::read(&file)?;
+
+    let res = Client::new()
+        .post(&target)
+        .headers(headers)
+        .header(CONTENT_TYPE, "application/sql")
+        .body(body)
+        .send()
+        .await
+        .expect("Failed to import snapshot.");
+
+    if res.status() != StatusCode::OK {
+        return print_error(res, &target, snapshot.verbose).await;
+    }
+
+    let res_json = res
+        .json::<Map<String, Value>>()
+        .await
+        .expect("Failed to read JSON response.");
+
+    info!(
+        "\n✅ Imported snapshot {}. Resume the indexer to continue indexing from it:\n\n{}",
+        file.display(),
+        to_string_pretty(&res_json)?
+    );
+
+    Ok(())
+}
+
+/// Return the URL of the indexer's snapshot endpoint, and the headers to send to it.
+fn snapshot_target(
+    SnapshotArgs {
+        url,
+        manifest,
+        path,
+        auth,
+        ..
+    }: &SnapshotArgs,
+) -> anyhow::Result<(String, HeaderMap)> {
+    let (_root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/index/{}/{}/snapshot",
+        manifest.namespace(),
+        manifest.identifier()
+    );
+
+    let mut headers = HeaderMap::new();
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    Ok((target, headers))
+}
+
+async fn print_error(res: Response, target: &str, verbose: bool) -> anyhow::Result<()> {
+    let status = res.status();
+
+    let res_json = res
+        .json::<Map<String, Value>>()
+        .await
+        .expect("Failed to read JSON response.");
+
+    if verbose {
+        error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+    }
+    info!("\n{}", to_string_pretty(&res_json)?);
+
+    Ok(())
+}
@@ -5,6 +5,7 @@ use tracing::info;
 pub async fn init(command: StartCommand) -> anyhow::Result<()> {
     let StartCommand {
         log_level,
+        log_format,
         config,
         fuel_node_host,
         fuel_node_port,
@@ -27,6 +28,14 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         jwt_issuer,
         jwt_expiry,
         verbose,
+        module_cache_dir,
+        module_cache_size,
+        block_archive_dir,
+        fallback_fuel_node,
+        ipfs_api_url,
+        telemetry,
+        otlp_endpoint,
+        trace_sampling_ratio,
         ..
     } = command;
 
@@ -46,6 +55,12 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         cmd.arg("--web-api-host").arg(&web_api_host);
         cmd.arg("--web-api-port").arg(&web_api_port);
         cmd.arg("--log-level").arg(&log_level);
+        cmd.arg("--log-format").arg(&log_format);
+        cmd.arg("--module-cache-size")
+            .arg(module_cache_size.to_string());
+        cmd.arg("--otlp-endpoint").arg(&otlp_endpoint);
+        cmd.arg("--trace-sampling-ratio")
+            .arg(trace_sampling_ratio.to_string());
 
         // Bool options
         let options = vec![
@@ -54,6 +69,7 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             ("--auth-enabled", auth_enabled),
             ("--verbose", verbose),
             ("--local-fuel-node", local_fuel_node),
+            ("--telemetry", telemetry),
         ];
         for (opt, value) in options.iter() {
             if *value {
@@ -67,6 +83,15 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             ("--jwt-secret", jwt_secret),
             ("--jwt-issuer", jwt_issuer),
             ("--jwt-expiry", jwt_expiry.map(|x| x.to_string())),
+            (
+                "--module-cache-dir",
+                module_cache_dir.map(|x| x.display().to_string()),
+            ),
+            (
+                "--block-archive-dir",
+                block_archive_dir.map(|x| x.display().to_string()),
+            ),
+            ("--ipfs-api-url", ipfs_api_url),
         ];
         for (opt, value) in options.iter() {
             if let Some(value) = value {
@@ -74,6 +99,10 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             }
         }
 
+        for node in fallback_fuel_node.iter() {
+            cmd.arg("--fallback-fuel-node").arg(node);
+        }
+
         match database.as_ref() {
             "postgres" => {
                 if embedded_database {
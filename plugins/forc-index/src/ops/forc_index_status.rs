@@ -1,12 +1,44 @@
 use crate::cli::StatusCommand;
-use fuel_indexer_database_types::RegisteredIndexer;
+use owo_colors::OwoColorize;
 use reqwest::header::{HeaderMap, AUTHORIZATION, CONNECTION};
+use serde::Deserialize;
 use serde_json::{to_string_pretty, value::Value, Map};
-use std::collections::BTreeMap;
+use std::time::Duration;
 use tracing::{error, info};
 
+/// Maximum number of characters of an indexer's last error shown in the table.
+const MAX_ERROR_WIDTH: usize = 40;
+
+/// An indexer as returned by the status API.
+#[derive(Debug, Deserialize)]
+struct IndexerStatusRow {
+    namespace: String,
+    identifier: String,
+    #[serde(default)]
+    health: Option<String>,
+    #[serde(default)]
+    status: Option<IndexerProgress>,
+}
+
+/// Sync progress of an indexer, as returned by the status API.
+#[derive(Debug, Deserialize)]
+struct IndexerProgress {
+    current_height: u64,
+    chain_tip: u64,
+    blocks_behind: u64,
+    #[serde(default)]
+    rows_per_minute: f64,
+    last_error: Option<String>,
+}
+
 pub async fn status(
-    StatusCommand { url, auth, verbose }: StatusCommand,
+    StatusCommand {
+        url,
+        auth,
+        watch,
+        interval,
+        verbose,
+    }: StatusCommand,
 ) -> anyhow::Result<()> {
     let health_target = format!("{url}/api/health");
     let status_target = format!("{url}/api/status");
@@ -19,6 +51,16 @@ pub async fn status(
 
     let client = reqwest::Client::new();
 
+    if watch {
+        loop {
+            // Clear the screen and move the cursor to its top left corner.
+            print!("\x1B[2J\x1B[H");
+            println!("Every {interval}s: {status_target}  (press Ctrl-C to stop)\n");
+            print_status(&client, &status_target, &headers, verbose).await?;
+            tokio::time::sleep(Duration::from_secs(interval.max(1))).await;
+        }
+    }
+
     match client.get(&health_target).send().await {
         Ok(res) => {
             if res.status() != reqwest::StatusCode::OK {
@@ -44,7 +86,22 @@ pub async fn status(
         }
     }
 
-    match client.get(&status_target).headers(headers).send().await {
+    print_status(&client, &status_target, &headers, verbose).await
+}
+
+/// Fetch the status of all indexers and print it as a table.
+async fn print_status(
+    client: &reqwest::Client,
+    status_target: &str,
+    headers: &HeaderMap,
+    verbose: bool,
+) -> anyhow::Result<()> {
+    match client
+        .get(status_target)
+        .headers(headers.clone())
+        .send()
+        .await
+    {
         Ok(res) => {
             let status = res.status();
 
@@ -66,7 +123,7 @@ pub async fn status(
             }
 
             let result = res
-                .json::<Vec<RegisteredIndexer>>()
+                .json::<Vec<IndexerStatusRow>>()
                 .await
                 .expect("Failed to read JSON response.");
 
@@ -84,48 +141,110 @@ pub async fn status(
     Ok(())
 }
 
-fn print_indexers(indexers: Vec<RegisteredIndexer>) {
-    let groupped: Vec<Vec<RegisteredIndexer>> = {
-        let mut ixs: BTreeMap<String, Vec<RegisteredIndexer>> = BTreeMap::new();
-        for i in indexers.into_iter() {
-            ixs.entry(i.namespace.clone()).or_insert(Vec::new()).push(i);
-        }
-        ixs.into_values().collect()
-    };
-    for (namespace_i, group) in groupped.iter().enumerate() {
-        let namespace = group[0].namespace.clone();
-        let is_last_namespace = namespace_i == groupped.len() - 1;
-        // namespace glyphs
-        let (ng1, ng2) = if namespace_i == 0 {
-            // first and only
-            if is_last_namespace {
-                ("─", " ")
-            // first
-            } else {
-                ("┌─", "|")
+fn print_indexers(mut indexers: Vec<IndexerStatusRow>) {
+    if indexers.is_empty() {
+        println!("No indexers are registered.");
+        return;
+    }
+
+    indexers.sort_by(|a, b| {
+        (&a.namespace, &a.identifier).cmp(&(&b.namespace, &b.identifier))
+    });
+
+    let rows = indexers
+        .iter()
+        .map(|indexer| {
+            let uid = format!("{}.{}", indexer.namespace, indexer.identifier);
+            let health = indexer.health.clone().unwrap_or_else(|| "-".to_string());
+            match &indexer.status {
+                Some(status) => [
+                    uid,
+                    status.current_height.to_string(),
+                    status.chain_tip.to_string(),
+                    status.blocks_behind.to_string(),
+                    health,
+                    format!("{:.0}", status.rows_per_minute),
+                    status
+                        .last_error
+                        .as_deref()
+                        .map(truncate_error)
+                        .unwrap_or_else(|| "-".to_string()),
+                ],
+                None => [
+                    uid,
+                    "-".to_string(),
+                    "-".to_string(),
+                    "-".to_string(),
+                    health,
+                    "-".to_string(),
+                    "-".to_string(),
+                ],
             }
-        } else if !is_last_namespace {
-            // middle
-            ("├─", "|")
-        } else {
-            // last
-            ("└─", " ")
-        };
-        println!("{} {}", ng1, namespace);
-        for (i, indexer) in group.iter().enumerate() {
-            // indexer glyphs
-            let (ig1, ig2) = if i != group.len() - 1 {
-                ("├─", "|")
-            } else {
-                ("└─", " ")
-            };
-            println!("{}  {} {}", ng2, ig1, indexer.identifier);
-            println!("{}  {}  • id: {}", ng2, ig2, indexer.id);
-            println!("{}  {}  • created_at: {}", ng2, ig2, indexer.created_at);
-            println!("{}  {}  • pubkey: {:?}", ng2, ig2, indexer.pubkey);
-        }
-        if !is_last_namespace {
-            println!("{}", ng2);
+        })
+        .collect::<Vec<_>>();
+
+    let header = [
+        "INDEXER",
+        "HEIGHT",
+        "CHAIN TIP",
+        "LAG",
+        "HEALTH",
+        "ROWS/MIN",
+        "LAST ERROR",
+    ];
+    let mut widths = header.map(str::len);
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
         }
     }
+
+    let line = header
+        .iter()
+        .zip(widths.iter())
+        .map(|(cell, width)| format!("{cell:<width$}"))
+        .collect::<Vec<_>>()
+        .join("  ");
+    println!("{}", line.trim_end().bold());
+
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .enumerate()
+            .map(|(i, (cell, width))| {
+                let cell = format!("{cell:<width$}");
+                // The health column is colored after padding, so that escape codes
+                // don't count towards its width.
+                if i == 4 {
+                    color_health(&cell)
+                } else {
+                    cell
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+}
+
+/// Color a (padded) health status by severity.
+fn color_health(health: &str) -> String {
+    match health.trim_end() {
+        "healthy" => health.green().to_string(),
+        "lagging" | "starting" | "paused" => health.yellow().to_string(),
+        "failing" | "crash_looping" => health.red().to_string(),
+        _ => health.to_string(),
+    }
+}
+
+/// Shorten an error message to a single line that fits in the table.
+fn truncate_error(error: &str) -> String {
+    let error = error.lines().next().unwrap_or_default();
+    if error.chars().count() > MAX_ERROR_WIDTH {
+        let truncated = error.chars().take(MAX_ERROR_WIDTH - 1).collect::<String>();
+        format!("{truncated}…")
+    } else {
+        error.to_string()
+    }
 }
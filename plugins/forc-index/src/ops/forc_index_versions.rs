@@ -0,0 +1,83 @@
+use crate::{cli::VersionsCommand, utils::project_dir_info};
+use fuel_indexer_database_types::IndexerDeployment;
+use fuel_indexer_lib::manifest::Manifest;
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION},
+    Client, StatusCode,
+};
+use serde_json::{to_string_pretty, value::Value, Map};
+use tracing::{error, info};
+
+pub async fn init(command: VersionsCommand) -> anyhow::Result<()> {
+    let VersionsCommand {
+        path,
+        manifest,
+        url,
+        auth,
+        verbose,
+    } = command;
+
+    let (_root_dir, manifest_path, _index_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/index/{}/{}/versions",
+        manifest.namespace(),
+        manifest.identifier()
+    );
+
+    let mut headers = HeaderMap::new();
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    let res = Client::new()
+        .get(&target)
+        .headers(headers)
+        .send()
+        .await
+        .expect("Failed to fetch indexer versions.");
+
+    let status = res.status();
+    if status != StatusCode::OK {
+        if verbose {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+        }
+
+        let res_json = res
+            .json::<Map<String, Value>>()
+            .await
+            .expect("Failed to read JSON response.");
+        info!("\n{}", to_string_pretty(&res_json)?);
+
+        return Ok(());
+    }
+
+    let deployments = res
+        .json::<Vec<IndexerDeployment>>()
+        .await
+        .expect("Failed to read JSON response.");
+
+    if deployments.is_empty() {
+        info!(
+            "\nNo versions of indexer '{}' have been deployed.\n",
+            manifest.uid()
+        );
+        return Ok(());
+    }
+
+    println!("{}", manifest.uid());
+    for deployment in deployments {
+        println!("└─ version {}", deployment.version);
+        println!("   • created_at: {}", deployment.created_at);
+        println!("   • wasm_digest: {}", deployment.wasm_digest);
+        println!("   • schema_digest: {}", deployment.schema_digest);
+        if let Some(version) = deployment.rollback_of {
+            println!("   • rollback_of: {version}");
+        }
+    }
+
+    Ok(())
+}
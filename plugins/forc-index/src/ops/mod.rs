@@ -2,9 +2,17 @@ pub mod forc_index_auth;
 pub mod forc_index_build;
 pub mod forc_index_check;
 pub mod forc_index_deploy;
+pub mod forc_index_dev;
+pub mod forc_index_import_subgraph;
+pub mod forc_index_install;
 pub mod forc_index_kill;
+pub mod forc_index_manifest_schema;
+pub mod forc_index_migrate;
 pub mod forc_index_new;
 pub mod forc_index_remove;
+pub mod forc_index_rollback;
+pub mod forc_index_snapshot;
 pub mod forc_index_start;
 pub mod forc_index_status;
+pub mod forc_index_versions;
 pub mod utils;
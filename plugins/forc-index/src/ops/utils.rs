@@ -1,5 +1,7 @@
+use fuel_indexer_lib::manifest::{abi_cache_path, remote_abi_url, Manifest};
 use serde_yaml::Value;
 use std::path::Path;
+use tracing::info;
 
 pub fn cargo_target_dir(
     cargo_manifest_path: &Path,
@@ -27,3 +29,57 @@ pub fn cargo_target_dir(
 
     Ok(target_directory.into())
 }
+
+/// Fetch the ABIs of the given manifest that are given as a URL or registry package
+/// into the ABI cache. ABIs that are already cached are not fetched again.
+pub fn fetch_remote_abis(manifest: &Manifest) -> anyhow::Result<()> {
+    let remote = manifest
+        .all_abis()
+        .into_iter()
+        .filter_map(remote_abi_url)
+        .map(|url| {
+            let path = abi_cache_path(&url);
+            (url, path)
+        })
+        .filter(|(_, path)| !path.exists())
+        .collect::<Vec<_>>();
+
+    if remote.is_empty() {
+        return Ok(());
+    }
+
+    // Builds can be run from within an async command (e.g., `forc index deploy`),
+    // so ABIs are fetched on a separate thread with its own runtime.
+    std::thread::spawn(move || -> anyhow::Result<()> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        runtime.block_on(async {
+            let client = reqwest::Client::new();
+            for (url, path) in remote {
+                info!("Fetching ABI from {url}.");
+
+                let res = client.get(&url).send().await?;
+                if !res.status().is_success() {
+                    anyhow::bail!("Failed to fetch ABI from {url}: {}", res.status());
+                }
+
+                // Catch error pages and other non-ABI responses before they reach the compiler.
+                let abi = res.text().await?;
+                serde_json::from_str::<serde_json::Value>(&abi).map_err(|e| {
+                    anyhow::anyhow!("ABI fetched from {url} is not valid JSON: {e}")
+                })?;
+
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(&path, abi)?;
+            }
+
+            Ok(())
+        })
+    })
+    .join()
+    .map_err(|_| anyhow::anyhow!("Failed to fetch remote ABIs."))?
+}
@@ -0,0 +1,332 @@
+//! Conversion of The Graph subgraphs into indexer projects, used by
+//! `forc index import-subgraph`.
+//!
+//! A subgraph's schema maps onto an indexer schema almost one-to-one, except for
+//! its scalars and derived fields. Its mappings are AssemblyScript, so only the
+//! signatures of its handlers are carried over, as Rust stubs to be ported by hand.
+
+use async_graphql_parser::{
+    parse_schema,
+    types::{BaseType, FieldDefinition, Type, TypeKind, TypeSystemDefinition},
+};
+use serde::Deserialize;
+use std::{collections::BTreeSet, fmt::Write, path::PathBuf};
+
+/// A `subgraph.yaml` manifest, keeping only the fields an indexer can use.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubgraphManifest {
+    pub schema: SubgraphSchema,
+    #[serde(default)]
+    pub data_sources: Vec<DataSource>,
+    #[serde(default)]
+    pub templates: Vec<DataSource>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SubgraphSchema {
+    pub file: PathBuf,
+}
+
+/// A data source or data source template of a subgraph.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataSource {
+    pub name: String,
+    pub network: Option<String>,
+    #[serde(default)]
+    pub source: Source,
+    pub mapping: Mapping,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Source {
+    pub address: Option<String>,
+    pub abi: Option<String>,
+    pub start_block: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mapping {
+    #[serde(default)]
+    pub entities: Vec<String>,
+    #[serde(default)]
+    pub event_handlers: Vec<EventHandler>,
+    #[serde(default)]
+    pub call_handlers: Vec<CallHandler>,
+    #[serde(default)]
+    pub block_handlers: Vec<BlockHandler>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventHandler {
+    pub event: String,
+    pub handler: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CallHandler {
+    pub function: String,
+    pub handler: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BlockHandler {
+    pub handler: String,
+}
+
+impl SubgraphManifest {
+    /// The earliest start block of the subgraph's data sources.
+    pub fn start_block(&self) -> Option<u64> {
+        self.data_sources
+            .iter()
+            .filter_map(|source| source.source.start_block)
+            .min()
+    }
+
+    /// Return warnings about the parts of the subgraph that have no indexer equivalent.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if self.data_sources.len() > 1 {
+            warnings.push(format!(
+                "The subgraph has {} data sources, whose handlers were merged into a single indexer.",
+                self.data_sources.len()
+            ));
+        }
+
+        for source in self.data_sources.iter().chain(self.templates.iter()) {
+            if let Some(address) = &source.source.address {
+                warnings.push(format!(
+                    "Data source '{}' indexes the EVM contract {address}. Set `contract_id` to the Sway contract that replaces it.",
+                    source.name
+                ));
+            }
+            if let Some(abi) = &source.source.abi {
+                warnings.push(format!(
+                    "Data source '{}' uses the EVM ABI '{abi}'. Set `abi` to the JSON ABI of the Sway contract that replaces it.",
+                    source.name
+                ));
+            }
+        }
+
+        for template in self.templates.iter() {
+            warnings.push(format!(
+                "Data source template '{}' can't be created at runtime by an indexer. Its handlers were kept as stubs.",
+                template.name
+            ));
+        }
+
+        warnings
+    }
+
+    /// Return stubs of the subgraph's handlers, as the body of the indexer module.
+    ///
+    /// Each stub takes the struct the Sway contract is expected to log in place of
+    /// the EVM event, and is named after the subgraph's handler in snake case.
+    pub fn handlers(&self, native: bool) -> String {
+        let fn_kw = if native { "async fn" } else { "fn" };
+
+        let mut handlers = String::new();
+        let mut names = BTreeSet::new();
+        for source in self.data_sources.iter().chain(self.templates.iter()) {
+            let entities = if source.mapping.entities.is_empty() {
+                "none".to_string()
+            } else {
+                source.mapping.entities.join(", ")
+            };
+
+            let stubs = source
+                .mapping
+                .event_handlers
+                .iter()
+                .map(|h| {
+                    let event = h.event.split('(').next().unwrap_or_default().trim();
+                    (
+                        &h.handler,
+                        format!("the `{}` event", h.event),
+                        format!("event: {event}"),
+                    )
+                })
+                .chain(source.mapping.call_handlers.iter().map(|h| {
+                    (
+                        &h.handler,
+                        format!("calls of `{}`", h.function),
+                        "call: Call".to_string(),
+                    )
+                }))
+                .chain(source.mapping.block_handlers.iter().map(|h| {
+                    (
+                        &h.handler,
+                        "blocks".to_string(),
+                        "block_data: BlockData".to_string(),
+                    )
+                }));
+
+            for (handler, handled, param) in stubs {
+                let name = to_snake_case(handler);
+                if !names.insert(name.clone()) {
+                    continue;
+                }
+
+                let _ = write!(
+                    handlers,
+                    r#"    /// Port of `{handler}` of the `{}` data source, which handled {handled}.
+    {fn_kw} {name}({param}) {{
+        // TODO: Update the entities that `{handler}` updated: {entities}.
+    }}
+
+"#,
+                    source.name
+                );
+            }
+        }
+
+        handlers
+    }
+}
+
+/// Map a subgraph scalar to the indexer scalar that holds its values.
+fn scalar(name: &str) -> &str {
+    match name {
+        "BigInt" => "Int16",
+        "BigDecimal" | "String" => "Charfield",
+        "Bytes" => "HexString",
+        "Int" => "Int4",
+        "Timestamp" => "Int8",
+        name => name,
+    }
+}
+
+fn convert_type(ty: &Type) -> String {
+    let base = match &ty.base {
+        BaseType::Named(name) => scalar(name.as_str()).to_string(),
+        BaseType::List(ty) => format!("[{}]", convert_type(ty)),
+    };
+
+    if ty.nullable {
+        base
+    } else {
+        format!("{base}!")
+    }
+}
+
+fn convert_field(field: &FieldDefinition, out: &mut String) {
+    let name = field.name.node.as_str();
+    let ty = &field.ty.node;
+
+    if let Some(derived) = field
+        .directives
+        .iter()
+        .find(|d| d.node.name.node == "derivedFrom")
+    {
+        let from = derived
+            .node
+            .get_argument("field")
+            .map(|v| v.node.to_string())
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "    # {name}: {ty} was derived from the {from} field of {}, which indexers don't support.",
+            field_type_name(ty)
+        );
+        return;
+    }
+
+    if name == "id" {
+        if field_type_name(ty) != "ID" {
+            let _ = writeln!(
+                out,
+                "    # Was {ty} in the subgraph. Indexer IDs are 64-bit integers, such as those returned by `id8`."
+            );
+        }
+        let _ = writeln!(out, "    id: ID!");
+        return;
+    }
+
+    if field_type_name(ty) == "BigDecimal" {
+        let _ = writeln!(
+            out,
+            "    # Was {ty} in the subgraph, which is stored as its decimal string."
+        );
+    }
+    let _ = writeln!(out, "    {name}: {}", convert_type(ty));
+}
+
+fn field_type_name(ty: &Type) -> &str {
+    match &ty.base {
+        BaseType::Named(name) => name.as_str(),
+        BaseType::List(ty) => field_type_name(ty),
+    }
+}
+
+/// Convert a subgraph's GraphQL schema into an indexer schema, returning it along
+/// with warnings about the definitions that were dropped.
+pub fn convert_schema(schema: &str) -> anyhow::Result<(String, Vec<String>)> {
+    let ast = parse_schema(schema)?;
+
+    let mut out = String::new();
+    let mut warnings = Vec::new();
+    for def in ast.definitions.iter() {
+        let t = match def {
+            TypeSystemDefinition::Type(t) => &t.node,
+            _ => continue,
+        };
+        let name = t.name.node.as_str();
+
+        match &t.kind {
+            TypeKind::Object(o) => {
+                let is_entity = t.directives.iter().any(|d| d.node.name.node == "entity");
+                if !is_entity {
+                    warnings.push(format!(
+                        "Dropped type '{name}', which isn't an entity (e.g., a fulltext search definition)."
+                    ));
+                    continue;
+                }
+
+                let _ = writeln!(out, "type {name} @entity {{");
+                for field in o.fields.iter() {
+                    convert_field(&field.node, &mut out);
+                }
+                let _ = writeln!(out, "}}\n");
+            }
+            TypeKind::Enum(e) => {
+                let _ = writeln!(out, "enum {name} {{");
+                for value in e.values.iter() {
+                    let _ = writeln!(out, "    {}", value.node.value.node);
+                }
+                let _ = writeln!(out, "}}\n");
+            }
+            TypeKind::Interface(_) => warnings.push(format!(
+                "Dropped interface '{name}'. Its fields remain on the entities that implement it."
+            )),
+            _ => warnings.push(format!(
+                "Dropped '{name}', since only entities and enums are imported."
+            )),
+        }
+    }
+
+    Ok((out.trim_end().to_string() + "\n", warnings))
+}
+
+/// Convert a handler name such as `handleNewOwner` into `handle_new_owner`.
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |n| n.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
@@ -0,0 +1,293 @@
+//! Templates from which `forc index new` scaffolds indexer projects.
+//!
+//! Each template provides a GraphQL schema and a handler skeleton. Templates that
+//! index a contract expect the contract's ABI to log the event types listed by
+//! `Template::abi_events`, and are wired to the ABI given with `--abi`.
+
+use clap::ArgEnum;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ArgEnum)]
+pub enum Template {
+    /// Blocks and their transactions.
+    #[default]
+    Default,
+
+    /// ERC20-style token transfers and balances.
+    Token,
+
+    /// AMM swaps and per-pair volume.
+    Amm,
+
+    /// NFT mints, transfers and ownership.
+    Nft,
+
+    /// Blocks, transactions and their fees, as shown by a block explorer.
+    BlockExplorer,
+}
+
+impl Template {
+    /// Names of the structs that a contract's ABI must log for this template's
+    /// handlers to compile.
+    pub fn abi_events(&self) -> &'static [&'static str] {
+        match self {
+            Template::Default | Template::BlockExplorer => &[],
+            Template::Token => &["TransferEvent"],
+            Template::Amm => &["SwapEvent"],
+            Template::Nft => &["MintEvent", "TransferEvent"],
+        }
+    }
+
+    /// Whether this template indexes a contract, and so requires its ABI.
+    pub fn requires_abi(&self) -> bool {
+        !self.abi_events().is_empty()
+    }
+
+    /// Return the GraphQL schema of this template.
+    pub fn schema(&self) -> String {
+        match self {
+            Template::Default => crate::defaults::default_indexer_schema(),
+            Template::Token => TOKEN_SCHEMA.to_string(),
+            Template::Amm => AMM_SCHEMA.to_string(),
+            Template::Nft => NFT_SCHEMA.to_string(),
+            Template::BlockExplorer => BLOCK_EXPLORER_SCHEMA.to_string(),
+        }
+    }
+
+    /// Return the handlers of this template, as the body of the indexer module.
+    ///
+    /// Native handlers are `async`, and await the entities they load and save.
+    pub fn handlers(&self, indexer_name: &str, native: bool) -> String {
+        let (fn_kw, aw) = if native {
+            ("async fn", ".await")
+        } else {
+            ("fn", "")
+        };
+
+        match self {
+            Template::Default => {
+                format!(
+                    r#"    {fn_kw} {indexer_name}_handler(block_data: BlockData) {{
+        info!("Processing Block#{{}}. (>'.')>", block_data.height);
+
+        let block_id = id8(block_data.id);
+        let block = Block{{ id: block_id, height: block_data.height, hash: block_data.id }};
+        block.save(){aw};
+
+        for transaction in block_data.transactions.iter() {{
+            let tx = Transaction{{ id: id8(transaction.id), block: block_data.id, hash: Bytes32::from(<[u8; 32]>::from(transaction.id)) }};
+            tx.save(){aw};
+        }}
+    }}
+"#
+                )
+            }
+            Template::Token => format!(
+                r#"    {fn_kw} handle_transfer(event: TransferEvent, block: BlockData) {{
+        let transfer = Transfer::new(
+            event.from,
+            event.to,
+            event.asset_id,
+            event.amount,
+            block.height,
+        );
+        transfer.save(){aw};
+
+        let id = balance_id(&event.from, &event.asset_id);
+        let mut sender = Balance::load(id){aw}.unwrap_or(Balance {{
+            id,
+            owner: event.from,
+            asset_id: event.asset_id,
+            amount: 0,
+        }});
+        sender.amount = sender.amount.saturating_sub(event.amount);
+        sender.save(){aw};
+
+        let id = balance_id(&event.to, &event.asset_id);
+        let mut recipient = Balance::load(id){aw}.unwrap_or(Balance {{
+            id,
+            owner: event.to,
+            asset_id: event.asset_id,
+            amount: 0,
+        }});
+        recipient.amount += event.amount;
+        recipient.save(){aw};
+    }}
+
+    fn balance_id(owner: &Address, asset_id: &AssetId) -> u64 {{
+        id8([owner.as_ref(), asset_id.as_ref()].concat())
+    }}
+"#
+            ),
+            Template::Amm => format!(
+                r#"    {fn_kw} handle_swap(event: SwapEvent, block: BlockData) {{
+        let swap = Swap::new(
+            event.sender,
+            event.asset_in,
+            event.amount_in,
+            event.asset_out,
+            event.amount_out,
+            block.height,
+        );
+        swap.save(){aw};
+
+        let id = id8([event.asset_in.as_ref(), event.asset_out.as_ref()].concat());
+        let mut pair = PairVolume::load(id){aw}.unwrap_or(PairVolume {{
+            id,
+            asset_in: event.asset_in,
+            asset_out: event.asset_out,
+            volume_in: 0,
+            volume_out: 0,
+            swaps: 0,
+        }});
+        pair.volume_in += event.amount_in;
+        pair.volume_out += event.amount_out;
+        pair.swaps += 1;
+        pair.save(){aw};
+    }}
+"#
+            ),
+            Template::Nft => format!(
+                r#"    {fn_kw} handle_mint(event: MintEvent, block: BlockData) {{
+        let token = Token {{
+            id: event.token_id,
+            owner: event.owner,
+            minted_at: block.height,
+            transfers: 0,
+        }};
+        token.save(){aw};
+
+        let mint = Mint::new(event.token_id, event.owner, block.height);
+        mint.save(){aw};
+    }}
+
+    {fn_kw} handle_transfer(event: TransferEvent, block: BlockData) {{
+        match Token::load(event.token_id){aw} {{
+            Some(mut token) => {{
+                token.owner = event.to;
+                token.transfers += 1;
+                token.save(){aw};
+            }}
+            None => {{
+                warn!("Token#{{}} was transferred before it was minted.", event.token_id);
+            }}
+        }}
+
+        let transfer =
+            Transfer::new(event.token_id, event.from, event.to, block.height);
+        transfer.save(){aw};
+    }}
+"#
+            ),
+            Template::BlockExplorer => format!(
+                r#"    {fn_kw} handle_block(block_data: BlockData) {{
+        let block = Block {{
+            id: block_data.height,
+            height: block_data.height,
+            hash: block_data.id,
+            time: block_data.time,
+            producer: block_data.producer,
+            transaction_count: block_data.transactions.len() as u64,
+        }};
+        block.save(){aw};
+
+        for transaction in block_data.transactions.iter() {{
+            let tx = Transaction {{
+                id: id8(transaction.id),
+                block: block_data.id,
+                hash: Bytes32::from(<[u8; 32]>::from(transaction.id)),
+                success: matches!(
+                    transaction.status,
+                    fuel::TransactionStatus::Success {{ .. }}
+                ),
+                gas_used: transaction.gas_used,
+                fee: transaction.fee,
+                receipt_count: transaction.receipts.len() as u64,
+            }};
+            tx.save(){aw};
+        }}
+    }}
+"#
+            ),
+        }
+    }
+}
+
+const TOKEN_SCHEMA: &str = r#"type Transfer @entity {
+    id: ID!
+    from: Address!
+    to: Address!
+    asset_id: AssetId!
+    amount: UInt8!
+    block_height: UInt8!
+}
+
+type Balance @entity {
+    id: ID!
+    owner: Address!
+    asset_id: AssetId!
+    amount: UInt8!
+}
+"#;
+
+const AMM_SCHEMA: &str = r#"type Swap @entity {
+    id: ID!
+    sender: Address!
+    asset_in: AssetId!
+    amount_in: UInt8!
+    asset_out: AssetId!
+    amount_out: UInt8!
+    block_height: UInt8!
+}
+
+type PairVolume @entity {
+    id: ID!
+    asset_in: AssetId!
+    asset_out: AssetId!
+    volume_in: UInt8!
+    volume_out: UInt8!
+    swaps: UInt8!
+}
+"#;
+
+const NFT_SCHEMA: &str = r#"type Token @entity {
+    id: ID!
+    owner: Address!
+    minted_at: UInt8!
+    transfers: UInt8!
+}
+
+type Mint @entity {
+    id: ID!
+    token_id: UInt8!
+    owner: Address!
+    block_height: UInt8!
+}
+
+type Transfer @entity {
+    id: ID!
+    token_id: UInt8!
+    from: Address!
+    to: Address!
+    block_height: UInt8!
+}
+"#;
+
+const BLOCK_EXPLORER_SCHEMA: &str = r#"type Block @entity {
+    id: ID!
+    height: UInt8! @unique
+    hash: Bytes32! @unique
+    time: Int8!
+    producer: Bytes32
+    transaction_count: UInt8!
+}
+
+type Transaction @entity {
+    id: ID!
+    block: Block! @join(on:hash)
+    hash: Bytes32! @unique
+    success: Boolean!
+    gas_used: UInt8!
+    fee: UInt8!
+    receipt_count: UInt8!
+}
+"#;
@@ -21,7 +21,19 @@ pub fn project_dir_info(
     let root = canonicalize(path.unwrap_or(&curr))?;
     let name = root.file_name().unwrap().to_str().unwrap().to_string();
     let mani_name = dasherize_to_underscore(&manifest_name(&name));
-    let manifest = root.join(manifest.unwrap_or(&mani_name));
+    let manifest = match manifest {
+        Some(manifest) => root.join(manifest),
+        None => {
+            // Fall back to a TOML manifest if there is no YAML manifest.
+            let manifest = root.join(&mani_name);
+            let toml_manifest = manifest.with_extension("toml");
+            if !manifest.exists() && toml_manifest.exists() {
+                toml_manifest
+            } else {
+                manifest
+            }
+        }
+    };
     Ok((root, manifest, name))
 }
 